@@ -1,3 +1,4 @@
+use std::str::FromStr;
 use structopt::StructOpt;
 use sc_cli::RunCmd;
 
@@ -8,6 +9,42 @@ pub struct Cli {
 
 	#[structopt(flatten)]
 	pub run: RunCmd,
+
+	/// Author blocks with manual seal instead of Aura.
+	///
+	/// Either `instant`, to author a block as soon as a transaction enters the pool, or a
+	/// number of milliseconds, to author a (possibly empty) block at this interval. Meant to be
+	/// used with `--dev`.
+	#[structopt(long, value_name = "instant|MILLIS")]
+	pub sealing: Option<Sealing>,
+}
+
+/// The block sealing strategy of a manually sealed development node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sealing {
+	/// Seal a block as soon as a transaction is imported into the pool.
+	Instant,
+	/// Seal a block every given number of milliseconds.
+	Interval(u64),
+}
+
+impl FromStr for Sealing {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"instant" => Ok(Sealing::Instant),
+			millis => millis
+				.parse::<u64>()
+				.ok()
+				.filter(|millis| *millis > 0)
+				.map(Sealing::Interval)
+				.ok_or_else(|| format!(
+					"invalid sealing '{}': expected 'instant' or a positive number of milliseconds",
+					s,
+				)),
+		}
+	}
 }
 
 #[derive(Debug, StructOpt)]
@@ -39,3 +76,21 @@ pub enum Subcommand {
 	#[structopt(name = "benchmark", about = "Benchmark runtime pallets.")]
 	Benchmark(frame_benchmarking_cli::BenchmarkCmd),
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parses_sealing() {
+		assert_eq!("instant".parse::<Sealing>(), Ok(Sealing::Instant));
+		assert_eq!("6000".parse::<Sealing>(), Ok(Sealing::Interval(6000)));
+	}
+
+	#[test]
+	fn rejects_invalid_sealing() {
+		for invalid in &["", "0", "-1", "1.5", "Instant", "manual", "18446744073709551616"] {
+			assert!(invalid.parse::<Sealing>().is_err(), "{:?} must be rejected", invalid);
+		}
+	}
+}
@@ -127,10 +127,14 @@ pub fn run() -> sc_cli::Result<()> {
 		},
 		None => {
 			let runner = cli.create_runner(&cli.run)?;
+			let sealing = cli.sealing;
 			runner.run_node_until_exit(|config| async move {
-				match config.role {
-					Role::Light => service::new_light(config),
-					_ => service::new_full(config),
+				match (config.role.clone(), sealing) {
+					(Role::Light, None) => service::new_light(config),
+					(Role::Light, Some(_)) => Err(sc_service::Error::Other(
+						"Manual sealing is not supported by light clients".into(),
+					)),
+					(_, sealing) => service::new_full(config, sealing),
 				}.map_err(sc_cli::Error::Service)
 			})
 		}
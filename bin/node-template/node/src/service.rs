@@ -6,12 +6,18 @@ use node_template_runtime::{self, opaque::Block, RuntimeApi};
 use sc_service::{error::Error as ServiceError, Configuration, TaskManager};
 use sc_executor::native_executor_instance;
 pub use sc_executor::NativeExecutor;
-use sp_consensus_aura::sr25519::AuthorityPair as AuraPair;
+use sp_consensus_aura::sr25519::{AuthorityPair as AuraPair, AuthorityId as AuraId};
 use sc_consensus_aura::{ImportQueueParams, StartAuraParams, SlotProportion};
+use sc_consensus_manual_seal::{
+	EngineCommand, InstantSealParams, ManualSealParams,
+	consensus::{aura::AuraConsensusDataProvider, timestamp::SlotTimestampProvider},
+};
 use sc_finality_grandpa::SharedVoterState;
 use sc_keystore::LocalKeystore;
 use sc_telemetry::{Telemetry, TelemetryWorker};
 use sp_consensus::SlotData;
+use futures::{FutureExt, StreamExt};
+use crate::cli::Sealing;
 
 // Our native executor instance.
 native_executor_instance!(
@@ -125,7 +131,12 @@ fn remote_keystore(_url: &String) -> Result<Arc<LocalKeystore>, &'static str> {
 }
 
 /// Builds a new service for a full client.
-pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError> {
+///
+/// Blocks are authored with Aura, unless a manual `sealing` strategy is given.
+pub fn new_full(
+	mut config: Configuration,
+	sealing: Option<Sealing>,
+) -> Result<TaskManager, ServiceError> {
 	let sc_service::PartialComponents {
 		client,
 		backend,
@@ -209,48 +220,63 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 		let proposer_factory = sc_basic_authorship::ProposerFactory::new(
 			task_manager.spawn_handle(),
 			client.clone(),
-			transaction_pool,
+			transaction_pool.clone(),
 			prometheus_registry.as_ref(),
 			telemetry.as_ref().map(|x| x.handle()),
 		);
 
-		let can_author_with =
-			sp_consensus::CanAuthorWithNativeVersion::new(client.executor().clone());
-
-		let slot_duration = sc_consensus_aura::slot_duration(&*client)?;
-		let raw_slot_duration = slot_duration.slot_duration();
-
-		let aura = sc_consensus_aura::start_aura::<AuraPair, _, _, _, _, _, _, _, _, _, _>(
-			StartAuraParams {
-				slot_duration,
-				client: client.clone(),
+		if let Some(sealing) = sealing {
+			let manual_seal = start_manual_seal(
+				sealing,
+				client.clone(),
+				transaction_pool,
 				select_chain,
 				block_import,
 				proposer_factory,
-				create_inherent_data_providers: move |_, ()| async move {
-					let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
-
-					let slot =
-						sp_consensus_aura::inherents::InherentDataProvider::from_timestamp_and_duration(
-							*timestamp,
-							raw_slot_duration,
-						);
-
-					Ok((timestamp, slot))
+			)?;
+
+			// the manual seal authoring task is considered essential, i.e. if it
+			// fails we take down the service with it.
+			task_manager.spawn_essential_handle().spawn_blocking("manual-seal", manual_seal);
+		} else {
+			let can_author_with =
+				sp_consensus::CanAuthorWithNativeVersion::new(client.executor().clone());
+
+			let slot_duration = sc_consensus_aura::slot_duration(&*client)?;
+			let raw_slot_duration = slot_duration.slot_duration();
+
+			let aura = sc_consensus_aura::start_aura::<AuraPair, _, _, _, _, _, _, _, _, _, _>(
+				StartAuraParams {
+					slot_duration,
+					client: client.clone(),
+					select_chain,
+					block_import,
+					proposer_factory,
+					create_inherent_data_providers: move |_, ()| async move {
+						let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
+
+						let slot =
+							sp_consensus_aura::inherents::InherentDataProvider::from_timestamp_and_duration(
+								*timestamp,
+								raw_slot_duration,
+							);
+
+						Ok((timestamp, slot))
+					},
+					force_authoring,
+					backoff_authoring_blocks,
+					keystore: keystore_container.sync_keystore(),
+					can_author_with,
+					sync_oracle: network.clone(),
+					block_proposal_slot_portion: SlotProportion::new(2f32 / 3f32),
+					telemetry: telemetry.as_ref().map(|x| x.handle()),
 				},
-				force_authoring,
-				backoff_authoring_blocks,
-				keystore: keystore_container.sync_keystore(),
-				can_author_with,
-				sync_oracle: network.clone(),
-				block_proposal_slot_portion: SlotProportion::new(2f32 / 3f32),
-				telemetry: telemetry.as_ref().map(|x| x.handle()),
-			},
-		)?;
+			)?;
 
-		// the AURA authoring task is considered essential, i.e. if it
-		// fails we take down the service with it.
-		task_manager.spawn_essential_handle().spawn_blocking("aura", aura);
+			// the AURA authoring task is considered essential, i.e. if it
+			// fails we take down the service with it.
+			task_manager.spawn_essential_handle().spawn_blocking("aura", aura);
+		}
 	}
 
 	// if the node isn't actively participating in consensus then it doesn't
@@ -301,6 +327,67 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 	Ok(task_manager)
 }
 
+/// Build the manual seal authorship task for the given `sealing` strategy.
+///
+/// Blocks carry an Aura pre-digest and a timestamp that advances by one slot per block, so that
+/// they are accepted by the Aura runtime no matter how fast they are authored.
+fn start_manual_seal(
+	sealing: Sealing,
+	client: Arc<FullClient>,
+	transaction_pool: Arc<sc_transaction_pool::FullPool<Block, FullClient>>,
+	select_chain: FullSelectChain,
+	block_import: sc_finality_grandpa::GrandpaBlockImport<FullBackend, Block, FullClient, FullSelectChain>,
+	proposer_factory: sc_basic_authorship::ProposerFactory<
+		sc_transaction_pool::FullPool<Block, FullClient>,
+		FullBackend,
+		FullClient,
+		sp_consensus::DisableProofRecording,
+	>,
+) -> Result<futures::future::BoxFuture<'static, ()>, ServiceError> {
+	let timestamp = SlotTimestampProvider::new_aura::<Block, _, AuraId>(client.clone())
+		.map_err(|e| ServiceError::Other(e.to_string()))?;
+	let create_inherent_data_providers = move |_, ()| {
+		let timestamp = timestamp.clone();
+		async move { Ok(timestamp) }
+	};
+	let consensus_data_provider = AuraConsensusDataProvider::<Block, _>::new::<AuraId>(client.clone())
+		.map_err(|e| ServiceError::Other(e.to_string()))?;
+	let pool = transaction_pool.pool().clone();
+
+	Ok(match sealing {
+		Sealing::Instant => sc_consensus_manual_seal::run_instant_seal(InstantSealParams {
+			block_import,
+			env: proposer_factory,
+			client,
+			pool,
+			select_chain,
+			consensus_data_provider: Some(Box::new(consensus_data_provider)),
+			create_inherent_data_providers,
+		}).boxed(),
+		Sealing::Interval(millis) => {
+			let commands_stream = futures::stream::repeat(())
+				.then(move |()| futures_timer::Delay::new(Duration::from_millis(millis)))
+				.map(|()| EngineCommand::SealNewBlock {
+					create_empty: true,
+					finalize: false,
+					parent_hash: None,
+					sender: None,
+				});
+
+			sc_consensus_manual_seal::run_manual_seal(ManualSealParams {
+				block_import,
+				env: proposer_factory,
+				client,
+				pool,
+				commands_stream: Box::pin(commands_stream),
+				select_chain,
+				consensus_data_provider: Some(Box::new(consensus_data_provider)),
+				create_inherent_data_providers,
+			}).boxed()
+		},
+	})
+}
+
 /// Builds a new service for a light client.
 pub fn new_light(mut config: Configuration) -> Result<TaskManager, ServiceError> {
 	let telemetry = config.telemetry_endpoints.clone()
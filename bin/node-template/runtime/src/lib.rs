@@ -240,10 +240,19 @@ impl pallet_timestamp::Config for Runtime {
 parameter_types! {
 	pub const ExistentialDeposit: u128 = 500;
 	pub const MaxLocks: u32 = 50;
+	pub const MaxHolds: u32 = 1;
+	pub const MaxFreezes: u32 = 1;
 }
 
 impl pallet_balances::Config for Runtime {
 	type MaxLocks = MaxLocks;
+	/// No pallet of the template holds or freezes funds, so `()` is the only reason for either.
+	/// Add the `HoldReason` and `FreezeReason` parts of such pallets to `construct_runtime!` to
+	/// use the runtime enums instead.
+	type HoldIdentifier = ();
+	type FreezeIdentifier = ();
+	type MaxHolds = MaxHolds;
+	type MaxFreezes = MaxFreezes;
 	/// The type for recording an account's balance.
 	type Balance = Balance;
 	/// The ubiquitous event type.
@@ -335,6 +335,7 @@ pub fn testnet_genesis(
 		},
 		pallet_vesting: Default::default(),
 		pallet_gilt: Default::default(),
+		pallet_nomination_pools: Default::default(),
 	}
 }
 
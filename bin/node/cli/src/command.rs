@@ -161,8 +161,27 @@ pub fn run() -> Result<()> {
 					registry,
 				).map_err(|e| sc_cli::Error::Service(sc_service::Error::Prometheus(e)))?;
 
-				Ok((cmd.run::<Block, Executor>(config), task_manager))
+				Ok((cmd.run::<Block, Executor, _>(config, BabeBlockBuilding), task_manager))
 			})
 		}
 	}
 }
+
+/// Authors the blocks of `try-runtime fast-forward` in secondary BABE slots, all claimed by the
+/// first authority.
+#[cfg(feature = "try-runtime")]
+struct BabeBlockBuilding;
+
+#[cfg(feature = "try-runtime")]
+impl try_runtime_cli::BlockBuildingInfoProvider<Block> for BabeBlockBuilding {
+	fn pre_digest(&self, timestamp: u64) -> Vec<sp_runtime::traits::DigestItemFor<Block>> {
+		use sp_consensus_babe::digests::{CompatibleDigestItem, PreDigest, SecondaryPlainPreDigest};
+
+		let slot = timestamp / node_runtime::constants::time::SLOT_DURATION;
+		let pre_digest = PreDigest::SecondaryPlain(SecondaryPlainPreDigest {
+			authority_index: 0,
+			slot: slot.into(),
+		});
+		vec![CompatibleDigestItem::babe_pre_digest(pre_digest)]
+	}
+}
@@ -0,0 +1,87 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The bag thresholds of the voters list of the staking nominators.
+//!
+//! The thresholds grow geometrically, from the vote weight of the existential deposit up to
+//! `u64::MAX`, so that every bag covers the same proportion of its vote weights: the ratio between
+//! two consecutive thresholds is about 1.0628.
+
+/// The vote weight of the existential deposit.
+pub const EXISTENTIAL_WEIGHT: u64 = 100_000_000_000_000;
+
+/// The upper thresholds of the bags.
+pub const THRESHOLDS: [u64; 200] = [
+	100_000_000_000_000, 106_282_535_907_434, 112_959_774_389_150, 120_056_512_776_105,
+	127_599_106_300_477, 135_615_565_971_369, 144_135_662_599_590, 153_191_037_357_828,
+	162_815_319_286_804, 173_044_250_183_801, 183_915_817_337_348, 195_470_394_601_018,
+	207_750_892_330_230, 220_802_916_738_892, 234_674_939_267_674, 249_418_476_592_915,
+	265_088_281_944_640, 281_742_548_444_212, 299_443_125_216_740, 318_255_747_080_824,
+	338_250_278_668_650, 359_500_973_883_003, 382_086_751_654_778, 406_091_489_025_038,
+	431_604_332_640_070, 458_720_029_816_224, 487_539_280_404_020, 518_169_110_758_249,
+	550_723_271_202_868, 585_322_658_466_784, 622_095_764_659_307, 661_179_154_452_655,
+	702_717_972_243_612, 746_866_481_177_809, 793_788_636_038_395, 843_658_692_126_638,
+	896_661_852_395_683, 952_994_955_240_705, 1_012_867_205_499_738, 1_076_500_951_379_883,
+	1_144_132_510_194_194, 1_216_013_045_975_771, 1_292_409_502_228_282, 1_373_605_593_276_864,
+	1_459_902_857_901_006, 1_551_621_779_162_293, 1_649_102_974_585_732, 1_752_708_461_114_644,
+	1_862_822_999_536_807, 1_979_855_523_374_647, 2_104_240_657_545_976, 2_236_440_332_435_130,
+	2_376_945_499_368_705, 2_526_277_953_866_682, 2_684_992_273_439_948, 2_853_677_877_130_643,
+	3_032_961_214_443_878, 3_223_508_091_799_864, 3_426_026_145_146_234, 3_641_267_467_913_125,
+	3_870_031_404_070_484, 4_113_167_516_660_187, 4_371_578_742_827_278, 4_646_224_747_067_158,
+	4_938_125_485_141_740, 5_248_364_991_899_924, 5_578_095_407_069_237, 5_928_541_253_969_293,
+	6_301_003_987_036_956, 6_696_866_825_051_406, 7_117_599_888_008_301, 7_564_765_656_719_912,
+	8_040_024_775_416_582, 8_545_142_218_898_725, 9_081_993_847_142_346, 9_652_573_371_700_020,
+	10_258_999_759_768_494, 10_903_525_103_419_526, 11_588_542_983_217_946, 12_316_597_357_287_048,
+	13_090_392_008_832_682, 13_912_800_587_211_478, 14_786_877_279_832_740, 15_715_868_154_526_444,
+	16_703_223_214_499_564, 17_752_609_210_649_368, 18_867_923_258_814_864, 20_053_307_312_537_016,
+	21_313_163_545_075_260, 22_652_170_697_804_760, 24_075_301_455_707_604, 25_587_840_914_485_436,
+	27_195_406_207_875_096, 28_903_967_368_057_408, 30_719_869_496_628_644, 32_649_856_328_471_232,
+	34_701_095_276_033_072, 36_881_204_047_022_760, 39_198_278_934_371_000, 41_660_924_883_519_024,
+	44_278_287_448_695_256, 47_060_086_756_856_416, 50_016_653_605_425_544, 53_158_967_827_883_328,
+	56_498_699_069_691_432, 60_048_250_125_977_928, 63_820_803_001_928_320, 67_830_367_866_937_240,
+	72_091_835_084_322_208, 76_621_030_509_822_896, 81_434_774_264_248_544, 86_550_943_198_537_856,
+	91_988_537_283_208_864, 97_767_750_168_749_872, 103_910_044_178_992_032,
+	110_438_230_015_967_808, 117_376_551_472_255_648, 124_750_775_465_407_952,
+	132_588_287_728_824_656, 140_918_194_514_440_096, 149_771_430_684_917_600,
+	159_180_874_596_775_296, 169_181_470_201_085_344, 179_810_356_815_193_408,
+	191_107_007_047_393_280, 203_113_373_386_768_352, 215_874_044_002_592_736,
+	229_436_408_331_885_696, 243_850_833_070_063_488, 259_170_849_218_267_360,
+	275_453_350_882_006_848, 292_758_806_559_399_360, 311_151_483_703_669_120,
+	330_699_687_393_866_048, 351_476_014_000_157_952, 373_557_620_785_735_872,
+	397_026_512_446_556_224, 421_969_845_653_044_352, 448_480_252_724_741_056,
+	476_656_185_639_924_032, 506_602_281_657_757_888, 538_429_751_910_786_880,
+	572_256_794_410_890_304, 608_209_033_002_485_760, 646_419_983_893_124_608,
+	687_031_551_494_039_680, 730_194_555_412_054_272, 776_069_290_549_945_216,
+	824_826_122_395_314_432, 876_646_119_708_696_320, 931_721_726_960_522_752,
+	990_257_479_014_182_528, 1_052_470_760_709_300_096, 1_118_592_614_166_106_496,
+	1_188_868_596_808_997_888, 1_263_559_693_295_730_688, 1_342_943_284_738_899_200,
+	1_427_314_178_819_095_040, 1_516_985_704_615_302_656, 1_612_290_876_218_401_024,
+	1_713_583_629_449_105_920, 1_821_240_136_273_157_888, 1_935_660_201_795_120_640,
+	2_057_268_749_018_810_112, 2_186_517_396_888_336_640, 2_323_886_137_470_139_392,
+	2_469_885_118_504_584_192, 2_625_056_537_947_005_440, 2_789_976_657_533_971_968,
+	2_965_257_942_852_573_184, 3_151_551_337_860_326_912, 3_349_548_682_302_621_696,
+	3_559_985_281_005_268_992, 3_783_642_634_583_792_640, 4_021_351_341_710_505_472,
+	4_273_994_183_717_549_568, 4_542_509_402_991_249_408, 4_827_894_187_332_743_168,
+	5_131_208_373_224_846_336, 5_453_578_381_757_961_216, 5_796_201_401_831_968_768,
+	6_160_349_836_169_260_032, 6_547_376_026_650_149_888, 6_958_717_276_519_176_192,
+	7_395_901_188_113_313_792, 7_860_551_335_934_875_648, 8_354_393_296_137_274_368,
+	8_879_261_054_815_364_096, 9_437_103_818_898_950_144, 10_029_993_254_943_109_120,
+	10_660_131_182_698_127_360, 11_329_857_752_030_713_856, 12_041_660_133_563_248_640,
+	12_798_181_755_305_533_440, 13_602_232_119_581_280_256, 14_456_797_236_706_508_800,
+	15_365_050_714_167_533_568, 16_330_365_542_480_564_224, 17_356_326_621_502_148_608,
+	18_446_744_073_709_551_615,
+];
@@ -51,8 +51,33 @@ use sc_proposer_metrics::MetricsLink as PrometheusMetrics;
 /// transferred to other nodes.
 pub const DEFAULT_BLOCK_SIZE_LIMIT: usize = 4 * 1024 * 1024 + 512;
 
+/// Transactions of the pool that are ready to be included in a block.
+pub type ReadyTransactions<T> = Box<dyn Iterator<Item = Arc<T>> + Send>;
+
+/// Strategy selecting the ready transactions a [`Proposer`] tries to include in a block, and
+/// their order.
+///
+/// The proposer pushes the selected transactions in order until the block is full or the
+/// deadline is reached. Transactions that fail to apply are skipped, and reported as invalid to
+/// the pool unless the block is full.
+pub trait TransactionSelection<T: InPoolTransaction>: Send + Sync + 'static {
+	/// Select transactions out of `ready`, which is ordered by priority.
+	fn select(&self, ready: ReadyTransactions<T>) -> ReadyTransactions<T>;
+}
+
+/// The default [`TransactionSelection`], trying all ready transactions in the priority order of
+/// the pool.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PriorityOrder;
+
+impl<T: InPoolTransaction> TransactionSelection<T> for PriorityOrder {
+	fn select(&self, ready: ReadyTransactions<T>) -> ReadyTransactions<T> {
+		ready
+	}
+}
+
 /// Proposer factory.
-pub struct ProposerFactory<A, B, C, PR> {
+pub struct ProposerFactory<A, B, C, PR, S = PriorityOrder> {
 	spawn_handle: Box<dyn SpawnNamed>,
 	/// The client instance.
 	client: Arc<C>,
@@ -68,6 +93,8 @@ pub struct ProposerFactory<A, B, C, PR> {
 	telemetry: Option<TelemetryHandle>,
 	/// When estimating the block size, should the proof be included?
 	include_proof_in_block_size_estimation: bool,
+	/// The strategy selecting the transactions to include.
+	transaction_selection: Arc<S>,
 	/// phantom member to pin the `Backend`/`ProofRecording` type.
 	_phantom: PhantomData<(B, PR)>,
 }
@@ -91,6 +118,7 @@ impl<A, B, C> ProposerFactory<A, B, C, DisableProofRecording> {
 			telemetry,
 			client,
 			include_proof_in_block_size_estimation: false,
+			transaction_selection: Arc::new(PriorityOrder),
 			_phantom: PhantomData,
 		}
 	}
@@ -118,6 +146,7 @@ impl<A, B, C> ProposerFactory<A, B, C, EnableProofRecording> {
 			default_block_size_limit: DEFAULT_BLOCK_SIZE_LIMIT,
 			telemetry,
 			include_proof_in_block_size_estimation: true,
+			transaction_selection: Arc::new(PriorityOrder),
 			_phantom: PhantomData,
 		}
 	}
@@ -128,7 +157,7 @@ impl<A, B, C> ProposerFactory<A, B, C, EnableProofRecording> {
 	}
 }
 
-impl<A, B, C, PR> ProposerFactory<A, B, C, PR> {
+impl<A, B, C, PR, S> ProposerFactory<A, B, C, PR, S> {
 	/// Set the default block size limit in bytes.
 	///
 	/// The default value for the block size limit is:
@@ -138,9 +167,28 @@ impl<A, B, C, PR> ProposerFactory<A, B, C, PR> {
 	pub fn set_default_block_size_limit(&mut self, limit: usize) {
 		self.default_block_size_limit = limit;
 	}
+
+	/// Use `transaction_selection` instead of [`PriorityOrder`] to select the transactions
+	/// included in blocks built by proposers of this instance.
+	pub fn with_transaction_selection<S2>(
+		self,
+		transaction_selection: S2,
+	) -> ProposerFactory<A, B, C, PR, S2> {
+		ProposerFactory {
+			spawn_handle: self.spawn_handle,
+			client: self.client,
+			transaction_pool: self.transaction_pool,
+			metrics: self.metrics,
+			default_block_size_limit: self.default_block_size_limit,
+			telemetry: self.telemetry,
+			include_proof_in_block_size_estimation: self.include_proof_in_block_size_estimation,
+			transaction_selection: Arc::new(transaction_selection),
+			_phantom: PhantomData,
+		}
+	}
 }
 
-impl<B, Block, C, A, PR> ProposerFactory<A, B, C, PR>
+impl<B, Block, C, A, PR, S> ProposerFactory<A, B, C, PR, S>
 	where
 		A: TransactionPool<Block = Block> + 'static,
 		B: backend::Backend<Block> + Send + Sync + 'static,
@@ -149,19 +197,20 @@ impl<B, Block, C, A, PR> ProposerFactory<A, B, C, PR>
 			+ Send + Sync + 'static,
 		C::Api: ApiExt<Block, StateBackend = backend::StateBackendFor<B, Block>>
 			+ BlockBuilderApi<Block>,
+		S: TransactionSelection<A::InPoolTransaction>,
 {
 	fn init_with_now(
 		&mut self,
 		parent_header: &<Block as BlockT>::Header,
 		now: Box<dyn Fn() -> time::Instant + Send + Sync>,
-	) -> Proposer<B, Block, C, A, PR> {
+	) -> Proposer<B, Block, C, A, PR, S> {
 		let parent_hash = parent_header.hash();
 
 		let id = BlockId::hash(parent_hash);
 
 		info!("🙌 Starting consensus session on top of parent {:?}", parent_hash);
 
-		let proposer = Proposer::<_, _, _, _, PR> {
+		let proposer = Proposer::<_, _, _, _, PR, S> {
 			spawn_handle: self.spawn_handle.clone(),
 			client: self.client.clone(),
 			parent_hash,
@@ -174,14 +223,15 @@ impl<B, Block, C, A, PR> ProposerFactory<A, B, C, PR>
 			telemetry: self.telemetry.clone(),
 			_phantom: PhantomData,
 			include_proof_in_block_size_estimation: self.include_proof_in_block_size_estimation,
+			transaction_selection: self.transaction_selection.clone(),
 		};
 
 		proposer
 	}
 }
 
-impl<A, B, Block, C, PR> sp_consensus::Environment<Block> for
-	ProposerFactory<A, B, C, PR>
+impl<A, B, Block, C, PR, S> sp_consensus::Environment<Block> for
+	ProposerFactory<A, B, C, PR, S>
 		where
 			A: TransactionPool<Block = Block> + 'static,
 			B: backend::Backend<Block> + Send + Sync + 'static,
@@ -191,9 +241,10 @@ impl<A, B, Block, C, PR> sp_consensus::Environment<Block> for
 			C::Api: ApiExt<Block, StateBackend = backend::StateBackendFor<B, Block>>
 				+ BlockBuilderApi<Block>,
 			PR: ProofRecording,
+			S: TransactionSelection<A::InPoolTransaction>,
 {
 	type CreateProposer = future::Ready<Result<Self::Proposer, Self::Error>>;
-	type Proposer = Proposer<B, Block, C, A, PR>;
+	type Proposer = Proposer<B, Block, C, A, PR, S>;
 	type Error = sp_blockchain::Error;
 
 	fn init(
@@ -205,7 +256,7 @@ impl<A, B, Block, C, PR> sp_consensus::Environment<Block> for
 }
 
 /// The proposer logic.
-pub struct Proposer<B, Block: BlockT, C, A: TransactionPool, PR> {
+pub struct Proposer<B, Block: BlockT, C, A: TransactionPool, PR, S = PriorityOrder> {
 	spawn_handle: Box<dyn SpawnNamed>,
 	client: Arc<C>,
 	parent_hash: <Block as BlockT>::Hash,
@@ -217,11 +268,12 @@ pub struct Proposer<B, Block: BlockT, C, A: TransactionPool, PR> {
 	default_block_size_limit: usize,
 	include_proof_in_block_size_estimation: bool,
 	telemetry: Option<TelemetryHandle>,
+	transaction_selection: Arc<S>,
 	_phantom: PhantomData<(B, PR)>,
 }
 
-impl<A, B, Block, C, PR> sp_consensus::Proposer<Block> for
-	Proposer<B, Block, C, A, PR>
+impl<A, B, Block, C, PR, S> sp_consensus::Proposer<Block> for
+	Proposer<B, Block, C, A, PR, S>
 		where
 			A: TransactionPool<Block = Block> + 'static,
 			B: backend::Backend<Block> + Send + Sync + 'static,
@@ -231,6 +283,7 @@ impl<A, B, Block, C, PR> sp_consensus::Proposer<Block> for
 			C::Api: ApiExt<Block, StateBackend = backend::StateBackendFor<B, Block>>
 				+ BlockBuilderApi<Block>,
 			PR: ProofRecording,
+			S: TransactionSelection<A::InPoolTransaction>,
 {
 	type Transaction = backend::TransactionFor<B, Block>;
 	type Proposal = Pin<Box<dyn Future<
@@ -270,7 +323,7 @@ impl<A, B, Block, C, PR> sp_consensus::Proposer<Block> for
 	}
 }
 
-impl<A, B, Block, C, PR> Proposer<B, Block, C, A, PR>
+impl<A, B, Block, C, PR, S> Proposer<B, Block, C, A, PR, S>
 	where
 		A: TransactionPool<Block = Block>,
 		B: backend::Backend<Block> + Send + Sync + 'static,
@@ -280,6 +333,7 @@ impl<A, B, Block, C, PR> Proposer<B, Block, C, A, PR>
 		C::Api: ApiExt<Block, StateBackend = backend::StateBackendFor<B, Block>>
 			+ BlockBuilderApi<Block>,
 		PR: ProofRecording,
+		S: TransactionSelection<A::InPoolTransaction>,
 {
 	async fn propose_with(
 		self,
@@ -335,6 +389,7 @@ impl<A, B, Block, C, PR> Proposer<B, Block, C, A, PR>
 				self.transaction_pool.ready()
 			},
 		};
+		let pending_iterator = self.transaction_selection.select(pending_iterator);
 
 		let block_size_limit = block_size_limit.unwrap_or(self.default_block_size_limit);
 
@@ -841,4 +896,63 @@ mod tests {
 		// block size and thus, one less transaction should fit into the limit.
 		assert_eq!(block.extrinsics().len(), extrinsics_num - 2);
 	}
+
+	#[test]
+	fn should_use_custom_transaction_selection() {
+		/// Excludes data starting with `1` and reverses the priority order.
+		struct ExcludeOnesReversed;
+		impl<T> TransactionSelection<T> for ExcludeOnesReversed
+			where T: InPoolTransaction<Transaction = Extrinsic> + Send + Sync + 'static
+		{
+			fn select(&self, ready: ReadyTransactions<T>) -> ReadyTransactions<T> {
+				let mut selected = ready
+					.filter(|tx| !matches!(tx.data(), Extrinsic::IncludeData(data) if data[0] == 1))
+					.collect::<Vec<_>>();
+				selected.reverse();
+				Box::new(selected.into_iter())
+			}
+		}
+
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let txpool = BasicPool::new_full(
+			Default::default(),
+			true.into(),
+			None,
+			spawner.clone(),
+			client.clone(),
+		);
+		let genesis_header = client.header(&BlockId::Number(0u64))
+			.expect("header get error")
+			.expect("there should be header");
+
+		let extrinsics = (0..3u8)
+			.map(|v| Extrinsic::IncludeData(vec![v; 10]))
+			.collect::<Vec<_>>();
+		block_on(txpool.submit_at(&BlockId::number(0), SOURCE, extrinsics.clone())).unwrap();
+		block_on(txpool.maintain(chain_event(genesis_header.clone())));
+
+		let mut proposer_factory = ProposerFactory::new(
+			spawner.clone(),
+			client.clone(),
+			txpool.clone(),
+			None,
+			None,
+		).with_transaction_selection(ExcludeOnesReversed);
+
+		let ready = txpool.ready().map(|tx| tx.data().clone()).collect::<Vec<_>>();
+		let proposer = block_on(proposer_factory.init(&genesis_header)).unwrap();
+		let deadline = time::Duration::from_secs(300);
+		let block = block_on(
+			proposer.propose(Default::default(), Default::default(), deadline, None)
+		).map(|r| r.block).unwrap();
+
+		let expected = ready.into_iter()
+			.rev()
+			.filter(|xt| *xt != extrinsics[1])
+			.collect::<Vec<_>>();
+		assert_eq!(block.extrinsics().to_vec(), expected);
+		// excluded transactions stay in the pool.
+		assert_eq!(txpool.ready().count(), 3);
+	}
 }
@@ -73,4 +73,7 @@
 
 mod basic_authorship;
 
-pub use crate::basic_authorship::{ProposerFactory, Proposer, DEFAULT_BLOCK_SIZE_LIMIT};
+pub use crate::basic_authorship::{
+	ProposerFactory, Proposer, TransactionSelection, PriorityOrder, ReadyTransactions,
+	DEFAULT_BLOCK_SIZE_LIMIT,
+};
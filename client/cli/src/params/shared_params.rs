@@ -32,6 +32,9 @@ pub struct SharedParams {
 	pub chain: Option<String>,
 
 	/// Specify the development chain.
+	///
+	/// When running a node, this implies `--tmp` unless a `--base-path` is given explicitly, so
+	/// that the development chain starts from a fresh, automatically purged database.
 	#[structopt(long, conflicts_with_all = &["chain"])]
 	pub dev: bool,
 
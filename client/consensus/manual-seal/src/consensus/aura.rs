@@ -0,0 +1,91 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//! Aura consensus data provider, This allows manual seal author blocks that are valid for runtimes
+//! that expect the aura-specific digests.
+
+use crate::{ConsensusDataProvider, Error};
+use std::{marker::PhantomData, sync::Arc};
+use codec::Codec;
+use sc_client_api::{AuxStore, UsageProvider};
+use sp_api::{ProvideRuntimeApi, TransactionFor};
+use sp_consensus::BlockImportParams;
+use sp_consensus_aura::{AuraApi, digests::CompatibleDigestItem};
+use sp_consensus_slots::Slot;
+use sp_inherents::InherentData;
+use sp_runtime::{
+	traits::{Block as BlockT, DigestFor, DigestItemFor},
+	generic::Digest,
+};
+use sp_timestamp::TimestampInherentData;
+
+/// Consensus data provider for Aura.
+pub struct AuraConsensusDataProvider<B, C> {
+	/// Slot duration in milliseconds.
+	slot_duration: u64,
+	_phantom: PhantomData<(B, C)>,
+}
+
+impl<B, C> AuraConsensusDataProvider<B, C>
+	where
+		B: BlockT,
+		C: AuxStore + ProvideRuntimeApi<B> + UsageProvider<B>,
+{
+	/// Creates a new instance of the [`AuraConsensusDataProvider`], requires that `client`
+	/// implements [`sp_consensus_aura::AuraApi`].
+	pub fn new<AuthorityId>(client: Arc<C>) -> Result<Self, Error>
+		where
+			C::Api: AuraApi<B, AuthorityId>,
+			AuthorityId: Codec,
+	{
+		let slot_duration = sc_consensus_aura::slot_duration(&*client)?
+			.slot_duration()
+			.as_millis() as u64;
+
+		Ok(Self { slot_duration, _phantom: PhantomData })
+	}
+}
+
+impl<B, C> ConsensusDataProvider<B> for AuraConsensusDataProvider<B, C>
+	where
+		B: BlockT,
+		C: AuxStore + ProvideRuntimeApi<B> + UsageProvider<B> + Send + Sync,
+{
+	type Transaction = TransactionFor<C, B>;
+
+	fn create_digest(&self, _parent: &B::Header, inherents: &InherentData) -> Result<DigestFor<B>, Error> {
+		let timestamp = inherents.timestamp_inherent_data()?
+			.ok_or_else(|| Error::StringError("No timestamp inherent data".into()))?;
+
+		// we always calculate the new slot number based on the current time-stamp and the slot
+		// duration.
+		let slot = Slot::from(*timestamp / self.slot_duration);
+		// the signature type is irrelevant, manual seal does not sign the blocks.
+		let digest_item = <DigestItemFor<B> as CompatibleDigestItem<()>>::aura_pre_digest(slot);
+
+		Ok(Digest { logs: vec![digest_item] })
+	}
+
+	fn append_block_import(
+		&self,
+		_parent: &B::Header,
+		_params: &mut BlockImportParams<B, Self::Transaction>,
+		_inherents: &InherentData,
+	) -> Result<(), Error> {
+		Ok(())
+	}
+}
@@ -21,11 +21,10 @@
 use super::ConsensusDataProvider;
 use crate::Error;
 use codec::Encode;
-use std::{borrow::Cow, sync::{Arc, atomic}, time::SystemTime};
+use std::{borrow::Cow, sync::Arc};
 use sc_client_api::{AuxStore, UsageProvider};
 use sc_consensus_babe::{
 	Config, Epoch, authorship, CompatibleDigestItem, BabeIntermediate, INTERMEDIATE_KEY,
-	find_pre_digest,
 };
 use sc_consensus_epochs::{SharedEpochChanges, descendent_query, ViableEpochDescriptor, EpochHeader};
 use sp_keystore::SyncCryptoStorePtr;
@@ -38,12 +37,14 @@ use sp_consensus_babe::{
 	BabeApi, inherents::BabeInherentData, ConsensusLog, BABE_ENGINE_ID, AuthorityId,
 	digests::{PreDigest, SecondaryPlainPreDigest, NextEpochDescriptor}, BabeAuthorityWeight,
 };
-use sp_inherents::{InherentData, InherentDataProvider, InherentIdentifier};
+use sp_inherents::InherentData;
 use sp_runtime::{
-	traits::{DigestItemFor, DigestFor, Block as BlockT, Zero, Header},
-	generic::{Digest, BlockId},
+	traits::{DigestItemFor, DigestFor, Block as BlockT, Header},
+	generic::Digest,
 };
-use sp_timestamp::{InherentType, INHERENT_IDENTIFIER, TimestampInherentData};
+use sp_timestamp::TimestampInherentData;
+
+pub use super::timestamp::SlotTimestampProvider;
 
 /// Provides BABE-compatible predigests and BlockImportParams.
 /// Intended for use with BABE runtimes.
@@ -247,64 +248,3 @@ impl<B, C> ConsensusDataProvider<B> for BabeConsensusDataProvider<B, C>
 		Ok(())
 	}
 }
-
-/// Provide duration since unix epoch in millisecond for timestamp inherent.
-/// Mocks the timestamp inherent to always produce the timestamp for the next babe slot.
-pub struct SlotTimestampProvider {
-	time: atomic::AtomicU64,
-	slot_duration: u64
-}
-
-impl SlotTimestampProvider {
-	/// Create a new mocked time stamp provider.
-	pub fn new<B, C>(client: Arc<C>) -> Result<Self, Error>
-		where
-			B: BlockT,
-			C: AuxStore + HeaderBackend<B> + ProvideRuntimeApi<B> + UsageProvider<B>,
-			C::Api: BabeApi<B>,
-	{
-		let slot_duration = Config::get_or_compute(&*client)?.slot_duration;
-		let info = client.info();
-
-		// looks like this isn't the first block, rehydrate the fake time.
-		// otherwise we'd be producing blocks for older slots.
-		let duration = if info.best_number != Zero::zero() {
-			let header = client.header(BlockId::Hash(info.best_hash))?.unwrap();
-			let slot = find_pre_digest::<B>(&header).unwrap().slot();
-			// add the slot duration so there's no collision of slots
-			(*slot * slot_duration) + slot_duration
-		} else {
-			// this is the first block, use the correct time.
-			let now = SystemTime::now();
-			now.duration_since(SystemTime::UNIX_EPOCH)
-				.map_err(|err| Error::StringError(format!("{}", err)))?
-				.as_millis() as u64
-		};
-
-		Ok(Self {
-			time: atomic::AtomicU64::new(duration),
-			slot_duration,
-		})
-	}
-}
-
-#[async_trait::async_trait]
-impl InherentDataProvider for SlotTimestampProvider {
-	fn provide_inherent_data(&self, inherent_data: &mut InherentData) -> Result<(), sp_inherents::Error> {
-		// we update the time here.
-		let duration: InherentType = self.time.fetch_add(
-			self.slot_duration,
-			atomic::Ordering::SeqCst,
-		).into();
-		inherent_data.put_data(INHERENT_IDENTIFIER, &duration)?;
-		Ok(())
-	}
-
-	async fn try_handle_error(
-		&self,
-		_: &InherentIdentifier,
-		_: &[u8],
-	) -> Option<Result<(), sp_inherents::Error>> {
-		None
-	}
-}
@@ -0,0 +1,137 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//! Mocked timestamp inherent, allows for manual seal to create blocks for runtimes
+//! that expect this inherent.
+
+use crate::Error;
+use std::{sync::{Arc, atomic}, time::SystemTime};
+use codec::Codec;
+use sc_client_api::{AuxStore, UsageProvider};
+use sc_consensus_babe::{Config, find_pre_digest};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_consensus_aura::{AuraApi, digests::CompatibleDigestItem};
+use sp_consensus_babe::BabeApi;
+use sp_consensus_slots::Slot;
+use sp_inherents::{InherentData, InherentDataProvider, InherentIdentifier};
+use sp_runtime::{
+	traits::{Block as BlockT, Zero, Header},
+	generic::BlockId,
+};
+use sp_timestamp::{InherentType, INHERENT_IDENTIFIER};
+
+/// Mocks the timestamp inherent to always produce the timestamp for the next slot, so that
+/// blocks can be authored faster than the slot duration of the runtime.
+///
+/// Clones share the same clock, so a single instance can serve all blocks authored by a node.
+#[derive(Clone)]
+pub struct SlotTimestampProvider {
+	time: Arc<atomic::AtomicU64>,
+	slot_duration: u64
+}
+
+impl SlotTimestampProvider {
+	/// Create a new mocked time stamp provider, for babe.
+	pub fn new_babe<B, C>(client: Arc<C>) -> Result<Self, Error>
+		where
+			B: BlockT,
+			C: AuxStore + HeaderBackend<B> + ProvideRuntimeApi<B> + UsageProvider<B>,
+			C::Api: BabeApi<B>,
+	{
+		let slot_duration = Config::get_or_compute(&*client)?.slot_duration;
+
+		Self::with_header(&client, slot_duration, |header| {
+			find_pre_digest::<B>(&header)
+				.map(|pre_digest| pre_digest.slot())
+				.map_err(|e| Error::StringError(format!("{}", e)))
+		})
+	}
+
+	/// Create a new mocked time stamp provider, for aura.
+	pub fn new_aura<B, C, AuthorityId>(client: Arc<C>) -> Result<Self, Error>
+		where
+			B: BlockT,
+			C: AuxStore + HeaderBackend<B> + ProvideRuntimeApi<B> + UsageProvider<B>,
+			C::Api: AuraApi<B, AuthorityId>,
+			AuthorityId: Codec,
+	{
+		let slot_duration = sc_consensus_aura::slot_duration(&*client)?
+			.slot_duration()
+			.as_millis() as u64;
+
+		Self::with_header(&client, slot_duration, |header| {
+			header.digest().logs().iter()
+				.find_map(|log| {
+					// the signature type is irrelevant for reading the pre-digest.
+					CompatibleDigestItem::<()>::as_aura_pre_digest(log)
+				})
+				.ok_or_else(|| Error::StringError("No aura pre-digest found".into()))
+		})
+	}
+
+	fn with_header<F, C, B>(client: &Arc<C>, slot_duration: u64, func: F) -> Result<Self, Error>
+		where
+			B: BlockT,
+			C: HeaderBackend<B>,
+			F: Fn(B::Header) -> Result<Slot, Error>,
+	{
+		let info = client.info();
+
+		// looks like this isn't the first block, rehydrate the fake time.
+		// otherwise we'd be producing blocks for older slots.
+		let time = if info.best_number != Zero::zero() {
+			let header = client.header(BlockId::Hash(info.best_hash))?
+				.ok_or_else(|| Error::StringError("best header not found".into()))?;
+			let slot = func(header)?;
+			// add the slot duration so there's no collision of slots
+			(*slot * slot_duration) + slot_duration
+		} else {
+			// this is the first block, use the correct time.
+			let now = SystemTime::now();
+			now.duration_since(SystemTime::UNIX_EPOCH)
+				.map_err(|err| Error::StringError(format!("{}", err)))?
+				.as_millis() as u64
+		};
+
+		Ok(Self {
+			time: Arc::new(atomic::AtomicU64::new(time)),
+			slot_duration,
+		})
+	}
+}
+
+#[async_trait::async_trait]
+impl InherentDataProvider for SlotTimestampProvider {
+	fn provide_inherent_data(&self, inherent_data: &mut InherentData) -> Result<(), sp_inherents::Error> {
+		// we update the time here.
+		let duration: InherentType = self.time.fetch_add(
+			self.slot_duration,
+			atomic::Ordering::SeqCst,
+		).into();
+		inherent_data.put_data(INHERENT_IDENTIFIER, &duration)?;
+		Ok(())
+	}
+
+	async fn try_handle_error(
+		&self,
+		_: &InherentIdentifier,
+		_: &[u8],
+	) -> Option<Result<(), sp_inherents::Error>> {
+		None
+	}
+}
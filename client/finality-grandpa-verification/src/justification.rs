@@ -28,7 +28,7 @@ use sp_runtime::{
 	traits::{Block as BlockT, Header as HeaderT, NumberFor},
 };
 
-use crate::{AuthorityList, Commit, Error};
+use crate::{AuthorityList, Commit};
 
 /// A GRANDPA justification for block finality, it includes a commit message and
 /// an ancestry proof including all headers routing all precommit target blocks
@@ -41,7 +41,8 @@ use crate::{AuthorityList, Commit, Error};
 #[derive(Clone, Encode, Decode, PartialEq, Eq, Debug)]
 pub struct GrandpaJustification<Block: BlockT> {
 	round: u64,
-	pub(crate) commit: Commit<Block>,
+	/// The commit message finalizing the target block.
+	pub commit: Commit<Block>,
 	votes_ancestries: Vec<Block::Header>,
 }
 
@@ -52,7 +53,7 @@ impl<Block: BlockT> GrandpaJustification<Block> {
 		client: &Arc<C>,
 		round: u64,
 		commit: Commit<Block>,
-	) -> Result<GrandpaJustification<Block>, Error> where
+	) -> Result<GrandpaJustification<Block>, ClientError> where
 		C: HeaderBackend<Block>,
 	{
 		let mut votes_ancestries_hashes = HashSet::new();
@@ -60,7 +61,7 @@ impl<Block: BlockT> GrandpaJustification<Block> {
 
 		let error = || {
 			let msg = "invalid precommits for target commit".to_string();
-			Err(Error::Client(ClientError::BadJustification(msg)))
+			Err(ClientError::BadJustification(msg))
 		};
 
 		for signed in commit.precommits.iter() {
@@ -124,8 +125,9 @@ impl<Block: BlockT> GrandpaJustification<Block> {
 		self.verify_with_voter_set(set_id, &voters)
 	}
 
-	/// Validate the commit and the votes' ancestry proofs.
-	pub(crate) fn verify_with_voter_set(
+	/// Validate the commit and the votes' ancestry proofs against an already constructed
+	/// voter set.
+	pub fn verify_with_voter_set(
 		&self,
 		set_id: u64,
 		voters: &VoterSet<AuthorityId>,
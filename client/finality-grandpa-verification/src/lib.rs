@@ -0,0 +1,296 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Verification of GRANDPA finality proofs.
+//!
+//! This crate allows checking, using nothing but headers, that a block has been finalized by a
+//! given GRANDPA authority set ([`GrandpaJustification::verify`]) and following a chain of
+//! authority set handoffs from a known set to a later one ([`verify_authority_set_handoffs`]).
+//! It neither depends on a client nor on the voter and is meant to be shared by the warp sync
+//! protocol, bridges and any external verifier.
+
+#![warn(missing_docs)]
+
+use sp_finality_grandpa::{
+	AuthorityId, AuthorityList, AuthoritySignature, ConsensusLog, ScheduledChange, SetId,
+	GRANDPA_ENGINE_ID,
+};
+use sp_runtime::{
+	generic::OpaqueDigestItemId,
+	traits::{Block as BlockT, Header as HeaderT, NumberFor},
+};
+
+mod justification;
+
+pub use finality_grandpa::BlockNumberOps;
+pub use justification::GrandpaJustification;
+
+/// A commit message for this chain's block type.
+pub type Commit<Block> = finality_grandpa::Commit<
+	<Block as BlockT>::Hash,
+	NumberFor<Block>,
+	AuthoritySignature,
+	AuthorityId,
+>;
+
+/// Errors that can occur while verifying a chain of authority set handoffs.
+#[derive(Debug, derive_more::Display)]
+pub enum Error {
+	/// The justification of the fragment at the given index is invalid.
+	#[display(fmt = "Invalid justification in fragment {}: {}", _0, _1)]
+	InvalidJustification(usize, sp_blockchain::Error),
+	/// The justification of the fragment at the given index does not finalize its header.
+	#[display(fmt = "Mismatch between header and justification in fragment {}", _0)]
+	HeaderMismatch(usize),
+	/// The header of the fragment at the given index, which is not the last one, does not
+	/// signal a standard authority set change.
+	#[display(fmt = "Header is missing authority set change digest in fragment {}", _0)]
+	MissingAuthoritySetChange(usize),
+}
+
+impl std::error::Error for Error {}
+
+/// Checks the given header for a consensus digest signalling a **standard** scheduled change and
+/// extracts it.
+pub fn find_scheduled_change<B: BlockT>(
+	header: &B::Header,
+) -> Option<ScheduledChange<NumberFor<B>>> {
+	let id = OpaqueDigestItemId::Consensus(&GRANDPA_ENGINE_ID);
+
+	let filter_log = |log: ConsensusLog<NumberFor<B>>| match log {
+		ConsensusLog::ScheduledChange(change) => Some(change),
+		_ => None,
+	};
+
+	// find the first consensus digest with the right ID which converts to
+	// the right kind of consensus log.
+	header.digest().convert_first(|l| l.try_to(id).and_then(filter_log))
+}
+
+/// Checks the given header for a consensus digest signalling a **forced** scheduled change and
+/// extracts it.
+pub fn find_forced_change<B: BlockT>(
+	header: &B::Header,
+) -> Option<(NumberFor<B>, ScheduledChange<NumberFor<B>>)> {
+	let id = OpaqueDigestItemId::Consensus(&GRANDPA_ENGINE_ID);
+
+	let filter_log = |log: ConsensusLog<NumberFor<B>>| match log {
+		ConsensusLog::ForcedChange(delay, change) => Some((delay, change)),
+		_ => None,
+	};
+
+	// find the first consensus digest with the right ID which converts to
+	// the right kind of consensus log.
+	header.digest().convert_first(|l| l.try_to(id).and_then(filter_log))
+}
+
+/// Verifies a chain of authority set handoffs starting at the given set id and authorities.
+///
+/// Each fragment is a header together with a justification for it, which must have been signed
+/// by the current authority set. Every header but the last one must signal a standard authority
+/// set change, which is then applied before checking the next fragment. The last fragment may
+/// instead just prove finality of a later block under the latest set.
+///
+/// Forced changes break the chain of trust and are never followed. If all fragments are valid
+/// the resulting set id and authorities are returned.
+pub fn verify_authority_set_handoffs<'a, Block, I>(
+	set_id: SetId,
+	authorities: AuthorityList,
+	fragments: I,
+) -> Result<(SetId, AuthorityList), Error>
+where
+	Block: BlockT,
+	NumberFor<Block>: BlockNumberOps,
+	I: IntoIterator<Item = (&'a Block::Header, &'a GrandpaJustification<Block>)>,
+{
+	let mut current_set_id = set_id;
+	let mut current_authorities = authorities;
+	let mut fragments = fragments.into_iter().enumerate().peekable();
+
+	while let Some((fragment_num, (header, justification))) = fragments.next() {
+		justification
+			.verify(current_set_id, &current_authorities)
+			.map_err(|err| Error::InvalidJustification(fragment_num, err))?;
+
+		if justification.target().1 != header.hash() {
+			return Err(Error::HeaderMismatch(fragment_num));
+		}
+
+		if let Some(scheduled_change) = find_scheduled_change::<Block>(header) {
+			current_authorities = scheduled_change.next_authorities;
+			current_set_id += 1;
+		} else if fragments.peek().is_some() {
+			// Only the last fragment is allowed to be missing the authority set change.
+			return Err(Error::MissingAuthoritySetChange(fragment_num));
+		}
+	}
+
+	Ok((current_set_id, current_authorities))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use parity_scale_codec::Encode;
+	use sp_keyring::Ed25519Keyring;
+	use sp_runtime::generic::DigestItem;
+	use substrate_test_runtime_client::runtime::{Block, Header};
+
+	fn header_with_log(log: ConsensusLog<u64>) -> Header {
+		let mut header = Header::new(
+			1,
+			Default::default(),
+			Default::default(),
+			Default::default(),
+			Default::default(),
+		);
+		header.digest_mut().push(DigestItem::Consensus(GRANDPA_ENGINE_ID, log.encode()));
+		header
+	}
+
+	fn signed_justification(
+		header: &Header,
+		set_id: SetId,
+		keys: &[Ed25519Keyring],
+	) -> GrandpaJustification<Block> {
+		let precommit = finality_grandpa::Precommit {
+			target_hash: header.hash(),
+			target_number: *header.number(),
+		};
+		let msg = finality_grandpa::Message::Precommit(precommit.clone());
+		let encoded = sp_finality_grandpa::localized_payload(1, set_id, &msg);
+
+		let precommits = keys.iter().map(|keyring| finality_grandpa::SignedPrecommit {
+			precommit: precommit.clone(),
+			signature: keyring.sign(&encoded[..]).into(),
+			id: keyring.public().into(),
+		}).collect();
+
+		let commit = Commit::<Block> {
+			target_hash: header.hash(),
+			target_number: *header.number(),
+			precommits,
+		};
+
+		GrandpaJustification::from_commit(&std::sync::Arc::new(NoHeaders), 1, commit).unwrap()
+	}
+
+	/// A header backend without any headers, for justifications without votes ancestries.
+	struct NoHeaders;
+
+	impl sp_blockchain::HeaderBackend<Block> for NoHeaders {
+		fn header(
+			&self,
+			_: sp_runtime::generic::BlockId<Block>,
+		) -> sp_blockchain::Result<Option<Header>> {
+			Ok(None)
+		}
+		fn info(&self) -> sp_blockchain::Info<Block> {
+			unimplemented!()
+		}
+		fn status(
+			&self,
+			_: sp_runtime::generic::BlockId<Block>,
+		) -> sp_blockchain::Result<sp_blockchain::BlockStatus> {
+			unimplemented!()
+		}
+		fn number(
+			&self,
+			_: <Block as BlockT>::Hash,
+		) -> sp_blockchain::Result<Option<u64>> {
+			unimplemented!()
+		}
+		fn hash(&self, _: u64) -> sp_blockchain::Result<Option<<Block as BlockT>::Hash>> {
+			unimplemented!()
+		}
+	}
+
+	fn authorities(keys: &[Ed25519Keyring]) -> AuthorityList {
+		keys.iter().map(|keyring| (keyring.public().into(), 1)).collect()
+	}
+
+	#[test]
+	fn finds_scheduled_and_forced_changes() {
+		let change = ScheduledChange {
+			next_authorities: authorities(&[Ed25519Keyring::Bob]),
+			delay: 0,
+		};
+
+		let header = header_with_log(ConsensusLog::ScheduledChange(change.clone()));
+		assert_eq!(find_scheduled_change::<Block>(&header), Some(change.clone()));
+		assert_eq!(find_forced_change::<Block>(&header), None);
+
+		let header = header_with_log(ConsensusLog::ForcedChange(5, change.clone()));
+		assert_eq!(find_scheduled_change::<Block>(&header), None);
+		assert_eq!(find_forced_change::<Block>(&header), Some((5, change)));
+	}
+
+	#[test]
+	fn verifies_authority_set_handoffs() {
+		let alice = [Ed25519Keyring::Alice];
+		let bob = [Ed25519Keyring::Bob];
+
+		let change = ScheduledChange { next_authorities: authorities(&bob), delay: 0 };
+		let handoff = header_with_log(ConsensusLog::ScheduledChange(change));
+		let handoff_justification = signed_justification(&handoff, 0, &alice);
+
+		let mut last = handoff.clone();
+		last.set_parent_hash(handoff.hash());
+		last.set_number(2);
+		let last_justification = signed_justification(&last, 1, &bob);
+
+		assert_eq!(
+			verify_authority_set_handoffs::<Block, _>(
+				0,
+				authorities(&alice),
+				vec![(&handoff, &handoff_justification), (&last, &last_justification)],
+			).unwrap(),
+			(1, authorities(&bob)),
+		);
+
+		// justification signed by the wrong set.
+		assert!(matches!(
+			verify_authority_set_handoffs::<Block, _>(
+				0,
+				authorities(&alice),
+				vec![(&handoff, &handoff_justification), (&last, &handoff_justification)],
+			),
+			Err(Error::InvalidJustification(1, _)),
+		));
+
+		// justification for a different header.
+		assert!(matches!(
+			verify_authority_set_handoffs::<Block, _>(
+				0,
+				authorities(&alice),
+				vec![(&last, &handoff_justification)],
+			),
+			Err(Error::HeaderMismatch(0)),
+		));
+
+		// a fragment without a set change can only come last.
+		assert!(matches!(
+			verify_authority_set_handoffs::<Block, _>(
+				1,
+				authorities(&bob),
+				vec![(&last, &last_justification), (&last, &last_justification)],
+			),
+			Err(Error::MissingAuthoritySetChange(0)),
+		));
+	}
+}
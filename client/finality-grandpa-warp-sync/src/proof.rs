@@ -18,13 +18,14 @@ use codec::{Decode, Encode};
 
 use sc_client_api::Backend as ClientBackend;
 use sc_finality_grandpa::{
-	find_scheduled_change, AuthoritySetChanges, BlockNumberOps, GrandpaJustification,
+	find_scheduled_change, verify_authority_set_handoffs, AuthoritySetChanges, BlockNumberOps,
+	GrandpaJustification,
 };
 use sp_blockchain::{Backend as BlockchainBackend, HeaderBackend};
 use sp_finality_grandpa::{AuthorityList, SetId, GRANDPA_ENGINE_ID};
 use sp_runtime::{
 	generic::BlockId,
-	traits::{Block as BlockT, Header as HeaderT, NumberFor, One},
+	traits::{Block as BlockT, NumberFor, One},
 };
 
 use crate::HandleRequestError;
@@ -185,34 +186,12 @@ impl<Block: BlockT> WarpSyncProof<Block> {
 	where
 		NumberFor<Block>: BlockNumberOps,
 	{
-		let mut current_set_id = set_id;
-		let mut current_authorities = authorities;
-
-		for (fragment_num, proof) in self.proofs.iter().enumerate() {
-			proof
-				.justification
-				.verify(current_set_id, &current_authorities)
-				.map_err(|err| HandleRequestError::InvalidProof(err.to_string()))?;
-
-			if proof.justification.target().1 != proof.header.hash() {
-				return Err(HandleRequestError::InvalidProof(
-					"mismatch between header and justification".to_owned()
-				));
-			}
-
-			if let Some(scheduled_change) = find_scheduled_change::<Block>(&proof.header) {
-				current_authorities = scheduled_change.next_authorities;
-				current_set_id += 1;
-			} else if fragment_num != self.proofs.len() - 1 {
-				// Only the last fragment of the proof is allowed to be missing the authority
-				// set change.
-				return Err(HandleRequestError::InvalidProof(
-					"Header is missing authority set change digest".to_string(),
-				));
-			}
-		}
-
-		Ok((current_set_id, current_authorities))
+		verify_authority_set_handoffs::<Block, _>(
+			set_id,
+			authorities,
+			self.proofs.iter().map(|proof| (&proof.header, &proof.justification)),
+		)
+		.map_err(|err| HandleRequestError::InvalidProof(err.to_string()))
 	}
 }
 
@@ -51,7 +51,7 @@ use sc_client_api::backend::Backend;
 use crate::{
 	SharedAuthoritySet, best_justification,
 	authorities::{AuthoritySetChangeId, AuthoritySetChanges},
-	justification::GrandpaJustification,
+	GrandpaJustification,
 };
 
 const MAX_UNKNOWN_HEADERS: usize = 100_000;
@@ -33,9 +33,9 @@ use sp_consensus::{
 	BlockCheckParams, BlockImportParams, BlockOrigin, ImportResult, JustificationImport,
 	SelectChain,
 };
-use sp_finality_grandpa::{ConsensusLog, ScheduledChange, SetId, GRANDPA_ENGINE_ID};
+use sp_finality_grandpa::{SetId, GRANDPA_ENGINE_ID};
 use sp_runtime::Justification;
-use sp_runtime::generic::{BlockId, OpaqueDigestItemId};
+use sp_runtime::generic::BlockId;
 use sp_runtime::traits::{
 	Block as BlockT, DigestFor, Header as HeaderT, NumberFor, Zero,
 };
@@ -43,7 +43,7 @@ use sp_runtime::traits::{
 use crate::{Error, CommandOrError, NewAuthoritySet, VoterCommand};
 use crate::authorities::{AuthoritySet, SharedAuthoritySet, DelayKind, PendingChange};
 use crate::environment::finalize_block;
-use crate::justification::GrandpaJustification;
+use crate::{find_forced_change, find_scheduled_change, GrandpaJustification};
 use crate::notification::GrandpaJustificationSender;
 use std::marker::PhantomData;
 
@@ -185,40 +185,6 @@ impl<Block: BlockT> Drop for PendingSetChanges<Block> {
 	}
 }
 
-/// Checks the given header for a consensus digest signalling a **standard** scheduled change and
-/// extracts it.
-pub fn find_scheduled_change<B: BlockT>(
-	header: &B::Header,
-) -> Option<ScheduledChange<NumberFor<B>>> {
-	let id = OpaqueDigestItemId::Consensus(&GRANDPA_ENGINE_ID);
-
-	let filter_log = |log: ConsensusLog<NumberFor<B>>| match log {
-		ConsensusLog::ScheduledChange(change) => Some(change),
-		_ => None,
-	};
-
-	// find the first consensus digest with the right ID which converts to
-	// the right kind of consensus log.
-	header.digest().convert_first(|l| l.try_to(id).and_then(filter_log))
-}
-
-/// Checks the given header for a consensus digest signalling a **forced** scheduled change and
-/// extracts it.
-pub fn find_forced_change<B: BlockT>(
-	header: &B::Header,
-) -> Option<(NumberFor<B>, ScheduledChange<NumberFor<B>>)> {
-	let id = OpaqueDigestItemId::Consensus(&GRANDPA_ENGINE_ID);
-
-	let filter_log = |log: ConsensusLog<NumberFor<B>>| match log {
-		ConsensusLog::ForcedChange(delay, change) => Some((delay, change)),
-		_ => None,
-	};
-
-	// find the first consensus digest with the right ID which converts to
-	// the right kind of consensus log.
-	header.digest().convert_first(|l| l.try_to(id).and_then(filter_log))
-}
-
 impl<BE, Block: BlockT, Client, SC>
 	GrandpaBlockImport<BE, Block, Client, SC>
 where
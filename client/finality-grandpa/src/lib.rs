@@ -115,7 +115,6 @@ mod communication;
 mod environment;
 mod finality_proof;
 mod import;
-mod justification;
 mod notification;
 mod observer;
 mod until_imported;
@@ -124,8 +123,11 @@ mod voting_rule;
 pub use authorities::{AuthoritySet, AuthoritySetChanges, SharedAuthoritySet};
 pub use aux_schema::best_justification;
 pub use finality_proof::{FinalityProof, FinalityProofError, FinalityProofProvider};
-pub use import::{find_forced_change, find_scheduled_change, GrandpaBlockImport};
-pub use justification::GrandpaJustification;
+pub use import::GrandpaBlockImport;
+pub use sc_finality_grandpa_verification::{
+	find_forced_change, find_scheduled_change, verify_authority_set_handoffs, Commit,
+	GrandpaJustification,
+};
 pub use notification::{GrandpaJustificationSender, GrandpaJustificationStream};
 pub use observer::run_grandpa_observer;
 pub use voting_rule::{
@@ -171,13 +173,6 @@ pub type CatchUp<Block> = finality_grandpa::CatchUp<
 	AuthoritySignature,
 	AuthorityId,
 >;
-/// A commit message for this chain's block type.
-pub type Commit<Block> = finality_grandpa::Commit<
-	<Block as BlockT>::Hash,
-	NumberFor<Block>,
-	AuthoritySignature,
-	AuthorityId,
->;
 /// A compact commit message for this chain's block type.
 pub type CompactCommit<Block> = finality_grandpa::CompactCommit<
 	<Block as BlockT>::Hash,
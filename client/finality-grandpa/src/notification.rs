@@ -22,7 +22,7 @@ use parking_lot::Mutex;
 use sp_runtime::traits::Block as BlockT;
 use sp_utils::mpsc::{tracing_unbounded, TracingUnboundedReceiver, TracingUnboundedSender};
 
-use crate::justification::GrandpaJustification;
+use crate::GrandpaJustification;
 use crate::Error;
 
 // Stream of justifications returned when subscribing.
@@ -35,6 +35,7 @@ pub use policy::DenyUnsafe;
 pub mod author;
 pub mod chain;
 pub mod offchain;
+pub mod session;
 pub mod state;
 pub mod child_state;
 pub mod system;
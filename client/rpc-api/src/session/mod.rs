@@ -0,0 +1,48 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Substrate session API.
+
+pub mod error;
+
+use jsonrpc_derive::rpc;
+use sp_core::Bytes;
+use self::error::Result;
+
+pub use self::gen_client::Client as SessionClient;
+
+/// Substrate session RPC API
+#[rpc]
+pub trait SessionApi<BlockHash> {
+	/// Generate a proof that the public session key `public` of type `key_type` is owned by
+	/// `validator` in the session of the given block, or of the best block if none is given.
+	///
+	/// `validator` is the SCALE encoded validator id. The proof is returned SCALE encoded, as
+	/// expected by equivocation report extrinsics, and is only valid for offences committed within
+	/// the same session.
+	///
+	/// Returns `None` if the key is not a member of the session or is owned by another validator.
+	#[rpc(name = "session_generateKeyOwnershipProof")]
+	fn generate_key_ownership_proof(
+		&self,
+		key_type: String,
+		public: Bytes,
+		validator: Bytes,
+		at: Option<BlockHash>,
+	) -> Result<Option<Bytes>>;
+}
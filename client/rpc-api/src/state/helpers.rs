@@ -18,7 +18,7 @@
 
 //! Substrate state API helpers.
 
-use sp_core::Bytes;
+use sp_core::{Bytes, storage::StorageKey};
 use serde::{Serialize, Deserialize};
 
 /// ReadProof struct returned by the RPC
@@ -30,3 +30,30 @@ pub struct ReadProof<Hash> {
 	/// A proof used to prove that storage entries are included in the storage trie
 	pub proof: Vec<Bytes>,
 }
+
+/// StorageUsage struct returned by the RPC
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageUsage<Hash> {
+	/// Block hash of the state that was walked
+	pub at: Hash,
+	/// Usage of every pallet encountered, ordered by prefix
+	pub pallets: Vec<PalletStorageUsage>,
+	/// Key to continue walking the state after, if the state was not walked entirely
+	pub next_key: Option<StorageKey>,
+}
+
+/// Storage usage of a single pallet
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PalletStorageUsage {
+	/// The `twox_128` hash of the pallet prefix, or the whole key for keys that are shorter,
+	/// such as `:code`
+	pub prefix: Bytes,
+	/// Number of keys
+	pub keys: u64,
+	/// Total size of the keys, in bytes
+	pub key_bytes: u64,
+	/// Total size of the values, in bytes
+	pub value_bytes: u64,
+}
@@ -30,7 +30,7 @@ use sp_version::RuntimeVersion;
 use self::error::FutureResult;
 
 pub use self::gen_client::Client as StateClient;
-pub use self::helpers::ReadProof;
+pub use self::helpers::{ReadProof, StorageUsage, PalletStorageUsage};
 
 /// Substrate state API
 #[rpc]
@@ -107,6 +107,22 @@ pub trait StateApi<Hash> {
 	#[rpc(name = "state_getReadProof")]
 	fn read_proof(&self, keys: Vec<StorageKey>, hash: Option<Hash>) -> FutureResult<ReadProof<Hash>>;
 
+	/// Returns the number of keys and their total size per pallet, walking up to `count` keys of
+	/// a block's state.
+	///
+	/// If `start_key` is passed, the walk starts at the next key in lexicographic order. The
+	/// returned `nextKey` is to be passed as `start_key` to continue, and a pallet may appear in
+	/// consecutive pages.
+	///
+	/// Note: requires the node to run with `--rpc-methods=Unsafe`.
+	#[rpc(name = "state_getStorageUsage")]
+	fn storage_usage(
+		&self,
+		count: u32,
+		start_key: Option<StorageKey>,
+		hash: Option<Hash>,
+	) -> FutureResult<StorageUsage<Hash>>;
+
 	/// New runtime version subscription
 	#[pubsub(
 		subscription = "state_runtimeVersion",
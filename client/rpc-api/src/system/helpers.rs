@@ -49,6 +49,33 @@ pub struct Health {
 	///
 	/// Might be false for local chains or when running without discovery.
 	pub should_have_peers: bool,
+	/// Outcome of the node's health checks, if they have been run.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub report: Option<HealthReport>,
+}
+
+/// Outcome of a single health check.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthCheck {
+	/// Name of the check, e.g. `finality`.
+	pub name: String,
+	/// Whether the check passed.
+	pub healthy: bool,
+	/// Human readable outcome of the check.
+	pub detail: String,
+}
+
+/// Aggregated outcome of the node's health checks.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthReport {
+	/// Whether all checks passed, i.e. the node is ready to serve requests.
+	pub healthy: bool,
+	/// Whether the node is alive, i.e. its database responds.
+	pub live: bool,
+	/// Individual checks.
+	pub checks: Vec<HealthCheck>,
 }
 
 impl fmt::Display for Health {
@@ -107,11 +134,33 @@ mod tests {
 				peers: 1,
 				is_syncing: false,
 				should_have_peers: true,
+				report: None,
 			}).unwrap(),
 			r#"{"peers":1,"isSyncing":false,"shouldHavePeers":true}"#,
 		);
 	}
 
+	#[test]
+	fn should_serialize_health_report() {
+		assert_eq!(
+			::serde_json::to_string(&Health {
+				peers: 0,
+				is_syncing: true,
+				should_have_peers: true,
+				report: Some(HealthReport {
+					healthy: false,
+					live: true,
+					checks: vec![HealthCheck {
+						name: "sync".into(),
+						healthy: false,
+						detail: "major sync in progress".into(),
+					}],
+				}),
+			}).unwrap(),
+			r#"{"peers":0,"isSyncing":true,"shouldHavePeers":true,"report":{"healthy":false,"live":true,"checks":[{"name":"sync","healthy":false,"detail":"major sync in progress"}]}}"#,
+		);
+	}
+
 	#[test]
 	fn should_serialize_peer_info() {
 		assert_eq!(
@@ -33,6 +33,7 @@ pub use rpc::IoHandlerExtension as RpcExtension;
 pub mod author;
 pub mod chain;
 pub mod offchain;
+pub mod session;
 pub mod state;
 pub mod system;
 
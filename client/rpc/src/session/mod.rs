@@ -0,0 +1,74 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//! Substrate session API.
+
+#[cfg(test)]
+mod tests;
+
+use std::{sync::Arc, convert::TryInto, marker::PhantomData};
+
+use codec::Encode;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use sp_session::SessionKeyOwnership;
+
+/// Re-export the API for backward compatibility.
+pub use sc_rpc_api::session::*;
+use self::error::{Error, Result};
+
+/// Session API
+pub struct Session<Block, Client> {
+	/// Substrate client
+	client: Arc<Client>,
+	_marker: PhantomData<Block>,
+}
+
+impl<Block, Client> Session<Block, Client> {
+	/// Create new instance of Session API.
+	pub fn new(client: Arc<Client>) -> Self {
+		Session {
+			client,
+			_marker: PhantomData,
+		}
+	}
+}
+
+impl<Block, Client> SessionApi<Block::Hash> for Session<Block, Client>
+	where
+		Block: BlockT,
+		Client: HeaderBackend<Block> + ProvideRuntimeApi<Block> + Send + Sync + 'static,
+		Client::Api: SessionKeyOwnership<Block>,
+{
+	fn generate_key_ownership_proof(
+		&self,
+		key_type: String,
+		public: Bytes,
+		validator: Bytes,
+		at: Option<Block::Hash>,
+	) -> Result<Option<Bytes>> {
+		let key_type = key_type.as_str().try_into().map_err(|_| Error::BadKeyType)?;
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		self.client.runtime_api()
+			.generate_key_ownership_proof(&at, key_type, public.to_vec(), validator.to_vec())
+			.map(|proof| proof.map(|proof| proof.encode().into()))
+			.map_err(|e| Error::Client(Box::new(e)))
+	}
+}
@@ -0,0 +1,51 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+use assert_matches::assert_matches;
+use sp_core::testing::ED25519;
+use substrate_test_runtime_client::{AccountKeyring, runtime::Block};
+
+fn session() -> Session<Block, substrate_test_runtime_client::TestClient> {
+	Session::new(Arc::new(substrate_test_runtime_client::new()))
+}
+
+#[test]
+fn generate_key_ownership_proof_should_work() {
+	let proof = session().generate_key_ownership_proof(
+		String::from_utf8(ED25519.0.to_vec()).unwrap(),
+		AccountKeyring::Alice.public().0.to_vec().into(),
+		AccountKeyring::Alice.to_account_id().encode().into(),
+		None,
+	);
+
+	// the test runtime has no historical sessions to prove membership in.
+	assert_matches!(proof, Ok(None));
+}
+
+#[test]
+fn generate_key_ownership_proof_should_reject_bad_key_type() {
+	let proof = session().generate_key_ownership_proof(
+		"bad".into(),
+		AccountKeyring::Alice.public().0.to_vec().into(),
+		AccountKeyring::Alice.to_account_id().encode().into(),
+		None,
+	);
+
+	assert_matches!(proof, Err(Error::BadKeyType));
+}
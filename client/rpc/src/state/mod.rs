@@ -28,7 +28,7 @@ use std::sync::Arc;
 use jsonrpc_pubsub::{typed::Subscriber, SubscriptionId, manager::SubscriptionManager};
 use rpc::{Result as RpcResult, futures::{Future, future::result}};
 
-use sc_rpc_api::{DenyUnsafe, state::ReadProof};
+use sc_rpc_api::{DenyUnsafe, state::{ReadProof, StorageUsage}};
 use sc_client_api::light::{RemoteBlockchain, Fetcher};
 use sp_core::{Bytes, storage::{StorageKey, PrefixedStorageKey, StorageData, StorageChangeSet}};
 use sp_version::RuntimeVersion;
@@ -46,6 +46,7 @@ use sc_client_api::{
 use sp_blockchain::{HeaderMetadata, HeaderBackend};
 
 const STORAGE_KEYS_PAGED_MAX_COUNT: u32 = 1000;
+const STORAGE_USAGE_MAX_COUNT: u32 = 100_000;
 
 /// State backend API.
 pub trait StateBackend<Block: BlockT, Client>: Send + Sync + 'static
@@ -139,6 +140,14 @@ pub trait StateBackend<Block: BlockT, Client>: Send + Sync + 'static
 		keys: Vec<StorageKey>,
 	) -> FutureResult<ReadProof<Block::Hash>>;
 
+	/// Returns the storage usage per pallet of up to `count` keys, starting after `start_key`.
+	fn storage_usage(
+		&self,
+		block: Option<Block::Hash>,
+		count: u32,
+		start_key: Option<StorageKey>,
+	) -> FutureResult<StorageUsage<Block::Hash>>;
+
 	/// New runtime version subscription
 	fn subscribe_runtime_version(
 		&self,
@@ -329,6 +338,27 @@ impl<Block, Client> StateApi<Block::Hash> for State<Block, Client>
 		self.backend.read_proof(block, keys)
 	}
 
+	fn storage_usage(
+		&self,
+		count: u32,
+		start_key: Option<StorageKey>,
+		block: Option<Block::Hash>,
+	) -> FutureResult<StorageUsage<Block::Hash>> {
+		if let Err(err) = self.deny_unsafe.check_if_safe() {
+			return Box::new(result(Err(err.into())))
+		}
+
+		if count > STORAGE_USAGE_MAX_COUNT {
+			return Box::new(result(Err(
+				Error::InvalidCount {
+					value: count,
+					max: STORAGE_USAGE_MAX_COUNT,
+				}
+			)));
+		}
+		self.backend.storage_usage(block, count, start_key)
+	}
+
 	fn subscribe_storage(
 		&self,
 		meta: Self::Metadata,
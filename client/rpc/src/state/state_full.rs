@@ -26,7 +26,7 @@ use log::warn;
 use jsonrpc_pubsub::{typed::Subscriber, SubscriptionId, manager::SubscriptionManager};
 use rpc::{Result as RpcResult, futures::{stream, Future, Sink, Stream, future::result}};
 
-use sc_rpc_api::state::ReadProof;
+use sc_rpc_api::state::{ReadProof, StorageUsage, PalletStorageUsage};
 use sp_blockchain::{
 	Result as ClientResult, Error as ClientError, HeaderMetadata, CachedHeaderMetadata,
 	HeaderBackend
@@ -415,6 +415,47 @@ impl<BE, Block, Client> StateBackend<Block, Client> for FullState<BE, Block, Cli
 		))
 	}
 
+	fn storage_usage(
+		&self,
+		block: Option<Block::Hash>,
+		count: u32,
+		start_key: Option<StorageKey>,
+	) -> FutureResult<StorageUsage<Block::Hash>> {
+		Box::new(result(
+			self.block_or_best(block)
+				.and_then(|block| {
+					let id = BlockId::Hash(block);
+					let mut keys = self.client
+						.storage_keys_iter(&id, None, start_key.as_ref())?
+						.peekable();
+					let mut pallets = BTreeMap::<Vec<u8>, PalletStorageUsage>::new();
+					let mut last_key = None;
+					for key in keys.by_ref().take(count as usize) {
+						let value_len = self.client.storage(&id, &key)?
+							.map_or(0, |value| value.0.len() as u64);
+						// pallet storage is prefixed by the `twox_128` hash of the pallet prefix.
+						let prefix = key.0[..key.0.len().min(16)].to_vec();
+						let usage = pallets.entry(prefix.clone()).or_insert_with(|| PalletStorageUsage {
+							prefix: prefix.into(),
+							..Default::default()
+						});
+						usage.keys += 1;
+						usage.key_bytes += key.0.len() as u64;
+						usage.value_bytes += value_len;
+						last_key = Some(key);
+					}
+					let next_key = if keys.peek().is_some() { last_key } else { None };
+
+					Ok(StorageUsage {
+						at: block,
+						pallets: pallets.into_iter().map(|(_, usage)| usage).collect(),
+						next_key,
+					})
+				})
+				.map_err(client_err),
+		))
+	}
+
 	fn subscribe_runtime_version(
 		&self,
 		_meta: crate::Metadata,
@@ -40,7 +40,7 @@ use rpc::{
 	futures::stream::Stream,
 };
 
-use sc_rpc_api::state::ReadProof;
+use sc_rpc_api::state::{ReadProof, StorageUsage};
 use sp_blockchain::{Error as ClientError, HeaderBackend};
 use sc_client_api::{
 	BlockchainEvents,
@@ -475,6 +475,15 @@ impl<Block, F, Client> StateBackend<Block, Client> for LightState<Block, F, Clie
 		Ok(self.subscriptions.cancel(id))
 	}
 
+	fn storage_usage(
+		&self,
+		_block: Option<Block::Hash>,
+		_count: u32,
+		_start_key: Option<StorageKey>,
+	) -> FutureResult<StorageUsage<Block::Hash>> {
+		Box::new(result(Err(client_err(ClientError::NotAvailableOnLightClient))))
+	}
+
 	fn trace_block(
 		&self,
 		_block: Block::Hash,
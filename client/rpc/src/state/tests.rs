@@ -20,7 +20,7 @@ use super::*;
 use super::state_full::split_range;
 use self::error::Error;
 
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 use assert_matches::assert_matches;
 use futures01::stream::Stream;
 use sp_core::{storage::ChildInfo, ChangesTrieConfiguration};
@@ -94,6 +94,65 @@ fn should_return_storage() {
 	);
 }
 
+#[test]
+fn should_return_storage_usage() {
+	let prefix = vec![1u8; 16];
+	let client = TestClientBuilder::new()
+		.add_extra_storage([&prefix[..], b"a"].concat(), vec![1, 2])
+		.add_extra_storage([&prefix[..], b"bb"].concat(), vec![1, 2, 3])
+		.build();
+	let (client, _child) = new_full(
+		Arc::new(client),
+		SubscriptionManager::new(Arc::new(TaskExecutor)),
+		DenyUnsafe::No,
+	);
+
+	let usage = client.storage_usage(STORAGE_USAGE_MAX_COUNT, None, None).wait().unwrap();
+	assert_eq!(usage.next_key, None);
+	assert_eq!(
+		usage.pallets.iter().find(|p| p.prefix.0 == prefix),
+		Some(&PalletStorageUsage { prefix: prefix.clone().into(), keys: 2, key_bytes: 35, value_bytes: 5 }),
+	);
+
+	// walking the state in pages sums up to the same usage.
+	let mut paged = BTreeMap::<Vec<u8>, (u64, u64, u64)>::new();
+	let mut start_key = None;
+	loop {
+		let page = client.storage_usage(1, start_key, None).wait().unwrap();
+		for p in page.pallets {
+			let total = paged.entry(p.prefix.0).or_default();
+			*total = (total.0 + p.keys, total.1 + p.key_bytes, total.2 + p.value_bytes);
+		}
+		match page.next_key {
+			Some(key) => start_key = Some(key),
+			None => break,
+		}
+	}
+	assert_eq!(
+		paged,
+		usage.pallets.into_iter().map(|p| (p.prefix.0, (p.keys, p.key_bytes, p.value_bytes))).collect(),
+	);
+
+	assert_matches!(
+		client.storage_usage(STORAGE_USAGE_MAX_COUNT + 1, None, None).wait(),
+		Err(Error::InvalidCount { .. })
+	);
+}
+
+#[test]
+fn should_deny_storage_usage_if_unsafe() {
+	let (client, _child) = new_full(
+		Arc::new(substrate_test_runtime_client::new()),
+		SubscriptionManager::new(Arc::new(TaskExecutor)),
+		DenyUnsafe::Yes,
+	);
+
+	assert_matches!(
+		client.storage_usage(1, None, None).wait(),
+		Err(Error::UnsafeRpcCalled(_))
+	);
+}
+
 #[test]
 fn should_return_child_storage() {
 	let child_info = ChildInfo::new_default(STORAGE_KEY);
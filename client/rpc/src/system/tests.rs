@@ -56,6 +56,7 @@ fn api<T: Into<Option<Status>>>(sync: T) -> System<Block> {
 						peers: status.peers,
 						is_syncing: status.is_syncing,
 						should_have_peers,
+						report: None,
 					});
 				},
 				Request::LocalPeerId(sender) => {
@@ -185,6 +186,7 @@ fn system_health() {
 			peers: 0,
 			is_syncing: false,
 			should_have_peers: true,
+			report: None,
 		}
 	);
 
@@ -199,6 +201,7 @@ fn system_health() {
 			peers: 5,
 			is_syncing: true,
 			should_have_peers: false,
+			report: None,
 		}
 	);
 
@@ -213,6 +216,7 @@ fn system_health() {
 			peers: 5,
 			is_syncing: false,
 			should_have_peers: true,
+			report: None,
 		}
 	);
 
@@ -227,6 +231,7 @@ fn system_health() {
 			peers: 0,
 			is_syncing: false,
 			should_have_peers: false,
+			report: None,
 		}
 	);
 }
@@ -638,7 +638,11 @@ pub fn spawn_tasks<TBl, TBackend, TExPool, TRpc, TCl>(
 		let metrics = MetricsService::with_prometheus(telemetry.clone(), &registry, &config)?;
 		spawn_handle.spawn(
 			"prometheus-endpoint",
-			prometheus_endpoint::init_prometheus(port, registry).map(drop)
+			prometheus_endpoint::init_prometheus_with_health(
+				port,
+				registry,
+				Some(Arc::new(metrics.health())),
+			).map(drop)
 		);
 
 		metrics
@@ -652,6 +656,7 @@ pub fn spawn_tasks<TBl, TBackend, TExPool, TRpc, TCl>(
 			client.clone(),
 			transaction_pool.clone(),
 			network.clone(),
+			!config.network.boot_nodes.is_empty(),
 		)
 	);
 
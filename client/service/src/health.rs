@@ -0,0 +1,238 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Node health checks.
+//!
+//! Aggregates sync status, peer count, finality lag, block production recency and
+//! database health into a single [`HealthReport`]. The report is attached to the
+//! `system_health` RPC, exported as Prometheus gauges and backs the liveness and
+//! readiness endpoints served next to the metrics.
+
+use std::{sync::Arc, time::Duration};
+use parking_lot::RwLock;
+use sc_rpc::system::{HealthCheck, HealthReport};
+
+/// Name of the sync check.
+pub const SYNC_CHECK: &str = "sync";
+/// Name of the peer count check.
+pub const PEERS_CHECK: &str = "peers";
+/// Name of the finality lag check.
+pub const FINALITY_CHECK: &str = "finality";
+/// Name of the block production recency check.
+pub const BLOCK_PRODUCTION_CHECK: &str = "block_production";
+/// Name of the database check.
+pub const DATABASE_CHECK: &str = "database";
+
+/// Limits beyond which a node is considered unhealthy.
+#[derive(Debug, Clone)]
+pub struct HealthThresholds {
+	/// Minimum number of connected peers, if the node should have peers at all.
+	pub min_peers: usize,
+	/// Maximum number of blocks the finalized block may lag behind the best block.
+	pub max_finality_lag: u64,
+	/// Maximum time since a new best block was imported.
+	pub max_block_age: Duration,
+}
+
+impl Default for HealthThresholds {
+	fn default() -> Self {
+		HealthThresholds {
+			min_peers: 1,
+			max_finality_lag: 50,
+			max_block_age: Duration::from_secs(120),
+		}
+	}
+}
+
+/// Observations the health checks are run against.
+#[derive(Debug, Clone)]
+pub struct HealthInputs {
+	/// Number of connected peers.
+	pub peers: usize,
+	/// Whether the node is expected to have peers.
+	pub should_have_peers: bool,
+	/// Whether the node is performing a major sync.
+	pub is_syncing: bool,
+	/// Number of the best block.
+	pub best_number: u64,
+	/// Number of the finalized block.
+	pub finalized_number: u64,
+	/// Time since a new best block was imported.
+	pub since_best_import: Duration,
+	/// Whether the database could be read.
+	pub database_ok: bool,
+}
+
+/// Runs all health checks and aggregates their outcome.
+pub fn check_health(inputs: &HealthInputs, thresholds: &HealthThresholds) -> HealthReport {
+	let check = |name: &str, healthy: bool, detail: String| HealthCheck {
+		name: name.into(),
+		healthy,
+		detail,
+	};
+
+	let finality_lag = inputs.best_number.saturating_sub(inputs.finalized_number);
+	let checks = vec![
+		check(
+			SYNC_CHECK,
+			!inputs.is_syncing,
+			if inputs.is_syncing { "major sync in progress" } else { "idle" }.into(),
+		),
+		check(
+			PEERS_CHECK,
+			!inputs.should_have_peers || inputs.peers >= thresholds.min_peers,
+			format!("{} peers, {} required", inputs.peers, if inputs.should_have_peers {
+				thresholds.min_peers
+			} else {
+				0
+			}),
+		),
+		check(
+			FINALITY_CHECK,
+			finality_lag <= thresholds.max_finality_lag,
+			format!("finality lags {} blocks, at most {} allowed", finality_lag, thresholds.max_finality_lag),
+		),
+		check(
+			BLOCK_PRODUCTION_CHECK,
+			inputs.since_best_import <= thresholds.max_block_age,
+			format!(
+				"last best block imported {}s ago, at most {}s allowed",
+				inputs.since_best_import.as_secs(),
+				thresholds.max_block_age.as_secs(),
+			),
+		),
+		check(
+			DATABASE_CHECK,
+			inputs.database_ok,
+			if inputs.database_ok { "ok" } else { "failed to read best header" }.into(),
+		),
+	];
+
+	HealthReport {
+		healthy: checks.iter().all(|c| c.healthy),
+		live: inputs.database_ok,
+		checks,
+	}
+}
+
+/// Latest health report, shared between the task computing it and its consumers.
+#[derive(Debug, Clone, Default)]
+pub struct HealthState(Arc<RwLock<Option<HealthReport>>>);
+
+impl HealthState {
+	/// Replaces the latest report.
+	pub fn set(&self, report: HealthReport) {
+		*self.0.write() = Some(report);
+	}
+
+	/// Returns the latest report, if any has been computed yet.
+	pub fn report(&self) -> Option<HealthReport> {
+		self.0.read().clone()
+	}
+}
+
+impl prometheus_endpoint::HealthProbe for HealthState {
+	fn is_live(&self) -> bool {
+		// Until the first report is in, the node is starting up and alive.
+		self.0.read().as_ref().map_or(true, |report| report.live)
+	}
+
+	fn is_ready(&self) -> bool {
+		self.0.read().as_ref().map_or(false, |report| report.healthy)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use prometheus_endpoint::HealthProbe;
+
+	fn healthy_inputs() -> HealthInputs {
+		HealthInputs {
+			peers: 3,
+			should_have_peers: true,
+			is_syncing: false,
+			best_number: 100,
+			finalized_number: 98,
+			since_best_import: Duration::from_secs(6),
+			database_ok: true,
+		}
+	}
+
+	fn failing(report: &HealthReport) -> Vec<&str> {
+		report.checks.iter().filter(|c| !c.healthy).map(|c| c.name.as_str()).collect()
+	}
+
+	#[test]
+	fn healthy_node_passes_all_checks() {
+		let report = check_health(&healthy_inputs(), &Default::default());
+		assert!(report.healthy);
+		assert!(report.live);
+		assert_eq!(report.checks.len(), 5);
+	}
+
+	#[test]
+	fn reports_failing_checks() {
+		let inputs = HealthInputs {
+			peers: 0,
+			is_syncing: true,
+			finalized_number: 10,
+			since_best_import: Duration::from_secs(600),
+			..healthy_inputs()
+		};
+		let report = check_health(&inputs, &Default::default());
+		assert!(!report.healthy);
+		assert!(report.live);
+		assert_eq!(
+			failing(&report),
+			vec![SYNC_CHECK, PEERS_CHECK, FINALITY_CHECK, BLOCK_PRODUCTION_CHECK],
+		);
+	}
+
+	#[test]
+	fn peers_not_required_without_discovery() {
+		let inputs = HealthInputs { peers: 0, should_have_peers: false, ..healthy_inputs() };
+		assert!(check_health(&inputs, &Default::default()).healthy);
+	}
+
+	#[test]
+	fn database_failure_is_not_live() {
+		let inputs = HealthInputs { database_ok: false, ..healthy_inputs() };
+		let report = check_health(&inputs, &Default::default());
+		assert!(!report.live);
+		assert_eq!(failing(&report), vec![DATABASE_CHECK]);
+	}
+
+	#[test]
+	fn probe_follows_latest_report() {
+		let state = HealthState::default();
+		assert!(state.is_live());
+		assert!(!state.is_ready());
+
+		state.set(check_health(&healthy_inputs(), &Default::default()));
+		assert!(state.is_live());
+		assert!(state.is_ready());
+
+		state.set(check_health(
+			&HealthInputs { database_ok: false, ..healthy_inputs() },
+			&Default::default(),
+		));
+		assert!(!state.is_live());
+		assert!(!state.is_ready());
+	}
+}
@@ -25,6 +25,7 @@
 pub mod config;
 pub mod chain_ops;
 pub mod error;
+pub mod health;
 
 mod metrics;
 mod builder;
@@ -44,7 +45,7 @@ use sc_network::PeerId;
 use log::{warn, debug, error};
 use codec::{Encode, Decode};
 use sp_runtime::generic::BlockId;
-use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT, SaturatedConversion};
 use parity_util_mem::MallocSizeOf;
 use sp_utils::mpsc::TracingUnboundedReceiver;
 
@@ -163,6 +164,10 @@ async fn build_network_future<
 	// Current best block at initialization, to report to the RPC layer.
 	let starting_block = client.info().best_number;
 
+	// When the last new best block was imported, for the health checks.
+	let mut last_best_import = wasm_timer::Instant::now();
+	let health_thresholds = health::HealthThresholds::default();
+
 	// Stream of finalized blocks reported by the client.
 	let mut finality_notification_stream = {
 		let mut finality_notification_stream = client.finality_notification_stream().fuse();
@@ -198,6 +203,7 @@ async fn build_network_future<
 				}
 
 				if notification.is_new_best {
+					last_best_import = wasm_timer::Instant::now();
 					network.service().new_best_block_imported(
 						notification.hash,
 						notification.header.number().clone(),
@@ -214,10 +220,24 @@ async fn build_network_future<
 			request = rpc_rx.select_next_some() => {
 				match request {
 					sc_rpc::system::Request::Health(sender) => {
+						let peers = network.peers_debug_info().len();
+						let is_syncing = network.service().is_major_syncing();
+						let info = client.info();
+						let report = health::check_health(&health::HealthInputs {
+							peers,
+							should_have_peers,
+							is_syncing,
+							best_number: info.best_number.saturated_into(),
+							finalized_number: info.finalized_number.saturated_into(),
+							since_best_import: last_best_import.elapsed(),
+							database_ok: matches!(client.header(BlockId::Hash(info.best_hash)), Ok(Some(_))),
+						}, &health_thresholds);
+
 						let _ = sender.send(sc_rpc::system::Health {
-							peers: network.peers_debug_info().len(),
-							is_syncing: network.service().is_major_syncing(),
+							peers,
+							is_syncing,
 							should_have_peers,
+							report: Some(report),
 						});
 					},
 					sc_rpc::system::Request::LocalPeerId(sender) => {
@@ -19,15 +19,19 @@
 use std::{convert::TryFrom, time::SystemTime};
 
 use crate::config::Configuration;
+use crate::health::{check_health, HealthInputs, HealthState, HealthThresholds};
 use futures_timer::Delay;
 use prometheus_endpoint::{register, Gauge, U64, Registry, PrometheusError, Opts, GaugeVec};
 use sc_telemetry::{telemetry, TelemetryHandle, SUBSTRATE_INFO};
 use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::generic::BlockId;
 use sp_runtime::traits::{NumberFor, Block, SaturatedConversion, UniqueSaturatedInto};
 use sp_transaction_pool::{PoolStatus, MaintainedTransactionPool};
 use sp_utils::metrics::register_globals;
 use sc_client_api::{ClientInfo, UsageProvider};
-use sc_network::{config::Role, NetworkStatus, NetworkService, network_state::NetworkState};
+use sc_network::{config::Role, NetworkStatus, NetworkService, SyncState, network_state::NetworkState};
+use sc_rpc::system::HealthReport;
 use std::sync::Arc;
 use std::time::Duration;
 use wasm_timer::Instant;
@@ -42,6 +46,10 @@ struct PrometheusMetrics {
 	database_cache: Gauge<U64>,
 	state_cache: Gauge<U64>,
 	state_db: GaugeVec<U64>,
+
+	// health
+	health_check: GaugeVec<U64>,
+	health_status: Gauge<U64>,
 }
 
 impl PrometheusMetrics {
@@ -99,6 +107,15 @@ impl PrometheusMetrics {
 				Opts::new("state_db_cache_bytes", "State DB cache in bytes"),
 				&["subtype"]
 			)?, registry)?,
+
+			// health
+			health_check: register(GaugeVec::new(
+				Opts::new("health_check", "Outcome of a health check, 1 if it passed"),
+				&["check"]
+			)?, registry)?,
+			health_status: register(Gauge::new(
+				"health_status", "1 if all health checks passed",
+			)?, registry)?,
 		})
 	}
 }
@@ -111,6 +128,9 @@ pub struct MetricsService {
 	last_update: Instant,
 	last_total_bytes_inbound: u64,
 	last_total_bytes_outbound: u64,
+	last_best: Option<(u64, Instant)>,
+	health: HealthState,
+	health_thresholds: HealthThresholds,
 	telemetry: Option<TelemetryHandle>,
 }
 
@@ -123,6 +143,9 @@ impl MetricsService {
 			last_total_bytes_inbound: 0,
 			last_total_bytes_outbound: 0,
 			last_update: Instant::now(),
+			last_best: None,
+			health: Default::default(),
+			health_thresholds: Default::default(),
 			telemetry,
 		}
 	}
@@ -151,10 +174,18 @@ impl MetricsService {
 			last_total_bytes_inbound: 0,
 			last_total_bytes_outbound: 0,
 			last_update: Instant::now(),
+			last_best: None,
+			health: Default::default(),
+			health_thresholds: Default::default(),
 			telemetry,
 		})
 	}
 
+	/// Returns the health state updated on every tick.
+	pub fn health(&self) -> HealthState {
+		self.health.clone()
+	}
+
 	/// Returns a never-ending `Future` that performs the
 	/// metric and telemetry updates with information from
 	/// the given sources.
@@ -163,9 +194,10 @@ impl MetricsService {
 		client: Arc<TCl>,
 		transactions: Arc<TExPool>,
 		network: Arc<NetworkService<TBl, <TBl as Block>::Hash>>,
+		should_have_peers: bool,
 	) where
 		TBl: Block,
-		TCl: ProvideRuntimeApi<TBl> + UsageProvider<TBl>,
+		TCl: ProvideRuntimeApi<TBl> + UsageProvider<TBl> + HeaderBackend<TBl>,
 		TExPool: MaintainedTransactionPool<Block = TBl, Hash = <TBl as Block>::Hash>,
 	{
 		let mut timer = Delay::new(Duration::from_secs(0));
@@ -189,9 +221,16 @@ impl MetricsService {
 				None
 			};
 
+			let info = client.usage_info();
+			let database_ok = matches!(
+				client.header(BlockId::Hash(info.chain.best_hash)),
+				Ok(Some(_)),
+			);
+			self.update_health(&info, net_status.as_ref(), should_have_peers, database_ok);
+
 			// Update / Send the metrics.
 			self.update(
-				&client.usage_info(),
+				&info,
 				&transactions.status(),
 				net_status,
 				net_state,
@@ -202,6 +241,37 @@ impl MetricsService {
 		}
 	}
 
+	fn update_health<T: Block>(
+		&mut self,
+		info: &ClientInfo<T>,
+		net_status: Option<&NetworkStatus<T>>,
+		should_have_peers: bool,
+		database_ok: bool,
+	) {
+		let now = Instant::now();
+		let best_number = info.chain.best_number.saturated_into::<u64>();
+		let last_best = match self.last_best {
+			Some((number, at)) if number == best_number => at,
+			_ => now,
+		};
+		self.last_best = Some((best_number, last_best));
+
+		let report = check_health(&HealthInputs {
+			peers: net_status.map_or(0, |status| status.num_connected_peers),
+			should_have_peers,
+			is_syncing: net_status.map_or(false, |status| status.sync_state == SyncState::Downloading),
+			best_number,
+			finalized_number: info.chain.finalized_number.saturated_into::<u64>(),
+			since_best_import: now.duration_since(last_best),
+			database_ok,
+		}, &self.health_thresholds);
+
+		if let Some(metrics) = self.metrics.as_ref() {
+			set_health_metrics(metrics, &report);
+		}
+		self.health.set(report);
+	}
+
 	fn update<T: Block>(
 		&mut self,
 		info: &ClientInfo<T>,
@@ -312,3 +382,10 @@ impl MetricsService {
 		}
 	}
 }
+
+fn set_health_metrics(metrics: &PrometheusMetrics, report: &HealthReport) {
+	for check in &report.checks {
+		metrics.health_check.with_label_values(&[&check.name]).set(check.healthy as u64);
+	}
+	metrics.health_status.set(report.healthy as u64);
+}
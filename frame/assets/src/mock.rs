@@ -80,6 +80,10 @@ impl pallet_balances::Config for Test {
 	type AccountStore = System;
 	type WeightInfo = ();
 	type MaxLocks = ();
+	type HoldIdentifier = ();
+	type FreezeIdentifier = ();
+	type MaxHolds = frame_support::traits::ConstU32<1>;
+	type MaxFreezes = frame_support::traits::ConstU32<1>;
 }
 
 parameter_types! {
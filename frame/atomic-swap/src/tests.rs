@@ -60,6 +60,10 @@ parameter_types! {
 }
 impl pallet_balances::Config for Test {
 	type MaxLocks = ();
+	type HoldIdentifier = ();
+	type FreezeIdentifier = ();
+	type MaxHolds = frame_support::traits::ConstU32<1>;
+	type MaxFreezes = frame_support::traits::ConstU32<1>;
 	type Balance = u64;
 	type DustRemoval = ();
 	type Event = Event;
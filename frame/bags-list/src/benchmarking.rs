@@ -0,0 +1,70 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bags List pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{benchmarks, account, whitelisted_caller, impl_benchmark_test_suite};
+use frame_support::traits::Get;
+use frame_system::RawOrigin;
+
+use crate::Pallet as BagsList;
+
+const SEED: u32 = 0;
+
+fn insert<T: Config>(
+	name: &'static str,
+	weight: VoteWeight,
+) -> Result<T::AccountId, &'static str> {
+	let who: T::AccountId = account(name, 0, SEED);
+	List::<T>::insert(who.clone(), weight).map_err(|_| "the voter is in the list already")?;
+	Ok(who)
+}
+
+benchmarks! {
+	// The worst case: the voter has neighbours in its old bag, which both need to be updated,
+	// and the new bag has a tail already.
+	rebag {
+		List::<T>::clear();
+		let thresholds = T::BagThresholds::get();
+		let origin_bag = *thresholds.get(0).ok_or("there must be at least two bags")?;
+		let dest_bag = *thresholds.get(1).ok_or("there must be at least two bags")?;
+
+		let origin_head = insert::<T>("origin_head", origin_bag)?;
+		let origin_middle = insert::<T>("origin_middle", origin_bag)?;
+		let origin_tail = insert::<T>("origin_tail", origin_bag)?;
+		let dest_head = insert::<T>("dest_head", dest_bag)?;
+
+		T::VoteWeightProvider::set_vote_weight_of(&origin_middle, dest_bag);
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), origin_middle.clone())
+	verify {
+		let bag_of = |bag_upper| list::Bag::<T>::get(bag_upper)
+			.map(|bag| bag.iter().map(|node| node.id).collect::<Vec<_>>())
+			.unwrap_or_default();
+		assert_eq!(bag_of(origin_bag), vec![origin_head, origin_tail]);
+		assert_eq!(bag_of(dest_bag), vec![dest_head, origin_middle]);
+	}
+}
+
+impl_benchmark_test_suite!(
+	BagsList,
+	crate::mock::new_test_ext(),
+	crate::mock::Test,
+);
@@ -0,0 +1,206 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Bags List Pallet
+//!
+//! A semi-sorted list of voters, where the voters are kept in bags according to their vote
+//! weight.
+//!
+//! ## Overview
+//!
+//! Each bag covers a range of vote weights: the bag with the upper threshold `u` contains the
+//! voters whose vote weight is at most `u`, and above the threshold of the bag below. The
+//! thresholds are the [`Config::BagThresholds`], plus `VoteWeight::MAX` for the top bag.
+//!
+//! Within a bag, the voters are kept in insertion order in a doubly linked list. Inserting, moving
+//! or removing a voter is therefore O(1), and iterating the list from the highest bag to the
+//! lowest yields the voters sorted by bag, which is good enough to select the best voters of an
+//! election without sorting all of them.
+//!
+//! ## Usage
+//!
+//! The pallet implements [`SortedListProvider`], e.g. to be used by the staking pallet for its
+//! nominators. The owner of the voters must report all their changes through the hooks of the
+//! trait. If the vote weight of a voter changes without being reported, e.g. because it was
+//! slashed or rewarded, anyone can put it back in its correct bag with `rebag`.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! - `rebag`: Move a voter to the bag matching its current vote weight.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_std::prelude::*;
+use frame_election_provider_support::{VoteWeight, VoteWeightProvider, SortedListProvider};
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+mod benchmarking;
+pub mod list;
+pub mod weights;
+
+use list::List;
+
+pub(crate) const LOG_TARGET: &'static str = "runtime::bags_list";
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+
+		/// Something that provides the current vote weight of the voters.
+		type VoteWeightProvider: VoteWeightProvider<Self::AccountId>;
+
+		/// The upper thresholds of the bags, in strictly increasing order.
+		///
+		/// A bag above the highest threshold, with the upper threshold `VoteWeight::MAX`, is
+		/// implied. With no thresholds at all, every voter is in that single bag and the list is
+		/// not sorted at all.
+		///
+		/// The thresholds should grow geometrically, so that each bag covers a similar proportion
+		/// of its vote weights. Changing them requires regenerating the list, as the existing
+		/// voters are not moved to the new bags.
+		#[pallet::constant]
+		type BagThresholds: Get<&'static [VoteWeight]>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The voters of the list, by account.
+	#[pallet::storage]
+	pub type ListNodes<T: Config> =
+		CountedStorageMap<_, Twox64Concat, T::AccountId, list::Node<T>>;
+
+	/// The non-empty bags of the list, by upper threshold.
+	#[pallet::storage]
+	pub type ListBags<T: Config> = StorageMap<_, Twox64Concat, VoteWeight, list::Bag<T>>;
+
+	#[pallet::event]
+	#[pallet::metadata(T::AccountId = "AccountId")]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A voter was moved to another bag. \[who, from, to\]
+		Rebagged(T::AccountId, VoteWeight, VoteWeight),
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn integrity_test() {
+			assert!(
+				T::BagThresholds::get().windows(2).all(|pair| pair[0] < pair[1]),
+				"the bag thresholds must be strictly increasing",
+			);
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Move `dislocated` to the bag matching its current vote weight.
+		///
+		/// Does nothing if `dislocated` is already in the right bag, or is not in the list.
+		///
+		/// The dispatch origin for this call must be _Signed_, by anyone.
+		///
+		/// Emits `Rebagged` if `dislocated` was moved.
+		#[pallet::weight(T::WeightInfo::rebag())]
+		pub fn rebag(origin: OriginFor<T>, dislocated: T::AccountId) -> DispatchResult {
+			ensure_signed(origin)?;
+			let weight = T::VoteWeightProvider::vote_weight(&dislocated);
+			Self::do_rebag(&dislocated, weight);
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Move `account` to the bag of `new_weight`, if it is in the list and not in that bag yet.
+	///
+	/// Returns the upper thresholds of the old and the new bag if `account` was moved.
+	pub fn do_rebag(
+		account: &T::AccountId,
+		new_weight: VoteWeight,
+	) -> Option<(VoteWeight, VoteWeight)> {
+		let moved = list::Node::<T>::get(account)
+			.and_then(|node| List::<T>::update_position_for(node, new_weight));
+		if let Some((from, to)) = moved {
+			Self::deposit_event(Event::<T>::Rebagged(account.clone(), from, to));
+		}
+		moved
+	}
+}
+
+impl<T: Config> SortedListProvider<T::AccountId> for Pallet<T> {
+	type Error = list::Error;
+
+	fn iter() -> Box<dyn Iterator<Item = T::AccountId>> {
+		Box::new(List::<T>::iter().map(|node| node.id))
+	}
+
+	fn count() -> u32 {
+		ListNodes::<T>::count()
+	}
+
+	fn contains(id: &T::AccountId) -> bool {
+		ListNodes::<T>::contains_key(id)
+	}
+
+	fn on_insert(id: T::AccountId, weight: VoteWeight) -> Result<(), Self::Error> {
+		List::<T>::insert(id, weight)
+	}
+
+	fn on_update(id: &T::AccountId, weight: VoteWeight) {
+		Self::do_rebag(id, weight);
+	}
+
+	fn on_remove(id: &T::AccountId) {
+		List::<T>::remove(id)
+	}
+
+	fn regenerate(
+		all: impl IntoIterator<Item = T::AccountId>,
+		weight_of: Box<dyn Fn(&T::AccountId) -> VoteWeight>,
+	) -> u32 {
+		let inserted = List::<T>::regenerate(all, weight_of);
+		log::info!(target: LOG_TARGET, "regenerated the list with {} voters", inserted);
+		inserted
+	}
+
+	fn clear() {
+		List::<T>::clear()
+	}
+
+	fn sanity_check() -> Result<(), &'static str> {
+		List::<T>::sanity_check()
+	}
+}
@@ -0,0 +1,300 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The list of voters and its bags, stored in the `ListNodes` and `ListBags` maps.
+//!
+//! Every voter is a [`Node`] of a doubly linked list of the [`Bag`] covering its vote weight. The
+//! functions of [`List`] keep the pointers of the nodes, the head and tail of the bags and the
+//! node counter consistent.
+
+use sp_std::{iter, marker::PhantomData, prelude::*};
+use codec::{Encode, Decode};
+use sp_runtime::RuntimeDebug;
+use frame_support::{
+	traits::Get, CloneNoBound, DefaultNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+};
+use frame_election_provider_support::VoteWeight;
+use crate::{Config, ListNodes, ListBags};
+
+/// An error of the list.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum Error {
+	/// The voter is already in the list.
+	Duplicate,
+}
+
+/// The upper threshold of the bag in which a voter with the given `weight` belongs.
+///
+/// This is the lowest threshold of `T::BagThresholds` which is at least `weight`, or
+/// `VoteWeight::MAX` if `weight` is above all the thresholds.
+pub fn notional_bag_for<T: Config>(weight: VoteWeight) -> VoteWeight {
+	T::BagThresholds::get()
+		.iter()
+		.copied()
+		.find(|&threshold| weight <= threshold)
+		.unwrap_or(VoteWeight::MAX)
+}
+
+/// The upper thresholds of all the bags, from the highest to the lowest.
+fn bag_uppers<T: Config>() -> impl Iterator<Item = VoteWeight> {
+	let thresholds = T::BagThresholds::get();
+	// the bag above the highest threshold only exists if that threshold isn't the maximum.
+	let top = match thresholds.last() {
+		Some(&VoteWeight::MAX) => None,
+		_ => Some(VoteWeight::MAX),
+	};
+	top.into_iter().chain(thresholds.iter().rev().copied())
+}
+
+/// A voter of the list.
+#[derive(Encode, Decode, CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound)]
+pub struct Node<T: Config> {
+	/// The voter.
+	pub id: T::AccountId,
+	/// The previous voter in the same bag.
+	pub prev: Option<T::AccountId>,
+	/// The next voter in the same bag.
+	pub next: Option<T::AccountId>,
+	/// The upper threshold of the bag of the voter.
+	pub bag_upper: VoteWeight,
+}
+
+impl<T: Config> Node<T> {
+	/// Get the node of `id`.
+	pub fn get(id: &T::AccountId) -> Option<Self> {
+		ListNodes::<T>::get(id)
+	}
+
+	/// Write the node to storage.
+	fn put(self) {
+		ListNodes::<T>::insert(self.id.clone(), self);
+	}
+
+	/// The previous node in the same bag.
+	fn prev(&self) -> Option<Self> {
+		self.prev.as_ref().and_then(Self::get)
+	}
+
+	/// The next node in the same bag.
+	fn next(&self) -> Option<Self> {
+		self.next.as_ref().and_then(Self::get)
+	}
+
+	/// Returns true if a voter with `weight` does not belong in the bag of this node.
+	pub fn is_misplaced(&self, weight: VoteWeight) -> bool {
+		notional_bag_for::<T>(weight) != self.bag_upper
+	}
+
+	/// Unlink the node from its neighbours, without touching the bag.
+	///
+	/// The node itself is not written back.
+	fn excise(&self) {
+		if let Some(mut prev) = self.prev() {
+			prev.next = self.next.clone();
+			prev.put();
+		}
+		if let Some(mut next) = self.next() {
+			next.prev = self.prev.clone();
+			next.put();
+		}
+	}
+}
+
+/// A bag of voters, i.e. the head and tail of the linked list of the voters whose vote weight
+/// falls below its upper threshold and above the threshold of the bag below.
+#[derive(
+	Encode, Decode, CloneNoBound, DefaultNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound,
+)]
+pub struct Bag<T: Config> {
+	/// The first voter of the bag.
+	pub head: Option<T::AccountId>,
+	/// The last voter of the bag.
+	pub tail: Option<T::AccountId>,
+	/// The upper threshold of the bag. This is the key of the bag and is not stored.
+	#[codec(skip)]
+	pub bag_upper: VoteWeight,
+}
+
+impl<T: Config> Bag<T> {
+	/// Get the bag with the upper threshold `bag_upper`, if it has any voter.
+	pub fn get(bag_upper: VoteWeight) -> Option<Self> {
+		ListBags::<T>::get(bag_upper).map(|bag| Self { bag_upper, ..bag })
+	}
+
+	/// Get the bag with the upper threshold `bag_upper`, or an empty bag.
+	fn get_or_make(bag_upper: VoteWeight) -> Self {
+		Self::get(bag_upper).unwrap_or(Self { head: None, tail: None, bag_upper })
+	}
+
+	/// Write the bag to storage, removing it if it is empty.
+	fn put(self) {
+		if self.head.is_none() && self.tail.is_none() {
+			ListBags::<T>::remove(self.bag_upper);
+		} else {
+			ListBags::<T>::insert(self.bag_upper, self);
+		}
+	}
+
+	/// Iterate over the nodes of the bag, from the head to the tail.
+	pub fn iter(&self) -> impl Iterator<Item = Node<T>> {
+		iter::successors(self.head.as_ref().and_then(Node::<T>::get), |node| node.next())
+	}
+
+	/// Append `id` at the tail of the bag.
+	///
+	/// The bag itself is not written back, and `id` must not be in the list already.
+	fn insert_unchecked(&mut self, id: T::AccountId) {
+		if let Some(mut tail) = self.tail.as_ref().and_then(Node::<T>::get) {
+			tail.next = Some(id.clone());
+			tail.put();
+		}
+		let node = Node::<T> {
+			id: id.clone(),
+			prev: self.tail.clone(),
+			next: None,
+			bag_upper: self.bag_upper,
+		};
+		node.put();
+
+		if self.head.is_none() {
+			self.head = Some(id.clone());
+		}
+		self.tail = Some(id);
+	}
+
+	/// Remove `node` from the bag.
+	///
+	/// Neither the bag nor the node are written back, and `node` must belong to this bag.
+	fn remove_unchecked(&mut self, node: &Node<T>) {
+		node.excise();
+		if self.head.as_ref() == Some(&node.id) {
+			self.head = node.next.clone();
+		}
+		if self.tail.as_ref() == Some(&node.id) {
+			self.tail = node.prev.clone();
+		}
+	}
+}
+
+/// The list of all the voters, across all the bags.
+pub struct List<T>(PhantomData<T>);
+
+impl<T: Config> List<T> {
+	/// Iterate over the voters, from the highest bag to the lowest.
+	///
+	/// Within a bag, the voters are iterated in insertion order.
+	pub fn iter() -> impl Iterator<Item = Node<T>> {
+		bag_uppers::<T>()
+			.filter_map(Bag::<T>::get)
+			.flat_map(|bag| bag.iter())
+	}
+
+	/// Insert `id` with `weight` at the tail of its bag.
+	pub fn insert(id: T::AccountId, weight: VoteWeight) -> Result<(), Error> {
+		if ListNodes::<T>::contains_key(&id) {
+			return Err(Error::Duplicate);
+		}
+
+		let mut bag = Bag::<T>::get_or_make(notional_bag_for::<T>(weight));
+		bag.insert_unchecked(id);
+		bag.put();
+		Ok(())
+	}
+
+	/// Remove `id` from the list. Does nothing if `id` is not in the list.
+	pub fn remove(id: &T::AccountId) {
+		if let Some(node) = Node::<T>::get(id) {
+			let mut bag = Bag::<T>::get_or_make(node.bag_upper);
+			bag.remove_unchecked(&node);
+			bag.put();
+			ListNodes::<T>::remove(id);
+		}
+	}
+
+	/// Move `node` to the tail of the bag of `new_weight`, if it doesn't belong in its bag anymore.
+	///
+	/// Returns the upper thresholds of the old and the new bag if the node was moved.
+	pub fn update_position_for(
+		node: Node<T>,
+		new_weight: VoteWeight,
+	) -> Option<(VoteWeight, VoteWeight)> {
+		if !node.is_misplaced(new_weight) {
+			return None;
+		}
+
+		let old_bag_upper = node.bag_upper;
+		let mut old_bag = Bag::<T>::get_or_make(old_bag_upper);
+		old_bag.remove_unchecked(&node);
+		old_bag.put();
+
+		let new_bag_upper = notional_bag_for::<T>(new_weight);
+		let mut new_bag = Bag::<T>::get_or_make(new_bag_upper);
+		new_bag.insert_unchecked(node.id);
+		new_bag.put();
+
+		Some((old_bag_upper, new_bag_upper))
+	}
+
+	/// Remove all the voters and bags.
+	pub fn clear() {
+		ListNodes::<T>::remove_all();
+		ListBags::<T>::remove_all();
+	}
+
+	/// Clear the list and insert all the voters of `all`, weighted by `weight_of`.
+	///
+	/// Returns the number of voters inserted, duplicates being skipped.
+	pub fn regenerate(
+		all: impl IntoIterator<Item = T::AccountId>,
+		weight_of: Box<dyn Fn(&T::AccountId) -> VoteWeight>,
+	) -> u32 {
+		Self::clear();
+		all.into_iter()
+			.filter(|id| Self::insert(id.clone(), weight_of(id)).is_ok())
+			.count() as u32
+	}
+
+	/// Check that the bags link all the nodes of the list, each in the bag it claims, and that the
+	/// node counter is correct.
+	pub fn sanity_check() -> Result<(), &'static str> {
+		let mut count = 0u32;
+		for bag in bag_uppers::<T>().filter_map(Bag::<T>::get) {
+			let mut last = None;
+			for node in bag.iter() {
+				if node.bag_upper != bag.bag_upper {
+					return Err("a node is linked from the wrong bag");
+				}
+				if node.prev != last {
+					return Err("a node doesn't point to the previous node of its bag");
+				}
+				last = Some(node.id);
+				count = count.saturating_add(1);
+			}
+			if bag.tail != last {
+				return Err("a bag doesn't point to its last node");
+			}
+		}
+
+		if count != ListNodes::<T>::count() {
+			return Err("the node counter doesn't match the number of linked nodes");
+		}
+		if count as usize != ListNodes::<T>::iter().count() {
+			return Err("some nodes aren't linked from any bag");
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,133 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test utilities
+
+use std::collections::BTreeMap;
+
+use super::*;
+use crate as pallet_bags_list;
+
+use frame_support::parameter_types;
+use sp_core::H256;
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup}, testing::Header,
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		BagsList: pallet_bags_list::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Call = Call;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+parameter_types! {
+	pub static VoteWeights: BTreeMap<u64, VoteWeight> = BTreeMap::new();
+	pub const BagThresholds: &'static [VoteWeight] =
+		&[10, 20, 30, 40, 50, 60, 1_000, 2_000, 10_000];
+}
+
+/// Provides the vote weights set with [`set_vote_weight`].
+pub struct StakingMock;
+impl VoteWeightProvider<u64> for StakingMock {
+	fn vote_weight(who: &u64) -> VoteWeight {
+		VoteWeights::get().get(who).copied().unwrap_or_default()
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_vote_weight_of(who: &u64, weight: VoteWeight) {
+		set_vote_weight(*who, weight);
+	}
+}
+
+pub fn set_vote_weight(who: u64, weight: VoteWeight) {
+	let mut weights = VoteWeights::get();
+	weights.insert(who, weight);
+	VoteWeights::set(weights);
+}
+
+impl Config for Test {
+	type Event = Event;
+	type WeightInfo = ();
+	type VoteWeightProvider = StakingMock;
+	type BagThresholds = BagThresholds;
+}
+
+/// The voters of the list at genesis, with their vote weight.
+pub const GENESIS_VOTERS: [(u64, VoteWeight); 4] = [(1, 10), (2, 1_000), (3, 1_000), (4, 1_000)];
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		VoteWeights::set(GENESIS_VOTERS.iter().copied().collect());
+		for (who, weight) in GENESIS_VOTERS.iter() {
+			assert_eq!(List::<Test>::insert(*who, *weight), Ok(()));
+		}
+	});
+	ext
+}
+
+/// The voters of the list, in iteration order.
+pub fn list_as_ids() -> Vec<u64> {
+	List::<Test>::iter().map(|node| node.id).collect()
+}
+
+/// The voters of the bag with the upper threshold `bag_upper`, in iteration order.
+pub fn bag_as_ids(bag_upper: VoteWeight) -> Vec<u64> {
+	list::Bag::<Test>::get(bag_upper)
+		.map(|bag| bag.iter().map(|node| node.id).collect())
+		.unwrap_or_default()
+}
@@ -0,0 +1,188 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the Bags List pallet.
+
+use super::*;
+use crate::mock::*;
+use frame_support::{assert_ok, traits::Hooks};
+
+#[test]
+fn notional_bag_for_works() {
+	assert_eq!(list::notional_bag_for::<Test>(0), 10);
+	assert_eq!(list::notional_bag_for::<Test>(10), 10);
+	assert_eq!(list::notional_bag_for::<Test>(11), 20);
+	assert_eq!(list::notional_bag_for::<Test>(61), 1_000);
+	assert_eq!(list::notional_bag_for::<Test>(10_000), 10_000);
+	assert_eq!(list::notional_bag_for::<Test>(10_001), VoteWeight::MAX);
+	assert_eq!(list::notional_bag_for::<Test>(VoteWeight::MAX), VoteWeight::MAX);
+}
+
+#[test]
+fn bag_thresholds_are_checked() {
+	new_test_ext().execute_with(|| {
+		<BagsList as Hooks<u64>>::integrity_test();
+	});
+}
+
+#[test]
+fn genesis_voters_are_in_their_bags() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(bag_as_ids(10), vec![1]);
+		assert_eq!(bag_as_ids(1_000), vec![2, 3, 4]);
+		assert_eq!(ListBags::<Test>::iter().count(), 2);
+
+		assert_eq!(BagsList::count(), 4);
+		assert!(BagsList::contains(&2));
+		assert!(!BagsList::contains(&5));
+		assert_eq!(BagsList::sanity_check(), Ok(()));
+	});
+}
+
+#[test]
+fn iteration_goes_from_the_highest_bag_to_the_lowest() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(list_as_ids(), vec![2, 3, 4, 1]);
+
+		assert_ok!(BagsList::on_insert(5, 15));
+		assert_ok!(BagsList::on_insert(6, 20_000));
+		assert_ok!(BagsList::on_insert(7, 5));
+
+		assert_eq!(bag_as_ids(VoteWeight::MAX), vec![6]);
+		assert_eq!(BagsList::iter().collect::<Vec<_>>(), vec![6, 2, 3, 4, 5, 1, 7]);
+		assert_eq!(BagsList::sanity_check(), Ok(()));
+	});
+}
+
+#[test]
+fn duplicates_cannot_be_inserted() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(BagsList::on_insert(2, 20), Err(list::Error::Duplicate));
+		assert_eq!(bag_as_ids(1_000), vec![2, 3, 4]);
+		assert_eq!(BagsList::count(), 4);
+	});
+}
+
+#[test]
+fn remove_relinks_the_bag() {
+	new_test_ext().execute_with(|| {
+		// from the middle.
+		BagsList::on_remove(&3);
+		assert_eq!(bag_as_ids(1_000), vec![2, 4]);
+		assert_eq!(list::Node::<Test>::get(&2).unwrap().next, Some(4));
+		assert_eq!(list::Node::<Test>::get(&4).unwrap().prev, Some(2));
+
+		// from the head.
+		BagsList::on_remove(&2);
+		assert_eq!(bag_as_ids(1_000), vec![4]);
+		assert_eq!(list::Node::<Test>::get(&4).unwrap().prev, None);
+
+		// the last voter of a bag removes the bag.
+		BagsList::on_remove(&4);
+		assert!(!ListBags::<Test>::contains_key(1_000));
+
+		// removing a voter which isn't in the list is a no-op.
+		BagsList::on_remove(&42);
+
+		assert_eq!(list_as_ids(), vec![1]);
+		assert_eq!(BagsList::count(), 1);
+		assert!(!ListNodes::<Test>::contains_key(&3));
+		assert_eq!(BagsList::sanity_check(), Ok(()));
+	});
+}
+
+#[test]
+fn on_update_moves_voters_between_bags() {
+	new_test_ext().execute_with(|| {
+		// still in the same bag.
+		BagsList::on_update(&2, 999);
+		assert_eq!(bag_as_ids(1_000), vec![2, 3, 4]);
+		assert!(System::events().is_empty());
+
+		// moved at the tail of the new bag.
+		BagsList::on_update(&2, 5);
+		assert_eq!(bag_as_ids(1_000), vec![3, 4]);
+		assert_eq!(bag_as_ids(10), vec![1, 2]);
+		let event = crate::Event::Rebagged(2, 1_000, 10);
+		System::assert_last_event(mock::Event::pallet_bags_list(event));
+
+		BagsList::on_update(&4, 2_000);
+		assert_eq!(list_as_ids(), vec![4, 3, 1, 2]);
+		assert_eq!(BagsList::sanity_check(), Ok(()));
+	});
+}
+
+#[test]
+fn rebag_uses_the_current_vote_weight() {
+	new_test_ext().execute_with(|| {
+		// nothing to do.
+		assert_ok!(BagsList::rebag(Origin::signed(42), 3));
+		assert!(System::events().is_empty());
+
+		set_vote_weight(3, 25);
+		assert_ok!(BagsList::rebag(Origin::signed(42), 3));
+		assert_eq!(bag_as_ids(30), vec![3]);
+		assert_eq!(list::Node::<Test>::get(&3).unwrap().bag_upper, 30);
+		let event = crate::Event::Rebagged(3, 1_000, 30);
+		System::assert_last_event(mock::Event::pallet_bags_list(event));
+
+		// voters which aren't in the list are ignored.
+		set_vote_weight(5, 25);
+		assert_ok!(BagsList::rebag(Origin::signed(42), 5));
+		assert!(!BagsList::contains(&5));
+		assert_eq!(System::events().len(), 1);
+
+		assert_eq!(list_as_ids(), vec![2, 4, 3, 1]);
+		assert_eq!(BagsList::sanity_check(), Ok(()));
+	});
+}
+
+#[test]
+fn regenerate_replaces_the_list() {
+	new_test_ext().execute_with(|| {
+		let inserted = BagsList::regenerate(vec![5, 6, 5], Box::new(|who: &u64| *who * 100));
+		assert_eq!(inserted, 2);
+
+		assert_eq!(list_as_ids(), vec![5, 6]);
+		assert_eq!(bag_as_ids(1_000), vec![5, 6]);
+		assert_eq!(BagsList::count(), 2);
+		assert!(!BagsList::contains(&1));
+		assert_eq!(BagsList::sanity_check(), Ok(()));
+
+		BagsList::clear();
+		assert_eq!(BagsList::count(), 0);
+		assert_eq!(BagsList::iter().count(), 0);
+		assert_eq!(ListBags::<Test>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn sanity_check_detects_inconsistencies() {
+	new_test_ext().execute_with(|| {
+		let counter = ListNodes::<Test>::counter_storage_final_key();
+		frame_support::storage::unhashed::put(&counter, &5u32);
+		assert!(BagsList::sanity_check().is_err());
+		frame_support::storage::unhashed::put(&counter, &4u32);
+		assert_eq!(BagsList::sanity_check(), Ok(()));
+
+		// a node which no bag links to.
+		let mut node = list::Node::<Test>::get(&1).unwrap();
+		node.id = 5;
+		ListNodes::<Test>::insert(5, node);
+		assert!(BagsList::sanity_check().is_err());
+	});
+}
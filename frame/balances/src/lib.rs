@@ -65,6 +65,11 @@
 //! - **Lock:** A freeze on a specified amount of an account's free balance until a specified block number. Multiple
 //! locks always operate over the same funds, so they "overlay" rather than "stack".
 //!
+//! - **Hold:** A portion of the reserved balance which is held for a named reason. Holds for
+//!   different reasons "stack", and only the held balance of a reason can be released for it.
+//!
+//! - **Freeze:** A named restriction on withdrawing from an account. Like locks, freezes "overlay".
+//!
 //! ### Implementations
 //!
 //! The Balances pallet provides implementations for the following traits. If these traits provide the functionality
@@ -159,7 +164,7 @@ use sp_std::prelude::*;
 use sp_std::{cmp, result, mem, fmt::Debug, ops::BitOr};
 use codec::{Codec, Encode, Decode};
 use frame_support::{
-	ensure,
+	ensure, BoundedVec,
 	traits::{
 		Currency, OnUnbalanced, TryDrop, StoredMap,
 		WithdrawReasons, LockIdentifier, LockableCurrency, ExistenceRequirement,
@@ -214,6 +219,22 @@ pub mod pallet {
 		/// The maximum number of locks that should exist on an account.
 		/// Not strictly enforced, but used for weight estimation.
 		type MaxLocks: Get<u32>;
+
+		/// The identifier of a hold, i.e. the reason for which funds are held. This is typically an
+		/// enum of the runtime with a variant per pallet holding funds.
+		type HoldIdentifier: Parameter + Member + Copy;
+
+		/// The identifier of a freeze, i.e. the reason for which funds are frozen. This is
+		/// typically an enum of the runtime with a variant per pallet freezing funds.
+		type FreezeIdentifier: Parameter + Member + Copy;
+
+		/// The maximum number of holds that can exist on an account at any time.
+		#[pallet::constant]
+		type MaxHolds: Get<u32>;
+
+		/// The maximum number of freezes that can exist on an account at any time.
+		#[pallet::constant]
+		type MaxFreezes: Get<u32>;
 	}
 
 	#[pallet::pallet]
@@ -408,6 +429,10 @@ pub mod pallet {
 		ExistingVestingSchedule,
 		/// Beneficiary account must pre-exist
 		DeadAccount,
+		/// Number of holds exceed `MaxHolds`
+		TooManyHolds,
+		/// Number of freezes exceed `MaxFreezes`
+		TooManyFreezes,
 	}
 
 	/// The total units issued in the system.
@@ -439,9 +464,33 @@ pub mod pallet {
 		ValueQuery
 	>;
 
+	/// Holds on account balances, by reason.
+	///
+	/// The amounts on hold are part of the reserved balance of the account.
+	#[pallet::storage]
+	pub type Holds<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<IdAmount<T::HoldIdentifier, T::Balance>, T::MaxHolds>,
+		ValueQuery
+	>;
+
+	/// Freezes on account balances, by reason.
+	///
+	/// Like locks, freezes overlap: the free balance may not drop below the largest of them.
+	#[pallet::storage]
+	pub type Freezes<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<IdAmount<T::FreezeIdentifier, T::Balance>, T::MaxFreezes>,
+		ValueQuery
+	>;
+
 	/// Storage version of the pallet.
 	///
-	/// This is set to v2.0.0 for new networks.
+	/// This is set to v3.0.0 for new networks.
 	#[pallet::storage]
 	pub(super) type StorageVersion<T: Config<I>, I: 'static = ()> = StorageValue<
 		_,
@@ -471,7 +520,7 @@ pub mod pallet {
 				.fold(Zero::zero(), |acc: T::Balance, &(_, n)| acc + n);
 			<TotalIssuance<T, I>>::put(total);
 
-			<StorageVersion<T, I>>::put(Releases::V2_0_0);
+			<StorageVersion<T, I>>::put(Releases::V3_0_0);
 
 			for (_, balance) in &self.balances {
 				assert!(
@@ -555,6 +604,16 @@ pub struct BalanceLock<Balance> {
 	pub reasons: Reasons,
 }
 
+/// An amount identified by some `Id`, e.g. a hold or a freeze. There can be many of these on an
+/// account, one for each identifier.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct IdAmount<Id, Balance> {
+	/// The identifier of this item.
+	pub id: Id,
+	/// The amount of this item.
+	pub amount: Balance,
+}
+
 /// All balance information for an account.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
 pub struct AccountData<Balance> {
@@ -564,7 +623,7 @@ pub struct AccountData<Balance> {
 	/// This is the only balance that matters in terms of most operations on tokens. It
 	/// alone is used to determine the balance when in the contract execution environment.
 	pub free: Balance,
-	/// Balance which is reserved and may not be used at all.
+	/// Balance which is reserved and may not be used at all, including the balance on hold.
 	///
 	/// This can still get slashed, but gets slashed last of all.
 	///
@@ -572,10 +631,10 @@ pub struct AccountData<Balance> {
 	/// that are still 'owned' by the account holder, but which are suspendable.
 	pub reserved: Balance,
 	/// The amount that `free` may not drop below when withdrawing for *anything except transaction
-	/// fee payment*.
+	/// fee payment*, because of locks or freezes.
 	pub misc_frozen: Balance,
 	/// The amount that `free` may not drop below when withdrawing specifically for transaction
-	/// fee payment.
+	/// fee payment, because of locks or freezes.
 	pub fee_frozen: Balance,
 }
 
@@ -606,6 +665,7 @@ impl<Balance: Saturating + Copy + Ord> AccountData<Balance> {
 enum Releases {
 	V1_0_0,
 	V2_0_0,
+	V3_0_0,
 }
 
 impl Default for Releases {
@@ -830,18 +890,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			);
 		}
 		// No way this can fail since we do not alter the existential balances.
-		let res = Self::mutate_account(who, |b| {
-			b.misc_frozen = Zero::zero();
-			b.fee_frozen = Zero::zero();
-			for l in locks.iter() {
-				if l.reasons == Reasons::All || l.reasons == Reasons::Misc {
-					b.misc_frozen = b.misc_frozen.max(l.amount);
-				}
-				if l.reasons == Reasons::All || l.reasons == Reasons::Fee {
-					b.fee_frozen = b.fee_frozen.max(l.amount);
-				}
-			}
-		});
+		let res = Self::update_frozen(who, locks, &Freezes::<T, I>::get(who));
 		debug_assert!(res.is_ok());
 
 		let existed = Locks::<T, I>::contains_key(who);
@@ -869,6 +918,124 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		}
 	}
 
+	/// Update the account entry for `who`, given the freezes.
+	fn update_freezes(
+		who: &T::AccountId,
+		freezes: BoundedVec<IdAmount<T::FreezeIdentifier, T::Balance>, T::MaxFreezes>,
+	) -> DispatchResult {
+		Self::update_frozen(who, &Self::locks(who), &freezes)?;
+		let existed = Freezes::<T, I>::contains_key(who);
+		let exists = !freezes.is_empty();
+		if exists {
+			Freezes::<T, I>::insert(who, freezes);
+		} else {
+			Freezes::<T, I>::remove(who);
+		}
+		Self::update_consumer(who, existed, exists, "freeze");
+		Ok(())
+	}
+
+	/// Set the frozen balances of the account entry for `who` to the largest of the `locks` which
+	/// apply to them, and of the `freezes`, which apply to every withdrawal.
+	fn update_frozen(
+		who: &T::AccountId,
+		locks: &[BalanceLock<T::Balance>],
+		freezes: &[IdAmount<T::FreezeIdentifier, T::Balance>],
+	) -> Result<(), StoredMapError> {
+		Self::mutate_account(who, |b| {
+			b.misc_frozen = Zero::zero();
+			b.fee_frozen = Zero::zero();
+			for l in locks.iter() {
+				if l.reasons == Reasons::All || l.reasons == Reasons::Misc {
+					b.misc_frozen = b.misc_frozen.max(l.amount);
+				}
+				if l.reasons == Reasons::All || l.reasons == Reasons::Fee {
+					b.fee_frozen = b.fee_frozen.max(l.amount);
+				}
+			}
+			for f in freezes.iter() {
+				b.misc_frozen = b.misc_frozen.max(f.amount);
+				b.fee_frozen = b.fee_frozen.max(f.amount);
+			}
+		})
+	}
+
+	/// Mutate the holds of `who`, removing the empty ones. Nothing is changed if `f` returns an
+	/// error.
+	fn try_mutate_holds<R, E>(
+		who: &T::AccountId,
+		f: impl FnOnce(&mut BoundedVec<IdAmount<T::HoldIdentifier, T::Balance>, T::MaxHolds>)
+			-> Result<R, E>,
+	) -> Result<R, E> {
+		let mut holds = Holds::<T, I>::get(who);
+		let existed = !holds.is_empty();
+		let result = f(&mut holds)?;
+		holds.retain(|h| !h.amount.is_zero());
+		let exists = !holds.is_empty();
+		if exists {
+			Holds::<T, I>::insert(who, holds);
+		} else {
+			Holds::<T, I>::remove(who);
+		}
+		Self::update_consumer(who, existed, exists, "hold");
+		Ok(result)
+	}
+
+	/// Keep a consumer reference on the account of `who` as long as it has some items of `kind`,
+	/// given whether it had some before (`existed`) and has some now (`exists`).
+	fn update_consumer(who: &T::AccountId, existed: bool, exists: bool, kind: &str) {
+		if existed && !exists {
+			system::Pallet::<T>::dec_consumers(who);
+		} else if !existed && exists && system::Pallet::<T>::inc_consumers(who).is_err() {
+			// No providers for the item. This is impossible under normal circumstances since the
+			// funds under it will themselves be stored in the account and therefore will need a
+			// reference.
+			log::warn!(
+				target: "runtime::balances",
+				"Warning: Attempt to introduce {} consumer reference, yet no providers. \
+				This is unexpected but should be safe.",
+				kind,
+			);
+		}
+	}
+
+	/// The amount of funds of `who` on hold, for any reason.
+	fn total_held(who: &T::AccountId) -> T::Balance {
+		Holds::<T, I>::get(who).iter().fold(Zero::zero(), |total, h| total.saturating_add(h.amount))
+	}
+
+	/// The amount of funds of `who` on hold for `reason`.
+	fn held(who: &T::AccountId, reason: &T::HoldIdentifier) -> T::Balance {
+		Holds::<T, I>::get(who).iter()
+			.find(|h| &h.id == reason)
+			.map_or_else(Zero::zero, |h| h.amount)
+	}
+
+	/// Increase the hold `reason` in `holds` by `amount`.
+	fn increase_hold(
+		holds: &mut BoundedVec<IdAmount<T::HoldIdentifier, T::Balance>, T::MaxHolds>,
+		reason: &T::HoldIdentifier,
+		amount: T::Balance,
+	) -> DispatchResult {
+		if let Some(hold) = holds.as_mut().iter_mut().find(|h| &h.id == reason) {
+			hold.amount = hold.amount.checked_add(&amount).ok_or(ArithmeticError::Overflow)?;
+		} else {
+			holds.try_push(IdAmount { id: *reason, amount })
+				.map_err(|_| Error::<T, I>::TooManyHolds)?;
+		}
+		Ok(())
+	}
+
+	/// Decrease the hold `reason` in `holds` by `amount`, which must be at most the amount on hold.
+	fn decrease_hold(
+		holds: &mut BoundedVec<IdAmount<T::HoldIdentifier, T::Balance>, T::MaxHolds>,
+		reason: &T::HoldIdentifier,
+		amount: T::Balance,
+	) {
+		if let Some(hold) = holds.as_mut().iter_mut().find(|h| &h.id == reason) {
+			hold.amount = hold.amount.saturating_sub(amount);
+		}
+	}
 
 	/// Move the reserved balance of one account into the balance of another, according to `status`.
 	///
@@ -1003,10 +1170,19 @@ impl<T: Config<I>, I: 'static> fungible::Unbalanced<T::AccountId> for Pallet<T,
 }
 
 impl<T: Config<I>, I: 'static> fungible::InspectHold<T::AccountId> for Pallet<T, I> {
-	fn balance_on_hold(who: &T::AccountId) -> T::Balance {
+	type Reason = T::HoldIdentifier;
+
+	fn balance_on_hold(reason: &Self::Reason, who: &T::AccountId) -> T::Balance {
+		Self::held(who, reason)
+	}
+	fn total_balance_on_hold(who: &T::AccountId) -> T::Balance {
 		Self::account(who).reserved
 	}
-	fn can_hold(who: &T::AccountId, amount: T::Balance) -> bool {
+	fn can_hold(reason: &Self::Reason, who: &T::AccountId, amount: T::Balance) -> bool {
+		let holds = Holds::<T, I>::get(who);
+		if !holds.iter().any(|h| &h.id == reason) && holds.len() >= T::MaxHolds::get() as usize {
+			return false
+		}
 		let a = Self::account(who);
 		let min_balance = T::ExistentialDeposit::get().max(a.frozen(Reasons::All));
 		if a.reserved.checked_add(&amount).is_none() { return false }
@@ -1020,39 +1196,168 @@ impl<T: Config<I>, I: 'static> fungible::InspectHold<T::AccountId> for Pallet<T,
 	}
 }
 impl<T: Config<I>, I: 'static> fungible::MutateHold<T::AccountId> for Pallet<T, I> {
-	fn hold(who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+	fn hold(reason: &Self::Reason, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
 		if amount.is_zero() { return Ok(()) }
 		ensure!(Self::can_reserve(who, amount), Error::<T, I>::InsufficientBalance);
-		Self::mutate_account(who, |a| {
-			a.free -= amount;
-			a.reserved += amount;
-		})?;
-		Ok(())
+		Self::try_mutate_holds(who, |holds| -> DispatchResult {
+			Self::increase_hold(holds, reason, amount)?;
+			Self::mutate_account(who, |a| {
+				a.free -= amount;
+				a.reserved += amount;
+			})?;
+			Ok(())
+		})
 	}
-	fn release(who: &T::AccountId, amount: Self::Balance, best_effort: bool)
-		-> Result<T::Balance, DispatchError>
-	{
+	fn release(
+		reason: &Self::Reason,
+		who: &T::AccountId,
+		amount: Self::Balance,
+		best_effort: bool,
+	) -> Result<T::Balance, DispatchError> {
 		if amount.is_zero() { return Ok(amount) }
-		// Done on a best-effort basis.
-		Self::try_mutate_account(who, |a, _| {
-			let new_free = a.free.saturating_add(amount.min(a.reserved));
-			let actual = new_free - a.free;
-			ensure!(best_effort || actual == amount, Error::<T, I>::InsufficientBalance);
-			// ^^^ Guaranteed to be <= amount and <= a.reserved
-			a.free = new_free;
-			a.reserved = a.reserved.saturating_sub(actual.clone());
+		let actual = amount.min(Self::held(who, reason));
+		ensure!(best_effort || actual == amount, Error::<T, I>::InsufficientBalance);
+		Self::try_mutate_holds(who, |holds| -> Result<T::Balance, DispatchError> {
+			Self::decrease_hold(holds, reason, actual);
+			Self::try_mutate_account(who, |a, _| -> DispatchResult {
+				// ^^^ `actual` is at most the amount on hold, which is part of `a.reserved`.
+				a.free = a.free.checked_add(&actual).ok_or(ArithmeticError::Overflow)?;
+				a.reserved = a.reserved.saturating_sub(actual);
+				Ok(())
+			})?;
 			Ok(actual)
 		})
 	}
 	fn transfer_held(
+		reason: &Self::Reason,
 		source: &T::AccountId,
 		dest: &T::AccountId,
 		amount: Self::Balance,
 		best_effort: bool,
 		on_hold: bool,
 	) -> Result<Self::Balance, DispatchError> {
-		let status = if on_hold { Status::Reserved } else { Status::Free };
-		Self::do_transfer_reserved(source, dest, amount, best_effort, status)
+		let actual = amount.min(Self::held(source, reason));
+		ensure!(best_effort || actual == amount, Error::<T, I>::InsufficientBalance);
+		if actual.is_zero() || (source == dest && on_hold) { return Ok(actual) }
+		if source == dest {
+			return <Self as fungible::MutateHold<_>>::release(reason, source, actual, false)
+		}
+
+		Self::try_mutate_holds(source, |source_holds| {
+			Self::decrease_hold(source_holds, reason, actual);
+			if on_hold {
+				Self::try_mutate_holds(dest, |dest_holds| {
+					Self::increase_hold(dest_holds, reason, actual)?;
+					Self::do_transfer_reserved(source, dest, actual, false, Status::Reserved)
+				})
+			} else {
+				Self::do_transfer_reserved(source, dest, actual, false, Status::Free)
+			}
+		})
+	}
+}
+
+impl<T: Config<I>, I: 'static> fungible::InspectFreeze<T::AccountId> for Pallet<T, I> {
+	type Id = T::FreezeIdentifier;
+
+	fn balance_frozen(id: &Self::Id, who: &T::AccountId) -> Self::Balance {
+		Freezes::<T, I>::get(who).iter()
+			.find(|f| &f.id == id)
+			.map_or_else(Zero::zero, |f| f.amount)
+	}
+	fn can_freeze(id: &Self::Id, who: &T::AccountId) -> bool {
+		let freezes = Freezes::<T, I>::get(who);
+		freezes.iter().any(|f| &f.id == id) || freezes.len() < T::MaxFreezes::get() as usize
+	}
+}
+
+impl<T: Config<I>, I: 'static> fungible::MutateFreeze<T::AccountId> for Pallet<T, I> {
+	fn set_freeze(id: &Self::Id, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+		if amount.is_zero() {
+			return <Self as fungible::MutateFreeze<_>>::thaw(id, who)
+		}
+		let mut freezes = Freezes::<T, I>::get(who);
+		if let Some(freeze) = freezes.as_mut().iter_mut().find(|f| &f.id == id) {
+			freeze.amount = amount;
+		} else {
+			freezes.try_push(IdAmount { id: *id, amount })
+				.map_err(|_| Error::<T, I>::TooManyFreezes)?;
+		}
+		Self::update_freezes(who, freezes)
+	}
+	fn thaw(id: &Self::Id, who: &T::AccountId) -> DispatchResult {
+		let mut freezes = Freezes::<T, I>::get(who);
+		freezes.retain(|f| &f.id != id);
+		Self::update_freezes(who, freezes)
+	}
+}
+
+/// Migration utilities for moving the reserves of pallets onto holds.
+pub mod migration {
+	use super::*;
+	use frame_support::{traits::OnRuntimeUpgrade, weights::Weight};
+	use sp_std::marker::PhantomData;
+
+	/// Move up to `amount` of the reserved balance of each of the `accounts` onto the hold
+	/// `reason`.
+	///
+	/// This is meant for the runtime upgrade of a pallet moving from `ReservableCurrency` to
+	/// `fungible::MutateHold`: the balance it reserved stays reserved, but is now on hold for
+	/// `reason`, so that the pallet can release it. The balance already on hold is never moved,
+	/// and the accounts which have too many holds are skipped.
+	///
+	/// Returns the weight consumed.
+	pub fn migrate_reserves_to_hold<T: Config<I>, I: 'static>(
+		reason: T::HoldIdentifier,
+		accounts: impl IntoIterator<Item = (T::AccountId, T::Balance)>,
+	) -> Weight {
+		let mut weight: Weight = 0;
+		for (who, amount) in accounts {
+			let reserved = Pallet::<T, I>::reserved_balance(&who);
+			let amount = amount.min(reserved.saturating_sub(Pallet::<T, I>::total_held(&who)));
+			let result = Pallet::<T, I>::try_mutate_holds(&who, |holds| {
+				Pallet::<T, I>::increase_hold(holds, &reason, amount)
+			});
+			if let Err(e) = result {
+				log::warn!(
+					target: "runtime::balances",
+					"Failed to move the reserve of {:?} onto a hold: {:?}",
+					who,
+					e,
+				);
+			}
+			weight = weight.saturating_add(T::DbWeight::get().reads_writes(3, 2));
+		}
+		weight
+	}
+
+	/// Move the reserves given by `Reserves` onto their holds, once.
+	///
+	/// `Reserves` lists the hold reason, account and amount of each reserve of the pallets moving
+	/// to holds in this runtime upgrade. The migration only runs at storage version v2.0.0, and
+	/// then sets it to v3.0.0, so that the reserves are not moved again by later upgrades.
+	pub struct MigrateToV3<T, I, Reserves>(PhantomData<(T, I, Reserves)>);
+
+	impl<T, I, Reserves> OnRuntimeUpgrade for MigrateToV3<T, I, Reserves> where
+		T: Config<I>,
+		I: 'static,
+		Reserves: Get<Vec<(T::HoldIdentifier, T::AccountId, T::Balance)>>,
+	{
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::<T, I>::get() != Releases::V2_0_0 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let weight = Reserves::get()
+				.into_iter()
+				.fold(0 as Weight, |weight, (reason, who, amount)| {
+					weight.saturating_add(
+						migrate_reserves_to_hold::<T, I>(reason, Some((who, amount)))
+					)
+				});
+			StorageVersion::<T, I>::put(Releases::V3_0_0);
+			weight.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+		}
 	}
 }
 
@@ -1364,7 +1669,8 @@ impl<T: Config<I>, I: 'static> Currency<T::AccountId> for Pallet<T, I> where
 	/// NOTE: `slash()` prefers free balance, but assumes that reserve balance can be drawn
 	/// from in extreme circumstances. `can_slash()` should be used prior to `slash()` to avoid having
 	/// to draw from reserved funds, however we err on the side of punishment if things are inconsistent
-	/// or `can_slash` wasn't used appropriately.
+	/// or `can_slash` wasn't used appropriately. The balance on hold is never slashed here, see
+	/// `fungible::BalancedHold::slash_held`.
 	fn slash(
 		who: &T::AccountId,
 		value: Self::Balance
@@ -1372,6 +1678,7 @@ impl<T: Config<I>, I: 'static> Currency<T::AccountId> for Pallet<T, I> where
 		if value.is_zero() { return (NegativeImbalance::zero(), Zero::zero()) }
 		if Self::total_balance(&who).is_zero() { return (NegativeImbalance::zero(), value) }
 
+		let held = Self::total_held(who);
 		for attempt in 0..2 {
 			match Self::try_mutate_account(who,
 				|account, _is_new| -> Result<(Self::NegativeImbalance, Self::Balance), StoredMapError> {
@@ -1389,8 +1696,12 @@ impl<T: Config<I>, I: 'static> Currency<T::AccountId> for Pallet<T, I> where
 					let remaining_slash = best_value - free_slash; // Safe because of above check
 
 					if !remaining_slash.is_zero() {
-						// If we have remaining slash, take it from reserved balance.
-						let reserved_slash = cmp::min(account.reserved, remaining_slash);
+						// If we have remaining slash, take it from reserved balance, except the
+						// balance on hold.
+						let reserved_slash = cmp::min(
+							account.reserved.saturating_sub(held),
+							remaining_slash,
+						);
 						account.reserved -= reserved_slash; // Safe because of above check
 						Ok((
 							NegativeImbalance::new(free_slash + reserved_slash),
@@ -1536,6 +1847,7 @@ impl<T: Config<I>, I: 'static> ReservableCurrency<T::AccountId> for Pallet<T, I>
 			)
 	}
 
+	/// The reserved balance of `who`, including their balance on hold.
 	fn reserved_balance(who: &T::AccountId) -> Self::Balance {
 		Self::account(who).reserved
 	}
@@ -1558,13 +1870,17 @@ impl<T: Config<I>, I: 'static> ReservableCurrency<T::AccountId> for Pallet<T, I>
 
 	/// Unreserve some funds, returning any amount that was unable to be unreserved.
 	///
+	/// The balance on hold can't be unreserved, it must be released through
+	/// `fungible::MutateHold`.
+	///
 	/// Is a no-op if the value to be unreserved is zero or the account does not exist.
 	fn unreserve(who: &T::AccountId, value: Self::Balance) -> Self::Balance {
 		if value.is_zero() { return Zero::zero() }
 		if Self::total_balance(&who).is_zero() { return value }
 
+		let held = Self::total_held(who);
 		let actual = match Self::mutate_account(who, |account| {
-			let actual = cmp::min(account.reserved, value);
+			let actual = cmp::min(account.reserved.saturating_sub(held), value);
 			account.reserved -= actual;
 			// defensive only: this can never fail since total issuance which is at least free+reserved
 			// fits into the same data type.
@@ -1587,6 +1903,8 @@ impl<T: Config<I>, I: 'static> ReservableCurrency<T::AccountId> for Pallet<T, I>
 	/// Slash from reserved balance, returning the negative imbalance created,
 	/// and any amount that was unable to be slashed.
 	///
+	/// The balance on hold isn't slashed, see `fungible::BalancedHold::slash_held`.
+	///
 	/// Is a no-op if the value to be slashed is zero or the account does not exist.
 	fn slash_reserved(
 		who: &T::AccountId,
@@ -1598,6 +1916,7 @@ impl<T: Config<I>, I: 'static> ReservableCurrency<T::AccountId> for Pallet<T, I>
 		// NOTE: `mutate_account` may fail if it attempts to reduce the balance to the point that an
 		//   account is attempted to be illegally destroyed.
 
+		let held = Self::total_held(who);
 		for attempt in 0..2 {
 			match Self::mutate_account(who, |account| {
 				let best_value = match attempt {
@@ -1607,7 +1926,7 @@ impl<T: Config<I>, I: 'static> ReservableCurrency<T::AccountId> for Pallet<T, I>
 					_ => value.min((account.free + account.reserved).saturating_sub(T::ExistentialDeposit::get())),
 				};
 
-				let actual = cmp::min(account.reserved, best_value);
+				let actual = cmp::min(account.reserved.saturating_sub(held), best_value);
 				account.reserved -= actual;
 
 				// underflow should never happen, but it if does, there's nothing to be done here.
@@ -1624,6 +1943,8 @@ impl<T: Config<I>, I: 'static> ReservableCurrency<T::AccountId> for Pallet<T, I>
 
 	/// Move the reserved balance of one account into the balance of another, according to `status`.
 	///
+	/// The balance on hold isn't moved, see `fungible::MutateHold::transfer_held`.
+	///
 	/// Is a no-op if:
 	/// - the value to be moved is zero; or
 	/// - the `slashed` id equal to `beneficiary` and the `status` is `Reserved`.
@@ -1633,7 +1954,9 @@ impl<T: Config<I>, I: 'static> ReservableCurrency<T::AccountId> for Pallet<T, I>
 		value: Self::Balance,
 		status: Status,
 	) -> Result<Self::Balance, DispatchError> {
-		let actual = Self::do_transfer_reserved(slashed, beneficiary, value, true, status)?;
+		let held = Self::total_held(slashed);
+		let movable = value.min(Self::reserved_balance(slashed).saturating_sub(held));
+		let actual = Self::do_transfer_reserved(slashed, beneficiary, movable, true, status)?;
 		Ok(value.saturating_sub(actual))
 	}
 }
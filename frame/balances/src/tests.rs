@@ -19,6 +19,17 @@
 
 #![cfg(test)]
 
+use codec::{Encode, Decode};
+use sp_runtime::RuntimeDebug;
+
+/// The identifiers of the holds and freezes of the tests.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum TestId {
+	Foo,
+	Bar,
+	Baz,
+}
+
 #[macro_export]
 macro_rules! decl_tests {
 	($test:ty, $ext_builder:ty, $existential_deposit:expr) => {
@@ -29,7 +40,7 @@ macro_rules! decl_tests {
 			assert_noop, assert_storage_noop, assert_ok, assert_err, StorageValue,
 			traits::{
 				LockableCurrency, LockIdentifier, WithdrawReasons,
-				Currency, ReservableCurrency, ExistenceRequirement::AllowDeath
+				Currency, ReservableCurrency, ExistenceRequirement::AllowDeath, Imbalance,
 			}
 		};
 		use pallet_transaction_payment::{ChargeTransactionPayment, Multiplier};
@@ -964,5 +975,178 @@ macro_rules! decl_tests {
 					assert_eq!(Balances::total_balance(&2), 100);
 				});
 		}
+
+		#[test]
+		fn fungible_adapter_implements_the_legacy_traits() {
+			frame_support::parameter_types! {
+				pub const HoldReason: TestId = TestId::Foo;
+			}
+			type Adapter =
+				frame_support::traits::tokens::currency::FungibleAdapter<Balances, HoldReason>;
+			<$ext_builder>::default()
+				.build()
+				.execute_with(|| {
+					let _ = Balances::deposit_creating(&1, 100);
+
+					// reserves are holds.
+					assert_ok!(<Adapter as ReservableCurrency<_>>::reserve(&1, 30));
+					assert_eq!(<Adapter as Currency<_>>::free_balance(&1), 70);
+					assert_eq!(<Adapter as Currency<_>>::total_balance(&1), 100);
+					assert_eq!(<Adapter as ReservableCurrency<_>>::reserved_balance(&1), 30);
+					assert_eq!(Balances::reserved_balance(&1), 30);
+					let hold = IdAmount { id: TestId::Foo, amount: 30 };
+					assert_eq!(Holds::<$test>::get(&1)[0], hold);
+					assert_eq!(<Adapter as ReservableCurrency<_>>::unreserve(&1, 40), 10);
+					assert_eq!(Balances::free_balance(&1), 100);
+
+					assert_ok!(<Adapter as Currency<_>>::transfer(&1, &2, 50, AllowDeath));
+					assert_eq!(Balances::free_balance(&2), 50);
+
+					// imbalances keep the issuance up to date.
+					let issuance = Balances::total_issuance();
+					drop(<Adapter as Currency<_>>::deposit_creating(&3, 20));
+					assert_eq!(Balances::free_balance(&3), 20);
+					assert_eq!(Balances::total_issuance(), issuance + 20);
+					let withdrawn = <Adapter as Currency<_>>::withdraw(
+						&3,
+						5,
+						WithdrawReasons::all(),
+						AllowDeath,
+					);
+					assert_eq!(withdrawn.map(|imbalance| Imbalance::peek(&imbalance)), Ok(5));
+					assert_eq!(Balances::total_issuance(), issuance + 15);
+				});
+		}
+
+		#[test]
+		fn named_holds_work() {
+			use frame_support::traits::fungible::{InspectHold, MutateHold};
+			<$ext_builder>::default()
+				.build()
+				.execute_with(|| {
+					let _ = Balances::deposit_creating(&1, 100);
+					let _ = Balances::deposit_creating(&2, 10);
+
+					assert_ok!(Balances::hold(&TestId::Foo, &1, 30));
+					assert_ok!(Balances::hold(&TestId::Bar, &1, 20));
+					assert_ok!(Balances::hold(&TestId::Foo, &1, 10));
+					assert_noop!(
+						Balances::hold(&TestId::Baz, &1, 10),
+						Error::<$test, _>::TooManyHolds,
+					);
+					assert_eq!(Balances::balance_on_hold(&TestId::Foo, &1), 40);
+					assert_eq!(Balances::balance_on_hold(&TestId::Bar, &1), 20);
+					assert_eq!(Balances::total_balance_on_hold(&1), 60);
+					assert_eq!(Balances::free_balance(&1), 40);
+					assert!(!Balances::can_hold(&TestId::Baz, &1, 10));
+
+					// the legacy reserves don't touch the balance on hold.
+					assert_ok!(Balances::reserve(&1, 10));
+					assert_eq!(Balances::reserved_balance(&1), 70);
+					assert_eq!(Balances::unreserve(&1, 20), 10);
+					assert_eq!(Balances::slash_reserved(&1, 10).1, 10);
+					assert_eq!(Balances::reserved_balance(&1), 60);
+
+					assert_noop!(
+						Balances::release(&TestId::Foo, &1, 50, false),
+						Error::<$test, _>::InsufficientBalance,
+					);
+					assert_eq!(Balances::release(&TestId::Foo, &1, 50, true), Ok(40));
+					assert_eq!(Balances::free_balance(&1), 80);
+					assert_eq!(Holds::<$test>::get(&1).len(), 1);
+
+					// on hold, then free in the destination account.
+					let bar = TestId::Bar;
+					assert_eq!(Balances::transfer_held(&bar, &1, &2, 5, false, true), Ok(5));
+					assert_eq!(Balances::transfer_held(&bar, &1, &2, 5, false, false), Ok(5));
+					assert_eq!(Balances::balance_on_hold(&TestId::Bar, &1), 10);
+					assert_eq!(Balances::balance_on_hold(&TestId::Bar, &2), 5);
+					assert_eq!(Balances::reserved_balance(&2), 5);
+					assert_eq!(Balances::free_balance(&2), 15);
+				});
+		}
+
+		#[test]
+		fn named_freezes_work() {
+			use frame_support::traits::fungible::{InspectFreeze, MutateFreeze};
+			<$ext_builder>::default()
+				.build()
+				.execute_with(|| {
+					let _ = Balances::deposit_creating(&1, 100);
+
+					assert_ok!(Balances::set_freeze(&TestId::Foo, &1, 30));
+					assert_ok!(Balances::set_freeze(&TestId::Bar, &1, 50));
+					assert_noop!(
+						Balances::set_freeze(&TestId::Baz, &1, 10),
+						Error::<$test, _>::TooManyFreezes,
+					);
+					assert!(!Balances::can_freeze(&TestId::Baz, &1));
+					assert_eq!(Balances::balance_frozen(&TestId::Bar, &1), 50);
+
+					// freezes overlap with each other and with the locks.
+					Balances::set_lock(ID_1, &1, 40, WithdrawReasons::all());
+					assert_noop!(
+						<Balances as Currency<_>>::transfer(&1, &2, 60, AllowDeath),
+						Error::<$test, _>::LiquidityRestrictions,
+					);
+					assert_ok!(Balances::thaw(&TestId::Bar, &1));
+					assert_noop!(
+						<Balances as Currency<_>>::transfer(&1, &2, 61, AllowDeath),
+						Error::<$test, _>::LiquidityRestrictions,
+					);
+					Balances::remove_lock(ID_1, &1);
+					assert_ok!(<Balances as Currency<_>>::transfer(&1, &2, 70, AllowDeath));
+				});
+		}
+
+		#[test]
+		fn reserves_can_be_migrated_to_holds() {
+			use frame_support::traits::fungible::{InspectHold, MutateHold};
+			<$ext_builder>::default()
+				.build()
+				.execute_with(|| {
+					let _ = Balances::deposit_creating(&1, 100);
+					assert_ok!(Balances::hold(&TestId::Bar, &1, 10));
+					assert_ok!(Balances::reserve(&1, 30));
+
+					migration::migrate_reserves_to_hold::<$test, ()>(
+						TestId::Foo,
+						vec![(1, 50), (2, 10)],
+					);
+					assert_eq!(Balances::balance_on_hold(&TestId::Foo, &1), 30);
+					assert_eq!(Balances::reserved_balance(&1), 40);
+					assert_eq!(Balances::unreserve(&1, 10), 10);
+					assert_eq!(Balances::release(&TestId::Foo, &1, 30, false), Ok(30));
+					assert_eq!(Balances::free_balance(&1), 90);
+				});
+		}
+
+		#[test]
+		fn reserves_are_migrated_to_holds_once() {
+			use frame_support::traits::{OnRuntimeUpgrade, fungible::InspectHold};
+			frame_support::parameter_types! {
+				pub Reserves: Vec<(TestId, u64, u64)> = vec![(TestId::Foo, 1, 30)];
+			}
+			type Migration = migration::MigrateToV3<$test, (), Reserves>;
+			<$ext_builder>::default()
+				.build()
+				.execute_with(|| {
+					let _ = Balances::deposit_creating(&1, 100);
+					assert_ok!(Balances::reserve(&1, 60));
+
+					// new networks are at v3.0.0 already.
+					Migration::on_runtime_upgrade();
+					assert_eq!(Balances::balance_on_hold(&TestId::Foo, &1), 0);
+
+					StorageVersion::<$test, ()>::put(Releases::V2_0_0);
+					Migration::on_runtime_upgrade();
+					assert_eq!(Balances::balance_on_hold(&TestId::Foo, &1), 30);
+					assert_eq!(StorageVersion::<$test, ()>::get(), Releases::V3_0_0);
+
+					Migration::on_runtime_upgrade();
+					assert_eq!(Balances::balance_on_hold(&TestId::Foo, &1), 30);
+					assert_eq!(Balances::reserved_balance(&1), 60);
+				});
+		}
 	}
 }
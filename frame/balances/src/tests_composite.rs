@@ -25,12 +25,12 @@ use sp_runtime::{
 };
 use sp_core::H256;
 use sp_io;
-use frame_support::parameter_types;
+use frame_support::{parameter_types, traits::ConstU32};
 use frame_support::weights::{Weight, DispatchInfo, IdentityFee};
 use pallet_transaction_payment::CurrencyAdapter;
 use crate::{
 	self as pallet_balances,
-	Pallet, Config, decl_tests,
+	Pallet, Config, decl_tests, tests::TestId,
 };
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -94,6 +94,10 @@ impl Config for Test {
 	type ExistentialDeposit = ExistentialDeposit;
 	type AccountStore = frame_system::Pallet<Test>;
 	type MaxLocks = ();
+	type HoldIdentifier = TestId;
+	type FreezeIdentifier = TestId;
+	type MaxHolds = ConstU32<2>;
+	type MaxFreezes = ConstU32<2>;
 	type WeightInfo = ();
 }
 
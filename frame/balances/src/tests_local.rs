@@ -26,11 +26,11 @@ use sp_runtime::{
 use sp_core::H256;
 use sp_io;
 use frame_support::parameter_types;
-use frame_support::traits::StorageMapShim;
+use frame_support::traits::{ConstU32, StorageMapShim};
 use frame_support::weights::{Weight, DispatchInfo, IdentityFee};
 use crate::{
 	self as pallet_balances,
-	Pallet, Config, decl_tests,
+	Pallet, Config, decl_tests, tests::TestId,
 };
 use pallet_transaction_payment::CurrencyAdapter;
 
@@ -103,6 +103,10 @@ impl Config for Test {
 		super::AccountData<u64>,
 	>;
 	type MaxLocks = MaxLocks;
+	type HoldIdentifier = TestId;
+	type FreezeIdentifier = TestId;
+	type MaxHolds = ConstU32<2>;
+	type MaxFreezes = ConstU32<2>;
 	type WeightInfo = ();
 }
 
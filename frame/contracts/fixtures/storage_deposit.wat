@@ -0,0 +1,45 @@
+;; The constructor stores one item of 4 bytes. Calling it with the input `0u32` stores
+;; a second item of 4 bytes and calling it with `1u32` removes that item again.
+(module
+	(import "seal0" "seal_set_storage" (func $seal_set_storage (param i32 i32 i32)))
+	(import "seal0" "seal_clear_storage" (func $seal_clear_storage (param i32)))
+	(import "seal0" "seal_input" (func $seal_input (param i32 i32)))
+	(import "env" "memory" (memory 1 1))
+
+	;; [0, 32) storage key written by the constructor
+
+	;; [32, 64) storage key written and cleared by `call`
+	(data (i32.const 32) "\01")
+
+	;; [64, 68) the value that is stored
+	(data (i32.const 64) "\28")
+
+	;; [68, 72) size of the input buffer
+	(data (i32.const 68) "\04")
+
+	;; [72, 76) input buffer
+
+	(func (export "deploy")
+		(call $seal_set_storage
+			(i32.const 0)	;; Pointer to storage key
+			(i32.const 64)	;; Pointer to value
+			(i32.const 4)	;; Size of value
+		)
+	)
+
+	(func (export "call")
+		(call $seal_input (i32.const 72) (i32.const 68))
+		(if (i32.eqz (i32.load (i32.const 72)))
+			(then
+				(call $seal_set_storage
+					(i32.const 32)	;; Pointer to storage key
+					(i32.const 64)	;; Pointer to value
+					(i32.const 4)	;; Size of value
+				)
+			)
+			(else
+				(call $seal_clear_storage (i32.const 32))
+			)
+		)
+	)
+)
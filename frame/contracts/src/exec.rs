@@ -17,8 +17,8 @@
 
 use crate::{
 	CodeHash, Event, Config, Pallet as Contracts,
-	BalanceOf, ContractInfo, gas::GasMeter, rent::{Rent, RentStatus}, storage::Storage,
-	Error, ContractInfoOf, Schedule, AliveContractInfo, AccountCounter,
+	BalanceOf, ContractInfo, gas::GasMeter, storage::{self, Storage},
+	Error, ContractInfoOf, Schedule, AccountCounter,
 };
 use sp_core::crypto::UncheckedFrom;
 use sp_std::{
@@ -26,13 +26,13 @@ use sp_std::{
 	marker::PhantomData,
 	mem,
 };
-use sp_runtime::{Perbill, traits::{Convert, Saturating}};
+use sp_runtime::traits::{Convert, Saturating};
 use frame_support::{
 	dispatch::{DispatchResult, DispatchError},
 	storage::{with_transaction, TransactionOutcome},
 	traits::{ExistenceRequirement, Currency, Time, Randomness, Get},
 	weights::Weight,
-	ensure, DefaultNoBound,
+	ensure,
 };
 use pallet_contracts_primitives::{ExecReturnValue};
 use smallvec::{SmallVec, Array};
@@ -81,67 +81,6 @@ impl<T: Into<DispatchError>> From<T> for ExecError {
 	}
 }
 
-/// Information needed for rent calculations that can be requested by a contract.
-#[derive(codec::Encode, DefaultNoBound)]
-#[cfg_attr(test, derive(Debug, PartialEq))]
-pub struct RentParams<T: Config> {
-	/// The total balance of the contract. Includes the balance transferred from the caller.
-	total_balance: BalanceOf<T>,
-	/// The free balance of the contract. Includes the balance transferred from the caller.
-	free_balance: BalanceOf<T>,
-	/// See crate [`Contracts::subsistence_threshold()`].
-	subsistence_threshold: BalanceOf<T>,
-	/// See crate [`Config::DepositPerContract`].
-	deposit_per_contract: BalanceOf<T>,
-	/// See crate [`Config::DepositPerStorageByte`].
-	deposit_per_storage_byte: BalanceOf<T>,
-	/// See crate [`Config::DepositPerStorageItem`].
-	deposit_per_storage_item: BalanceOf<T>,
-	/// See crate [`Ext::rent_allowance()`].
-	rent_allowance: BalanceOf<T>,
-	/// See crate [`Config::RentFraction`].
-	rent_fraction: Perbill,
-	/// See crate [`AliveContractInfo::storage_size`].
-	storage_size: u32,
-	/// See crate [`Executable::aggregate_code_len()`].
-	code_size: u32,
-	/// See crate [`Executable::refcount()`].
-	code_refcount: u32,
-	/// Reserved for backwards compatible changes to this data structure.
-	_reserved: Option<()>,
-}
-
-impl<T> RentParams<T>
-where
-	T: Config,
-	T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>,
-{
-	/// Derive new `RentParams` from the passed in data.
-	///
-	/// `value` is added to the current free and total balance of the contracts' account.
-	fn new<E: Executable<T>>(
-		account_id: &T::AccountId,
-		value: &BalanceOf<T>,
-		contract: &AliveContractInfo<T>,
-		executable: &E
-	) -> Self {
-		Self {
-			total_balance: T::Currency::total_balance(account_id).saturating_add(*value),
-			free_balance: T::Currency::free_balance(account_id).saturating_add(*value),
-			subsistence_threshold: <Contracts<T>>::subsistence_threshold(),
-			deposit_per_contract: T::DepositPerContract::get(),
-			deposit_per_storage_byte: T::DepositPerStorageByte::get(),
-			deposit_per_storage_item: T::DepositPerStorageItem::get(),
-			rent_allowance: contract.rent_allowance,
-			rent_fraction: T::RentFraction::get(),
-			storage_size: contract.storage_size,
-			code_size: executable.aggregate_code_len(),
-			code_refcount: executable.refcount(),
-			_reserved: None,
-		}
-	}
-}
-
 /// An interface that provides access to the external environment in which the
 /// smart-contract is executed.
 ///
@@ -204,25 +143,6 @@ pub trait Ext: sealing::Sealed {
 		beneficiary: &AccountIdOf<Self::T>,
 	) -> Result<u32, (DispatchError, u32)>;
 
-	/// Restores the given destination contract sacrificing the current one.
-	///
-	/// Since this function removes the self contract eagerly, if succeeded, no further actions should
-	/// be performed on this `Ext` instance.
-	///
-	/// This function will fail if the same contract is present
-	/// on the contract call stack.
-	///
-	/// # Return Value
-	///
-	/// Result<(CallerCodeSize, DestCodeSize), (DispatchError, CallerCodeSize, DestCodesize)>
-	fn restore_to(
-		&mut self,
-		dest: AccountIdOf<Self::T>,
-		code_hash: CodeHash<Self::T>,
-		rent_allowance: BalanceOf<Self::T>,
-		delta: Vec<StorageKey>,
-	) -> Result<(u32, u32), (DispatchError, u32, u32)>;
-
 	/// Transfer some amount of funds into the specified account.
 	fn transfer(
 		&mut self,
@@ -260,8 +180,8 @@ pub trait Ext: sealing::Sealed {
 	/// Returns the minimum balance that is required for creating an account.
 	fn minimum_balance(&self) -> BalanceOf<Self::T>;
 
-	/// Returns the deposit required to create a tombstone upon contract eviction.
-	fn tombstone_deposit(&self) -> BalanceOf<Self::T>;
+	/// Returns the deposit that is required for the storage occupied by a contract itself.
+	fn contract_deposit(&self) -> BalanceOf<Self::T>;
 
 	/// Returns a random number for the current block with the given subject.
 	fn random(&self, subject: &[u8]) -> (SeedOf<Self::T>, BlockNumberOf<Self::T>);
@@ -271,12 +191,6 @@ pub trait Ext: sealing::Sealed {
 	/// There should not be any duplicates in `topics`.
 	fn deposit_event(&mut self, topics: Vec<TopicOf<Self::T>>, data: Vec<u8>);
 
-	/// Set rent allowance of the contract
-	fn set_rent_allowance(&mut self, rent_allowance: BalanceOf<Self::T>);
-
-	/// Rent allowance of the contract
-	fn rent_allowance(&mut self) -> BalanceOf<Self::T>;
-
 	/// Returns the current block number.
 	fn block_number(&self) -> BlockNumberOf<Self::T>;
 
@@ -286,15 +200,13 @@ pub trait Ext: sealing::Sealed {
 	/// Returns the price for the specified amount of weight.
 	fn get_weight_price(&self, weight: Weight) -> BalanceOf<Self::T>;
 
+	/// Get a mutable reference to the contract info of the current contract.
+	#[cfg(test)]
+	fn contract_info(&mut self) -> &mut ContractInfo<Self::T>;
+
 	/// Get a reference to the schedule used by the current call.
 	fn schedule(&self) -> &Schedule<Self::T>;
 
-	/// Information needed for rent calculations.
-	fn rent_params(&self) -> &RentParams<Self::T>;
-
-	/// Information about the required deposit and resulting rent.
-	fn rent_status(&mut self, at_refcount: u32) -> RentStatus<Self::T>;
-
 	/// Get a mutable reference to the nested gas meter.
 	fn gas_meter(&mut self) -> &mut GasMeter<Self::T>;
 
@@ -337,9 +249,6 @@ pub trait Executable<T: Config>: Sized {
 	/// queried for purposes other than execution.
 	fn from_storage_noinstr(code_hash: CodeHash<T>) -> Result<Self, DispatchError>;
 
-	/// Decrements the refcount by one and deletes the code if it drops to zero.
-	fn drop_from_storage(self);
-
 	/// Increment the refcount by one. Fails if the code does not exist on-chain.
 	///
 	/// Returns the size of the original code.
@@ -371,29 +280,6 @@ pub trait Executable<T: Config>: Sized {
 
 	/// Size of the instrumented code in bytes.
 	fn code_len(&self) -> u32;
-
-	/// Sum of instrumented and pristine code len.
-	fn aggregate_code_len(&self) -> u32;
-
-	// The number of contracts using this executable.
-	fn refcount(&self) -> u32;
-
-	/// The storage that is occupied by the instrumented executable and its pristine source.
-	///
-	/// The returned size is already divided by the number of users who share the code.
-	/// This is essentially `aggregate_code_len() / refcount()`.
-	///
-	/// # Note
-	///
-	/// This works with the current in-memory value of refcount. When calling any contract
-	/// without refetching this from storage the result can be inaccurate as it might be
-	/// working with a stale value. Usually this inaccuracy is tolerable.
-	fn occupied_storage(&self) -> u32 {
-		// We disregard the size of the struct itself as the size is completely
-		// dominated by the code size.
-		let len = self.aggregate_code_len();
-		len.checked_div(self.refcount()).unwrap_or(len)
-	}
 }
 
 /// The complete call stack of a contract execution.
@@ -414,6 +300,8 @@ pub struct Stack<'a, T: Config, E> {
 	schedule: &'a Schedule<T>,
 	/// The gas meter where costs are charged to.
 	gas_meter: &'a mut GasMeter<T>,
+	/// The storage meter that tracks the storage deposit of the whole call stack.
+	storage_meter: &'a mut storage::meter::Meter<T>,
 	/// The timestamp at the point of call stack instantiation.
 	timestamp: MomentOf<T>,
 	/// The block number at the time of call stack instantiation.
@@ -451,12 +339,12 @@ pub struct Frame<T: Config> {
 	contract_info: CachedContract<T>,
 	/// The amount of balance transferred by the caller as part of the call.
 	value_transferred: BalanceOf<T>,
-	/// Snapshotted rent information that can be copied to the contract if requested.
-	rent_params: RentParams<T>,
 	/// Determines whether this is a call or instantiate frame.
 	entry_point: ExportedFunction,
 	/// The gas meter capped to the supplied gas limit.
 	nested_meter: GasMeter<T>,
+	/// The storage meter for the individual call.
+	nested_storage: storage::meter::NestedMeter<T>,
 }
 
 /// Parameter passed in when creating a new `Frame`.
@@ -467,7 +355,7 @@ enum FrameArgs<'a, T: Config, E> {
 		/// The account id of the contract that is to be called.
 		dest: T::AccountId,
 		/// If `None` the contract info needs to be reloaded from storage.
-		cached_info: Option<AliveContractInfo<T>>,
+		cached_info: Option<ContractInfo<T>>,
 	},
 	Instantiate {
 		/// The contract or signed origin which instantiates the new contract.
@@ -484,12 +372,12 @@ enum FrameArgs<'a, T: Config, E> {
 /// Describes the different states of a contract as contained in a `Frame`.
 enum CachedContract<T: Config> {
 	/// The cached contract is up to date with the in-storage value.
-	Cached(AliveContractInfo<T>),
+	Cached(ContractInfo<T>),
 	/// A recursive call into the same contract did write to the contract info.
 	///
 	/// In this case the cached contract is stale and needs to be reloaded from storage.
 	Invalidated,
-	/// The current contract executed `terminate` or `restore_to` and removed the contract.
+	/// The current contract executed `terminate` and removed the contract.
 	///
 	/// In this case a reload is neither allowed nor possible. Please note that recursive
 	/// calls cannot remove a contract as this is checked and denied.
@@ -498,13 +386,8 @@ enum CachedContract<T: Config> {
 
 impl<T: Config> Frame<T> {
 	/// Return the `contract_info` of the current contract.
-	fn contract_info(&mut self) -> &mut AliveContractInfo<T> {
-		self.contract_info.as_alive(&self.account_id)
-	}
-
-	/// Invalidate and return the `contract_info` of the current contract.
-	fn invalidate(&mut self) -> AliveContractInfo<T> {
-		self.contract_info.invalidate(&self.account_id)
+	fn contract_info(&mut self) -> &mut ContractInfo<T> {
+		self.contract_info.get(&self.account_id)
 	}
 
 	/// Terminate and return the `contract_info` of the current contract.
@@ -513,7 +396,7 @@ impl<T: Config> Frame<T> {
 	///
 	/// Under no circumstances the contract is allowed to access the `contract_info` after
 	/// a call to this function. This would constitute a programming error in the exec module.
-	fn terminate(&mut self) -> AliveContractInfo<T> {
+	fn terminate(&mut self) -> ContractInfo<T> {
 		self.contract_info.terminate(&self.account_id)
 	}
 }
@@ -528,7 +411,7 @@ macro_rules! get_cached_or_panic_after_load {
 		} else {
 			panic!(
 				"It is impossible to remove a contract that is on the call stack;\
-				See implementations of terminate and restore_to;\
+				See implementations of terminate;\
 				Therefore fetching a contract will never fail while using an account id
 				that is currently active on the call stack;\
 				qed"
@@ -541,28 +424,37 @@ impl<T: Config> CachedContract<T> {
 	/// Load the `contract_info` from storage if necessary.
 	fn load(&mut self, account_id: &T::AccountId) {
 		if let CachedContract::Invalidated = self {
-			let contract = <ContractInfoOf<T>>::get(&account_id)
-				.and_then(|contract| contract.get_alive());
+			let contract = <ContractInfoOf<T>>::get(&account_id);
 			if let Some(contract) = contract {
 				*self = CachedContract::Cached(contract);
 			}
 		}
 	}
 
-	/// Return the cached contract_info as alive contract info.
-	fn as_alive(&mut self, account_id: &T::AccountId) -> &mut AliveContractInfo<T> {
+	/// Return the cached contract_info.
+	fn get(&mut self, account_id: &T::AccountId) -> &mut ContractInfo<T> {
 		self.load(account_id);
 		get_cached_or_panic_after_load!(self)
 	}
 
-	/// Invalidate and return the contract info.
-	fn invalidate(&mut self, account_id: &T::AccountId) -> AliveContractInfo<T> {
-		self.load(account_id);
-		get_cached_or_panic_after_load!(mem::replace(self, Self::Invalidated))
+	/// Return `Some(&mut ContractInfo)` if the contract is in cached state. `None` otherwise.
+	fn as_contract(&mut self) -> Option<&mut ContractInfo<T>> {
+		match self {
+			CachedContract::Cached(contract) => Some(contract),
+			_ => None,
+		}
+	}
+
+	/// Return `Some(ContractInfo)` if the contract is in cached state. `None` otherwise.
+	fn into_contract(self) -> Option<ContractInfo<T>> {
+		match self {
+			CachedContract::Cached(contract) => Some(contract),
+			_ => None,
+		}
 	}
 
 	/// Terminate and return the contract info.
-	fn terminate(&mut self, account_id: &T::AccountId) -> AliveContractInfo<T> {
+	fn terminate(&mut self, account_id: &T::AccountId) -> ContractInfo<T> {
 		self.load(account_id);
 		get_cached_or_panic_after_load!(mem::replace(self, Self::Terminated))
 	}
@@ -588,6 +480,7 @@ where
 		origin: T::AccountId,
 		dest: T::AccountId,
 		gas_meter: &'a mut GasMeter<T>,
+		storage_meter: &'a mut storage::meter::Meter<T>,
 		schedule: &'a Schedule<T>,
 		value: BalanceOf<T>,
 		input_data: Vec<u8>,
@@ -597,6 +490,7 @@ where
 			FrameArgs::Call{dest, cached_info: None},
 			origin,
 			gas_meter,
+			storage_meter,
 			schedule,
 			value,
 			debug_message,
@@ -618,6 +512,7 @@ where
 		origin: T::AccountId,
 		executable: E,
 		gas_meter: &'a mut GasMeter<T>,
+		storage_meter: &'a mut storage::meter::Meter<T>,
 		schedule: &'a Schedule<T>,
 		value: BalanceOf<T>,
 		input_data: Vec<u8>,
@@ -633,6 +528,7 @@ where
 			},
 			origin,
 			gas_meter,
+			storage_meter,
 			schedule,
 			value,
 			debug_message,
@@ -648,15 +544,19 @@ where
 		args: FrameArgs<T, E>,
 		origin: T::AccountId,
 		gas_meter: &'a mut GasMeter<T>,
+		storage_meter: &'a mut storage::meter::Meter<T>,
 		schedule: &'a Schedule<T>,
 		value: BalanceOf<T>,
 		debug_message: Option<&'a mut Vec<u8>>,
 	) -> Result<(Self, E), (ExecError, u32)> {
-		let (first_frame, executable) = Self::new_frame(args, value, gas_meter, 0, &schedule)?;
+		let (first_frame, executable) = Self::new_frame(
+			args, value, gas_meter, storage_meter, 0, &schedule,
+		)?;
 		let stack = Self {
 			origin,
 			schedule,
 			gas_meter,
+			storage_meter,
 			timestamp: T::Time::now(),
 			block_number: <frame_system::Pallet<T>>::block_number(),
 			account_counter: None,
@@ -673,10 +573,11 @@ where
 	///
 	/// This does not take `self` because when constructing the first frame `self` is
 	/// not initialized, yet.
-	fn new_frame(
+	fn new_frame<S: storage::meter::State>(
 		frame_args: FrameArgs<T, E>,
 		value_transferred: BalanceOf<T>,
 		gas_meter: &mut GasMeter<T>,
+		storage_meter: &storage::meter::GenericMeter<T, S>,
 		gas_limit: Weight,
 		schedule: &Schedule<T>
 	) -> Result<(Frame<T>, E), (ExecError, u32)> {
@@ -686,25 +587,12 @@ where
 					contract
 				} else {
 					<ContractInfoOf<T>>::get(&dest)
-						.ok_or((<Error<T>>::ContractNotFound.into(), 0))
-						.and_then(|contract|
-							contract.get_alive()
-								.ok_or((<Error<T>>::ContractIsTombstone.into(), 0))
-						)?
+						.ok_or((<Error<T>>::ContractNotFound.into(), 0))?
 				};
 
 				let executable = E::from_storage(contract.code_hash, schedule, gas_meter)
 					.map_err(|e| (e.into(), 0))?;
 
-				// This charges the rent and denies access to a contract that is in need of
-				// eviction by returning `None`. We cannot evict eagerly here because those
-				// changes would be rolled back in case this contract is called by another
-				// contract.
-				// See: https://github.com/paritytech/substrate/issues/6439#issuecomment-648754324
-				let contract = Rent::<T, E>
-					::charge(&dest, contract, executable.occupied_storage())
-					.map_err(|e| (e.into(), executable.code_len()))?
-					.ok_or((Error::<T>::RentNotPaid.into(), executable.code_len()))?;
 				(dest, contract, executable, ExportedFunction::Call)
 			}
 			FrameArgs::Instantiate{sender, trie_seed, executable, salt} => {
@@ -722,15 +610,13 @@ where
 		};
 
 		let frame = Frame {
-			rent_params: RentParams::new(
-				&account_id, &value_transferred, &contract_info, &executable,
-			),
 			value_transferred,
 			contract_info: CachedContract::Cached(contract_info),
 			account_id,
 			entry_point,
 			nested_meter: gas_meter.nested(gas_limit)
 				.map_err(|e| (e.into(), executable.code_len()))?,
+			nested_storage: storage_meter.nested(),
 		};
 
 		Ok((frame, executable))
@@ -755,20 +641,15 @@ where
 		if let (CachedContract::Cached(contract), ExportedFunction::Call) =
 			(&frame.contract_info, frame.entry_point)
 		{
-			<ContractInfoOf<T>>::insert(
-				frame.account_id.clone(),
-				ContractInfo::Alive(contract.clone()),
-			);
+			<ContractInfoOf<T>>::insert(frame.account_id.clone(), contract.clone());
 		}
 
-		let nested_meter = &mut self.frames
-			.last_mut()
-			.unwrap_or(&mut self.first_frame)
-			.nested_meter;
+		let frame = self.frames.last_mut().unwrap_or(&mut self.first_frame);
 		let (frame, executable) = Self::new_frame(
 			frame_args,
 			value_transferred,
-			nested_meter,
+			&mut frame.nested_meter,
+			&frame.nested_storage,
 			gas_limit,
 			self.schedule,
 		)?;
@@ -786,16 +667,18 @@ where
 	) -> Result<(ExecReturnValue, u32), (ExecError, u32)> {
 		let entry_point = self.top_frame().entry_point;
 		let do_transaction = || {
-			// Cache the value before calling into the constructor because that
-			// consumes the value. If the constructor creates additional contracts using
-			// the same code hash we still charge the "1 block rent" as if they weren't
-			// spawned. This is OK as overcharging is always safe.
-			let occupied_storage = executable.occupied_storage();
 			let code_len = executable.code_len();
 
 			// Every call or instantiate also optionally transferres balance.
 			self.initial_transfer().map_err(|e| (ExecError::from(e), code_len))?;
 
+			// The storage occupied by a new contract itself is paid for by the origin. This is
+			// charged after the endowment was transferred so that the contract's account exists.
+			if entry_point == ExportedFunction::Constructor {
+				self.top_frame_mut().nested_storage.charge_instantiate()
+					.map_err(|e| (ExecError::from(e), code_len))?;
+			}
+
 			// Call into the wasm blob.
 			let output = executable.execute(
 				self,
@@ -813,17 +696,6 @@ where
 					return Err((Error::<T>::TerminatedInConstructor.into(), code_len));
 				}
 
-				// Collect the rent for the first block to prevent the creation of very large
-				// contracts that never intended to pay for even one block.
-				// This also makes sure that it is above the subsistence threshold
-				// in order to keep up the guarantuee that we always leave a tombstone behind
-				// with the exception of a contract that called `seal_terminate`.
-				let contract = Rent::<T, E>
-					::charge(&account_id, frame.invalidate(), occupied_storage)
-					.map_err(|e| (e.into(), code_len))?
-					.ok_or((Error::<T>::NewContractNotFunded.into(), code_len))?;
-				frame.contract_info = CachedContract::Cached(contract);
-
 				// Deposit an instantiation event.
 				deposit_event::<T>(vec![], Event::Instantiated(
 					self.caller().clone(),
@@ -865,15 +737,30 @@ where
 		// A `None` means that we are returning from the `first_frame`.
 		let frame = self.frames.pop();
 
-		if let Some(frame) = frame {
-			let prev = self.top_frame_mut();
+		if let Some(mut frame) = frame {
 			let account_id = &frame.account_id;
+			let prev = self.frames.last_mut().unwrap_or(&mut self.first_frame);
 			prev.nested_meter.absorb_nested(frame.nested_meter);
 			// Only gas counter changes are persisted in case of a failure.
 			if !persist {
 				return;
 			}
-			if let CachedContract::Cached(contract) = frame.contract_info {
+
+			// Record the storage meter changes of the nested call into the parent meter.
+			// If the dropped frame's contract wasn't terminated we update the deposit counter
+			// in its contract info. The load is necessary to pull it from storage in case
+			// it was invalidated.
+			frame.contract_info.load(account_id);
+			let mut contract = frame.contract_info.into_contract();
+			prev.nested_storage.absorb(
+				frame.nested_storage,
+				&self.origin,
+				account_id,
+				contract.as_mut(),
+			);
+
+			// In case the contract wasn't terminated we need to persist changes made to it.
+			if let Some(contract) = contract {
 				// optimization: Predecessor is the same contract.
 				// We can just copy the contract into the predecessor without a storage write.
 				// This is possible when there is no other contract in-between that could
@@ -888,7 +775,7 @@ where
 				// because that case is already handled by the optimization above. Only the first
 				// cache needs to be invalidated because that one will invalidate the next cache
 				// when it is popped from the stack.
-				<ContractInfoOf<T>>::insert(account_id, ContractInfo::Alive(contract));
+				<ContractInfoOf<T>>::insert(account_id, contract);
 				if let Some(c) = self.frames_mut().skip(1).find(|f| f.account_id == *account_id) {
 					c.contract_info = CachedContract::Invalidated;
 				}
@@ -907,11 +794,17 @@ where
 			if !persist {
 				return;
 			}
-			if let CachedContract::Cached(contract) = &self.first_frame.contract_info {
-				<ContractInfoOf<T>>::insert(
-					&self.first_frame.account_id,
-					ContractInfo::Alive(contract.clone())
-				);
+			let frame = &mut self.first_frame;
+			frame.contract_info.load(&frame.account_id);
+			let mut contract = frame.contract_info.as_contract();
+			self.storage_meter.absorb(
+				mem::take(&mut frame.nested_storage),
+				&self.origin,
+				&frame.account_id,
+				contract.as_deref_mut(),
+			);
+			if let Some(contract) = contract {
+				<ContractInfoOf<T>>::insert(&frame.account_id, contract.clone());
 			}
 			if let Some(counter) = self.account_counter {
 				<AccountCounter<T>>::set(counter);
@@ -922,9 +815,9 @@ where
 	/// Transfer some funds from `from` to `to`.
 	///
 	/// We only allow allow for draining all funds of the sender if `allow_death` is
-	/// is specified as `true`. Otherwise, any transfer that would bring the sender below the
-	/// subsistence threshold (for contracts) or the existential deposit (for plain accounts)
-	/// results in an error.
+	/// is specified as `true`. Otherwise, any transfer that would bring the free balance of the
+	/// sender below the subsistence threshold (for contracts) or the existential deposit
+	/// (for plain accounts) results in an error.
 	fn transfer(
 		sender_is_contract: bool,
 		allow_death: bool,
@@ -1099,9 +992,10 @@ where
 		if self.is_recursive() {
 			return Err((Error::<T>::ReentranceDenied.into(), 0));
 		}
-		let frame = self.top_frame_mut();
+		let frame = self.frames.last_mut().unwrap_or(&mut self.first_frame);
 		let info = frame.terminate();
 		Storage::<T>::queue_trie_for_deletion(&info).map_err(|e| (e, 0))?;
+		frame.nested_storage.terminate(&self.origin, &frame.account_id, &info);
 		<Stack<'a, T, E>>::transfer(
 			true,
 			true,
@@ -1117,40 +1011,6 @@ where
 		Ok(code_len)
 	}
 
-	fn restore_to(
-		&mut self,
-		dest: AccountIdOf<Self::T>,
-		code_hash: CodeHash<Self::T>,
-		rent_allowance: BalanceOf<Self::T>,
-		delta: Vec<StorageKey>,
-	) -> Result<(u32, u32), (DispatchError, u32, u32)> {
-		if self.is_recursive() {
-			return Err((Error::<T>::ReentranceDenied.into(), 0, 0));
-		}
-		let origin_contract = self.top_frame_mut().contract_info().clone();
-		let result = Rent::<T, E>::restore_to(
-			&self.top_frame().account_id,
-			origin_contract,
-			dest.clone(),
-			code_hash.clone(),
-			rent_allowance,
-			delta,
-		);
-		if let Ok(_) = result {
-			deposit_event::<Self::T>(
-				vec![],
-				Event::Restored(
-					self.top_frame().account_id.clone(),
-					dest,
-					code_hash,
-					rent_allowance,
-				),
-			);
-			self.top_frame_mut().terminate();
-		}
-		result
-	}
-
 	fn transfer(
 		&mut self,
 		to: &T::AccountId,
@@ -1164,10 +1024,12 @@ where
 	}
 
 	fn set_storage(&mut self, key: StorageKey, value: Option<Vec<u8>>) -> DispatchResult {
-		let block_number = self.block_number;
 		let frame = self.top_frame_mut();
 		Storage::<T>::write(
-			block_number, frame.contract_info(), &key, value,
+			frame.contract_info.get(&frame.account_id),
+			&key,
+			value,
+			Some(&mut frame.nested_storage),
 		)
 	}
 
@@ -1199,8 +1061,8 @@ where
 		T::Currency::minimum_balance()
 	}
 
-	fn tombstone_deposit(&self) -> BalanceOf<T> {
-		T::TombstoneDeposit::get()
+	fn contract_deposit(&self) -> BalanceOf<T> {
+		T::ContractDeposit::get()
 	}
 
 	fn deposit_event(&mut self, topics: Vec<T::Hash>, data: Vec<u8>) {
@@ -1210,14 +1072,6 @@ where
 		);
 	}
 
-	fn set_rent_allowance(&mut self, rent_allowance: BalanceOf<T>) {
-		self.top_frame_mut().contract_info().rent_allowance = rent_allowance;
-	}
-
-	fn rent_allowance(&mut self) -> BalanceOf<T> {
-		self.top_frame_mut().contract_info().rent_allowance
-	}
-
 	fn block_number(&self) -> T::BlockNumber { self.block_number }
 
 	fn max_value_size(&self) -> u32 {
@@ -1228,26 +1082,13 @@ where
 		T::WeightPrice::convert(weight)
 	}
 
-	fn schedule(&self) -> &Schedule<Self::T> {
-		&self.schedule
-	}
-
-	fn rent_params(&self) -> &RentParams<Self::T> {
-		&self.top_frame().rent_params
+	#[cfg(test)]
+	fn contract_info(&mut self) -> &mut ContractInfo<Self::T> {
+		self.top_frame_mut().contract_info()
 	}
 
-	fn rent_status(&mut self, at_refcount: u32) -> RentStatus<Self::T> {
-		let frame = self.top_frame_mut();
-		let balance = T::Currency::free_balance(&frame.account_id);
-		let code_size = frame.rent_params.code_size;
-		let refcount = frame.rent_params.code_refcount;
-		<Rent<T, E>>::rent_status(
-			&balance,
-			&frame.contract_info(),
-			code_size,
-			refcount,
-			at_refcount,
-		)
+	fn schedule(&self) -> &Schedule<Self::T> {
+		&self.schedule
 	}
 
 	fn gas_meter(&mut self) -> &mut GasMeter<Self::T> {
@@ -1397,17 +1238,6 @@ mod tests {
 				}
 			});
 		}
-
-		fn refcount(code_hash: &CodeHash<Test>) -> u32 {
-			LOADER.with(|loader| {
-				loader
-					.borrow()
-					.map
-					.get(code_hash)
-					.expect("code_hash does not exist")
-					.refcount()
-			})
-		}
 	}
 
 	impl Executable<Test> for MockExecutable {
@@ -1429,10 +1259,6 @@ mod tests {
 			})
 		}
 
-		fn drop_from_storage(self) {
-			MockLoader::decrement_refcount(self.code_hash);
-		}
-
 		fn add_user(code_hash: CodeHash<Test>) -> Result<u32, DispatchError> {
 			MockLoader::increment_refcount(code_hash);
 			Ok(0)
@@ -1469,14 +1295,6 @@ mod tests {
 		fn code_len(&self) -> u32 {
 			0
 		}
-
-		fn aggregate_code_len(&self) -> u32 {
-			0
-		}
-
-		fn refcount(&self) -> u32 {
-			self.refcount as u32
-		}
 	}
 
 	fn exec_success() -> ExecResult {
@@ -1506,7 +1324,14 @@ mod tests {
 
 			assert_matches!(
 				MockStack::run_call(
-					ALICE, BOB, &mut gas_meter, &schedule, value, vec![], None,
+					ALICE,
+					BOB,
+					&mut gas_meter,
+					&mut storage::meter::Meter::new(&ALICE, Some(0), 0).unwrap(),
+					&schedule,
+					value,
+					vec![],
+					None,
 				),
 				Ok(_)
 			);
@@ -1561,6 +1386,7 @@ mod tests {
 				origin.clone(),
 				dest.clone(),
 				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&mut storage::meter::Meter::new(&origin, Some(0), 0).unwrap(),
 				&schedule,
 				55,
 				vec![],
@@ -1569,9 +1395,7 @@ mod tests {
 
 			assert!(!output.0.is_success());
 			assert_eq!(get_balance(&origin), 100);
-
-			// the rent is still charged
-			assert!(get_balance(&dest) < balance);
+			assert_eq!(get_balance(&dest), balance);
 		});
 	}
 
@@ -1621,6 +1445,7 @@ mod tests {
 				origin,
 				dest,
 				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&mut storage::meter::Meter::new(&origin, Some(0), 0).unwrap(),
 				&schedule,
 				0,
 				vec![],
@@ -1652,6 +1477,7 @@ mod tests {
 				origin,
 				dest,
 				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&mut storage::meter::Meter::new(&origin, Some(0), 0).unwrap(),
 				&schedule,
 				0,
 				vec![],
@@ -1680,6 +1506,7 @@ mod tests {
 				ALICE,
 				BOB,
 				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&mut storage::meter::Meter::new(&ALICE, Some(0), 0).unwrap(),
 				&schedule,
 				0,
 				vec![1, 2, 3, 4],
@@ -1705,12 +1532,13 @@ mod tests {
 				input_data_ch, &schedule, &mut gas_meter
 			).unwrap();
 
-			set_balance(&ALICE, subsistence * 10);
+			set_balance(&ALICE, subsistence * 100);
 
 			let result = MockStack::run_instantiate(
 				ALICE,
 				executable,
 				&mut gas_meter,
+				&mut storage::meter::Meter::new(&ALICE, None, 0).unwrap(),
 				&schedule,
 				subsistence * 3,
 				vec![1, 2, 3, 4],
@@ -1761,6 +1589,7 @@ mod tests {
 				ALICE,
 				BOB,
 				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&mut storage::meter::Meter::new(&ALICE, Some(0), 0).unwrap(),
 				&schedule,
 				value,
 				vec![],
@@ -1811,6 +1640,7 @@ mod tests {
 				origin.clone(),
 				dest.clone(),
 				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&mut storage::meter::Meter::new(&origin, Some(0), 0).unwrap(),
 				&schedule,
 				0,
 				vec![],
@@ -1851,6 +1681,7 @@ mod tests {
 				ALICE,
 				BOB,
 				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&mut storage::meter::Meter::new(&ALICE, Some(0), 0).unwrap(),
 				&schedule,
 				0,
 				vec![],
@@ -1877,6 +1708,7 @@ mod tests {
 					ALICE,
 					executable,
 					&mut gas_meter,
+					&mut storage::meter::Meter::new(&ALICE, None, 0).unwrap(),
 					&schedule,
 					0, // <- zero endowment
 					vec![],
@@ -1908,6 +1740,7 @@ mod tests {
 					ALICE,
 					executable,
 					&mut gas_meter,
+					&mut storage::meter::Meter::new(&ALICE, None, 0).unwrap(),
 					&schedule,
 					100,
 					vec![],
@@ -1946,6 +1779,7 @@ mod tests {
 					ALICE,
 					executable,
 					&mut gas_meter,
+					&mut storage::meter::Meter::new(&ALICE, None, 0).unwrap(),
 					&schedule,
 					100,
 					vec![],
@@ -1990,7 +1824,14 @@ mod tests {
 
 			assert_matches!(
 				MockStack::run_call(
-					ALICE, BOB, &mut GasMeter::<Test>::new(GAS_LIMIT), &schedule, 20, vec![], None,
+					ALICE,
+					BOB,
+					&mut GasMeter::<Test>::new(GAS_LIMIT),
+					&mut storage::meter::Meter::new(&ALICE, None, 0).unwrap(),
+					&schedule,
+					20,
+					vec![],
+					None,
 				),
 				Ok(_)
 			);
@@ -2041,7 +1882,14 @@ mod tests {
 
 			assert_matches!(
 				MockStack::run_call(
-					ALICE, BOB, &mut GasMeter::<Test>::new(GAS_LIMIT), &schedule, 20, vec![], None,
+					ALICE,
+					BOB,
+					&mut GasMeter::<Test>::new(GAS_LIMIT),
+					&mut storage::meter::Meter::new(&ALICE, None, 0).unwrap(),
+					&schedule,
+					20,
+					vec![],
+					None,
 				),
 				Ok(_)
 			);
@@ -2075,6 +1923,7 @@ mod tests {
 						ALICE,
 						executable,
 						&mut gas_meter,
+						&mut storage::meter::Meter::new(&ALICE, None, 0).unwrap(),
 						&schedule,
 						100,
 						vec![],
@@ -2091,161 +1940,6 @@ mod tests {
 			});
 	}
 
-	#[test]
-	fn rent_allowance() {
-		let rent_allowance_ch = MockLoader::insert(Constructor, |ctx, _| {
-			let subsistence = Contracts::<Test>::subsistence_threshold();
-			let allowance = subsistence * 3;
-			assert_eq!(ctx.ext.rent_allowance(), <BalanceOf<Test>>::max_value());
-			ctx.ext.set_rent_allowance(allowance);
-			assert_eq!(ctx.ext.rent_allowance(), allowance);
-			exec_success()
-		});
-
-		ExtBuilder::default().build().execute_with(|| {
-			let subsistence = Contracts::<Test>::subsistence_threshold();
-			let schedule = <Test as Config>::Schedule::get();
-			let mut gas_meter = GasMeter::<Test>::new(GAS_LIMIT);
-			let executable = MockExecutable::from_storage(
-				rent_allowance_ch, &schedule, &mut gas_meter
-			).unwrap();
-			set_balance(&ALICE, subsistence * 10);
-
-			let result = MockStack::run_instantiate(
-				ALICE,
-				executable,
-				&mut gas_meter,
-				&schedule,
-				subsistence * 5,
-				vec![],
-				&[],
-				None,
-			);
-			assert_matches!(result, Ok(_));
-		});
-	}
-
-	#[test]
-	fn rent_params_works() {
-		let code_hash = MockLoader::insert(Call, |ctx, executable| {
-			let address = ctx.ext.address();
-			let contract = <ContractInfoOf<Test>>::get(address)
-				.and_then(|c| c.get_alive())
-				.unwrap();
-			assert_eq!(ctx.ext.rent_params(), &RentParams::new(address, &0, &contract, executable));
-			exec_success()
-		});
-
-		ExtBuilder::default().build().execute_with(|| {
-			let subsistence = Contracts::<Test>::subsistence_threshold();
-			let schedule = <Test as Config>::Schedule::get();
-			let mut gas_meter = GasMeter::<Test>::new(GAS_LIMIT);
-			set_balance(&ALICE, subsistence * 10);
-			place_contract(&BOB, code_hash);
-			MockStack::run_call(
-				ALICE,
-				BOB,
-				&mut gas_meter,
-				&schedule,
-				0,
-				vec![],
-				None,
-			).unwrap();
-		});
-	}
-
-	#[test]
-	fn rent_params_snapshotted() {
-		let code_hash = MockLoader::insert(Call, |ctx, executable| {
-			let subsistence = Contracts::<Test>::subsistence_threshold();
-			let address = ctx.ext.address();
-			let contract = <ContractInfoOf<Test>>::get(address)
-				.and_then(|c| c.get_alive())
-				.unwrap();
-			let rent_params = RentParams::new(address, &0, &contract, executable);
-
-			// Changing the allowance during the call: rent params stay unchanged.
-			let allowance = 42;
-			assert_ne!(allowance, rent_params.rent_allowance);
-			ctx.ext.set_rent_allowance(allowance);
-			assert_eq!(ctx.ext.rent_params(), &rent_params);
-
-			// Creating another instance from the same code_hash increases the refcount.
-			// This is also not reflected in the rent params.
-			assert_eq!(MockLoader::refcount(&executable.code_hash), 1);
-			ctx.ext.instantiate(
-				0,
-				executable.code_hash,
-				subsistence * 25,
-				vec![],
-				&[],
-			).unwrap();
-			assert_eq!(MockLoader::refcount(&executable.code_hash), 2);
-			assert_eq!(ctx.ext.rent_params(), &rent_params);
-
-			exec_success()
-		});
-
-		ExtBuilder::default().build().execute_with(|| {
-			let subsistence = Contracts::<Test>::subsistence_threshold();
-			let schedule = <Test as Config>::Schedule::get();
-			let mut gas_meter = GasMeter::<Test>::new(GAS_LIMIT);
-			set_balance(&ALICE, subsistence * 100);
-			place_contract(&BOB, code_hash);
-			MockStack::run_call(
-				ALICE,
-				BOB,
-				&mut gas_meter,
-				&schedule,
-				subsistence * 50,
-				vec![],
-				None,
-			).unwrap();
-		});
-	}
-
-	#[test]
-	fn rent_status_works() {
-		let code_hash = MockLoader::insert(Call, |ctx, _| {
-			assert_eq!(ctx.ext.rent_status(0), RentStatus {
-				max_deposit: 80000,
-				current_deposit: 80000,
-				custom_refcount_deposit: None,
-				max_rent: 32,
-				current_rent: 32,
-				custom_refcount_rent: None,
-				_reserved: None,
-			});
-			assert_eq!(ctx.ext.rent_status(1), RentStatus {
-				max_deposit: 80000,
-				current_deposit: 80000,
-				custom_refcount_deposit: Some(80000),
-				max_rent: 32,
-				current_rent: 32,
-				custom_refcount_rent: Some(32),
-				_reserved: None,
-			});
-			exec_success()
-		});
-
-		ExtBuilder::default().build().execute_with(|| {
-			let subsistence = Contracts::<Test>::subsistence_threshold();
-			let schedule = <Test as Config>::Schedule::get();
-			let mut gas_meter = GasMeter::<Test>::new(GAS_LIMIT);
-			set_balance(&ALICE, subsistence * 10);
-			place_contract(&BOB, code_hash);
-			MockStack::run_call(
-				ALICE,
-				BOB,
-				&mut gas_meter,
-				&schedule,
-				0,
-				vec![],
-				None,
-			).unwrap();
-		});
-	}
-
 	#[test]
 	fn in_memory_changes_not_discarded() {
 		// Call stack: BOB -> CHARLIE (trap) -> BOB' (success)
@@ -2258,16 +1952,14 @@ mod tests {
 		// are made before calling into CHARLIE are not discarded.
 		let code_bob = MockLoader::insert(Call, |ctx, _| {
 			if ctx.input_data[0] == 0 {
-				let original_allowance = ctx.ext.rent_allowance();
-				let changed_allowance = <BalanceOf<Test>>::max_value() / 2;
-				assert_ne!(original_allowance, changed_allowance);
-				ctx.ext.set_rent_allowance(changed_allowance);
+				let info = ctx.ext.contract_info();
+				assert_eq!(info.storage_deposit, 0);
+				info.storage_deposit = 42;
 				assert_eq!(
 					ctx.ext.call(0, CHARLIE, 0, vec![]).map(|v| v.0).map_err(|e| e.0),
 					exec_trapped()
 				);
-				assert_eq!(ctx.ext.rent_allowance(), changed_allowance);
-				assert_ne!(ctx.ext.rent_allowance(), original_allowance);
+				assert_eq!(ctx.ext.contract_info().storage_deposit, 42);
 			}
 			exec_success()
 		});
@@ -2286,6 +1978,7 @@ mod tests {
 				ALICE,
 				BOB,
 				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&mut storage::meter::Meter::new(&ALICE, Some(0), 0).unwrap(),
 				&schedule,
 				0,
 				vec![0],
@@ -2314,12 +2007,13 @@ mod tests {
 				code, &schedule, &mut gas_meter
 			).unwrap();
 
-			set_balance(&ALICE, subsistence * 10);
+			set_balance(&ALICE, subsistence * 100);
 
 			let result = MockStack::run_instantiate(
 				ALICE,
 				executable,
 				&mut gas_meter,
+				&mut storage::meter::Meter::new(&ALICE, None, 0).unwrap(),
 				&schedule,
 				subsistence * 3,
 				vec![],
@@ -2350,6 +2044,7 @@ mod tests {
 				ALICE,
 				BOB,
 				&mut gas_meter,
+				&mut storage::meter::Meter::new(&ALICE, Some(0), 0).unwrap(),
 				&schedule,
 				0,
 				vec![],
@@ -2380,6 +2075,7 @@ mod tests {
 				ALICE,
 				BOB,
 				&mut gas_meter,
+				&mut storage::meter::Meter::new(&ALICE, Some(0), 0).unwrap(),
 				&schedule,
 				0,
 				vec![],
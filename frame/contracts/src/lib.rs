@@ -39,6 +39,15 @@
 //! Finally, when an account is reaped, its associated code and storage of the smart-contract account
 //! will also be deleted.
 //!
+//! ### Storage Deposit
+//!
+//! Contracts do not pay rent for the storage they occupy. Instead, the origin of a call stack
+//! puts down a deposit for all storage that is newly created during its execution. This
+//! deposit is reserved on the account of the contract whose storage it pays for. Whenever
+//! storage is freed the deposit is refunded to the origin that freed it. Terminating a contract
+//! refunds its whole deposit. How much an origin is willing to deposit can be capped with the
+//! `storage_deposit_limit` of each dispatchable.
+//!
 //! ### Gas
 //!
 //! Senders must specify a gas limit with every call, as all instructions invoked by the smart-contract require gas.
@@ -66,7 +75,6 @@
 //! * [`Pallet::instantiate`] - The same as `instantiate_with_code` but instead of uploading new
 //! code an existing `code_hash` is supplied.
 //! * [`Pallet::call`] - Makes a call to an account, optionally transferring some balance.
-//! * [`Pallet::claim_surcharge`] - Evict a contract that cannot pay rent anymore.
 //!
 //! ## Usage
 //!
@@ -89,7 +97,6 @@ mod gas;
 mod storage;
 mod exec;
 mod wasm;
-mod rent;
 mod benchmarking;
 mod schedule;
 mod migration;
@@ -104,35 +111,27 @@ pub use crate::{pallet::*, schedule::Schedule, exec::Frame};
 use crate::{
 	gas::GasMeter,
 	exec::{Stack as ExecStack, Executable},
-	rent::Rent,
-	storage::{Storage, DeletedContract, ContractInfo, AliveContractInfo, TombstoneContractInfo},
+	storage::{Storage, DeletedContract, ContractInfo, meter::Meter as StorageMeter},
 	weights::WeightInfo,
 	wasm::PrefabWasmModule,
 };
 use sp_core::{Bytes, crypto::UncheckedFrom};
 use sp_std::prelude::*;
-use sp_runtime::{
-	traits::{
-		Hash, StaticLookup, Convert, Saturating, Zero,
-	},
-	Perbill,
-};
+use sp_runtime::traits::{Hash, StaticLookup, Convert};
 use frame_support::{
-	traits::{OnUnbalanced, Currency, Get, Time, Randomness},
-	weights::{Weight, PostDispatchInfo, WithPostDispatchInfo},
+	traits::{ReservableCurrency, Currency, Get, Time, Randomness},
+	weights::Weight,
 };
 use frame_system::Pallet as System;
 use pallet_contracts_primitives::{
-	RentProjectionResult, GetStorageResult, ContractAccessError, ContractExecResult,
-	ContractInstantiateResult, Code, InstantiateReturnValue,
+	GetStorageResult, ContractAccessError, ContractExecResult, ContractInstantiateResult, Code,
+	InstantiateReturnValue,
 };
 
 type CodeHash<T> = <T as frame_system::Config>::Hash;
 type TrieId = Vec<u8>;
 type BalanceOf<T> =
 	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
-type NegativeImbalanceOf<T> =
-	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -149,14 +148,13 @@ pub mod pallet {
 		type Randomness: Randomness<Self::Hash, Self::BlockNumber>;
 
 		/// The currency in which fees are paid and contract balances are held.
-		type Currency: Currency<Self::AccountId>;
+		///
+		/// Storage deposits are reserved on the account of the contract they pay for.
+		type Currency: ReservableCurrency<Self::AccountId>;
 
 		/// The overarching event type.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
-		/// Handler for rent payments.
-		type RentPayment: OnUnbalanced<NegativeImbalanceOf<Self>>;
-
 		/// Used to answer contracts' queries regarding the current weight price. This is **not**
 		/// used to calculate the actual fee and is only for informational purposes.
 		type WeightPrice: Convert<Weight, BalanceOf<Self>>;
@@ -172,55 +170,28 @@ pub mod pallet {
 		#[pallet::constant]
 		type Schedule: Get<Schedule<Self>>;
 
-		/// Number of block delay an extrinsic claim surcharge has.
+		/// The amount of balance a caller has to pay for the storage occupied by a new contract.
 		///
-		/// When claim surcharge is called by an extrinsic the rent is checked
-		/// for current_block - delay
-		#[pallet::constant]
-		type SignedClaimHandicap: Get<Self::BlockNumber>;
-
-		/// The minimum amount required to generate a tombstone.
+		/// This is deposited in addition to the deposit for the contract's own storage items
+		/// and refunded when the contract is terminated.
 		#[pallet::constant]
-		type TombstoneDeposit: Get<BalanceOf<Self>>;
+		type ContractDeposit: Get<BalanceOf<Self>>;
 
-		/// The balance every contract needs to deposit to stay alive indefinitely.
-		///
-		/// This is different from the [`Self::TombstoneDeposit`] because this only needs to be
-		/// deposited while the contract is alive. Costs for additional storage are added to
-		/// this base cost.
+		/// The amount of balance a caller has to pay for each byte of storage.
 		///
-		/// This is a simple way to ensure that contracts with empty storage eventually get deleted by
-		/// making them pay rent. This creates an incentive to remove them early in order to save rent.
-		#[pallet::constant]
-		type DepositPerContract: Get<BalanceOf<Self>>;
-
-		/// The balance a contract needs to deposit per storage byte to stay alive indefinitely.
+		/// # Note
 		///
-		/// Let's suppose the deposit is 1,000 BU (balance units)/byte and the rent is 1 BU/byte/day,
-		/// then a contract with 1,000,000 BU that uses 1,000 bytes of storage would pay no rent.
-		/// But if the balance reduced to 500,000 BU and the storage stayed the same at 1,000,
-		/// then it would pay 500 BU/day.
+		/// Changing this value for an existing chain might need a storage migration.
 		#[pallet::constant]
-		type DepositPerStorageByte: Get<BalanceOf<Self>>;
+		type DepositPerByte: Get<BalanceOf<Self>>;
 
-		/// The balance a contract needs to deposit per storage item to stay alive indefinitely.
+		/// The amount of balance a caller has to pay for each storage item.
 		///
-		/// It works the same as [`Self::DepositPerStorageByte`] but for storage items.
-		#[pallet::constant]
-		type DepositPerStorageItem: Get<BalanceOf<Self>>;
-
-		/// The fraction of the deposit that should be used as rent per block.
+		/// # Note
 		///
-		/// When a contract hasn't enough balance deposited to stay alive indefinitely it needs
-		/// to pay per block for the storage it consumes that is not covered by the deposit.
-		/// This determines how high this rent payment is per block as a fraction of the deposit.
-		#[pallet::constant]
-		type RentFraction: Get<Perbill>;
-
-		/// Reward that is received by the party whose touch has led
-		/// to removal of a contract.
+		/// Changing this value for an existing chain might need a storage migration.
 		#[pallet::constant]
-		type SurchargeReward: Get<BalanceOf<Self>>;
+		type DepositPerItem: Get<BalanceOf<Self>>;
 
 		/// The type of the call stack determines the maximum nesting depth of contract calls.
 		///
@@ -275,6 +246,12 @@ pub mod pallet {
 		/// * If the account is a regular account, any value will be transferred.
 		/// * If no account exists and the call value is not less than `existential_deposit`,
 		/// a regular account will be created and any value will be transferred.
+		///
+		/// # Parameters
+		///
+		/// * `storage_deposit_limit`: The maximum amount of balance that can be charged from the
+		/// caller to pay for the storage consumed. `None` means that the whole free balance of
+		/// the caller (minus `value` and the existential deposit) can be used.
 		#[pallet::weight(T::WeightInfo::call(T::Schedule::get().limits.code_len / 1024)
 			.saturating_add(*gas_limit)
 		)]
@@ -283,14 +260,18 @@ pub mod pallet {
 			dest: <T::Lookup as StaticLookup>::Source,
 			#[pallet::compact] value: BalanceOf<T>,
 			#[pallet::compact] gas_limit: Weight,
+			storage_deposit_limit: Option<<BalanceOf<T> as codec::HasCompact>::Type>,
 			data: Vec<u8>
 		) -> DispatchResultWithPostInfo {
 			let origin = ensure_signed(origin)?;
 			let dest = T::Lookup::lookup(dest)?;
 			let mut gas_meter = GasMeter::new(gas_limit);
+			let mut storage_meter = StorageMeter::<T>::new(
+				&origin, storage_deposit_limit.map(Into::into), value,
+			)?;
 			let schedule = T::Schedule::get();
 			let (result, code_len) = match ExecStack::<T, PrefabWasmModule<T>>::run_call(
-				origin, dest, &mut gas_meter, &schedule, value, data, None,
+				origin, dest, &mut gas_meter, &mut storage_meter, &schedule, value, data, None,
 			) {
 				Ok((output, len)) => (Ok(output), len),
 				Err((err, len)) => (Err(err), len),
@@ -307,6 +288,8 @@ pub mod pallet {
 		///
 		/// * `endowment`: The balance to transfer from the `origin` to the newly created contract.
 		/// * `gas_limit`: The gas limit enforced when executing the constructor.
+		/// * `storage_deposit_limit`: The maximum amount of balance that can be charged/reserved
+		/// from the caller to pay for the storage consumed.
 		/// * `code`: The contract code to deploy in raw bytes.
 		/// * `data`: The input data to pass to the contract constructor.
 		/// * `salt`: Used for the address derivation. See [`Pallet::contract_address`].
@@ -318,6 +301,7 @@ pub mod pallet {
 		/// - The destination address is computed based on the sender, code_hash and the salt.
 		/// - The smart-contract account is created at the computed address.
 		/// - The `endowment` is transferred to the new account.
+		/// - The storage deposit for the new contract is charged from the `origin`.
 		/// - The `deploy` function is executed in the context of the newly-created account.
 		#[pallet::weight(
 			T::WeightInfo::instantiate_with_code(
@@ -330,6 +314,7 @@ pub mod pallet {
 			origin: OriginFor<T>,
 			#[pallet::compact] endowment: BalanceOf<T>,
 			#[pallet::compact] gas_limit: Weight,
+			storage_deposit_limit: Option<<BalanceOf<T> as codec::HasCompact>::Type>,
 			code: Vec<u8>,
 			data: Vec<u8>,
 			salt: Vec<u8>,
@@ -338,12 +323,16 @@ pub mod pallet {
 			let code_len = code.len() as u32;
 			ensure!(code_len <= T::Schedule::get().limits.code_len, Error::<T>::CodeTooLarge);
 			let mut gas_meter = GasMeter::new(gas_limit);
+			let mut storage_meter = StorageMeter::<T>::new(
+				&origin, storage_deposit_limit.map(Into::into), endowment,
+			)?;
 			let schedule = T::Schedule::get();
 			let executable = PrefabWasmModule::from_code(code, &schedule)?;
 			let code_len = executable.code_len();
 			ensure!(code_len <= T::Schedule::get().limits.code_len, Error::<T>::CodeTooLarge);
 			let result = ExecStack::<T, PrefabWasmModule<T>>::run_instantiate(
-				origin, executable, &mut gas_meter, &schedule, endowment, data, &salt, None,
+				origin, executable, &mut gas_meter, &mut storage_meter, &schedule,
+				endowment, data, &salt, None,
 			).map(|(_address, output)| output);
 			gas_meter.into_dispatch_result(
 				result,
@@ -366,90 +355,38 @@ pub mod pallet {
 			origin: OriginFor<T>,
 			#[pallet::compact] endowment: BalanceOf<T>,
 			#[pallet::compact] gas_limit: Weight,
+			storage_deposit_limit: Option<<BalanceOf<T> as codec::HasCompact>::Type>,
 			code_hash: CodeHash<T>,
 			data: Vec<u8>,
 			salt: Vec<u8>,
 		) -> DispatchResultWithPostInfo {
 			let origin = ensure_signed(origin)?;
 			let mut gas_meter = GasMeter::new(gas_limit);
+			let mut storage_meter = StorageMeter::<T>::new(
+				&origin, storage_deposit_limit.map(Into::into), endowment,
+			)?;
 			let schedule = T::Schedule::get();
 			let executable = PrefabWasmModule::from_storage(code_hash, &schedule, &mut gas_meter)?;
 			let code_len = executable.code_len();
 			let result = ExecStack::<T, PrefabWasmModule<T>>::run_instantiate(
-				origin, executable, &mut gas_meter, &schedule, endowment, data, &salt, None,
+				origin, executable, &mut gas_meter, &mut storage_meter, &schedule,
+				endowment, data, &salt, None,
 			).map(|(_address, output)| output);
 			gas_meter.into_dispatch_result(
 				result,
 				T::WeightInfo::instantiate(code_len / 1024, salt.len() as u32 / 1024),
 			)
 		}
-
-		/// Allows block producers to claim a small reward for evicting a contract. If a block
-		/// producer fails to do so, a regular users will be allowed to claim the reward.
-		///
-		/// In case of a successful eviction no fees are charged from the sender. However, the
-		/// reward is capped by the total amount of rent that was paid by the contract while
-		/// it was alive.
-		///
-		/// If contract is not evicted as a result of this call, [`Error::ContractNotEvictable`]
-		/// is returned and the sender is not eligible for the reward.
-		#[pallet::weight(T::WeightInfo::claim_surcharge(T::Schedule::get().limits.code_len / 1024))]
-		pub fn claim_surcharge(
-			origin: OriginFor<T>,
-			dest: T::AccountId,
-			aux_sender: Option<T::AccountId>
-		) -> DispatchResultWithPostInfo {
-			let origin = origin.into();
-			let (signed, rewarded) = match (origin, aux_sender) {
-				(Ok(frame_system::RawOrigin::Signed(account)), None) => {
-					(true, account)
-				},
-				(Ok(frame_system::RawOrigin::None), Some(aux_sender)) => {
-					(false, aux_sender)
-				},
-				_ => Err(Error::<T>::InvalidSurchargeClaim)?,
-			};
-
-			// Add some advantage for block producers (who send unsigned extrinsics) by
-			// adding a handicap: for signed extrinsics we use a slightly older block number
-			// for the eviction check. This can be viewed as if we pushed regular users back in past.
-			let handicap = if signed {
-				T::SignedClaimHandicap::get()
-			} else {
-				Zero::zero()
-			};
-
-			// If poking the contract has lead to eviction of the contract, give out the rewards.
-			match Rent::<T, PrefabWasmModule<T>>::try_eviction(&dest, handicap)? {
-				(Some(rent_paid), code_len) => {
-					T::Currency::deposit_into_existing(
-						&rewarded,
-						T::SurchargeReward::get().min(rent_paid),
-					)
-					.map(|_| PostDispatchInfo {
-						actual_weight: Some(T::WeightInfo::claim_surcharge(code_len / 1024)),
-						pays_fee: Pays::No,
-					})
-					.map_err(Into::into)
-				}
-				(None, code_len) => Err(Error::<T>::ContractNotEvictable.with_weight(
-					T::WeightInfo::claim_surcharge(code_len / 1024)
-				)),
-			}
-		}
 	}
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
-	#[pallet::metadata(T::AccountId = "AccountId", T::Hash = "Hash", BalanceOf<T> = "Balance")]
+	#[pallet::metadata(T::AccountId = "AccountId", T::Hash = "Hash")]
 	pub enum Event<T: Config> {
 		/// Contract deployed by address at the specified address. \[deployer, contract\]
 		Instantiated(T::AccountId, T::AccountId),
 
-		/// Contract has been evicted and is now in tombstone state. \[contract\]
-		Evicted(T::AccountId),
-
-		/// Contract has been terminated without leaving a tombstone.
+		/// Contract has been removed.
 		/// \[contract, beneficiary\]
 		///
 		/// # Params
@@ -459,21 +396,10 @@ pub mod pallet {
 		///
 		/// # Note
 		///
-		/// The only way for a contract to be removed without a tombstone and emitting
-		/// this event is by calling `seal_terminate`.
+		/// The only way for a contract to be removed and emitting this event is by calling
+		/// `seal_terminate`. Its storage deposit is refunded to the origin of the call stack.
 		Terminated(T::AccountId, T::AccountId),
 
-		/// Restoration of a contract has been successful.
-		/// \[restorer, dest, code_hash, rent_allowance\]
-		///
-		/// # Params
-		///
-		/// - `restorer`: Account ID of the restoring contract.
-		/// - `dest`: Account ID of the restored contract.
-		/// - `code_hash`: Code hash of the restored contract.
-		/// - `rent_allowance`: Rent allowance of the restored contract.
-		Restored(T::AccountId, T::AccountId, T::Hash, BalanceOf<T>),
-
 		/// Code with the specified hash has been stored. \[code_hash\]
 		CodeStored(T::Hash),
 
@@ -498,7 +424,7 @@ pub mod pallet {
 		/// A code with the specified hash was removed.
 		/// \[code_hash\]
 		///
-		/// This happens when the last contract that uses this code hash was removed or evicted.
+		/// This happens when the last contract that uses this code hash was removed.
 		CodeRemoved(T::Hash),
 	}
 
@@ -506,27 +432,17 @@ pub mod pallet {
 	pub enum Error<T> {
 		/// A new schedule must have a greater version than the current one.
 		InvalidScheduleVersion,
-		/// An origin must be signed or inherent and auxiliary sender only provided on inherent.
-		InvalidSurchargeClaim,
-		/// Cannot restore from nonexisting or tombstone contract.
-		InvalidSourceContract,
-		/// Cannot restore to nonexisting or alive contract.
-		InvalidDestinationContract,
-		/// Tombstones don't match.
-		InvalidTombstone,
-		/// An origin TrieId written in the current block.
-		InvalidContractOrigin,
 		/// The executed contract exhausted its gas limit.
 		OutOfGas,
 		/// The output buffer supplied to a contract API call was too small.
 		OutputBufferTooSmall,
 		/// Performing the requested transfer would have brought the contract below
-		/// the subsistence threshold. No transfer is allowed to do this in order to allow
-		/// for a tombstone to be created. Use `seal_terminate` to remove a contract without
-		/// leaving a tombstone behind.
+		/// the subsistence threshold. No transfer is allowed to do this because the contract's
+		/// account must stay alive while it holds storage deposits. Use `seal_terminate` to
+		/// remove a contract together with its balance.
 		BelowSubsistenceThreshold,
-		/// The newly created contract is below the subsistence threshold after executing
-		/// its contructor. No contracts are allowed to exist below that threshold.
+		/// The endowment of a new contract is below the subsistence threshold.
+		/// No contracts are allowed to exist below that threshold.
 		NewContractNotFunded,
 		/// Performing the requested transfer failed for a reason originating in the
 		/// chosen currency implementation of the runtime. Most probably the balance is
@@ -537,18 +453,6 @@ pub mod pallet {
 		MaxCallDepthReached,
 		/// No contract was found at the specified address.
 		ContractNotFound,
-		/// A tombstone exist at the specified address.
-		///
-		/// Tombstone cannot be called. Anyone can use `seal_restore_to` in order to revive
-		/// the contract, though.
-		ContractIsTombstone,
-		/// The called contract does not have enough balance to pay for its storage.
-		///
-		/// The contract ran out of balance and is therefore eligible for eviction into a
-		/// tombstone. Anyone can evict the contract by submitting a `claim_surcharge`
-		/// extrinsic. Alternatively, a plain balance transfer can be used in order to
-		/// increase the contracts funds so that it can be called again.
-		RentNotPaid,
 		/// The code supplied to `instantiate_with_code` exceeds the limit specified in the
 		/// current schedule.
 		CodeTooLarge,
@@ -563,8 +467,7 @@ pub mod pallet {
 		/// The size defined in `T::MaxValueSize` was exceeded.
 		ValueTooLarge,
 		/// The action performed is not allowed while the contract performing it is already
-		/// on the call stack. Those actions are contract self destruction and restoration
-		/// of a tombstone.
+		/// on the call stack. This is the case for contract self destruction.
 		ReentranceDenied,
 		/// `seal_input` was called twice from the same contract execution context.
 		InputAlreadyRead,
@@ -580,15 +483,10 @@ pub mod pallet {
 		NoChainExtension,
 		/// Removal of a contract failed because the deletion queue is full.
 		///
-		/// This can happen when either calling [`Pallet::claim_surcharge`] or `seal_terminate`.
+		/// This can happen when calling `seal_terminate`.
 		/// The queue is filled by deleting contracts and emptied by a fixed amount each block.
 		/// Trying again during another block is the only way to resolve this issue.
 		DeletionQueueFull,
-		/// A contract could not be evicted because it has enough balance to pay rent.
-		///
-		/// This can be returned from [`Pallet::claim_surcharge`] because the target
-		/// contract has enough balance to pay for its rent.
-		ContractNotEvictable,
 		/// A storage modification exhausted the 32bit type that holds the storage size.
 		///
 		/// This can either happen when the accumulated storage in bytes is too large or
@@ -598,10 +496,14 @@ pub mod pallet {
 		DuplicateContract,
 		/// A contract self destructed in its constructor.
 		///
-		/// This can be triggered by a call to `seal_terminate` or `seal_restore_to`.
+		/// This can be triggered by a call to `seal_terminate`.
 		TerminatedInConstructor,
 		/// The debug message specified to `seal_debug_message` does contain invalid UTF-8.
 		DebugMessageInvalidUTF8,
+		/// Origin doesn't have enough balance to pay the required storage deposits.
+		StorageDepositNotEnoughFunds,
+		/// More storage was created than allowed by the storage deposit limit.
+		StorageDepositLimitExhausted,
 	}
 
 	/// A mapping from an original code hash to the original code, untouched by instrumentation.
@@ -622,7 +524,7 @@ pub mod pallet {
 	#[pallet::storage]
 	pub(crate) type ContractInfoOf<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, ContractInfo<T>>;
 
-	/// Evicted contracts that await child trie deletion.
+	/// Terminated contracts that await child trie deletion.
 	///
 	/// Child trie deletion is a heavy operation depending on the amount of storage items
 	/// stored in said trie. Therefore this operation is performed lazily in `on_initialize`.
@@ -645,16 +547,26 @@ where
 	/// it adds allocations and could be abused to drive the runtime into an OOM panic.
 	/// If set to `true` it returns additional human readable debugging information.
 	///
-	/// It returns the execution result and the amount of used weight.
+	/// It returns the execution result, the amount of used weight and the storage deposit.
 	pub fn bare_call(
 		origin: T::AccountId,
 		dest: T::AccountId,
 		value: BalanceOf<T>,
 		gas_limit: Weight,
+		storage_deposit_limit: Option<BalanceOf<T>>,
 		input_data: Vec<u8>,
 		debug: bool,
-	) -> ContractExecResult {
+	) -> ContractExecResult<BalanceOf<T>> {
 		let mut gas_meter = GasMeter::new(gas_limit);
+		let mut storage_meter = match StorageMeter::new(&origin, storage_deposit_limit, value) {
+			Ok(meter) => meter,
+			Err(err) => return ContractExecResult {
+				result: Err(err),
+				gas_consumed: 0,
+				storage_deposit: Default::default(),
+				debug_message: Vec::new(),
+			},
+		};
 		let schedule = T::Schedule::get();
 		let mut debug_message = if debug {
 			Some(Vec::new())
@@ -662,11 +574,13 @@ where
 			None
 		};
 		let result = ExecStack::<T, PrefabWasmModule<T>>::run_call(
-			origin, dest, &mut gas_meter, &schedule, value, input_data, debug_message.as_mut(),
+			origin, dest, &mut gas_meter, &mut storage_meter, &schedule,
+			value, input_data, debug_message.as_mut(),
 		);
 		ContractExecResult {
 			result: result.map(|r| r.0).map_err(|r| r.0.error),
 			gas_consumed: gas_meter.gas_spent(),
+			storage_deposit: storage_meter.into_deposit(),
 			debug_message: debug_message.unwrap_or_default(),
 		}
 	}
@@ -676,11 +590,8 @@ where
 	/// This function is similar to [`Self::instantiate`], but doesn't perform any address lookups
 	/// and better suitable for calling directly from Rust.
 	///
-	/// It returns the execution result, account id and the amount of used weight.
-	///
-	/// If `compute_projection` is set to `true` the result also contains the rent projection.
-	/// This is optional because some non trivial and stateful work is performed to compute
-	/// the projection. See [`Self::rent_projection`].
+	/// It returns the execution result, account id, the amount of used weight and the
+	/// storage deposit.
 	///
 	/// # Note
 	///
@@ -691,13 +602,22 @@ where
 		origin: T::AccountId,
 		endowment: BalanceOf<T>,
 		gas_limit: Weight,
+		storage_deposit_limit: Option<BalanceOf<T>>,
 		code: Code<CodeHash<T>>,
 		data: Vec<u8>,
 		salt: Vec<u8>,
-		compute_projection: bool,
 		debug: bool,
-	) -> ContractInstantiateResult<T::AccountId, T::BlockNumber> {
+	) -> ContractInstantiateResult<T::AccountId, BalanceOf<T>> {
 		let mut gas_meter = GasMeter::new(gas_limit);
+		let mut storage_meter = match StorageMeter::new(&origin, storage_deposit_limit, endowment) {
+			Ok(meter) => meter,
+			Err(err) => return ContractInstantiateResult {
+				result: Err(err),
+				gas_consumed: 0,
+				storage_deposit: Default::default(),
+				debug_message: Vec::new(),
+			},
+		};
 		let schedule = T::Schedule::get();
 		let executable = match code {
 			Code::Upload(Bytes(binary)) => PrefabWasmModule::from_code(binary, &schedule),
@@ -708,6 +628,7 @@ where
 			Err(error) => return ContractInstantiateResult {
 				result: Err(error.into()),
 				gas_consumed: gas_meter.gas_spent(),
+				storage_deposit: Default::default(),
 				debug_message: Vec::new(),
 			}
 		};
@@ -717,25 +638,13 @@ where
 			None
 		};
 		let result = ExecStack::<T, PrefabWasmModule<T>>::run_instantiate(
-			origin, executable, &mut gas_meter, &schedule,
+			origin, executable, &mut gas_meter, &mut storage_meter, &schedule,
 			endowment, data, &salt, debug_message.as_mut(),
-		).and_then(|(account_id, result)| {
-			let rent_projection = if compute_projection {
-				Some(Rent::<T, PrefabWasmModule<T>>::compute_projection(&account_id)
-					.map_err(|_| <Error<T>>::NewContractNotFunded)?)
-			} else {
-				None
-			};
-
-			Ok(InstantiateReturnValue {
-				result,
-				account_id,
-				rent_projection,
-			})
-		});
+		).map(|(account_id, result)| InstantiateReturnValue { result, account_id });
 		ContractInstantiateResult {
 			result: result.map_err(|e| e.error),
 			gas_consumed: gas_meter.gas_spent(),
+			storage_deposit: storage_meter.into_deposit(),
 			debug_message: debug_message.unwrap_or_default(),
 		}
 	}
@@ -743,20 +652,12 @@ where
 	/// Query storage of a specified contract under a specified key.
 	pub fn get_storage(address: T::AccountId, key: [u8; 32]) -> GetStorageResult {
 		let contract_info = ContractInfoOf::<T>::get(&address)
-			.ok_or(ContractAccessError::DoesntExist)?
-			.get_alive()
-			.ok_or(ContractAccessError::IsTombstone)?;
+			.ok_or(ContractAccessError::DoesntExist)?;
 
 		let maybe_value = Storage::<T>::read(&contract_info.trie_id, &key);
 		Ok(maybe_value)
 	}
 
-	/// Query how many blocks the contract stays alive given that the amount endowment
-	/// and consumed storage does not change.
-	pub fn rent_projection(address: T::AccountId) -> RentProjectionResult<T::BlockNumber> {
-		Rent::<T, PrefabWasmModule<T>>::compute_projection(&address)
-	}
-
 	/// Determine the address of a contract,
 	///
 	/// This is the address generation function used by contract instantiation. Its result
@@ -779,15 +680,15 @@ where
 		UncheckedFrom::unchecked_from(T::Hashing::hash(&buf))
 	}
 
-	/// Subsistence threshold is the extension of the minimum balance (aka existential deposit)
-	/// by the tombstone deposit, required for leaving a tombstone.
+	/// Subsistence threshold is the minimum free balance a contract needs to keep.
 	///
-	/// Rent or any contract initiated balance transfer mechanism cannot make the balance lower
-	/// than the subsistence threshold in order to guarantee that a tombstone is created.
+	/// Storage deposits are reserved on top of it. Any contract initiated balance transfer
+	/// mechanism cannot make the free balance lower than the subsistence threshold in order to
+	/// guarantee that the contract's account (and with it its deposits) is never removed.
 	///
-	/// The only way to completely kill a contract without a tombstone is calling `seal_terminate`.
+	/// The only way to completely kill a contract is calling `seal_terminate`.
 	pub fn subsistence_threshold() -> BalanceOf<T> {
-		T::Currency::minimum_balance().saturating_add(T::TombstoneDeposit::get())
+		T::Currency::minimum_balance()
 	}
 
 	/// The in-memory size in bytes of the data structure associated with each contract.
@@ -15,12 +15,21 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{Config, Weight, Pallet};
+use crate::{
+	Config, Weight, Pallet, BalanceOf, CodeHash, TrieId, ContractInfoOf,
+	storage::ContractInfo,
+};
+use codec::Decode;
 use frame_support::{
 	storage::migration,
 	traits::{GetPalletVersion, PalletVersion, PalletInfoAccess, Get},
 };
 
+/// Migrate the storage of a chain that still charges rent.
+///
+/// This only runs when the stored pallet version is the one of the last release with rent.
+/// The pallet writes its current crate version into storage after this hook returned, so that
+/// every subsequent runtime upgrade skips this migration.
 pub fn migrate<T: Config>() -> Weight {
 	let mut weight: Weight = 0;
 
@@ -32,9 +41,56 @@ pub fn migrate<T: Config>() -> Weight {
 				b"CurrentSchedule",
 				b"",
 			);
+			weight = weight.saturating_add(migrate_contract_info::<T>());
 		}
 		_ => (),
 	}
 
 	weight
 }
+
+/// The contract info as it was stored when contracts were paying rent.
+#[derive(Decode)]
+enum OldContractInfo<T: Config> {
+	Alive(OldAliveContractInfo<T>),
+	Tombstone(T::Hash),
+}
+
+#[derive(Decode)]
+struct OldAliveContractInfo<T: Config> {
+	trie_id: TrieId,
+	storage_size: u32,
+	pair_count: u32,
+	code_hash: CodeHash<T>,
+	rent_allowance: BalanceOf<T>,
+	rent_paid: BalanceOf<T>,
+	deduct_block: T::BlockNumber,
+	last_write: Option<T::BlockNumber>,
+	_reserved: Option<()>,
+}
+
+/// Drop all rent related fields from the stored contracts and remove tombstones.
+///
+/// Contracts that existed before storage deposits were introduced start with a
+/// `storage_deposit` of zero: They did not deposit anything and hence nothing can be refunded.
+///
+/// Entries that were already migrated fail to decode as [`OldContractInfo`] and are skipped
+/// by `translate`. It is therefore safe to run this more than once.
+fn migrate_contract_info<T: Config>() -> Weight {
+	let mut count: Weight = 0;
+	<ContractInfoOf<T>>::translate(|_key, old: OldContractInfo<T>| {
+		count += 1;
+		match old {
+			OldContractInfo::Alive(info) => Some(ContractInfo::<T> {
+				trie_id: info.trie_id,
+				storage_size: info.storage_size,
+				pair_count: info.pair_count,
+				code_hash: info.code_hash,
+				storage_deposit: Default::default(),
+				_reserved: info._reserved,
+			}),
+			OldContractInfo::Tombstone(_) => None,
+		}
+	});
+	T::DbWeight::get().reads_writes(count, count)
+}
@@ -0,0 +1,633 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains functions to meter the storage deposit.
+
+use crate::{storage::ContractInfo, BalanceOf, Config, Error};
+use sp_core::crypto::UncheckedFrom;
+use sp_std::marker::PhantomData;
+use sp_runtime::traits::Saturating;
+use frame_support::{
+	dispatch::DispatchError,
+	traits::{Currency, ExistenceRequirement, Get, ReservableCurrency},
+	DefaultNoBound,
+};
+use pallet_contracts_primitives::StorageDeposit as Deposit;
+
+/// Deposit that uses the native currency's balance type.
+pub type DepositOf<T> = Deposit<BalanceOf<T>>;
+
+/// A production root storage meter that actually charges from its origin.
+pub type Meter<T> = RawMeter<T, ReservingExt, Root>;
+
+/// A production nested storage meter that actually charges from its origin.
+pub type NestedMeter<T> = RawMeter<T, ReservingExt, Nested>;
+
+/// A production storage meter that actually charges from its origin.
+///
+/// This can be used where we want to be generic over the state (Root vs. Nested).
+pub type GenericMeter<T, S> = RawMeter<T, ReservingExt, S>;
+
+/// A trait that allows to decouple the metering from the charging of balance.
+///
+/// This mostly exists for testing so that the charging can be mocked.
+pub trait Ext<T: Config> {
+	/// This checks whether `origin` is able to afford the storage deposit limit.
+	///
+	/// It is necessary to do this check beforehand so that the charge won't fail later on.
+	///
+	/// `origin`: The origin of the call stack from which is responsible for putting down a deposit.
+	/// `limit`: The limit with which the meter was constructed.
+	/// `min_leftover`: How much `free_balance` in addition to the existential deposit (ed) should
+	/// be left inside the `origin` account.
+	///
+	/// Returns the limit that should be used by the meter. If origin can't afford the `limit`
+	/// it returns `Err`.
+	fn check_limit(
+		origin: &T::AccountId,
+		limit: Option<BalanceOf<T>>,
+		min_leftover: BalanceOf<T>,
+	) -> Result<BalanceOf<T>, DispatchError>;
+
+	/// This is called to inform the implementer that some balance should be charged due to
+	/// some interaction of the `origin` with a `contract`.
+	///
+	/// The balance transfer can either flow from `origin` to `contract` or the other way
+	/// around depending on whether `amount` constitutes a `Charge` or a `Refund`.
+	/// It is guaranteed that this succeeds because no more balance than returned by
+	/// `check_limit` is ever charged. This is why this function is infallible.
+	fn charge(origin: &T::AccountId, contract: &T::AccountId, amount: &DepositOf<T>);
+}
+
+/// This [`Ext`] is used for actual on-chain execution when balance needs to be charged.
+///
+/// It uses [`ReservableCurrency`] in order to do accomplish the reserves.
+pub enum ReservingExt {}
+
+/// Used to implement a type state pattern for the meter.
+///
+/// It is sealed and cannot be implemented outside of this module.
+pub trait State: private::Sealed {}
+
+/// State parameter that constitutes a meter that is in its root state.
+pub enum Root {}
+
+/// State parameter that constitutes a meter that is in its nested state.
+pub enum Nested {}
+
+impl State for Root {}
+impl State for Nested {}
+
+/// A type that allows the metering of consumed or freed storage of a single contract call stack.
+#[derive(DefaultNoBound)]
+pub struct RawMeter<T: Config, E: Ext<T>, S: State> {
+	/// The limit of how much balance this meter is allowed to consume.
+	limit: BalanceOf<T>,
+	/// The amount of balance that was used in this meter and all of its already absorbed children.
+	total_deposit: DepositOf<T>,
+	/// The amount of balance that was used in this meter alone.
+	own_deposit: DepositOf<T>,
+	/// Set when the contract of this meter was terminated. It cannot be charged afterwards.
+	terminated: bool,
+	/// Type parameters are only used in impls.
+	_phantom: PhantomData<(E, S)>,
+}
+
+/// This type is used to describe a storage change when charging from the meter.
+#[derive(Default)]
+pub struct Diff {
+	/// How many bytes were added to storage.
+	pub bytes_added: u32,
+	/// How many bytes were removed from storage.
+	pub bytes_removed: u32,
+	/// How many storage items were added to storage.
+	pub items_added: u32,
+	/// How many storage items were removed from storage.
+	pub items_removed: u32,
+}
+
+impl Diff {
+	/// Calculate how much of a charge or refund results from applying the diff.
+	pub fn to_deposit<T: Config>(&self) -> DepositOf<T> {
+		let mut deposit = Deposit::default();
+		let per_byte = T::DepositPerByte::get();
+		let per_item = T::DepositPerItem::get();
+
+		if self.bytes_added > self.bytes_removed {
+			deposit = deposit.saturating_add(&Deposit::Charge(
+				per_byte.saturating_mul((self.bytes_added - self.bytes_removed).into())
+			));
+		} else if self.bytes_removed > self.bytes_added {
+			deposit = deposit.saturating_add(&Deposit::Refund(
+				per_byte.saturating_mul((self.bytes_removed - self.bytes_added).into())
+			));
+		}
+
+		if self.items_added > self.items_removed {
+			deposit = deposit.saturating_add(&Deposit::Charge(
+				per_item.saturating_mul((self.items_added - self.items_removed).into())
+			));
+		} else if self.items_removed > self.items_added {
+			deposit = deposit.saturating_add(&Deposit::Refund(
+				per_item.saturating_mul((self.items_removed - self.items_added).into())
+			));
+		}
+
+		deposit
+	}
+}
+
+impl<T, E, S> RawMeter<T, E, S>
+where
+	T: Config,
+	T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>,
+	E: Ext<T>,
+	S: State,
+{
+	/// Create a new child that has its `limit` set to whatever is remaining of it.
+	///
+	/// This is called whenever a new subcall is initiated in order to track the storage
+	/// usage for this sub call separately. This is necessary because we want to exchange balance
+	/// with the current contract we are interacting with.
+	pub fn nested(&self) -> RawMeter<T, E, Nested> {
+		RawMeter { limit: self.available(), ..Default::default() }
+	}
+
+	/// Absorb a child that was spawned to handle a sub call.
+	///
+	/// This should be called whenever a sub call comes to its end and it is **not** reverted.
+	/// This does the actual balance transfer from/to `origin` and `contract` based on the overall
+	/// storage consumption of the call. It also updates the supplied contract info.
+	///
+	/// In case a contract reverted the child meter should just be dropped in order to revert
+	/// any changes it recorded.
+	///
+	/// # Parameters
+	///
+	/// `absorbed`: The child storage meter that should be absorbed.
+	/// `origin`: The origin that spawned the original root meter.
+	/// `contract`: The contract that this sub call belongs to.
+	/// `info`: The info of the contract in question. `None` if the contract was terminated.
+	pub fn absorb(
+		&mut self,
+		mut absorbed: RawMeter<T, E, Nested>,
+		origin: &T::AccountId,
+		contract: &T::AccountId,
+		info: Option<&mut ContractInfo<T>>,
+	) {
+		// Absorbing from an existing (non terminated) contract.
+		if let Some(info) = info {
+			match &mut absorbed.own_deposit {
+				Deposit::Charge(amount) =>
+					info.storage_deposit = info.storage_deposit.saturating_add(*amount),
+				Deposit::Refund(amount) => {
+					// We need to make sure to never refund more than what was deposited.
+					// This case can happen when costs change due to a runtime upgrade where
+					// increased refunds could drain the reserved balance of a contract.
+					if *amount > info.storage_deposit {
+						*amount = info.storage_deposit;
+					}
+					info.storage_deposit = info.storage_deposit.saturating_sub(*amount);
+				},
+			}
+		}
+
+		self.total_deposit = self.total_deposit.saturating_add(&absorbed.total_deposit);
+		if !absorbed.own_deposit.is_zero() {
+			E::charge(origin, contract, &absorbed.own_deposit);
+		}
+	}
+
+	/// The amount of balance that is still available from the original `limit`.
+	fn available(&self) -> BalanceOf<T> {
+		self.total_deposit.available(&self.limit)
+	}
+}
+
+/// Functions that only apply to the root state.
+impl<T, E> RawMeter<T, E, Root>
+where
+	T: Config,
+	T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>,
+	E: Ext<T>,
+{
+	/// Create new storage meter for the specified `origin` and `limit`.
+	///
+	/// This tries to [`Ext::check_limit`] on `origin` and fails if this is not possible.
+	pub fn new(
+		origin: &T::AccountId,
+		limit: Option<BalanceOf<T>>,
+		min_leftover: BalanceOf<T>,
+	) -> Result<Self, DispatchError> {
+		let limit = E::check_limit(origin, limit, min_leftover)?;
+		Ok(Self { limit, ..Default::default() })
+	}
+
+	/// The total amount of deposit that should change hands as result of the execution
+	/// that this meter was passed into.
+	///
+	/// This drops the root meter in order to make sure it is only called when the whole
+	/// execution did finish.
+	pub fn into_deposit(self) -> DepositOf<T> {
+		self.total_deposit
+	}
+}
+
+/// Functions that only apply to the nested state.
+impl<T, E> RawMeter<T, E, Nested>
+where
+	T: Config,
+	T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>,
+	E: Ext<T>,
+{
+	/// Try to charge the `diff` from the meter. Fails if this would exceed the original limit.
+	pub fn charge(&mut self, diff: &Diff) -> Result<DepositOf<T>, DispatchError> {
+		self.record(diff.to_deposit::<T>())
+	}
+
+	/// Charge the deposit for the storage that is occupied by a new contract itself.
+	///
+	/// Just as any other deposit it is only transferred when this meter is absorbed.
+	pub fn charge_instantiate(&mut self) -> Result<DepositOf<T>, DispatchError> {
+		self.record(Deposit::Charge(T::ContractDeposit::get()))
+	}
+
+	/// Call to tell the meter that the currently executing contract was terminated.
+	///
+	/// This immediately refunds the whole deposit of `info` to the `origin` of the meter.
+	/// This is necessary because the contract's account is removed right after.
+	pub fn terminate(
+		&mut self,
+		origin: &T::AccountId,
+		contract: &T::AccountId,
+		info: &ContractInfo<T>,
+	) {
+		debug_assert!(!self.terminated);
+		let refund = Deposit::Refund(info.storage_deposit);
+
+		// The deposit for `own_deposit` isn't persisted into the contract info until the current
+		// frame is dropped. This means that whatever changes were introduced during the
+		// current frame are discarded when terminating.
+		self.total_deposit = self.total_deposit
+			.saturating_sub(&self.own_deposit)
+			.saturating_add(&refund);
+		self.own_deposit = Default::default();
+		self.terminated = true;
+		if !refund.is_zero() {
+			E::charge(origin, contract, &refund);
+		}
+	}
+
+	/// Add `deposit` to this meter in case it does not exceed its limit.
+	fn record(&mut self, deposit: DepositOf<T>) -> Result<DepositOf<T>, DispatchError> {
+		debug_assert!(!self.terminated);
+		let total_deposit = self.total_deposit.saturating_add(&deposit);
+		if let Deposit::Charge(amount) = total_deposit {
+			if amount > self.limit {
+				return Err(<Error<T>>::StorageDepositLimitExhausted.into())
+			}
+		}
+		self.total_deposit = total_deposit;
+		self.own_deposit = self.own_deposit.saturating_add(&deposit);
+		Ok(deposit)
+	}
+}
+
+impl<T> Ext<T> for ReservingExt
+where
+	T: Config,
+	T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>,
+{
+	fn check_limit(
+		origin: &T::AccountId,
+		limit: Option<BalanceOf<T>>,
+		min_leftover: BalanceOf<T>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		let max = T::Currency::free_balance(origin)
+			.saturating_sub(T::Currency::minimum_balance())
+			.saturating_sub(min_leftover);
+		match limit {
+			Some(limit) if limit <= max => Ok(limit),
+			None => Ok(max),
+			_ => Err(<Error<T>>::StorageDepositNotEnoughFunds.into()),
+		}
+	}
+
+	fn charge(origin: &T::AccountId, contract: &T::AccountId, amount: &DepositOf<T>) {
+		// There is nothing we can do when this fails as this constitutes a bug in the runtime:
+		// Either the runtime does not hold up the invariant of never deleting a contract's
+		// account or it does not honor reserved balances. We need to settle for emitting
+		// an error log in this case.
+		match amount {
+			Deposit::Charge(amount) => {
+				// This will never fail because the limit was checked against the free
+				// balance of `origin` and a contract's account is required to exist
+				// before any deposit is charged.
+				let result = T::Currency::transfer(
+					origin, contract, *amount, ExistenceRequirement::KeepAlive,
+				).and_then(|_| T::Currency::reserve(contract, *amount));
+				if let Err(err) = result {
+					log::error!(
+						target: "runtime::contracts",
+						"Failed to transfer storage deposit {:?} from origin {:?} to contract \
+						{:?}: {:?}",
+						amount, origin, contract, err,
+					);
+				}
+			},
+			// A contract can never transfer its free balance below the existential deposit.
+			// Therefore it always exists and the sender has enough reserved balance because
+			// we track it in the `ContractInfo` and never send more back than was deposited.
+			Deposit::Refund(amount) => {
+				let remaining = T::Currency::unreserve(contract, *amount);
+				let result = T::Currency::transfer(
+					contract,
+					origin,
+					amount.saturating_sub(remaining),
+					ExistenceRequirement::KeepAlive,
+				);
+				if let Err(err) = result {
+					log::error!(
+						target: "runtime::contracts",
+						"Failed to refund storage deposit {:?} from contract {:?} to origin {:?}: \
+						{:?}",
+						amount, contract, origin, err,
+					);
+				}
+			},
+		}
+	}
+}
+
+mod private {
+	pub trait Sealed {}
+	impl Sealed for super::Root {}
+	impl Sealed for super::Nested {}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{exec::AccountIdOf, tests::{Test, ALICE, BOB, CHARLIE}};
+	use pretty_assertions::assert_eq;
+	use std::cell::RefCell;
+
+	type TestMeter = RawMeter<Test, TestExt, Root>;
+
+	thread_local! {
+		static TEST_EXT: RefCell<TestExt> = RefCell::new(Default::default());
+	}
+
+	#[derive(Debug, PartialEq, Eq)]
+	struct LimitCheck {
+		origin: AccountIdOf<Test>,
+		limit: BalanceOf<Test>,
+		min_leftover: BalanceOf<Test>,
+	}
+
+	#[derive(Debug, PartialEq, Eq)]
+	struct Charge {
+		origin: AccountIdOf<Test>,
+		contract: AccountIdOf<Test>,
+		amount: DepositOf<Test>,
+	}
+
+	#[derive(Default, Debug, PartialEq, Eq)]
+	struct TestExt {
+		limit_checks: Vec<LimitCheck>,
+		charges: Vec<Charge>,
+	}
+
+	impl TestExt {
+		fn clear(&mut self) {
+			self.limit_checks.clear();
+			self.charges.clear();
+		}
+	}
+
+	impl Ext<Test> for TestExt {
+		fn check_limit(
+			origin: &AccountIdOf<Test>,
+			limit: Option<BalanceOf<Test>>,
+			min_leftover: BalanceOf<Test>,
+		) -> Result<BalanceOf<Test>, DispatchError> {
+			let limit = limit.unwrap_or(42);
+			TEST_EXT.with(|ext| {
+				ext.borrow_mut().limit_checks.push(LimitCheck {
+					origin: origin.clone(),
+					limit,
+					min_leftover,
+				})
+			});
+			Ok(limit)
+		}
+
+		fn charge(
+			origin: &AccountIdOf<Test>,
+			contract: &AccountIdOf<Test>,
+			amount: &DepositOf<Test>,
+		) {
+			TEST_EXT.with(|ext| {
+				ext.borrow_mut().charges.push(Charge {
+					origin: origin.clone(),
+					contract: contract.clone(),
+					amount: amount.clone(),
+				})
+			});
+		}
+	}
+
+	fn clear_ext() {
+		TEST_EXT.with(|ext| ext.borrow_mut().clear())
+	}
+
+	fn new_info(deposit: BalanceOf<Test>) -> ContractInfo<Test> {
+		ContractInfo::<Test> {
+			trie_id: Default::default(),
+			storage_size: 0,
+			pair_count: 0,
+			code_hash: Default::default(),
+			storage_deposit: deposit,
+			_reserved: None,
+		}
+	}
+
+	fn diff(bytes_added: u32, bytes_removed: u32, items_added: u32, items_removed: u32) -> Diff {
+		Diff { bytes_added, bytes_removed, items_added, items_removed }
+	}
+
+	#[test]
+	fn new_reserves_balance_works() {
+		clear_ext();
+
+		TestMeter::new(&ALICE, Some(1_000), 0).unwrap();
+
+		TEST_EXT.with(|ext| {
+			assert_eq!(
+				*ext.borrow(),
+				TestExt {
+					limit_checks: vec![
+						LimitCheck { origin: ALICE, limit: 1_000, min_leftover: 0 },
+					],
+					..Default::default()
+				},
+			)
+		});
+	}
+
+	#[test]
+	fn empty_charge_works() {
+		clear_ext();
+
+		let mut meter = TestMeter::new(&ALICE, Some(1_000), 0).unwrap();
+		assert_eq!(meter.available(), 1_000);
+
+		// an empty charge does not create a `Charge` entry
+		let mut nested0 = meter.nested();
+		nested0.charge(&Default::default()).unwrap();
+		meter.absorb(nested0, &ALICE, &BOB, None);
+
+		TEST_EXT.with(|ext| {
+			assert_eq!(
+				*ext.borrow(),
+				TestExt {
+					limit_checks: vec![
+						LimitCheck { origin: ALICE, limit: 1_000, min_leftover: 0 },
+					],
+					..Default::default()
+				},
+			)
+		});
+	}
+
+	#[test]
+	fn existing_contract_works() {
+		clear_ext();
+
+		let mut meter = TestMeter::new(&ALICE, Some(1_000), 0).unwrap();
+		assert_eq!(meter.available(), 1_000);
+
+		// a normal charge
+		let mut nested0_info = new_info(100);
+		let mut nested0 = meter.nested();
+		nested0.charge(&diff(108, 100, 3, 0)).unwrap();
+		assert_eq!(nested0.available(), 1_000 - 8 - 3 * 2);
+
+		// refunds are capped at the deposit of the contract
+		let mut nested1_info = new_info(50);
+		let mut nested1 = nested0.nested();
+		nested1.charge(&diff(0, 0, 0, 40)).unwrap();
+		nested0.absorb(nested1, &ALICE, &CHARLIE, Some(&mut nested1_info));
+
+		// a refund that is covered by the deposit
+		let mut nested2_info = new_info(100);
+		let mut nested2 = nested0.nested();
+		nested2.charge(&diff(0, 7, 0, 0)).unwrap();
+		nested0.absorb(nested2, &ALICE, &CHARLIE, Some(&mut nested2_info));
+
+		nested0.charge(&diff(0, 0, 0, 1)).unwrap();
+		meter.absorb(nested0, &ALICE, &BOB, Some(&mut nested0_info));
+
+		assert_eq!(nested0_info.storage_deposit, 112);
+		assert_eq!(nested1_info.storage_deposit, 0);
+		assert_eq!(nested2_info.storage_deposit, 93);
+
+		TEST_EXT.with(|ext| {
+			assert_eq!(
+				*ext.borrow(),
+				TestExt {
+					limit_checks: vec![
+						LimitCheck { origin: ALICE, limit: 1_000, min_leftover: 0 },
+					],
+					charges: vec![
+						Charge { origin: ALICE, contract: CHARLIE, amount: Deposit::Refund(50) },
+						Charge { origin: ALICE, contract: CHARLIE, amount: Deposit::Refund(7) },
+						Charge { origin: ALICE, contract: BOB, amount: Deposit::Charge(12) },
+					],
+				},
+			)
+		});
+	}
+
+	#[test]
+	fn contract_deposit_is_charged() {
+		clear_ext();
+
+		let mut meter = TestMeter::new(&ALICE, Some(1_000), 0).unwrap();
+		let mut info = new_info(0);
+		let mut nested0 = meter.nested();
+		nested0.charge_instantiate().unwrap();
+		meter.absorb(nested0, &ALICE, &BOB, Some(&mut info));
+
+		let deposit = <Test as Config>::ContractDeposit::get();
+		assert_eq!(info.storage_deposit, deposit);
+		assert_eq!(meter.into_deposit(), Deposit::Charge(deposit));
+	}
+
+	#[test]
+	fn termination_works() {
+		clear_ext();
+
+		let mut meter = TestMeter::new(&ALICE, Some(1_000), 0).unwrap();
+		assert_eq!(meter.available(), 1_000);
+
+		let mut nested0 = meter.nested();
+		nested0.charge(&diff(800, 0, 3, 0)).unwrap();
+		assert_eq!(nested0.available(), 1_000 - 800 - 3 * 2);
+
+		// whatever the terminated contract charged itself is discarded
+		let mut nested1 = nested0.nested();
+		nested1.charge(&diff(5, 0, 0, 0)).unwrap();
+		nested1.terminate(&ALICE, &CHARLIE, &new_info(400));
+		nested0.absorb(nested1, &ALICE, &CHARLIE, None);
+
+		meter.absorb(nested0, &ALICE, &BOB, None);
+		assert_eq!(meter.into_deposit(), Deposit::Charge(806 - 400));
+
+		TEST_EXT.with(|ext| {
+			assert_eq!(
+				*ext.borrow(),
+				TestExt {
+					limit_checks: vec![
+						LimitCheck { origin: ALICE, limit: 1_000, min_leftover: 0 },
+					],
+					charges: vec![
+						Charge { origin: ALICE, contract: CHARLIE, amount: Deposit::Refund(400) },
+						Charge { origin: ALICE, contract: BOB, amount: Deposit::Charge(806) },
+					],
+				},
+			)
+		});
+	}
+
+	#[test]
+	fn exceeding_the_limit_fails() {
+		clear_ext();
+
+		let meter = TestMeter::new(&ALICE, Some(100), 0).unwrap();
+		let mut nested0 = meter.nested();
+		assert_eq!(
+			nested0.charge(&diff(101, 0, 0, 0)),
+			Err(<Error<Test>>::StorageDepositLimitExhausted.into()),
+		);
+
+		// refunds free up some of the limit again
+		nested0.charge(&diff(0, 10, 0, 0)).unwrap();
+		nested0.charge(&diff(110, 0, 0, 0)).unwrap();
+		assert_eq!(nested0.available(), 0);
+	}
+}
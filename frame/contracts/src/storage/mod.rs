@@ -17,19 +17,18 @@
 
 //! This module contains routines for accessing and altering a contract related state.
 
+pub mod meter;
+
 use crate::{
 	exec::{AccountIdOf, StorageKey},
 	BalanceOf, CodeHash, ContractInfoOf, Config, TrieId, DeletionQueue, Error,
 	weights::WeightInfo,
 };
-use codec::{Codec, Encode, Decode};
+use codec::{Encode, Decode};
 use sp_std::prelude::*;
-use sp_std::{marker::PhantomData, fmt::Debug};
+use sp_std::marker::PhantomData;
 use sp_io::hashing::blake2_256;
-use sp_runtime::{
-	RuntimeDebug,
-	traits::{Bounded, Saturating, Zero, Hash, Member, MaybeSerializeDeserialize},
-};
+use sp_runtime::{RuntimeDebug, traits::{Hash, Zero}};
 use sp_core::crypto::UncheckedFrom;
 use frame_support::{
 	dispatch::{DispatchError, DispatchResult},
@@ -38,53 +37,12 @@ use frame_support::{
 	weights::Weight,
 };
 
-pub type AliveContractInfo<T> =
-	RawAliveContractInfo<CodeHash<T>, BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
-pub type TombstoneContractInfo<T> =
-	RawTombstoneContractInfo<<T as frame_system::Config>::Hash, <T as frame_system::Config>::Hashing>;
-
-/// Information for managing an account and its sub trie abstraction.
-/// This is the required info to cache for an account
-#[derive(Encode, Decode, RuntimeDebug)]
-pub enum ContractInfo<T: Config> {
-	Alive(AliveContractInfo<T>),
-	Tombstone(TombstoneContractInfo<T>),
-}
-
-impl<T: Config> ContractInfo<T> {
-	/// If contract is alive then return some alive info
-	pub fn get_alive(self) -> Option<AliveContractInfo<T>> {
-		if let ContractInfo::Alive(alive) = self {
-			Some(alive)
-		} else {
-			None
-		}
-	}
-
-	/// If contract is alive then return some reference to alive info
-	#[cfg(test)]
-	pub fn as_alive(&self) -> Option<&AliveContractInfo<T>> {
-		if let ContractInfo::Alive(ref alive) = self {
-			Some(alive)
-		} else {
-			None
-		}
-	}
-
-	/// If contract is tombstone then return some tombstone info
-	pub fn get_tombstone(self) -> Option<TombstoneContractInfo<T>> {
-		if let ContractInfo::Tombstone(tombstone) = self {
-			Some(tombstone)
-		} else {
-			None
-		}
-	}
-}
+pub type ContractInfo<T> = RawContractInfo<CodeHash<T>, BalanceOf<T>>;
 
 /// Information for managing an account and its sub trie abstraction.
 /// This is the required info to cache for an account.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
-pub struct RawAliveContractInfo<CodeHash, Balance, BlockNumber> {
+pub struct RawContractInfo<CodeHash, Balance> {
 	/// Unique ID for the subtree encoded as a bytes vector.
 	pub trie_id: TrieId,
 	/// The total number of bytes used by this contract.
@@ -95,21 +53,16 @@ pub struct RawAliveContractInfo<CodeHash, Balance, BlockNumber> {
 	pub pair_count: u32,
 	/// The code associated with a given account.
 	pub code_hash: CodeHash,
-	/// Pay rent at most up to this value.
-	pub rent_allowance: Balance,
-	/// The amount of rent that was paid by the contract over its whole lifetime.
+	/// The amount of balance that is currently deposited to pay for consumed storage.
 	///
-	/// A restored contract starts with a value of zero just like a new contract.
-	pub rent_paid: Balance,
-	/// Last block rent has been paid.
-	pub deduct_block: BlockNumber,
-	/// Last block child storage has been written.
-	pub last_write: Option<BlockNumber>,
+	/// This balance is reserved on the contract's account and refunded to whoever frees
+	/// the storage it pays for.
+	pub storage_deposit: Balance,
 	/// This field is reserved for future evolution of format.
 	pub _reserved: Option<()>,
 }
 
-impl<CodeHash, Balance, BlockNumber> RawAliveContractInfo<CodeHash, Balance, BlockNumber> {
+impl<CodeHash, Balance> RawContractInfo<CodeHash, Balance> {
 	/// Associated child trie unique id is built from the hash part of the trie id.
 	pub fn child_trie_info(&self) -> ChildInfo {
 		child_trie_info(&self.trie_id[..])
@@ -121,30 +74,6 @@ fn child_trie_info(trie_id: &[u8]) -> ChildInfo {
 	ChildInfo::new_default(trie_id)
 }
 
-#[derive(Encode, Decode, PartialEq, Eq, RuntimeDebug)]
-pub struct RawTombstoneContractInfo<H, Hasher>(H, PhantomData<Hasher>);
-
-impl<H, Hasher> RawTombstoneContractInfo<H, Hasher>
-where
-	H: Member + MaybeSerializeDeserialize+ Debug
-		+ AsRef<[u8]> + AsMut<[u8]> + Copy + Default
-		+ sp_std::hash::Hash + Codec,
-	Hasher: Hash<Output=H>,
-{
-	pub fn new(storage_root: &[u8], code_hash: H) -> Self {
-		let mut buf = Vec::new();
-		storage_root.using_encoded(|encoded| buf.extend_from_slice(encoded));
-		buf.extend_from_slice(code_hash.as_ref());
-		RawTombstoneContractInfo(<Hasher as Hash>::hash(&buf[..]), PhantomData)
-	}
-}
-
-impl<T: Config> From<AliveContractInfo<T>> for ContractInfo<T> {
-	fn from(alive_info: AliveContractInfo<T>) -> Self {
-		Self::Alive(alive_info)
-	}
-}
-
 #[derive(Encode, Decode)]
 pub struct DeletedContract {
 	pair_count: u32,
@@ -171,46 +100,56 @@ where
 	/// If the `opt_new_value` is `None` then the kv pair is removed.
 	///
 	/// This function also updates the bookkeeping info such as: number of total non-empty pairs a
-	/// contract owns, the last block the storage was written to, etc. That's why, in contrast to
-	/// `read`, this function also requires the `account` ID.
+	/// contract owns, the total size of its storage, etc. That's why, in contrast to `read`, this
+	/// function also requires the contract info.
+	///
+	/// The storage deposit for the change is charged from `storage_meter` if one is supplied.
+	/// The change is not performed if this charge exceeds the limit of the meter.
 	pub fn write(
-		block_number: T::BlockNumber,
-		new_info: &mut AliveContractInfo<T>,
+		new_info: &mut ContractInfo<T>,
 		key: &StorageKey,
 		opt_new_value: Option<Vec<u8>>,
+		storage_meter: Option<&mut meter::NestedMeter<T>>,
 	) -> DispatchResult {
 		let hashed_key = blake2_256(key);
 		let child_trie_info = &child_trie_info(&new_info.trie_id);
 
 		let opt_prev_len = child::len(&child_trie_info, &hashed_key);
+		let prev_value_len = opt_prev_len.unwrap_or(0);
+		let new_value_len = opt_new_value
+			.as_ref()
+			.map(|new_value| new_value.len() as u32)
+			.unwrap_or(0);
+		let mut diff = meter::Diff::default();
 
 		// Update the total number of KV pairs and the number of empty pairs.
 		match (&opt_prev_len, &opt_new_value) {
 			(Some(_), None) => {
 				new_info.pair_count = new_info.pair_count.checked_sub(1)
 					.ok_or_else(|| Error::<T>::StorageExhausted)?;
+				diff.items_removed = 1;
 			},
 			(None, Some(_)) => {
 				new_info.pair_count = new_info.pair_count.checked_add(1)
 					.ok_or_else(|| Error::<T>::StorageExhausted)?;
+				diff.items_added = 1;
 			},
 			(Some(_), Some(_)) => {},
 			(None, None) => {},
 		}
 
 		// Update the total storage size.
-		let prev_value_len = opt_prev_len.unwrap_or(0);
-		let new_value_len = opt_new_value
-			.as_ref()
-			.map(|new_value| new_value.len() as u32)
-			.unwrap_or(0);
 		new_info.storage_size = new_info
 			.storage_size
 			.checked_sub(prev_value_len)
 			.and_then(|val| val.checked_add(new_value_len))
 			.ok_or_else(|| Error::<T>::StorageExhausted)?;
+		diff.bytes_added = new_value_len.saturating_sub(prev_value_len);
+		diff.bytes_removed = prev_value_len.saturating_sub(new_value_len);
 
-		new_info.last_write = Some(block_number);
+		if let Some(storage_meter) = storage_meter {
+			storage_meter.charge(&diff)?;
+		}
 
 		// Finally, perform the change on the storage.
 		match opt_new_value {
@@ -223,29 +162,22 @@ where
 
 	/// Creates a new contract descriptor in the storage with the given code hash at the given address.
 	///
-	/// Returns `Err` if there is already a contract (or a tombstone) exists at the given address.
+	/// Returns `Err` if there is already a contract exists at the given address.
 	pub fn new_contract(
 		account: &AccountIdOf<T>,
 		trie_id: TrieId,
 		ch: CodeHash<T>,
-	) -> Result<AliveContractInfo<T>, DispatchError> {
+	) -> Result<ContractInfo<T>, DispatchError> {
 		if <ContractInfoOf<T>>::contains_key(account) {
 			return Err(Error::<T>::DuplicateContract.into());
 		}
 
-		let contract = AliveContractInfo::<T> {
+		let contract = ContractInfo::<T> {
 			code_hash: ch,
 			storage_size: 0,
 			trie_id,
-			deduct_block:
-				// We want to charge rent for the first block in advance. Therefore we
-				// treat the contract as if it was created in the last block and then
-				// charge rent for it during instantiation.
-				<frame_system::Pallet<T>>::block_number().saturating_sub(1u32.into()),
-			rent_allowance: <BalanceOf<T>>::max_value(),
-			rent_paid: <BalanceOf<T>>::zero(),
 			pair_count: 0,
-			last_write: None,
+			storage_deposit: <BalanceOf<T>>::zero(),
 			_reserved: None,
 		};
 
@@ -254,9 +186,8 @@ where
 
 	/// Push a contract's trie to the deletion queue for lazy removal.
 	///
-	/// You must make sure that the contract is also removed or converted into a tombstone
-	/// when queuing the trie for deletion.
-	pub fn queue_trie_for_deletion(contract: &AliveContractInfo<T>) -> DispatchResult {
+	/// You must make sure that the contract is also removed when queuing the trie for deletion.
+	pub fn queue_trie_for_deletion(contract: &ContractInfo<T>) -> DispatchResult {
 		if <DeletionQueue<T>>::decode_len().unwrap_or(0) >= T::DeletionQueueDepth::get() as usize {
 			Err(Error::<T>::DeletionQueueFull.into())
 		} else {
@@ -363,8 +294,7 @@ where
 	#[cfg(test)]
 	pub fn code_hash(account: &AccountIdOf<T>) -> Option<CodeHash<T>>
 	{
-		<ContractInfoOf<T>>::get(account)
-			.and_then(|i| i.as_alive().map(|i| i.code_hash))
+		<ContractInfoOf<T>>::get(account).map(|i| i.code_hash)
 	}
 
 	/// Fill up the queue in order to exercise the limits during testing.
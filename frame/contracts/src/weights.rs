@@ -51,7 +51,6 @@ pub trait WeightInfo {
 	fn instantiate_with_code(c: u32, s: u32, ) -> Weight;
 	fn instantiate(c: u32, s: u32, ) -> Weight;
 	fn call(c: u32, ) -> Weight;
-	fn claim_surcharge(c: u32, ) -> Weight;
 	fn seal_caller(r: u32, ) -> Weight;
 	fn seal_address(r: u32, ) -> Weight;
 	fn seal_gas_left(r: u32, ) -> Weight;
@@ -62,8 +61,6 @@ pub trait WeightInfo {
 	fn seal_rent_allowance(r: u32, ) -> Weight;
 	fn seal_block_number(r: u32, ) -> Weight;
 	fn seal_now(r: u32, ) -> Weight;
-	fn seal_rent_params(r: u32, ) -> Weight;
-	fn seal_rent_status(r: u32, ) -> Weight;
 	fn seal_weight_to_fee(r: u32, ) -> Weight;
 	fn seal_gas(r: u32, ) -> Weight;
 	fn seal_input(r: u32, ) -> Weight;
@@ -72,8 +69,6 @@ pub trait WeightInfo {
 	fn seal_return_per_kb(n: u32, ) -> Weight;
 	fn seal_terminate(r: u32, ) -> Weight;
 	fn seal_terminate_per_code_kb(c: u32, ) -> Weight;
-	fn seal_restore_to(r: u32, ) -> Weight;
-	fn seal_restore_to_per_code_kb_delta(c: u32, t: u32, d: u32, ) -> Weight;
 	fn seal_random(r: u32, ) -> Weight;
 	fn seal_deposit_event(r: u32, ) -> Weight;
 	fn seal_deposit_event_per_topic_and_kb(t: u32, n: u32, ) -> Weight;
@@ -204,13 +199,6 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(4 as Weight))
 			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
-	fn claim_surcharge(c: u32, ) -> Weight {
-		(132_551_000 as Weight)
-			// Standard Error: 1_000
-			.saturating_add((4_740_000 as Weight).saturating_mul(c as Weight))
-			.saturating_add(T::DbWeight::get().reads(5 as Weight))
-			.saturating_add(T::DbWeight::get().writes(4 as Weight))
-	}
 	fn seal_caller(r: u32, ) -> Weight {
 		(137_742_000 as Weight)
 			// Standard Error: 74_000
@@ -281,20 +269,6 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(4 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
-	fn seal_rent_params(r: u32, ) -> Weight {
-		(149_701_000 as Weight)
-			// Standard Error: 297_000
-			.saturating_add((357_149_000 as Weight).saturating_mul(r as Weight))
-			.saturating_add(T::DbWeight::get().reads(4 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-	}
-	fn seal_rent_status(r: u32, ) -> Weight {
-		(146_863_000 as Weight)
-			// Standard Error: 191_000
-			.saturating_add((638_683_000 as Weight).saturating_mul(r as Weight))
-			.saturating_add(T::DbWeight::get().reads(4 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-	}
 	fn seal_weight_to_fee(r: u32, ) -> Weight {
 		(144_278_000 as Weight)
 			// Standard Error: 149_000
@@ -353,28 +327,6 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(6 as Weight))
 			.saturating_add(T::DbWeight::get().writes(5 as Weight))
 	}
-	fn seal_restore_to(r: u32, ) -> Weight {
-		(159_200_000 as Weight)
-			// Standard Error: 261_000
-			.saturating_add((103_048_000 as Weight).saturating_mul(r as Weight))
-			.saturating_add(T::DbWeight::get().reads(4 as Weight))
-			.saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(r as Weight)))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes((5 as Weight).saturating_mul(r as Weight)))
-	}
-	fn seal_restore_to_per_code_kb_delta(c: u32, t: u32, d: u32, ) -> Weight {
-		(58_389_000 as Weight)
-			// Standard Error: 131_000
-			.saturating_add((7_910_000 as Weight).saturating_mul(c as Weight))
-			// Standard Error: 131_000
-			.saturating_add((4_036_000 as Weight).saturating_mul(t as Weight))
-			// Standard Error: 1_156_000
-			.saturating_add((3_714_110_000 as Weight).saturating_mul(d as Weight))
-			.saturating_add(T::DbWeight::get().reads(7 as Weight))
-			.saturating_add(T::DbWeight::get().reads((100 as Weight).saturating_mul(d as Weight)))
-			.saturating_add(T::DbWeight::get().writes(7 as Weight))
-			.saturating_add(T::DbWeight::get().writes((100 as Weight).saturating_mul(d as Weight)))
-	}
 	fn seal_random(r: u32, ) -> Weight {
 		(138_794_000 as Weight)
 			// Standard Error: 216_000
@@ -874,13 +826,6 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
 	}
-	fn claim_surcharge(c: u32, ) -> Weight {
-		(132_551_000 as Weight)
-			// Standard Error: 1_000
-			.saturating_add((4_740_000 as Weight).saturating_mul(c as Weight))
-			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
-	}
 	fn seal_caller(r: u32, ) -> Weight {
 		(137_742_000 as Weight)
 			// Standard Error: 74_000
@@ -951,20 +896,6 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
-	fn seal_rent_params(r: u32, ) -> Weight {
-		(149_701_000 as Weight)
-			// Standard Error: 297_000
-			.saturating_add((357_149_000 as Weight).saturating_mul(r as Weight))
-			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
-	}
-	fn seal_rent_status(r: u32, ) -> Weight {
-		(146_863_000 as Weight)
-			// Standard Error: 191_000
-			.saturating_add((638_683_000 as Weight).saturating_mul(r as Weight))
-			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
-	}
 	fn seal_weight_to_fee(r: u32, ) -> Weight {
 		(144_278_000 as Weight)
 			// Standard Error: 149_000
@@ -1023,28 +954,6 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
 	}
-	fn seal_restore_to(r: u32, ) -> Weight {
-		(159_200_000 as Weight)
-			// Standard Error: 261_000
-			.saturating_add((103_048_000 as Weight).saturating_mul(r as Weight))
-			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
-			.saturating_add(RocksDbWeight::get().reads((4 as Weight).saturating_mul(r as Weight)))
-			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
-			.saturating_add(RocksDbWeight::get().writes((5 as Weight).saturating_mul(r as Weight)))
-	}
-	fn seal_restore_to_per_code_kb_delta(c: u32, t: u32, d: u32, ) -> Weight {
-		(58_389_000 as Weight)
-			// Standard Error: 131_000
-			.saturating_add((7_910_000 as Weight).saturating_mul(c as Weight))
-			// Standard Error: 131_000
-			.saturating_add((4_036_000 as Weight).saturating_mul(t as Weight))
-			// Standard Error: 1_156_000
-			.saturating_add((3_714_110_000 as Weight).saturating_mul(d as Weight))
-			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
-			.saturating_add(RocksDbWeight::get().reads((100 as Weight).saturating_mul(d as Weight)))
-			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
-			.saturating_add(RocksDbWeight::get().writes((100 as Weight).saturating_mul(d as Weight)))
-	}
 	fn seal_random(r: u32, ) -> Weight {
 		(138_794_000 as Weight)
 			// Standard Error: 216_000
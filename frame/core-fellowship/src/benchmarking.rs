@@ -0,0 +1,156 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Core Fellowship pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{benchmarks, account, whitelisted_caller, impl_benchmark_test_suite};
+use frame_support::{traits::{EnsureOrigin, Get}, dispatch::UnfilteredDispatchable};
+use frame_system::RawOrigin;
+
+use crate::Pallet as CoreFellowship;
+
+const SEED: u32 = 0;
+
+/// The number of ranks of the parameters used by the benchmarks.
+const RANKS: usize = 9;
+
+/// Parameters with `RANKS` ranks, where every period is 100 blocks.
+fn params<T: Config>() -> ParamsType<T::Balance, T::BlockNumber> {
+	ParamsType {
+		active_salary: vec![100u32.into(); RANKS],
+		passive_salary: vec![10u32.into(); RANKS],
+		demotion_period: vec![100u32.into(); RANKS],
+		min_promotion_period: vec![100u32.into(); RANKS],
+		offboard_timeout: 100u32.into(),
+	}
+}
+
+/// Add a tracked member of `rank` to the collective, last promoted and proven at block zero, and
+/// with evidence of the maximum size.
+fn make_member<T: Config>(rank: Rank) -> Result<T::AccountId, &'static str> {
+	let who: T::AccountId = account("member", 0, SEED);
+	T::Members::induct(&who)?;
+	for _ in 0..rank {
+		T::Members::promote(&who)?;
+	}
+	let status = MemberStatus {
+		is_active: true,
+		last_promotion: Zero::zero(),
+		last_proof: Zero::zero(),
+	};
+	Member::<T>::insert(&who, status);
+	let evidence = vec![0; T::EvidenceSize::get() as usize];
+	MemberEvidence::<T>::insert(&who, (Wish::Promotion, evidence));
+	Ok(who)
+}
+
+benchmarks! {
+	set_params {
+		let params = params::<T>();
+		let origin = T::ParamsOrigin::successful_origin();
+		let call = Call::<T>::set_params(params.clone());
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert_eq!(Params::<T>::get(), params);
+	}
+
+	induct {
+		let who: T::AccountId = account("member", 0, SEED);
+		let origin = T::InductOrigin::successful_origin();
+		let call = Call::<T>::induct(who.clone());
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert!(Member::<T>::contains_key(&who));
+	}
+
+	// `PromoteOrigin::successful_origin` must be allowed to promote to rank one.
+	promote {
+		Params::<T>::put(params::<T>());
+		let who = make_member::<T>(0)?;
+		frame_system::Pallet::<T>::set_block_number(100u32.into());
+		let origin = T::PromoteOrigin::successful_origin();
+		let call = Call::<T>::promote(who.clone(), 1);
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert_eq!(T::Members::rank_of(&who), Some(1));
+		assert!(!MemberEvidence::<T>::contains_key(&who));
+	}
+
+	// `PromoteOrigin::successful_origin` must be allowed to approve at rank one.
+	approve {
+		Params::<T>::put(params::<T>());
+		let who = make_member::<T>(1)?;
+		let origin = T::PromoteOrigin::successful_origin();
+		let call = Call::<T>::approve(who.clone(), 1);
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert!(!MemberEvidence::<T>::contains_key(&who));
+	}
+
+	bump_offboard {
+		Params::<T>::put(params::<T>());
+		let who = make_member::<T>(0)?;
+		frame_system::Pallet::<T>::set_block_number(100u32.into());
+		let caller: T::AccountId = whitelisted_caller();
+	}: bump(RawOrigin::Signed(caller), who.clone())
+	verify {
+		assert!(!Member::<T>::contains_key(&who));
+	}
+
+	bump_demote {
+		Params::<T>::put(params::<T>());
+		let who = make_member::<T>(1)?;
+		frame_system::Pallet::<T>::set_block_number(100u32.into());
+		let caller: T::AccountId = whitelisted_caller();
+	}: bump(RawOrigin::Signed(caller), who.clone())
+	verify {
+		assert_eq!(T::Members::rank_of(&who), Some(0));
+		assert!(Member::<T>::contains_key(&who));
+	}
+
+	set_active {
+		let who = make_member::<T>(0)?;
+	}: _(RawOrigin::Signed(who.clone()), false)
+	verify {
+		assert!(!Member::<T>::get(&who).ok_or("member is tracked")?.is_active);
+	}
+
+	submit_evidence {
+		let who = make_member::<T>(0)?;
+		let evidence = vec![1; T::EvidenceSize::get() as usize];
+	}: _(RawOrigin::Signed(who.clone()), Wish::Retention, evidence)
+	verify {
+		assert_eq!(MemberEvidence::<T>::get(&who).map(|(wish, _)| wish), Some(Wish::Retention));
+	}
+
+	import {
+		let who: T::AccountId = account("member", 0, SEED);
+		T::Members::induct(&who)?;
+	}: _(RawOrigin::Signed(who.clone()))
+	verify {
+		assert!(Member::<T>::contains_key(&who));
+	}
+}
+
+impl_benchmark_test_suite!(
+	CoreFellowship,
+	crate::tests::new_test_ext(),
+	crate::tests::Test,
+);
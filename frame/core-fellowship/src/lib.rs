@@ -0,0 +1,487 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Core Fellowship Pallet
+//!
+//! Logic specific to technical fellowships, layered on top of a ranked membership collective.
+//!
+//! ## Overview
+//!
+//! The underlying collective (anything implementing [`RankedMembers`]) only knows about the
+//! rank of each member. This pallet adds the notion of time to it: every tracked member has a
+//! record of when they were last promoted and when they last proved their competence at their
+//! current rank (`last_proof`).
+//!
+//! ## Cadence
+//!
+//! Cadence is governed by the [`Params`] storage item, set through `ParamsOrigin`:
+//!
+//! - `min_promotion_period[r - 1]`: the minimum time which must pass after the last promotion
+//!   before a member may be promoted to rank `r`.
+//! - `demotion_period[r - 1]`: the maximum time a member of rank `r` may go without an approval
+//!   before anyone may `bump` them down a rank.
+//! - `offboard_timeout`: the maximum time a rank zero member may go without an approval before
+//!   anyone may `bump` them out of the collective.
+//!
+//! A zero period disables the corresponding check.
+//!
+//! ## Activity
+//!
+//! Members are active unless they declare otherwise with `set_active`. An active member of rank
+//! `r` earns `active_salary[r - 1]`, an inactive one `passive_salary[r - 1]`, through the
+//! [`GetSalary`] implementation of the pallet. Inactive members may neither be promoted nor
+//! approved at their rank, so they are demoted once the demotion period of their rank lapses.
+//!
+//! ## Evidence
+//!
+//! Members may submit evidence, along with a [`Wish`] to either be retained at their current rank
+//! or be promoted. The evidence is stored until the member is approved, promoted or demoted.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! - `set_params`: Set the cadence parameters. `ParamsOrigin` only.
+//! - `induct`: Induct a new member at rank zero. `InductOrigin` only.
+//! - `promote`: Promote a member by one rank. `PromoteOrigin` of sufficient rank only.
+//! - `approve`: Confirm an active member at their current rank, resetting their demotion timer.
+//! - `bump`: Demote or offboard a member whose demotion period has lapsed. Anyone.
+//! - `set_active`: Mark oneself as active or inactive.
+//! - `submit_evidence`: Submit evidence of one's work along with a wish.
+//! - `import`: Begin tracking an existing member of the underlying collective.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_std::prelude::*;
+use codec::{Encode, Decode};
+use sp_runtime::{RuntimeDebug, traits::{AtLeast32BitUnsigned, Saturating, Zero}};
+use frame_support::traits::{GetSalary, RankedMembers};
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[cfg(test)]
+mod tests;
+mod benchmarking;
+pub mod weights;
+
+/// The desired outcome for which evidence is presented.
+#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug)]
+pub enum Wish {
+	/// Member wishes only to retain their current rank.
+	Retention,
+	/// Member wishes to be promoted.
+	Promotion,
+}
+
+/// The cadence and salary parameters of the fellowship.
+///
+/// All vectors are indexed by rank, offset by one; i.e. the entry at index `0` relates to rank
+/// one.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, Default, RuntimeDebug)]
+pub struct ParamsType<Balance, BlockNumber> {
+	/// The salary of an active member of the given rank.
+	pub active_salary: Vec<Balance>,
+	/// The salary of an inactive member of the given rank.
+	pub passive_salary: Vec<Balance>,
+	/// The time after which a member of the given rank will be open to demotion if not approved.
+	pub demotion_period: Vec<BlockNumber>,
+	/// The minimum time since the last promotion before a member may be promoted to the given rank.
+	pub min_promotion_period: Vec<BlockNumber>,
+	/// The time after which an unapproved rank zero member may be removed.
+	pub offboard_timeout: BlockNumber,
+}
+
+/// The status of a single member.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug)]
+pub struct MemberStatus<BlockNumber> {
+	/// Whether the member is actively participating. Inactive members earn the passive salary,
+	/// and may neither be promoted nor approved.
+	pub is_active: bool,
+	/// The block number at which we last promoted them.
+	pub last_promotion: BlockNumber,
+	/// The last time a member was demoted, promoted or proved their rank.
+	pub last_proof: BlockNumber,
+}
+
+/// The rank type used by the fellowship.
+pub type Rank = u16;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The balance type of the salaries.
+		type Balance: Parameter + Member + AtLeast32BitUnsigned + Default + Copy;
+
+		/// The underlying ranked membership collective.
+		type Members: RankedMembers<AccountId = Self::AccountId, Rank = Rank>;
+
+		/// The origin which may set the cadence parameters.
+		type ParamsOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The origin which may induct new members at rank zero.
+		type InductOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The origin which may approve and promote members. The `Success` value is the maximum
+		/// rank to which it may promote or at which it may approve.
+		type PromoteOrigin: EnsureOrigin<Self::Origin, Success = Rank>;
+
+		/// The maximum size in bytes of submitted evidence.
+		#[pallet::constant]
+		type EvidenceSize: Get<u32>;
+
+		/// Information on runtime weights.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	/// The overall cadence parameters of the fellowship.
+	#[pallet::storage]
+	pub type Params<T: Config> =
+		StorageValue<_, ParamsType<T::Balance, T::BlockNumber>, ValueQuery>;
+
+	/// The status of every tracked member.
+	#[pallet::storage]
+	pub type Member<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, MemberStatus<T::BlockNumber>, OptionQuery>;
+
+	/// Some evidence together with the desired outcome for which it was presented.
+	#[pallet::storage]
+	pub type MemberEvidence<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, (Wish, Vec<u8>), OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::metadata(
+		T::AccountId = "AccountId",
+		T::Balance = "Balance",
+		T::BlockNumber = "BlockNumber",
+	)]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Parameters for the fellowship have changed.
+		ParamsChanged(ParamsType<T::Balance, T::BlockNumber>),
+		/// Member activity flag has been set. \[ who, is_active \]
+		ActiveChanged(T::AccountId, bool),
+		/// Member has begun being tracked in this pallet. \[ who, rank \]
+		Inducted(T::AccountId, Rank),
+		/// Member has been removed from being tracked in this pallet (i.e. because rank is now
+		/// zero and the offboard timeout has elapsed). \[ who \]
+		Offboarded(T::AccountId),
+		/// Member has been promoted to the given rank. \[ who, to_rank \]
+		Promoted(T::AccountId, Rank),
+		/// Member has been demoted to the given (non-zero) rank. \[ who, to_rank \]
+		Demoted(T::AccountId, Rank),
+		/// Member has been proven at their current rank, postponing auto-demotion.
+		/// \[ who, at_rank \]
+		Proven(T::AccountId, Rank),
+		/// Member has stated evidence. Any existing evidence is replaced. \[ who, wish \]
+		EvidenceSubmitted(T::AccountId, Wish),
+		/// Pre-ranked account has been inducted at their current rank. \[ who, rank \]
+		Imported(T::AccountId, Rank),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Member's rank is too low.
+		Unranked,
+		/// Member's rank is not zero.
+		Ranked,
+		/// Member's rank is not as expected - generally means that the rank provided to the call
+		/// does not agree with the state of the system.
+		UnexpectedRank,
+		/// The given rank is invalid - this generally means it's not between 1 and the number of
+		/// ranks covered by `Params`.
+		InvalidRank,
+		/// The origin does not have enough permission to do this operation.
+		NoPermission,
+		/// No work needs to be done at present for this member.
+		NothingDoing,
+		/// The candidate has already been inducted. This should never happen since it would
+		/// require a candidate (rank 0) to already be tracked in the pallet.
+		AlreadyInducted,
+		/// The candidate has not been inducted, so cannot be offboarded from this pallet.
+		NotTracked,
+		/// Operation cannot be done yet since not enough time has passed.
+		TooSoon,
+		/// The evidence submitted is larger than `EvidenceSize`.
+		EvidenceTooLarge,
+		/// The period and salary vectors of the parameters do not all have the same length.
+		InconsistentParams,
+		/// The member is not active.
+		Inactive,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the parameters.
+		///
+		/// - `origin`: An origin complying with `ParamsOrigin`.
+		/// - `params`: The new parameters for the pallet.
+		#[pallet::weight(T::WeightInfo::set_params())]
+		pub fn set_params(
+			origin: OriginFor<T>,
+			params: ParamsType<T::Balance, T::BlockNumber>,
+		) -> DispatchResultWithPostInfo {
+			T::ParamsOrigin::ensure_origin(origin)?;
+			let ranks = params.demotion_period.len();
+			ensure!(
+				params.min_promotion_period.len() == ranks &&
+					params.active_salary.len() == ranks &&
+					params.passive_salary.len() == ranks,
+				Error::<T>::InconsistentParams,
+			);
+			Params::<T>::put(&params);
+			Self::deposit_event(Event::<T>::ParamsChanged(params));
+			Ok(().into())
+		}
+
+		/// Introduce a new and unranked candidate (rank zero).
+		///
+		/// - `origin`: An origin complying with `InductOrigin`.
+		/// - `who`: The account ID of the candidate to be inducted and become a member.
+		#[pallet::weight(T::WeightInfo::induct())]
+		pub fn induct(origin: OriginFor<T>, who: T::AccountId) -> DispatchResultWithPostInfo {
+			T::InductOrigin::ensure_origin(origin)?;
+			ensure!(!Member::<T>::contains_key(&who), Error::<T>::AlreadyInducted);
+			ensure!(T::Members::rank_of(&who).is_none(), Error::<T>::AlreadyInducted);
+
+			T::Members::induct(&who)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			Member::<T>::insert(
+				&who,
+				MemberStatus { is_active: true, last_promotion: now, last_proof: now },
+			);
+			Self::deposit_event(Event::<T>::Inducted(who, T::Members::min_rank()));
+			Ok(().into())
+		}
+
+		/// Increment the rank of a ranked, tracked and active account.
+		///
+		/// - `origin`: An origin complying with `PromoteOrigin` whose success value is at least
+		///   `to_rank`.
+		/// - `who`: The account ID of the member to be promoted.
+		/// - `to_rank`: One more than the current rank of `who`.
+		#[pallet::weight(T::WeightInfo::promote())]
+		pub fn promote(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			to_rank: Rank,
+		) -> DispatchResultWithPostInfo {
+			let max_rank = T::PromoteOrigin::ensure_origin(origin)?;
+			ensure!(max_rank >= to_rank, Error::<T>::NoPermission);
+			let rank = T::Members::rank_of(&who).ok_or(Error::<T>::Unranked)?;
+			ensure!(rank.checked_add(1) == Some(to_rank), Error::<T>::UnexpectedRank);
+
+			let mut member = Member::<T>::get(&who).ok_or(Error::<T>::NotTracked)?;
+			ensure!(member.is_active, Error::<T>::Inactive);
+			let now = frame_system::Pallet::<T>::block_number();
+
+			let params = Params::<T>::get();
+			let min_period =
+				Self::period_of(&params.min_promotion_period, to_rank).ok_or(Error::<T>::InvalidRank)?;
+			ensure!(
+				min_period.is_zero() || now.saturating_sub(member.last_promotion) >= min_period,
+				Error::<T>::TooSoon,
+			);
+
+			T::Members::promote(&who)?;
+			member.last_promotion = now;
+			member.last_proof = now;
+			Member::<T>::insert(&who, &member);
+			MemberEvidence::<T>::remove(&who);
+
+			Self::deposit_event(Event::<T>::Promoted(who, to_rank));
+			Ok(().into())
+		}
+
+		/// Approve an active member to continue at their rank, resetting their demotion timer.
+		///
+		/// - `origin`: An origin complying with `PromoteOrigin` whose success value is at least
+		///   `at_rank`.
+		/// - `who`: The account ID of the member to be approved.
+		/// - `at_rank`: The current rank of `who`.
+		#[pallet::weight(T::WeightInfo::approve())]
+		pub fn approve(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			at_rank: Rank,
+		) -> DispatchResultWithPostInfo {
+			let max_rank = T::PromoteOrigin::ensure_origin(origin)?;
+			ensure!(max_rank >= at_rank, Error::<T>::NoPermission);
+			ensure!(at_rank > T::Members::min_rank(), Error::<T>::InvalidRank);
+			let rank = T::Members::rank_of(&who).ok_or(Error::<T>::Unranked)?;
+			ensure!(rank == at_rank, Error::<T>::UnexpectedRank);
+
+			let mut member = Member::<T>::get(&who).ok_or(Error::<T>::NotTracked)?;
+			ensure!(member.is_active, Error::<T>::Inactive);
+			member.last_proof = frame_system::Pallet::<T>::block_number();
+			Member::<T>::insert(&who, &member);
+			MemberEvidence::<T>::remove(&who);
+
+			Self::deposit_event(Event::<T>::Proven(who, at_rank));
+			Ok(().into())
+		}
+
+		/// Bump the state of a member.
+		///
+		/// This will demote a member whose `last_proof` is now beyond their rank's
+		/// `demotion_period`, or offboard a rank zero member whose `last_proof` is beyond the
+		/// `offboard_timeout`.
+		///
+		/// The dispatch origin for this call must be _Signed_. The call is free if it results in a
+		/// demotion or removal.
+		///
+		/// - `who`: The account ID of the member to be bumped.
+		#[pallet::weight(T::WeightInfo::bump_offboard().max(T::WeightInfo::bump_demote()))]
+		pub fn bump(origin: OriginFor<T>, who: T::AccountId) -> DispatchResultWithPostInfo {
+			let _ = ensure_signed(origin)?;
+			let mut member = Member::<T>::get(&who).ok_or(Error::<T>::NotTracked)?;
+			let rank = T::Members::rank_of(&who).ok_or(Error::<T>::Unranked)?;
+			let now = frame_system::Pallet::<T>::block_number();
+
+			let params = Params::<T>::get();
+			let demotion_period = if rank == T::Members::min_rank() {
+				params.offboard_timeout
+			} else {
+				Self::period_of(&params.demotion_period, rank).ok_or(Error::<T>::InvalidRank)?
+			};
+			ensure!(!demotion_period.is_zero(), Error::<T>::NothingDoing);
+			ensure!(
+				now.saturating_sub(member.last_proof) >= demotion_period,
+				Error::<T>::NothingDoing,
+			);
+
+			T::Members::demote(&who)?;
+			MemberEvidence::<T>::remove(&who);
+			match T::Members::rank_of(&who) {
+				Some(new_rank) => {
+					member.last_proof = now;
+					Member::<T>::insert(&who, &member);
+					Self::deposit_event(Event::<T>::Demoted(who, new_rank));
+				},
+				None => {
+					Member::<T>::remove(&who);
+					Self::deposit_event(Event::<T>::Offboarded(who));
+				},
+			}
+
+			Ok(Pays::No.into())
+		}
+
+		/// Set whether a member is active or not.
+		///
+		/// Inactive members earn the passive salary of their rank, and may neither be promoted nor
+		/// approved, so they are demoted once the demotion period of their rank lapses.
+		///
+		/// - `origin`: A `Signed` origin of a tracked member.
+		/// - `is_active`: `true` iff the member is active.
+		#[pallet::weight(T::WeightInfo::set_active())]
+		pub fn set_active(origin: OriginFor<T>, is_active: bool) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let mut member = Member::<T>::get(&who).ok_or(Error::<T>::NotTracked)?;
+			member.is_active = is_active;
+			Member::<T>::insert(&who, &member);
+			Self::deposit_event(Event::<T>::ActiveChanged(who, is_active));
+			Ok(().into())
+		}
+
+		/// Provide evidence that a rank is deserved.
+		///
+		/// This is free as long as no evidence for the forthcoming judgement is already submitted.
+		/// Evidence is cleared after an outcome (either demotion, promotion or approval).
+		///
+		/// - `origin`: A `Signed` origin of a tracked member.
+		/// - `wish`: The stated desire of the member.
+		/// - `evidence`: A dump of evidence to be considered. Must be no larger than
+		///   `EvidenceSize`.
+		#[pallet::weight(T::WeightInfo::submit_evidence())]
+		pub fn submit_evidence(
+			origin: OriginFor<T>,
+			wish: Wish,
+			evidence: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(Member::<T>::contains_key(&who), Error::<T>::NotTracked);
+			ensure!(evidence.len() <= T::EvidenceSize::get() as usize, Error::<T>::EvidenceTooLarge);
+
+			let replaced = MemberEvidence::<T>::contains_key(&who);
+			MemberEvidence::<T>::insert(&who, (wish, evidence));
+			Self::deposit_event(Event::<T>::EvidenceSubmitted(who, wish));
+			Ok(if replaced { Pays::Yes } else { Pays::No }.into())
+		}
+
+		/// Introduce an already-ranked individual of the collective into this pallet. The rank
+		/// may still be zero.
+		///
+		/// This resets `last_proof` to the current block and `last_promotion` will be set to zero,
+		/// thereby delaying any automatic demotion but allowing immediate promotion.
+		///
+		/// - `origin`: A signed origin of a ranked, but not tracked, account.
+		#[pallet::weight(T::WeightInfo::import())]
+		pub fn import(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!Member::<T>::contains_key(&who), Error::<T>::AlreadyInducted);
+			let rank = T::Members::rank_of(&who).ok_or(Error::<T>::Unranked)?;
+
+			let now = frame_system::Pallet::<T>::block_number();
+			Member::<T>::insert(
+				&who,
+				MemberStatus { is_active: true, last_promotion: Zero::zero(), last_proof: now },
+			);
+			Self::deposit_event(Event::<T>::Imported(who, rank));
+
+			Ok(Pays::No.into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Look up the period for `rank` (which must be at least one) in a rank-indexed list.
+		pub(crate) fn period_of(periods: &[T::BlockNumber], rank: Rank) -> Option<T::BlockNumber> {
+			let index = (rank as usize).checked_sub(1)?;
+			periods.get(index).cloned()
+		}
+	}
+
+	impl<T: Config> GetSalary<Rank, T::AccountId, T::Balance> for Pallet<T> {
+		/// The active or passive salary of `rank`, depending on whether `who` is active. Rank
+		/// zero and untracked members earn nothing.
+		fn get_salary(rank: Rank, who: &T::AccountId) -> T::Balance {
+			let index = match (rank as usize).checked_sub(1) {
+				Some(index) => index,
+				None => return Zero::zero(),
+			};
+			let params = Params::<T>::get();
+			let salaries = match Member::<T>::get(who) {
+				Some(member) if member.is_active => params.active_salary,
+				Some(_) => params.passive_salary,
+				None => return Zero::zero(),
+			};
+			salaries.get(index).cloned().unwrap_or_else(Zero::zero)
+		}
+	}
+}
@@ -0,0 +1,363 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the Core Fellowship pallet.
+
+use std::{cell::RefCell, collections::BTreeMap};
+
+use super::*;
+use crate as pallet_core_fellowship;
+
+use frame_support::{
+	assert_noop, assert_ok, parameter_types, ord_parameter_types,
+	traits::{EnsureOrigin, GetSalary},
+	dispatch::DispatchResult,
+};
+use frame_system::EnsureSignedBy;
+use sp_core::H256;
+use sp_runtime::{
+	DispatchError,
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		CoreFellowship: pallet_core_fellowship::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Call = Call;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+thread_local! {
+	pub static CLUB: RefCell<BTreeMap<u64, Rank>> = RefCell::new(BTreeMap::new());
+}
+
+/// A trivial ranked collective backed by a thread-local map.
+pub struct TestClub;
+impl RankedMembers for TestClub {
+	type AccountId = u64;
+	type Rank = Rank;
+	fn min_rank() -> Rank {
+		0
+	}
+	fn rank_of(who: &u64) -> Option<Rank> {
+		CLUB.with(|club| club.borrow().get(who).cloned())
+	}
+	fn induct(who: &u64) -> DispatchResult {
+		CLUB.with(|club| club.borrow_mut().insert(*who, 0));
+		Ok(())
+	}
+	fn promote(who: &u64) -> DispatchResult {
+		CLUB.with(|club| club.borrow_mut().entry(*who).and_modify(|r| *r += 1));
+		Ok(())
+	}
+	fn demote(who: &u64) -> DispatchResult {
+		CLUB.with(|club| {
+			let mut club = club.borrow_mut();
+			match club.get(who).cloned() {
+				None => return Err(DispatchError::Other("not a member")),
+				Some(0) => { club.remove(who); },
+				Some(r) => { club.insert(*who, r - 1); },
+			}
+			Ok(())
+		})
+	}
+}
+
+/// Origin which succeeds for signed members of the club, yielding their rank.
+pub struct EnsureClubRank;
+impl EnsureOrigin<Origin> for EnsureClubRank {
+	type Success = Rank;
+	fn try_origin(o: Origin) -> Result<Rank, Origin> {
+		let who = frame_system::ensure_signed(o.clone()).map_err(|_| o.clone())?;
+		TestClub::rank_of(&who).ok_or(o)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> Origin {
+		Origin::signed(100)
+	}
+}
+
+ord_parameter_types! {
+	pub const One: u64 = 1;
+}
+
+parameter_types! {
+	pub const EvidenceSize: u32 = 16;
+}
+
+impl Config for Test {
+	type Event = Event;
+	type Balance = u64;
+	type Members = TestClub;
+	type ParamsOrigin = EnsureSignedBy<One, u64>;
+	type InductOrigin = EnsureSignedBy<One, u64>;
+	type PromoteOrigin = EnsureClubRank;
+	type EvidenceSize = EvidenceSize;
+	type WeightInfo = ();
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| {
+		CLUB.with(|club| *club.borrow_mut() = vec![(100, 9)].into_iter().collect());
+		let params = ParamsType {
+			active_salary: vec![10, 20, 30, 40, 50, 60, 70, 80, 90],
+			passive_salary: vec![1, 2, 3, 4, 5, 6, 7, 8, 9],
+			demotion_period: vec![2, 4, 6, 8, 10, 12, 14, 16, 18],
+			min_promotion_period: vec![1, 2, 3, 4, 5, 6, 7, 8, 9],
+			offboard_timeout: 1,
+		};
+		assert_ok!(CoreFellowship::set_params(Origin::signed(1), params));
+		System::set_block_number(1);
+	});
+	ext
+}
+
+fn run_to(n: u64) {
+	System::set_block_number(n);
+}
+
+fn rank_of(who: u64) -> Option<Rank> {
+	TestClub::rank_of(&who)
+}
+
+#[test]
+fn basic_stuff() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(rank_of(10), None);
+		assert_eq!(Member::<Test>::get(10), None);
+		assert_eq!(Params::<Test>::get().demotion_period.len(), 9);
+	});
+}
+
+#[test]
+fn set_params_works() {
+	new_test_ext().execute_with(|| {
+		let params = ParamsType {
+			active_salary: vec![10, 20],
+			passive_salary: vec![1, 2],
+			demotion_period: vec![1, 2],
+			min_promotion_period: vec![1],
+			offboard_timeout: 1,
+		};
+		assert_noop!(
+			CoreFellowship::set_params(Origin::signed(2), params.clone()),
+			DispatchError::BadOrigin,
+		);
+		assert_noop!(
+			CoreFellowship::set_params(Origin::signed(1), params.clone()),
+			Error::<Test>::InconsistentParams,
+		);
+		let params =
+			ParamsType { min_promotion_period: vec![1, 2], passive_salary: vec![1], ..params };
+		assert_noop!(
+			CoreFellowship::set_params(Origin::signed(1), params),
+			Error::<Test>::InconsistentParams,
+		);
+	});
+}
+
+#[test]
+fn induct_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(CoreFellowship::induct(Origin::signed(2), 10), DispatchError::BadOrigin);
+		assert_ok!(CoreFellowship::induct(Origin::signed(1), 10));
+		assert_eq!(rank_of(10), Some(0));
+		assert_eq!(
+			Member::<Test>::get(10),
+			Some(MemberStatus { is_active: true, last_promotion: 1, last_proof: 1 }),
+		);
+		assert_noop!(CoreFellowship::induct(Origin::signed(1), 10), Error::<Test>::AlreadyInducted);
+	});
+}
+
+#[test]
+fn promote_respects_cadence_and_permissions() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoreFellowship::induct(Origin::signed(1), 10));
+		assert_noop!(CoreFellowship::promote(Origin::signed(100), 10, 2), Error::<Test>::UnexpectedRank);
+		// min promotion period to rank 1 is one block.
+		assert_noop!(CoreFellowship::promote(Origin::signed(100), 10, 1), Error::<Test>::TooSoon);
+		run_to(2);
+		assert_noop!(CoreFellowship::promote(Origin::signed(10), 10, 1), Error::<Test>::NoPermission);
+		assert_ok!(CoreFellowship::promote(Origin::signed(100), 10, 1));
+		assert_eq!(rank_of(10), Some(1));
+
+		// to rank 2 takes two blocks.
+		run_to(3);
+		assert_noop!(CoreFellowship::promote(Origin::signed(100), 10, 2), Error::<Test>::TooSoon);
+		run_to(4);
+		assert_ok!(CoreFellowship::promote(Origin::signed(100), 10, 2));
+		assert_eq!(rank_of(10), Some(2));
+		assert_eq!(
+			Member::<Test>::get(10),
+			Some(MemberStatus { is_active: true, last_promotion: 4, last_proof: 4 }),
+		);
+	});
+}
+
+#[test]
+fn auto_demote_and_offboard_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoreFellowship::induct(Origin::signed(1), 10));
+		run_to(2);
+		assert_ok!(CoreFellowship::promote(Origin::signed(100), 10, 1));
+
+		// demotion period of rank 1 is two blocks.
+		run_to(3);
+		assert_noop!(CoreFellowship::bump(Origin::signed(2), 10), Error::<Test>::NothingDoing);
+		run_to(4);
+		assert_ok!(CoreFellowship::bump(Origin::signed(2), 10));
+		assert_eq!(rank_of(10), Some(0));
+		assert_eq!(Member::<Test>::get(10).unwrap().last_proof, 4);
+
+		// offboard timeout is one block.
+		run_to(5);
+		assert_ok!(CoreFellowship::bump(Origin::signed(2), 10));
+		assert_eq!(rank_of(10), None);
+		assert_eq!(Member::<Test>::get(10), None);
+		assert_noop!(CoreFellowship::bump(Origin::signed(2), 10), Error::<Test>::NotTracked);
+	});
+}
+
+#[test]
+fn approve_postpones_demotion() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoreFellowship::induct(Origin::signed(1), 10));
+		run_to(2);
+		assert_ok!(CoreFellowship::promote(Origin::signed(100), 10, 1));
+		run_to(3);
+		assert_noop!(CoreFellowship::approve(Origin::signed(100), 10, 2), Error::<Test>::UnexpectedRank);
+		assert_ok!(CoreFellowship::approve(Origin::signed(100), 10, 1));
+		run_to(4);
+		assert_noop!(CoreFellowship::bump(Origin::signed(2), 10), Error::<Test>::NothingDoing);
+		run_to(5);
+		assert_ok!(CoreFellowship::bump(Origin::signed(2), 10));
+		assert_eq!(rank_of(10), Some(0));
+	});
+}
+
+#[test]
+fn evidence_is_stored_and_cleared() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			CoreFellowship::submit_evidence(Origin::signed(10), Wish::Promotion, vec![0; 4]),
+			Error::<Test>::NotTracked,
+		);
+		assert_ok!(CoreFellowship::induct(Origin::signed(1), 10));
+		assert_noop!(
+			CoreFellowship::submit_evidence(Origin::signed(10), Wish::Promotion, vec![0; 17]),
+			Error::<Test>::EvidenceTooLarge,
+		);
+		assert_ok!(CoreFellowship::submit_evidence(Origin::signed(10), Wish::Promotion, vec![0; 4]));
+		assert_eq!(MemberEvidence::<Test>::get(10), Some((Wish::Promotion, vec![0; 4])));
+		run_to(2);
+		assert_ok!(CoreFellowship::promote(Origin::signed(100), 10, 1));
+		assert_eq!(MemberEvidence::<Test>::get(10), None);
+	});
+}
+
+#[test]
+fn import_and_set_active_work() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(CoreFellowship::set_active(Origin::signed(100), false), Error::<Test>::NotTracked);
+		assert_noop!(CoreFellowship::import(Origin::signed(10)), Error::<Test>::Unranked);
+		assert_ok!(CoreFellowship::import(Origin::signed(100)));
+		assert_eq!(
+			Member::<Test>::get(100),
+			Some(MemberStatus { is_active: true, last_promotion: 0, last_proof: 1 }),
+		);
+		assert_noop!(CoreFellowship::import(Origin::signed(100)), Error::<Test>::AlreadyInducted);
+		assert_ok!(CoreFellowship::set_active(Origin::signed(100), false));
+		assert!(!Member::<Test>::get(100).unwrap().is_active);
+	});
+}
+
+#[test]
+fn salary_depends_on_activity() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(CoreFellowship::get_salary(9, &100), 0);
+		assert_ok!(CoreFellowship::import(Origin::signed(100)));
+		assert_eq!(CoreFellowship::get_salary(9, &100), 90);
+		assert_ok!(CoreFellowship::set_active(Origin::signed(100), false));
+		assert_eq!(CoreFellowship::get_salary(9, &100), 9);
+		// rank zero earns nothing.
+		assert_ok!(CoreFellowship::induct(Origin::signed(1), 10));
+		assert_eq!(CoreFellowship::get_salary(0, &10), 0);
+	});
+}
+
+#[test]
+fn inactive_members_are_demoted() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoreFellowship::induct(Origin::signed(1), 10));
+		assert_ok!(CoreFellowship::set_active(Origin::signed(10), false));
+		run_to(2);
+		assert_noop!(CoreFellowship::promote(Origin::signed(100), 10, 1), Error::<Test>::Inactive);
+		assert_ok!(CoreFellowship::set_active(Origin::signed(10), true));
+		assert_ok!(CoreFellowship::promote(Origin::signed(100), 10, 1));
+
+		// an inactive member can not postpone their demotion.
+		assert_ok!(CoreFellowship::set_active(Origin::signed(10), false));
+		run_to(3);
+		assert_noop!(CoreFellowship::approve(Origin::signed(100), 10, 1), Error::<Test>::Inactive);
+		run_to(4);
+		assert_ok!(CoreFellowship::bump(Origin::signed(2), 10));
+		assert_eq!(rank_of(10), Some(0));
+		assert!(!Member::<Test>::get(10).unwrap().is_active);
+	});
+}
@@ -23,7 +23,7 @@ use frame_benchmarking::{benchmarks, account, whitelist_account, impl_benchmark_
 use frame_support::{
 	assert_noop, assert_ok, IterableStorageMap,
 	traits::{Currency, Get, EnsureOrigin, OnInitialize, UnfilteredDispatchable,
-        schedule::DispatchTime},
+        schedule::DispatchTime, StorePreimage},
 };
 use frame_system::{RawOrigin, Pallet as System, self};
 use sp_runtime::traits::{Bounded, One};
@@ -70,13 +70,15 @@ fn add_referendum<T: Config>(n: u32) -> Result<ReferendumIndex, &'static str> {
 		0u32.into(),
 	);
 	let referendum_index: ReferendumIndex = ReferendumCount::get() - 1;
+	let call = Call::enact_proposal(proposal_hash, referendum_index).into();
+	let call = <() as StorePreimage>::bound::<T::Proposal>(call)?;
 	T::Scheduler::schedule_named(
 		(DEMOCRACY_ID, referendum_index).encode(),
 		DispatchTime::At(1u32.into()),
 		None,
 		63,
 		system::RawOrigin::Root.into(),
-		Call::enact_proposal(proposal_hash, referendum_index).into(),
+		call,
 	).map_err(|_| "failed to schedule named")?;
 	Ok(referendum_index)
 }
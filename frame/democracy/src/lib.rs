@@ -163,7 +163,8 @@ use frame_support::{
 	weights::{Weight, DispatchClass, Pays},
 	traits::{
 		Currency, ReservableCurrency, LockableCurrency, WithdrawReasons, LockIdentifier, Get,
-		OnUnbalanced, BalanceStatus, schedule::{Named as ScheduleNamed, DispatchTime}, EnsureOrigin
+		OnUnbalanced, BalanceStatus, schedule::{Named as ScheduleNamed, DispatchTime}, EnsureOrigin,
+		StorePreimage,
 	},
 	dispatch::DispatchResultWithPostInfo,
 };
@@ -287,7 +288,11 @@ pub trait Config: frame_system::Config + Sized {
 	type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
 
 	/// The Scheduler.
-	type Scheduler: ScheduleNamed<Self::BlockNumber, Self::Proposal, Self::PalletsOrigin>;
+	type Scheduler: ScheduleNamed<
+		Self::BlockNumber,
+		frame_support::traits::Bounded<Self::Proposal>,
+		Self::PalletsOrigin,
+	>;
 
 	/// Overarching type of all pallets origins.
 	type PalletsOrigin: From<system::RawOrigin<Self::AccountId>>;
@@ -1577,14 +1582,20 @@ impl<T: Config> Module<T> {
 					ref mut a => *a = Some(PreimageStatus::Missing(when)),
 				});
 
-				if T::Scheduler::schedule_named(
-					(DEMOCRACY_ID, index).encode(),
-					DispatchTime::At(when),
-					None,
-					63,
-					system::RawOrigin::Root.into(),
-					Call::enact_proposal(status.proposal_hash, index).into(),
-				).is_err() {
+				// The enactment call only refers to the proposal by its hash, so it is small
+				// enough to be held inline without storing a preimage.
+				let call = Call::enact_proposal(status.proposal_hash, index).into();
+				let scheduled = <() as StorePreimage>::bound::<T::Proposal>(call)
+					.map_err(|_| ())
+					.and_then(|call| T::Scheduler::schedule_named(
+						(DEMOCRACY_ID, index).encode(),
+						DispatchTime::At(when),
+						None,
+						63,
+						system::RawOrigin::Root.into(),
+						call,
+					));
+				if scheduled.is_err() {
 					frame_support::print("LOGIC ERROR: bake_referendum/schedule_named failed");
 				}
 			}
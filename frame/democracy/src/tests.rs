@@ -22,7 +22,7 @@ use super::*;
 use codec::Encode;
 use frame_support::{
 	assert_noop, assert_ok, parameter_types, ord_parameter_types,
-	traits::{SortedMembers, OnInitialize, Filter},
+	traits::{SortedMembers, OnInitialize, Filter, EqualPrivilegeOnly},
 	weights::Weight,
 };
 use sp_core::H256;
@@ -115,8 +115,11 @@ impl pallet_scheduler::Config for Test {
 	type Call = Call;
 	type MaximumWeight = MaximumSchedulerWeight;
 	type ScheduleOrigin = EnsureRoot<u64>;
+	type OriginPrivilegeCmp = EqualPrivilegeOnly;
 	type MaxScheduledPerBlock = ();
 	type WeightInfo = ();
+	type Preimages = ();
+	type NoPreimagePostponement = ();
 }
 parameter_types! {
 	pub const ExistentialDeposit: u64 = 1;
@@ -124,6 +127,10 @@ parameter_types! {
 }
 impl pallet_balances::Config for Test {
 	type MaxLocks = MaxLocks;
+	type HoldIdentifier = ();
+	type FreezeIdentifier = ();
+	type MaxHolds = frame_support::traits::ConstU32<1>;
+	type MaxFreezes = frame_support::traits::ConstU32<1>;
 	type Balance = u64;
 	type Event = Event;
 	type DustRemoval = ();
@@ -188,6 +188,36 @@ frame_benchmarking::benchmarks! {
 		assert!(<MultiPhase<T>>::current_phase().is_unsigned());
 	}
 
+	finalize_signed_phase_accept_solution {
+		let receiver = frame_benchmarking::account("receiver", 0, SEED);
+		let deposit: BalanceOf<T> = T::Currency::minimum_balance().max(One::one());
+		let reward: BalanceOf<T> = deposit;
+		let initial_balance = deposit * 10u32.into();
+		T::Currency::make_free_balance_be(&receiver, initial_balance);
+		assert_ok!(T::Currency::reserve(&receiver, deposit));
+		assert_eq!(T::Currency::free_balance(&receiver), initial_balance - deposit);
+		let ready: ReadySolution<T::AccountId> = Default::default();
+	}: {
+		<MultiPhase<T>>::finalize_signed_phase_accept_solution(ready, &receiver, deposit, reward)
+	} verify {
+		assert_eq!(T::Currency::free_balance(&receiver), initial_balance + reward);
+		assert_eq!(T::Currency::reserved_balance(&receiver), Zero::zero());
+	}
+
+	finalize_signed_phase_reject_solution {
+		let receiver = frame_benchmarking::account("receiver", 0, SEED);
+		let deposit: BalanceOf<T> = T::Currency::minimum_balance().max(One::one());
+		let initial_balance = deposit * 10u32.into();
+		T::Currency::make_free_balance_be(&receiver, initial_balance);
+		assert_ok!(T::Currency::reserve(&receiver, deposit));
+		assert_eq!(T::Currency::free_balance(&receiver), initial_balance - deposit);
+	}: {
+		<MultiPhase<T>>::finalize_signed_phase_reject_solution(&receiver, deposit)
+	} verify {
+		assert_eq!(T::Currency::free_balance(&receiver), initial_balance - deposit);
+		assert_eq!(T::Currency::reserved_balance(&receiver), Zero::zero());
+	}
+
 	// a call to `<Pallet as ElectionProvider>::elect` where we only return the queued solution.
 	elect_queued {
 		// assume largest values for the election status. These will merely affect the decoding.
@@ -218,6 +248,42 @@ frame_benchmarking::benchmarks! {
 		assert_eq!(<CurrentPhase<T>>::get(), <Phase<T::BlockNumber>>::Off);
 	}
 
+	submit {
+		let c in 1 .. (T::MaxSignedSubmissions::get() - 1);
+
+		// the solution will be worse than all of them, meaning that the score needs to be checked
+		// against all of them.
+		let solution = RawSolution {
+			compact: Default::default(),
+			score: [(10_000_000u128 - 1).into(), 0, 0],
+			round: 1,
+		};
+
+		<MultiPhase<T>>::on_initialize_open_signed().unwrap();
+		assert!(<MultiPhase<T>>::current_phase().is_signed());
+		<Round<T>>::put(1);
+
+		let signed_submissions = (0..c)
+			.map(|i| SignedSubmission {
+				who: frame_benchmarking::account("submitter", i, SEED),
+				deposit: Zero::zero(),
+				reward: Zero::zero(),
+				solution: RawSolution {
+					compact: Default::default(),
+					score: [(10_000_000 + i).into(), 0, 0],
+					round: 1,
+				},
+			})
+			.collect::<Vec<_>>();
+		<SignedSubmissions<T>>::put(signed_submissions);
+
+		let caller = frame_benchmarking::whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 4u32.into());
+	}: _(RawOrigin::Signed(caller), solution, c)
+	verify {
+		assert_eq!(<MultiPhase<T>>::signed_submissions().len() as u32, c + 1);
+	}
+
 	#[extra]
 	create_snapshot {
 		assert!(<MultiPhase<T>>::snapshot().is_none());
@@ -104,9 +104,10 @@
 //! than the best queued one (see [`pallet::Config::SolutionImprovementThreshold`]) and will limit
 //! the weigh of the solution to [`pallet::Config::MinerMaxWeight`].
 //!
-//! The unsigned phase can be made passive depending on how the previous signed phase went, by
-//! setting the first inner value of [`Phase`] to `false`. For now, the signed phase is always
-//! active.
+//! The unsigned phase is made passive, by setting the first inner value of [`Phase`] to `false`,
+//! if a feasible signed solution has already been queued at the end of the signed phase. In that
+//! case, no unsigned solutions are accepted. Otherwise, the unsigned phase acts as a fallback for
+//! the signed one.
 //!
 //! ### Fallback
 //!
@@ -173,13 +174,6 @@
 //! afterwards, and remove their solution (for a small cost of probably just transaction fees, or a
 //! portion of the bond).
 //!
-//! **Conditionally open unsigned phase**: Currently, the unsigned phase is always opened. This is
-//! useful because an honest validation will run our OCW code, which should be good enough to trump
-//! a mediocre or malicious signed submission (assuming in the absence of honest signed bots). If an
-//! when the signed submissions are checked against an absolute measure (e.g. PJR), then we can only
-//! open the unsigned phase in extreme conditions (i.e. "not good signed solution received") to
-//! spare some work in the validators
-//!
 //! **Allow smaller solutions and build up**: For now we only allow solutions that are exactly
 //! [`DesiredTargets`], no more, no less. Over time, we can change this to a [min, max] where any
 //! solution within this range is acceptable, where bigger solutions are prioritized.
@@ -214,10 +208,10 @@ use codec::{Decode, Encode};
 use frame_support::{
 	dispatch::DispatchResultWithPostInfo,
 	ensure,
-	traits::{Currency, Get, ReservableCurrency},
+	traits::{Currency, Get, OnUnbalanced, ReservableCurrency},
 	weights::Weight,
 };
-use frame_system::{ensure_none, offchain::SendTransactionTypes};
+use frame_system::{ensure_none, ensure_signed, offchain::SendTransactionTypes};
 use frame_election_provider_support::{ElectionDataProvider, ElectionProvider, onchain};
 use sp_npos_elections::{
 	assignment_ratio_to_staked_normalized, CompactSolution, ElectionScore,
@@ -249,9 +243,14 @@ pub mod helpers;
 
 const LOG_TARGET: &'static str = "runtime::election-provider";
 
+pub mod signed;
 pub mod unsigned;
 pub mod weights;
 
+pub use signed::{
+	BalanceOf, NegativeImbalanceOf, PositiveImbalanceOf, SignedSubmission, SignedSubmissionOf,
+};
+
 /// The weight declaration of the pallet.
 pub use weights::WeightInfo;
 
@@ -533,6 +532,32 @@ pub mod pallet {
 		#[pallet::constant]
 		type SignedPhase: Get<Self::BlockNumber>;
 
+		/// Maximum number of signed submissions that can be queued.
+		#[pallet::constant]
+		type MaxSignedSubmissions: Get<u32>;
+		/// Maximum weight of a signed solution.
+		///
+		/// This should probably be similar to [`Config::MinerMaxWeight`].
+		#[pallet::constant]
+		type SignedMaxWeight: Get<Weight>;
+		/// Base reward for a signed solution.
+		#[pallet::constant]
+		type SignedRewardBase: Get<BalanceOf<Self>>;
+		/// Base deposit for a signed solution.
+		#[pallet::constant]
+		type SignedDepositBase: Get<BalanceOf<Self>>;
+		/// Per-byte deposit for a signed solution.
+		#[pallet::constant]
+		type SignedDepositByte: Get<BalanceOf<Self>>;
+		/// Per-weight deposit for a signed solution.
+		#[pallet::constant]
+		type SignedDepositWeight: Get<BalanceOf<Self>>;
+
+		/// Handler for the slashed deposits.
+		type SlashHandler: OnUnbalanced<NegativeImbalanceOf<Self>>;
+		/// Handler for the rewards.
+		type RewardHandler: OnUnbalanced<PositiveImbalanceOf<Self>>;
+
 		/// The minimum amount of improvement to the solution score that defines a solution as
 		/// "better" (in any phase).
 		#[pallet::constant]
@@ -625,8 +650,9 @@ pub mod pallet {
 					// determine if followed by signed or not.
 					let (need_snapshot, enabled, signed_weight) = if current_phase == Phase::Signed {
 						// followed by a signed phase: close the signed phase, no need for snapshot.
-						// TODO: proper weight https://github.com/paritytech/substrate/pull/7910.
-						(false, true, Weight::zero())
+						// the unsigned phase is only enabled if no signed solution was accepted.
+						let (success, signed_weight) = Self::finalize_signed_phase();
+						(false, !success, signed_weight)
 					} else {
 						// no signed phase: create a new snapshot, definitely `enable` the unsigned
 						// phase.
@@ -779,6 +805,71 @@ pub mod pallet {
 			Ok(None.into())
 		}
 
+		/// Submit a solution for the signed phase.
+		///
+		/// The dispatch origin of this call must be __signed__.
+		///
+		/// The solution is potentially queued, based on the claimed score and processed at the end
+		/// of the signed phase.
+		///
+		/// A deposit is reserved and recorded for the solution. Based on the outcome, the solution
+		/// might be rewarded, slashed, or get all or a part of the deposit back.
+		///
+		/// # <weight>
+		/// Queue size must be provided as witness data.
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::submit(*num_signed_submissions))]
+		pub fn submit(
+			origin: OriginFor<T>,
+			solution: RawSolution<CompactOf<T>>,
+			num_signed_submissions: u32,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			// ensure witness data is correct.
+			ensure!(
+				num_signed_submissions >=
+					<SignedSubmissions<T>>::decode_len().unwrap_or_default() as u32,
+				Error::<T>::SignedInvalidWitness,
+			);
+
+			// ensure solution is timely.
+			ensure!(Self::current_phase().is_signed(), Error::<T>::PreDispatchEarlySubmission);
+
+			// defensive-only: if phase is signed, snapshot will exist.
+			let size = Self::snapshot_metadata().ok_or(Error::<T>::MissingSnapshotMetadata)?;
+
+			ensure!(
+				Self::feasibility_weight_of(&solution, size) <= T::SignedMaxWeight::get(),
+				Error::<T>::SignedTooMuchWeight,
+			);
+
+			// queue the solution, if its claimed score is good enough.
+			let mut signed_submissions = Self::signed_submissions();
+			let (index, maybe_ejected) =
+				Self::insert_submission(&who, &mut signed_submissions, solution, size)
+					.ok_or(Error::<T>::SignedQueueFull)?;
+
+			// collect deposit. Thereafter, the function cannot fail.
+			let deposit = signed_submissions[index].deposit;
+			T::Currency::reserve(&who, deposit).map_err(|_| Error::<T>::SignedCannotPayDeposit)?;
+
+			// the ejected submission, if any, gets its deposit back.
+			if let Some(SignedSubmission { who: ejected, deposit, .. }) = maybe_ejected {
+				let _remaining = T::Currency::unreserve(&ejected, deposit);
+				debug_assert!(_remaining.is_zero());
+			}
+
+			log!(
+				info,
+				"queued signed solution with score {:?}",
+				signed_submissions[index].solution.score,
+			);
+			<SignedSubmissions<T>>::put(signed_submissions);
+			Self::deposit_event(Event::SolutionStored(ElectionCompute::Signed));
+			Ok(())
+		}
+
 		/// Set a new value for `MinimumUntrustedScore`.
 		///
 		/// Dispatch origin must be aligned with `T::ForceOrigin`.
@@ -828,6 +919,16 @@ pub mod pallet {
 		PreDispatchWeakSubmission,
 		/// OCW submitted solution for wrong round
 		OcwCallWrongEra,
+		/// The queue was full, and the solution was not better than any of the existing ones.
+		SignedQueueFull,
+		/// The origin failed to pay the deposit.
+		SignedCannotPayDeposit,
+		/// Witness data to dispatchable is invalid.
+		SignedInvalidWitness,
+		/// The signed submission consumes too much weight.
+		SignedTooMuchWeight,
+		/// Snapshot metadata should exist but didn't.
+		MissingSnapshotMetadata,
 	}
 
 	#[pallet::origin]
@@ -937,6 +1038,15 @@ pub mod pallet {
 	#[pallet::getter(fn minimum_untrusted_score)]
 	pub type MinimumUntrustedScore<T: Config> = StorageValue<_, ElectionScore>;
 
+	/// Sorted (worse -> best) list of unchecked, signed solutions.
+	///
+	/// Always bounded by [`Config::MaxSignedSubmissions`], and drained at the end of the signed
+	/// phase.
+	#[pallet::storage]
+	#[pallet::getter(fn signed_submissions)]
+	pub type SignedSubmissions<T: Config> =
+		StorageValue<_, Vec<SignedSubmissionOf<T>>, ValueQuery>;
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
 	pub struct Pallet<T>(PhantomData<T>);
@@ -1183,6 +1293,11 @@ impl<T: Config> Pallet<T> {
 	}
 
 	fn do_elect() -> Result<(Supports<T::AccountId>, Weight), ElectionError> {
+		// if the election is requested while the signed phase is still open, its queue has not
+		// been processed yet. If the signed phase is already over, this is merely one read of an
+		// empty queue.
+		let (_, signed_weight) = Self::finalize_signed_phase();
+
 		<QueuedSolution<T>>::take()
 			.map_or_else(
 				|| match T::Fallback::get() {
@@ -1198,6 +1313,7 @@ impl<T: Config> Pallet<T> {
 				)),
 			)
 			.map(|(supports, weight, compute)| {
+				let weight = weight.saturating_add(signed_weight);
 				Self::deposit_event(Event::ElectionFinalized(Some(compute)));
 				if Self::round() != 1 {
 					log!(info, "Finalized election round with compute {:?}.", compute);
@@ -0,0 +1,647 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The signed phase implementation.
+
+use crate::{
+	CompactOf, Config, ElectionCompute, Event, Pallet, QueuedSolution, RawSolution, ReadySolution,
+	SignedSubmissions, SolutionOrSnapshotSize, Weight, WeightInfo,
+};
+use codec::{Decode, Encode};
+use frame_support::traits::{Currency, Get, OnUnbalanced, ReservableCurrency};
+use sp_arithmetic::Perbill;
+use sp_npos_elections::{is_score_better, CompactSolution};
+use sp_runtime::{
+	traits::{Saturating, Zero},
+	RuntimeDebug, SaturatedConversion,
+};
+use sp_std::vec::Vec;
+
+/// A raw, unchecked signed submission.
+///
+/// This is just a wrapper around [`RawSolution`] and some additional info.
+#[derive(PartialEq, Eq, Encode, Decode, Clone, RuntimeDebug)]
+pub struct SignedSubmission<AccountId, Balance, CompactSolution> {
+	/// Who submitted this solution.
+	pub who: AccountId,
+	/// The deposit reserved for storing this solution.
+	pub deposit: Balance,
+	/// The reward that should be given to this solution, if chosen as the final one.
+	pub reward: Balance,
+	/// The raw solution itself.
+	pub solution: RawSolution<CompactSolution>,
+}
+
+/// The balance type of the pallet's currency.
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+/// The positive imbalance type of the pallet's currency.
+pub type PositiveImbalanceOf<T> = <<T as Config>::Currency as Currency<
+	<T as frame_system::Config>::AccountId,
+>>::PositiveImbalance;
+/// The negative imbalance type of the pallet's currency.
+pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+	<T as frame_system::Config>::AccountId,
+>>::NegativeImbalance;
+/// A [`SignedSubmission`] specialized for a particular runtime `T`.
+pub type SignedSubmissionOf<T> =
+	SignedSubmission<<T as frame_system::Config>::AccountId, BalanceOf<T>, CompactOf<T>>;
+
+impl<T: Config> Pallet<T> {
+	/// Finish the signed phase. Process the signed submissions from best to worse until a valid
+	/// one is found, rewarding the best one and slashing the invalid ones along the way.
+	///
+	/// Returns true if we have a good solution in the signed phase.
+	///
+	/// This drains the [`SignedSubmissions`], potentially storing the best valid one in
+	/// [`QueuedSolution`].
+	pub fn finalize_signed_phase() -> (bool, Weight) {
+		let mut all_submissions: Vec<SignedSubmissionOf<T>> = <SignedSubmissions<T>>::take();
+		let mut found_solution = false;
+		let mut weight = T::DbWeight::get().reads_writes(1, 1);
+
+		while let Some(best) = all_submissions.pop() {
+			let SignedSubmission { solution, who, deposit, reward } = best;
+			let active_voters = solution.compact.voter_count() as u32;
+			let feasibility_weight = {
+				// defensive only: at the end of signed phase, snapshot will exist.
+				let SolutionOrSnapshotSize { voters, targets } =
+					Self::snapshot_metadata().unwrap_or_default();
+				let desired_targets = Self::desired_targets().unwrap_or_default();
+				T::WeightInfo::feasibility_check(voters, targets, active_voters, desired_targets)
+			};
+
+			match Self::feasibility_check(solution, ElectionCompute::Signed) {
+				Ok(ready_solution) => {
+					Self::finalize_signed_phase_accept_solution(
+						ready_solution,
+						&who,
+						deposit,
+						reward,
+					);
+					found_solution = true;
+
+					weight = weight
+						.saturating_add(feasibility_weight)
+						.saturating_add(T::WeightInfo::finalize_signed_phase_accept_solution());
+					break;
+				}
+				Err(_) => {
+					Self::finalize_signed_phase_reject_solution(&who, deposit);
+					weight = weight
+						.saturating_add(feasibility_weight)
+						.saturating_add(T::WeightInfo::finalize_signed_phase_reject_solution());
+				}
+			}
+		}
+
+		// Any unprocessed solution is pointless to even consider. Feasible or malicious,
+		// they didn't end up being used. Unreserve the bonds.
+		for SignedSubmission { who, deposit, .. } in all_submissions.drain(..) {
+			let _remaining = T::Currency::unreserve(&who, deposit);
+			weight = weight.saturating_add(T::DbWeight::get().writes(1));
+		}
+
+		log!(debug, "closed signed phase, found solution? {}", found_solution);
+		(found_solution, weight)
+	}
+
+	/// Helper function for the case where a solution is accepted in the signed phase.
+	///
+	/// Extracted to facilitate with weight calculation.
+	///
+	/// Infallible
+	pub fn finalize_signed_phase_accept_solution(
+		ready_solution: ReadySolution<T::AccountId>,
+		who: &T::AccountId,
+		deposit: BalanceOf<T>,
+		reward: BalanceOf<T>,
+	) {
+		// write this ready solution.
+		<QueuedSolution<T>>::put(ready_solution);
+
+		// emit reward event
+		Self::deposit_event(Event::Rewarded(who.clone()));
+
+		// unreserve deposit.
+		let _remaining = T::Currency::unreserve(who, deposit);
+		debug_assert!(_remaining.is_zero());
+
+		// Reward.
+		let positive_imbalance = T::Currency::deposit_creating(who, reward);
+		T::RewardHandler::on_unbalanced(positive_imbalance);
+	}
+
+	/// Helper function for the case where a solution is rejected in the signed phase.
+	///
+	/// Extracted to facilitate with weight calculation.
+	///
+	/// Infallible
+	pub fn finalize_signed_phase_reject_solution(who: &T::AccountId, deposit: BalanceOf<T>) {
+		Self::deposit_event(Event::Slashed(who.clone()));
+		let (negative_imbalance, _remaining) = T::Currency::slash_reserved(who, deposit);
+		debug_assert!(_remaining.is_zero());
+		T::SlashHandler::on_unbalanced(negative_imbalance);
+	}
+
+	/// Insert a solution into the queue, keeping it sorted from the worst to the best score.
+	///
+	/// Returns `None` if the queue is full and the solution is not an improvement over any of the
+	/// queued ones. Otherwise, returns the index at which the solution now lives, along with the
+	/// weakest submission if it had to be evicted to make room.
+	///
+	/// No deposit is reserved or unreserved here, that is left to the caller.
+	pub fn insert_submission(
+		who: &T::AccountId,
+		queue: &mut Vec<SignedSubmissionOf<T>>,
+		solution: RawSolution<CompactOf<T>>,
+		size: SolutionOrSnapshotSize,
+	) -> Option<(usize, Option<SignedSubmissionOf<T>>)> {
+		let max_submissions = T::MaxSignedSubmissions::get() as usize;
+
+		// from the last (best) score, find the first one that the new solution is better than;
+		// the new solution goes right above it. If none, it goes at the bottom.
+		let threshold = T::SolutionImprovementThreshold::get();
+		let at = queue
+			.iter()
+			.rposition(|s| is_score_better::<Perbill>(solution.score, s.solution.score, threshold))
+			.map_or(0, |i| i + 1);
+
+		if at == 0 && queue.len() >= max_submissions {
+			// worse than all of them, and the queue is full. Don't bother.
+			return None;
+		}
+
+		let deposit = Self::deposit_for(&solution, size);
+		let reward = T::SignedRewardBase::get();
+		queue.insert(at, SignedSubmission { who: who.clone(), deposit, reward, solution });
+
+		if queue.len() > max_submissions {
+			// the weakest one is evicted, which shifts the new one down by one.
+			let weakest = queue.remove(0);
+			Some((at - 1, Some(weakest)))
+		} else {
+			Some((at, None))
+		}
+	}
+
+	/// The feasibility weight of the given raw solution.
+	pub fn feasibility_weight_of(
+		solution: &RawSolution<CompactOf<T>>,
+		size: SolutionOrSnapshotSize,
+	) -> Weight {
+		T::WeightInfo::feasibility_check(
+			size.voters,
+			size.targets,
+			solution.compact.voter_count() as u32,
+			solution.compact.unique_targets().len() as u32,
+		)
+	}
+
+	/// Collect sufficient deposit to store this solution on chain.
+	///
+	/// The deposit is composed of 3 main elements:
+	///
+	/// 1. base deposit, fixed for all submissions.
+	/// 2. a per-byte deposit, for renting the state usage.
+	/// 3. a per-weight deposit, for the potential weight usage in an upcoming on_initialize
+	pub fn deposit_for(
+		solution: &RawSolution<CompactOf<T>>,
+		size: SolutionOrSnapshotSize,
+	) -> BalanceOf<T> {
+		let encoded_len: u32 = solution.encoded_size().saturated_into();
+		let encoded_len: BalanceOf<T> = encoded_len.into();
+		let feasibility_weight = Self::feasibility_weight_of(solution, size);
+
+		let len_deposit = T::SignedDepositByte::get().saturating_mul(encoded_len);
+		let weight_deposit =
+			T::SignedDepositWeight::get().saturating_mul(feasibility_weight.saturated_into());
+
+		T::SignedDepositBase::get().saturating_add(len_deposit).saturating_add(weight_deposit)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		Error, Phase,
+		mock::{
+			balances, ExtBuilder, MultiPhase, Origin, raw_solution, roll_to, Runtime,
+			SignedMaxWeight, MaxSignedSubmissions, multi_phase_events,
+		},
+	};
+	use frame_support::{assert_noop, assert_ok, dispatch::DispatchResult};
+
+	fn submit_with_witness(
+		origin: Origin,
+		solution: RawSolution<CompactOf<Runtime>>,
+	) -> DispatchResult {
+		MultiPhase::submit(
+			origin,
+			solution,
+			MultiPhase::signed_submissions().len() as u32,
+		)
+	}
+
+	#[test]
+	fn cannot_submit_too_early() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to(2);
+			assert_eq!(MultiPhase::current_phase(), Phase::Off);
+
+			// create a temp snapshot only for this test.
+			MultiPhase::create_snapshot().unwrap();
+			let solution = raw_solution();
+
+			assert_noop!(
+				submit_with_witness(Origin::signed(10), solution),
+				Error::<Runtime>::PreDispatchEarlySubmission,
+			);
+		})
+	}
+
+	#[test]
+	fn wrong_witness_fails() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to(15);
+			assert!(MultiPhase::current_phase().is_signed());
+
+			let solution = raw_solution();
+			// submit this once correctly
+			assert_ok!(submit_with_witness(Origin::signed(99), solution.clone()));
+			assert_eq!(MultiPhase::signed_submissions().len(), 1);
+
+			// now try and cheat by passing a lower queue length
+			assert_noop!(
+				MultiPhase::submit(Origin::signed(99), solution, 0),
+				Error::<Runtime>::SignedInvalidWitness,
+			);
+		})
+	}
+
+	#[test]
+	fn should_pay_deposit() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to(15);
+			assert!(MultiPhase::current_phase().is_signed());
+
+			let solution = raw_solution();
+			assert_eq!(balances(&99), (100, 0));
+
+			assert_ok!(submit_with_witness(Origin::signed(99), solution));
+
+			assert_eq!(balances(&99), (95, 5));
+			assert_eq!(MultiPhase::signed_submissions().first().unwrap().deposit, 5);
+			assert_eq!(
+				multi_phase_events(),
+				vec![
+					Event::SignedPhaseStarted(1),
+					Event::SolutionStored(ElectionCompute::Signed),
+				],
+			);
+		})
+	}
+
+	#[test]
+	fn deposit_depends_on_the_solution_size() {
+		ExtBuilder::default().signed_deposit(5, 1, 0).build_and_execute(|| {
+			roll_to(15);
+			assert!(MultiPhase::current_phase().is_signed());
+
+			let solution = raw_solution();
+			let deposit = 5 + solution.encoded_size() as u64;
+
+			assert_ok!(submit_with_witness(Origin::signed(99), solution));
+			assert_eq!(balances(&99), (100 - deposit, deposit));
+		})
+	}
+
+	#[test]
+	fn cannot_submit_without_paying_deposit() {
+		ExtBuilder::default().signed_deposit(101, 0, 0).build_and_execute(|| {
+			roll_to(15);
+			assert!(MultiPhase::current_phase().is_signed());
+
+			assert_noop!(
+				submit_with_witness(Origin::signed(99), raw_solution()),
+				Error::<Runtime>::SignedCannotPayDeposit,
+			);
+		})
+	}
+
+	#[test]
+	fn good_solution_is_rewarded() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to(15);
+			assert!(MultiPhase::current_phase().is_signed());
+
+			let solution = raw_solution();
+			assert_eq!(balances(&99), (100, 0));
+
+			assert_ok!(submit_with_witness(Origin::signed(99), solution));
+			assert_eq!(balances(&99), (95, 5));
+
+			assert!(MultiPhase::finalize_signed_phase().0);
+			assert_eq!(balances(&99), (100 + 7, 0));
+			assert!(MultiPhase::queued_solution().is_some());
+			assert!(MultiPhase::signed_submissions().is_empty());
+		})
+	}
+
+	#[test]
+	fn bad_solution_is_slashed() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to(15);
+			assert!(MultiPhase::current_phase().is_signed());
+
+			let mut solution = raw_solution();
+			assert_eq!(balances(&99), (100, 0));
+
+			// make the solution invalid.
+			solution.score[0] += 1;
+
+			assert_ok!(submit_with_witness(Origin::signed(99), solution));
+			assert_eq!(balances(&99), (95, 5));
+
+			// no good solution was stored.
+			assert!(!MultiPhase::finalize_signed_phase().0);
+			// and the bond is gone.
+			assert_eq!(balances(&99), (95, 0));
+			assert!(MultiPhase::queued_solution().is_none());
+			assert!(multi_phase_events().contains(&Event::Slashed(99)));
+		})
+	}
+
+	#[test]
+	fn suppressed_solution_gets_bond_back() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to(15);
+			assert!(MultiPhase::current_phase().is_signed());
+
+			let mut solution = raw_solution();
+			assert_eq!(balances(&99), (100, 0));
+			assert_eq!(balances(&999), (100, 0));
+
+			// submit as correct.
+			assert_ok!(submit_with_witness(Origin::signed(99), solution.clone()));
+
+			// make the solution invalid and weaker.
+			solution.score[0] -= 1;
+			assert_ok!(submit_with_witness(Origin::signed(999), solution));
+			assert_eq!(balances(&99), (95, 5));
+			assert_eq!(balances(&999), (95, 5));
+
+			// _some_ good solution was stored.
+			assert!(MultiPhase::finalize_signed_phase().0);
+
+			// 99 is rewarded.
+			assert_eq!(balances(&99), (100 + 7, 0));
+			// 999 gets everything back.
+			assert_eq!(balances(&999), (100, 0));
+		})
+	}
+
+	#[test]
+	fn cannot_submit_worse_with_full_queue() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to(15);
+			assert!(MultiPhase::current_phase().is_signed());
+
+			for s in 0..MaxSignedSubmissions::get() {
+				// score is always getting better
+				let solution = RawSolution { score: [(5 + s).into(), 0, 0], ..raw_solution() };
+				assert_ok!(submit_with_witness(Origin::signed(99), solution));
+			}
+
+			// weaker.
+			let solution = RawSolution { score: [4, 0, 0], ..raw_solution() };
+
+			assert_noop!(
+				submit_with_witness(Origin::signed(99), solution),
+				Error::<Runtime>::SignedQueueFull,
+			);
+		})
+	}
+
+	#[test]
+	fn weakest_is_removed_if_better_provided() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to(15);
+			assert!(MultiPhase::current_phase().is_signed());
+
+			for s in 0..MaxSignedSubmissions::get() {
+				// score is always getting better
+				let solution = RawSolution { score: [(5 + s).into(), 0, 0], ..raw_solution() };
+				assert_ok!(submit_with_witness(Origin::signed(99), solution));
+			}
+
+			assert_eq!(
+				MultiPhase::signed_submissions()
+					.into_iter()
+					.map(|s| s.solution.score[0])
+					.collect::<Vec<_>>(),
+				vec![5, 6, 7, 8, 9]
+			);
+			assert_eq!(balances(&99), (75, 25));
+
+			// better.
+			let solution = RawSolution { score: [20, 0, 0], ..raw_solution() };
+			assert_ok!(submit_with_witness(Origin::signed(999), solution));
+
+			// the one with score 5 was rejected, the new one inserted.
+			assert_eq!(
+				MultiPhase::signed_submissions()
+					.into_iter()
+					.map(|s| s.solution.score[0])
+					.collect::<Vec<_>>(),
+				vec![6, 7, 8, 9, 20]
+			);
+			// and its deposit was returned.
+			assert_eq!(balances(&99), (80, 20));
+			assert_eq!(balances(&999), (95, 5));
+		})
+	}
+
+	#[test]
+	fn ejected_submission_is_refunded_only_if_new_deposit_is_paid() {
+		ExtBuilder::default().signed_max_submission(1).build_and_execute(|| {
+			roll_to(15);
+			assert!(MultiPhase::current_phase().is_signed());
+
+			let solution = RawSolution { score: [5, 0, 0], ..raw_solution() };
+			assert_ok!(submit_with_witness(Origin::signed(99), solution));
+			assert_eq!(balances(&99), (95, 5));
+
+			// 10 cannot pay for the deposit, so 99 keeps its place.
+			let solution = RawSolution { score: [6, 0, 0], ..raw_solution() };
+			assert_noop!(
+				submit_with_witness(Origin::signed(10), solution.clone()),
+				Error::<Runtime>::SignedCannotPayDeposit,
+			);
+
+			assert_ok!(submit_with_witness(Origin::signed(999), solution));
+			assert_eq!(
+				MultiPhase::signed_submissions().iter().map(|s| s.who).collect::<Vec<_>>(),
+				vec![999],
+			);
+			assert_eq!(balances(&99), (100, 0));
+			assert_eq!(balances(&999), (95, 5));
+		})
+	}
+
+	#[test]
+	fn equally_good_solution_is_not_accepted_with_full_queue() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to(15);
+			assert!(MultiPhase::current_phase().is_signed());
+
+			for s in 1..=MaxSignedSubmissions::get() {
+				let solution = RawSolution { score: [(5 * s).into(), 0, 0], ..raw_solution() };
+				assert_ok!(submit_with_witness(Origin::signed(99), solution));
+			}
+
+			// equal to the weakest one.
+			let solution = RawSolution { score: [5, 0, 0], ..raw_solution() };
+			assert_noop!(
+				submit_with_witness(Origin::signed(99), solution),
+				Error::<Runtime>::SignedQueueFull,
+			);
+
+			// slightly better than the weakest one.
+			let solution = RawSolution { score: [6, 0, 0], ..raw_solution() };
+			assert_ok!(submit_with_witness(Origin::signed(99), solution));
+
+			assert_eq!(
+				MultiPhase::signed_submissions()
+					.into_iter()
+					.map(|s| s.solution.score[0])
+					.collect::<Vec<_>>(),
+				vec![6, 10, 15, 20, 25]
+			);
+		})
+	}
+
+	#[test]
+	fn all_in_one_signed_submission_scenario() {
+		// a combination of:
+		// - good_solution_is_rewarded
+		// - bad_solution_is_slashed
+		// - suppressed_solution_gets_bond_back
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to(15);
+			assert!(MultiPhase::current_phase().is_signed());
+
+			assert_eq!(balances(&99), (100, 0));
+			assert_eq!(balances(&999), (100, 0));
+			assert_eq!(balances(&9999), (100, 0));
+			let solution = raw_solution();
+
+			// submit a correct one.
+			assert_ok!(submit_with_witness(Origin::signed(99), solution.clone()));
+
+			// make the solution invalidly better and submit. This ought to be slashed.
+			let mut solution_999 = solution.clone();
+			solution_999.score[0] += 1;
+			assert_ok!(submit_with_witness(Origin::signed(999), solution_999));
+
+			// make the solution invalidly worse and submit. This ought to be suppressed and
+			// returned.
+			let mut solution_9999 = solution.clone();
+			solution_9999.score[0] -= 1;
+			assert_ok!(submit_with_witness(Origin::signed(9999), solution_9999));
+
+			assert_eq!(
+				MultiPhase::signed_submissions().iter().map(|x| x.who).collect::<Vec<_>>(),
+				vec![9999, 99, 999]
+			);
+
+			// _some_ good solution was stored.
+			assert!(MultiPhase::finalize_signed_phase().0);
+
+			// 99 is rewarded.
+			assert_eq!(balances(&99), (100 + 7, 0));
+			// 999 is slashed.
+			assert_eq!(balances(&999), (95, 0));
+			// 9999 gets everything back.
+			assert_eq!(balances(&9999), (100, 0));
+		})
+	}
+
+	#[test]
+	fn cannot_consume_too_much_future_weight() {
+		ExtBuilder::default().signed_weight(40).mock_weight_info(true).build_and_execute(|| {
+			roll_to(15);
+			assert!(MultiPhase::current_phase().is_signed());
+
+			let (solution, witness) = MultiPhase::mine_solution(2).unwrap();
+			let solution_weight = <Runtime as Config>::WeightInfo::feasibility_check(
+				witness.voters,
+				witness.targets,
+				solution.compact.voter_count() as u32,
+				solution.compact.unique_targets().len() as u32,
+			);
+			// default solution will have 5 edges (5 * 5 + 10)
+			assert_eq!(solution_weight, 35);
+			assert_eq!(solution.compact.voter_count(), 5);
+			assert_eq!(<Runtime as Config>::SignedMaxWeight::get(), 40);
+
+			assert_ok!(submit_with_witness(Origin::signed(99), solution.clone()));
+
+			<SignedMaxWeight>::set(30);
+
+			// note: resubmitting the same solution is technically okay as long as the queue has
+			// space.
+			assert_noop!(
+				submit_with_witness(Origin::signed(99), solution),
+				Error::<Runtime>::SignedTooMuchWeight,
+			);
+		})
+	}
+
+	#[test]
+	fn unsigned_phase_is_disabled_if_signed_solution_is_accepted() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to(15);
+			assert!(MultiPhase::current_phase().is_signed());
+
+			assert_ok!(submit_with_witness(Origin::signed(99), raw_solution()));
+
+			roll_to(25);
+			assert_eq!(MultiPhase::current_phase(), Phase::Unsigned((false, 25)));
+			assert!(MultiPhase::queued_solution().is_some());
+			assert_eq!(balances(&99), (100 + 7, 0));
+		})
+	}
+
+	#[test]
+	fn unsigned_phase_is_enabled_if_no_signed_solution_is_accepted() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to(15);
+			assert!(MultiPhase::current_phase().is_signed());
+
+			let mut solution = raw_solution();
+			solution.score[0] += 1;
+			assert_ok!(submit_with_witness(Origin::signed(99), solution));
+
+			roll_to(25);
+			assert_eq!(MultiPhase::current_phase(), Phase::Unsigned((true, 25)));
+			assert!(MultiPhase::queued_solution().is_none());
+			assert_eq!(balances(&99), (95, 0));
+		})
+	}
+}
@@ -274,3 +274,58 @@ impl<AccountId, BlockNumber> ElectionProvider<AccountId, BlockNumber> for () {
 		Err("<() as ElectionProvider> cannot do anything.")
 	}
 }
+
+/// A list of voters, kept sorted by their vote weight, from which an [`ElectionDataProvider`]
+/// can take the best voters when it can't provide all of them.
+///
+/// The sorting may be approximate, e.g. it may only hold between groups of voters. The owner of
+/// the voters must report every change to the list through the `on_*` hooks.
+pub trait SortedListProvider<AccountId> {
+	/// The error type returned when a voter can't be inserted.
+	type Error: Debug;
+
+	/// An iterator over the voters of the list, from the highest vote weight to the lowest.
+	fn iter() -> Box<dyn Iterator<Item = AccountId>>;
+
+	/// The number of voters in the list.
+	fn count() -> u32;
+
+	/// Returns true if `id` is in the list.
+	fn contains(id: &AccountId) -> bool;
+
+	/// Hook for inserting a new voter with the given vote weight.
+	fn on_insert(id: AccountId, weight: VoteWeight) -> Result<(), Self::Error>;
+
+	/// Hook for updating the vote weight of a voter which is already in the list.
+	fn on_update(id: &AccountId, weight: VoteWeight);
+
+	/// Hook for removing a voter from the list.
+	fn on_remove(id: &AccountId);
+
+	/// Regenerate the list from scratch with the voters of `all`, weighted by `weight_of`.
+	///
+	/// This is meant to populate a new list from an existing set of voters, e.g. in a migration.
+	/// Returns the number of voters inserted.
+	fn regenerate(
+		all: impl IntoIterator<Item = AccountId>,
+		weight_of: Box<dyn Fn(&AccountId) -> VoteWeight>,
+	) -> u32;
+
+	/// Remove all voters from the list.
+	fn clear();
+
+	/// Check the internal consistency of the list.
+	fn sanity_check() -> Result<(), &'static str>;
+}
+
+/// Something that can provide the current vote weight of a voter.
+pub trait VoteWeightProvider<AccountId> {
+	/// The current vote weight of `who`.
+	fn vote_weight(who: &AccountId) -> VoteWeight;
+
+	/// Make `vote_weight` return `weight` for `who`, e.g. to set up a benchmark.
+	///
+	/// The state may be inconsistent afterwards, so this is for benchmarking only.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_vote_weight_of(_: &AccountId, _: VoteWeight) {}
+}
@@ -1159,6 +1159,10 @@ mod tests {
 		type ExistentialDeposit = ExistentialDeposit;
 		type AccountStore = frame_system::Pallet<Test>;
 		type MaxLocks = ();
+		type HoldIdentifier = ();
+		type FreezeIdentifier = ();
+		type MaxHolds = frame_support::traits::ConstU32<1>;
+		type MaxFreezes = frame_support::traits::ConstU32<1>;
 		type WeightInfo = ();
 	}
 
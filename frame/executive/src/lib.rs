@@ -118,13 +118,15 @@
 
 use sp_std::{prelude::*, marker::PhantomData};
 use frame_support::{
-	weights::{GetDispatchInfo, DispatchInfo, DispatchClass},
+	weights::{GetDispatchInfo, DispatchInfo, DispatchClass, WeightMeter},
 	traits::{
-		OnInitialize, OnIdle, OnFinalize, OnRuntimeUpgrade, OffchainWorker, ExecuteBlock,
+		OnInitialize, OnPoll, OnIdle, OnFinalize, OnRuntimeUpgrade, OffchainWorker, ExecuteBlock,
 		EnsureInherentsAreFirst,
 	},
 	dispatch::PostDispatchInfo,
 };
+#[cfg(feature = "try-runtime")]
+use frame_support::traits::{TryState, UpgradeCheckSelect};
 use sp_runtime::{
 	generic::Digest, ApplyExtrinsicResult,
 	traits::{
@@ -163,6 +165,7 @@ impl<
 	AllPallets:
 		OnRuntimeUpgrade +
 		OnInitialize<System::BlockNumber> +
+		OnPoll<System::BlockNumber> +
 		OnIdle<System::BlockNumber> +
 		OnFinalize<System::BlockNumber> +
 		OffchainWorker<System::BlockNumber>,
@@ -190,6 +193,7 @@ impl<
 		UnsignedValidator,
 		AllPallets: OnRuntimeUpgrade
 			+ OnInitialize<System::BlockNumber>
+			+ OnPoll<System::BlockNumber>
 			+ OnIdle<System::BlockNumber>
 			+ OnFinalize<System::BlockNumber>
 			+ OffchainWorker<System::BlockNumber>,
@@ -215,26 +219,175 @@ where
 		weight
 	}
 
-	/// Execute all `OnRuntimeUpgrade` of this runtime, including the pre and post migration checks.
+	/// Execute the `OnRuntimeUpgrade` of this runtime, along with the `checks` selected.
+	///
+	/// Only the migrations named in `pallets`, by the name of their pallet or their type name
+	/// otherwise, run along with their checks, or all of them if `pallets` is empty.
+	///
+	/// The state returned by each pre migration check is handed to its post migration check. The
+	/// [`TryState`] invariants of all pallets are checked after the migrations.
+	///
+	/// Returns the weight consumed by each migration, labelled with the name of its pallet, in the
+	/// same order as [`Self::execute_on_runtime_upgrade`] executes them.
 	///
 	/// This should only be used for testing.
 	#[cfg(feature = "try-runtime")]
-	pub fn try_runtime_upgrade() -> Result<frame_support::weights::Weight, &'static str> {
+	pub fn try_runtime_upgrade(
+		checks: UpgradeCheckSelect,
+		pallets: &[Vec<u8>],
+	) -> Result<Vec<(&'static str, frame_support::weights::Weight)>, &'static str>
+	where
+		AllPallets: TryState<System::BlockNumber>,
+	{
+		let filter = |name: &str| {
+			pallets.is_empty() || pallets.iter().any(|pallet| &pallet[..] == name.as_bytes())
+		};
+		let state = if checks.pre_and_post() {
+			Some(Self::try_pre_upgrade(&filter)?)
+		} else {
+			None
+		};
+
+		let mut weights = COnRuntimeUpgrade::on_runtime_upgrade_breakdown(&filter);
+		weights.extend(
+			<frame_system::Pallet<System> as OnRuntimeUpgrade>::on_runtime_upgrade_breakdown(
+				&filter,
+			),
+		);
+		weights.extend(<AllPallets as OnRuntimeUpgrade>::on_runtime_upgrade_breakdown(&filter));
+
+		if let Some(state) = state {
+			Self::try_post_upgrade(state, &filter)?;
+		}
+		if checks.try_state() {
+			Self::try_state()?;
+		}
+
+		Ok(weights)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn try_pre_upgrade(filter: &dyn Fn(&str) -> bool) -> Result<Vec<u8>, &'static str> {
 		<
 			(frame_system::Pallet::<System>, COnRuntimeUpgrade, AllPallets)
 			as
 			OnRuntimeUpgrade
-		>::pre_upgrade()?;
-
-		let weight = Self::execute_on_runtime_upgrade();
+		>::pre_upgrade_filtered(filter)
+	}
 
+	#[cfg(feature = "try-runtime")]
+	fn try_post_upgrade(
+		state: Vec<u8>,
+		filter: &dyn Fn(&str) -> bool,
+	) -> Result<(), &'static str> {
 		<
 			(frame_system::Pallet::<System>, COnRuntimeUpgrade, AllPallets)
 			as
 			OnRuntimeUpgrade
-		>::post_upgrade()?;
+		>::post_upgrade_filtered(state, filter)
+	}
 
-		Ok(weight)
+	/// Check the [`TryState`] invariants of all pallets of this runtime, at the current block.
+	///
+	/// This should only be used for testing.
+	#[cfg(feature = "try-runtime")]
+	pub fn try_state() -> Result<(), &'static str>
+	where
+		AllPallets: TryState<System::BlockNumber>,
+	{
+		<(frame_system::Pallet<System>, AllPallets) as TryState<System::BlockNumber>>::try_state(
+			<frame_system::Pallet<System>>::block_number(),
+		)
+	}
+
+	/// Execute `block` like [`Self::execute_block`], optionally without checking the state root
+	/// and the signatures of its extrinsics, and return the weight it consumed.
+	///
+	/// If the block upgrades the runtime, the pre and post migration checks run around the
+	/// initialization of the block if selected by `checks`. The [`TryState`] invariants of all
+	/// pallets are checked once all hooks have run, if selected as well. Panics if any check fails.
+	///
+	/// This should only be used for testing.
+	#[cfg(feature = "try-runtime")]
+	pub fn try_execute_block(
+		block: Block,
+		state_root_check: bool,
+		signature_check: bool,
+		checks: UpgradeCheckSelect,
+	) -> frame_support::weights::Weight
+	where
+		AllPallets: TryState<System::BlockNumber>,
+	{
+		let state = if checks.pre_and_post() && Self::runtime_upgrade_pending() {
+			match Self::try_pre_upgrade(&|_| true) {
+				Ok(state) => Some(state),
+				Err(e) => panic!("pre-upgrade checks failed: {}", e),
+			}
+		} else {
+			None
+		};
+		Self::initialize_block(block.header());
+		if let Some(Err(e)) = state.map(|state| Self::try_post_upgrade(state, &|_| true)) {
+			panic!("post-upgrade checks failed: {}", e)
+		}
+		Self::initial_checks(&block);
+
+		let (header, extrinsics) = block.deconstruct();
+		for uxt in extrinsics {
+			let encoded = uxt.encode();
+			let encoded_len = encoded.len();
+			let checked = if signature_check {
+				uxt.check(&Default::default())
+			} else {
+				uxt.unchecked_into_checked_i_know_what_i_am_doing(&Default::default())
+			};
+			let r = checked.and_then(|xt| {
+				<frame_system::Pallet<System>>::note_extrinsic(encoded);
+				let dispatch_info = xt.get_dispatch_info();
+				let r = Applyable::apply::<UnsignedValidator>(xt, &dispatch_info, encoded_len)?;
+				<frame_system::Pallet<System>>::note_applied_extrinsic(&r, dispatch_info);
+				Ok(())
+			});
+			if let Err(e) = r {
+				let err: &'static str = e.into();
+				panic!("{}", err)
+			}
+		}
+
+		<frame_system::Pallet<System>>::note_finished_extrinsics();
+		Self::idle_and_finalize_hook(*header.number());
+		let weight = <frame_system::Pallet<System>>::block_weight().total();
+
+		if checks.try_state() {
+			if let Err(e) = Self::try_state() {
+				panic!("try-state checks failed: {}", e)
+			}
+		}
+
+		// the checks of `final_checks`, with the state root check being optional.
+		Self::check_block_weight();
+		let new_header = <frame_system::Pallet<System>>::finalize();
+		assert_eq!(
+			header.digest().logs().len(),
+			new_header.digest().logs().len(),
+			"Number of digest items must match that calculated."
+		);
+		let items_zip = header.digest().logs().iter().zip(new_header.digest().logs().iter());
+		for (header_item, computed_item) in items_zip {
+			header_item.check_equal(&computed_item);
+			assert!(header_item == computed_item, "Digest item must match that calculated.");
+		}
+		if state_root_check {
+			let storage_root = new_header.state_root();
+			header.state_root().check_equal(&storage_root);
+			assert!(header.state_root() == storage_root, "Storage root must match that calculated.");
+		}
+		assert!(
+			header.extrinsics_root() == new_header.extrinsics_root(),
+			"Transaction trie root must be valid.",
+		);
+
+		weight
 	}
 
 	/// Start the execution of a particular block.
@@ -290,10 +443,8 @@ where
 
 	/// Returns if the runtime was upgraded since the last time this function was called.
 	fn runtime_upgraded() -> bool {
-		let last = frame_system::LastRuntimeUpgrade::<System>::get();
-		let current = <System::Version as frame_support::traits::Get<_>>::get();
-
-		if last.map(|v| v.was_upgraded(&current)).unwrap_or(true) {
+		if Self::runtime_upgrade_pending() {
+			let current = <System::Version as frame_support::traits::Get<_>>::get();
 			frame_system::LastRuntimeUpgrade::<System>::put(
 				frame_system::LastRuntimeUpgradeInfo::from(current),
 			);
@@ -303,6 +454,14 @@ where
 		}
 	}
 
+	/// Returns if the runtime was upgraded since the last call to [`Self::runtime_upgraded`],
+	/// without noting the upgrade.
+	fn runtime_upgrade_pending() -> bool {
+		let last = frame_system::LastRuntimeUpgrade::<System>::get();
+		let current = <System::Version as frame_support::traits::Get<_>>::get();
+		last.map(|v| v.was_upgraded(&current)).unwrap_or(true)
+	}
+
 	fn initial_checks(block: &Block) {
 		sp_tracing::enter_span!(sp_tracing::Level::TRACE, "initial_checks");
 		let header = block.header();
@@ -371,6 +530,8 @@ where
 		let block_number = <frame_system::Pallet<System>>::block_number();
 
 		Self::idle_and_finalize_hook(block_number);
+		// an authored block is checked like an imported one, rather than rejected by the peers.
+		Self::check_block_weight();
 
 		<frame_system::Pallet<System>>::finalize()
 	}
@@ -378,7 +539,18 @@ where
 	fn idle_and_finalize_hook(block_number: NumberFor<Block>) {
 		let weight =  <frame_system::Pallet<System>>::block_weight();
 		let max_weight =  <System::BlockWeights as frame_support::traits::Get<_>>::get().max_block;
-		let mut remaining_weight = max_weight.saturating_sub(weight.total());
+		let mut meter = WeightMeter::from_limit(max_weight.saturating_sub(weight.total()));
+		<frame_system::Pallet<System> as OnPoll<System::BlockNumber>>::on_poll(
+			block_number,
+			&mut meter,
+		);
+		<AllPallets as OnPoll<System::BlockNumber>>::on_poll(block_number, &mut meter);
+		<frame_system::Pallet::<System>>::register_extra_weight_unchecked(
+			meter.consumed(),
+			DispatchClass::Mandatory,
+		);
+
+		let mut remaining_weight = meter.remaining();
 
 		if remaining_weight > 0 {
 			let mut used_weight =
@@ -439,8 +611,22 @@ where
 		Ok(r.map(|_| ()).map_err(|e| e.error))
 	}
 
+	/// Check that the weight consumed by each dispatch class is within its limits.
+	///
+	/// `CheckWeight` enforces the same limits extrinsic by extrinsic, so this only fails for
+	/// blocks which were not authored by an honest node. It runs when authoring a block too.
+	fn check_block_weight() {
+		let consumed = <frame_system::Pallet<System>>::block_weight();
+		let limits = <System::BlockWeights as frame_support::traits::Get<_>>::get();
+		if let Err(class) = limits.check_consumed(&consumed) {
+			panic!("Weight of the {:?} dispatch class exceeds its limits.", class);
+		}
+	}
+
 	fn final_checks(header: &System::Header) {
 		sp_tracing::enter_span!(sp_tracing::Level::TRACE, "final_checks");
+		Self::check_block_weight();
+
 		// remove temporaries
 		let new_header = <frame_system::Pallet<System>>::finalize();
 
@@ -721,6 +907,10 @@ mod tests {
 		type ExistentialDeposit = ExistentialDeposit;
 		type AccountStore = System;
 		type MaxLocks = ();
+		type HoldIdentifier = ();
+		type FreezeIdentifier = ();
+		type MaxHolds = frame_support::traits::ConstU32<1>;
+		type MaxFreezes = frame_support::traits::ConstU32<1>;
 		type WeightInfo = ();
 	}
 
@@ -859,6 +1049,67 @@ mod tests {
 		});
 	}
 
+	#[cfg(feature = "try-runtime")]
+	#[test]
+	fn try_execute_block_can_skip_state_root_check() {
+		new_test_ext(1).execute_with(|| {
+			let weight = Executive::try_execute_block(
+				Block {
+					header: Header {
+						parent_hash: [69u8; 32].into(),
+						number: 1,
+						state_root: [0u8; 32].into(),
+						extrinsics_root: hex!("03170a2e7597b7b7e3d84c05391d139a62b157e78786d8c082f29dcf4c111314").into(),
+						digest: Digest { logs: vec![], },
+					},
+					extrinsics: vec![],
+				},
+				false,
+				true,
+				UpgradeCheckSelect::All,
+			);
+			assert!(weight > 0);
+		});
+	}
+
+	#[cfg(feature = "try-runtime")]
+	#[test]
+	fn try_runtime_upgrade_reports_weight_per_pallet() {
+		new_test_ext(1).execute_with(|| {
+			let weights = Executive::try_runtime_upgrade(UpgradeCheckSelect::None, &[]).unwrap();
+			assert_eq!(weights[0], (sp_std::any::type_name::<CustomOnRuntimeUpgrade>(), 100));
+			assert_eq!(weights[1].0, "System");
+			let mut pallets = weights[2..].iter().map(|(pallet, _)| *pallet).collect::<Vec<_>>();
+			pallets.sort();
+			assert_eq!(pallets, vec!["Balances", "Custom"]);
+
+			let total = weights.iter().map(|(_, weight)| weight).sum::<Weight>();
+			assert_eq!(total, Executive::execute_on_runtime_upgrade());
+		});
+	}
+
+	#[cfg(feature = "try-runtime")]
+	#[test]
+	fn try_runtime_upgrade_runs_selected_pallets_only() {
+		new_test_ext(1).execute_with(|| {
+			let pallets = vec![b"Balances".to_vec(), b"System".to_vec()];
+			let weights =
+				Executive::try_runtime_upgrade(UpgradeCheckSelect::All, &pallets).unwrap();
+			let pallets = weights.iter().map(|(pallet, _)| *pallet).collect::<Vec<_>>();
+			assert_eq!(pallets, vec!["System", "Balances"]);
+		});
+	}
+
+	#[cfg(feature = "try-runtime")]
+	#[test]
+	fn try_runtime_upgrade_runs_try_state_checks() {
+		new_test_ext(1).execute_with(|| {
+			assert_eq!(Executive::try_state(), Ok(()));
+			assert!(Executive::try_runtime_upgrade(UpgradeCheckSelect::All, &[]).is_ok());
+			assert!(Executive::try_runtime_upgrade(UpgradeCheckSelect::TryState, &[]).is_ok());
+		});
+	}
+
 	#[test]
 	#[should_panic]
 	fn block_import_of_bad_extrinsic_root_fails() {
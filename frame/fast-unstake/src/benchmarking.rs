@@ -0,0 +1,140 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Fast Unstake pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{benchmarks, account, impl_benchmark_test_suite};
+use frame_support::{
+	traits::EnsureOrigin,
+	dispatch::UnfilteredDispatchable,
+	StorageValue as _,
+	StorageDoubleMap as _,
+};
+use pallet_staking::{testing_utils::*, Exposure, RewardDestination};
+
+use crate::Pallet as FastUnstake;
+
+const SEED: u32 = 0;
+const MAX_VALIDATORS: u32 = 256;
+// Distinct from the validators created by `create_validators`, which start at 0.
+const NOMINATOR: u32 = 1_000;
+
+/// Create a nominating stash, with enough free balance left for the deposit.
+///
+/// Returns the stash and its controller.
+fn create_nominator<T: Config>() -> Result<(T::AccountId, T::AccountId), &'static str> {
+	let (stash, controller) =
+		create_stash_controller::<T>(NOMINATOR, 100, RewardDestination::Staked)?;
+	<T as Config>::Currency::deposit_creating(&stash, T::Deposit::get());
+	let targets = create_validators::<T>(1, 100)?;
+	let origin = RawOrigin::Signed(controller.clone());
+	pallet_staking::Module::<T>::nominate(origin.into(), targets)?;
+	Ok((stash, controller))
+}
+
+/// Create a nominating stash and register it.
+fn register_nominator<T: Config>() -> Result<(T::AccountId, T::AccountId), &'static str> {
+	let (stash, controller) = create_nominator::<T>()?;
+	FastUnstake::<T>::register_fast_unstake(RawOrigin::Signed(controller.clone()).into())?;
+	Ok((stash, controller))
+}
+
+/// Set the current era to `BondingDuration` and give each of the eras to check exactly
+/// `validators` exposures, none of them exposing the stash. `ValidatorCount` is set to match.
+///
+/// Returns the number of eras to check.
+fn setup_eras<T: Config>(validators: u32) -> u32 {
+	let bonding_duration = <T as pallet_staking::Config>::BondingDuration::get();
+	pallet_staking::CurrentEra::put(bonding_duration);
+	for era in 0..=bonding_duration {
+		pallet_staking::ErasStakers::<T>::remove_prefix(era);
+		for i in 0..validators {
+			let validator: T::AccountId = account("validator", i, SEED);
+			pallet_staking::ErasStakers::<T>::insert(era, validator, Exposure::default());
+		}
+	}
+	pallet_staking::ValidatorCount::put(validators);
+	bonding_duration + 1
+}
+
+benchmarks! {
+	// The worst case: the stash is unstaked, which reads and removes all its staking records.
+	on_idle_unstake {
+		let eras = setup_eras::<T>(1);
+		let (stash, _) = register_nominator::<T>()?;
+		ErasToCheckPerBlock::<T>::put(eras);
+		// Check all the eras, so that only the unstaking is left.
+		FastUnstake::<T>::do_on_idle(Weight::max_value());
+		let head = Head::<T>::get().ok_or("the stash must be checked")?;
+		assert_eq!(head.checked.len() as u32, eras);
+	}: {
+		FastUnstake::<T>::do_on_idle(Weight::max_value());
+	}
+	verify {
+		assert!(Head::<T>::get().is_none());
+		assert!(pallet_staking::Module::<T>::bonded(&stash).is_none());
+	}
+
+	// The worst case: `e` eras of `v` exposures each are read, none of them exposing the stash.
+	on_idle_check {
+		let v in 1 .. MAX_VALIDATORS;
+		let e in 1 .. <T as pallet_staking::Config>::BondingDuration::get() + 1;
+
+		setup_eras::<T>(v);
+		let (stash, _) = register_nominator::<T>()?;
+		ErasToCheckPerBlock::<T>::put(e);
+	}: {
+		FastUnstake::<T>::do_on_idle(Weight::max_value());
+	}
+	verify {
+		let head = Head::<T>::get().ok_or("the stash must be checked")?;
+		assert_eq!(head.stash, stash);
+		assert_eq!(head.checked.len() as u32, e);
+	}
+
+	register_fast_unstake {
+		let (stash, controller) = create_nominator::<T>()?;
+	}: _(RawOrigin::Signed(controller))
+	verify {
+		assert!(Queue::<T>::contains_key(&stash));
+		assert!(!pallet_staking::Nominators::<T>::contains_key(&stash));
+	}
+
+	deregister {
+		let (stash, controller) = register_nominator::<T>()?;
+	}: _(RawOrigin::Signed(controller))
+	verify {
+		assert!(!Queue::<T>::contains_key(&stash));
+	}
+
+	control {
+		let call = Call::<T>::control(4);
+		let origin = T::ControlOrigin::successful_origin();
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert_eq!(ErasToCheckPerBlock::<T>::get(), 4);
+	}
+}
+
+impl_benchmark_test_suite!(
+	FastUnstake,
+	crate::mock::new_test_ext(),
+	crate::mock::Test,
+);
@@ -0,0 +1,349 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Fast Unstake Pallet
+//!
+//! A pallet to unstake the stashes which were not exposed in any recent era, without waiting for
+//! the bonding duration.
+//!
+//! ## Overview
+//!
+//! A stash which was not exposed, neither as a validator nor as a nominator, in any of the last
+//! `BondingDuration` eras cannot be slashed for them, so there is no reason to keep its funds
+//! locked. Checking this requires iterating all the exposures of each of these eras though, which
+//! is far too expensive for a single transaction.
+//!
+//! Instead, the controller registers its stash with `register_fast_unstake`: the stash is chilled,
+//! fully unbonded and queued, and a deposit is reserved from it. In `on_idle`, the pallet takes a
+//! stash from the queue and checks up to [`ErasToCheckPerBlock`] of its eras per block, as long as
+//! the remaining weight of the block allows it. Once all its eras are checked, the stash is
+//! unstaked and its deposit is returned. If it turns out to be exposed in any of them, its deposit
+//! is slashed instead, and it keeps unbonding as usual.
+//!
+//! The eras to check are always the last `BondingDuration` eras up to the current era, so a stash
+//! being checked while a new era starts also gets checked for that era. At most `ValidatorCount`
+//! exposures are read per era: A stash that can't be checked within that bound is removed from the
+//! queue and gets its deposit back, as is a stash that rebonded or started to nominate or validate
+//! again since it registered. Either way, it keeps unbonding as usual.
+//!
+//! A queued stash can leave the queue with `deregister`, unless it is already being checked. It
+//! then keeps unbonding as usual.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! - `register_fast_unstake`: Chill and unbond the stash of the controller, and queue it.
+//! - `deregister`: Remove the stash of the controller from the queue.
+//! - `control`: Set the number of eras checked per block.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_std::prelude::*;
+use codec::{Encode, Decode};
+use sp_runtime::{RuntimeDebug, traits::{Saturating, Zero}};
+use frame_support::{
+	traits::{Currency, ReservableCurrency, Get},
+	weights::Weight,
+	StorageMap as _,
+};
+use frame_system::RawOrigin;
+use pallet_staking::EraIndex;
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+mod benchmarking;
+pub mod weights;
+
+pub(crate) const LOG_TARGET: &'static str = "runtime::fast_unstake";
+
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// A stash being checked, with the eras checked so far.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct UnstakeRequest<AccountId, Balance> {
+	/// The stash to unstake.
+	pub stash: AccountId,
+	/// The eras in which the stash is known not to be exposed.
+	pub checked: Vec<EraIndex>,
+	/// The deposit reserved from the stash.
+	pub deposit: Balance,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{pallet_prelude::*, transactional};
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_staking::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency in which the deposit is reserved.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The deposit reserved from a stash while it is queued, slashed if the stash turns out to
+		/// be exposed in one of the checked eras.
+		///
+		/// This must be enough to cover the checks of all the eras of a stash, as they are paid
+		/// for by the remaining weight of the blocks rather than by the stash.
+		#[pallet::constant]
+		type Deposit: Get<BalanceOf<Self>>;
+
+		/// The origin which can set the number of eras checked per block.
+		type ControlOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The stash being checked, if any.
+	#[pallet::storage]
+	pub type Head<T: Config> = StorageValue<_, UnstakeRequest<T::AccountId, BalanceOf<T>>>;
+
+	/// The stashes waiting to be checked, with their deposit.
+	///
+	/// They are not checked in registration order.
+	#[pallet::storage]
+	pub type Queue<T: Config> = CountedStorageMap<_, Twox64Concat, T::AccountId, BalanceOf<T>>;
+
+	/// The number of eras checked per block. Nothing is checked while this is zero.
+	#[pallet::storage]
+	pub type ErasToCheckPerBlock<T> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::metadata(T::AccountId = "AccountId", BalanceOf<T> = "Balance")]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A stash was unstaked, with the result of `force_unstake`. \[stash, result\]
+		Unstaked(T::AccountId, DispatchResult),
+		/// A stash was exposed in one of the checked eras and its deposit was slashed.
+		/// \[stash, amount\]
+		Slashed(T::AccountId, BalanceOf<T>),
+		/// A stash was found not to be exposed in some eras. \[stash, eras\]
+		Checking(T::AccountId, Vec<EraIndex>),
+		/// A stash did not qualify for a fast unstake anymore, or could not be checked, and was
+		/// removed from the queue with its deposit returned. \[stash\]
+		Deregistered(T::AccountId),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The origin is not the controller of a stash.
+		NotController,
+		/// The stash is already queued.
+		AlreadyQueued,
+		/// The stash has funds unbonding already.
+		NotFullyBonded,
+		/// The stash is not queued.
+		NotQueued,
+		/// The stash is being checked, and can't be deregistered anymore.
+		AlreadyHead,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(_: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::do_on_idle(remaining_weight)
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Chill and fully unbond the stash of the controller, and queue it to be unstaked once
+		/// it is known not to be exposed in any of the last `BondingDuration` eras.
+		///
+		/// `Deposit` is reserved from the stash, which must not have any funds unbonding yet.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the controller.
+		#[pallet::weight(<T as Config>::WeightInfo::register_fast_unstake())]
+		#[transactional]
+		pub fn register_fast_unstake(origin: OriginFor<T>) -> DispatchResult {
+			let controller = ensure_signed(origin.clone())?;
+			let ledger = pallet_staking::Module::<T>::ledger(&controller)
+				.ok_or(Error::<T>::NotController)?;
+			ensure!(!Queue::<T>::contains_key(&ledger.stash), Error::<T>::AlreadyQueued);
+			ensure!(!Self::is_head(&ledger.stash), Error::<T>::AlreadyHead);
+			ensure!(ledger.unlocking.is_empty(), Error::<T>::NotFullyBonded);
+
+			pallet_staking::Module::<T>::chill(origin.clone())?;
+			pallet_staking::Module::<T>::unbond(origin, ledger.total)?;
+
+			let deposit = T::Deposit::get();
+			<T as Config>::Currency::reserve(&ledger.stash, deposit)?;
+			Queue::<T>::insert(&ledger.stash, deposit);
+			Ok(())
+		}
+
+		/// Remove the stash of the controller from the queue and return its deposit.
+		///
+		/// The stash keeps unbonding as usual. This is not possible anymore once the stash is
+		/// being checked.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the controller.
+		#[pallet::weight(<T as Config>::WeightInfo::deregister())]
+		pub fn deregister(origin: OriginFor<T>) -> DispatchResult {
+			let controller = ensure_signed(origin)?;
+			let stash = pallet_staking::Module::<T>::ledger(&controller)
+				.map(|ledger| ledger.stash)
+				.ok_or(Error::<T>::NotController)?;
+			ensure!(!Self::is_head(&stash), Error::<T>::AlreadyHead);
+
+			let deposit = Queue::<T>::take(&stash).ok_or(Error::<T>::NotQueued)?;
+			<T as Config>::Currency::unreserve(&stash, deposit);
+			Ok(())
+		}
+
+		/// Set the number of eras checked per block. Zero stops the checks.
+		///
+		/// The dispatch origin for this call must be `ControlOrigin`.
+		#[pallet::weight(<T as Config>::WeightInfo::control())]
+		pub fn control(origin: OriginFor<T>, eras_to_check: EraIndex) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+			ErasToCheckPerBlock::<T>::put(eras_to_check);
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Check the eras of the stash being checked, or of a new stash from the queue, if
+	/// `remaining_weight` allows checking `ErasToCheckPerBlock` eras.
+	///
+	/// The stash is unstaked once no era is left to check, i.e. in the block after its last
+	/// eras were checked, or has its deposit slashed as soon as it is found exposed.
+	///
+	/// Returns the weight consumed.
+	pub(crate) fn do_on_idle(remaining_weight: Weight) -> Weight {
+		let eras_to_check_per_block = ErasToCheckPerBlock::<T>::get();
+		if eras_to_check_per_block.is_zero() {
+			return T::DbWeight::get().reads(1);
+		}
+
+		let validator_count = pallet_staking::Module::<T>::validator_count();
+		let check_weight = <T as Config>::WeightInfo::on_idle_check(
+			validator_count,
+			eras_to_check_per_block,
+		);
+		let unstake_weight = <T as Config>::WeightInfo::on_idle_unstake();
+		let max_weight = check_weight.max(unstake_weight);
+		if max_weight > remaining_weight {
+			return T::DbWeight::get().reads(2);
+		}
+
+		let request = Head::<T>::take().or_else(|| {
+			Queue::<T>::drain().next().map(|(stash, deposit)| {
+				UnstakeRequest { stash, checked: Default::default(), deposit }
+			})
+		});
+		let UnstakeRequest { stash, mut checked, deposit } = match request {
+			Some(request) => request,
+			None => return T::DbWeight::get().reads(4),
+		};
+
+		// The stash could have rebonded, or even nominated again, since it registered.
+		if !Self::is_eligible(&stash) {
+			Self::deregister_request(stash, deposit);
+			return max_weight;
+		}
+
+		let current_era = pallet_staking::Module::<T>::current_era().unwrap_or_default();
+		let bonding_duration = <T as pallet_staking::Config>::BondingDuration::get();
+		let eras_to_check = (current_era.saturating_sub(bonding_duration)..=current_era)
+			.rev()
+			.filter(|era| !checked.contains(era))
+			.take(eras_to_check_per_block as usize)
+			.collect::<Vec<_>>();
+
+		if eras_to_check.is_empty() {
+			let num_slashing_spans = pallet_staking::Module::<T>::num_slashing_spans(&stash);
+			let result = pallet_staking::Module::<T>::force_unstake(
+				RawOrigin::Root.into(),
+				stash.clone(),
+				num_slashing_spans,
+			);
+			<T as Config>::Currency::unreserve(&stash, deposit);
+			log::info!(target: LOG_TARGET, "unstaked {:?}: {:?}", stash, result);
+			Self::deposit_event(Event::<T>::Unstaked(stash, result));
+			return unstake_weight;
+		}
+
+		let check_weight = <T as Config>::WeightInfo::on_idle_check(
+			validator_count,
+			eras_to_check.len() as u32,
+		);
+		let mut exposed = Some(false);
+		for era in eras_to_check.iter() {
+			exposed = pallet_staking::Module::<T>::is_exposed_in_era(
+				&stash,
+				*era,
+				validator_count,
+			);
+			if exposed != Some(false) {
+				break;
+			}
+		}
+		match exposed {
+			Some(false) => {
+				checked.extend(eras_to_check.iter().copied());
+				Head::<T>::put(UnstakeRequest { stash: stash.clone(), checked, deposit });
+				Self::deposit_event(Event::<T>::Checking(stash, eras_to_check));
+			},
+			Some(true) => {
+				let (_, not_slashed) = <T as Config>::Currency::slash_reserved(&stash, deposit);
+				let slashed = deposit.saturating_sub(not_slashed);
+				log::info!(target: LOG_TARGET, "{:?} was exposed, slashed {:?}", stash, slashed);
+				Self::deposit_event(Event::<T>::Slashed(stash, slashed));
+			},
+			// An era has more exposures than can be checked within `check_weight`.
+			None => Self::deregister_request(stash, deposit),
+		}
+		check_weight
+	}
+
+	/// Returns true if `stash` is still chilled and fully unbonding, as it was when registered.
+	fn is_eligible(stash: &T::AccountId) -> bool {
+		!pallet_staking::Nominators::<T>::contains_key(stash) &&
+			!pallet_staking::Validators::<T>::contains_key(stash) &&
+			pallet_staking::Module::<T>::bonded(stash)
+				.and_then(|controller| pallet_staking::Module::<T>::ledger(&controller))
+				.map_or(false, |ledger| ledger.active.is_zero())
+	}
+
+	/// Drop the request of `stash` and return its deposit. It keeps unbonding as usual.
+	fn deregister_request(stash: T::AccountId, deposit: BalanceOf<T>) {
+		<T as Config>::Currency::unreserve(&stash, deposit);
+		log::info!(target: LOG_TARGET, "{:?} can't be fast unstaked, deregistered", stash);
+		Self::deposit_event(Event::<T>::Deregistered(stash));
+	}
+
+	/// Returns true if `stash` is being checked.
+	fn is_head(stash: &T::AccountId) -> bool {
+		Head::<T>::get().map_or(false, |head| head.stash == *stash)
+	}
+}
@@ -0,0 +1,322 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the Fast Unstake pallet.
+
+use super::*;
+use crate::mock::*;
+use frame_support::{assert_noop, assert_ok, traits::{Hooks, ReservableCurrency}};
+use pallet_staking::RewardDestination;
+use sp_runtime::DispatchError;
+
+#[test]
+fn register_fast_unstake_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(FastUnstake::register_fast_unstake(Origin::signed(2)));
+
+		assert_eq!(Queue::<Test>::get(1), Some(Deposit::get()));
+		assert_eq!(Queue::<Test>::count(), 1);
+		assert_eq!(Balances::reserved_balance(1), Deposit::get());
+
+		// the stash is chilled and fully unbonding.
+		assert!(Staking::nominators(1).is_none());
+		let ledger = Staking::ledger(2).unwrap();
+		assert_eq!(ledger.active, 0);
+		assert_eq!(ledger.total, 100);
+		assert_eq!(ledger.unlocking.len(), 1);
+	});
+}
+
+#[test]
+fn register_fast_unstake_checks_the_stash() {
+	new_test_ext().execute_with(|| {
+		// signed by the stash rather than the controller.
+		assert_noop!(
+			FastUnstake::register_fast_unstake(Origin::signed(1)),
+			Error::<Test>::NotController,
+		);
+
+		assert_ok!(FastUnstake::register_fast_unstake(Origin::signed(2)));
+		assert_noop!(
+			FastUnstake::register_fast_unstake(Origin::signed(2)),
+			Error::<Test>::AlreadyQueued,
+		);
+
+		assert_ok!(Staking::unbond(Origin::signed(6), 50));
+		assert_noop!(
+			FastUnstake::register_fast_unstake(Origin::signed(6)),
+			Error::<Test>::NotFullyBonded,
+		);
+	});
+}
+
+#[test]
+fn register_fast_unstake_is_reverted_without_deposit() {
+	new_test_ext().execute_with(|| {
+		// 7 can't reserve the deposit on top of what it bonds.
+		Balances::make_free_balance_be(&7, 105);
+		assert_ok!(Staking::bond(Origin::signed(7), 8, 100, RewardDestination::Staked));
+		assert_ok!(Staking::nominate(Origin::signed(8), vec![3]));
+		assert!(!Balances::can_reserve(&7, Deposit::get()));
+
+		assert!(FastUnstake::register_fast_unstake(Origin::signed(8)).is_err());
+
+		// neither chilled nor unbonded.
+		assert!(Staking::nominators(7).is_some());
+		assert_eq!(Staking::ledger(8).unwrap().active, 100);
+		assert!(!Queue::<Test>::contains_key(7));
+	});
+}
+
+#[test]
+fn deregister_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(FastUnstake::deregister(Origin::signed(2)), Error::<Test>::NotQueued);
+		assert_ok!(FastUnstake::register_fast_unstake(Origin::signed(2)));
+		assert_noop!(FastUnstake::deregister(Origin::signed(1)), Error::<Test>::NotController);
+
+		assert_ok!(FastUnstake::deregister(Origin::signed(2)));
+		assert!(!Queue::<Test>::contains_key(1));
+		assert_eq!(Queue::<Test>::count(), 0);
+		assert_eq!(Balances::reserved_balance(1), 0);
+
+		// the stash keeps unbonding as usual.
+		assert_eq!(Staking::ledger(2).unwrap().unlocking.len(), 1);
+		assert_noop!(FastUnstake::deregister(Origin::signed(2)), Error::<Test>::NotQueued);
+	});
+}
+
+#[test]
+fn the_stash_being_checked_cannot_deregister() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(FastUnstake::control(Origin::root(), 1));
+		assert_ok!(FastUnstake::register_fast_unstake(Origin::signed(2)));
+		run_on_idle();
+		assert_eq!(Head::<Test>::get().unwrap().stash, 1);
+		assert_eq!(Queue::<Test>::count(), 0);
+
+		assert_noop!(FastUnstake::deregister(Origin::signed(2)), Error::<Test>::AlreadyHead);
+	});
+}
+
+#[test]
+fn control_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(FastUnstake::control(Origin::signed(1), 2), DispatchError::BadOrigin);
+		assert_ok!(FastUnstake::control(Origin::root(), 2));
+		assert_eq!(ErasToCheckPerBlock::<Test>::get(), 2);
+	});
+}
+
+#[test]
+fn nothing_is_checked_without_eras_to_check() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(FastUnstake::register_fast_unstake(Origin::signed(2)));
+		run_on_idle();
+
+		assert!(Head::<Test>::get().is_none());
+		assert!(Queue::<Test>::contains_key(1));
+		assert!(fast_unstake_events().is_empty());
+	});
+}
+
+#[test]
+fn nothing_is_checked_without_enough_weight() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(FastUnstake::control(Origin::root(), 2));
+		assert_ok!(FastUnstake::register_fast_unstake(Origin::signed(2)));
+		FastUnstake::on_idle(1, <Test as Config>::WeightInfo::on_idle_unstake() - 1);
+
+		assert!(Head::<Test>::get().is_none());
+		assert!(Queue::<Test>::contains_key(1));
+	});
+}
+
+#[test]
+fn unstakes_once_all_eras_are_checked() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(FastUnstake::control(Origin::root(), 2));
+		assert_ok!(FastUnstake::register_fast_unstake(Origin::signed(2)));
+
+		run_on_idle();
+		let head = Head::<Test>::get().unwrap();
+		assert_eq!(head, UnstakeRequest { stash: 1, checked: vec![3, 2], deposit: Deposit::get() });
+		run_on_idle();
+		assert_eq!(Head::<Test>::get().unwrap().checked, vec![3, 2, 1, 0]);
+		run_on_idle();
+
+		assert!(Head::<Test>::get().is_none());
+		assert_eq!(
+			fast_unstake_events(),
+			vec![
+				crate::Event::Checking(1, vec![3, 2]),
+				crate::Event::Checking(1, vec![1, 0]),
+				crate::Event::Unstaked(1, Ok(())),
+			],
+		);
+
+		// all the funds of the stash are free again.
+		assert!(Staking::bonded(1).is_none());
+		assert!(Staking::ledger(2).is_none());
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::usable_balance(1), 1_000);
+	});
+}
+
+#[test]
+fn eras_started_while_checking_are_checked() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(FastUnstake::control(Origin::root(), 2));
+		assert_ok!(FastUnstake::register_fast_unstake(Origin::signed(2)));
+		run_on_idle();
+
+		pallet_staking::CurrentEra::put(GENESIS_ERA + 1);
+		run_on_idle();
+		run_on_idle();
+
+		assert_eq!(
+			fast_unstake_events(),
+			vec![
+				crate::Event::Checking(1, vec![3, 2]),
+				crate::Event::Checking(1, vec![4, 1]),
+				crate::Event::Unstaked(1, Ok(())),
+			],
+		);
+	});
+}
+
+#[test]
+fn exposed_stash_is_slashed() {
+	new_test_ext().execute_with(|| {
+		expose_in_era(1, 1);
+		assert_ok!(FastUnstake::control(Origin::root(), 2));
+		assert_ok!(FastUnstake::register_fast_unstake(Origin::signed(2)));
+
+		run_on_idle();
+		assert!(Head::<Test>::get().is_some());
+		run_on_idle();
+
+		assert!(Head::<Test>::get().is_none());
+		assert_eq!(
+			fast_unstake_events(),
+			vec![
+				crate::Event::Checking(1, vec![3, 2]),
+				crate::Event::Slashed(1, Deposit::get()),
+			],
+		);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 1_000 - Deposit::get());
+
+		// the stash keeps unbonding as usual.
+		assert_eq!(Staking::ledger(2).unwrap().unlocking.len(), 1);
+	});
+}
+
+#[test]
+fn stash_that_rebonded_is_deregistered() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(FastUnstake::control(Origin::root(), 2));
+		assert_ok!(FastUnstake::register_fast_unstake(Origin::signed(2)));
+		assert_ok!(FastUnstake::register_fast_unstake(Origin::signed(6)));
+
+		// both rebond, one while queued and one while being checked.
+		run_on_idle();
+		let head = Head::<Test>::get().unwrap().stash;
+		let queued = if head == 1 { 5 } else { 1 };
+		assert_ok!(Staking::rebond(Origin::signed(head + 1), 50));
+		assert_ok!(Staking::rebond(Origin::signed(queued + 1), 50));
+		run_on_idle();
+		run_on_idle();
+
+		assert!(Head::<Test>::get().is_none());
+		assert_eq!(Queue::<Test>::count(), 0);
+		assert_eq!(
+			fast_unstake_events(),
+			vec![
+				crate::Event::Checking(head, vec![3, 2]),
+				crate::Event::Deregistered(head),
+				crate::Event::Deregistered(queued),
+			],
+		);
+
+		// both keep their deposit and their bond.
+		for stash in [head, queued].iter() {
+			assert_eq!(Balances::reserved_balance(stash), 0);
+			assert_eq!(Staking::ledger(stash + 1).unwrap().active, 50);
+		}
+	});
+}
+
+#[test]
+fn stash_is_deregistered_if_an_era_has_too_many_exposures() {
+	new_test_ext().execute_with(|| {
+		// the validator count is 1, but era 2 has two exposures.
+		expose_in_era(30, 2);
+		pallet_staking::ErasStakers::<Test>::insert(2, 40, pallet_staking::Exposure {
+			total: 100,
+			own: 100,
+			others: vec![],
+		});
+		assert_ok!(FastUnstake::control(Origin::root(), 2));
+		assert_ok!(FastUnstake::register_fast_unstake(Origin::signed(2)));
+
+		run_on_idle();
+
+		assert!(Head::<Test>::get().is_none());
+		assert_eq!(fast_unstake_events(), vec![crate::Event::Deregistered(1)]);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Staking::ledger(2).unwrap().unlocking.len(), 1);
+	});
+}
+
+#[test]
+fn queued_stashes_are_checked_one_after_the_other() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(FastUnstake::control(Origin::root(), 4));
+		assert_ok!(FastUnstake::register_fast_unstake(Origin::signed(2)));
+		assert_ok!(FastUnstake::register_fast_unstake(Origin::signed(6)));
+		assert_eq!(Queue::<Test>::count(), 2);
+
+		run_on_idle();
+		let first = Head::<Test>::get().unwrap().stash;
+		assert_eq!(Queue::<Test>::count(), 1);
+		run_on_idle();
+		assert!(Head::<Test>::get().is_none());
+
+		run_on_idle();
+		let second = Head::<Test>::get().unwrap().stash;
+		assert_ne!(first, second);
+		assert_eq!(Queue::<Test>::count(), 0);
+		run_on_idle();
+
+		assert!(Staking::bonded(1).is_none());
+		assert!(Staking::bonded(5).is_none());
+		assert_eq!(
+			fast_unstake_events(),
+			vec![
+				crate::Event::Checking(first, vec![3, 2, 1, 0]),
+				crate::Event::Unstaked(first, Ok(())),
+				crate::Event::Checking(second, vec![3, 2, 1, 0]),
+				crate::Event::Unstaked(second, Ok(())),
+			],
+		);
+
+		// nothing left to check.
+		run_on_idle();
+		assert_eq!(fast_unstake_events().len(), 4);
+	});
+}
@@ -0,0 +1,357 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Migrations Pallet
+//!
+//! Executes migrations which run over multiple blocks.
+//!
+//! ## Overview
+//!
+//! Each migration implements [`SteppedMigration`](frame_support::traits::SteppedMigration): it
+//! makes progress one step at a time, bounded by the weight it is given, and hands back a cursor
+//! to continue from.
+//!
+//! When the runtime is upgraded, this pallet starts the migrations of `Migrations` and steps
+//! through them in order at the beginning of every block, within the `ServiceWeight` budget. The
+//! progress is kept in the [`Cursor`] storage item: the index of the ongoing migration along with
+//! its own, encoded, cursor.
+//!
+//! Completed migrations are recorded in [`Historic`] by their identifier, and skipped by later
+//! upgrades. Each step runs in a storage transaction, which is rolled back if the step fails. A
+//! migration which fails, whose step would not even fit into the budget of an entire block, or
+//! whose step does not complete it without consuming any weight, leaves the upgrade stuck until
+//! the `ControlOrigin` intervenes.
+//!
+//! The storage being migrated is inconsistent while an upgrade is ongoing. [`Pallet::ongoing`]
+//! can be used in the `BaseCallFilter` of the runtime to hold back the calls touching it.
+//!
+//! ## Try-runtime
+//!
+//! With the `try-runtime` feature, the post upgrade checks of this pallet run the upgrade started
+//! by its `on_runtime_upgrade` hook to completion, block budget by block budget, along with the
+//! pre and post upgrade checks of every migration. They fail if the upgrade takes more than
+//! 100 000 blocks.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! - `force_set_cursor`: Set or clear the cursor, e.g. to resume a stuck upgrade. `ControlOrigin`
+//!   only.
+//! - `clear_historic`: Forget about completed migrations, so that they run again. `ControlOrigin`
+//!   only.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_std::prelude::*;
+use codec::{Encode, Decode};
+use sp_runtime::{RuntimeDebug, traits::Saturating};
+use frame_support::{
+	storage::{with_transaction, TransactionOutcome},
+	traits::{SteppedMigrationError, SteppedMigrations},
+	weights::Weight,
+};
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod tests;
+
+const LOG_TARGET: &str = "runtime::migrations";
+
+/// The maximum number of blocks `try_run_to_completion` runs an upgrade for.
+#[cfg(feature = "try-runtime")]
+const MAX_TRY_RUNTIME_BLOCKS: u32 = 100_000;
+
+/// The progress of an ongoing upgrade.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug)]
+pub struct ActiveCursor<BlockNumber> {
+	/// The index of the ongoing migration in `Migrations`.
+	pub index: u32,
+	/// The encoded cursor of the ongoing migration, `None` if it did not make a step yet.
+	pub inner_cursor: Option<Vec<u8>>,
+	/// The block at which the upgrade started.
+	pub started_at: BlockNumber,
+}
+
+/// The state of an upgrade.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug)]
+pub enum MigrationCursor<BlockNumber> {
+	/// The upgrade is in progress.
+	Active(ActiveCursor<BlockNumber>),
+	/// A migration failed, and the upgrade can not make progress by itself.
+	Stuck,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The migrations to execute on a runtime upgrade, in order.
+		type Migrations: SteppedMigrations;
+
+		/// The maximum weight the migrations may consume in a block.
+		#[pallet::constant]
+		type ServiceWeight: Get<Weight>;
+
+		/// The origin which may override the progress of upgrades.
+		type ControlOrigin: EnsureOrigin<Self::Origin>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	/// The progress of the ongoing upgrade, if any.
+	#[pallet::storage]
+	pub type Cursor<T: Config> =
+		StorageValue<_, MigrationCursor<T::BlockNumber>, OptionQuery>;
+
+	/// The identifiers of the completed migrations.
+	#[pallet::storage]
+	pub type Historic<T: Config> = StorageMap<_, Twox64Concat, Vec<u8>, (), OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::metadata(T::BlockNumber = "BlockNumber")]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An upgrade started. \[ number of migrations \]
+		UpgradeStarted(u32),
+		/// A migration was skipped, as it completed before. \[ index \]
+		MigrationSkipped(u32),
+		/// A migration completed. \[ index \]
+		MigrationCompleted(u32),
+		/// A migration failed, and the upgrade is stuck. \[ index \]
+		MigrationFailed(u32),
+		/// All migrations completed. \[ number of blocks the upgrade took \]
+		UpgradeCompleted(T::BlockNumber),
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			Self::start()
+		}
+
+		fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+			Self::progress(n)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+			Self::try_run_to_completion()
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the progress of the upgrade, or clear it to abandon the upgrade.
+		///
+		/// - `origin`: An origin complying with `ControlOrigin`.
+		/// - `cursor`: The new progress, if any.
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn force_set_cursor(
+			origin: OriginFor<T>,
+			cursor: Option<MigrationCursor<T::BlockNumber>>,
+		) -> DispatchResultWithPostInfo {
+			T::ControlOrigin::ensure_origin(origin)?;
+			Cursor::<T>::set(cursor);
+			Ok(().into())
+		}
+
+		/// Forget about completed migrations, so that they run again in the next upgrade.
+		///
+		/// - `origin`: An origin complying with `ControlOrigin`.
+		/// - `ids`: The identifiers of the migrations.
+		#[pallet::weight(T::DbWeight::get().writes(ids.len() as Weight))]
+		pub fn clear_historic(
+			origin: OriginFor<T>,
+			ids: Vec<Vec<u8>>,
+		) -> DispatchResultWithPostInfo {
+			T::ControlOrigin::ensure_origin(origin)?;
+			for id in ids {
+				Historic::<T>::remove(id);
+			}
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Whether an upgrade is ongoing or stuck, i.e. whether the storage may be inconsistent.
+	pub fn ongoing() -> bool {
+		Cursor::<T>::exists()
+	}
+
+	/// Start an upgrade, unless there are no migrations or an upgrade is ongoing already.
+	fn start() -> Weight {
+		let db_weight = T::DbWeight::get();
+		if Self::ongoing() {
+			log::warn!(target: LOG_TARGET, "an upgrade is ongoing already, not starting another");
+			return db_weight.reads(1);
+		}
+		let migrations = T::Migrations::len();
+		if migrations == 0 {
+			return db_weight.reads(1);
+		}
+
+		Cursor::<T>::put(MigrationCursor::Active(ActiveCursor {
+			index: 0,
+			inner_cursor: None,
+			started_at: frame_system::Pallet::<T>::block_number(),
+		}));
+		Self::deposit_event(Event::<T>::UpgradeStarted(migrations));
+		db_weight.reads_writes(1, 2)
+	}
+
+	/// Make progress on the ongoing upgrade at block `n`, if any, within `ServiceWeight`.
+	fn progress(n: T::BlockNumber) -> Weight {
+		let db_weight = T::DbWeight::get();
+		let limit = T::ServiceWeight::get();
+		let mut weight = db_weight.reads(1);
+		let mut cursor = match Cursor::<T>::get() {
+			Some(MigrationCursor::Active(cursor)) => cursor,
+			_ => return weight,
+		};
+		// the cursor is written back in any case.
+		weight = weight.saturating_add(db_weight.writes(1));
+
+		let mut stepped = false;
+		loop {
+			let id = match T::Migrations::nth_id(cursor.index) {
+				Some(id) => id,
+				None => {
+					Cursor::<T>::kill();
+					let took = n.saturating_sub(cursor.started_at);
+					Self::deposit_event(Event::<T>::UpgradeCompleted(took));
+					return weight;
+				},
+			};
+			if cursor.inner_cursor.is_none() {
+				weight = weight.saturating_add(db_weight.reads(1));
+				if Historic::<T>::contains_key(&id) {
+					Self::deposit_event(Event::<T>::MigrationSkipped(cursor.index));
+					cursor.index += 1;
+					continue;
+				}
+			}
+
+			// the first step of a block is attempted whatever the weight left, so that every block
+			// makes progress or gets the upgrade stuck. A step which does not complete its
+			// migration must consume some weight, otherwise the loop would never end.
+			let remaining = limit.saturating_sub(weight);
+			let inner_cursor = cursor.inner_cursor.clone();
+			let result = with_transaction(|| {
+				let result = T::Migrations::nth_step(cursor.index, inner_cursor, remaining)
+					.unwrap_or(Err(SteppedMigrationError::Failed))
+					.and_then(|(next, used)| match next {
+						Some(_) if used == 0 => Err(SteppedMigrationError::Failed),
+						_ => Ok((next, used)),
+					});
+				// a failed step leaves no changes behind.
+				if result.is_ok() {
+					TransactionOutcome::Commit(result)
+				} else {
+					TransactionOutcome::Rollback(result)
+				}
+			});
+			match result {
+				Ok((Some(next), used)) => {
+					weight = weight.saturating_add(used);
+					cursor.inner_cursor = Some(next);
+				},
+				Ok((None, used)) => {
+					weight = weight.saturating_add(used).saturating_add(db_weight.writes(1));
+					Historic::<T>::insert(&id, ());
+					Self::deposit_event(Event::<T>::MigrationCompleted(cursor.index));
+					cursor.index += 1;
+					cursor.inner_cursor = None;
+				},
+				// retried in the next block, with the budget of an entire block.
+				Err(SteppedMigrationError::InsufficientWeight { .. }) if stepped => break,
+				Err(e) => {
+					log::error!(
+						target: LOG_TARGET,
+						"migration #{} failed, the upgrade is stuck: {:?}",
+						cursor.index,
+						e,
+					);
+					Cursor::<T>::put(MigrationCursor::Stuck);
+					Self::deposit_event(Event::<T>::MigrationFailed(cursor.index));
+					return weight;
+				},
+			}
+			stepped = true;
+			if weight >= limit {
+				break;
+			}
+		}
+
+		Cursor::<T>::put(MigrationCursor::Active(cursor));
+		weight
+	}
+
+	/// Run the ongoing upgrade to completion, as if it was given one block after the other, along
+	/// with the pre and post upgrade checks of its migrations.
+	///
+	/// The pre upgrade checks of all migrations run before the first step of the upgrade.
+	#[cfg(feature = "try-runtime")]
+	fn try_run_to_completion() -> Result<(), &'static str> {
+		let cursor = match Cursor::<T>::get() {
+			Some(MigrationCursor::Active(cursor)) => cursor,
+			Some(MigrationCursor::Stuck) => return Err("the upgrade is stuck"),
+			None => return Ok(()),
+		};
+
+		let mut states = Vec::new();
+		for index in cursor.index..T::Migrations::len() {
+			let id = T::Migrations::nth_id(index).ok_or("migration vanished")?;
+			if Historic::<T>::contains_key(&id) {
+				continue;
+			}
+			let state = T::Migrations::nth_pre_upgrade(index).ok_or("migration vanished")??;
+			states.push((index, state));
+		}
+
+		let mut n = frame_system::Pallet::<T>::block_number();
+		let mut blocks = 0u32;
+		while let Some(MigrationCursor::Active(_)) = Cursor::<T>::get() {
+			if blocks >= MAX_TRY_RUNTIME_BLOCKS {
+				return Err("the upgrade did not complete within MAX_TRY_RUNTIME_BLOCKS blocks");
+			}
+			n = n.saturating_add(1u32.into());
+			blocks += 1;
+			Self::progress(n);
+		}
+		if Cursor::<T>::exists() {
+			return Err("the upgrade got stuck");
+		}
+		log::info!(target: LOG_TARGET, "the upgrade completed within {} blocks", blocks);
+
+		for (index, state) in states {
+			T::Migrations::nth_post_upgrade(index, state).ok_or("migration vanished")??;
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,309 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the Migrations pallet.
+
+use std::cell::RefCell;
+
+use super::*;
+use crate as pallet_migrations;
+
+use frame_support::{
+	assert_noop, assert_ok, parameter_types,
+	traits::{Hooks, SteppedMigration},
+};
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use sp_runtime::{
+	DispatchError,
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Migrations: pallet_migrations::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Call = Call;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+thread_local! {
+	/// Whether the steps of the mocked migrations fail.
+	pub static FAIL: RefCell<bool> = RefCell::new(false);
+	/// The weight each step of the mocked migrations requires.
+	pub static STEP_WEIGHT: RefCell<Weight> = RefCell::new(1);
+}
+
+/// A migration with the identifier `ID` which completes in `STEPS` steps.
+pub struct MockedMigration<const ID: u8, const STEPS: u32>;
+impl<const ID: u8, const STEPS: u32> SteppedMigration for MockedMigration<ID, STEPS> {
+	type Cursor = u32;
+
+	fn id() -> Vec<u8> {
+		vec![ID]
+	}
+
+	fn step(
+		cursor: Option<u32>,
+		limit: Weight,
+	) -> Result<(Option<u32>, Weight), SteppedMigrationError> {
+		let next = cursor.unwrap_or_default() + 1;
+		// the steps are recorded, to check that failed ones are rolled back.
+		frame_support::storage::unhashed::put(&[ID], &next);
+		if FAIL.with(|fail| *fail.borrow()) {
+			return Err(SteppedMigrationError::Failed);
+		}
+		let required = STEP_WEIGHT.with(|weight| *weight.borrow());
+		if limit < required {
+			return Err(SteppedMigrationError::InsufficientWeight { required });
+		}
+		Ok(((next < STEPS).then(|| next), required))
+	}
+}
+
+parameter_types! {
+	pub const ServiceWeight: Weight = 2;
+}
+
+impl Config for Test {
+	type Event = Event;
+	type Migrations = (MockedMigration<0, 3>, MockedMigration<1, 2>);
+	type ServiceWeight = ServiceWeight;
+	type ControlOrigin = EnsureRoot<u64>;
+}
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+fn run_to(n: u64) {
+	while System::block_number() < n {
+		let next = System::block_number() + 1;
+		System::set_block_number(next);
+		Migrations::on_initialize(next);
+	}
+}
+
+fn events() -> Vec<pallet_migrations::Event<Test>> {
+	let events = System::events()
+		.into_iter()
+		.filter_map(|record| match record.event {
+			Event::pallet_migrations(event) => Some(event),
+			_ => None,
+		})
+		.collect();
+	System::reset_events();
+	events
+}
+
+fn active(index: u32, inner_cursor: Option<u32>) -> Option<MigrationCursor<u64>> {
+	Some(MigrationCursor::Active(ActiveCursor {
+		index,
+		inner_cursor: inner_cursor.map(|cursor| cursor.encode()),
+		started_at: 1,
+	}))
+}
+
+#[test]
+fn upgrade_runs_across_blocks() {
+	new_test_ext().execute_with(|| {
+		assert!(!Migrations::ongoing());
+		Migrations::on_runtime_upgrade();
+		assert!(Migrations::ongoing());
+		assert_eq!(Cursor::<Test>::get(), active(0, None));
+
+		// two steps of the first migration fit into a block.
+		run_to(2);
+		assert_eq!(Cursor::<Test>::get(), active(0, Some(2)));
+
+		// the first migration completes, the second one makes a step.
+		run_to(3);
+		assert_eq!(Cursor::<Test>::get(), active(1, Some(1)));
+		assert!(Historic::<Test>::contains_key(vec![0]));
+
+		run_to(4);
+		assert!(!Migrations::ongoing());
+		assert!(Historic::<Test>::contains_key(vec![1]));
+		assert_eq!(
+			events(),
+			vec![
+				crate::Event::UpgradeStarted(2),
+				crate::Event::MigrationCompleted(0),
+				crate::Event::MigrationCompleted(1),
+				crate::Event::UpgradeCompleted(3),
+			],
+		);
+	});
+}
+
+#[test]
+fn completed_migrations_are_skipped() {
+	new_test_ext().execute_with(|| {
+		Migrations::on_runtime_upgrade();
+		run_to(4);
+		events();
+
+		Migrations::on_runtime_upgrade();
+		run_to(5);
+		assert!(!Migrations::ongoing());
+		assert_eq!(
+			events(),
+			vec![
+				crate::Event::UpgradeStarted(2),
+				crate::Event::MigrationSkipped(0),
+				crate::Event::MigrationSkipped(1),
+				crate::Event::UpgradeCompleted(1),
+			],
+		);
+	});
+}
+
+#[test]
+fn failing_migration_gets_stuck() {
+	new_test_ext().execute_with(|| {
+		Migrations::on_runtime_upgrade();
+		FAIL.with(|fail| *fail.borrow_mut() = true);
+		run_to(3);
+		assert_eq!(Cursor::<Test>::get(), Some(MigrationCursor::Stuck));
+		assert!(Migrations::ongoing());
+		assert_eq!(frame_support::storage::unhashed::get::<u32>(&[0]), None);
+		assert_eq!(
+			events(),
+			vec![crate::Event::UpgradeStarted(2), crate::Event::MigrationFailed(0)],
+		);
+
+		// a new upgrade does not start while stuck.
+		Migrations::on_runtime_upgrade();
+		assert_eq!(Cursor::<Test>::get(), Some(MigrationCursor::Stuck));
+
+		assert_noop!(
+			Migrations::force_set_cursor(Origin::signed(1), None),
+			DispatchError::BadOrigin,
+		);
+		FAIL.with(|fail| *fail.borrow_mut() = false);
+		assert_ok!(Migrations::force_set_cursor(Origin::root(), active(0, None)));
+		run_to(5);
+		assert_eq!(Cursor::<Test>::get(), active(1, Some(1)));
+	});
+}
+
+#[test]
+fn step_exceeding_service_weight_gets_stuck() {
+	new_test_ext().execute_with(|| {
+		STEP_WEIGHT.with(|weight| *weight.borrow_mut() = 3);
+		Migrations::on_runtime_upgrade();
+		run_to(2);
+		assert_eq!(Cursor::<Test>::get(), Some(MigrationCursor::Stuck));
+	});
+}
+
+#[test]
+fn step_without_weight_gets_stuck() {
+	new_test_ext().execute_with(|| {
+		STEP_WEIGHT.with(|weight| *weight.borrow_mut() = 0);
+		Migrations::on_runtime_upgrade();
+		run_to(2);
+		assert_eq!(Cursor::<Test>::get(), Some(MigrationCursor::Stuck));
+		assert_eq!(
+			events(),
+			vec![crate::Event::UpgradeStarted(2), crate::Event::MigrationFailed(0)],
+		);
+	});
+}
+
+#[test]
+fn clear_historic_lets_migrations_run_again() {
+	new_test_ext().execute_with(|| {
+		Migrations::on_runtime_upgrade();
+		run_to(4);
+		assert_ok!(Migrations::clear_historic(Origin::root(), vec![vec![1]]));
+		assert!(Historic::<Test>::contains_key(vec![0]));
+		assert!(!Historic::<Test>::contains_key(vec![1]));
+
+		Migrations::on_runtime_upgrade();
+		events();
+		// the budget is used up by the second migration, the upgrade completes in the next block.
+		run_to(6);
+		assert_eq!(
+			events(),
+			vec![
+				crate::Event::MigrationSkipped(0),
+				crate::Event::MigrationCompleted(1),
+				crate::Event::UpgradeCompleted(2),
+			],
+		);
+	});
+}
+
+#[cfg(feature = "try-runtime")]
+#[test]
+fn post_upgrade_runs_upgrade_to_completion() {
+	new_test_ext().execute_with(|| {
+		Migrations::on_runtime_upgrade();
+		assert_ok!(<Migrations as Hooks<u64>>::post_upgrade(Vec::new()));
+		assert!(!Migrations::ongoing());
+		assert!(Historic::<Test>::contains_key(vec![1]));
+
+		FAIL.with(|fail| *fail.borrow_mut() = true);
+		assert_ok!(Migrations::clear_historic(Origin::root(), vec![vec![1]]));
+		Migrations::on_runtime_upgrade();
+		assert_eq!(
+			<Migrations as Hooks<u64>>::post_upgrade(Vec::new()),
+			Err("the upgrade got stuck"),
+		);
+	});
+}
@@ -0,0 +1,104 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{BalanceOf, Config, Pallet};
+use frame_support::{
+	generate_storage_alias, Identity,
+	traits::{Get, GetPalletVersion, PalletVersion, ReservableCurrency},
+	weights::Weight,
+};
+use sp_std::vec::Vec;
+
+// Calls that were stored for later dispatch, together with the account that stored them and
+// the deposit it holds for them.
+generate_storage_alias!(
+	Multisig,
+	Calls<T: Config> => Map<(Identity, [u8; 32]), (Vec<u8>, T::AccountId, BalanceOf<T>)>
+);
+
+/// Remove all the stored calls, returning their deposits, if the storage version of the pallet
+/// is at most 3.0.0.
+///
+/// The pallet writes its new storage version once the runtime upgrade is done, so this only runs
+/// once. All the calls are removed in the same block: there can't be many of them, as storing a
+/// call requires a deposit.
+pub fn remove_stored_calls<T: Config>() -> Weight {
+	match <Pallet<T> as GetPalletVersion>::storage_version() {
+		Some(storage_version) if storage_version <= PalletVersion::new(3, 0, 0) =>
+			drain_stored_calls::<T>().saturating_add(T::DbWeight::get().reads(1)),
+		_ => T::DbWeight::get().reads(1),
+	}
+}
+
+fn drain_stored_calls<T: Config>() -> Weight {
+	let mut count: Weight = 0;
+	for (_, (_, who, deposit)) in <Calls<T>>::drain() {
+		T::Currency::unreserve(&who, deposit);
+		count += 1;
+	}
+	T::DbWeight::get().reads_writes(count.saturating_add(1), count.saturating_mul(2))
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::tests::{new_test_ext, Balances, Multisig, Test as T};
+	use frame_support::traits::OnRuntimeUpgrade;
+
+	type PalletInfo = <T as frame_system::Config>::PalletInfo;
+
+	#[test]
+	fn stored_calls_are_removed_and_refunded() {
+		new_test_ext().execute_with(|| {
+			PalletVersion::new(3, 0, 0).put_into_storage::<PalletInfo, Multisig>();
+			assert!(Balances::reserve(&1, 5).is_ok());
+			<Calls<T>>::insert([1; 32], (vec![1, 2, 3], 1, 5));
+
+			// the storage version is written by the pallet too.
+			assert_eq!(
+				Multisig::on_runtime_upgrade(),
+				<T as frame_system::Config>::DbWeight::get().reads_writes(3, 3),
+			);
+
+			assert_eq!(<Calls<T>>::iter().count(), 0);
+			assert_eq!(Balances::reserved_balance(1), 0);
+			assert_eq!(Balances::free_balance(1), 10);
+			assert_eq!(
+				<Multisig as GetPalletVersion>::storage_version(),
+				Some(<Multisig as GetPalletVersion>::current_version()),
+			);
+		});
+	}
+
+	#[test]
+	fn stored_calls_are_only_removed_once() {
+		new_test_ext().execute_with(|| {
+			PalletVersion::new(3, 0, 0).put_into_storage::<PalletInfo, Multisig>();
+			Multisig::on_runtime_upgrade();
+
+			assert!(Balances::reserve(&1, 5).is_ok());
+			<Calls<T>>::insert([1; 32], (vec![1, 2, 3], 1, 5));
+
+			assert_eq!(
+				Multisig::on_runtime_upgrade(),
+				<T as frame_system::Config>::DbWeight::get().reads_writes(1, 1),
+			);
+			assert_eq!(<Calls<T>>::iter().count(), 1);
+			assert_eq!(Balances::reserved_balance(1), 5);
+		});
+	}
+}
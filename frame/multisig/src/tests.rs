@@ -78,6 +78,10 @@ parameter_types! {
 }
 impl pallet_balances::Config for Test {
 	type MaxLocks = ();
+	type HoldIdentifier = ();
+	type FreezeIdentifier = ();
+	type MaxHolds = frame_support::traits::ConstU32<1>;
+	type MaxFreezes = frame_support::traits::ConstU32<1>;
 	type Balance = u64;
 	type Event = Event;
 	type DustRemoval = ();
@@ -138,77 +142,31 @@ fn multisig_deposit_is_taken_and_returned() {
 
 		let call = Call::Balances(BalancesCall::transfer(6, 15));
 		let call_weight = call.get_dispatch_info().weight;
-		let data = call.encode();
-		assert_ok!(Multisig::as_multi(Origin::signed(1), 2, vec![2, 3], None, data.clone(), false, 0));
+		assert_ok!(Multisig::as_multi(
+			Origin::signed(1), 2, vec![2, 3], None, Box::new(call.clone()), 0,
+		));
 		assert_eq!(Balances::free_balance(1), 2);
 		assert_eq!(Balances::reserved_balance(1), 3);
 
-		assert_ok!(Multisig::as_multi(Origin::signed(2), 2, vec![1, 3], Some(now()), data, false, call_weight));
+		assert_ok!(Multisig::as_multi(
+			Origin::signed(2), 2, vec![1, 3], Some(now()), Box::new(call), call_weight,
+		));
 		assert_eq!(Balances::free_balance(1), 5);
 		assert_eq!(Balances::reserved_balance(1), 0);
 	});
 }
 
-#[test]
-fn multisig_deposit_is_taken_and_returned_with_call_storage() {
-	new_test_ext().execute_with(|| {
-		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 2);
-		assert_ok!(Balances::transfer(Origin::signed(1), multi, 5));
-		assert_ok!(Balances::transfer(Origin::signed(2), multi, 5));
-		assert_ok!(Balances::transfer(Origin::signed(3), multi, 5));
-
-		let call = Call::Balances(BalancesCall::transfer(6, 15));
-		let call_weight = call.get_dispatch_info().weight;
-		let data = call.encode();
-		let hash = blake2_256(&data);
-		assert_ok!(Multisig::as_multi(Origin::signed(1), 2, vec![2, 3], None, data, true, 0));
-		assert_eq!(Balances::free_balance(1), 0);
-		assert_eq!(Balances::reserved_balance(1), 5);
-
-		assert_ok!(Multisig::approve_as_multi(Origin::signed(2), 2, vec![1, 3], Some(now()), hash, call_weight));
-		assert_eq!(Balances::free_balance(1), 5);
-		assert_eq!(Balances::reserved_balance(1), 0);
-	});
-}
-
-#[test]
-fn multisig_deposit_is_taken_and_returned_with_alt_call_storage() {
-	new_test_ext().execute_with(|| {
-		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 3);
-		assert_ok!(Balances::transfer(Origin::signed(1), multi, 5));
-		assert_ok!(Balances::transfer(Origin::signed(2), multi, 5));
-		assert_ok!(Balances::transfer(Origin::signed(3), multi, 5));
-
-		let call = Call::Balances(BalancesCall::transfer(6, 15));
-		let call_weight = call.get_dispatch_info().weight;
-		let data = call.encode();
-		let hash = blake2_256(&data);
-
-		assert_ok!(Multisig::approve_as_multi(Origin::signed(1), 3, vec![2, 3], None, hash.clone(), 0));
-		assert_eq!(Balances::free_balance(1), 1);
-		assert_eq!(Balances::reserved_balance(1), 4);
-
-		assert_ok!(Multisig::as_multi(Origin::signed(2), 3, vec![1, 3], Some(now()), data, true, 0));
-		assert_eq!(Balances::free_balance(2), 3);
-		assert_eq!(Balances::reserved_balance(2), 2);
-		assert_eq!(Balances::free_balance(1), 1);
-		assert_eq!(Balances::reserved_balance(1), 4);
-
-		assert_ok!(Multisig::approve_as_multi(Origin::signed(3), 3, vec![1, 2], Some(now()), hash, call_weight));
-		assert_eq!(Balances::free_balance(1), 5);
-		assert_eq!(Balances::reserved_balance(1), 0);
-		assert_eq!(Balances::free_balance(2), 5);
-		assert_eq!(Balances::reserved_balance(2), 0);
-	});
-}
-
 #[test]
 fn cancel_multisig_returns_deposit() {
 	new_test_ext().execute_with(|| {
-		let call = Call::Balances(BalancesCall::transfer(6, 15)).encode();
-		let hash = blake2_256(&call);
-		assert_ok!(Multisig::approve_as_multi(Origin::signed(1), 3, vec![2, 3], None, hash.clone(), 0));
-		assert_ok!(Multisig::approve_as_multi(Origin::signed(2), 3, vec![1, 3], Some(now()), hash.clone(), 0));
+		let call = Call::Balances(BalancesCall::transfer(6, 15));
+		let hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::approve_as_multi(
+			Origin::signed(1), 3, vec![2, 3], None, hash.clone(),
+		));
+		assert_ok!(Multisig::approve_as_multi(
+			Origin::signed(2), 3, vec![1, 3], Some(now()), hash.clone(),
+		));
 		assert_eq!(Balances::free_balance(1), 6);
 		assert_eq!(Balances::reserved_balance(1), 4);
 		assert_ok!(
@@ -227,48 +185,30 @@ fn timepoint_checking_works() {
 		assert_ok!(Balances::transfer(Origin::signed(2), multi, 5));
 		assert_ok!(Balances::transfer(Origin::signed(3), multi, 5));
 
-		let call = Call::Balances(BalancesCall::transfer(6, 15)).encode();
-		let hash = blake2_256(&call);
+		let call = Call::Balances(BalancesCall::transfer(6, 15));
+		let hash = blake2_256(&call.encode());
 
 		assert_noop!(
-			Multisig::approve_as_multi(Origin::signed(2), 2, vec![1, 3], Some(now()), hash.clone(), 0),
+			Multisig::approve_as_multi(Origin::signed(2), 2, vec![1, 3], Some(now()), hash.clone()),
 			Error::<Test>::UnexpectedTimepoint,
 		);
 
-		assert_ok!(Multisig::approve_as_multi(Origin::signed(1), 2, vec![2, 3], None, hash, 0));
+		assert_ok!(Multisig::approve_as_multi(Origin::signed(1), 2, vec![2, 3], None, hash));
 
 		assert_noop!(
-			Multisig::as_multi(Origin::signed(2), 2, vec![1, 3], None, call.clone(), false, 0),
+			Multisig::as_multi(Origin::signed(2), 2, vec![1, 3], None, Box::new(call.clone()), 0),
 			Error::<Test>::NoTimepoint,
 		);
 		let later = Timepoint { index: 1, .. now() };
 		assert_noop!(
-			Multisig::as_multi(Origin::signed(2), 2, vec![1, 3], Some(later), call.clone(), false, 0),
+			Multisig::as_multi(
+				Origin::signed(2), 2, vec![1, 3], Some(later), Box::new(call.clone()), 0,
+			),
 			Error::<Test>::WrongTimepoint,
 		);
 	});
 }
 
-#[test]
-fn multisig_2_of_3_works_with_call_storing() {
-	new_test_ext().execute_with(|| {
-		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 2);
-		assert_ok!(Balances::transfer(Origin::signed(1), multi, 5));
-		assert_ok!(Balances::transfer(Origin::signed(2), multi, 5));
-		assert_ok!(Balances::transfer(Origin::signed(3), multi, 5));
-
-		let call = Call::Balances(BalancesCall::transfer(6, 15));
-		let call_weight = call.get_dispatch_info().weight;
-		let data = call.encode();
-		let hash = blake2_256(&data);
-		assert_ok!(Multisig::as_multi(Origin::signed(1), 2, vec![2, 3], None, data, true, 0));
-		assert_eq!(Balances::free_balance(6), 0);
-
-		assert_ok!(Multisig::approve_as_multi(Origin::signed(2), 2, vec![1, 3], Some(now()), hash, call_weight));
-		assert_eq!(Balances::free_balance(6), 15);
-	});
-}
-
 #[test]
 fn multisig_2_of_3_works() {
 	new_test_ext().execute_with(|| {
@@ -279,12 +219,13 @@ fn multisig_2_of_3_works() {
 
 		let call = Call::Balances(BalancesCall::transfer(6, 15));
 		let call_weight = call.get_dispatch_info().weight;
-		let data = call.encode();
-		let hash = blake2_256(&data);
-		assert_ok!(Multisig::approve_as_multi(Origin::signed(1), 2, vec![2, 3], None, hash, 0));
+		let hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::approve_as_multi(Origin::signed(1), 2, vec![2, 3], None, hash));
 		assert_eq!(Balances::free_balance(6), 0);
 
-		assert_ok!(Multisig::as_multi(Origin::signed(2), 2, vec![1, 3], Some(now()), data, false, call_weight));
+		assert_ok!(Multisig::as_multi(
+			Origin::signed(2), 2, vec![1, 3], Some(now()), Box::new(call), call_weight,
+		));
 		assert_eq!(Balances::free_balance(6), 15);
 	});
 }
@@ -299,13 +240,18 @@ fn multisig_3_of_3_works() {
 
 		let call = Call::Balances(BalancesCall::transfer(6, 15));
 		let call_weight = call.get_dispatch_info().weight;
-		let data = call.encode();
-		let hash = blake2_256(&data);
-		assert_ok!(Multisig::approve_as_multi(Origin::signed(1), 3, vec![2, 3], None, hash.clone(), 0));
-		assert_ok!(Multisig::approve_as_multi(Origin::signed(2), 3, vec![1, 3], Some(now()), hash.clone(), 0));
+		let hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::approve_as_multi(
+			Origin::signed(1), 3, vec![2, 3], None, hash.clone(),
+		));
+		assert_ok!(Multisig::approve_as_multi(
+			Origin::signed(2), 3, vec![1, 3], Some(now()), hash.clone(),
+		));
 		assert_eq!(Balances::free_balance(6), 0);
 
-		assert_ok!(Multisig::as_multi(Origin::signed(3), 3, vec![1, 2], Some(now()), data, false, call_weight));
+		assert_ok!(Multisig::as_multi(
+			Origin::signed(3), 3, vec![1, 2], Some(now()), Box::new(call), call_weight,
+		));
 		assert_eq!(Balances::free_balance(6), 15);
 	});
 }
@@ -313,10 +259,14 @@ fn multisig_3_of_3_works() {
 #[test]
 fn cancel_multisig_works() {
 	new_test_ext().execute_with(|| {
-		let call = Call::Balances(BalancesCall::transfer(6, 15)).encode();
-		let hash = blake2_256(&call);
-		assert_ok!(Multisig::approve_as_multi(Origin::signed(1), 3, vec![2, 3], None, hash.clone(), 0));
-		assert_ok!(Multisig::approve_as_multi(Origin::signed(2), 3, vec![1, 3], Some(now()), hash.clone(), 0));
+		let call = Call::Balances(BalancesCall::transfer(6, 15));
+		let hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::approve_as_multi(
+			Origin::signed(1), 3, vec![2, 3], None, hash.clone(),
+		));
+		assert_ok!(Multisig::approve_as_multi(
+			Origin::signed(2), 3, vec![1, 3], Some(now()), hash.clone(),
+		));
 		assert_noop!(
 			Multisig::cancel_as_multi(Origin::signed(2), 3, vec![1, 3], now(), hash.clone()),
 			Error::<Test>::NotOwner,
@@ -328,36 +278,23 @@ fn cancel_multisig_works() {
 }
 
 #[test]
-fn cancel_multisig_with_call_storage_works() {
+fn cancel_multisig_started_with_the_call_works() {
 	new_test_ext().execute_with(|| {
-		let call = Call::Balances(BalancesCall::transfer(6, 15)).encode();
-		let hash = blake2_256(&call);
-		assert_ok!(Multisig::as_multi(Origin::signed(1), 3, vec![2, 3], None, call, true, 0));
-		assert_eq!(Balances::free_balance(1), 4);
-		assert_ok!(Multisig::approve_as_multi(Origin::signed(2), 3, vec![1, 3], Some(now()), hash.clone(), 0));
+		let call = Call::Balances(BalancesCall::transfer(6, 15));
+		let hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::as_multi(Origin::signed(1), 3, vec![2, 3], None, Box::new(call), 0));
+		// the call is not stored, only the deposit for the operation is taken.
+		assert_eq!(Balances::free_balance(1), 6);
+		assert_ok!(Multisig::approve_as_multi(
+			Origin::signed(2), 3, vec![1, 3], Some(now()), hash,
+		));
+		assert_eq!(Balances::free_balance(2), 10);
 		assert_noop!(
-			Multisig::cancel_as_multi(Origin::signed(2), 3, vec![1, 3], now(), hash.clone()),
+			Multisig::cancel_as_multi(Origin::signed(2), 3, vec![1, 3], now(), hash),
 			Error::<Test>::NotOwner,
 		);
-		assert_ok!(
-			Multisig::cancel_as_multi(Origin::signed(1), 3, vec![2, 3], now(), hash.clone()),
-		);
-		assert_eq!(Balances::free_balance(1), 10);
-	});
-}
-
-#[test]
-fn cancel_multisig_with_alt_call_storage_works() {
-	new_test_ext().execute_with(|| {
-		let call = Call::Balances(BalancesCall::transfer(6, 15)).encode();
-		let hash = blake2_256(&call);
-		assert_ok!(Multisig::approve_as_multi(Origin::signed(1), 3, vec![2, 3], None, hash.clone(), 0));
-		assert_eq!(Balances::free_balance(1), 6);
-		assert_ok!(Multisig::as_multi(Origin::signed(2), 3, vec![1, 3], Some(now()), call, true, 0));
-		assert_eq!(Balances::free_balance(2), 8);
 		assert_ok!(Multisig::cancel_as_multi(Origin::signed(1), 3, vec![2, 3], now(), hash));
 		assert_eq!(Balances::free_balance(1), 10);
-		assert_eq!(Balances::free_balance(2), 10);
 	});
 }
 
@@ -371,11 +308,14 @@ fn multisig_2_of_3_as_multi_works() {
 
 		let call = Call::Balances(BalancesCall::transfer(6, 15));
 		let call_weight = call.get_dispatch_info().weight;
-		let data = call.encode();
-		assert_ok!(Multisig::as_multi(Origin::signed(1), 2, vec![2, 3], None, data.clone(), false, 0));
+		assert_ok!(Multisig::as_multi(
+			Origin::signed(1), 2, vec![2, 3], None, Box::new(call.clone()), 0,
+		));
 		assert_eq!(Balances::free_balance(6), 0);
 
-		assert_ok!(Multisig::as_multi(Origin::signed(2), 2, vec![1, 3], Some(now()), data, false, call_weight));
+		assert_ok!(Multisig::as_multi(
+			Origin::signed(2), 2, vec![1, 3], Some(now()), Box::new(call), call_weight,
+		));
 		assert_eq!(Balances::free_balance(6), 15);
 	});
 }
@@ -390,15 +330,21 @@ fn multisig_2_of_3_as_multi_with_many_calls_works() {
 
 		let call1 = Call::Balances(BalancesCall::transfer(6, 10));
 		let call1_weight = call1.get_dispatch_info().weight;
-		let data1 = call1.encode();
-		let call2 = Call::Balances(BalancesCall::transfer(7, 5));
+				let call2 = Call::Balances(BalancesCall::transfer(7, 5));
 		let call2_weight = call2.get_dispatch_info().weight;
-		let data2 = call2.encode();
-
-		assert_ok!(Multisig::as_multi(Origin::signed(1), 2, vec![2, 3], None, data1.clone(), false, 0));
-		assert_ok!(Multisig::as_multi(Origin::signed(2), 2, vec![1, 3], None, data2.clone(), false, 0));
-		assert_ok!(Multisig::as_multi(Origin::signed(3), 2, vec![1, 2], Some(now()), data1, false, call1_weight));
-		assert_ok!(Multisig::as_multi(Origin::signed(3), 2, vec![1, 2], Some(now()), data2, false, call2_weight));
+		
+		assert_ok!(Multisig::as_multi(
+			Origin::signed(1), 2, vec![2, 3], None, Box::new(call1.clone()), 0,
+		));
+		assert_ok!(Multisig::as_multi(
+			Origin::signed(2), 2, vec![1, 3], None, Box::new(call2.clone()), 0,
+		));
+		assert_ok!(Multisig::as_multi(
+			Origin::signed(3), 2, vec![1, 2], Some(now()), Box::new(call1), call1_weight,
+		));
+		assert_ok!(Multisig::as_multi(
+			Origin::signed(3), 2, vec![1, 2], Some(now()), Box::new(call2), call2_weight,
+		));
 
 		assert_eq!(Balances::free_balance(6), 10);
 		assert_eq!(Balances::free_balance(7), 5);
@@ -415,14 +361,21 @@ fn multisig_2_of_3_cannot_reissue_same_call() {
 
 		let call = Call::Balances(BalancesCall::transfer(6, 10));
 		let call_weight = call.get_dispatch_info().weight;
-		let data = call.encode();
-		let hash = blake2_256(&data);
-		assert_ok!(Multisig::as_multi(Origin::signed(1), 2, vec![2, 3], None, data.clone(), false, 0));
-		assert_ok!(Multisig::as_multi(Origin::signed(2), 2, vec![1, 3], Some(now()), data.clone(), false, call_weight));
+		let hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::as_multi(
+			Origin::signed(1), 2, vec![2, 3], None, Box::new(call.clone()), 0,
+		));
+		assert_ok!(Multisig::as_multi(
+			Origin::signed(2), 2, vec![1, 3], Some(now()), Box::new(call.clone()), call_weight,
+		));
 		assert_eq!(Balances::free_balance(multi), 5);
 
-		assert_ok!(Multisig::as_multi(Origin::signed(1), 2, vec![2, 3], None, data.clone(), false, 0));
-		assert_ok!(Multisig::as_multi(Origin::signed(3), 2, vec![1, 2], Some(now()), data.clone(), false, call_weight));
+		assert_ok!(Multisig::as_multi(
+			Origin::signed(1), 2, vec![2, 3], None, Box::new(call.clone()), 0,
+		));
+		assert_ok!(Multisig::as_multi(
+			Origin::signed(3), 2, vec![1, 2], Some(now()), Box::new(call.clone()), call_weight,
+		));
 
 		let err = DispatchError::from(BalancesError::<Test, _>::InsufficientBalance).stripped();
 		System::assert_last_event(pallet_multisig::Event::MultisigExecuted(3, now(), multi, hash, Err(err)).into());
@@ -432,13 +385,13 @@ fn multisig_2_of_3_cannot_reissue_same_call() {
 #[test]
 fn minimum_threshold_check_works() {
 	new_test_ext().execute_with(|| {
-		let call = Call::Balances(BalancesCall::transfer(6, 15)).encode();
+		let call = Call::Balances(BalancesCall::transfer(6, 15));
 		assert_noop!(
-			Multisig::as_multi(Origin::signed(1), 0, vec![2], None, call.clone(), false, 0),
+			Multisig::as_multi(Origin::signed(1), 0, vec![2], None, Box::new(call.clone()), 0),
 			Error::<Test>::MinimumThreshold,
 		);
 		assert_noop!(
-			Multisig::as_multi(Origin::signed(1), 1, vec![2], None, call.clone(), false, 0),
+			Multisig::as_multi(Origin::signed(1), 1, vec![2], None, Box::new(call.clone()), 0),
 			Error::<Test>::MinimumThreshold,
 		);
 	});
@@ -447,9 +400,11 @@ fn minimum_threshold_check_works() {
 #[test]
 fn too_many_signatories_fails() {
 	new_test_ext().execute_with(|| {
-		let call = Call::Balances(BalancesCall::transfer(6, 15)).encode();
+		let call = Call::Balances(BalancesCall::transfer(6, 15));
 		assert_noop!(
-			Multisig::as_multi(Origin::signed(1), 2, vec![2, 3, 4], None, call.clone(), false, 0),
+			Multisig::as_multi(
+				Origin::signed(1), 2, vec![2, 3, 4], None, Box::new(call.clone()), 0,
+			),
 			Error::<Test>::TooManySignatories,
 		);
 	});
@@ -458,16 +413,20 @@ fn too_many_signatories_fails() {
 #[test]
 fn duplicate_approvals_are_ignored() {
 	new_test_ext().execute_with(|| {
-		let call = Call::Balances(BalancesCall::transfer(6, 15)).encode();
-		let hash = blake2_256(&call);
-		assert_ok!(Multisig::approve_as_multi(Origin::signed(1), 2, vec![2, 3], None, hash.clone(), 0));
+		let call = Call::Balances(BalancesCall::transfer(6, 15));
+		let hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::approve_as_multi(
+			Origin::signed(1), 2, vec![2, 3], None, hash.clone(),
+		));
 		assert_noop!(
-			Multisig::approve_as_multi(Origin::signed(1), 2, vec![2, 3], Some(now()), hash.clone(), 0),
+			Multisig::approve_as_multi(Origin::signed(1), 2, vec![2, 3], Some(now()), hash.clone()),
 			Error::<Test>::AlreadyApproved,
 		);
-		assert_ok!(Multisig::approve_as_multi(Origin::signed(2), 2, vec![1, 3], Some(now()), hash.clone(), 0));
+		assert_ok!(Multisig::approve_as_multi(
+			Origin::signed(2), 2, vec![1, 3], Some(now()), hash.clone(),
+		));
 		assert_noop!(
-			Multisig::approve_as_multi(Origin::signed(3), 2, vec![1, 2], Some(now()), hash.clone(), 0),
+			Multisig::approve_as_multi(Origin::signed(3), 2, vec![1, 2], Some(now()), hash.clone()),
 			Error::<Test>::AlreadyApproved,
 		);
 	});
@@ -481,14 +440,14 @@ fn multisig_1_of_3_works() {
 		assert_ok!(Balances::transfer(Origin::signed(2), multi, 5));
 		assert_ok!(Balances::transfer(Origin::signed(3), multi, 5));
 
-		let call = Call::Balances(BalancesCall::transfer(6, 15)).encode();
-		let hash = blake2_256(&call);
+		let call = Call::Balances(BalancesCall::transfer(6, 15));
+		let hash = blake2_256(&call.encode());
 		assert_noop!(
-			Multisig::approve_as_multi(Origin::signed(1), 1, vec![2, 3], None, hash.clone(), 0),
+			Multisig::approve_as_multi(Origin::signed(1), 1, vec![2, 3], None, hash.clone()),
 			Error::<Test>::MinimumThreshold,
 		);
 		assert_noop!(
-			Multisig::as_multi(Origin::signed(1), 1, vec![2, 3], None, call.clone(), false, 0),
+			Multisig::as_multi(Origin::signed(1), 1, vec![2, 3], None, Box::new(call.clone()), 0),
 			Error::<Test>::MinimumThreshold,
 		);
 		let boxed_call = Box::new(Call::Balances(BalancesCall::transfer(6, 15)));
@@ -518,12 +477,13 @@ fn weight_check_works() {
 		assert_ok!(Balances::transfer(Origin::signed(3), multi, 5));
 
 		let call = Call::Balances(BalancesCall::transfer(6, 15));
-		let data = call.encode();
-		assert_ok!(Multisig::as_multi(Origin::signed(1), 2, vec![2, 3], None, data.clone(), false, 0));
+		assert_ok!(Multisig::as_multi(
+			Origin::signed(1), 2, vec![2, 3], None, Box::new(call.clone()), 0,
+		));
 		assert_eq!(Balances::free_balance(6), 0);
 
 		assert_noop!(
-			Multisig::as_multi(Origin::signed(2), 2, vec![1, 3], Some(now()), data, false, 0),
+			Multisig::as_multi(Origin::signed(2), 2, vec![1, 3], Some(now()), Box::new(call), 0),
 			Error::<Test>::MaxWeightTooLow,
 		);
 	});
@@ -532,7 +492,8 @@ fn weight_check_works() {
 #[test]
 fn multisig_handles_no_preimage_after_all_approve() {
 	// This test checks the situation where everyone approves a multi-sig, but no-one provides the call data.
-	// In the end, any of the multisig callers can approve again with the call data and the call will go through.
+	// The call is never stored, so it can only go through once one of the multisig callers approves
+	// again with the call data.
 	new_test_ext().execute_with(|| {
 		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 3);
 		assert_ok!(Balances::transfer(Origin::signed(1), multi, 5));
@@ -541,14 +502,27 @@ fn multisig_handles_no_preimage_after_all_approve() {
 
 		let call = Call::Balances(BalancesCall::transfer(6, 15));
 		let call_weight = call.get_dispatch_info().weight;
-		let data = call.encode();
-		let hash = blake2_256(&data);
-		assert_ok!(Multisig::approve_as_multi(Origin::signed(1), 3, vec![2, 3], None, hash.clone(), 0));
-		assert_ok!(Multisig::approve_as_multi(Origin::signed(2), 3, vec![1, 3], Some(now()), hash.clone(), 0));
-		assert_ok!(Multisig::approve_as_multi(Origin::signed(3), 3, vec![1, 2], Some(now()), hash.clone(), 0));
+		let hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::approve_as_multi(
+			Origin::signed(1), 3, vec![2, 3], None, hash.clone(),
+		));
+		assert_ok!(Multisig::approve_as_multi(
+			Origin::signed(2), 3, vec![1, 3], Some(now()), hash.clone(),
+		));
+		assert_ok!(Multisig::approve_as_multi(
+			Origin::signed(3), 3, vec![1, 2], Some(now()), hash.clone(),
+		));
 		assert_eq!(Balances::free_balance(6), 0);
+		assert_noop!(
+			Multisig::approve_as_multi(
+				Origin::signed(3), 3, vec![1, 2], Some(now()), hash.clone(),
+			),
+			Error::<Test>::AlreadyApproved,
+		);
 
-		assert_ok!(Multisig::as_multi(Origin::signed(3), 3, vec![1, 2], Some(now()), data, false, call_weight));
+		assert_ok!(Multisig::as_multi(
+			Origin::signed(3), 3, vec![1, 2], Some(now()), Box::new(call), call_weight,
+		));
 		assert_eq!(Balances::free_balance(6), 15);
 	});
 }
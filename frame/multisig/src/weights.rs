@@ -45,13 +45,10 @@ use sp_std::marker::PhantomData;
 pub trait WeightInfo {
 	fn as_multi_threshold_1(z: u32, ) -> Weight;
 	fn as_multi_create(s: u32, z: u32, ) -> Weight;
-	fn as_multi_create_store(s: u32, z: u32, ) -> Weight;
 	fn as_multi_approve(s: u32, z: u32, ) -> Weight;
-	fn as_multi_approve_store(s: u32, z: u32, ) -> Weight;
 	fn as_multi_complete(s: u32, z: u32, ) -> Weight;
 	fn approve_as_multi_create(s: u32, ) -> Weight;
 	fn approve_as_multi_approve(s: u32, ) -> Weight;
-	fn approve_as_multi_complete(s: u32, ) -> Weight;
 	fn cancel_as_multi(s: u32, ) -> Weight;
 	
 }
@@ -71,14 +68,6 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 			
-	}
-	fn as_multi_create_store(s: u32, z: u32, ) -> Weight {
-		(83_175_000 as Weight)
-			.saturating_add((72_000 as Weight).saturating_mul(s as Weight))
-			.saturating_add((3_000 as Weight).saturating_mul(z as Weight))
-			.saturating_add(T::DbWeight::get().reads(3 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
-			
 	}
 	fn as_multi_approve(s: u32, z: u32, ) -> Weight {
 		(43_035_000 as Weight)
@@ -87,14 +76,6 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 			
-	}
-	fn as_multi_approve_store(s: u32, z: u32, ) -> Weight {
-		(75_190_000 as Weight)
-			.saturating_add((127_000 as Weight).saturating_mul(s as Weight))
-			.saturating_add((3_000 as Weight).saturating_mul(z as Weight))
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
-			
 	}
 	fn as_multi_complete(s: u32, z: u32, ) -> Weight {
 		(92_751_000 as Weight)
@@ -117,13 +98,6 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 			
-	}
-	fn approve_as_multi_complete(s: u32, ) -> Weight {
-		(163_098_000 as Weight)
-			.saturating_add((276_000 as Weight).saturating_mul(s as Weight))
-			.saturating_add(T::DbWeight::get().reads(3 as Weight))
-			.saturating_add(T::DbWeight::get().writes(3 as Weight))
-			
 	}
 	fn cancel_as_multi(s: u32, ) -> Weight {
 		(115_731_000 as Weight)
@@ -149,14 +123,6 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 			
-	}
-	fn as_multi_create_store(s: u32, z: u32, ) -> Weight {
-		(83_175_000 as Weight)
-			.saturating_add((72_000 as Weight).saturating_mul(s as Weight))
-			.saturating_add((3_000 as Weight).saturating_mul(z as Weight))
-			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
-			
 	}
 	fn as_multi_approve(s: u32, z: u32, ) -> Weight {
 		(43_035_000 as Weight)
@@ -165,14 +131,6 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 			
-	}
-	fn as_multi_approve_store(s: u32, z: u32, ) -> Weight {
-		(75_190_000 as Weight)
-			.saturating_add((127_000 as Weight).saturating_mul(s as Weight))
-			.saturating_add((3_000 as Weight).saturating_mul(z as Weight))
-			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
-			
 	}
 	fn as_multi_complete(s: u32, z: u32, ) -> Weight {
 		(92_751_000 as Weight)
@@ -195,13 +153,6 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 			
-	}
-	fn approve_as_multi_complete(s: u32, ) -> Weight {
-		(163_098_000 as Weight)
-			.saturating_add((276_000 as Weight).saturating_mul(s as Weight))
-			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
-			
 	}
 	fn cancel_as_multi(s: u32, ) -> Weight {
 		(115_731_000 as Weight)
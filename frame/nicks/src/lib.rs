@@ -304,6 +304,10 @@ mod tests {
 	}
 	impl pallet_balances::Config for Test {
 		type MaxLocks = ();
+		type HoldIdentifier = ();
+		type FreezeIdentifier = ();
+		type MaxHolds = frame_support::traits::ConstU32<1>;
+		type MaxFreezes = frame_support::traits::ConstU32<1>;
 		type Balance = u64;
 		type Event = Event;
 		type DustRemoval = ();
@@ -0,0 +1,266 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Nomination Pools pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{benchmarks, account, impl_benchmark_test_suite};
+use frame_support::StorageValue as _;
+use pallet_staking::testing_utils::{add_slashing_spans, create_validators};
+
+use crate::Pallet as Pools;
+
+const SEED: u32 = 0;
+const MAX_SPANS: u32 = 100;
+
+/// The bond of the depositors and members created here, enough to create or join a pool.
+fn min_bond<T: Config>() -> BalanceOf<T> {
+	CurrencyOf::<T>::minimum_balance() * 10u32.into()
+}
+
+/// Set the minimum bonds to `min_bond`, and remove the maximum numbers of pools and members.
+fn set_limits<T: Config>() {
+	MinJoinBond::<T>::put(min_bond::<T>());
+	MinCreateBond::<T>::put(min_bond::<T>());
+	MaxPools::<T>::kill();
+	MaxPoolMembers::<T>::kill();
+}
+
+/// Create an account with enough free balance to bond `min_bond` a few times.
+fn funded_account<T: Config>(name: &'static str) -> T::AccountId {
+	let who = account(name, 0, SEED);
+	CurrencyOf::<T>::make_free_balance_be(&who, min_bond::<T>() * 10u32.into());
+	who
+}
+
+/// Create a pool, whose depositor has all the roles.
+///
+/// Returns the depositor and the pool.
+fn create_pool<T: Config>() -> Result<(T::AccountId, PoolId), &'static str> {
+	set_limits::<T>();
+	let depositor = funded_account::<T>("depositor");
+	Pools::<T>::create(
+		RawOrigin::Signed(depositor.clone()).into(),
+		min_bond::<T>(),
+		depositor.clone(),
+		depositor.clone(),
+		depositor.clone(),
+	)?;
+	Ok((depositor, LastPoolId::<T>::get()))
+}
+
+/// Create a member of pool `pool_id`, bonding `min_bond`.
+fn join_pool<T: Config>(pool_id: PoolId) -> Result<T::AccountId, &'static str> {
+	let member = funded_account::<T>("member");
+	Pools::<T>::join(RawOrigin::Signed(member.clone()).into(), min_bond::<T>(), pool_id)?;
+	Ok(member)
+}
+
+/// Pay `min_bond` of staking rewards to pool `pool_id`.
+fn reward_pool<T: Config>(pool_id: PoolId) {
+	let reward_account = Pools::<T>::create_account(pool_id, AccountType::Reward);
+	let _ = CurrencyOf::<T>::deposit_creating(&reward_account, min_bond::<T>());
+}
+
+/// Give the bonded account of pool `pool_id` `spans` slashing spans, and unlock all its
+/// unbonding funds.
+fn unlock_with_spans<T: Config>(pool_id: PoolId, spans: u32) {
+	let bonded_account = Pools::<T>::create_account(pool_id, AccountType::Bonded);
+	add_slashing_spans::<T>(&bonded_account, spans);
+	pallet_staking::CurrentEra::put(EraIndex::max_value());
+}
+
+/// Create `n` validators.
+fn validators<T: Config>(n: u32) -> Result<Vec<T::AccountId>, &'static str> {
+	create_validators::<T>(n, 100)?
+		.into_iter()
+		.map(|validator| T::Lookup::lookup(validator).map_err(|_| "validator not found"))
+		.collect()
+}
+
+benchmarks! {
+	join {
+		let (_, pool_id) = create_pool::<T>()?;
+		let joiner = funded_account::<T>("joiner");
+	}: _(RawOrigin::Signed(joiner.clone()), min_bond::<T>(), pool_id)
+	verify {
+		assert_eq!(PoolMembers::<T>::get(&joiner).map(|member| member.pool_id), Some(pool_id));
+	}
+
+	// The pending rewards are paid out too.
+	bond_extra_transfer {
+		let (_, pool_id) = create_pool::<T>()?;
+		let member = join_pool::<T>(pool_id)?;
+		reward_pool::<T>(pool_id);
+	}: bond_extra(RawOrigin::Signed(member.clone()), BondExtra::FreeBalance(min_bond::<T>()))
+	verify {
+		let member = PoolMembers::<T>::get(&member).ok_or("member not found")?;
+		assert!(member.points > min_bond::<T>());
+	}
+
+	bond_extra_reward {
+		let (_, pool_id) = create_pool::<T>()?;
+		let member = join_pool::<T>(pool_id)?;
+		reward_pool::<T>(pool_id);
+	}: bond_extra(RawOrigin::Signed(member.clone()), BondExtra::Rewards)
+	verify {
+		let member = PoolMembers::<T>::get(&member).ok_or("member not found")?;
+		assert!(member.points > min_bond::<T>());
+	}
+
+	claim_payout {
+		let (_, pool_id) = create_pool::<T>()?;
+		let member = join_pool::<T>(pool_id)?;
+		reward_pool::<T>(pool_id);
+		let free_balance = CurrencyOf::<T>::free_balance(&member);
+	}: _(RawOrigin::Signed(member.clone()))
+	verify {
+		assert!(CurrencyOf::<T>::free_balance(&member) > free_balance);
+	}
+
+	// The pending rewards are paid out too.
+	unbond {
+		let (_, pool_id) = create_pool::<T>()?;
+		let member = join_pool::<T>(pool_id)?;
+		reward_pool::<T>(pool_id);
+	}: _(RawOrigin::Signed(member.clone()), member.clone(), min_bond::<T>())
+	verify {
+		let member = PoolMembers::<T>::get(&member).ok_or("member not found")?;
+		assert!(member.points.is_zero());
+		assert_eq!(member.unbonding_eras.len(), 1);
+	}
+
+	pool_withdraw_unbonded {
+		let s in 0 .. MAX_SPANS;
+		let (_, pool_id) = create_pool::<T>()?;
+		let member = join_pool::<T>(pool_id)?;
+		Pools::<T>::unbond(
+			RawOrigin::Signed(member.clone()).into(),
+			member.clone(),
+			min_bond::<T>(),
+		)?;
+		unlock_with_spans::<T>(pool_id, s);
+	}: _(RawOrigin::Signed(member), pool_id, s)
+	verify {
+		let bonded_account = Pools::<T>::create_account(pool_id, AccountType::Bonded);
+		let ledger = pallet_staking::Module::<T>::ledger(&bonded_account)
+			.ok_or("ledger not found")?;
+		assert!(ledger.unlocking.is_empty());
+	}
+
+	// A member which isn't the depositor withdraws its funds and leaves the pool.
+	withdraw_unbonded_update {
+		let s in 0 .. MAX_SPANS;
+		let (_, pool_id) = create_pool::<T>()?;
+		let member = join_pool::<T>(pool_id)?;
+		Pools::<T>::unbond(
+			RawOrigin::Signed(member.clone()).into(),
+			member.clone(),
+			min_bond::<T>(),
+		)?;
+		unlock_with_spans::<T>(pool_id, s);
+	}: withdraw_unbonded(RawOrigin::Signed(member.clone()), member.clone(), s)
+	verify {
+		assert!(!PoolMembers::<T>::contains_key(&member));
+		assert!(BondedPools::<T>::contains_key(pool_id));
+	}
+
+	// The depositor withdraws its funds last, which kills the stash and dissolves the pool.
+	withdraw_unbonded_kill {
+		let s in 0 .. MAX_SPANS;
+		let (depositor, pool_id) = create_pool::<T>()?;
+		let origin = RawOrigin::Signed(depositor.clone());
+		Pools::<T>::set_state(origin.clone().into(), pool_id, PoolState::Destroying)?;
+		Pools::<T>::unbond(origin.into(), depositor.clone(), min_bond::<T>())?;
+		unlock_with_spans::<T>(pool_id, s);
+	}: withdraw_unbonded(RawOrigin::Signed(depositor.clone()), depositor.clone(), s)
+	verify {
+		assert!(!PoolMembers::<T>::contains_key(&depositor));
+		assert!(!BondedPools::<T>::contains_key(pool_id));
+	}
+
+	create {
+		set_limits::<T>();
+		let depositor = funded_account::<T>("depositor");
+		let role = depositor.clone();
+	}: _(RawOrigin::Signed(depositor.clone()), min_bond::<T>(), role.clone(), role.clone(), role)
+	verify {
+		let pool_id = LastPoolId::<T>::get();
+		assert_eq!(PoolMembers::<T>::get(&depositor).map(|member| member.pool_id), Some(pool_id));
+	}
+
+	nominate {
+		let n in 1 .. <T as pallet_staking::Config>::MAX_NOMINATIONS;
+		let (depositor, pool_id) = create_pool::<T>()?;
+		let validators = validators::<T>(n)?;
+	}: _(RawOrigin::Signed(depositor), pool_id, validators)
+	verify {
+		let bonded_account = Pools::<T>::create_account(pool_id, AccountType::Bonded);
+		assert!(pallet_staking::Nominators::<T>::contains_key(&bonded_account));
+	}
+
+	set_state {
+		let (depositor, pool_id) = create_pool::<T>()?;
+	}: _(RawOrigin::Signed(depositor), pool_id, PoolState::Blocked)
+	verify {
+		let pool = BondedPools::<T>::get(pool_id).ok_or("pool not found")?;
+		assert_eq!(pool.state, PoolState::Blocked);
+	}
+
+	set_configs {
+		let min_bond = min_bond::<T>();
+	}: _(RawOrigin::Root, min_bond, min_bond, Some(16), Some(16 * 32))
+	verify {
+		assert_eq!(MinJoinBond::<T>::get(), min_bond);
+		assert_eq!(MaxPoolMembers::<T>::get(), Some(16 * 32));
+	}
+
+	update_roles {
+		let (depositor, pool_id) = create_pool::<T>()?;
+		let role: T::AccountId = account("role", 0, SEED);
+	}: _(
+		RawOrigin::Signed(depositor),
+		pool_id,
+		Some(role.clone()),
+		Some(role.clone()),
+		Some(role.clone())
+	)
+	verify {
+		let pool = BondedPools::<T>::get(pool_id).ok_or("pool not found")?;
+		assert_eq!(pool.roles.root, role);
+		assert_eq!(pool.roles.state_toggler, role);
+	}
+
+	chill {
+		let (depositor, pool_id) = create_pool::<T>()?;
+		let validators = validators::<T>(1)?;
+		let origin = RawOrigin::Signed(depositor.clone());
+		Pools::<T>::nominate(origin.into(), pool_id, validators)?;
+	}: _(RawOrigin::Signed(depositor), pool_id)
+	verify {
+		let bonded_account = Pools::<T>::create_account(pool_id, AccountType::Bonded);
+		assert!(!pallet_staking::Nominators::<T>::contains_key(&bonded_account));
+	}
+}
+
+impl_benchmark_test_suite!(
+	Pools,
+	crate::mock::new_test_ext(),
+	crate::mock::Test,
+);
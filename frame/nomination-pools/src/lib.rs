@@ -0,0 +1,1288 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Nomination Pools Pallet
+//!
+//! A pallet which lets accounts pool their funds to nominate together.
+//!
+//! ## Overview
+//!
+//! A pool is a staker of the staking pallet, whose stash and controller is the _bonded account_
+//! of the pool. Its members bond funds into the pool and get _points_ for them, which they can
+//! later redeem against their share of the funds of the pool, including the slashes it suffered.
+//!
+//! ### Joining
+//!
+//! Anyone can create a pool with `create`, becoming its _depositor_. Accounts join an open pool
+//! with `join`, and add funds with `bond_extra`. An account can be a member of a single pool.
+//!
+//! ### Rewards
+//!
+//! The staking rewards of a pool are paid to its _reward account_ and are shared between the
+//! members according to their points, at the time the rewards were received. The pool keeps a
+//! _reward counter_, i.e. the rewards earned per point since the creation of the pool; the pending
+//! rewards of a member are its points times the growth of the reward counter since it last
+//! claimed. Members claim their rewards with `claim_payout`, or bond them with `bond_extra`. The
+//! pending rewards of a member are also paid out whenever its points change.
+//!
+//! ### Leaving
+//!
+//! A member unbonds points with `unbond`. The funds of these points are moved to the _unbonding
+//! pool_ of the era in which they will be unlocked, in exchange for points of that unbonding pool,
+//! and withdrawn with `withdraw_unbonded` once they are unlocked. Unbonding pools which unlocked
+//! more than `PostUnbondingPoolsWindow` eras ago are merged into a single pool, to keep their
+//! number bounded.
+//!
+//! ### Slashing
+//!
+//! The pallet must be the [`OnStakerSlash`] handler of the staking pallet. The slashes of the
+//! bonded funds are shared by all the points of the bonded pool, and the slashes of the
+//! unbonding funds by the points of the corresponding unbonding pools.
+//!
+//! ### Roles
+//!
+//! - The _depositor_ created the pool. It can only fully unbond once it is the last member of a
+//!   pool being destroyed, and the pool is dissolved once it withdrew its funds.
+//! - The _root_ can change the roles, and do everything the other roles can.
+//! - The _nominator_ sets the nominations of the pool with `nominate`, and stops them with `chill`.
+//! - The _state toggler_ changes the [`PoolState`] with `set_state`. Once a pool is `Blocked`, it
+//!   can unbond and withdraw the funds of the members; once it is `Destroying`, anyone can.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! - `join`: Join a pool.
+//! - `bond_extra`: Bond more funds, or the pending rewards, into the pool.
+//! - `claim_payout`: Claim the pending rewards.
+//! - `unbond`: Unbond the funds of some points of a member.
+//! - `pool_withdraw_unbonded`: Withdraw the unlocked funds of a pool from the staking pallet.
+//! - `withdraw_unbonded`: Withdraw the unlocked funds of a member.
+//! - `create`: Create a pool.
+//! - `nominate`: Set the nominations of a pool.
+//! - `set_state`: Set the state of a pool.
+//! - `set_configs`: Set the limits of the pallet.
+//! - `update_roles`: Change the roles of a pool.
+//! - `chill`: Stop the nominations of a pool.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_std::{
+	prelude::*, collections::btree_map::BTreeMap, marker::PhantomData, ops::{Deref, DerefMut},
+};
+use codec::{Encode, Decode};
+use sp_runtime::{
+	DispatchError, FixedPointNumber, FixedU128, RuntimeDebug, SaturatedConversion,
+	helpers_128bit::multiply_by_rational,
+	traits::{AccountIdConversion, Saturating, StaticLookup, Zero},
+};
+use frame_support::{
+	CloneNoBound, DefaultNoBound, EqNoBound, PalletId, PartialEqNoBound, RuntimeDebugNoBound,
+	ensure,
+	dispatch::DispatchResult,
+	storage::bounded_btree_map::BoundedBTreeMap,
+	traits::{Currency, ExistenceRequirement, Get},
+};
+use frame_system::RawOrigin;
+use pallet_staking::RewardDestination;
+use sp_staking::{EraIndex, OnStakerSlash};
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+mod benchmarking;
+pub mod weights;
+
+/// The identifier of a pool.
+pub type PoolId = u32;
+
+pub type BalanceOf<T> = pallet_staking::BalanceOf<T>;
+
+type CurrencyOf<T> = <T as pallet_staking::Config>::Currency;
+type StakingOf<T> = pallet_staking::Module<T>;
+
+/// The maximum number of unbonding pools of a pool, and of unbonding eras of a member.
+pub struct TotalUnbondingPools<T: Config>(PhantomData<T>);
+
+impl<T: Config> Get<u32> for TotalUnbondingPools<T> {
+	fn get() -> u32 {
+		<T as pallet_staking::Config>::BondingDuration::get() + T::PostUnbondingPoolsWindow::get()
+	}
+}
+
+/// The state of a pool.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum PoolState {
+	/// Anyone can join the pool.
+	Open,
+	/// No one can join the pool, and the root and state toggler can kick its members.
+	Blocked,
+	/// The pool is being dissolved: anyone can kick its members, and the depositor can leave
+	/// once it is the last member.
+	Destroying,
+}
+
+/// The roles of a pool.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct PoolRoles<AccountId> {
+	/// The creator of the pool, which bonded the first funds.
+	pub depositor: AccountId,
+	/// Can change the roles, and do everything the other roles can.
+	pub root: AccountId,
+	/// Can set and stop the nominations of the pool.
+	pub nominator: AccountId,
+	/// Can change the state of the pool.
+	pub state_toggler: AccountId,
+}
+
+/// How to bond more funds into a pool.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum BondExtra<Balance> {
+	/// Bond the given amount from the free balance of the member.
+	FreeBalance(Balance),
+	/// Bond the pending rewards of the member.
+	Rewards,
+}
+
+/// The kind of account of a pool.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+enum AccountType {
+	Bonded,
+	Reward,
+}
+
+/// How the funds of a pool are bonded in the staking pallet.
+enum BondType {
+	/// The pool is being created and isn't bonded yet.
+	Create,
+	/// The pool is bonded already.
+	Later,
+}
+
+/// The number of points to issue for `new_funds`, added to a pool of `current_balance` funds and
+/// `current_points` points.
+fn points_to_issue<T: Config>(
+	current_balance: BalanceOf<T>,
+	current_points: BalanceOf<T>,
+	new_funds: BalanceOf<T>,
+) -> BalanceOf<T> {
+	match (current_balance.is_zero(), current_points.is_zero()) {
+		(_, true) => new_funds,
+		// the pool was slashed to nothing: its points are worth nothing either.
+		(true, false) => new_funds.saturating_mul(current_points),
+		(false, false) => mul_div::<T>(new_funds, current_points, current_balance),
+	}
+}
+
+/// The funds to unbond for `points`, out of a pool of `current_balance` funds and
+/// `current_points` points.
+fn balance_to_unbond<T: Config>(
+	current_balance: BalanceOf<T>,
+	current_points: BalanceOf<T>,
+	points: BalanceOf<T>,
+) -> BalanceOf<T> {
+	if current_balance.is_zero() || current_points.is_zero() || points.is_zero() {
+		return Zero::zero();
+	}
+	mul_div::<T>(current_balance, points, current_points)
+}
+
+/// `a * b / c`, rounded down.
+fn mul_div<T: Config>(a: BalanceOf<T>, b: BalanceOf<T>, c: BalanceOf<T>) -> BalanceOf<T> {
+	multiply_by_rational(a.saturated_into(), b.saturated_into(), c.saturated_into())
+		.map(|result| result.saturated_into())
+		.unwrap_or_else(|_| Zero::zero())
+}
+
+/// A member of a pool.
+#[derive(Encode, Decode, CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound)]
+pub struct PoolMember<T: Config> {
+	/// The pool of the member.
+	pub pool_id: PoolId,
+	/// The points of the member in the bonded pool.
+	pub points: BalanceOf<T>,
+	/// The reward counter of the pool when the member last claimed its rewards.
+	pub last_recorded_reward_counter: FixedU128,
+	/// The points of the member in the unbonding pools, by unlocking era.
+	pub unbonding_eras: BoundedBTreeMap<EraIndex, BalanceOf<T>, TotalUnbondingPools<T>>,
+}
+
+impl<T: Config> PoolMember<T> {
+	/// The rewards earned by the points of the member, given the `current_reward_counter` of the
+	/// pool.
+	fn pending_rewards(&self, current_reward_counter: FixedU128) -> BalanceOf<T> {
+		current_reward_counter
+			.saturating_sub(self.last_recorded_reward_counter)
+			.saturating_mul_int(self.points.saturated_into::<u128>())
+			.saturated_into()
+	}
+
+	/// Remove the unbonding eras unlocked at `current_era`, returning them.
+	fn withdraw_unlocked(&mut self, current_era: EraIndex) -> BTreeMap<EraIndex, BalanceOf<T>> {
+		let unlocked = self.unbonding_eras
+			.iter()
+			.filter(|(era, _)| **era <= current_era)
+			.map(|(era, points)| (*era, *points))
+			.collect::<BTreeMap<_, _>>();
+		for era in unlocked.keys() {
+			self.unbonding_eras.remove(era);
+		}
+		unlocked
+	}
+
+	/// Returns true if the member has neither bonded nor unbonding points left.
+	fn is_empty(&self) -> bool {
+		self.points.is_zero() && self.unbonding_eras.is_empty()
+	}
+}
+
+/// A pool, as stored.
+#[derive(Encode, Decode, CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound)]
+pub struct BondedPoolInner<T: Config> {
+	/// The total points of the members.
+	pub points: BalanceOf<T>,
+	/// The state of the pool.
+	pub state: PoolState,
+	/// The number of members, including the ones which are only unbonding.
+	pub member_counter: u32,
+	/// The roles of the pool.
+	pub roles: PoolRoles<T::AccountId>,
+}
+
+/// A pool, with its identifier.
+#[derive(CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound)]
+pub struct BondedPool<T: Config> {
+	/// The identifier of the pool.
+	pub id: PoolId,
+	/// The pool.
+	pub inner: BondedPoolInner<T>,
+}
+
+impl<T: Config> Deref for BondedPool<T> {
+	type Target = BondedPoolInner<T>;
+
+	fn deref(&self) -> &Self::Target {
+		&self.inner
+	}
+}
+
+impl<T: Config> DerefMut for BondedPool<T> {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		&mut self.inner
+	}
+}
+
+impl<T: Config> BondedPool<T> {
+	/// A new open pool, with its depositor as only member.
+	fn new(id: PoolId, roles: PoolRoles<T::AccountId>) -> Self {
+		let inner = BondedPoolInner {
+			points: Zero::zero(),
+			state: PoolState::Open,
+			member_counter: 1,
+			roles,
+		};
+		Self { id, inner }
+	}
+
+	/// Get the pool `id`.
+	pub fn get(id: PoolId) -> Option<Self> {
+		BondedPools::<T>::get(id).map(|inner| Self { id, inner })
+	}
+
+	/// Write the pool to storage.
+	fn put(self) {
+		BondedPools::<T>::insert(self.id, self.inner);
+	}
+
+	/// The account bonding the funds of the pool.
+	pub fn bonded_account(&self) -> T::AccountId {
+		Pallet::<T>::create_account(self.id, AccountType::Bonded)
+	}
+
+	/// The account receiving the staking rewards of the pool.
+	pub fn reward_account(&self) -> T::AccountId {
+		Pallet::<T>::create_account(self.id, AccountType::Reward)
+	}
+
+	/// The active funds of the pool in the staking pallet.
+	pub fn balance(&self) -> BalanceOf<T> {
+		StakingOf::<T>::ledger(&self.bonded_account())
+			.map(|ledger| ledger.active)
+			.unwrap_or_else(Zero::zero)
+	}
+
+	/// The funds of `points` of the pool.
+	pub fn points_to_balance(&self, points: BalanceOf<T>) -> BalanceOf<T> {
+		balance_to_unbond::<T>(self.balance(), self.points, points)
+	}
+
+	fn is_root(&self, who: &T::AccountId) -> bool {
+		*who == self.roles.root
+	}
+
+	fn can_nominate(&self, who: &T::AccountId) -> bool {
+		self.is_root(who) || *who == self.roles.nominator
+	}
+
+	fn can_toggle_state(&self, who: &T::AccountId) -> bool {
+		self.is_root(who) || *who == self.roles.state_toggler
+	}
+
+	/// Returns true if `who` can unbond and withdraw the funds of other members.
+	fn can_kick(&self, who: &T::AccountId) -> bool {
+		match self.state {
+			PoolState::Open => false,
+			PoolState::Blocked => self.can_toggle_state(who),
+			PoolState::Destroying => true,
+		}
+	}
+
+	fn ok_to_join(&self) -> DispatchResult {
+		ensure!(self.state == PoolState::Open, Error::<T>::NotOpen);
+		if let Some(max) = MaxPoolMembers::<T>::get() {
+			ensure!(PoolMembers::<T>::count() < max, Error::<T>::MaxPoolMembers);
+		}
+		Ok(())
+	}
+
+	/// Check that `caller` can unbond `unbonding_points` of `target`.
+	fn ok_to_unbond_with(
+		&self,
+		caller: &T::AccountId,
+		target_account: &T::AccountId,
+		target_member: &PoolMember<T>,
+		unbonding_points: BalanceOf<T>,
+	) -> DispatchResult {
+		let is_permissioned = caller == target_account;
+		let is_full_unbond = unbonding_points == target_member.points;
+		let remaining =
+			self.points_to_balance(target_member.points.saturating_sub(unbonding_points));
+
+		if *target_account == self.roles.depositor {
+			if is_full_unbond {
+				// the depositor leaves last, which anyone can trigger.
+				ensure!(self.state == PoolState::Destroying, Error::<T>::NotDestroying);
+				ensure!(self.member_counter == 1, Error::<T>::NotOnlyPoolMember);
+			} else {
+				ensure!(is_permissioned, Error::<T>::DoesNotHavePermission);
+				ensure!(remaining >= MinCreateBond::<T>::get(), Error::<T>::MinimumBondNotMet);
+			}
+		} else if is_permissioned {
+			ensure!(
+				is_full_unbond || remaining >= MinJoinBond::<T>::get(),
+				Error::<T>::MinimumBondNotMet,
+			);
+		} else {
+			ensure!(self.can_kick(caller), Error::<T>::NotKickerOrDestroying);
+			ensure!(is_full_unbond, Error::<T>::PartialUnbondNotAllowedPermissionlessly);
+		}
+		Ok(())
+	}
+
+	/// Issue points for `amount` transferred from `who`, and bond it.
+	///
+	/// Returns the points issued.
+	fn try_bond_funds(
+		&mut self,
+		who: &T::AccountId,
+		amount: BalanceOf<T>,
+		ty: BondType,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		let bonded_account = self.bonded_account();
+		// the points are issued against the funds bonded before this one.
+		let points_issued = points_to_issue::<T>(self.balance(), self.points, amount);
+
+		CurrencyOf::<T>::transfer(
+			who,
+			&bonded_account,
+			amount,
+			ExistenceRequirement::KeepAlive,
+		)?;
+		let origin = RawOrigin::Signed(bonded_account.clone());
+		match ty {
+			BondType::Create => StakingOf::<T>::bond(
+				origin.into(),
+				T::Lookup::unlookup(bonded_account),
+				amount,
+				RewardDestination::Account(self.reward_account()),
+			)?,
+			BondType::Later => StakingOf::<T>::bond_extra(origin.into(), amount)?,
+		}
+
+		self.points = self.points.saturating_add(points_issued);
+		Ok(points_issued)
+	}
+
+	/// Remove `points` from the pool, returning the funds to unbond for them.
+	fn dissolve(&mut self, points: BalanceOf<T>) -> BalanceOf<T> {
+		let balance = self.points_to_balance(points);
+		self.points = self.points.saturating_sub(points);
+		balance
+	}
+}
+
+/// The reward accounting of a pool.
+#[derive(
+	Encode, Decode, CloneNoBound, DefaultNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound,
+)]
+pub struct RewardPool<T: Config> {
+	/// The reward counter of the pool when it was last recorded.
+	pub last_recorded_reward_counter: FixedU128,
+	/// The total rewards received by the pool when the reward counter was last recorded.
+	pub last_recorded_total_payouts: BalanceOf<T>,
+	/// The total rewards claimed by the members so far.
+	pub total_rewards_claimed: BalanceOf<T>,
+}
+
+impl<T: Config> RewardPool<T> {
+	/// The rewards which can be paid from the reward account of pool `id`.
+	fn balance(id: PoolId) -> BalanceOf<T> {
+		let reward_account = Pallet::<T>::create_account(id, AccountType::Reward);
+		CurrencyOf::<T>::free_balance(&reward_account)
+			.saturating_sub(CurrencyOf::<T>::minimum_balance())
+	}
+
+	/// The current reward counter of pool `id`, with `bonded_points` in the bonded pool.
+	fn current_reward_counter(&self, id: PoolId, bonded_points: BalanceOf<T>) -> FixedU128 {
+		let total_payouts = Self::balance(id).saturating_add(self.total_rewards_claimed);
+		let new_payouts = total_payouts.saturating_sub(self.last_recorded_total_payouts);
+		if bonded_points.is_zero() {
+			return self.last_recorded_reward_counter;
+		}
+		let new_rewards_per_point = FixedU128::saturating_from_rational(
+			new_payouts.saturated_into::<u128>(),
+			bonded_points.saturated_into::<u128>(),
+		);
+		self.last_recorded_reward_counter.saturating_add(new_rewards_per_point)
+	}
+
+	/// Record the current reward counter, before the points of the bonded pool change.
+	///
+	/// Nothing is recorded while the bonded pool has no points, so the rewards received in the
+	/// meantime are shared by the points of the next record instead of being lost.
+	fn update_records(&mut self, id: PoolId, bonded_points: BalanceOf<T>) {
+		if bonded_points.is_zero() {
+			return;
+		}
+		self.last_recorded_reward_counter = self.current_reward_counter(id, bonded_points);
+		self.last_recorded_total_payouts =
+			Self::balance(id).saturating_add(self.total_rewards_claimed);
+	}
+}
+
+/// A pool of unbonding funds, all unlocked in the same era.
+#[derive(
+	Encode, Decode, CloneNoBound, DefaultNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound,
+)]
+pub struct UnbondPool<T: Config> {
+	/// The total points of the members in the pool.
+	pub points: BalanceOf<T>,
+	/// The funds of the pool.
+	pub balance: BalanceOf<T>,
+}
+
+impl<T: Config> UnbondPool<T> {
+	/// Add `new_funds` to the pool, returning the points issued for them.
+	fn issue(&mut self, new_funds: BalanceOf<T>) -> BalanceOf<T> {
+		let points = points_to_issue::<T>(self.balance, self.points, new_funds);
+		self.points = self.points.saturating_add(points);
+		self.balance = self.balance.saturating_add(new_funds);
+		points
+	}
+
+	/// Remove `points` from the pool, returning their funds.
+	fn dissolve(&mut self, points: BalanceOf<T>) -> BalanceOf<T> {
+		let balance = balance_to_unbond::<T>(self.balance, self.points, points);
+		self.points = self.points.saturating_sub(points);
+		self.balance = self.balance.saturating_sub(balance);
+		balance
+	}
+}
+
+/// The unbonding pools of a pool.
+#[derive(
+	Encode, Decode, CloneNoBound, DefaultNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound,
+)]
+pub struct SubPools<T: Config> {
+	/// The unbonding pools merged together once they are old enough.
+	pub no_era: UnbondPool<T>,
+	/// The unbonding pools, by unlocking era.
+	pub with_era: BoundedBTreeMap<EraIndex, UnbondPool<T>, TotalUnbondingPools<T>>,
+}
+
+impl<T: Config> SubPools<T> {
+	/// Merge the unbonding pools unlocked more than `PostUnbondingPoolsWindow` eras before
+	/// `current_era` into `no_era`.
+	fn maybe_merge_pools(mut self, current_era: EraIndex) -> Self {
+		let window = T::PostUnbondingPoolsWindow::get();
+		let newest_era_to_merge = match current_era.checked_sub(window) {
+			Some(era) => era,
+			None => return self,
+		};
+		let eras_to_merge = self.with_era
+			.keys()
+			.filter(|era| **era <= newest_era_to_merge)
+			.copied()
+			.collect::<Vec<_>>();
+		for era in eras_to_merge {
+			if let Some(pool) = self.with_era.remove(&era) {
+				self.no_era.points = self.no_era.points.saturating_add(pool.points);
+				self.no_era.balance = self.no_era.balance.saturating_add(pool.balance);
+			}
+		}
+		self
+	}
+
+	/// The unbonding pool of `era`, or `no_era` if it was merged already.
+	fn pool_of(&mut self, era: EraIndex) -> &mut UnbondPool<T> {
+		match self.with_era.get_mut(&era) {
+			Some(pool) => pool,
+			None => &mut self.no_era,
+		}
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{pallet_prelude::*, transactional};
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_staking::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+
+		/// The identifier from which the accounts of the pools are derived.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The number of eras an unbonding pool is kept separate after its funds were unlocked.
+		///
+		/// The older unbonding pools are merged together, and the slashes of their eras are not
+		/// accounted to them anymore.
+		#[pallet::constant]
+		type PostUnbondingPoolsWindow: Get<u32>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The minimum funds to join a pool.
+	#[pallet::storage]
+	pub type MinJoinBond<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	/// The minimum funds to create a pool, which the depositor must keep bonded until the pool is
+	/// destroyed.
+	#[pallet::storage]
+	pub type MinCreateBond<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	/// The maximum number of pools, if any.
+	#[pallet::storage]
+	pub type MaxPools<T> = StorageValue<_, u32>;
+
+	/// The maximum number of members of all the pools, if any.
+	#[pallet::storage]
+	pub type MaxPoolMembers<T> = StorageValue<_, u32>;
+
+	/// The members of the pools.
+	#[pallet::storage]
+	pub type PoolMembers<T: Config> =
+		CountedStorageMap<_, Twox64Concat, T::AccountId, PoolMember<T>>;
+
+	/// The pools.
+	#[pallet::storage]
+	pub type BondedPools<T: Config> =
+		CountedStorageMap<_, Twox64Concat, PoolId, BondedPoolInner<T>>;
+
+	/// The reward accounting of the pools.
+	#[pallet::storage]
+	pub type RewardPools<T: Config> = StorageMap<_, Twox64Concat, PoolId, RewardPool<T>>;
+
+	/// The unbonding pools of the pools.
+	#[pallet::storage]
+	pub type SubPoolsStorage<T: Config> = StorageMap<_, Twox64Concat, PoolId, SubPools<T>>;
+
+	/// The identifier of the last pool created.
+	#[pallet::storage]
+	pub type LastPoolId<T> = StorageValue<_, PoolId, ValueQuery>;
+
+	/// The pools, by bonded account.
+	#[pallet::storage]
+	pub type ReversePoolIdLookup<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, PoolId>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		pub min_join_bond: BalanceOf<T>,
+		pub min_create_bond: BalanceOf<T>,
+		pub max_pools: Option<u32>,
+		pub max_pool_members: Option<u32>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self {
+				min_join_bond: Zero::zero(),
+				min_create_bond: Zero::zero(),
+				max_pools: Some(16),
+				max_pool_members: Some(16 * 32),
+			}
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			MinJoinBond::<T>::put(self.min_join_bond);
+			MinCreateBond::<T>::put(self.min_create_bond);
+			MaxPools::<T>::set(self.max_pools);
+			MaxPoolMembers::<T>::set(self.max_pool_members);
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::metadata(T::AccountId = "AccountId", BalanceOf<T> = "Balance")]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A pool was created. \[depositor, pool_id\]
+		Created(T::AccountId, PoolId),
+		/// A member bonded funds into a pool. \[member, pool_id, bonded, joined\]
+		Bonded(T::AccountId, PoolId, BalanceOf<T>, bool),
+		/// The rewards of a member were paid out. \[member, pool_id, payout\]
+		PaidOut(T::AccountId, PoolId, BalanceOf<T>),
+		/// A member unbonded some of its points. \[member, pool_id, balance, points\]
+		Unbonded(T::AccountId, PoolId, BalanceOf<T>, BalanceOf<T>),
+		/// A member withdrew its unlocked funds, for the given points of the unbonding pools.
+		/// \[member, pool_id, balance, points\]
+		Withdrawn(T::AccountId, PoolId, BalanceOf<T>, BalanceOf<T>),
+		/// A member withdrew all its funds and left its pool. \[pool_id, member\]
+		MemberRemoved(PoolId, T::AccountId),
+		/// A pool was dissolved. \[pool_id\]
+		Destroyed(PoolId),
+		/// The state of a pool changed. \[pool_id, new_state\]
+		StateChanged(PoolId, PoolState),
+		/// The roles of a pool changed. \[pool_id, root, nominator, state_toggler\]
+		RolesUpdated(PoolId, T::AccountId, T::AccountId, T::AccountId),
+		/// The bonded funds of a pool were slashed. \[pool_id, new_balance\]
+		PoolSlashed(PoolId, BalanceOf<T>),
+		/// The funds of an unbonding pool were slashed. \[pool_id, era, new_balance\]
+		UnbondingPoolSlashed(PoolId, EraIndex, BalanceOf<T>),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The pool does not exist.
+		PoolNotFound,
+		/// The account is not a member of a pool.
+		PoolMemberNotFound,
+		/// The reward pool of the pool does not exist.
+		RewardPoolNotFound,
+		/// The unbonding pools of the pool do not exist.
+		SubPoolsNotFound,
+		/// The account is a member of a pool already.
+		AccountBelongsToOtherPool,
+		/// The member has fewer points than requested.
+		NotEnoughPointsToUnbond,
+		/// The member is unbonding in too many eras. It must withdraw its unlocked funds first.
+		MaxUnbondingLimit,
+		/// The member has no unlocked funds to withdraw.
+		CannotWithdrawAny,
+		/// The funds are below the minimum bond.
+		MinimumBondNotMet,
+		/// The pool is not being destroyed.
+		NotDestroying,
+		/// The depositor is not the last member of the pool.
+		NotOnlyPoolMember,
+		/// The caller is neither the root nor the nominator of the pool.
+		NotNominator,
+		/// The caller can't kick the members of the pool.
+		NotKickerOrDestroying,
+		/// The pool doesn't accept new funds.
+		NotOpen,
+		/// There are too many pools.
+		MaxPools,
+		/// There are too many members.
+		MaxPoolMembers,
+		/// The pool is being destroyed, and its state can't change anymore.
+		CanNotChangeState,
+		/// The caller doesn't have the role required.
+		DoesNotHavePermission,
+		/// The funds of other members can only be fully unbonded.
+		PartialUnbondNotAllowedPermissionlessly,
+		/// Some internal accounting of the pool is inconsistent.
+		DefensiveError,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Join pool `pool_id`, bonding `amount` into it.
+		///
+		/// The pool must be open, and `amount` at least `MinJoinBond`. The caller must not be a
+		/// member of a pool yet.
+		///
+		/// Emits `Bonded`.
+		#[pallet::weight(<T as Config>::WeightInfo::join())]
+		#[transactional]
+		pub fn join(
+			origin: OriginFor<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+			pool_id: PoolId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(amount >= MinJoinBond::<T>::get(), Error::<T>::MinimumBondNotMet);
+			ensure!(!PoolMembers::<T>::contains_key(&who), Error::<T>::AccountBelongsToOtherPool);
+
+			let mut bonded_pool = BondedPool::<T>::get(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			bonded_pool.ok_to_join()?;
+			let mut reward_pool =
+				RewardPools::<T>::get(pool_id).ok_or(Error::<T>::RewardPoolNotFound)?;
+			// the new member doesn't earn the rewards received so far.
+			reward_pool.update_records(pool_id, bonded_pool.points);
+
+			let points = bonded_pool.try_bond_funds(&who, amount, BondType::Later)?;
+			bonded_pool.member_counter = bonded_pool.member_counter.saturating_add(1);
+
+			PoolMembers::<T>::insert(&who, PoolMember {
+				pool_id,
+				points,
+				last_recorded_reward_counter: reward_pool.last_recorded_reward_counter,
+				unbonding_eras: Default::default(),
+			});
+			RewardPools::<T>::insert(pool_id, reward_pool);
+			bonded_pool.put();
+
+			Self::deposit_event(Event::<T>::Bonded(who, pool_id, amount, true));
+			Ok(())
+		}
+
+		/// Bond more funds into the pool of the caller, either from its free balance or its
+		/// pending rewards.
+		///
+		/// The pending rewards are paid out in any case.
+		///
+		/// Emits `Bonded`.
+		#[pallet::weight(
+			<T as Config>::WeightInfo::bond_extra_transfer()
+				.max(<T as Config>::WeightInfo::bond_extra_reward())
+		)]
+		#[transactional]
+		pub fn bond_extra(origin: OriginFor<T>, extra: BondExtra<BalanceOf<T>>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let (mut member, mut bonded_pool, mut reward_pool) = Self::get_member_with_pools(&who)?;
+			ensure!(bonded_pool.state != PoolState::Destroying, Error::<T>::NotOpen);
+
+			let claimed =
+				Self::do_reward_payout(&who, &mut member, &mut bonded_pool, &mut reward_pool)?;
+			let amount = match extra {
+				BondExtra::FreeBalance(amount) => amount,
+				BondExtra::Rewards => claimed,
+			};
+			let points = bonded_pool.try_bond_funds(&who, amount, BondType::Later)?;
+			member.points = member.points.saturating_add(points);
+
+			let pool_id = bonded_pool.id;
+			Self::put_member_with_pools(&who, member, bonded_pool, reward_pool);
+			Self::deposit_event(Event::<T>::Bonded(who, pool_id, amount, false));
+			Ok(())
+		}
+
+		/// Pay out the pending rewards of the caller.
+		///
+		/// Emits `PaidOut` if there was anything to pay out.
+		#[pallet::weight(<T as Config>::WeightInfo::claim_payout())]
+		pub fn claim_payout(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let (mut member, mut bonded_pool, mut reward_pool) = Self::get_member_with_pools(&who)?;
+			Self::do_reward_payout(&who, &mut member, &mut bonded_pool, &mut reward_pool)?;
+			Self::put_member_with_pools(&who, member, bonded_pool, reward_pool);
+			Ok(())
+		}
+
+		/// Unbond the funds of `unbonding_points` of `member_account`, paying out its pending
+		/// rewards.
+		///
+		/// A member can unbond its own points, as long as it keeps at least `MinJoinBond`, or
+		/// `MinCreateBond` for the depositor, bonded. The depositor can only unbond fully once it
+		/// is the last member of a pool being destroyed, which anyone can trigger. The root and
+		/// state toggler of a blocked pool, or anyone if the pool is being destroyed, can fully
+		/// unbond the other members.
+		///
+		/// The funds can be withdrawn with `withdraw_unbonded` after the bonding duration.
+		///
+		/// Emits `Unbonded`.
+		#[pallet::weight(<T as Config>::WeightInfo::unbond())]
+		#[transactional]
+		pub fn unbond(
+			origin: OriginFor<T>,
+			member_account: T::AccountId,
+			#[pallet::compact] unbonding_points: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let (mut member, mut bonded_pool, mut reward_pool) =
+				Self::get_member_with_pools(&member_account)?;
+			ensure!(unbonding_points <= member.points, Error::<T>::NotEnoughPointsToUnbond);
+			bonded_pool.ok_to_unbond_with(&who, &member_account, &member, unbonding_points)?;
+
+			Self::do_reward_payout(
+				&member_account,
+				&mut member,
+				&mut bonded_pool,
+				&mut reward_pool,
+			)?;
+
+			let current_era = StakingOf::<T>::current_era().unwrap_or_default();
+			let unbond_era = current_era + <T as pallet_staking::Config>::BondingDuration::get();
+			let unbonding_balance = bonded_pool.dissolve(unbonding_points);
+			StakingOf::<T>::unbond(
+				RawOrigin::Signed(bonded_pool.bonded_account()).into(),
+				unbonding_balance,
+			)?;
+
+			let mut sub_pools = SubPoolsStorage::<T>::get(bonded_pool.id)
+				.unwrap_or_default()
+				.maybe_merge_pools(current_era);
+			if !sub_pools.with_era.contains_key(&unbond_era) {
+				sub_pools.with_era
+					.try_insert(unbond_era, UnbondPool::default())
+					.map_err(|_| Error::<T>::DefensiveError)?;
+			}
+			let points_unbonded = sub_pools.pool_of(unbond_era).issue(unbonding_balance);
+
+			member.points = member.points.saturating_sub(unbonding_points);
+			match member.unbonding_eras.get_mut(&unbond_era) {
+				Some(points) => *points = points.saturating_add(points_unbonded),
+				None => member.unbonding_eras
+					.try_insert(unbond_era, points_unbonded)
+					.map_err(|_| Error::<T>::MaxUnbondingLimit)?,
+			}
+
+			let pool_id = bonded_pool.id;
+			SubPoolsStorage::<T>::insert(pool_id, sub_pools);
+			Self::put_member_with_pools(&member_account, member, bonded_pool, reward_pool);
+			Self::deposit_event(Event::<T>::Unbonded(
+				member_account,
+				pool_id,
+				unbonding_balance,
+				unbonding_points,
+			));
+			Ok(())
+		}
+
+		/// Withdraw the unlocked funds of pool `pool_id` from the staking pallet.
+		///
+		/// This frees up unlocking chunks of the pool, e.g. when too many of them prevent the
+		/// members from unbonding. Anyone can call this.
+		#[pallet::weight(<T as Config>::WeightInfo::pool_withdraw_unbonded(*num_slashing_spans))]
+		pub fn pool_withdraw_unbonded(
+			origin: OriginFor<T>,
+			pool_id: PoolId,
+			num_slashing_spans: u32,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			let bonded_pool = BondedPool::<T>::get(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			StakingOf::<T>::withdraw_unbonded(
+				RawOrigin::Signed(bonded_pool.bonded_account()).into(),
+				num_slashing_spans,
+			)
+			.map_err(|e| e.error)?;
+			Ok(())
+		}
+
+		/// Withdraw the unlocked funds of `member_account`.
+		///
+		/// A member can withdraw its own funds. The root and state toggler of a blocked pool, or
+		/// anyone if the pool is being destroyed, can withdraw the funds of the other members.
+		///
+		/// Once the member has no funds left in the pool, it is removed. Once the depositor is
+		/// removed, the pool is dissolved and the depositor gets the rest of the reward account.
+		///
+		/// Emits `Withdrawn`, and `MemberRemoved` and `Destroyed` if relevant.
+		#[pallet::weight(
+			<T as Config>::WeightInfo::withdraw_unbonded_kill(*num_slashing_spans)
+		)]
+		#[transactional]
+		pub fn withdraw_unbonded(
+			origin: OriginFor<T>,
+			member_account: T::AccountId,
+			num_slashing_spans: u32,
+		) -> DispatchResultWithPostInfo {
+			let caller = ensure_signed(origin)?;
+			let mut member =
+				PoolMembers::<T>::get(&member_account).ok_or(Error::<T>::PoolMemberNotFound)?;
+			let mut bonded_pool =
+				BondedPool::<T>::get(member.pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			let mut sub_pools =
+				SubPoolsStorage::<T>::get(member.pool_id).ok_or(Error::<T>::SubPoolsNotFound)?;
+			ensure!(
+				caller == member_account || bonded_pool.can_kick(&caller),
+				Error::<T>::NotKickerOrDestroying,
+			);
+
+			let current_era = StakingOf::<T>::current_era().unwrap_or_default();
+			let withdrawn_points = member.withdraw_unlocked(current_era);
+			ensure!(!withdrawn_points.is_empty(), Error::<T>::CannotWithdrawAny);
+
+			let bonded_account = bonded_pool.bonded_account();
+			StakingOf::<T>::withdraw_unbonded(
+				RawOrigin::Signed(bonded_account.clone()).into(),
+				num_slashing_spans,
+			)
+			.map_err(|e| e.error)?;
+
+			let mut balance = BalanceOf::<T>::zero();
+			let mut points = BalanceOf::<T>::zero();
+			for (era, era_points) in withdrawn_points {
+				balance = balance.saturating_add(sub_pools.pool_of(era).dissolve(era_points));
+				points = points.saturating_add(era_points);
+			}
+			// defensive: never transfer more than what is unlocked, in case of rounding errors.
+			let still_locked = StakingOf::<T>::ledger(&bonded_account)
+				.map(|ledger| ledger.total)
+				.unwrap_or_else(Zero::zero);
+			let balance = balance.min(
+				CurrencyOf::<T>::free_balance(&bonded_account).saturating_sub(still_locked),
+			);
+			CurrencyOf::<T>::transfer(
+				&bonded_account,
+				&member_account,
+				balance,
+				ExistenceRequirement::AllowDeath,
+			)?;
+			Self::deposit_event(Event::<T>::Withdrawn(
+				member_account.clone(),
+				bonded_pool.id,
+				balance,
+				points,
+			));
+
+			if !member.is_empty() {
+				PoolMembers::<T>::insert(&member_account, member);
+				SubPoolsStorage::<T>::insert(bonded_pool.id, sub_pools);
+				let weight =
+					<T as Config>::WeightInfo::withdraw_unbonded_update(num_slashing_spans);
+				return Ok(Some(weight).into());
+			}
+
+			PoolMembers::<T>::remove(&member_account);
+			Self::deposit_event(Event::<T>::MemberRemoved(bonded_pool.id, member_account.clone()));
+
+			if member_account == bonded_pool.roles.depositor {
+				Self::dissolve_pool(bonded_pool);
+				Ok(None.into())
+			} else {
+				bonded_pool.member_counter = bonded_pool.member_counter.saturating_sub(1);
+				SubPoolsStorage::<T>::insert(bonded_pool.id, sub_pools);
+				bonded_pool.put();
+				let weight =
+					<T as Config>::WeightInfo::withdraw_unbonded_update(num_slashing_spans);
+				Ok(Some(weight).into())
+			}
+		}
+
+		/// Create a new pool, bonding `amount` from the caller, which becomes its depositor.
+		///
+		/// `amount` must be at least `MinCreateBond` and `MinJoinBond`. The caller also pays the
+		/// existential deposit of the reward account of the pool, and must not be a member of a
+		/// pool yet.
+		///
+		/// Emits `Created` and `Bonded`.
+		#[pallet::weight(<T as Config>::WeightInfo::create())]
+		#[transactional]
+		pub fn create(
+			origin: OriginFor<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+			root: T::AccountId,
+			nominator: T::AccountId,
+			state_toggler: T::AccountId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				amount >= MinCreateBond::<T>::get().max(MinJoinBond::<T>::get()),
+				Error::<T>::MinimumBondNotMet,
+			);
+			if let Some(max) = MaxPools::<T>::get() {
+				ensure!(BondedPools::<T>::count() < max, Error::<T>::MaxPools);
+			}
+			if let Some(max) = MaxPoolMembers::<T>::get() {
+				ensure!(PoolMembers::<T>::count() < max, Error::<T>::MaxPoolMembers);
+			}
+			ensure!(!PoolMembers::<T>::contains_key(&who), Error::<T>::AccountBelongsToOtherPool);
+
+			let pool_id = LastPoolId::<T>::mutate(|id| {
+				*id = id.saturating_add(1);
+				*id
+			});
+			let roles = PoolRoles { depositor: who.clone(), root, nominator, state_toggler };
+			let mut bonded_pool = BondedPool::<T>::new(pool_id, roles);
+
+			let points = bonded_pool.try_bond_funds(&who, amount, BondType::Create)?;
+			CurrencyOf::<T>::transfer(
+				&who,
+				&bonded_pool.reward_account(),
+				CurrencyOf::<T>::minimum_balance(),
+				ExistenceRequirement::KeepAlive,
+			)?;
+
+			PoolMembers::<T>::insert(&who, PoolMember {
+				pool_id,
+				points,
+				last_recorded_reward_counter: Zero::zero(),
+				unbonding_eras: Default::default(),
+			});
+			RewardPools::<T>::insert(pool_id, RewardPool::<T>::default());
+			ReversePoolIdLookup::<T>::insert(bonded_pool.bonded_account(), pool_id);
+			bonded_pool.put();
+
+			Self::deposit_event(Event::<T>::Created(who.clone(), pool_id));
+			Self::deposit_event(Event::<T>::Bonded(who, pool_id, amount, true));
+			Ok(())
+		}
+
+		/// Nominate `validators` with the funds of pool `pool_id`.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the root or the nominator of the
+		/// pool.
+		#[pallet::weight(<T as Config>::WeightInfo::nominate(validators.len() as u32))]
+		pub fn nominate(
+			origin: OriginFor<T>,
+			pool_id: PoolId,
+			validators: Vec<T::AccountId>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let bonded_pool = BondedPool::<T>::get(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			ensure!(bonded_pool.can_nominate(&who), Error::<T>::NotNominator);
+			StakingOf::<T>::nominate(
+				RawOrigin::Signed(bonded_pool.bonded_account()).into(),
+				validators.into_iter().map(T::Lookup::unlookup).collect(),
+			)
+		}
+
+		/// Set the state of pool `pool_id`.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the root or the state toggler of
+		/// the pool. The state of a pool being destroyed can't change anymore.
+		///
+		/// Emits `StateChanged`.
+		#[pallet::weight(<T as Config>::WeightInfo::set_state())]
+		pub fn set_state(
+			origin: OriginFor<T>,
+			pool_id: PoolId,
+			state: PoolState,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut bonded_pool = BondedPool::<T>::get(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			ensure!(bonded_pool.state != PoolState::Destroying, Error::<T>::CanNotChangeState);
+			ensure!(bonded_pool.can_toggle_state(&who), Error::<T>::DoesNotHavePermission);
+
+			bonded_pool.state = state;
+			bonded_pool.put();
+			Self::deposit_event(Event::<T>::StateChanged(pool_id, state));
+			Ok(())
+		}
+
+		/// Set the limits of the pallet. `None` removes the maximum numbers.
+		///
+		/// The dispatch origin for this call must be _Root_.
+		#[pallet::weight(<T as Config>::WeightInfo::set_configs())]
+		pub fn set_configs(
+			origin: OriginFor<T>,
+			min_join_bond: BalanceOf<T>,
+			min_create_bond: BalanceOf<T>,
+			max_pools: Option<u32>,
+			max_pool_members: Option<u32>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			MinJoinBond::<T>::put(min_join_bond);
+			MinCreateBond::<T>::put(min_create_bond);
+			MaxPools::<T>::set(max_pools);
+			MaxPoolMembers::<T>::set(max_pool_members);
+			Ok(())
+		}
+
+		/// Change the roles of pool `pool_id`. `None` keeps a role unchanged.
+		///
+		/// The dispatch origin for this call must be _Root_, or _Signed_ by the root of the pool.
+		///
+		/// Emits `RolesUpdated`.
+		#[pallet::weight(<T as Config>::WeightInfo::update_roles())]
+		pub fn update_roles(
+			origin: OriginFor<T>,
+			pool_id: PoolId,
+			new_root: Option<T::AccountId>,
+			new_nominator: Option<T::AccountId>,
+			new_state_toggler: Option<T::AccountId>,
+		) -> DispatchResult {
+			let mut bonded_pool = BondedPool::<T>::get(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			if ensure_root(origin.clone()).is_err() {
+				let who = ensure_signed(origin)?;
+				ensure!(bonded_pool.is_root(&who), Error::<T>::DoesNotHavePermission);
+			}
+
+			let roles = &mut bonded_pool.roles;
+			if let Some(root) = new_root {
+				roles.root = root;
+			}
+			if let Some(nominator) = new_nominator {
+				roles.nominator = nominator;
+			}
+			if let Some(state_toggler) = new_state_toggler {
+				roles.state_toggler = state_toggler;
+			}
+			let PoolRoles { root, nominator, state_toggler, .. } = roles.clone();
+			bonded_pool.put();
+			Self::deposit_event(Event::<T>::RolesUpdated(pool_id, root, nominator, state_toggler));
+			Ok(())
+		}
+
+		/// Stop the nominations of pool `pool_id`.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the root or the nominator of the
+		/// pool.
+		#[pallet::weight(<T as Config>::WeightInfo::chill())]
+		pub fn chill(origin: OriginFor<T>, pool_id: PoolId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let bonded_pool = BondedPool::<T>::get(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			ensure!(bonded_pool.can_nominate(&who), Error::<T>::NotNominator);
+			StakingOf::<T>::chill(RawOrigin::Signed(bonded_pool.bonded_account()).into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The account of `kind` of pool `id`.
+	fn create_account(id: PoolId, kind: AccountType) -> T::AccountId {
+		T::PalletId::get().into_sub_account((kind, id))
+	}
+
+	/// Get the member `who`, with its bonded and reward pools.
+	fn get_member_with_pools(
+		who: &T::AccountId,
+	) -> Result<(PoolMember<T>, BondedPool<T>, RewardPool<T>), Error<T>> {
+		let member = PoolMembers::<T>::get(who).ok_or(Error::<T>::PoolMemberNotFound)?;
+		let bonded_pool = BondedPool::<T>::get(member.pool_id).ok_or(Error::<T>::PoolNotFound)?;
+		let reward_pool =
+			RewardPools::<T>::get(member.pool_id).ok_or(Error::<T>::RewardPoolNotFound)?;
+		Ok((member, bonded_pool, reward_pool))
+	}
+
+	/// Write the member `who`, with its bonded and reward pools, to storage.
+	fn put_member_with_pools(
+		who: &T::AccountId,
+		member: PoolMember<T>,
+		bonded_pool: BondedPool<T>,
+		reward_pool: RewardPool<T>,
+	) {
+		RewardPools::<T>::insert(bonded_pool.id, reward_pool);
+		bonded_pool.put();
+		PoolMembers::<T>::insert(who, member);
+	}
+
+	/// Pay out the pending rewards of `member`, returning them.
+	///
+	/// The reward counter of the pool is recorded too, so the points of the pool can change
+	/// afterwards.
+	fn do_reward_payout(
+		member_account: &T::AccountId,
+		member: &mut PoolMember<T>,
+		bonded_pool: &mut BondedPool<T>,
+		reward_pool: &mut RewardPool<T>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		reward_pool.update_records(bonded_pool.id, bonded_pool.points);
+		let pending = member.pending_rewards(reward_pool.last_recorded_reward_counter);
+		member.last_recorded_reward_counter = reward_pool.last_recorded_reward_counter;
+		if pending.is_zero() {
+			return Ok(pending);
+		}
+
+		// the claimed rewards leave the reward account, so the total payouts don't change.
+		reward_pool.total_rewards_claimed =
+			reward_pool.total_rewards_claimed.saturating_add(pending);
+		CurrencyOf::<T>::transfer(
+			&bonded_pool.reward_account(),
+			member_account,
+			pending,
+			ExistenceRequirement::KeepAlive,
+		)?;
+		Self::deposit_event(Event::<T>::PaidOut(member_account.clone(), bonded_pool.id, pending));
+		Ok(pending)
+	}
+
+	/// Remove all the storage of `bonded_pool`, and give its remaining funds to the depositor.
+	fn dissolve_pool(bonded_pool: BondedPool<T>) {
+		let depositor = &bonded_pool.roles.depositor;
+		let bonded_account = bonded_pool.bonded_account();
+		let reward_account = bonded_pool.reward_account();
+
+		// the staking ledger is gone once the funds of the depositor are withdrawn, so whatever is
+		// left in the bonded account is dust.
+		if StakingOf::<T>::ledger(&bonded_account).is_none() {
+			let dust = CurrencyOf::<T>::free_balance(&bonded_account);
+			let _ = CurrencyOf::<T>::transfer(
+				&bonded_account,
+				depositor,
+				dust,
+				ExistenceRequirement::AllowDeath,
+			);
+		}
+		let rest = CurrencyOf::<T>::free_balance(&reward_account);
+		let _ = CurrencyOf::<T>::transfer(
+			&reward_account,
+			depositor,
+			rest,
+			ExistenceRequirement::AllowDeath,
+		);
+
+		ReversePoolIdLookup::<T>::remove(&bonded_account);
+		RewardPools::<T>::remove(bonded_pool.id);
+		SubPoolsStorage::<T>::remove(bonded_pool.id);
+		BondedPools::<T>::remove(bonded_pool.id);
+		Self::deposit_event(Event::<T>::Destroyed(bonded_pool.id));
+	}
+}
+
+impl<T: Config> OnStakerSlash<T::AccountId, BalanceOf<T>> for Pallet<T> {
+	fn on_slash(
+		pool_account: &T::AccountId,
+		slashed_bonded: BalanceOf<T>,
+		slashed_unlocking: &BTreeMap<EraIndex, BalanceOf<T>>,
+	) {
+		let pool_id = match ReversePoolIdLookup::<T>::get(pool_account) {
+			Some(pool_id) => pool_id,
+			None => return,
+		};
+		// the bonded pool reads its funds from the staking ledger, only the unbonding pools keep
+		// their own balance.
+		if let Some(mut sub_pools) = SubPoolsStorage::<T>::get(pool_id) {
+			for (era, balance) in slashed_unlocking {
+				if let Some(pool) = sub_pools.with_era.get_mut(era) {
+					pool.balance = *balance;
+					Self::deposit_event(Event::<T>::UnbondingPoolSlashed(pool_id, *era, *balance));
+				}
+			}
+			SubPoolsStorage::<T>::insert(pool_id, sub_pools);
+		}
+		Self::deposit_event(Event::<T>::PoolSlashed(pool_id, slashed_bonded));
+	}
+}
@@ -0,0 +1,319 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test utilities
+
+use super::*;
+use crate as pallet_nomination_pools;
+
+use frame_support::{assert_ok, parameter_types, traits::Imbalance, StorageValue};
+use sp_core::H256;
+use sp_runtime::{
+	Perbill,
+	traits::{BlakeTwo256, IdentityLookup}, testing::{Header, UintAuthorityId},
+};
+use frame_election_provider_support::onchain;
+use pallet_session::historical as pallet_session_historical;
+use pallet_staking::StakerStatus;
+
+type AccountId = u128;
+type Balance = u128;
+type BlockNumber = u64;
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Staking: pallet_staking::{Pallet, Call, Config<T>, Storage, Event<T>},
+		Session: pallet_session::{Pallet, Call, Storage, Event, Config<T>},
+		Historical: pallet_session_historical::{Pallet},
+		Pools: pallet_nomination_pools::{Pallet, Call, Config<T>, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Hash = H256;
+	type Call = Call;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: Balance = 5;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type HoldIdentifier = ();
+	type FreezeIdentifier = ();
+	type MaxHolds = frame_support::traits::ConstU32<1>;
+	type MaxFreezes = frame_support::traits::ConstU32<1>;
+	type Balance = Balance;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const MinimumPeriod: u64 = 5;
+}
+
+impl pallet_timestamp::Config for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = MinimumPeriod;
+	type WeightInfo = ();
+}
+
+sp_runtime::impl_opaque_keys! {
+	pub struct SessionKeys {
+		pub foo: UintAuthorityId,
+	}
+}
+
+pub struct TestSessionHandler;
+impl pallet_session::SessionHandler<AccountId> for TestSessionHandler {
+	const KEY_TYPE_IDS: &'static [sp_runtime::KeyTypeId] = &[];
+
+	fn on_genesis_session<Ks: sp_runtime::traits::OpaqueKeys>(_validators: &[(AccountId, Ks)]) {}
+
+	fn on_new_session<Ks: sp_runtime::traits::OpaqueKeys>(
+		_: bool,
+		_: &[(AccountId, Ks)],
+		_: &[(AccountId, Ks)],
+	) {}
+
+	fn on_disabled(_: usize) {}
+}
+
+parameter_types! {
+	pub const Period: u64 = 1;
+	pub const Offset: u64 = 0;
+}
+
+impl pallet_session::Config for Test {
+	type SessionManager = pallet_session::historical::NoteHistoricalRoot<Test, Staking>;
+	type Keys = SessionKeys;
+	type ShouldEndSession = pallet_session::PeriodicSessions<Period, Offset>;
+	type NextSessionRotation = pallet_session::PeriodicSessions<Period, Offset>;
+	type SessionHandler = TestSessionHandler;
+	type Event = Event;
+	type ValidatorId = AccountId;
+	type ValidatorIdOf = pallet_staking::StashOf<Test>;
+	type DisabledValidatorsThreshold = ();
+	type WeightInfo = ();
+}
+
+impl pallet_session::historical::Config for Test {
+	type FullIdentification = pallet_staking::Exposure<AccountId, Balance>;
+	type FullIdentificationOf = pallet_staking::ExposureOf<Test>;
+}
+
+pallet_staking_reward_curve::build! {
+	const I_NPOS: sp_runtime::curve::PiecewiseLinear<'static> = curve!(
+		min_inflation: 0_025_000,
+		max_inflation: 0_100_000,
+		ideal_stake: 0_500_000,
+		falloff: 0_050_000,
+		max_piece_count: 40,
+		test_precision: 0_005_000,
+	);
+}
+
+parameter_types! {
+	pub const RewardCurve: &'static sp_runtime::curve::PiecewiseLinear<'static> = &I_NPOS;
+	pub const BondingDuration: EraIndex = 3;
+	pub const MaxNominatorRewardedPerValidator: u32 = 64;
+}
+
+impl onchain::Config for Test {
+	type AccountId = AccountId;
+	type BlockNumber = BlockNumber;
+	type BlockWeights = ();
+	type Accuracy = Perbill;
+	type DataProvider = Staking;
+}
+
+impl pallet_staking::Config for Test {
+	const MAX_NOMINATIONS: u32 = 16;
+	type Currency = Balances;
+	type UnixTime = Timestamp;
+	type CurrencyToVote = frame_support::traits::SaturatingCurrencyToVote;
+	type RewardRemainder = ();
+	type Event = Event;
+	type Slash = ();
+	type Reward = ();
+	type SessionsPerEra = ();
+	type SlashDeferDuration = ();
+	type SlashCancelOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type BondingDuration = BondingDuration;
+	type SessionInterface = Self;
+	type EraPayout = pallet_staking::ConvertCurve<RewardCurve>;
+	type NextNewSession = Session;
+	type MaxNominatorRewardedPerValidator = MaxNominatorRewardedPerValidator;
+	type ElectionProvider = onchain::OnChainSequentialPhragmen<Self>;
+	type SortedListProvider = pallet_staking::UseNominatorsMap<Self>;
+	type OnStakerSlash = Pools;
+	type WeightInfo = ();
+}
+
+pub type Extrinsic = sp_runtime::testing::TestXt<Call, ()>;
+
+impl<T> frame_system::offchain::SendTransactionTypes<T> for Test where Call: From<T> {
+	type Extrinsic = Extrinsic;
+	type OverarchingCall = Call;
+}
+
+parameter_types! {
+	pub const PoolsPalletId: PalletId = PalletId(*b"py/nopls");
+	pub const PostUnbondingPoolsWindow: u32 = 2;
+}
+
+impl Config for Test {
+	type Event = Event;
+	type WeightInfo = ();
+	type PalletId = PoolsPalletId;
+	type PostUnbondingPoolsWindow = PostUnbondingPoolsWindow;
+}
+
+/// The depositor of the pool created at genesis.
+pub const DEPOSITOR: AccountId = 10;
+/// The root of the pool created at genesis.
+pub const ROOT: AccountId = 900;
+/// The nominator of the pool created at genesis.
+pub const NOMINATOR: AccountId = 901;
+/// The state toggler of the pool created at genesis.
+pub const STATE_TOGGLER: AccountId = 902;
+/// The validator nominated by the pools.
+pub const VALIDATOR: AccountId = 100;
+
+/// Pool 1 was created by `DEPOSITOR` with 20 out of its 1_000, and `VALIDATOR` bonds 1_000. The
+/// accounts 20, 30 and 40 have 100 each.
+///
+/// Joining requires 10, creating a pool 20, and there can be at most 3 pools and 5 members.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(DEPOSITOR, 1_000), (20, 100), (30, 100), (40, 100), (VALIDATOR, 10_000)],
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+	pallet_staking::GenesisConfig::<Test> {
+		stakers: vec![(VALIDATOR, VALIDATOR, 1_000, StakerStatus::Validator)],
+		validator_count: 1,
+		..Default::default()
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+	pallet_nomination_pools::GenesisConfig::<Test> {
+		min_join_bond: 10,
+		min_create_bond: 20,
+		max_pools: Some(3),
+		max_pool_members: Some(5),
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		set_current_era(0);
+		assert_ok!(Pools::create(Origin::signed(DEPOSITOR), 20, ROOT, NOMINATOR, STATE_TOGGLER));
+	});
+	ext
+}
+
+/// Set the current era of the staking pallet.
+pub fn set_current_era(era: EraIndex) {
+	pallet_staking::CurrentEra::put(era);
+}
+
+/// The bonded account of pool `id`.
+pub fn bonded_account(id: PoolId) -> AccountId {
+	BondedPool::<Test>::get(id).unwrap().bonded_account()
+}
+
+/// The reward account of pool `id`.
+pub fn reward_account(id: PoolId) -> AccountId {
+	BondedPool::<Test>::get(id).unwrap().reward_account()
+}
+
+/// Pay `amount` of staking rewards to pool `id`.
+pub fn reward_pool(id: PoolId, amount: Balance) {
+	let _ = Balances::deposit_creating(&reward_account(id), amount);
+}
+
+/// Slash `amount` from the funds of pool `id` in the staking pallet.
+pub fn slash_pool(id: PoolId, amount: Balance) {
+	let mut reward_payout = 0;
+	let mut imbalance = <Balances as Currency<AccountId>>::NegativeImbalance::zero();
+	pallet_staking::slashing::do_slash::<Test>(
+		&bonded_account(id),
+		amount,
+		&mut reward_payout,
+		&mut imbalance,
+	);
+}
+
+/// The active and unlocking funds of pool `id` in the staking pallet.
+pub fn staked_funds(id: PoolId) -> (Balance, Vec<(EraIndex, Balance)>) {
+	let ledger = Staking::ledger(&bonded_account(id)).unwrap();
+	(ledger.active, ledger.unlocking.iter().map(|chunk| (chunk.era, chunk.value)).collect())
+}
+
+/// The events of this pallet, in order.
+pub fn pool_events() -> Vec<crate::Event<Test>> {
+	System::events()
+		.into_iter()
+		.filter_map(|record| match record.event {
+			Event::pallet_nomination_pools(inner) => Some(inner),
+			_ => None,
+		})
+		.collect()
+}
@@ -0,0 +1,520 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the Nomination Pools pallet.
+
+use super::*;
+use crate::mock::*;
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::traits::BadOrigin;
+use std::convert::TryInto;
+
+fn roles() -> PoolRoles<u128> {
+	PoolRoles {
+		depositor: DEPOSITOR,
+		root: ROOT,
+		nominator: NOMINATOR,
+		state_toggler: STATE_TOGGLER,
+	}
+}
+
+fn member(points: u128, unbonding_eras: Vec<(EraIndex, u128)>) -> PoolMember<Test> {
+	PoolMember {
+		pool_id: 1,
+		points,
+		last_recorded_reward_counter: Zero::zero(),
+		unbonding_eras: unbonding_eras
+			.into_iter()
+			.collect::<BTreeMap<_, _>>()
+			.try_into()
+			.unwrap(),
+	}
+}
+
+fn sub_pools(no_era: (u128, u128), with_era: Vec<(EraIndex, u128, u128)>) -> SubPools<Test> {
+	SubPools {
+		no_era: UnbondPool { points: no_era.0, balance: no_era.1 },
+		with_era: with_era
+			.into_iter()
+			.map(|(era, points, balance)| (era, UnbondPool { points, balance }))
+			.collect::<BTreeMap<_, _>>()
+			.try_into()
+			.unwrap(),
+	}
+}
+
+#[test]
+fn create_works() {
+	new_test_ext().execute_with(|| {
+		let pool = BondedPool::<Test>::get(1).unwrap();
+		assert_eq!(
+			pool.inner,
+			BondedPoolInner {
+				points: 20,
+				state: PoolState::Open,
+				member_counter: 1,
+				roles: roles(),
+			},
+		);
+		assert_eq!(PoolMembers::<Test>::get(DEPOSITOR), Some(member(20, vec![])));
+		assert_eq!(RewardPools::<Test>::get(1), Some(RewardPool::default()));
+		assert_eq!(ReversePoolIdLookup::<Test>::get(bonded_account(1)), Some(1));
+
+		// the pool bonds with itself as controller, and is rewarded in its reward account.
+		assert_eq!(staked_funds(1), (20, vec![]));
+		assert_eq!(Staking::bonded(bonded_account(1)), Some(bonded_account(1)));
+		assert_eq!(
+			Staking::payee(bonded_account(1)),
+			RewardDestination::Account(reward_account(1)),
+		);
+		assert_eq!(Balances::free_balance(reward_account(1)), 5);
+		assert_eq!(Balances::free_balance(DEPOSITOR), 1_000 - 20 - 5);
+		assert_eq!(
+			pool_events(),
+			vec![crate::Event::Created(DEPOSITOR, 1), crate::Event::Bonded(DEPOSITOR, 1, 20, true)],
+		);
+
+		// the accounts of each pool are distinct.
+		assert_ok!(Pools::create(Origin::signed(20), 20, 20, 20, 20));
+		assert_eq!(LastPoolId::<Test>::get(), 2);
+		assert_eq!(BondedPools::<Test>::count(), 2);
+		assert_eq!(PoolMembers::<Test>::count(), 2);
+		assert_ne!(bonded_account(2), bonded_account(1));
+		assert_ne!(bonded_account(2), reward_account(2));
+		assert_eq!(staked_funds(2), (20, vec![]));
+
+		assert_noop!(
+			Pools::create(Origin::signed(20), 20, 20, 20, 20),
+			Error::<Test>::AccountBelongsToOtherPool,
+		);
+		assert_noop!(
+			Pools::create(Origin::signed(30), 19, 30, 30, 30),
+			Error::<Test>::MinimumBondNotMet,
+		);
+	});
+}
+
+#[test]
+fn set_configs_limits_the_pools_and_members() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Pools::set_configs(Origin::signed(ROOT), 10, 20, None, None), BadOrigin);
+
+		assert_ok!(Pools::set_configs(Origin::root(), 10, 20, Some(1), None));
+		assert_noop!(Pools::create(Origin::signed(20), 20, 20, 20, 20), Error::<Test>::MaxPools);
+
+		assert_ok!(Pools::set_configs(Origin::root(), 10, 20, None, Some(1)));
+		assert_noop!(
+			Pools::create(Origin::signed(20), 20, 20, 20, 20),
+			Error::<Test>::MaxPoolMembers,
+		);
+		assert_noop!(Pools::join(Origin::signed(20), 10, 1), Error::<Test>::MaxPoolMembers);
+
+		assert_ok!(Pools::set_configs(Origin::root(), 50, 20, None, None));
+		assert_noop!(Pools::join(Origin::signed(20), 49, 1), Error::<Test>::MinimumBondNotMet);
+		assert_ok!(Pools::join(Origin::signed(20), 50, 1));
+	});
+}
+
+#[test]
+fn join_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Pools::join(Origin::signed(20), 10, 1));
+
+		assert_eq!(PoolMembers::<Test>::get(20), Some(member(10, vec![])));
+		assert_eq!(BondedPool::<Test>::get(1).unwrap().points, 30);
+		assert_eq!(BondedPool::<Test>::get(1).unwrap().member_counter, 2);
+		assert_eq!(PoolMembers::<Test>::count(), 2);
+		assert_eq!(staked_funds(1), (30, vec![]));
+		assert_eq!(Balances::free_balance(20), 90);
+		assert_eq!(pool_events().last(), Some(&crate::Event::Bonded(20, 1, 10, true)));
+
+		assert_noop!(
+			Pools::join(Origin::signed(20), 10, 1),
+			Error::<Test>::AccountBelongsToOtherPool,
+		);
+		assert_noop!(Pools::join(Origin::signed(30), 9, 1), Error::<Test>::MinimumBondNotMet);
+		assert_noop!(Pools::join(Origin::signed(30), 10, 2), Error::<Test>::PoolNotFound);
+
+		assert_ok!(Pools::set_state(Origin::signed(STATE_TOGGLER), 1, PoolState::Blocked));
+		assert_noop!(Pools::join(Origin::signed(30), 10, 1), Error::<Test>::NotOpen);
+	});
+}
+
+#[test]
+fn points_are_issued_at_the_current_value_of_the_pool() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Pools::join(Origin::signed(20), 10, 1));
+
+		// the pool lost half of its funds, so each point is worth half as much.
+		slash_pool(1, 15);
+		assert_eq!(staked_funds(1), (15, vec![]));
+		assert_eq!(pool_events().last(), Some(&crate::Event::PoolSlashed(1, 15)));
+
+		assert_ok!(Pools::join(Origin::signed(30), 10, 1));
+		assert_eq!(PoolMembers::<Test>::get(30).unwrap().points, 20);
+
+		let pool = BondedPool::<Test>::get(1).unwrap();
+		assert_eq!(pool.points, 50);
+		assert_eq!(pool.points_to_balance(20), 10);
+		assert_eq!(pool.points_to_balance(10), 5);
+	});
+}
+
+#[test]
+fn rewards_are_shared_by_points() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Pools::join(Origin::signed(20), 10, 1));
+
+		// 10 has 20 points, 20 has 10.
+		reward_pool(1, 30);
+		assert_ok!(Pools::claim_payout(Origin::signed(DEPOSITOR)));
+		assert_eq!(Balances::free_balance(DEPOSITOR), 975 + 20);
+		assert_eq!(pool_events().last(), Some(&crate::Event::PaidOut(DEPOSITOR, 1, 20)));
+
+		// 30 doesn't earn the rewards received before it joined.
+		assert_ok!(Pools::join(Origin::signed(30), 10, 1));
+		reward_pool(1, 40);
+
+		assert_ok!(Pools::claim_payout(Origin::signed(20)));
+		assert_eq!(Balances::free_balance(20), 90 + 10 + 10);
+		assert_ok!(Pools::claim_payout(Origin::signed(30)));
+		assert_eq!(Balances::free_balance(30), 90 + 10);
+		assert_ok!(Pools::claim_payout(Origin::signed(DEPOSITOR)));
+		assert_eq!(Balances::free_balance(DEPOSITOR), 975 + 20 + 20);
+
+		// everything was paid out, only the existential deposit is left.
+		assert_eq!(Balances::free_balance(reward_account(1)), 5);
+		let events = pool_events().len();
+		assert_ok!(Pools::claim_payout(Origin::signed(DEPOSITOR)));
+		assert_eq!(pool_events().len(), events);
+
+		assert_noop!(Pools::claim_payout(Origin::signed(40)), Error::<Test>::PoolMemberNotFound);
+	});
+}
+
+#[test]
+fn rewards_received_without_points_are_carried_forward() {
+	new_test_ext().execute_with(|| {
+		let mut pool = RewardPools::<Test>::get(1).unwrap();
+		reward_pool(1, 10);
+
+		// nothing is recorded while there are no points to share the rewards.
+		pool.update_records(1, 0);
+		assert_eq!(pool, RewardPool::default());
+
+		// the next points get them.
+		pool.update_records(1, 20);
+		assert_eq!(pool.last_recorded_reward_counter, FixedU128::saturating_from_rational(10, 20));
+		assert_eq!(pool.last_recorded_total_payouts, 10);
+	});
+}
+
+#[test]
+fn bond_extra_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Pools::join(Origin::signed(20), 10, 1));
+
+		assert_ok!(Pools::bond_extra(Origin::signed(DEPOSITOR), BondExtra::FreeBalance(10)));
+		assert_eq!(PoolMembers::<Test>::get(DEPOSITOR).unwrap().points, 30);
+		assert_eq!(staked_funds(1), (40, vec![]));
+
+		// 20 has 10 out of the 40 points.
+		reward_pool(1, 40);
+		assert_ok!(Pools::bond_extra(Origin::signed(20), BondExtra::Rewards));
+		assert_eq!(PoolMembers::<Test>::get(20).unwrap().points, 20);
+		assert_eq!(Balances::free_balance(20), 90);
+		assert_eq!(staked_funds(1), (50, vec![]));
+		assert_eq!(
+			pool_events()[pool_events().len() - 2..],
+			[crate::Event::PaidOut(20, 1, 10), crate::Event::Bonded(20, 1, 10, false)],
+		);
+
+		assert_ok!(Pools::set_state(Origin::signed(STATE_TOGGLER), 1, PoolState::Destroying));
+		assert_noop!(
+			Pools::bond_extra(Origin::signed(20), BondExtra::FreeBalance(10)),
+			Error::<Test>::NotOpen,
+		);
+	});
+}
+
+#[test]
+fn unbond_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Pools::join(Origin::signed(20), 10, 1));
+		assert_ok!(Pools::join(Origin::signed(30), 20, 1));
+		set_current_era(1);
+
+		assert_ok!(Pools::unbond(Origin::signed(30), 30, 10));
+		assert_eq!(PoolMembers::<Test>::get(30).unwrap().points, 10);
+		assert_eq!(PoolMembers::<Test>::get(30).unwrap().unbonding_eras.get(&4), Some(&10));
+		assert_eq!(SubPoolsStorage::<Test>::get(1), Some(sub_pools((0, 0), vec![(4, 10, 10)])));
+		assert_eq!(staked_funds(1), (40, vec![(4, 10)]));
+		assert_eq!(pool_events().last(), Some(&crate::Event::Unbonded(30, 1, 10, 10)));
+
+		assert_noop!(
+			Pools::unbond(Origin::signed(30), 30, 11),
+			Error::<Test>::NotEnoughPointsToUnbond,
+		);
+		assert_noop!(Pools::unbond(Origin::signed(30), 30, 5), Error::<Test>::MinimumBondNotMet);
+		// the members of an open pool can only unbond themselves.
+		assert_noop!(
+			Pools::unbond(Origin::signed(30), 20, 10),
+			Error::<Test>::NotKickerOrDestroying,
+		);
+
+		// unbonding in the same era adds to the same unbonding pool.
+		assert_ok!(Pools::unbond(Origin::signed(30), 30, 10));
+		assert_eq!(PoolMembers::<Test>::get(30), Some(member(0, vec![(4, 20)])));
+		assert_eq!(SubPoolsStorage::<Test>::get(1), Some(sub_pools((0, 0), vec![(4, 20, 20)])));
+		assert_eq!(staked_funds(1), (30, vec![(4, 20)]));
+		// 30 is still a member until it withdraws.
+		assert_eq!(BondedPool::<Test>::get(1).unwrap().member_counter, 3);
+	});
+}
+
+#[test]
+fn depositor_unbonds_last() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Pools::unbond(Origin::signed(DEPOSITOR), DEPOSITOR, 1),
+			Error::<Test>::MinimumBondNotMet,
+		);
+		assert_noop!(
+			Pools::unbond(Origin::signed(DEPOSITOR), DEPOSITOR, 20),
+			Error::<Test>::NotDestroying,
+		);
+		assert_ok!(Pools::bond_extra(Origin::signed(DEPOSITOR), BondExtra::FreeBalance(10)));
+		assert_ok!(Pools::unbond(Origin::signed(DEPOSITOR), DEPOSITOR, 10));
+
+		assert_ok!(Pools::join(Origin::signed(20), 10, 1));
+		assert_ok!(Pools::set_state(Origin::signed(STATE_TOGGLER), 1, PoolState::Destroying));
+		assert_noop!(
+			Pools::unbond(Origin::signed(40), DEPOSITOR, 20),
+			Error::<Test>::NotOnlyPoolMember,
+		);
+
+		// 20 must withdraw before the depositor can leave.
+		assert_ok!(Pools::unbond(Origin::signed(40), 20, 10));
+		assert_noop!(
+			Pools::unbond(Origin::signed(40), DEPOSITOR, 20),
+			Error::<Test>::NotOnlyPoolMember,
+		);
+		set_current_era(3);
+		assert_ok!(Pools::withdraw_unbonded(Origin::signed(40), 20, 0));
+		assert_ok!(Pools::unbond(Origin::signed(40), DEPOSITOR, 20));
+		assert_eq!(PoolMembers::<Test>::get(DEPOSITOR).unwrap().points, 0);
+	});
+}
+
+#[test]
+fn blocked_pools_can_be_kicked_from() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Pools::join(Origin::signed(20), 10, 1));
+		assert_ok!(Pools::join(Origin::signed(30), 10, 1));
+		assert_ok!(Pools::set_state(Origin::signed(STATE_TOGGLER), 1, PoolState::Blocked));
+
+		assert_noop!(
+			Pools::unbond(Origin::signed(NOMINATOR), 20, 10),
+			Error::<Test>::NotKickerOrDestroying,
+		);
+		assert_noop!(
+			Pools::unbond(Origin::signed(ROOT), 20, 5),
+			Error::<Test>::PartialUnbondNotAllowedPermissionlessly,
+		);
+		assert_ok!(Pools::unbond(Origin::signed(ROOT), 20, 10));
+		assert_ok!(Pools::unbond(Origin::signed(STATE_TOGGLER), 30, 10));
+
+		set_current_era(3);
+		assert_noop!(
+			Pools::withdraw_unbonded(Origin::signed(40), 20, 0),
+			Error::<Test>::NotKickerOrDestroying,
+		);
+		assert_ok!(Pools::withdraw_unbonded(Origin::signed(ROOT), 20, 0));
+		assert_eq!(Balances::free_balance(20), 100);
+	});
+}
+
+#[test]
+fn withdraw_unbonded_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Pools::join(Origin::signed(20), 10, 1));
+		assert_ok!(Pools::unbond(Origin::signed(20), 20, 10));
+
+		assert_noop!(
+			Pools::withdraw_unbonded(Origin::signed(30), 20, 0),
+			Error::<Test>::NotKickerOrDestroying,
+		);
+		set_current_era(2);
+		assert_noop!(
+			Pools::withdraw_unbonded(Origin::signed(20), 20, 0),
+			Error::<Test>::CannotWithdrawAny,
+		);
+
+		set_current_era(3);
+		assert_ok!(Pools::withdraw_unbonded(Origin::signed(20), 20, 0));
+		assert_eq!(Balances::free_balance(20), 100);
+		assert_eq!(staked_funds(1), (20, vec![]));
+		assert_eq!(PoolMembers::<Test>::get(20), None);
+		assert_eq!(PoolMembers::<Test>::count(), 1);
+		assert_eq!(BondedPool::<Test>::get(1).unwrap().member_counter, 1);
+		assert_eq!(
+			pool_events()[pool_events().len() - 2..],
+			[crate::Event::Withdrawn(20, 1, 10, 10), crate::Event::MemberRemoved(1, 20)],
+		);
+	});
+}
+
+#[test]
+fn old_unbonding_pools_are_merged() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Pools::join(Origin::signed(20), 10, 1));
+		assert_ok!(Pools::join(Origin::signed(30), 10, 1));
+		assert_ok!(Pools::unbond(Origin::signed(20), 20, 10));
+
+		// the pool of era 3 unlocked more than `PostUnbondingPoolsWindow` eras ago.
+		set_current_era(5);
+		assert_ok!(Pools::unbond(Origin::signed(30), 30, 10));
+		assert_eq!(SubPoolsStorage::<Test>::get(1), Some(sub_pools((10, 10), vec![(8, 10, 10)])));
+
+		// 20 withdraws from the merged pool.
+		assert_ok!(Pools::withdraw_unbonded(Origin::signed(20), 20, 0));
+		assert_eq!(Balances::free_balance(20), 100);
+		assert_eq!(SubPoolsStorage::<Test>::get(1), Some(sub_pools((0, 0), vec![(8, 10, 10)])));
+	});
+}
+
+#[test]
+fn slashes_are_shared_by_the_unbonding_members() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Pools::join(Origin::signed(20), 40, 1));
+		assert_ok!(Pools::unbond(Origin::signed(20), 20, 40));
+		assert_eq!(staked_funds(1), (20, vec![(3, 40)]));
+
+		// the active funds are slashed first, then the unbonding ones.
+		slash_pool(1, 40);
+		assert_eq!(staked_funds(1), (0, vec![(3, 20)]));
+		assert_eq!(SubPoolsStorage::<Test>::get(1), Some(sub_pools((0, 0), vec![(3, 40, 20)])));
+		assert_eq!(
+			pool_events()[pool_events().len() - 2..],
+			[crate::Event::UnbondingPoolSlashed(1, 3, 20), crate::Event::PoolSlashed(1, 0)],
+		);
+
+		set_current_era(3);
+		assert_ok!(Pools::withdraw_unbonded(Origin::signed(20), 20, 0));
+		assert_eq!(Balances::free_balance(20), 60 + 20);
+	});
+}
+
+#[test]
+fn roles_work() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Pools::nominate(Origin::signed(20), 1, vec![VALIDATOR]),
+			Error::<Test>::NotNominator,
+		);
+		assert_ok!(Pools::nominate(Origin::signed(NOMINATOR), 1, vec![VALIDATOR]));
+		assert_eq!(Staking::nominators(bonded_account(1)).unwrap().targets, vec![VALIDATOR]);
+		assert_noop!(Pools::chill(Origin::signed(20), 1), Error::<Test>::NotNominator);
+		assert_ok!(Pools::chill(Origin::signed(ROOT), 1));
+		assert_eq!(Staking::nominators(bonded_account(1)), None);
+
+		assert_noop!(
+			Pools::set_state(Origin::signed(NOMINATOR), 1, PoolState::Blocked),
+			Error::<Test>::DoesNotHavePermission,
+		);
+		assert_ok!(Pools::set_state(Origin::signed(ROOT), 1, PoolState::Blocked));
+		assert_eq!(pool_events().last(), Some(&crate::Event::StateChanged(1, PoolState::Blocked)));
+
+		assert_noop!(
+			Pools::update_roles(Origin::signed(STATE_TOGGLER), 1, None, Some(20), None),
+			Error::<Test>::DoesNotHavePermission,
+		);
+		assert_ok!(Pools::update_roles(Origin::signed(ROOT), 1, None, Some(20), None));
+		assert_eq!(
+			pool_events().last(),
+			Some(&crate::Event::RolesUpdated(1, ROOT, 20, STATE_TOGGLER)),
+		);
+		assert_ok!(Pools::update_roles(Origin::root(), 1, Some(30), None, None));
+		let expected = PoolRoles { root: 30, nominator: 20, ..roles() };
+		assert_eq!(BondedPool::<Test>::get(1).unwrap().roles, expected);
+		assert_ok!(Pools::nominate(Origin::signed(20), 1, vec![VALIDATOR]));
+
+		assert_ok!(Pools::set_state(Origin::signed(30), 1, PoolState::Destroying));
+		assert_noop!(
+			Pools::set_state(Origin::signed(30), 1, PoolState::Open),
+			Error::<Test>::CanNotChangeState,
+		);
+	});
+}
+
+#[test]
+fn the_pool_is_dissolved_once_the_depositor_withdraws() {
+	new_test_ext().execute_with(|| {
+		let bonded = bonded_account(1);
+		assert_ok!(Pools::join(Origin::signed(20), 10, 1));
+		reward_pool(1, 30);
+		assert_ok!(Pools::set_state(Origin::signed(STATE_TOGGLER), 1, PoolState::Destroying));
+
+		// anyone can kick the members, which get their pending rewards.
+		assert_ok!(Pools::unbond(Origin::signed(30), 20, 10));
+		assert_eq!(Balances::free_balance(20), 90 + 10);
+		set_current_era(3);
+		assert_ok!(Pools::withdraw_unbonded(Origin::signed(30), 20, 0));
+		assert_eq!(Balances::free_balance(20), 100 + 10);
+
+		assert_ok!(Pools::unbond(Origin::signed(30), DEPOSITOR, 20));
+		assert_eq!(Balances::free_balance(DEPOSITOR), 975 + 20);
+		set_current_era(6);
+		assert_ok!(Pools::withdraw_unbonded(Origin::signed(30), DEPOSITOR, 0));
+
+		// the depositor gets its funds back, and the existential deposit of the reward account.
+		assert_eq!(Balances::free_balance(DEPOSITOR), 1_000 + 20);
+		assert_eq!(Balances::free_balance(bonded), 0);
+		assert_eq!(Staking::bonded(bonded), None);
+		assert_eq!(BondedPools::<Test>::get(1), None);
+		assert_eq!(RewardPools::<Test>::get(1), None);
+		assert_eq!(SubPoolsStorage::<Test>::get(1), None);
+		assert_eq!(PoolMembers::<Test>::get(DEPOSITOR), None);
+		assert_eq!(ReversePoolIdLookup::<Test>::get(bonded), None);
+		assert_eq!(BondedPools::<Test>::count(), 0);
+		assert_eq!(PoolMembers::<Test>::count(), 0);
+		assert_eq!(
+			pool_events()[pool_events().len() - 3..],
+			[
+				crate::Event::Withdrawn(DEPOSITOR, 1, 20, 20),
+				crate::Event::MemberRemoved(1, DEPOSITOR),
+				crate::Event::Destroyed(1),
+			],
+		);
+	});
+}
+
+#[test]
+fn unbonding_pools_are_slashed_with_the_ledger() {
+	new_test_ext().execute_with(|| {
+		let mut unlocking = BTreeMap::new();
+		unlocking.insert(3, 5);
+
+		// slashes of other stakers are ignored.
+		<Pools as OnStakerSlash<_, _>>::on_slash(&VALIDATOR, 10, &unlocking);
+		assert!(pool_events().iter().all(|e| !matches!(e, crate::Event::PoolSlashed(..))));
+
+		// eras without an unbonding pool are ignored.
+		<Pools as OnStakerSlash<_, _>>::on_slash(&bonded_account(1), 10, &unlocking);
+		assert_eq!(pool_events().last(), Some(&crate::Event::PoolSlashed(1, 10)));
+	});
+}
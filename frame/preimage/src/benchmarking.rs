@@ -0,0 +1,93 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Preimage pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{benchmarks, whitelisted_caller, impl_benchmark_test_suite};
+use frame_support::dispatch::UnfilteredDispatchable;
+use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+
+use crate::Pallet as Preimage;
+
+/// A funded caller, with enough free balance for the deposit of the largest preimage.
+fn funded_caller<T: Config>() -> T::AccountId {
+	let caller: T::AccountId = whitelisted_caller();
+	T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+	caller
+}
+
+/// A preimage of `len` bytes, and its hash.
+fn preimage(len: u32) -> (Vec<u8>, H256) {
+	let bytes = vec![1u8; len as usize];
+	let hash = BlakeTwo256::hash(&bytes[..]);
+	(bytes, hash)
+}
+
+benchmarks! {
+	// The worst case: the preimage is not requested, so a deposit is reserved.
+	note_preimage {
+		let s in 0 .. MAX_SIZE;
+		let caller = funded_caller::<T>();
+		let (bytes, hash) = preimage(s);
+	}: _(RawOrigin::Signed(caller), bytes)
+	verify {
+		assert!(StatusFor::<T>::contains_key(&hash));
+	}
+
+	// The worst case: the deposit is returned, and the preimage removed.
+	unnote_preimage {
+		let caller = funded_caller::<T>();
+		let (bytes, hash) = preimage(32);
+		Preimage::<T>::note_preimage(RawOrigin::Signed(caller.clone()).into(), bytes)?;
+	}: _(RawOrigin::Signed(caller), hash)
+	verify {
+		assert!(!StatusFor::<T>::contains_key(&hash));
+	}
+
+	// The worst case: the preimage is noted with a deposit already.
+	request_preimage {
+		let caller = funded_caller::<T>();
+		let (bytes, hash) = preimage(32);
+		Preimage::<T>::note_preimage(RawOrigin::Signed(caller).into(), bytes)?;
+		let call = Call::<T>::request_preimage(hash);
+		let origin = T::ManagerOrigin::successful_origin();
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert!(Preimage::<T>::is_requested(&hash));
+	}
+
+	// The worst case: the last request is withdrawn, and the preimage removed.
+	unrequest_preimage {
+		let (bytes, hash) = preimage(32);
+		<Preimage<T> as StorePreimage>::note(bytes.into())?;
+		let call = Call::<T>::unrequest_preimage(hash);
+		let origin = T::ManagerOrigin::successful_origin();
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert!(!StatusFor::<T>::contains_key(&hash));
+	}
+}
+
+impl_benchmark_test_suite!(
+	Preimage,
+	crate::mock::new_test_ext(),
+	crate::mock::Test,
+);
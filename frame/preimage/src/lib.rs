@@ -0,0 +1,405 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Preimage Pallet
+//!
+//! A pallet to store the preimages of hashes, mostly encoded calls, on behalf of other pallets.
+//!
+//! ## Overview
+//!
+//! Pallets like the scheduler only keep the hash of values too large to be held inline, and rely
+//! on a [`QueryPreimage`] and [`StorePreimage`] implementation to get the value back. This pallet
+//! implements them, storing preimages of at most [`MAX_SIZE`] bytes.
+//!
+//! Anyone can note a preimage with `note_preimage`, reserving a deposit of `BaseDeposit` plus
+//! `ByteDeposit` per byte. The deposit is returned by `unnote_preimage`, which also removes the
+//! preimage unless it is requested.
+//!
+//! A preimage is requested either by another pallet through [`QueryPreimage::request`], or by the
+//! `ManagerOrigin` through `request_preimage`. Requests are counted, and a requested preimage is
+//! kept until each of them is withdrawn. It can be noted free of charge. Once it is not
+//! requested anymore, the preimage is removed, unless someone still holds a deposit for it.
+//!
+//! The `ManagerOrigin` and other pallets, through [`StorePreimage::note`], note preimages
+//! without a deposit. Such a preimage is requested once more instead, until it is unnoted.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! - `note_preimage`: Store a preimage, reserving a deposit unless it is requested.
+//! - `unnote_preimage`: Return the deposit of a preimage, removing it unless it is requested.
+//! - `request_preimage`: Keep a preimage, whether it is noted yet or not, until it is unrequested.
+//! - `unrequest_preimage`: Withdraw a request of a preimage.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_std::{prelude::*, borrow::Cow, convert::TryFrom};
+use codec::{Encode, Decode};
+use sp_core::H256;
+use sp_runtime::{
+	DispatchError, DispatchResult, RuntimeDebug, traits::{BlakeTwo256, Hash, Saturating},
+};
+use frame_support::{
+	ensure,
+	storage::bounded_vec::BoundedVec,
+	traits::{
+		ConstU32, Currency, ReservableCurrency, Get, EnsureOrigin, FetchResult, QueryPreimage,
+		StorePreimage,
+	},
+	weights::Pays,
+};
+use frame_system::ensure_signed;
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+mod benchmarking;
+pub mod weights;
+
+/// The maximal length of a preimage.
+pub const MAX_SIZE: u32 = 4 * 1024 * 1024;
+
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// The status of a preimage which is noted or requested.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum RequestStatus<AccountId, Balance> {
+	/// The preimage is noted with a deposit, but nobody requested it.
+	Unrequested {
+		/// The account which noted the preimage, and its deposit.
+		deposit: (AccountId, Balance),
+		/// The length of the preimage.
+		len: u32,
+	},
+	/// The preimage is requested, and may be noted already.
+	Requested {
+		/// The account which noted the preimage, and its deposit, if any.
+		deposit: Option<(AccountId, Balance)>,
+		/// The number of requests which were not withdrawn yet.
+		count: u32,
+		/// The length of the preimage, if it is noted.
+		len: Option<u32>,
+	},
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency in which the deposits are reserved.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The origin which can request preimages, and note them without a deposit.
+		type ManagerOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The base deposit for noting a preimage.
+		#[pallet::constant]
+		type BaseDeposit: Get<BalanceOf<Self>>;
+
+		/// The deposit per byte of a noted preimage.
+		#[pallet::constant]
+		type ByteDeposit: Get<BalanceOf<Self>>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The status of the preimages which are noted or requested.
+	#[pallet::storage]
+	pub type StatusFor<T: Config> =
+		StorageMap<_, Identity, H256, RequestStatus<T::AccountId, BalanceOf<T>>>;
+
+	/// The noted preimages, by hash and length.
+	#[pallet::storage]
+	pub type PreimageFor<T: Config> =
+		StorageMap<_, Identity, (H256, u32), BoundedVec<u8, ConstU32<MAX_SIZE>>>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A preimage was noted. \[hash\]
+		Noted(H256),
+		/// A preimage was requested. \[hash\]
+		Requested(H256),
+		/// A preimage was removed. \[hash\]
+		Cleared(H256),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The preimage is longer than `MAX_SIZE`.
+		TooLarge,
+		/// The preimage is noted already.
+		AlreadyNoted,
+		/// The origin is neither the account which noted the preimage nor the `ManagerOrigin`.
+		NotAuthorized,
+		/// The preimage is neither noted nor requested.
+		NotNoted,
+		/// The preimage is not requested.
+		NotRequested,
+	}
+
+	/// The reasons for which the pallet holds funds.
+	///
+	/// A runtime with holds can use it for the deposits, e.g. with a
+	/// [`FungibleAdapter`](frame_support::traits::tokens::currency::FungibleAdapter) holding
+	/// `HoldReason::Preimage` as `Currency`.
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		/// The deposit of a noted preimage.
+		Preimage,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Store `bytes` as a preimage.
+		///
+		/// A deposit of `BaseDeposit` plus `ByteDeposit` per byte is reserved from the signer,
+		/// unless the preimage is requested, in which case noting it is free.
+		///
+		/// The dispatch origin for this call must be _Signed_ or the `ManagerOrigin`, which does
+		/// not pay a deposit but requests the preimage instead.
+		#[pallet::weight(T::WeightInfo::note_preimage(bytes.len() as u32))]
+		pub fn note_preimage(origin: OriginFor<T>, bytes: Vec<u8>) -> DispatchResultWithPostInfo {
+			let maybe_sender = Self::ensure_signed_or_manager(origin)?;
+			let (was_requested, _) = Self::note_bytes(bytes.into(), maybe_sender.as_ref())?;
+			if was_requested {
+				Ok(Pays::No.into())
+			} else {
+				Ok(().into())
+			}
+		}
+
+		/// Return the deposit of the preimage of `hash`, and remove the preimage unless it is
+		/// requested.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the account which noted the
+		/// preimage, or the `ManagerOrigin`, which withdraws its request if it noted the
+		/// preimage itself.
+		#[pallet::weight(T::WeightInfo::unnote_preimage())]
+		pub fn unnote_preimage(origin: OriginFor<T>, hash: H256) -> DispatchResult {
+			let maybe_sender = Self::ensure_signed_or_manager(origin)?;
+			Self::do_unnote_preimage(&hash, maybe_sender)
+		}
+
+		/// Request the preimage of `hash`, so that it is kept until it is unrequested, and can
+		/// be noted free of charge.
+		///
+		/// The dispatch origin for this call must be the `ManagerOrigin`.
+		#[pallet::weight(T::WeightInfo::request_preimage())]
+		pub fn request_preimage(origin: OriginFor<T>, hash: H256) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+			Self::do_request_preimage(&hash);
+			Ok(())
+		}
+
+		/// Withdraw a request of the preimage of `hash`.
+		///
+		/// The dispatch origin for this call must be the `ManagerOrigin`.
+		#[pallet::weight(T::WeightInfo::unrequest_preimage())]
+		pub fn unrequest_preimage(origin: OriginFor<T>, hash: H256) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+			Self::do_unrequest_preimage(&hash)
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Returns `None` for the `ManagerOrigin`, or the signer otherwise.
+	fn ensure_signed_or_manager(
+		origin: T::Origin,
+	) -> Result<Option<T::AccountId>, DispatchError> {
+		if T::ManagerOrigin::ensure_origin(origin.clone()).is_ok() {
+			return Ok(None);
+		}
+		Ok(Some(ensure_signed(origin)?))
+	}
+
+	/// Store `bytes` as a preimage.
+	///
+	/// A deposit is reserved from `maybe_depositor` unless the preimage is requested already.
+	/// Without a depositor, the preimage is requested once more, so that it is kept until it is
+	/// unnoted.
+	///
+	/// Returns whether the preimage was requested already, and its hash.
+	fn note_bytes(
+		bytes: Cow<[u8]>,
+		maybe_depositor: Option<&T::AccountId>,
+	) -> Result<(bool, H256), DispatchError> {
+		let hash = BlakeTwo256::hash(&bytes[..]);
+		let len = bytes.len() as u32;
+		let bytes = BoundedVec::<u8, ConstU32<MAX_SIZE>>::try_from(bytes.into_owned())
+			.map_err(|_| Error::<T>::TooLarge)?;
+
+		let (was_requested, was_noted, deposit, count) = match StatusFor::<T>::get(hash) {
+			Some(RequestStatus::Requested { deposit, count, len }) =>
+				(true, len.is_some(), deposit, count),
+			Some(RequestStatus::Unrequested { deposit, .. }) => (false, true, Some(deposit), 0),
+			None => (false, false, None, 0),
+		};
+		let status = match maybe_depositor {
+			Some(_) if was_noted => return Err(Error::<T>::AlreadyNoted.into()),
+			// requested preimages are noted free of charge.
+			Some(_) if was_requested => RequestStatus::Requested { deposit, count, len: Some(len) },
+			Some(depositor) => {
+				let amount = T::BaseDeposit::get()
+					.saturating_add(T::ByteDeposit::get().saturating_mul(len.into()));
+				T::Currency::reserve(depositor, amount)?;
+				RequestStatus::Unrequested { deposit: (depositor.clone(), amount), len }
+			},
+			None => {
+				let count = count.saturating_add(1);
+				RequestStatus::Requested { deposit, count, len: Some(len) }
+			},
+		};
+		StatusFor::<T>::insert(hash, status);
+
+		if !was_noted {
+			PreimageFor::<T>::insert((hash, len), bytes);
+			Self::deposit_event(Event::Noted(hash));
+		}
+		Ok((was_requested, hash))
+	}
+
+	/// Return the deposit of the preimage of `hash`, and remove the preimage unless it is
+	/// requested.
+	///
+	/// If `maybe_check_owner` is given, it must be the account which noted the preimage.
+	/// Otherwise, a preimage noted without a deposit is unrequested.
+	fn do_unnote_preimage(hash: &H256, maybe_check_owner: Option<T::AccountId>) -> DispatchResult {
+		match StatusFor::<T>::get(hash).ok_or(Error::<T>::NotNoted)? {
+			RequestStatus::Requested { deposit: Some((owner, amount)), count, len } => {
+				ensure!(maybe_check_owner.map_or(true, |c| c == owner), Error::<T>::NotAuthorized);
+				T::Currency::unreserve(&owner, amount);
+				let status = RequestStatus::Requested { deposit: None, count, len };
+				StatusFor::<T>::insert(hash, status);
+				Ok(())
+			},
+			RequestStatus::Requested { deposit: None, len: Some(_), .. } => {
+				ensure!(maybe_check_owner.is_none(), Error::<T>::NotAuthorized);
+				Self::do_unrequest_preimage(hash)
+			},
+			RequestStatus::Requested { deposit: None, len: None, .. } =>
+				Err(Error::<T>::NotNoted.into()),
+			RequestStatus::Unrequested { deposit: (owner, amount), len } => {
+				ensure!(maybe_check_owner.map_or(true, |c| c == owner), Error::<T>::NotAuthorized);
+				T::Currency::unreserve(&owner, amount);
+				Self::clear(hash, len);
+				Ok(())
+			},
+		}
+	}
+
+	/// Request the preimage of `hash`, whether it is noted or not.
+	fn do_request_preimage(hash: &H256) {
+		let (deposit, count, len) = match StatusFor::<T>::get(hash) {
+			Some(RequestStatus::Requested { deposit, count, len }) =>
+				(deposit, count.saturating_add(1), len),
+			Some(RequestStatus::Unrequested { deposit, len }) => (Some(deposit), 1, Some(len)),
+			None => (None, 1, None),
+		};
+		StatusFor::<T>::insert(hash, RequestStatus::Requested { deposit, count, len });
+		if count == 1 {
+			Self::deposit_event(Event::Requested(*hash));
+		}
+	}
+
+	/// Withdraw a request of the preimage of `hash`.
+	///
+	/// Once the last request is withdrawn, the preimage is removed unless someone holds a
+	/// deposit for it.
+	fn do_unrequest_preimage(hash: &H256) -> DispatchResult {
+		match StatusFor::<T>::get(hash).ok_or(Error::<T>::NotRequested)? {
+			RequestStatus::Requested { deposit, count, len } if count > 1 => {
+				let count = count - 1;
+				StatusFor::<T>::insert(hash, RequestStatus::Requested { deposit, count, len });
+			},
+			RequestStatus::Requested { deposit, len, .. } => match (deposit, len) {
+				(Some(deposit), Some(len)) =>
+					StatusFor::<T>::insert(hash, RequestStatus::Unrequested { deposit, len }),
+				(_, Some(len)) => Self::clear(hash, len),
+				// a deposit is only held for a noted preimage.
+				(_, None) => StatusFor::<T>::remove(hash),
+			},
+			RequestStatus::Unrequested { .. } => return Err(Error::<T>::NotRequested.into()),
+		}
+		Ok(())
+	}
+
+	/// Remove the preimage of `hash` and its status.
+	fn clear(hash: &H256, len: u32) {
+		StatusFor::<T>::remove(hash);
+		PreimageFor::<T>::remove((*hash, len));
+		Self::deposit_event(Event::Cleared(*hash));
+	}
+}
+
+impl<T: Config> QueryPreimage for Pallet<T> {
+	fn len(hash: &H256) -> Option<u32> {
+		match StatusFor::<T>::get(hash)? {
+			RequestStatus::Requested { len, .. } => len,
+			RequestStatus::Unrequested { len, .. } => Some(len),
+		}
+	}
+
+	fn fetch(hash: &H256, len: Option<u32>) -> FetchResult {
+		let len = len.or_else(|| Self::len(hash)).ok_or(DispatchError::CannotLookup)?;
+		PreimageFor::<T>::get((*hash, len))
+			.map(|bytes| Cow::Owned(bytes.into_inner()))
+			.ok_or(DispatchError::CannotLookup)
+	}
+
+	fn is_requested(hash: &H256) -> bool {
+		matches!(StatusFor::<T>::get(hash), Some(RequestStatus::Requested { .. }))
+	}
+
+	fn request(hash: &H256) {
+		Self::do_request_preimage(hash)
+	}
+
+	fn unrequest(hash: &H256) {
+		let result = Self::do_unrequest_preimage(hash);
+		debug_assert!(result.is_ok(), "unrequesting a preimage which is not requested");
+	}
+}
+
+impl<T: Config> StorePreimage for Pallet<T> {
+	const MAX_LENGTH: usize = MAX_SIZE as usize;
+
+	fn note(bytes: Cow<[u8]>) -> Result<H256, DispatchError> {
+		Self::note_bytes(bytes, None).map(|(_, hash)| hash)
+	}
+}
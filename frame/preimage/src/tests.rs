@@ -0,0 +1,246 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the Preimage pallet.
+
+use super::*;
+use crate::mock::*;
+use frame_support::{assert_noop, assert_ok, traits::Bounded};
+use sp_runtime::traits::BadOrigin;
+
+#[test]
+fn user_note_preimage_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Preimage::note_preimage(Origin::signed(2), vec![1]));
+		System::assert_last_event(Event::pallet_preimage(crate::Event::Noted(hashed([1]))));
+		// base deposit of 2 and 1 per byte.
+		assert_eq!(Balances::reserved_balance(2), 3);
+		assert_eq!(Balances::free_balance(2), 97);
+
+		let h = hashed([1]);
+		assert!(!Preimage::is_requested(&h));
+		assert_eq!(Preimage::len(&h), Some(1));
+		assert_eq!(Preimage::fetch(&h, None).unwrap().to_vec(), vec![1]);
+		assert_eq!(Preimage::fetch(&h, Some(1)).unwrap().to_vec(), vec![1]);
+		assert!(Preimage::fetch(&h, Some(2)).is_err());
+
+		assert_noop!(
+			Preimage::note_preimage(Origin::signed(2), vec![1]),
+			Error::<Test>::AlreadyNoted,
+		);
+		assert_noop!(
+			Preimage::note_preimage(Origin::signed(3), vec![1]),
+			Error::<Test>::AlreadyNoted,
+		);
+	});
+}
+
+#[test]
+fn note_preimage_checks_the_length() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Preimage::note_preimage(Origin::signed(2), vec![0; MAX_SIZE as usize + 1]),
+			Error::<Test>::TooLarge,
+		);
+		assert_noop!(
+			Preimage::note_preimage(Origin::signed(2), vec![0; 100]),
+			pallet_balances::Error::<Test>::InsufficientBalance,
+		);
+	});
+}
+
+#[test]
+fn manager_note_preimage_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Preimage::note_preimage(Origin::signed(1), vec![1]));
+		assert_eq!(Balances::reserved_balance(1), 0);
+
+		let h = hashed([1]);
+		assert!(Preimage::is_requested(&h));
+		assert_eq!(Preimage::len(&h), Some(1));
+
+		assert_noop!(Preimage::unnote_preimage(Origin::signed(2), h), Error::<Test>::NotAuthorized);
+		assert_ok!(Preimage::unnote_preimage(Origin::signed(1), h));
+		System::assert_last_event(Event::pallet_preimage(crate::Event::Cleared(h)));
+		assert_eq!(Preimage::len(&h), None);
+		assert!(!Preimage::is_requested(&h));
+
+		assert_noop!(Preimage::unnote_preimage(Origin::signed(1), h), Error::<Test>::NotNoted);
+	});
+}
+
+#[test]
+fn user_unnote_preimage_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Preimage::note_preimage(Origin::signed(2), vec![1]));
+		let h = hashed([1]);
+
+		assert_noop!(Preimage::unnote_preimage(Origin::signed(3), h), Error::<Test>::NotAuthorized);
+		assert_noop!(
+			Preimage::unnote_preimage(Origin::signed(2), hashed([2])),
+			Error::<Test>::NotNoted,
+		);
+
+		assert_ok!(Preimage::unnote_preimage(Origin::signed(2), h));
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Preimage::len(&h), None);
+		assert!(StatusFor::<Test>::get(h).is_none());
+		assert!(PreimageFor::<Test>::get((h, 1)).is_none());
+
+		assert_noop!(Preimage::unnote_preimage(Origin::signed(2), h), Error::<Test>::NotNoted);
+	});
+}
+
+#[test]
+fn manager_unnote_user_preimage_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Preimage::note_preimage(Origin::signed(2), vec![1]));
+		assert_ok!(Preimage::unnote_preimage(Origin::signed(1), hashed([1])));
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Preimage::len(&hashed([1])), None);
+	});
+}
+
+#[test]
+fn requested_preimage_is_noted_free_of_charge() {
+	new_test_ext().execute_with(|| {
+		let h = hashed([1]);
+		assert_ok!(Preimage::request_preimage(Origin::signed(1), h));
+		System::assert_last_event(Event::pallet_preimage(crate::Event::Requested(h)));
+		assert!(Preimage::is_requested(&h));
+		assert_eq!(Preimage::len(&h), None);
+
+		let info = Preimage::note_preimage(Origin::signed(2), vec![1]).unwrap();
+		assert_eq!(info.pays_fee, Pays::No);
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Preimage::len(&h), Some(1));
+
+		// nobody holds a deposit, so it is removed once unrequested.
+		assert_ok!(Preimage::unrequest_preimage(Origin::signed(1), h));
+		assert_eq!(Preimage::len(&h), None);
+		assert!(StatusFor::<Test>::get(h).is_none());
+		assert_noop!(
+			Preimage::unrequest_preimage(Origin::signed(1), h),
+			Error::<Test>::NotRequested,
+		);
+	});
+}
+
+#[test]
+fn requested_preimage_is_kept_after_unnote() {
+	new_test_ext().execute_with(|| {
+		let h = hashed([1]);
+		assert_ok!(Preimage::note_preimage(Origin::signed(2), vec![1]));
+		assert_ok!(Preimage::request_preimage(Origin::signed(1), h));
+
+		// the deposit is returned, but the preimage stays.
+		assert_ok!(Preimage::unnote_preimage(Origin::signed(2), h));
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Preimage::len(&h), Some(1));
+
+		assert_ok!(Preimage::unrequest_preimage(Origin::signed(1), h));
+		assert_eq!(Preimage::len(&h), None);
+	});
+}
+
+#[test]
+fn unrequested_preimage_is_kept_while_its_deposit_is_held() {
+	new_test_ext().execute_with(|| {
+		let h = hashed([1]);
+		assert_ok!(Preimage::note_preimage(Origin::signed(2), vec![1]));
+		assert_ok!(Preimage::request_preimage(Origin::signed(1), h));
+		assert_ok!(Preimage::unrequest_preimage(Origin::signed(1), h));
+
+		assert!(!Preimage::is_requested(&h));
+		assert_eq!(Preimage::len(&h), Some(1));
+		assert_eq!(Balances::reserved_balance(2), 3);
+		assert_noop!(
+			Preimage::unrequest_preimage(Origin::signed(1), h),
+			Error::<Test>::NotRequested,
+		);
+
+		assert_ok!(Preimage::unnote_preimage(Origin::signed(2), h));
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Preimage::len(&h), None);
+	});
+}
+
+#[test]
+fn requests_are_counted() {
+	new_test_ext().execute_with(|| {
+		let h = hashed([1]);
+		assert_ok!(Preimage::request_preimage(Origin::signed(1), h));
+		assert_ok!(Preimage::request_preimage(Origin::signed(1), h));
+		assert_ok!(Preimage::note_preimage(Origin::signed(2), vec![1]));
+
+		assert_ok!(Preimage::unrequest_preimage(Origin::signed(1), h));
+		assert!(Preimage::is_requested(&h));
+		assert_eq!(Preimage::len(&h), Some(1));
+
+		assert_ok!(Preimage::unrequest_preimage(Origin::signed(1), h));
+		assert!(!Preimage::is_requested(&h));
+		assert_eq!(Preimage::len(&h), None);
+	});
+}
+
+#[test]
+fn request_needs_the_manager() {
+	new_test_ext().execute_with(|| {
+		let h = hashed([1]);
+		assert_noop!(Preimage::request_preimage(Origin::signed(2), h), BadOrigin);
+		assert_ok!(Preimage::request_preimage(Origin::signed(1), h));
+		assert_noop!(Preimage::unrequest_preimage(Origin::signed(2), h), BadOrigin);
+	});
+}
+
+#[test]
+fn store_preimage_works() {
+	new_test_ext().execute_with(|| {
+		let value = vec![7u8; 200];
+		let bounded = Preimage::bound(value.clone()).unwrap();
+		let h = hashed(value.encode());
+		assert_eq!(bounded, Bounded::Lookup { hash: h, len: value.encoded_size() as u32 });
+		assert!(Preimage::is_requested(&h));
+		assert!(Preimage::have(&bounded));
+
+		// held by someone else as well.
+		Preimage::hold(&bounded);
+		let len = value.encoded_size() as u32;
+		assert_eq!(Preimage::realize(&bounded).unwrap(), (value.clone(), Some(len)));
+		assert!(Preimage::have(&bounded));
+		assert_eq!(Preimage::peek(&bounded).unwrap().0, value);
+
+		Preimage::unnote(&h);
+		assert!(!Preimage::have(&bounded));
+		assert!(Preimage::peek(&bounded).is_err());
+	});
+}
+
+#[test]
+fn picked_preimage_can_be_noted_later() {
+	new_test_ext().execute_with(|| {
+		let value = vec![7u8; 200];
+		let h = hashed(value.encode());
+		let bounded = Preimage::pick::<Vec<u8>>(h, value.encoded_size() as u32);
+		assert!(!Preimage::have(&bounded));
+
+		assert_ok!(Preimage::note_preimage(Origin::signed(2), value.encode()));
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Preimage::realize(&bounded).unwrap().0, value);
+		assert!(!Preimage::have(&bounded));
+	});
+}
@@ -32,8 +32,9 @@ const BLOCK_NUMBER: u32 = 2;
 
 // Add `n` named items to the schedule
 fn fill_schedule<T: Config> (when: T::BlockNumber, n: u32) -> Result<(), &'static str> {
-	// Essentially a no-op call.
-	let call = frame_system::Call::set_storage(vec![]);
+	// Essentially a no-op call, small enough to be held inline.
+	let call = <T as Config>::Call::from(frame_system::Call::set_storage(vec![]));
+	let call = T::Preimages::bound(call)?;
 	for i in 0..n {
 		// Named schedule is strictly heavier than anonymous
 		Scheduler::<T>::do_schedule_named(
@@ -44,7 +45,7 @@ fn fill_schedule<T: Config> (when: T::BlockNumber, n: u32) -> Result<(), &'stati
 			// HARD_DEADLINE priority means it gets executed no matter what
 			0,
 			frame_system::RawOrigin::Root.into(),
-			call.clone().into(),
+			call.clone(),
 		)?;
 	}
 	ensure!(Agenda::<T>::get(when).len() == n as usize, "didn't fill schedule");
@@ -36,6 +36,16 @@
 //! If a call is scheduled using proxy or whatever mecanism which adds filter,
 //! then those filter will not be used when dispatching the schedule call.
 //!
+//! Calls are stored as a [`Bounded`] value: small calls are held inline, larger ones only by the
+//! hash of their preimage, which is looked up through `Config::Preimages` when the task is due.
+//! A task whose preimage is not available at that point is postponed by
+//! `NoPreimagePostponement` blocks, or dropped if that is `None`.
+//!
+//! Agendas are serviced within the `MaximumWeight` budget, in order of priority. Tasks which do
+//! not fit into the budget of their block stay in place and are serviced in later blocks, before
+//! any newer agenda. Tasks which could never fit into the budget are reported through the
+//! `PermanentlyOverweight` event and left in their agenda until canceled.
+//!
 //! ## Interface
 //!
 //! ### Dispatchable Functions
@@ -54,13 +64,16 @@
 mod benchmarking;
 pub mod weights;
 
-use sp_std::{prelude::*, marker::PhantomData, borrow::Borrow};
+use sp_std::{prelude::*, marker::PhantomData, borrow::Borrow, cmp::Ordering};
 use codec::{Encode, Decode, Codec};
 use sp_runtime::{RuntimeDebug, traits::{Zero, One, BadOrigin, Saturating}};
 use frame_support::{
 	dispatch::{Dispatchable, DispatchError, DispatchResult, Parameter},
-	traits::{Get, schedule::{self, DispatchTime}, OriginTrait, EnsureOrigin, IsType},
-	weights::{GetDispatchInfo, Weight},
+	traits::{
+		Get, schedule::{self, DispatchTime}, OriginTrait, EnsureOrigin, IsType, PrivilegeCmp,
+		Bounded, QueryPreimage, StorePreimage,
+	},
+	weights::{GetDispatchInfo, Weight, WeightMeter},
 };
 use frame_system::{self as system, ensure_signed};
 pub use weights::WeightInfo;
@@ -97,9 +110,29 @@ pub struct ScheduledV2<Call, BlockNumber, PalletsOrigin, AccountId> {
 	_phantom: PhantomData<AccountId>,
 }
 
+/// Information regarding an item to be executed in the future, whose call is bounded.
+pub type ScheduledV3<Call, BlockNumber, PalletsOrigin, AccountId> =
+	ScheduledV2<Bounded<Call>, BlockNumber, PalletsOrigin, AccountId>;
+
 /// The current version of Scheduled struct.
 pub type Scheduled<Call, BlockNumber, PalletsOrigin, AccountId> =
-	ScheduledV2<Call, BlockNumber, PalletsOrigin, AccountId>;
+	ScheduledV3<Call, BlockNumber, PalletsOrigin, AccountId>;
+
+type ScheduledOf<T> = Scheduled<
+	<T as Config>::Call,
+	<T as frame_system::Config>::BlockNumber,
+	<T as Config>::PalletsOrigin,
+	<T as frame_system::Config>::AccountId,
+>;
+
+/// The reason why the call of a task could not be looked up.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum LookupError {
+	/// The preimage of the call is not available.
+	Unknown,
+	/// The call could not be decoded.
+	BadFormat,
+}
 
 // A value placed in storage that represents the current version of the Scheduler storage.
 // This value is used by the `on_runtime_upgrade` logic to determine whether we run
@@ -108,6 +141,7 @@ pub type Scheduled<Call, BlockNumber, PalletsOrigin, AccountId> =
 enum Releases {
 	V1,
 	V2,
+	V3,
 }
 
 impl Default for Releases {
@@ -146,36 +180,51 @@ pub mod pallet {
 			+ GetDispatchInfo
 			+ From<system::Call<Self>>;
 
-		/// The maximum weight that may be scheduled per block for any dispatchables of less priority
-		/// than `schedule::HARD_DEADLINE`.
+		/// The maximum weight that may be used per block to service agendas. Tasks which do not fit
+		/// are postponed to later blocks.
 		type MaximumWeight: Get<Weight>;
 
 		/// Required origin to schedule or cancel calls.
 		type ScheduleOrigin: EnsureOrigin<<Self as system::Config>::Origin>;
 
+		/// Compares the privileges of origins.
+		///
+		/// A call scheduled by an origin may only be canceled by an origin with equal or greater
+		/// privilege.
+		type OriginPrivilegeCmp: PrivilegeCmp<Self::PalletsOrigin>;
+
 		/// The maximum number of scheduled calls in the queue for a single block.
 		/// Not strictly enforced, but used for weight estimation.
 		type MaxScheduledPerBlock: Get<u32>;
 
+		/// The provider of the preimages of calls which are not held inline.
+		///
+		/// Calls scheduled through the dispatchables which are too large to be held inline are
+		/// noted here.
+		type Preimages: QueryPreimage + StorePreimage;
+
+		/// The number of blocks by which a task is postponed if the preimage of its call is not
+		/// available when it is due. If `None`, such a task is dropped.
+		type NoPreimagePostponement: Get<Option<Self::BlockNumber>>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 	}
 
 	/// Items to be executed, indexed by the block number that they should be executed on.
 	#[pallet::storage]
-	pub type Agenda<T: Config> = StorageMap<
-		_,
-		Twox64Concat,
-		T::BlockNumber,
-		Vec<Option<Scheduled<<T as Config>::Call, T::BlockNumber, T::PalletsOrigin, T::AccountId>>>,
-		ValueQuery,
-	>;
+	pub type Agenda<T: Config> =
+		StorageMap<_, Twox64Concat, T::BlockNumber, Vec<Option<ScheduledOf<T>>>, ValueQuery>;
 
 	/// Lookup from identity to the block number and index of the task.
 	#[pallet::storage]
 	pub(crate) type Lookup<T: Config> =
 		StorageMap<_, Twox64Concat, Vec<u8>, TaskAddress<T::BlockNumber>>;
 
+	/// The earliest block whose agenda still holds tasks that were postponed for lack of weight.
+	#[pallet::storage]
+	pub type IncompleteSince<T: Config> = StorageValue<_, T::BlockNumber>;
+
 	/// Storage version of the pallet.
 	///
 	/// New networks start with last version.
@@ -193,6 +242,13 @@ pub mod pallet {
 		Canceled(T::BlockNumber, u32),
 		/// Dispatched some task. \[task, id, result\]
 		Dispatched(TaskAddress<T::BlockNumber>, Option<Vec<u8>>, DispatchResult),
+		/// The given task can never be executed since it is overweight; it stays in its agenda
+		/// until canceled. \[task, id\]
+		PermanentlyOverweight(TaskAddress<T::BlockNumber>, Option<Vec<u8>>),
+		/// The call of the given task could not be looked up; it is postponed if its preimage is
+		/// unknown and `NoPreimagePostponement` is set, and dropped otherwise.
+		/// \[task, id, error\]
+		CallLookupFailed(TaskAddress<T::BlockNumber>, Option<Vec<u8>>, LookupError),
 	}
 
 	#[pallet::error]
@@ -220,116 +276,26 @@ pub mod pallet {
  	#[pallet::genesis_build]
  	impl<T: Config> GenesisBuild<T> for GenesisConfig {
  		fn build(&self) {
-			StorageVersion::<T>::put(Releases::V2);
+			StorageVersion::<T>::put(Releases::V3);
  		}
  	}
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-		/// Execute the scheduled calls
+		/// Execute the scheduled calls, within the `MaximumWeight` budget.
 		///
 		/// # <weight>
-		/// - S = Number of already scheduled calls
+		/// - A = Number of agendas serviced, i.e. this block's and any incomplete earlier ones
 		/// - N = Named scheduled calls
 		/// - P = Periodic Calls
-		/// - Base Weight: 9.243 + 23.45 * S µs
 		/// - DB Weight:
-		///     - Read: Agenda + Lookup * N + Agenda(Future) * P
-		///     - Write: Agenda + Lookup * N  + Agenda(future) * P
+		///     - Read: IncompleteSince + Agenda * A + Agenda(Future) * P
+		///     - Write: IncompleteSince + Agenda * A + Lookup * N  + Agenda(future) * P
 		/// # </weight>
 		fn on_initialize(now: T::BlockNumber) -> Weight {
-			let limit = T::MaximumWeight::get();
-			let mut queued = Agenda::<T>::take(now)
-				.into_iter()
-				.enumerate()
-				.filter_map(|(index, s)| s.map(|inner| (index as u32, inner)))
-				.collect::<Vec<_>>();
-			if queued.len() as u32 > T::MaxScheduledPerBlock::get() {
-				log::warn!(
-					target: "runtime::scheduler",
-					"Warning: This block has more items queued in Scheduler than \
-					expected from the runtime configuration. An update might be needed."
-				);
-			}
-			queued.sort_by_key(|(_, s)| s.priority);
-			let base_weight: Weight = T::DbWeight::get().reads_writes(1, 2); // Agenda + Agenda(next)
-			let mut total_weight: Weight = 0;
-			queued
-				.into_iter()
-				.enumerate()
-				.scan(base_weight, |cumulative_weight, (order, (index, s))| {
-					*cumulative_weight =
-						cumulative_weight.saturating_add(s.call.get_dispatch_info().weight);
-
-					let origin =
-						<<T as Config>::Origin as From<T::PalletsOrigin>>::from(s.origin.clone())
-							.into();
-
-					if ensure_signed(origin).is_ok() {
-						// AccountData for inner call origin accountdata.
-						*cumulative_weight =
-							cumulative_weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
-					}
-
-					if s.maybe_id.is_some() {
-						// Remove/Modify Lookup
-						*cumulative_weight =
-							cumulative_weight.saturating_add(T::DbWeight::get().writes(1));
-					}
-					if s.maybe_periodic.is_some() {
-						// Read/Write Agenda for future block
-						*cumulative_weight =
-							cumulative_weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
-					}
-
-					Some((order, index, *cumulative_weight, s))
-				})
-				.filter_map(|(order, index, cumulative_weight, mut s)| {
-					// We allow a scheduled call if any is true:
-					// - It's priority is `HARD_DEADLINE`
-					// - It does not push the weight past the limit.
-					// - It is the first item in the schedule
-					if s.priority <= schedule::HARD_DEADLINE
-						|| cumulative_weight <= limit
-						|| order == 0
-					{
-						let r = s.call.clone().dispatch(s.origin.clone().into());
-						let maybe_id = s.maybe_id.clone();
-						if let &Some((period, count)) = &s.maybe_periodic {
-							if count > 1 {
-								s.maybe_periodic = Some((period, count - 1));
-							} else {
-								s.maybe_periodic = None;
-							}
-							let next = now + period;
-							// If scheduled is named, place it's information in `Lookup`
-							if let Some(ref id) = s.maybe_id {
-								let next_index = Agenda::<T>::decode_len(now + period).unwrap_or(0);
-								Lookup::<T>::insert(id, (next, next_index as u32));
-							}
-							Agenda::<T>::append(next, Some(s));
-						} else {
-							if let Some(ref id) = s.maybe_id {
-								Lookup::<T>::remove(id);
-							}
-						}
-						Self::deposit_event(Event::Dispatched(
-							(now, index),
-							maybe_id,
-							r.map(|_| ()).map_err(|e| e.error),
-						));
-						total_weight = cumulative_weight;
-						None
-					} else {
-						Some(Some(s))
-					}
-				})
-				.for_each(|unused| {
-					let next = now + One::one();
-					Agenda::<T>::append(next, unused);
-				});
-
-			total_weight
+			let mut weight = WeightMeter::from_limit(T::MaximumWeight::get());
+			Self::service_agendas(&mut weight, now);
+			weight.consumed()
 		}
 	}
 
@@ -360,7 +326,7 @@ pub mod pallet {
 				maybe_periodic,
 				priority,
 				origin.caller().clone(),
-				*call,
+				T::Preimages::bound(*call)?,
 			)?;
 			Ok(())
 		}
@@ -410,7 +376,7 @@ pub mod pallet {
 				maybe_periodic,
 				priority,
 				origin.caller().clone(),
-				*call,
+				T::Preimages::bound(*call)?,
 			)?;
 			Ok(())
 		}
@@ -453,7 +419,7 @@ pub mod pallet {
 				maybe_periodic,
 				priority,
 				origin.caller().clone(),
-				*call,
+				T::Preimages::bound(*call)?,
 			)?;
 			Ok(())
 		}
@@ -480,7 +446,7 @@ pub mod pallet {
 				maybe_periodic,
 				priority,
 				origin.caller().clone(),
-				*call,
+				T::Preimages::bound(*call)?,
 			)?;
 			Ok(())
 		}
@@ -488,25 +454,50 @@ pub mod pallet {
 }
 
 impl<T: Config> Pallet<T> {
-	/// Migrate storage format from V1 to V2.
+	/// Migrate storage format from V1 to V3.
 	/// Return true if migration is performed.
-	pub fn migrate_v1_to_t2() -> bool {
+	pub fn migrate_v1_to_v3() -> bool {
 		if StorageVersion::<T>::get() == Releases::V1 {
-			StorageVersion::<T>::put(Releases::V2);
+			StorageVersion::<T>::put(Releases::V3);
 
 			Agenda::<T>::translate::<
 				Vec<Option<ScheduledV1<<T as Config>::Call, T::BlockNumber>>>, _
 			>(|_, agenda| Some(
 				agenda
 					.into_iter()
-					.map(|schedule| schedule.map(|schedule| ScheduledV2 {
+					.map(|schedule| schedule.and_then(|schedule| Self::bound_task(ScheduledV2 {
 						maybe_id: schedule.maybe_id,
 						priority: schedule.priority,
 						call: schedule.call,
 						maybe_periodic: schedule.maybe_periodic,
 						origin: system::RawOrigin::Root.into(),
 						_phantom: Default::default(),
-					}))
+					})))
+					.collect::<Vec<_>>()
+			));
+
+			true
+		} else {
+			false
+		}
+	}
+
+	/// Migrate storage format from V2 to V3, which holds small calls inline and notes the
+	/// others as preimages.
+	/// Return true if migration is performed.
+	pub fn migrate_v2_to_v3() -> bool {
+		if StorageVersion::<T>::get() == Releases::V2 {
+			StorageVersion::<T>::put(Releases::V3);
+
+			Agenda::<T>::translate::<
+				Vec<Option<ScheduledV2<
+					<T as Config>::Call, T::BlockNumber, T::PalletsOrigin, T::AccountId,
+				>>>,
+				_,
+			>(|_, agenda| Some(
+				agenda
+					.into_iter()
+					.map(|schedule| schedule.and_then(Self::bound_task))
 					.collect::<Vec<_>>()
 			));
 
@@ -516,6 +507,37 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Bound the call of a task stored by an earlier version of the pallet.
+	///
+	/// The task is dropped if its call is too large to be held inline and can not be noted as a
+	/// preimage either.
+	fn bound_task(
+		task: ScheduledV2<<T as Config>::Call, T::BlockNumber, T::PalletsOrigin, T::AccountId>,
+	) -> Option<ScheduledOf<T>> {
+		let call = match T::Preimages::bound(task.call) {
+			Ok(call) => call,
+			Err(_) => {
+				log::error!(
+					target: "runtime::scheduler",
+					"Dropping task {:?} whose call could not be bounded.",
+					task.maybe_id,
+				);
+				if let Some(id) = task.maybe_id {
+					Lookup::<T>::remove(id);
+				}
+				return None;
+			},
+		};
+		Some(Scheduled {
+			maybe_id: task.maybe_id,
+			priority: task.priority,
+			call,
+			maybe_periodic: task.maybe_periodic,
+			origin: task.origin,
+			_phantom: Default::default(),
+		})
+	}
+
 	/// Helper to migrate scheduler when the pallet origin type has changed.
 	pub fn migrate_origin<OldOrigin: Into<T::PalletsOrigin> + codec::Decode>() {
 		Agenda::<T>::translate::<
@@ -535,6 +557,224 @@ impl<T: Config> Pallet<T> {
 		));
 	}
 
+	/// The weight of reading and writing a single storage item, used as the base weight of
+	/// servicing agendas, once for `IncompleteSince` and once per `Agenda`.
+	fn service_base_weight() -> Weight {
+		T::DbWeight::get().reads_writes(1, 1)
+	}
+
+	/// The weight of looking up `call` before its task is executed, i.e. reading and decoding its
+	/// preimage. Legacy calls of unknown length are charged for the largest possible preimage.
+	fn lookup_weight(call: &Bounded<<T as Config>::Call>) -> Weight {
+		if !call.lookup_needed() {
+			return 0
+		}
+		let len = call.lookup_len().unwrap_or(T::Preimages::MAX_LENGTH as u32);
+		// roughly one nanosecond per byte to read and decode the preimage.
+		T::DbWeight::get().reads(1).saturating_add((len as Weight).saturating_mul(1_000))
+	}
+
+	/// The weight of handling a task whose call could not be looked up: reading the preimage and
+	/// moving the task to a later agenda.
+	fn lookup_failed_weight() -> Weight {
+		T::DbWeight::get().reads_writes(2, 2)
+	}
+
+	/// The weight of executing `task` with its looked up `call`, including the bookkeeping
+	/// around it.
+	fn task_weight(task: &ScheduledOf<T>, call: &<T as Config>::Call) -> Weight {
+		let mut weight = call.get_dispatch_info().weight;
+		let origin =
+			<<T as Config>::Origin as From<T::PalletsOrigin>>::from(task.origin.clone()).into();
+		if ensure_signed(origin).is_ok() {
+			// AccountData for inner call origin accountdata.
+			weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+		}
+		if task.maybe_id.is_some() {
+			// Remove/Modify Lookup
+			weight = weight.saturating_add(T::DbWeight::get().writes(1));
+		}
+		if task.maybe_periodic.is_some() {
+			// Read/Write Agenda for future block
+			weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+		}
+		if task.call.lookup_needed() {
+			// Release the preimage, it was read by the lookup already
+			weight = weight.saturating_add(T::DbWeight::get().writes(1));
+		}
+		weight
+	}
+
+	/// Service the agendas from the earliest incomplete one up to and including `now`, for as
+	/// long as `weight` allows. Records the earliest agenda left incomplete, if any.
+	fn service_agendas(weight: &mut WeightMeter, now: T::BlockNumber) {
+		if weight.try_consume(Self::service_base_weight()).is_err() {
+			return;
+		}
+
+		let mut incomplete_since = now.saturating_add(One::one());
+		let mut when = IncompleteSince::<T>::take().unwrap_or(now);
+		while when <= now && weight.try_consume(Self::service_base_weight()).is_ok() {
+			if !Self::service_agenda(weight, now, when) {
+				incomplete_since = incomplete_since.min(when);
+			}
+			when = when.saturating_add(One::one());
+		}
+		// agendas we did not get to at all are incomplete as well.
+		incomplete_since = incomplete_since.min(when);
+
+		if incomplete_since <= now {
+			IncompleteSince::<T>::put(incomplete_since);
+		}
+	}
+
+	/// Execute the tasks of the agenda at `when` in order of priority, for as long as they fit
+	/// into `weight`. The remaining tasks keep their place in the agenda, so that their address
+	/// stays valid.
+	///
+	/// Returns `false` if any task was postponed.
+	fn service_agenda(
+		weight: &mut WeightMeter,
+		now: T::BlockNumber,
+		when: T::BlockNumber,
+	) -> bool {
+		let mut agenda = Agenda::<T>::get(when);
+		if agenda.len() as u32 > T::MaxScheduledPerBlock::get() {
+			log::warn!(
+				target: "runtime::scheduler",
+				"Warning: This block has more items queued in Scheduler than \
+				expected from the runtime configuration. An update might be needed."
+			);
+		}
+
+		let mut ordered = agenda
+			.iter()
+			.enumerate()
+			.filter_map(|(index, s)| s.as_ref().map(|s| (index, s.priority)))
+			.collect::<Vec<_>>();
+		ordered.sort_by_key(|&(_, priority)| priority);
+
+		// A task heavier than this does not fit into any block.
+		let max_task_weight = weight.limit().saturating_sub(2 * Self::service_base_weight());
+		let mut complete = true;
+		for (index, _) in ordered {
+			let task = match agenda[index].take() {
+				Some(task) => task,
+				None => continue,
+			};
+			// The lookup is paid for up front, since the preimage is read and decoded whether or
+			// not the task ends up being executed.
+			let lookup_weight = Self::lookup_weight(&task.call);
+			if weight.try_consume(lookup_weight).is_err() {
+				agenda[index] = Some(task);
+				complete = false;
+				break;
+			}
+			let call = match T::Preimages::peek(&task.call) {
+				Ok((call, _)) => call,
+				Err(_) => {
+					if weight.try_consume(Self::lookup_failed_weight()).is_ok() {
+						Self::handle_lookup_failure(now, (when, index as u32), task);
+						continue;
+					}
+					agenda[index] = Some(task);
+					complete = false;
+					break;
+				},
+			};
+			let task_weight = Self::task_weight(&task, &call);
+			if task_weight.saturating_add(lookup_weight) > max_task_weight {
+				Self::deposit_event(Event::PermanentlyOverweight(
+					(when, index as u32),
+					task.maybe_id.clone(),
+				));
+				agenda[index] = Some(task);
+			} else if weight.try_consume(task_weight).is_ok() {
+				Self::execute_task(now, (when, index as u32), task, call);
+			} else {
+				// Lower priority tasks must not overtake this one, so stop here.
+				agenda[index] = Some(task);
+				complete = false;
+				break;
+			}
+		}
+
+		if agenda.iter().any(|s| s.is_some()) {
+			Agenda::<T>::insert(when, agenda);
+		} else {
+			Agenda::<T>::remove(when);
+		}
+		complete
+	}
+
+	/// Postpone `task` by `NoPreimagePostponement` blocks if the preimage of its call is unknown,
+	/// or drop it otherwise.
+	fn handle_lookup_failure(
+		now: T::BlockNumber,
+		address: TaskAddress<T::BlockNumber>,
+		task: ScheduledOf<T>,
+	) {
+		let error = if T::Preimages::have(&task.call) {
+			LookupError::BadFormat
+		} else {
+			LookupError::Unknown
+		};
+		Self::deposit_event(Event::CallLookupFailed(address, task.maybe_id.clone(), error));
+
+		match (error, T::NoPreimagePostponement::get()) {
+			(LookupError::Unknown, Some(delay)) => {
+				// never into the agenda being serviced, it is written back afterwards.
+				let until = now.saturating_add(delay.max(One::one()));
+				if let Some(ref id) = task.maybe_id {
+					let index = Agenda::<T>::decode_len(until).unwrap_or(0);
+					Lookup::<T>::insert(id, (until, index as u32));
+				}
+				Agenda::<T>::append(until, Some(task));
+			},
+			_ => {
+				if let Some(ref id) = task.maybe_id {
+					Lookup::<T>::remove(id);
+				}
+				T::Preimages::drop(&task.call);
+			},
+		}
+	}
+
+	/// Dispatch the `call` of `task`, rescheduling the task if it is periodic.
+	fn execute_task(
+		now: T::BlockNumber,
+		address: TaskAddress<T::BlockNumber>,
+		mut task: ScheduledOf<T>,
+		call: <T as Config>::Call,
+	) {
+		let r = call.dispatch(task.origin.clone().into());
+		let maybe_id = task.maybe_id.clone();
+		if let &Some((period, count)) = &task.maybe_periodic {
+			if count > 1 {
+				task.maybe_periodic = Some((period, count - 1));
+			} else {
+				task.maybe_periodic = None;
+			}
+			let next = now + period;
+			// If scheduled is named, place it's information in `Lookup`
+			if let Some(ref id) = task.maybe_id {
+				let next_index = Agenda::<T>::decode_len(next).unwrap_or(0);
+				Lookup::<T>::insert(id, (next, next_index as u32));
+			}
+			Agenda::<T>::append(next, Some(task));
+		} else {
+			if let Some(ref id) = task.maybe_id {
+				Lookup::<T>::remove(id);
+			}
+			T::Preimages::drop(&task.call);
+		}
+		Self::deposit_event(Event::Dispatched(
+			address,
+			maybe_id,
+			r.map(|_| ()).map_err(|e| e.error),
+		));
+	}
+
 	fn resolve_time(when: DispatchTime<T::BlockNumber>) -> Result<T::BlockNumber, DispatchError> {
 		let now = frame_system::Pallet::<T>::block_number();
 
@@ -557,7 +797,7 @@ impl<T: Config> Pallet<T> {
 		maybe_periodic: Option<schedule::Period<T::BlockNumber>>,
 		priority: schedule::Priority,
 		origin: T::PalletsOrigin,
-		call: <T as Config>::Call,
+		call: Bounded<<T as Config>::Call>,
 	) -> Result<TaskAddress<T::BlockNumber>, DispatchError> {
 		let when = Self::resolve_time(when)?;
 
@@ -588,6 +828,18 @@ impl<T: Config> Pallet<T> {
 		Ok((when, index))
 	}
 
+	/// Ensure that `origin` has at least the privilege of `scheduled_origin`, the origin which
+	/// scheduled a task.
+	fn ensure_privilege(
+		origin: &T::PalletsOrigin,
+		scheduled_origin: &T::PalletsOrigin,
+	) -> Result<(), DispatchError> {
+		match T::OriginPrivilegeCmp::cmp_privilege(origin, scheduled_origin) {
+			Some(Ordering::Greater) | Some(Ordering::Equal) => Ok(()),
+			_ => Err(BadOrigin.into()),
+		}
+	}
+
 	fn do_cancel(
 		origin: Option<T::PalletsOrigin>,
 		(when, index): TaskAddress<T::BlockNumber>,
@@ -597,15 +849,14 @@ impl<T: Config> Pallet<T> {
 				Ok(None),
 				|s| -> Result<Option<Scheduled<_, _, _, _>>, DispatchError> {
 					if let (Some(ref o), Some(ref s)) = (origin, s.borrow()) {
-						if *o != s.origin {
-							return Err(BadOrigin.into());
-						}
+						Self::ensure_privilege(o, &s.origin)?;
 					};
 					Ok(s.take())
 				},
 			)
 		})?;
 		if let Some(s) = scheduled {
+			T::Preimages::drop(&s.call);
 			if let Some(id) = s.maybe_id {
 				Lookup::<T>::remove(id);
 			}
@@ -646,7 +897,7 @@ impl<T: Config> Pallet<T> {
 		maybe_periodic: Option<schedule::Period<T::BlockNumber>>,
 		priority: schedule::Priority,
 		origin: T::PalletsOrigin,
-		call: <T as Config>::Call,
+		call: Bounded<<T as Config>::Call>,
 	) -> Result<TaskAddress<T::BlockNumber>, DispatchError> {
 		// ensure id it is unique
 		if Lookup::<T>::contains_key(&id) {
@@ -692,11 +943,11 @@ impl<T: Config> Pallet<T> {
 				Agenda::<T>::try_mutate(when, |agenda| -> DispatchResult {
 					if let Some(s) = agenda.get_mut(i) {
 						if let (Some(ref o), Some(ref s)) = (origin, s.borrow()) {
-							if *o != s.origin {
-								return Err(BadOrigin.into());
-							}
+							Self::ensure_privilege(o, &s.origin)?;
+						}
+						if let Some(s) = s.take() {
+							T::Preimages::drop(&s.call);
 						}
-						*s = None;
 					}
 					Ok(())
 				})?;
@@ -743,7 +994,10 @@ impl<T: Config> Pallet<T> {
 	}
 }
 
-impl<T: Config> schedule::Anon<T::BlockNumber, <T as Config>::Call, T::PalletsOrigin>
+/// The call is given as a [`Bounded`] value, i.e. either inline or by the hash of its preimage.
+/// The scheduler takes over the hold on the preimage, if any, and drops it once the task is
+/// finished or canceled. The preimage only has to be available when the task is due.
+impl<T: Config> schedule::Anon<T::BlockNumber, Bounded<<T as Config>::Call>, T::PalletsOrigin>
 	for Pallet<T>
 {
 	type Address = TaskAddress<T::BlockNumber>;
@@ -753,7 +1007,7 @@ impl<T: Config> schedule::Anon<T::BlockNumber, <T as Config>::Call, T::PalletsOr
 		maybe_periodic: Option<schedule::Period<T::BlockNumber>>,
 		priority: schedule::Priority,
 		origin: T::PalletsOrigin,
-		call: <T as Config>::Call,
+		call: Bounded<<T as Config>::Call>,
 	) -> Result<Self::Address, DispatchError> {
 		Self::do_schedule(when, maybe_periodic, priority, origin, call)
 	}
@@ -777,7 +1031,8 @@ impl<T: Config> schedule::Anon<T::BlockNumber, <T as Config>::Call, T::PalletsOr
 	}
 }
 
-impl<T: Config> schedule::Named<T::BlockNumber, <T as Config>::Call, T::PalletsOrigin>
+/// The call is handled like for [`schedule::Anon`].
+impl<T: Config> schedule::Named<T::BlockNumber, Bounded<<T as Config>::Call>, T::PalletsOrigin>
 	for Pallet<T>
 {
 	type Address = TaskAddress<T::BlockNumber>;
@@ -788,7 +1043,7 @@ impl<T: Config> schedule::Named<T::BlockNumber, <T as Config>::Call, T::PalletsO
 		maybe_periodic: Option<schedule::Period<T::BlockNumber>>,
 		priority: schedule::Priority,
 		origin: T::PalletsOrigin,
-		call: <T as Config>::Call,
+		call: Bounded<<T as Config>::Call>,
 	) -> Result<Self::Address, ()> {
 		Self::do_schedule_named(id, when, maybe_periodic, priority, origin, call).map_err(|_| ())
 	}
@@ -818,7 +1073,7 @@ mod tests {
 	use crate as scheduler;
 	use frame_support::{
 		assert_err, assert_noop, assert_ok, ord_parameter_types, parameter_types,
-		traits::{Filter, OnFinalize, OnInitialize},
+		traits::{EqualPrivilegeOnly, FetchResult, Filter, OnFinalize, OnInitialize},
 		weights::constants::RocksDbWeight,
 		Hashable,
 	};
@@ -826,9 +1081,10 @@ mod tests {
 	use sp_core::H256;
 	use sp_runtime::{
 		testing::Header,
-		traits::{BlakeTwo256, IdentityLookup},
+		traits::{BlakeTwo256, Hash as HashT, IdentityLookup},
 		Perbill,
 	};
+	use std::{borrow::Cow, cell::RefCell, collections::BTreeMap};
 	use substrate_test_utils::assert_eq_uvec;
 
 	// Logger module to track execution.
@@ -945,6 +1201,7 @@ mod tests {
 	parameter_types! {
 		pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) * BlockWeights::get().max_block;
 		pub const MaxScheduledPerBlock: u32 = 10;
+		pub const NoPreimagePostponement: Option<u64> = Some(2);
 	}
 	ord_parameter_types! {
 		pub const One: u64 = 1;
@@ -957,8 +1214,67 @@ mod tests {
 		type Call = Call;
 		type MaximumWeight = MaximumSchedulerWeight;
 		type ScheduleOrigin = EnsureOneOf<u64, EnsureRoot<u64>, EnsureSignedBy<One, u64>>;
+		type OriginPrivilegeCmp = EqualPrivilegeOnly;
 		type MaxScheduledPerBlock = MaxScheduledPerBlock;
 		type WeightInfo = ();
+		type Preimages = TestPreimages;
+		type NoPreimagePostponement = NoPreimagePostponement;
+	}
+
+	thread_local! {
+		static PREIMAGES: RefCell<BTreeMap<H256, (Option<Vec<u8>>, u32)>> =
+			RefCell::new(BTreeMap::new());
+	}
+
+	/// Keeps preimages in memory, counting the requests of each of them.
+	pub struct TestPreimages;
+	impl QueryPreimage for TestPreimages {
+		fn len(hash: &H256) -> Option<u32> {
+			PREIMAGES.with(|p| {
+				p.borrow().get(hash).and_then(|(data, _)| data.as_ref().map(|d| d.len() as u32))
+			})
+		}
+
+		fn fetch(hash: &H256, _: Option<u32>) -> FetchResult {
+			PREIMAGES
+				.with(|p| p.borrow().get(hash).and_then(|(data, _)| data.clone()))
+				.map(Cow::Owned)
+				.ok_or(DispatchError::CannotLookup)
+		}
+
+		fn is_requested(hash: &H256) -> bool {
+			PREIMAGES.with(|p| p.borrow().contains_key(hash))
+		}
+
+		fn request(hash: &H256) {
+			PREIMAGES.with(|p| p.borrow_mut().entry(*hash).or_insert((None, 0)).1 += 1);
+		}
+
+		fn unrequest(hash: &H256) {
+			PREIMAGES.with(|p| {
+				let mut p = p.borrow_mut();
+				if let Some((_, count)) = p.get_mut(hash) {
+					*count -= 1;
+					if *count == 0 {
+						p.remove(hash);
+					}
+				}
+			});
+		}
+	}
+	impl StorePreimage for TestPreimages {
+		const MAX_LENGTH: usize = 4096;
+
+		fn note(bytes: Cow<[u8]>) -> Result<H256, DispatchError> {
+			let hash = BlakeTwo256::hash(&bytes[..]);
+			PREIMAGES.with(|p| {
+				let mut p = p.borrow_mut();
+				let entry = p.entry(hash).or_insert((None, 0));
+				entry.0 = Some(bytes.into_owned());
+				entry.1 += 1;
+			});
+			Ok(hash)
+		}
 	}
 
 	pub type LoggerCall = logger::Call<Test>;
@@ -980,6 +1296,16 @@ mod tests {
 		system::RawOrigin::Root.into()
 	}
 
+	fn bound(call: Call) -> Bounded<Call> {
+		TestPreimages::bound(call).unwrap()
+	}
+
+	/// Bounds `call` by the hash of its preimage, even if it could be held inline.
+	fn lookup(call: Call) -> Bounded<Call> {
+		let len = call.encoded_size() as u32;
+		Bounded::Lookup { hash: TestPreimages::note(call.encode().into()).unwrap(), len }
+	}
+
 	#[test]
 	fn basic_scheduling_works() {
 		new_test_ext().execute_with(|| {
@@ -987,7 +1313,7 @@ mod tests {
 			assert!(!<Test as frame_system::Config>::BaseCallFilter::filter(
 				&call
 			));
-			assert_ok!(Scheduler::do_schedule(DispatchTime::At(4), None, 127, root(), call));
+			assert_ok!(Scheduler::do_schedule(DispatchTime::At(4), None, 127, root(), bound(call)));
 			run_to_block(3);
 			assert!(logger::log().is_empty());
 			run_to_block(4);
@@ -1006,7 +1332,9 @@ mod tests {
 				&call
 			));
 			// This will schedule the call 3 blocks after the next block... so block 3 + 3 = 6
-			assert_ok!(Scheduler::do_schedule(DispatchTime::After(3), None, 127, root(), call));
+			assert_ok!(
+				Scheduler::do_schedule(DispatchTime::After(3), None, 127, root(), bound(call))
+			);
 			run_to_block(5);
 			assert!(logger::log().is_empty());
 			run_to_block(6);
@@ -1022,7 +1350,9 @@ mod tests {
 			run_to_block(2);
 			let call = Call::Logger(LoggerCall::log(42, 1000));
 			assert!(!<Test as frame_system::Config>::BaseCallFilter::filter(&call));
-			assert_ok!(Scheduler::do_schedule(DispatchTime::After(0), None, 127, root(), call));
+			assert_ok!(
+				Scheduler::do_schedule(DispatchTime::After(0), None, 127, root(), bound(call))
+			);
 			// Will trigger on the next block.
 			run_to_block(3);
 			assert_eq!(logger::log(), vec![(root(), 42u32)]);
@@ -1036,7 +1366,11 @@ mod tests {
 		new_test_ext().execute_with(|| {
 			// at #4, every 3 blocks, 3 times.
 			assert_ok!(Scheduler::do_schedule(
-				DispatchTime::At(4), Some((3, 3)), 127, root(), Call::Logger(logger::Call::log(42, 1000))
+				DispatchTime::At(4),
+				Some((3, 3)),
+				127,
+				root(),
+				bound(Call::Logger(logger::Call::log(42, 1000)))
 			));
 			run_to_block(3);
 			assert!(logger::log().is_empty());
@@ -1066,7 +1400,11 @@ mod tests {
 		new_test_ext().execute_with(|| {
 			let call = Call::Logger(LoggerCall::log(42, 1000));
 			assert!(!<Test as frame_system::Config>::BaseCallFilter::filter(&call));
-			assert_eq!(Scheduler::do_schedule(DispatchTime::At(4), None, 127, root(), call).unwrap(), (4, 0));
+			assert_eq!(
+				Scheduler::do_schedule(DispatchTime::At(4), None, 127, root(), bound(call))
+					.unwrap(),
+				(4, 0)
+			);
 
 			run_to_block(3);
 			assert!(logger::log().is_empty());
@@ -1092,7 +1430,7 @@ mod tests {
 			let call = Call::Logger(LoggerCall::log(42, 1000));
 			assert!(!<Test as frame_system::Config>::BaseCallFilter::filter(&call));
 			assert_eq!(Scheduler::do_schedule_named(
-				1u32.encode(), DispatchTime::At(4), None, 127, root(), call
+				1u32.encode(), DispatchTime::At(4), None, 127, root(), bound(call)
 			).unwrap(), (4, 0));
 
 			run_to_block(3);
@@ -1119,7 +1457,7 @@ mod tests {
 			let call = Call::Logger(LoggerCall::log(42, 1000));
 			assert!(!<Test as frame_system::Config>::BaseCallFilter::filter(&call));
 			assert_eq!(Scheduler::do_schedule_named(
-				1u32.encode(), DispatchTime::At(4), Some((3, 3)), 127, root(), call
+				1u32.encode(), DispatchTime::At(4), Some((3, 3)), 127, root(), bound(call)
 			).unwrap(), (4, 0));
 
 			run_to_block(3);
@@ -1155,10 +1493,19 @@ mod tests {
 		new_test_ext().execute_with(|| {
 			// at #4.
 			Scheduler::do_schedule_named(
-				1u32.encode(), DispatchTime::At(4), None, 127, root(), Call::Logger(LoggerCall::log(69, 1000))
+				1u32.encode(),
+				DispatchTime::At(4),
+				None,
+				127,
+				root(),
+				bound(Call::Logger(LoggerCall::log(69, 1000)))
 			).unwrap();
 			let i = Scheduler::do_schedule(
-				DispatchTime::At(4), None, 127, root(), Call::Logger(LoggerCall::log(42, 1000))
+				DispatchTime::At(4),
+				None,
+				127,
+				root(),
+				bound(Call::Logger(LoggerCall::log(42, 1000)))
 			).unwrap();
 			run_to_block(3);
 			assert!(logger::log().is_empty());
@@ -1179,7 +1526,7 @@ mod tests {
 				Some((3, 3)),
 				127,
 				root(),
-				Call::Logger(LoggerCall::log(42, 1000)),
+				bound(Call::Logger(LoggerCall::log(42, 1000))),
 			)
 			.unwrap();
 			// same id results in error.
@@ -1189,7 +1536,7 @@ mod tests {
 				None,
 				127,
 				root(),
-				Call::Logger(LoggerCall::log(69, 1000))
+				bound(Call::Logger(LoggerCall::log(69, 1000)))
 			)
 			.is_err());
 			// different id is ok.
@@ -1199,7 +1546,7 @@ mod tests {
 				None,
 				127,
 				root(),
-				Call::Logger(LoggerCall::log(69, 1000)),
+				bound(Call::Logger(LoggerCall::log(69, 1000))),
 			)
 			.unwrap();
 			run_to_block(3);
@@ -1221,14 +1568,14 @@ mod tests {
 				None,
 				127,
 				root(),
-				Call::Logger(LoggerCall::log(42, MaximumSchedulerWeight::get() / 2))
+				bound(Call::Logger(LoggerCall::log(42, MaximumSchedulerWeight::get() / 2)))
 			));
 			assert_ok!(Scheduler::do_schedule(
 				DispatchTime::At(4),
 				None,
 				127,
 				root(),
-				Call::Logger(LoggerCall::log(69, MaximumSchedulerWeight::get() / 2))
+				bound(Call::Logger(LoggerCall::log(69, MaximumSchedulerWeight::get() / 2)))
 			));
 			// 69 and 42 do not fit together
 			run_to_block(4);
@@ -1239,25 +1586,140 @@ mod tests {
 	}
 
 	#[test]
-	fn scheduler_respects_hard_deadlines_more() {
+	fn scheduler_does_not_exceed_weight_limit_for_hard_deadlines() {
 		new_test_ext().execute_with(|| {
 			assert_ok!(Scheduler::do_schedule(
 				DispatchTime::At(4),
 				None,
 				0,
 				root(),
-				Call::Logger(LoggerCall::log(42, MaximumSchedulerWeight::get() / 2))
+				bound(Call::Logger(LoggerCall::log(42, MaximumSchedulerWeight::get() / 2)))
 			));
 			assert_ok!(Scheduler::do_schedule(
 				DispatchTime::At(4),
 				None,
 				0,
 				root(),
-				Call::Logger(LoggerCall::log(69, MaximumSchedulerWeight::get() / 2))
+				bound(Call::Logger(LoggerCall::log(69, MaximumSchedulerWeight::get() / 2)))
+			));
+			// With base weights, 69 and 42 do not fit together, regardless of their priority
+			run_to_block(4);
+			assert_eq!(logger::log(), vec![(root(), 42u32)]);
+			run_to_block(5);
+			assert_eq!(logger::log(), vec![(root(), 42u32), (root(), 69u32)]);
+		});
+	}
+
+	#[test]
+	fn postponed_tasks_keep_their_address() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Scheduler::do_schedule(
+				DispatchTime::At(4),
+				None,
+				127,
+				root(),
+				bound(Call::Logger(LoggerCall::log(42, MaximumSchedulerWeight::get() / 2)))
+			));
+			assert_eq!(
+				Scheduler::do_schedule_named(
+					1u32.encode(),
+					DispatchTime::At(4),
+					None,
+					127,
+					root(),
+					bound(Call::Logger(LoggerCall::log(69, MaximumSchedulerWeight::get() / 2)))
+				).unwrap(),
+				(4, 1),
+			);
+			assert_ok!(Scheduler::do_schedule(
+				DispatchTime::At(5),
+				None,
+				127,
+				root(),
+				bound(Call::Logger(LoggerCall::log(2600, MaximumSchedulerWeight::get() / 2)))
 			));
-			// With base weights, 69 and 42 should not fit together, but do because of hard deadlines
+
 			run_to_block(4);
+			assert_eq!(logger::log(), vec![(root(), 42u32)]);
+			assert_eq!(IncompleteSince::<Test>::get(), Some(4));
+			assert_eq!(Lookup::<Test>::get(1u32.encode()), Some((4, 1)));
+			assert_eq!(
+				<Scheduler as schedule::Named<_, _, _>>::next_dispatch_time(1u32.encode()),
+				Ok(4),
+			);
+
+			// the postponed task is serviced before the agenda of block 5.
+			run_to_block(5);
 			assert_eq!(logger::log(), vec![(root(), 42u32), (root(), 69u32)]);
+			assert_eq!(IncompleteSince::<Test>::get(), Some(5));
+			assert_eq!(Lookup::<Test>::get(1u32.encode()), None);
+			assert!(!Agenda::<Test>::contains_key(4));
+
+			run_to_block(6);
+			assert_eq!(logger::log(), vec![(root(), 42u32), (root(), 69u32), (root(), 2600u32)]);
+			assert_eq!(IncompleteSince::<Test>::get(), None);
+			assert!(!Agenda::<Test>::contains_key(5));
+		});
+	}
+
+	#[test]
+	fn postponed_tasks_can_be_canceled() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Scheduler::do_schedule(
+				DispatchTime::At(4),
+				None,
+				127,
+				root(),
+				bound(Call::Logger(LoggerCall::log(42, MaximumSchedulerWeight::get() / 2)))
+			));
+			assert_ok!(Scheduler::do_schedule_named(
+				1u32.encode(),
+				DispatchTime::At(4),
+				None,
+				127,
+				root(),
+				bound(Call::Logger(LoggerCall::log(69, MaximumSchedulerWeight::get() / 2)))
+			));
+			run_to_block(4);
+			assert_eq!(IncompleteSince::<Test>::get(), Some(4));
+
+			assert_ok!(Scheduler::do_cancel_named(None, 1u32.encode()));
+			run_to_block(100);
+			assert_eq!(logger::log(), vec![(root(), 42u32)]);
+			assert_eq!(IncompleteSince::<Test>::get(), None);
+			assert_eq!(Agenda::<Test>::iter().count(), 0);
+		});
+	}
+
+	#[test]
+	fn permanently_overweight_tasks_do_not_block_the_agenda() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Scheduler::do_schedule(
+				DispatchTime::At(4),
+				None,
+				0,
+				root(),
+				bound(Call::Logger(LoggerCall::log(42, MaximumSchedulerWeight::get())))
+			));
+			assert_ok!(Scheduler::do_schedule(
+				DispatchTime::At(4),
+				None,
+				127,
+				root(),
+				bound(Call::Logger(LoggerCall::log(69, 1000)))
+			));
+			run_to_block(4);
+			assert_eq!(logger::log(), vec![(root(), 69u32)]);
+			System::assert_has_event(Event::scheduler(
+				crate::Event::PermanentlyOverweight((4, 0), None)
+			));
+			assert_eq!(IncompleteSince::<Test>::get(), None);
+
+			// the overweight task stays until canceled.
+			run_to_block(10);
+			assert_eq!(logger::log(), vec![(root(), 69u32)]);
+			assert!(Agenda::<Test>::get(4)[0].is_some());
+			assert_ok!(Scheduler::do_cancel(None, (4, 0)));
 		});
 	}
 
@@ -1269,16 +1731,19 @@ mod tests {
 				None,
 				1,
 				root(),
-				Call::Logger(LoggerCall::log(42, MaximumSchedulerWeight::get() / 2))
+				bound(Call::Logger(LoggerCall::log(42, MaximumSchedulerWeight::get() / 2)))
 			));
 			assert_ok!(Scheduler::do_schedule(
 				DispatchTime::At(4),
 				None,
 				0,
 				root(),
-				Call::Logger(LoggerCall::log(69, MaximumSchedulerWeight::get() / 2))
+				bound(Call::Logger(LoggerCall::log(69, MaximumSchedulerWeight::get() / 2)))
 			));
+			// 42 does not fit next to 69, so it is postponed.
 			run_to_block(4);
+			assert_eq!(logger::log(), vec![(root(), 69u32)]);
+			run_to_block(5);
 			assert_eq!(logger::log(), vec![(root(), 69u32), (root(), 42u32)]);
 		});
 	}
@@ -1291,21 +1756,21 @@ mod tests {
 				None,
 				255,
 				root(),
-				Call::Logger(LoggerCall::log(42, MaximumSchedulerWeight::get() / 3))
+				bound(Call::Logger(LoggerCall::log(42, MaximumSchedulerWeight::get() / 3)))
 			));
 			assert_ok!(Scheduler::do_schedule(
 				DispatchTime::At(4),
 				None,
 				127,
 				root(),
-				Call::Logger(LoggerCall::log(69, MaximumSchedulerWeight::get() / 2))
+				bound(Call::Logger(LoggerCall::log(69, MaximumSchedulerWeight::get() / 2)))
 			));
 			assert_ok!(Scheduler::do_schedule(
 				DispatchTime::At(4),
 				None,
 				126,
 				root(),
-				Call::Logger(LoggerCall::log(2600, MaximumSchedulerWeight::get() / 2))
+				bound(Call::Logger(LoggerCall::log(2600, MaximumSchedulerWeight::get() / 2)))
 			));
 
 			// 2600 does not fit with 69 or 42, but has higher priority, so will go through
@@ -1324,8 +1789,7 @@ mod tests {
 	fn on_initialize_weight_is_correct() {
 		new_test_ext().execute_with(|| {
 			let base_weight: Weight =
-				<Test as frame_system::Config>::DbWeight::get().reads_writes(1, 2);
-			let base_multiplier = 0;
+				<Test as frame_system::Config>::DbWeight::get().reads_writes(1, 1);
 			let named_multiplier = <Test as frame_system::Config>::DbWeight::get().writes(1);
 			let periodic_multiplier =
 				<Test as frame_system::Config>::DbWeight::get().reads_writes(1, 1);
@@ -1337,7 +1801,7 @@ mod tests {
 				None,
 				255,
 				root(),
-				Call::Logger(LoggerCall::log(3, MaximumSchedulerWeight::get() / 3))
+				bound(Call::Logger(LoggerCall::log(3, MaximumSchedulerWeight::get() / 3)))
 			));
 			// Anon Periodic
 			assert_ok!(Scheduler::do_schedule(
@@ -1345,7 +1809,7 @@ mod tests {
 				Some((1000, 3)),
 				128,
 				root(),
-				Call::Logger(LoggerCall::log(42, MaximumSchedulerWeight::get() / 3))
+				bound(Call::Logger(LoggerCall::log(42, MaximumSchedulerWeight::get() / 3)))
 			));
 			// Anon
 			assert_ok!(Scheduler::do_schedule(
@@ -1353,7 +1817,7 @@ mod tests {
 				None,
 				127,
 				root(),
-				Call::Logger(LoggerCall::log(69, MaximumSchedulerWeight::get() / 2))
+				bound(Call::Logger(LoggerCall::log(69, MaximumSchedulerWeight::get() / 2)))
 			));
 			// Named Periodic
 			assert_ok!(Scheduler::do_schedule_named(
@@ -1362,39 +1826,31 @@ mod tests {
 				Some((1000, 3)),
 				126,
 				root(),
-				Call::Logger(LoggerCall::log(2600, MaximumSchedulerWeight::get() / 2))
+				bound(Call::Logger(LoggerCall::log(2600, MaximumSchedulerWeight::get() / 2)))
 			));
 
-			// Will include the named periodic only
+			// Will include the named periodic only; IncompleteSince and one agenda.
 			let actual_weight = Scheduler::on_initialize(1);
 			let call_weight = MaximumSchedulerWeight::get() / 2;
 			assert_eq!(
 				actual_weight,
-				call_weight
-					+ base_weight + base_multiplier
-					+ named_multiplier + periodic_multiplier
+				call_weight + base_weight * 2 + named_multiplier + periodic_multiplier
 			);
 			assert_eq!(logger::log(), vec![(root(), 2600u32)]);
 
-			// Will include anon and anon periodic
+			// Will include anon and anon periodic; agendas of blocks 1 and 2.
 			let actual_weight = Scheduler::on_initialize(2);
 			let call_weight = MaximumSchedulerWeight::get() / 2 + MaximumSchedulerWeight::get() / 3;
-			assert_eq!(
-				actual_weight,
-				call_weight + base_weight + base_multiplier * 2 + periodic_multiplier
-			);
+			assert_eq!(actual_weight, call_weight + base_weight * 3 + periodic_multiplier);
 			assert_eq!(
 				logger::log(),
 				vec![(root(), 2600u32), (root(), 69u32), (root(), 42u32)]
 			);
 
-			// Will include named only
+			// Will include named only; agendas of blocks 1 to 3.
 			let actual_weight = Scheduler::on_initialize(3);
 			let call_weight = MaximumSchedulerWeight::get() / 3;
-			assert_eq!(
-				actual_weight,
-				call_weight + base_weight + base_multiplier + named_multiplier
-			);
+			assert_eq!(actual_weight, call_weight + base_weight * 4 + named_multiplier);
 			assert_eq!(
 				logger::log(),
 				vec![
@@ -1405,9 +1861,9 @@ mod tests {
 				]
 			);
 
-			// Will contain none
+			// Will contain none but the base weight of the agenda of block 4.
 			let actual_weight = Scheduler::on_initialize(4);
-			assert_eq!(actual_weight, 0);
+			assert_eq!(actual_weight, base_weight * 2);
 		});
 	}
 
@@ -1572,7 +2028,141 @@ mod tests {
 	}
 
 	#[test]
-	fn migration_to_v2_works() {
+	fn scheduling_by_preimage_works() {
+		new_test_ext().execute_with(|| {
+			let call = Call::Logger(LoggerCall::log(42, 1000));
+			let hash = BlakeTwo256::hash_of(&call);
+			assert_ok!(
+				Scheduler::do_schedule(DispatchTime::At(4), None, 127, root(), lookup(call))
+			);
+			assert!(TestPreimages::is_requested(&hash));
+			run_to_block(3);
+			assert!(logger::log().is_empty());
+			run_to_block(4);
+			assert_eq!(logger::log(), vec![(root(), 42u32)]);
+			// the preimage is released once the task is done.
+			assert!(!TestPreimages::is_requested(&hash));
+		});
+	}
+
+	#[test]
+	fn large_calls_are_noted_as_preimages() {
+		new_test_ext().execute_with(|| {
+			let call = Call::System(frame_system::Call::remark(vec![0; 256]));
+			let hash = BlakeTwo256::hash_of(&call);
+			assert_ok!(Scheduler::schedule(Origin::signed(1), 4, None, 127, Box::new(call)));
+			assert!(Agenda::<Test>::get(4)[0].as_ref().unwrap().call.lookup_needed());
+			assert!(TestPreimages::is_requested(&hash));
+			run_to_block(4);
+			System::assert_has_event(Event::scheduler(
+				crate::Event::Dispatched((4, 0), None, Ok(()))
+			));
+			assert!(!TestPreimages::is_requested(&hash));
+		});
+	}
+
+	#[test]
+	fn preimage_lookup_is_paid_for() {
+		new_test_ext().execute_with(|| {
+			let call = Call::Logger(LoggerCall::log(42, 1000));
+			let len = call.encoded_size() as Weight;
+			assert_ok!(
+				Scheduler::do_schedule(DispatchTime::At(1), None, 127, root(), lookup(call))
+			);
+			let db_weight = <Test as frame_system::Config>::DbWeight::get();
+			let base_weight = db_weight.reads_writes(1, 1);
+			// reading the preimage, then releasing it.
+			let lookup_weight = db_weight.reads(1) + len * 1_000 + db_weight.writes(1);
+			assert_eq!(Scheduler::on_initialize(1), 1000 + base_weight * 2 + lookup_weight);
+			assert_eq!(logger::log(), vec![(root(), 42u32)]);
+		});
+	}
+
+	#[test]
+	fn missing_preimage_postpones_task() {
+		new_test_ext().execute_with(|| {
+			let call = Call::Logger(LoggerCall::log(42, 1000));
+			let len = call.encoded_size() as u32;
+			let hash = BlakeTwo256::hash_of(&call);
+			assert_ok!(Scheduler::do_schedule_named(
+				1u32.encode(),
+				DispatchTime::At(4),
+				None,
+				127,
+				root(),
+				TestPreimages::pick(hash, len)
+			));
+			run_to_block(4);
+			assert!(logger::log().is_empty());
+			System::assert_has_event(Event::scheduler(crate::Event::CallLookupFailed(
+				(4, 0),
+				Some(1u32.encode()),
+				LookupError::Unknown,
+			)));
+			// postponed by `NoPreimagePostponement`, keeping track of its new address.
+			assert_eq!(Lookup::<Test>::get(1u32.encode()), Some((6, 0)));
+			assert!(Agenda::<Test>::get(4).is_empty());
+
+			assert_ok!(TestPreimages::note(call.encode().into()));
+			run_to_block(6);
+			assert_eq!(logger::log(), vec![(root(), 42u32)]);
+			assert_eq!(Lookup::<Test>::get(1u32.encode()), None);
+		});
+	}
+
+	#[test]
+	fn badly_formatted_calls_are_dropped() {
+		new_test_ext().execute_with(|| {
+			let hash = TestPreimages::note(vec![255u8; 8].into()).unwrap();
+			assert_ok!(Scheduler::do_schedule(
+				DispatchTime::At(4),
+				None,
+				127,
+				root(),
+				Bounded::Lookup { hash, len: 8 }
+			));
+			run_to_block(4);
+			System::assert_has_event(Event::scheduler(crate::Event::CallLookupFailed(
+				(4, 0),
+				None,
+				LookupError::BadFormat,
+			)));
+			assert!(Agenda::<Test>::iter().next().is_none());
+			assert!(!TestPreimages::is_requested(&hash));
+		});
+	}
+
+	#[test]
+	fn canceling_releases_preimage() {
+		new_test_ext().execute_with(|| {
+			let call = Call::Logger(LoggerCall::log(42, 1000));
+			let hash = BlakeTwo256::hash_of(&call);
+			assert_ok!(Scheduler::do_schedule(
+				DispatchTime::At(4),
+				None,
+				127,
+				root(),
+				lookup(call.clone())
+			));
+			assert_ok!(Scheduler::do_schedule_named(
+				1u32.encode(),
+				DispatchTime::At(4),
+				None,
+				127,
+				root(),
+				lookup(call)
+			));
+			assert_ok!(Scheduler::do_cancel(None, (4, 0)));
+			assert!(TestPreimages::is_requested(&hash));
+			assert_ok!(Scheduler::do_cancel_named(None, 1u32.encode()));
+			assert!(!TestPreimages::is_requested(&hash));
+			run_to_block(4);
+			assert!(logger::log().is_empty());
+		});
+	}
+
+	#[test]
+	fn migration_to_v3_works() {
 		new_test_ext().execute_with(|| {
 			for i in 0..3u64 {
 				let k = i.twox_64_concat();
@@ -1596,7 +2186,7 @@ mod tests {
 
 			assert_eq!(StorageVersion::<Test>::get(), Releases::V1);
 
-			assert!(Scheduler::migrate_v1_to_t2());
+			assert!(Scheduler::migrate_v1_to_v3());
 
 			assert_eq_uvec!(
 				Agenda::<Test>::iter().collect::<Vec<_>>(),
@@ -1607,7 +2197,7 @@ mod tests {
 							Some(ScheduledV2 {
 								maybe_id: None,
 								priority: 10,
-								call: Call::Logger(LoggerCall::log(96, 100)),
+								call: bound(Call::Logger(LoggerCall::log(96, 100))),
 								maybe_periodic: None,
 								origin: root(),
 								_phantom: PhantomData::<u64>::default(),
@@ -1616,7 +2206,7 @@ mod tests {
 							Some(ScheduledV2 {
 								maybe_id: Some(b"test".to_vec()),
 								priority: 123,
-								call: Call::Logger(LoggerCall::log(69, 1000)),
+								call: bound(Call::Logger(LoggerCall::log(69, 1000))),
 								maybe_periodic: Some((456u64, 10)),
 								origin: root(),
 								_phantom: PhantomData::<u64>::default(),
@@ -1629,7 +2219,7 @@ mod tests {
 							Some(ScheduledV2 {
 								maybe_id: None,
 								priority: 11,
-								call: Call::Logger(LoggerCall::log(96, 100)),
+								call: bound(Call::Logger(LoggerCall::log(96, 100))),
 								maybe_periodic: None,
 								origin: root(),
 								_phantom: PhantomData::<u64>::default(),
@@ -1638,7 +2228,7 @@ mod tests {
 							Some(ScheduledV2 {
 								maybe_id: Some(b"test".to_vec()),
 								priority: 123,
-								call: Call::Logger(LoggerCall::log(69, 1000)),
+								call: bound(Call::Logger(LoggerCall::log(69, 1000))),
 								maybe_periodic: Some((456u64, 10)),
 								origin: root(),
 								_phantom: PhantomData::<u64>::default(),
@@ -1651,7 +2241,7 @@ mod tests {
 							Some(ScheduledV2 {
 								maybe_id: None,
 								priority: 12,
-								call: Call::Logger(LoggerCall::log(96, 100)),
+								call: bound(Call::Logger(LoggerCall::log(96, 100))),
 								maybe_periodic: None,
 								origin: root(),
 								_phantom: PhantomData::<u64>::default(),
@@ -1660,7 +2250,7 @@ mod tests {
 							Some(ScheduledV2 {
 								maybe_id: Some(b"test".to_vec()),
 								priority: 123,
-								call: Call::Logger(LoggerCall::log(69, 1000)),
+								call: bound(Call::Logger(LoggerCall::log(69, 1000))),
 								maybe_periodic: Some((456u64, 10)),
 								origin: root(),
 								_phantom: PhantomData::<u64>::default(),
@@ -1670,7 +2260,68 @@ mod tests {
 				]
 			);
 
-			assert_eq!(StorageVersion::<Test>::get(), Releases::V2);
+			assert_eq!(StorageVersion::<Test>::get(), Releases::V3);
+		});
+	}
+
+	#[test]
+	fn migration_v2_to_v3_works() {
+		new_test_ext().execute_with(|| {
+			let small = Call::Logger(LoggerCall::log(96, 100));
+			let large = Call::System(frame_system::Call::remark(vec![0; 256]));
+			let old: Vec<Option<ScheduledV2<Call, u64, OriginCaller, u64>>> = vec![
+				Some(ScheduledV2 {
+					maybe_id: None,
+					priority: 10,
+					call: small.clone(),
+					maybe_periodic: None,
+					origin: root(),
+					_phantom: PhantomData::<u64>::default(),
+				}),
+				None,
+				Some(ScheduledV2 {
+					maybe_id: Some(b"test".to_vec()),
+					priority: 123,
+					call: large.clone(),
+					maybe_periodic: Some((456u64, 10)),
+					origin: root(),
+					_phantom: PhantomData::<u64>::default(),
+				}),
+			];
+			let k = 0u64.twox_64_concat();
+			frame_support::migration::put_storage_value(b"Scheduler", b"Agenda", &k, old);
+			StorageVersion::<Test>::put(Releases::V2);
+
+			assert!(Scheduler::migrate_v2_to_v3());
+
+			// small calls are held inline, large ones are noted as preimages.
+			let hash = BlakeTwo256::hash_of(&large);
+			let len = large.encoded_size() as u32;
+			assert_eq!(
+				Agenda::<Test>::get(0),
+				vec![
+					Some(ScheduledV2 {
+						maybe_id: None,
+						priority: 10,
+						call: bound(small),
+						maybe_periodic: None,
+						origin: root(),
+						_phantom: PhantomData::<u64>::default(),
+					}),
+					None,
+					Some(ScheduledV2 {
+						maybe_id: Some(b"test".to_vec()),
+						priority: 123,
+						call: Bounded::Lookup { hash, len },
+						maybe_periodic: Some((456u64, 10)),
+						origin: root(),
+						_phantom: PhantomData::<u64>::default(),
+					}),
+				]
+			);
+			assert_eq!(TestPreimages::len(&hash), Some(len));
+			assert_eq!(StorageVersion::<Test>::get(), Releases::V3);
+			assert!(!Scheduler::migrate_v2_to_v3());
 		});
 	}
 
@@ -1683,7 +2334,7 @@ mod tests {
 					Some(Scheduled {
 						maybe_id: None,
 						priority: i as u8 + 10,
-						call: Call::Logger(LoggerCall::log(96, 100)),
+						call: bound(Call::Logger(LoggerCall::log(96, 100))),
 						origin: 3u32,
 						maybe_periodic: None,
 						_phantom: Default::default(),
@@ -1693,7 +2344,7 @@ mod tests {
 						maybe_id: Some(b"test".to_vec()),
 						priority: 123,
 						origin: 2u32,
-						call: Call::Logger(LoggerCall::log(69, 1000)),
+						call: bound(Call::Logger(LoggerCall::log(69, 1000))),
 						maybe_periodic: Some((456u64, 10)),
 						_phantom: Default::default(),
 					}),
@@ -1722,7 +2373,7 @@ mod tests {
 							Some(ScheduledV2::<_, _, OriginCaller, u64> {
 								maybe_id: None,
 								priority: 10,
-								call: Call::Logger(LoggerCall::log(96, 100)),
+								call: bound(Call::Logger(LoggerCall::log(96, 100))),
 								maybe_periodic: None,
 								origin: system::RawOrigin::Root.into(),
 								_phantom: PhantomData::<u64>::default(),
@@ -1731,7 +2382,7 @@ mod tests {
 							Some(ScheduledV2 {
 								maybe_id: Some(b"test".to_vec()),
 								priority: 123,
-								call: Call::Logger(LoggerCall::log(69, 1000)),
+								call: bound(Call::Logger(LoggerCall::log(69, 1000))),
 								maybe_periodic: Some((456u64, 10)),
 								origin: system::RawOrigin::None.into(),
 								_phantom: PhantomData::<u64>::default(),
@@ -1744,7 +2395,7 @@ mod tests {
 							Some(ScheduledV2 {
 								maybe_id: None,
 								priority: 11,
-								call: Call::Logger(LoggerCall::log(96, 100)),
+								call: bound(Call::Logger(LoggerCall::log(96, 100))),
 								maybe_periodic: None,
 								origin: system::RawOrigin::Root.into(),
 								_phantom: PhantomData::<u64>::default(),
@@ -1753,7 +2404,7 @@ mod tests {
 							Some(ScheduledV2 {
 								maybe_id: Some(b"test".to_vec()),
 								priority: 123,
-								call: Call::Logger(LoggerCall::log(69, 1000)),
+								call: bound(Call::Logger(LoggerCall::log(69, 1000))),
 								maybe_periodic: Some((456u64, 10)),
 								origin: system::RawOrigin::None.into(),
 								_phantom: PhantomData::<u64>::default(),
@@ -1766,7 +2417,7 @@ mod tests {
 							Some(ScheduledV2 {
 								maybe_id: None,
 								priority: 12,
-								call: Call::Logger(LoggerCall::log(96, 100)),
+								call: bound(Call::Logger(LoggerCall::log(96, 100))),
 								maybe_periodic: None,
 								origin: system::RawOrigin::Root.into(),
 								_phantom: PhantomData::<u64>::default(),
@@ -1775,7 +2426,7 @@ mod tests {
 							Some(ScheduledV2 {
 								maybe_id: Some(b"test".to_vec()),
 								priority: 123,
-								call: Call::Logger(LoggerCall::log(69, 1000)),
+								call: bound(Call::Logger(LoggerCall::log(69, 1000))),
 								maybe_periodic: Some((456u64, 10)),
 								origin: system::RawOrigin::None.into(),
 								_phantom: PhantomData::<u64>::default(),
@@ -32,22 +32,6 @@ const MAX_SPANS: u32 = 100;
 const MAX_VALIDATORS: u32 = 1000;
 const MAX_SLASHES: u32 = 1000;
 
-// Add slashing spans to a user account. Not relevant for actual use, only to benchmark
-// read and write operations.
-fn add_slashing_spans<T: Config>(who: &T::AccountId, spans: u32) {
-	if spans == 0 { return }
-
-	// For the first slashing span, we initialize
-	let mut slashing_spans = crate::slashing::SlashingSpans::new(0);
-	SpanSlash::<T>::insert((who, 0), crate::slashing::SpanRecord::default());
-
-	for i in 1 .. spans {
-		assert!(slashing_spans.end_span(i));
-		SpanSlash::<T>::insert((who, i), crate::slashing::SpanRecord::default());
-	}
-	SlashingSpans::<T>::insert(who, slashing_spans);
-}
-
 // This function clears all existing validators and nominators from the set, and generates one new
 // validator being nominated by n nominators, and returns the validator stash account and the
 // nominators' stash and controller. It also starts an era and creates pending payouts.
@@ -352,6 +336,13 @@ benchmarks! {
 		assert_eq!(UnappliedSlashes::<T>::get(&era).len(), (MAX_SLASHES - s) as usize);
 	}
 
+	set_slash_defer_duration {
+		let duration = T::BondingDuration::get().saturating_sub(1);
+	}: _(RawOrigin::Root, duration)
+	verify {
+		assert_eq!(Staking::<T>::slash_defer_duration(), duration);
+	}
+
 	payout_stakers_dead_controller {
 		let n in 1 .. T::MaxNominatorRewardedPerValidator::get() as u32;
 		let (validator, nominators) = create_validator_with_nominators::<T>(
@@ -577,7 +568,7 @@ benchmarks! {
 			add_slashing_spans::<T>(&validators[index as usize], 10);
 		});
 	}: {
-		let voters = <Staking<T>>::get_npos_voters();
+		let voters = <Staking<T>>::get_npos_voters(None);
 		assert_eq!(voters.len() as u32, v + n);
 	}
 
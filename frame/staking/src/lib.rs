@@ -293,7 +293,7 @@ use frame_support::{
 		Weight, WithPostDispatchInfo,
 		constants::{WEIGHT_PER_MICROS, WEIGHT_PER_NANOS},
 	},
-	storage::IterableStorageMap,
+	storage::{IterableStorageMap, IterableStorageDoubleMap},
 	dispatch::{DispatchResult, DispatchResultWithPostInfo},
 	traits::{
 		Currency, LockIdentifier, LockableCurrency, WithdrawReasons, OnUnbalanced, Imbalance, Get,
@@ -310,14 +310,16 @@ use sp_runtime::{
 	},
 };
 use sp_staking::{
-	SessionIndex,
+	SessionIndex, OnStakerSlash,
 	offence::{OnOffenceHandler, OffenceDetails, Offence, ReportOffence, OffenceError},
 };
 use frame_system::{
 	self as system, ensure_signed, ensure_root,
 	offchain::SendTransactionTypes,
 };
-use frame_election_provider_support::{ElectionProvider, VoteWeight, Supports, data_provider};
+use frame_election_provider_support::{
+	ElectionProvider, VoteWeight, Supports, data_provider, SortedListProvider, VoteWeightProvider,
+};
 pub use weights::WeightInfo;
 
 const STAKING_ID: LockIdentifier = *b"staking ";
@@ -523,11 +525,13 @@ impl<AccountId, Balance> StakingLedger<AccountId, Balance> where
 	/// active funds. Returns the amount of funds actually slashed.
 	///
 	/// Slashes from `active` funds first, and then `unlocking`, starting with the
-	/// chunks that are closest to unlocking.
+	/// chunks that are closest to unlocking. The remaining balance of each slashed chunk is
+	/// recorded in `slashed_unlocking`, by era.
 	fn slash(
 		&mut self,
 		mut value: Balance,
 		minimum_balance: Balance,
+		slashed_unlocking: &mut BTreeMap<EraIndex, Balance>,
 	) -> Balance {
 		let pre_total = self.total;
 		let total = &mut self.total;
@@ -558,7 +562,10 @@ impl<AccountId, Balance> StakingLedger<AccountId, Balance> where
 
 		let i = self.unlocking.iter_mut()
 			.map(|chunk| {
-				slash_out_of(total, &mut chunk.value, &mut value);
+				if !value.is_zero() {
+					slash_out_of(total, &mut chunk.value, &mut value);
+					slashed_unlocking.insert(chunk.era, chunk.value);
+				}
 				chunk.value
 			})
 			.take_while(|value| value.is_zero()) // take all fully-consumed chunks out.
@@ -760,10 +767,12 @@ pub trait Config: frame_system::Config + SendTransactionTypes<Call<Self>> {
 	/// Number of eras that staked funds must remain bonded for.
 	type BondingDuration: Get<EraIndex>;
 
-	/// Number of eras that slashes are deferred by, after computation.
+	/// Default number of eras that slashes are deferred by, after computation.
 	///
 	/// This should be less than the bonding duration. Set to 0 if slashes
 	/// should be applied immediately, without opportunity for intervention.
+	///
+	/// Can be overridden at runtime through `set_slash_defer_duration`.
 	type SlashDeferDuration: Get<EraIndex>;
 
 	/// The origin which can cancel a deferred slash. Root can always do this.
@@ -785,10 +794,55 @@ pub trait Config: frame_system::Config + SendTransactionTypes<Call<Self>> {
 	/// their reward. This used to limit the i/o cost for the nominator payout.
 	type MaxNominatorRewardedPerValidator: Get<u32>;
 
+	/// The list of nominators, sorted by their vote weight, from which the best nominators are
+	/// taken when they can't all fit in the election snapshot.
+	///
+	/// Use [`UseNominatorsMap`] to keep taking the nominators in the (unsorted) order of the
+	/// `Nominators` map.
+	type SortedListProvider: SortedListProvider<Self::AccountId>;
+
+	/// Something notified when a stash is slashed, with its remaining active and unlocking
+	/// balances.
+	type OnStakerSlash: OnStakerSlash<Self::AccountId, BalanceOf<Self>>;
+
 	/// Weight information for extrinsics in this pallet.
 	type WeightInfo: WeightInfo;
 }
 
+/// A [`SortedListProvider`] which simply iterates the `Nominators` map, without any sorting.
+///
+/// The `on_*` hooks are no-ops, as the map is maintained by the pallet itself.
+pub struct UseNominatorsMap<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: Config> SortedListProvider<T::AccountId> for UseNominatorsMap<T> {
+	type Error = ();
+
+	fn iter() -> Box<dyn Iterator<Item = T::AccountId>> {
+		Box::new(<Nominators<T>>::iter().map(|(nominator, _)| nominator))
+	}
+	fn count() -> u32 {
+		<Nominators<T>>::iter().count() as u32
+	}
+	fn contains(id: &T::AccountId) -> bool {
+		<Nominators<T>>::contains_key(id)
+	}
+	fn on_insert(_: T::AccountId, _: VoteWeight) -> Result<(), Self::Error> {
+		Ok(())
+	}
+	fn on_update(_: &T::AccountId, _: VoteWeight) {}
+	fn on_remove(_: &T::AccountId) {}
+	fn regenerate(
+		_: impl IntoIterator<Item = T::AccountId>,
+		_: Box<dyn Fn(&T::AccountId) -> VoteWeight>,
+	) -> u32 {
+		0
+	}
+	fn clear() {}
+	fn sanity_check() -> Result<(), &'static str> {
+		Ok(())
+	}
+}
+
 /// Mode of era-forcing.
 #[derive(Copy, Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug)]
 #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
@@ -820,11 +874,12 @@ enum Releases {
 	V4_0_0,
 	V5_0_0, // blockable validators.
 	V6_0_0, // removal of all storage associated with offchain phragmen.
+	V7_0_0, // nominators kept in the `SortedListProvider`.
 }
 
 impl Default for Releases {
 	fn default() -> Self {
-		Releases::V6_0_0
+		Releases::V7_0_0
 	}
 }
 
@@ -984,6 +1039,11 @@ decl_storage! {
 		/// The earliest era for which we have a pending, unapplied slash.
 		EarliestUnappliedSlash: Option<EraIndex>;
 
+		/// Number of eras that slashes are deferred by, if set by governance.
+		///
+		/// When `None`, `T::SlashDeferDuration` is used instead.
+		pub SlashDeferDurationOverride get(fn slash_defer_duration_override): Option<EraIndex>;
+
 		/// The last planned session scheduled by the session pallet.
 		///
 		/// This is basically in sync with the call to [`SessionManager::new_session`].
@@ -992,8 +1052,8 @@ decl_storage! {
 		/// True if network has been upgraded to this version.
 		/// Storage version of the pallet.
 		///
-		/// This is set to v6.0.0 for new networks.
-		StorageVersion build(|_: &GenesisConfig<T>| Releases::V6_0_0): Releases;
+		/// This is set to v7.0.0 for new networks.
+		StorageVersion build(|_: &GenesisConfig<T>| Releases::V7_0_0): Releases;
 	}
 	add_extra_genesis {
 		config(stakers):
@@ -1073,6 +1133,26 @@ pub mod migrations {
 			T::DbWeight::get().writes(6 + 1)
 		}
 	}
+
+	pub mod v7 {
+		use super::*;
+
+		/// Migrate storage to v7: insert all the existing nominators in the
+		/// `T::SortedListProvider`.
+		pub fn migrate<T: Config>() -> Weight {
+			log!(info, "Migrating staking to Releases::V7_0_0");
+
+			let nominators = T::SortedListProvider::regenerate(
+				<Nominators<T>>::iter().map(|(nominator, _)| nominator),
+				<Module<T>>::slashable_balance_of_fn(),
+			);
+			debug_assert_eq!(T::SortedListProvider::sanity_check(), Ok(()));
+
+			StorageVersion::put(Releases::V7_0_0);
+			log!(info, "Inserted {} nominators in the sorted list. Done.", nominators);
+			T::BlockWeights::get().max_block
+		}
+	}
 }
 
 decl_event!(
@@ -1103,6 +1183,16 @@ decl_event!(
 		Withdrawn(AccountId, Balance),
 		/// A nominator has been kicked from a validator. \[nominator, stash\]
 		Kicked(AccountId, AccountId),
+		/// A slash of the given total amount was computed for a validator and its nominators and
+		/// queued for application. It can be canceled through `cancel_deferred_slash` using the
+		/// given era and index, as long as no earlier slash of that era was canceled meanwhile.
+		/// \[validator, amount, era, index\]
+		SlashDeferred(AccountId, Balance, EraIndex, u32),
+		/// The deferred slashes of an era at the given indices have been canceled.
+		/// \[era, slash_indices\]
+		DeferredSlashCanceled(EraIndex, Vec<u32>),
+		/// The number of eras that slashes are deferred by has been set. \[duration\]
+		SlashDeferDurationSet(EraIndex),
 	}
 );
 
@@ -1123,6 +1213,8 @@ decl_error! {
 		DuplicateIndex,
 		/// Slash record index out of bounds.
 		InvalidSlashIndex,
+		/// Slash defer duration is not less than the bonding duration.
+		InvalidSlashDeferDuration,
 		/// Can not bond with value less than minimum balance.
 		InsufficientValue,
 		/// Can not schedule more unlock chunks.
@@ -1160,11 +1252,11 @@ decl_module! {
 		/// Number of eras that staked funds must remain bonded for.
 		const BondingDuration: EraIndex = T::BondingDuration::get();
 
-		/// Number of eras that slashes are deferred by, after computation.
+		/// Default number of eras that slashes are deferred by, after computation.
 		///
 		/// This should be less than the bonding duration.
 		/// Set to 0 if slashes should be applied immediately, without opportunity for
-		/// intervention.
+		/// intervention. Governance may override it with `set_slash_defer_duration`.
 		const SlashDeferDuration: EraIndex = T::SlashDeferDuration::get();
 
 		/// The maximum number of nominators rewarded for each validator.
@@ -1181,10 +1273,11 @@ decl_module! {
 		fn deposit_event() = default;
 
 		fn on_runtime_upgrade() -> Weight {
-			if StorageVersion::get() == Releases::V5_0_0 {
-				migrations::v6::migrate::<T>()
-			} else {
-				T::DbWeight::get().reads(1)
+			match StorageVersion::get() {
+				Releases::V5_0_0 => migrations::v6::migrate::<T>()
+					.saturating_add(migrations::v7::migrate::<T>()),
+				Releases::V6_0_0 => migrations::v7::migrate::<T>(),
+				_ => T::DbWeight::get().reads(1),
 			}
 		}
 
@@ -1308,7 +1401,7 @@ decl_module! {
 		/// - Write: [Origin Account], Locks, Ledger
 		/// # </weight>
 		#[weight = T::WeightInfo::bond_extra()]
-		fn bond_extra(origin, #[compact] max_additional: BalanceOf<T>) {
+		pub fn bond_extra(origin, #[compact] max_additional: BalanceOf<T>) {
 			let stash = ensure_signed(origin)?;
 
 			let controller = Self::bonded(&stash).ok_or(Error::<T>::NotStash)?;
@@ -1360,7 +1453,7 @@ decl_module! {
 		/// - Write: Locks, Ledger, BalanceOf Stash,
 		/// </weight>
 		#[weight = T::WeightInfo::unbond()]
-		fn unbond(origin, #[compact] value: BalanceOf<T>) {
+		pub fn unbond(origin, #[compact] value: BalanceOf<T>) {
 			let controller = ensure_signed(origin)?;
 			let mut ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
 			ensure!(
@@ -1381,7 +1474,12 @@ decl_module! {
 
 				// Note: in case there is no current era it is fine to bond one era more.
 				let era = Self::current_era().unwrap_or(0) + T::BondingDuration::get();
-				ledger.unlocking.push(UnlockChunk { value, era });
+				// funds unbonded in the same era share a chunk, so that frequent unbonding, e.g.
+				// by a nomination pool, doesn't run out of chunks.
+				match ledger.unlocking.last_mut() {
+					Some(chunk) if chunk.era == era => chunk.value = chunk.value.saturating_add(value),
+					_ => ledger.unlocking.push(UnlockChunk { value, era }),
+				}
 				Self::update_ledger(&controller, &ledger);
 				Self::deposit_event(RawEvent::Unbonded(ledger.stash, value));
 			}
@@ -1419,7 +1517,7 @@ decl_module! {
 		/// NOTE: Weight annotation is the kill scenario, we refund otherwise.
 		/// # </weight>
 		#[weight = T::WeightInfo::withdraw_unbonded_kill(*num_slashing_spans)]
-		fn withdraw_unbonded(origin, num_slashing_spans: u32) -> DispatchResultWithPostInfo {
+		pub fn withdraw_unbonded(origin, num_slashing_spans: u32) -> DispatchResultWithPostInfo {
 			let controller = ensure_signed(origin)?;
 			let mut ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
 			let (stash, old_total) = (ledger.stash.clone(), ledger.total);
@@ -1477,7 +1575,7 @@ decl_module! {
 			let controller = ensure_signed(origin)?;
 			let ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
 			let stash = &ledger.stash;
-			<Nominators<T>>::remove(stash);
+			Self::do_remove_nominator(stash);
 			<Validators<T>>::insert(stash, prefs);
 		}
 
@@ -1527,7 +1625,7 @@ decl_module! {
 			};
 
 			<Validators<T>>::remove(stash);
-			<Nominators<T>>::insert(stash, &nominations);
+			Self::do_add_nominator(stash, nominations);
 		}
 
 		/// Declare no desire to either validate or nominate.
@@ -1548,7 +1646,7 @@ decl_module! {
 		/// - Write: Validators, Nominators
 		/// # </weight>
 		#[weight = T::WeightInfo::chill()]
-		fn chill(origin) {
+		pub fn chill(origin) {
 			let controller = ensure_signed(origin)?;
 			let ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
 			Self::chill_stash(&ledger.stash);
@@ -1706,7 +1804,7 @@ decl_module! {
 		/// Writes Each: SpanSlash * S
 		/// # </weight>
 		#[weight = T::WeightInfo::force_unstake(*num_slashing_spans)]
-		fn force_unstake(origin, stash: T::AccountId, num_slashing_spans: u32) {
+		pub fn force_unstake(origin, stash: T::AccountId, num_slashing_spans: u32) {
 			ensure_root(origin)?;
 
 			// remove all staking-related information.
@@ -1754,12 +1852,36 @@ decl_module! {
 			let last_item = slash_indices[slash_indices.len() - 1];
 			ensure!((last_item as usize) < unapplied.len(), Error::<T>::InvalidSlashIndex);
 
-			for (removed, index) in slash_indices.into_iter().enumerate() {
-				let index = (index as usize) - removed;
+			for (removed, index) in slash_indices.iter().enumerate() {
+				let index = (*index as usize) - removed;
 				unapplied.remove(index);
 			}
 
 			<Self as Store>::UnappliedSlashes::insert(&era, &unapplied);
+			Self::deposit_event(RawEvent::DeferredSlashCanceled(era, slash_indices));
+		}
+
+		/// Set the number of eras that slashes are deferred by, overriding
+		/// `T::SlashDeferDuration`.
+		///
+		/// Slashes that are already queued are applied once the new duration has elapsed since
+		/// the era they were computed in. Setting it to 0 applies future slashes immediately.
+		///
+		/// The dispatch origin must be Root.
+		///
+		/// # <weight>
+		/// Weight: O(1)
+		/// Write: Slash Defer Duration Override
+		/// # </weight>
+		#[weight = T::WeightInfo::set_slash_defer_duration()]
+		fn set_slash_defer_duration(origin, #[compact] new: EraIndex) {
+			ensure_root(origin)?;
+			ensure!(
+				new < T::BondingDuration::get() || T::BondingDuration::get() == 0,
+				Error::<T>::InvalidSlashDeferDuration,
+			);
+			SlashDeferDurationOverride::put(new);
+			Self::deposit_event(RawEvent::SlashDeferDurationSet(new));
 		}
 
 		/// Pay out all the stakers behind a single validator for a single era.
@@ -1942,6 +2064,40 @@ impl<T: Config> Module<T> {
 		T::CurrencyToVote::to_vote(Self::slashable_balance_of(stash), issuance)
 	}
 
+	/// The number of slashing spans of `stash`, as expected by `withdraw_unbonded`,
+	/// `force_unstake` and `reap_stash`.
+	pub fn num_slashing_spans(stash: &T::AccountId) -> u32 {
+		<SlashingSpans<T>>::get(stash).map_or(0, |spans| spans.iter().count() as u32)
+	}
+
+	/// Returns whether `who` is exposed in `era`, either as a validator or as a nominator.
+	///
+	/// At most `max_exposures` exposures of `era` are read. If `who` is not found in them and
+	/// `era` has more exposures than that, `None` is returned.
+	pub fn is_exposed_in_era(
+		who: &T::AccountId,
+		era: EraIndex,
+		max_exposures: u32,
+	) -> Option<bool> {
+		let mut exposures = <ErasStakers<T>>::iter_prefix(era);
+		let exposed = exposures.by_ref().take(max_exposures as usize).any(|(validator, exposure)| {
+			validator == *who || exposure.others.iter().any(|nominator| nominator.who == *who)
+		});
+		if exposed {
+			Some(true)
+		} else if exposures.next().is_some() {
+			None
+		} else {
+			Some(false)
+		}
+	}
+
+	/// The current vote weight of `stash`, i.e. its slashable balance converted to a
+	/// [`VoteWeight`].
+	pub fn weight_of(stash: &T::AccountId) -> VoteWeight {
+		Self::slashable_balance_of_vote_weight(stash, T::Currency::total_issuance())
+	}
+
 	/// Returns a closure around `slashable_balance_of_vote_weight` that can be passed around.
 	///
 	/// This prevents call sites from repeatedly requesting `total_issuance` from backend. But it is
@@ -2069,7 +2225,8 @@ impl<T: Config> Module<T> {
 
 	/// Update the ledger for a controller.
 	///
-	/// This will also update the stash lock.
+	/// This will also update the stash lock, and the vote weight of the stash in the
+	/// `T::SortedListProvider` if it is a nominator.
 	fn update_ledger(
 		controller: &T::AccountId,
 		ledger: &StakingLedger<T::AccountId, BalanceOf<T>>
@@ -2081,12 +2238,42 @@ impl<T: Config> Module<T> {
 			WithdrawReasons::all(),
 		);
 		<Ledger<T>>::insert(controller, ledger);
+		if <Nominators<T>>::contains_key(&ledger.stash) {
+			T::SortedListProvider::on_update(&ledger.stash, Self::weight_of(&ledger.stash));
+		}
+	}
+
+	/// Insert or update the nominations of `who`, inserting it in the `T::SortedListProvider` if
+	/// it is a new nominator.
+	///
+	/// This should be used instead of writing to `Nominators` directly, to keep the sorted list in
+	/// sync.
+	pub fn do_add_nominator(who: &T::AccountId, nominations: Nominations<T::AccountId>) {
+		if !<Nominators<T>>::contains_key(who) {
+			if let Err(e) = T::SortedListProvider::on_insert(who.clone(), Self::weight_of(who)) {
+				log!(warn, "Failed to insert nominator {:?} in the sorted list: {:?}", who, e);
+			}
+		}
+		<Nominators<T>>::insert(who, nominations);
+	}
+
+	/// Remove the nominations of `who`, along with its entry in the `T::SortedListProvider`.
+	///
+	/// Returns true if `who` was a nominator.
+	pub fn do_remove_nominator(who: &T::AccountId) -> bool {
+		if <Nominators<T>>::contains_key(who) {
+			<Nominators<T>>::remove(who);
+			T::SortedListProvider::on_remove(who);
+			true
+		} else {
+			false
+		}
 	}
 
 	/// Chill a stash account.
 	fn chill_stash(stash: &T::AccountId) {
 		<Validators<T>>::remove(stash);
-		<Nominators<T>>::remove(stash);
+		Self::do_remove_nominator(stash);
 	}
 
 	/// Actually make a payment to a staker. This uses the currency's reward function
@@ -2404,7 +2591,7 @@ impl<T: Config> Module<T> {
 
 		<Payee<T>>::remove(stash);
 		<Validators<T>>::remove(stash);
-		<Nominators<T>>::remove(stash);
+		Self::do_remove_nominator(stash);
 
 		system::Pallet::<T>::dec_consumers(stash);
 
@@ -2422,9 +2609,15 @@ impl<T: Config> Module<T> {
 		ErasStartSessionIndex::remove(era_index);
 	}
 
+	/// The number of eras that slashes are deferred by, taking the governance override into
+	/// account.
+	pub fn slash_defer_duration() -> EraIndex {
+		Self::slash_defer_duration_override().unwrap_or_else(T::SlashDeferDuration::get)
+	}
+
 	/// Apply previously-unapplied slashes on the beginning of a new era, after a delay.
 	fn apply_unapplied_slashes(active_era: EraIndex) {
-		let slash_defer_duration = T::SlashDeferDuration::get();
+		let slash_defer_duration = Self::slash_defer_duration();
 		<Self as Store>::EarliestUnappliedSlash::mutate(|earliest| if let Some(ref mut earliest) = earliest {
 			let keep_from = active_era.saturating_sub(slash_defer_duration);
 			for era in (*earliest)..keep_from {
@@ -2486,7 +2679,10 @@ impl<T: Config> Module<T> {
 
 	/// Get all of the voters that are eligible for the npos election.
 	///
-	/// This will use all on-chain nominators, and all the validators will inject a self vote.
+	/// All the validators will inject a self vote, and then the nominators are taken from
+	/// `T::SortedListProvider`, from the highest vote weight to the lowest. If `maybe_max_len` is
+	/// `Some(max_len)`, no more than `max_len` voters are returned: the validators come first, and
+	/// the nominators with the lowest vote weight are left out.
 	///
 	/// ### Slashing
 	///
@@ -2494,11 +2690,14 @@ impl<T: Config> Module<T> {
 	/// auto-chilled.
 	///
 	/// Note that this is VERY expensive. Use with care.
-	pub fn get_npos_voters() -> Vec<(T::AccountId, VoteWeight, Vec<T::AccountId>)> {
+	pub fn get_npos_voters(
+		maybe_max_len: Option<usize>,
+	) -> Vec<(T::AccountId, VoteWeight, Vec<T::AccountId>)> {
+		let max_len = maybe_max_len.unwrap_or(usize::max_value());
 		let weight_of = Self::slashable_balance_of_fn();
 		let mut all_voters = Vec::new();
 
-		for (validator, _) in <Validators<T>>::iter() {
+		for (validator, _) in <Validators<T>>::iter().take(max_len) {
 			// append self vote
 			let self_vote = (validator.clone(), weight_of(&validator), vec![validator.clone()]);
 			all_voters.push(self_vote);
@@ -2507,7 +2706,17 @@ impl<T: Config> Module<T> {
 		// collect all slashing spans into a BTreeMap for further queries.
 		let slashing_spans = <SlashingSpans<T>>::iter().collect::<BTreeMap<_, _>>();
 
-		for (nominator, nominations) in <Nominators<T>>::iter() {
+		let nominators = T::SortedListProvider::iter()
+			.filter_map(|nominator| {
+				let nominations = <Nominators<T>>::get(&nominator);
+				if nominations.is_none() {
+					log!(warn, "{:?} is in the sorted list but is not a nominator", nominator);
+				}
+				nominations.map(|nominations| (nominator, nominations))
+			})
+			.take(max_len.saturating_sub(all_voters.len()));
+
+		for (nominator, nominations) in nominators {
 			let Nominations { submitted_in, mut targets, suppressed: _ } = nominations;
 
 			// Filter out nomination targets which were nominated before the most recent
@@ -2530,6 +2739,28 @@ impl<T: Config> Module<T> {
 	}
 }
 
+impl<T: Config> VoteWeightProvider<T::AccountId> for Module<T> {
+	fn vote_weight(who: &T::AccountId) -> VoteWeight {
+		Self::weight_of(who)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_vote_weight_of(who: &T::AccountId, weight: VoteWeight) {
+		// `who` bonds itself, with no regard for its balance nor the other staking state. The
+		// weight is exact as long as the currency converts to votes one to one.
+		let active: BalanceOf<T> = weight.saturated_into();
+		let ledger = StakingLedger {
+			stash: who.clone(),
+			total: active,
+			active,
+			unlocking: vec![],
+			claimed_rewards: vec![],
+		};
+		<Ledger<T>>::insert(who, ledger);
+		<Bonded<T>>::insert(who, who);
+	}
+}
+
 impl<T: Config> frame_election_provider_support::ElectionDataProvider<T::AccountId, T::BlockNumber>
 	for Module<T>
 {
@@ -2541,24 +2772,19 @@ impl<T: Config> frame_election_provider_support::ElectionDataProvider<T::Account
 	fn voters(
 		maybe_max_len: Option<usize>,
 	) -> data_provider::Result<(Vec<(T::AccountId, VoteWeight, Vec<T::AccountId>)>, Weight)> {
-		// NOTE: reading these counts already needs to iterate a lot of storage keys, but they get
-		// cached. This is okay for the case of `Ok(_)`, but bad for `Err(_)`, as the trait does not
-		// report weight in failures.
-		let nominator_count = <Nominators<T>>::iter().count();
-		let validator_count = <Validators<T>>::iter().count();
-		let voter_count = nominator_count.saturating_add(validator_count);
-
-		if maybe_max_len.map_or(false, |max_len| voter_count > max_len) {
-			return Err("Voter snapshot too big");
-		}
+		// NOTE: instead of failing when there are too many voters, the nominators with the lowest
+		// vote weight are left out of the snapshot.
+		let voters = Self::get_npos_voters(maybe_max_len);
+		let validator_count = <Validators<T>>::iter().count().min(voters.len());
+		let nominator_count = voters.len() - validator_count;
 
 		let slashing_span_count = <SlashingSpans<T>>::iter().count();
 		let weight = T::WeightInfo::get_npos_voters(
-			nominator_count as u32,
 			validator_count as u32,
+			nominator_count as u32,
 			slashing_span_count as u32,
 		);
-		Ok((Self::get_npos_voters(), weight))
+		Ok((voters, weight))
 	}
 
 	fn targets(maybe_max_len: Option<usize>) -> data_provider::Result<(Vec<T::AccountId>, Weight)> {
@@ -2642,8 +2868,8 @@ impl<T: Config> frame_election_provider_support::ElectionDataProvider<T::Account
 					claimed_rewards: vec![],
 				},
 			);
-			<Nominators<T>>::insert(
-				v,
+			Self::do_add_nominator(
+				&v,
 				Nominations { targets: t, submitted_in: 0, suppressed: false },
 			);
 		});
@@ -2813,7 +3039,8 @@ where
 		});
 		add_db_reads_writes(1, 1);
 
-		let slash_defer_duration = T::SlashDeferDuration::get();
+		let slash_defer_duration = Self::slash_defer_duration();
+		add_db_reads_writes(1, 0);
 
 		let invulnerables = Self::invulnerables();
 		add_db_reads_writes(1, 0);
@@ -2859,11 +3086,18 @@ where
 					}
 				} else {
 					// defer to end of some `slash_defer_duration` from now.
-					<Self as Store>::UnappliedSlashes::mutate(
+					let validator = unapplied.validator.clone();
+					let amount = unapplied.others.iter()
+						.fold(unapplied.own, |acc, (_, value)| acc.saturating_add(*value));
+					let index = <Self as Store>::UnappliedSlashes::mutate(
 						active_era,
-						move |for_later| for_later.push(unapplied),
+						move |for_later| {
+							for_later.push(unapplied);
+							for_later.len() as u32 - 1
+						},
 					);
 					add_db_reads_writes(1, 1);
+					Self::deposit_event(RawEvent::SlashDeferred(validator, amount, active_era, index));
 				}
 			} else {
 				add_db_reads_writes(4 /* fetch_spans */, 5 /* kick_out_if_recent */)
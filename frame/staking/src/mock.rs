@@ -33,7 +33,7 @@ use sp_runtime::{
 	traits::{IdentityLookup, Zero},
 };
 use sp_staking::offence::{OffenceDetails, OnOffenceHandler};
-use std::{cell::RefCell, collections::HashSet};
+use std::{cell::RefCell, collections::{BTreeMap, HashSet}};
 use frame_election_provider_support::onchain;
 
 pub const INIT_TIMESTAMP: u64 = 30_000;
@@ -155,6 +155,10 @@ impl frame_system::Config for Test {
 }
 impl pallet_balances::Config for Test {
 	type MaxLocks = MaxLocks;
+	type HoldIdentifier = ();
+	type FreezeIdentifier = ();
+	type MaxHolds = frame_support::traits::ConstU32<1>;
+	type MaxFreezes = frame_support::traits::ConstU32<1>;
 	type Balance = Balance;
 	type Event = Event;
 	type DustRemoval = ();
@@ -234,6 +238,26 @@ impl OnUnbalanced<NegativeImbalanceOf<Test>> for RewardRemainderMock {
 	}
 }
 
+thread_local! {
+	pub static LEDGER_SLASH_PER_ERA: RefCell<(Balance, BTreeMap<EraIndex, Balance>)> =
+		RefCell::new((0, BTreeMap::new()));
+}
+
+/// Records the balances reported by the last call to `on_slash`.
+pub struct OnStakerSlashMock;
+
+impl sp_staking::OnStakerSlash<AccountId, Balance> for OnStakerSlashMock {
+	fn on_slash(
+		_stash: &AccountId,
+		slashed_active: Balance,
+		slashed_unlocking: &BTreeMap<EraIndex, Balance>,
+	) {
+		LEDGER_SLASH_PER_ERA.with(|v| {
+			*v.borrow_mut() = (slashed_active, slashed_unlocking.clone());
+		});
+	}
+}
+
 impl onchain::Config for Test {
 	type AccountId = AccountId;
 	type BlockNumber = BlockNumber;
@@ -259,6 +283,8 @@ impl Config for Test {
 	type NextNewSession = Session;
 	type MaxNominatorRewardedPerValidator = MaxNominatorRewardedPerValidator;
 	type ElectionProvider = onchain::OnChainSequentialPhragmen<Self>;
+	type SortedListProvider = UseNominatorsMap<Self>;
+	type OnStakerSlash = OnStakerSlashMock;
 	type WeightInfo = ();
 }
 
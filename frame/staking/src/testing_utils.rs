@@ -31,6 +31,23 @@ const SEED: u32 = 0;
 pub fn clear_validators_and_nominators<T: Config>() {
 	Validators::<T>::remove_all();
 	Nominators::<T>::remove_all();
+	T::SortedListProvider::clear();
+}
+
+/// Add slashing spans to a user account. Not relevant for actual use, only to benchmark
+/// read and write operations.
+pub fn add_slashing_spans<T: Config>(who: &T::AccountId, spans: u32) {
+	if spans == 0 { return }
+
+	// For the first slashing span, we initialize
+	let mut slashing_spans = crate::slashing::SlashingSpans::new(0);
+	SpanSlash::<T>::insert((who, 0), crate::slashing::SpanRecord::default());
+
+	for i in 1 .. spans {
+		assert!(slashing_spans.end_span(i));
+		SpanSlash::<T>::insert((who, i), crate::slashing::SpanRecord::default());
+	}
+	SlashingSpans::<T>::insert(who, slashing_spans);
 }
 
 /// Grab a funded user.
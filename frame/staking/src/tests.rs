@@ -1209,27 +1209,32 @@ fn bond_extra_and_withdraw_unbonded_works() {
 #[test]
 fn too_many_unbond_calls_should_not_work() {
 	ExtBuilder::default().build_and_execute(|| {
-		// locked at era 0 until 3
-		for _ in 0..MAX_UNLOCKING_CHUNKS-1 {
+		// funds unbonded in the same era share a chunk.
+		assert_ok!(Staking::unbond(Origin::signed(10), 1));
+		assert_ok!(Staking::unbond(Origin::signed(10), 1));
+		assert_eq!(Staking::ledger(&10).unwrap().unlocking.len(), 1);
+
+		// one chunk per era, locked at era `i` until `i + 3`.
+		let mut current_era = 0;
+		for i in 1..MAX_UNLOCKING_CHUNKS as EraIndex {
+			current_era = i;
+			mock::start_active_era(current_era);
 			assert_ok!(Staking::unbond(Origin::signed(10), 1));
 		}
+		assert_eq!(Staking::ledger(&10).unwrap().unlocking.len(), MAX_UNLOCKING_CHUNKS);
 
-		mock::start_active_era(1);
-
-		// locked at era 1 until 4
-		assert_ok!(Staking::unbond(Origin::signed(10), 1));
-		// can't do more.
+		// can't do more, not even in the era of the last chunk.
 		assert_noop!(Staking::unbond(Origin::signed(10), 1), Error::<Test>::NoMoreChunks);
-
-		mock::start_active_era(3);
-
+		mock::start_active_era(current_era + 1);
 		assert_noop!(Staking::unbond(Origin::signed(10), 1), Error::<Test>::NoMoreChunks);
-		// free up.
+
+		// free up all but the chunks of the last 2 eras.
 		assert_ok!(Staking::withdraw_unbonded(Origin::signed(10), 0));
+		assert_eq!(Staking::ledger(&10).unwrap().unlocking.len(), 2);
 
 		// Can add again.
 		assert_ok!(Staking::unbond(Origin::signed(10), 1));
-		assert_eq!(Staking::ledger(&10).unwrap().unlocking.len(), 2);
+		assert_eq!(Staking::ledger(&10).unwrap().unlocking.len(), 3);
 	})
 }
 
@@ -1354,11 +1359,7 @@ fn rebond_works() {
 					stash: 11,
 					total: 1000,
 					active: 100,
-					unlocking: vec![
-						UnlockChunk { value: 300, era: 5 },
-						UnlockChunk { value: 300, era: 5 },
-						UnlockChunk { value: 300, era: 5 },
-					],
+					unlocking: vec![UnlockChunk { value: 900, era: 5 }],
 					claimed_rewards: vec![],
 				})
 			);
@@ -1371,10 +1372,7 @@ fn rebond_works() {
 					stash: 11,
 					total: 1000,
 					active: 600,
-					unlocking: vec![
-						UnlockChunk { value: 300, era: 5 },
-						UnlockChunk { value: 100, era: 5 },
-					],
+					unlocking: vec![UnlockChunk { value: 400, era: 5 }],
 					claimed_rewards: vec![],
 				})
 			);
@@ -2909,6 +2907,74 @@ fn remove_multi_deferred() {
 	})
 }
 
+#[test]
+fn deferred_and_canceled_slashes_emit_events() {
+	ExtBuilder::default()
+		.slash_defer_duration(2)
+		.build_and_execute(|| {
+			mock::start_active_era(1);
+
+			let exposure = Staking::eras_stakers(1, 11);
+			on_offence_now(
+				&[OffenceDetails { offender: (11, exposure.clone()), reporters: vec![] }],
+				&[Perbill::from_percent(10)],
+			);
+
+			let slash = <Staking as Store>::UnappliedSlashes::get(&1)[0].clone();
+			let amount = slash.own + slash.others.iter().map(|(_, value)| value).sum::<Balance>();
+			assert!(amount > 0);
+			assert_eq!(
+				*staking_events().last().unwrap(),
+				RawEvent::SlashDeferred(11, amount, 1, 0),
+			);
+
+			assert_ok!(Staking::cancel_deferred_slash(Origin::root(), 1, vec![0]));
+			assert_eq!(
+				*staking_events().last().unwrap(),
+				RawEvent::DeferredSlashCanceled(1, vec![0]),
+			);
+			assert!(<Staking as Store>::UnappliedSlashes::get(&1).is_empty());
+		})
+}
+
+#[test]
+fn set_slash_defer_duration_works() {
+	ExtBuilder::default()
+		.slash_defer_duration(2)
+		.build_and_execute(|| {
+			assert_eq!(Staking::slash_defer_duration(), 2);
+
+			assert_noop!(
+				Staking::set_slash_defer_duration(Origin::signed(10), 1),
+				BadOrigin,
+			);
+			// must be less than the bonding duration.
+			assert_noop!(
+				Staking::set_slash_defer_duration(Origin::root(), BondingDuration::get()),
+				Error::<Test>::InvalidSlashDeferDuration,
+			);
+
+			assert_ok!(Staking::set_slash_defer_duration(Origin::root(), 1));
+			assert_eq!(Staking::slash_defer_duration(), 1);
+			assert_eq!(*staking_events().last().unwrap(), RawEvent::SlashDeferDurationSet(1));
+
+			mock::start_active_era(1);
+			assert_eq!(Balances::free_balance(11), 1000);
+
+			on_offence_now(
+				&[OffenceDetails { offender: (11, Staking::eras_stakers(1, 11)), reporters: vec![] }],
+				&[Perbill::from_percent(10)],
+			);
+
+			mock::start_active_era(2);
+			assert_eq!(Balances::free_balance(11), 1000);
+
+			// slashes from era 1 are processed after one full era.
+			mock::start_active_era(3);
+			assert_eq!(Balances::free_balance(11), 900);
+		})
+}
+
 #[test]
 fn slash_kicks_validators_not_nominators_and_disables_nominator_for_kicked_validator() {
 	ExtBuilder::default().build_and_execute(|| {
@@ -3094,6 +3160,28 @@ fn zero_slash_keeps_nominators() {
 	});
 }
 
+#[test]
+fn slash_reports_the_remaining_balances_of_the_ledger() {
+	ExtBuilder::default().build_and_execute(|| {
+		// 11 unbonds in two different eras.
+		assert_ok!(Staking::unbond(Origin::signed(10), 100));
+		mock::start_active_era(1);
+		assert_ok!(Staking::unbond(Origin::signed(10), 100));
+
+		// the active funds are slashed first, then the chunk closest to unlocking.
+		let mut reward_payout = 0;
+		let mut slashed_imbalance = NegativeImbalanceOf::<Test>::zero();
+		slashing::do_slash::<Test>(&11, 850, &mut reward_payout, &mut slashed_imbalance);
+
+		assert_eq!(
+			Staking::ledger(&10).unwrap().unlocking,
+			vec![UnlockChunk { value: 50, era: 3 }, UnlockChunk { value: 100, era: 4 }],
+		);
+		let expected = (0, vec![(3, 50)].into_iter().collect::<BTreeMap<_, _>>());
+		assert_eq!(LEDGER_SLASH_PER_ERA.with(|v| v.borrow().clone()), expected);
+	});
+}
+
 #[test]
 fn six_session_delay() {
 	ExtBuilder::default().initialize_first_session(false).build_and_execute(|| {
@@ -3934,11 +4022,25 @@ mod election_data_provider {
 	#[test]
 	fn respects_len_limits() {
 		ExtBuilder::default().build().execute_with(|| {
-			assert_eq!(Staking::voters(Some(1)).unwrap_err(), "Voter snapshot too big");
+			assert_eq!(Staking::voters(Some(1)).unwrap().0.len(), 1);
 			assert_eq!(Staking::targets(Some(1)).unwrap_err(), "Target snapshot too big");
 		});
 	}
 
+	#[test]
+	fn voters_are_truncated_nominators_first() {
+		ExtBuilder::default().build().execute_with(|| {
+			let validator_count = <Validators<Test>>::iter().count();
+			let (all_voters, _) = Staking::voters(None).unwrap();
+			assert!(all_voters.len() > validator_count);
+
+			// only the self votes of the validators fit.
+			let (voters, _) = Staking::voters(Some(validator_count)).unwrap();
+			assert_eq!(voters.len(), validator_count);
+			assert!(voters.iter().all(|(who, _, targets)| targets == &vec![*who]));
+		});
+	}
+
 	#[test]
 	fn estimate_next_election_works() {
 		ExtBuilder::default().session_per_era(5).period(5).build().execute_with(|| {
@@ -62,6 +62,7 @@ pub trait WeightInfo {
 	fn set_invulnerables(v: u32, ) -> Weight;
 	fn force_unstake(s: u32, ) -> Weight;
 	fn cancel_deferred_slash(s: u32, ) -> Weight;
+	fn set_slash_defer_duration() -> Weight;
 	fn payout_stakers_dead_controller(n: u32, ) -> Weight;
 	fn payout_stakers_alive_staked(n: u32, ) -> Weight;
 	fn rebond(l: u32, ) -> Weight;
@@ -178,6 +179,10 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	fn set_slash_defer_duration() -> Weight {
+		(2_417_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 	fn payout_stakers_dead_controller(n: u32, ) -> Weight {
 		(131_368_000 as Weight)
 			// Standard Error: 17_000
@@ -358,6 +363,10 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
+	fn set_slash_defer_duration() -> Weight {
+		(2_417_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
 	fn payout_stakers_dead_controller(n: u32, ) -> Weight {
 		(131_368_000 as Weight)
 			// Standard Error: 17_000
@@ -21,7 +21,7 @@ use frame_support_procedural_tools::syn_ext as ext;
 use frame_support_procedural_tools::{generate_crate_access, generate_hidden_includes};
 use parse::{PalletDeclaration, RuntimeDefinition, WhereSection, PalletPart};
 use proc_macro::TokenStream;
-use proc_macro2::{TokenStream as TokenStream2};
+use proc_macro2::{Span, TokenStream as TokenStream2};
 use quote::quote;
 use syn::{Ident, Result, TypePath};
 use std::collections::HashMap;
@@ -29,6 +29,9 @@ use std::collections::HashMap;
 /// The fixed name of the system pallet.
 const SYSTEM_PALLET_NAME: &str = "System";
 
+/// The composite enums of pallets, each aggregated into the runtime enum of the same name.
+const COMPOSITE_ENUM_NAMES: &[&str] = &["HoldReason", "FreezeReason", "SlashReason"];
+
 /// The complete definition of a pallet with the resulting fixed index.
 #[derive(Debug, Clone)]
 pub struct Pallet {
@@ -160,6 +163,7 @@ fn construct_runtime_parsed(definition: RuntimeDefinition) -> Result<TokenStream
 		&system_pallet,
 		&scrate,
 	)?;
+	let composite_enums = decl_composite_enums(&pallets, &scrate);
 	let all_pallets = decl_all_pallets(&name, pallets.iter());
 	let pallet_to_index = decl_pallet_runtime_setup(&pallets, &scrate);
 
@@ -198,6 +202,8 @@ fn construct_runtime_parsed(definition: RuntimeDefinition) -> Result<TokenStream
 
 		#outer_origin
 
+		#composite_enums
+
 		#all_pallets
 
 		#pallet_to_index
@@ -440,6 +446,68 @@ fn decl_outer_event<'a>(
 	))
 }
 
+fn decl_composite_enums(pallet_declarations: &[Pallet], scrate: &TokenStream2) -> TokenStream2 {
+	let mut enums = TokenStream2::new();
+	for composite in COMPOSITE_ENUM_NAMES {
+		let pallets: Vec<_> = pallet_declarations
+			.iter()
+			.filter(|pallet_declaration| pallet_declaration.exists_part(composite))
+			.collect();
+		if pallets.is_empty() {
+			continue;
+		}
+
+		let ident = Ident::new(composite, Span::call_site());
+		let variants = pallets.iter().map(|pallet_declaration| {
+			let pallet = &pallet_declaration.pallet;
+			let name = &pallet_declaration.name;
+			let index = pallet_declaration.index;
+			quote!(#[codec(index = #index)] #name(#pallet::#ident),)
+		});
+		// the instances of a pallet share its enum, which thus converts into neither of them.
+		let conversions = pallets
+			.iter()
+			.filter(|pallet_declaration| {
+				let pallet = &pallet_declaration.pallet;
+				pallets.iter().filter(|other| &other.pallet == pallet).count() == 1
+			})
+			.map(|pallet_declaration| {
+				let pallet = &pallet_declaration.pallet;
+				let name = &pallet_declaration.name;
+				quote!(
+					impl From<#pallet::#ident> for #ident {
+						fn from(reason: #pallet::#ident) -> Self {
+							#ident::#name(reason)
+						}
+					}
+				)
+			});
+		let doc = format!(" The `{}`s of all pallets of the runtime.", composite);
+
+		enums.extend(quote!(
+			#[doc = #doc]
+			#[derive(
+				Copy,
+				Clone,
+				Eq,
+				PartialEq,
+				Ord,
+				PartialOrd,
+				#scrate::codec::Encode,
+				#scrate::codec::Decode,
+				#scrate::traits::MaxEncodedLen,
+				#scrate::RuntimeDebug,
+			)]
+			pub enum #ident {
+				#( #variants )*
+			}
+
+			#( #conversions )*
+		));
+	}
+	enums
+}
+
 fn decl_all_pallets<'a>(
 	runtime: &'a Ident,
 	pallet_declarations: impl Iterator<Item = &'a Pallet>,
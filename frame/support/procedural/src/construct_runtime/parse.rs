@@ -36,6 +36,9 @@ mod keyword {
 	syn::custom_keyword!(Origin);
 	syn::custom_keyword!(Inherent);
 	syn::custom_keyword!(ValidateUnsigned);
+	syn::custom_keyword!(HoldReason);
+	syn::custom_keyword!(FreezeReason);
+	syn::custom_keyword!(SlashReason);
 }
 
 #[derive(Debug)]
@@ -228,6 +231,9 @@ pub enum PalletPartKeyword {
 	Origin(keyword::Origin),
 	Inherent(keyword::Inherent),
 	ValidateUnsigned(keyword::ValidateUnsigned),
+	HoldReason(keyword::HoldReason),
+	FreezeReason(keyword::FreezeReason),
+	SlashReason(keyword::SlashReason),
 }
 
 impl Parse for PalletPartKeyword {
@@ -250,6 +256,12 @@ impl Parse for PalletPartKeyword {
 			Ok(Self::Inherent(input.parse()?))
 		} else if lookahead.peek(keyword::ValidateUnsigned) {
 			Ok(Self::ValidateUnsigned(input.parse()?))
+		} else if lookahead.peek(keyword::HoldReason) {
+			Ok(Self::HoldReason(input.parse()?))
+		} else if lookahead.peek(keyword::FreezeReason) {
+			Ok(Self::FreezeReason(input.parse()?))
+		} else if lookahead.peek(keyword::SlashReason) {
+			Ok(Self::SlashReason(input.parse()?))
 		} else {
 			Err(lookahead.error())
 		}
@@ -268,6 +280,9 @@ impl PalletPartKeyword {
 			Self::Origin(_) => "Origin",
 			Self::Inherent(_) => "Inherent",
 			Self::ValidateUnsigned(_) => "ValidateUnsigned",
+			Self::HoldReason(_) => "HoldReason",
+			Self::FreezeReason(_) => "FreezeReason",
+			Self::SlashReason(_) => "SlashReason",
 		}
 	}
 
@@ -298,6 +313,9 @@ impl Spanned for PalletPartKeyword {
 			Self::Origin(inner) => inner.span(),
 			Self::Inherent(inner) => inner.span(),
 			Self::ValidateUnsigned(inner) => inner.span(),
+			Self::HoldReason(inner) => inner.span(),
+			Self::FreezeReason(inner) => inner.span(),
+			Self::SlashReason(inner) => inner.span(),
 		}
 	}
 }
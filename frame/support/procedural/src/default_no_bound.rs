@@ -17,7 +17,7 @@
 
 use syn::spanned::Spanned;
 
-/// Derive Clone but do not bound any generic.
+/// Derive Default but do not bound any generic.
 pub fn derive_default_no_bound(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 	let input: syn::DeriveInput = match syn::parse(input) {
 		Ok(input) => input,
@@ -76,12 +76,12 @@ pub fn derive_default_no_bound(input: proc_macro::TokenStream) -> proc_macro::To
 					syn::Fields::Unit => quote::quote!( #name :: #ty_generics :: #variant_ident ),
 				}
 			} else {
-				quote::quote!( Self )
+				let msg = "Enum without variant not supported by `derive(DefaultNoBound)`";
+				return syn::Error::new(name.span(), msg).to_compile_error().into()
 			}
-
 		},
 		syn::Data::Union(_) => {
-			let msg = "Union type not supported by `derive(CloneNoBound)`";
+			let msg = "Union type not supported by `derive(DefaultNoBound)`";
 			return syn::Error::new(input.span(), msg).to_compile_error().into()
 		},
 	};
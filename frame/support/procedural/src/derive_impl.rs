@@ -0,0 +1,199 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementation of the `register_default_impl` and `derive_impl` attribute macros.
+//!
+//! `register_default_impl` exports the tokens of a default implementation through a macro named
+//! after the type implementing it. `derive_impl` calls this macro, which hands the tokens over to
+//! `__derive_impl_inner` along with the implementation to complete.
+
+use std::{collections::HashSet, sync::atomic::{AtomicUsize, Ordering}};
+use frame_support_procedural_tools::generate_crate_access_2018;
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{Result, spanned::Spanned};
+
+/// Makes the names of the macros exported by `register_default_impl` unique in a crate.
+static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Whether `attr` is `#[inject_runtime_type]`.
+fn is_inject_runtime_type(attr: &syn::Attribute) -> bool {
+	attr.path.is_ident("inject_runtime_type")
+}
+
+/// The identifier of an item of an implementation, if it has one.
+fn impl_item_ident(item: &syn::ImplItem) -> Option<&syn::Ident> {
+	match item {
+		syn::ImplItem::Const(const_) => Some(&const_.ident),
+		syn::ImplItem::Method(method) => Some(&method.sig.ident),
+		syn::ImplItem::Type(type_) => Some(&type_.ident),
+		_ => None,
+	}
+}
+
+/// Parse for a trait implementation.
+fn parse_trait_impl(input: TokenStream, macro_name: &str) -> Result<syn::ItemImpl> {
+	let item_impl = syn::parse2::<syn::ItemImpl>(input)?;
+	if item_impl.trait_.is_none() {
+		let msg = format!("Invalid {}, expected trait implementation", macro_name);
+		return Err(syn::Error::new(item_impl.span(), msg));
+	}
+	Ok(item_impl)
+}
+
+pub fn register_default_impl(attr: TokenStream, input: TokenStream) -> Result<TokenStream> {
+	let name = syn::parse2::<syn::Ident>(attr)?;
+	let mut item_impl = parse_trait_impl(input, "register_default_impl")?;
+
+	// The exported tokens keep `#[inject_runtime_type]`, which is only meaningful to `derive_impl`.
+	let exported = item_impl.to_token_stream();
+	for item in item_impl.items.iter_mut() {
+		if let syn::ImplItem::Type(type_) = item {
+			type_.attrs.retain(|attr| !is_inject_runtime_type(attr));
+		}
+	}
+
+	let macro_ident = syn::Ident::new(
+		&format!("__export_default_impl_{}_{}", name, COUNTER.fetch_add(1, Ordering::Relaxed)),
+		name.span(),
+	);
+
+	Ok(quote!(
+		#item_impl
+
+		#[macro_export]
+		#[doc(hidden)]
+		macro_rules! #macro_ident {
+			{ $caller:path { $($args:tt)* } } => {
+				$caller! { $($args)* { #exported } }
+			};
+		}
+
+		#[doc(hidden)]
+		pub use #macro_ident as #name;
+	))
+}
+
+/// Parse for `path::to::DefaultImpl` or `path::to::DefaultImpl as path::to::DefaultTrait`.
+struct DeriveImplArgs {
+	default_impl_path: syn::Path,
+	disambiguation_path: Option<syn::Path>,
+}
+
+impl syn::parse::Parse for DeriveImplArgs {
+	fn parse(input: syn::parse::ParseStream) -> Result<Self> {
+		let default_impl_path = input.parse()?;
+		let disambiguation_path = if input.peek(syn::Token![as]) {
+			input.parse::<syn::Token![as]>()?;
+			Some(input.parse()?)
+		} else {
+			None
+		};
+		Ok(Self { default_impl_path, disambiguation_path })
+	}
+}
+
+pub fn derive_impl(attr: TokenStream, input: TokenStream) -> Result<TokenStream> {
+	let DeriveImplArgs { default_impl_path, disambiguation_path } = syn::parse2(attr)?;
+	let item_impl = parse_trait_impl(input, "derive_impl")?;
+
+	// By default, the trait implemented by the default is `DefaultConfig`, next to the trait
+	// implemented, e.g. `frame_system::DefaultConfig` for `frame_system::Config`.
+	let disambiguation_path = disambiguation_path.unwrap_or_else(|| {
+		let mut path = item_impl.trait_.as_ref().expect("Checked by parse_trait_impl").1.clone();
+		if let Some(segment) = path.segments.last_mut() {
+			*segment = syn::Ident::new("DefaultConfig", segment.ident.span()).into();
+		}
+		path
+	});
+
+	let frame_support = generate_crate_access_2018("frame-support")?;
+
+	Ok(quote!(
+		#default_impl_path! {
+			#frame_support::__derive_impl_inner {
+				{ #default_impl_path } { #disambiguation_path } { #item_impl }
+			}
+		}
+	))
+}
+
+/// The input of `__derive_impl_inner`, as given by the macro exported by `register_default_impl`.
+struct DeriveImplInner {
+	default_impl_path: syn::Path,
+	disambiguation_path: syn::Path,
+	local_impl: syn::ItemImpl,
+	default_impl: syn::ItemImpl,
+}
+
+impl syn::parse::Parse for DeriveImplInner {
+	fn parse(input: syn::parse::ParseStream) -> Result<Self> {
+		let (default_impl_path, disambiguation_path, local_impl, default_impl);
+		syn::braced!(default_impl_path in input);
+		syn::braced!(disambiguation_path in input);
+		syn::braced!(local_impl in input);
+		syn::braced!(default_impl in input);
+		Ok(Self {
+			default_impl_path: default_impl_path.parse()?,
+			disambiguation_path: disambiguation_path.parse()?,
+			local_impl: local_impl.parse()?,
+			default_impl: default_impl.parse()?,
+		})
+	}
+}
+
+pub fn derive_impl_inner(input: TokenStream) -> Result<TokenStream> {
+	let DeriveImplInner {
+		default_impl_path,
+		disambiguation_path,
+		mut local_impl,
+		default_impl,
+	} = syn::parse2(input)?;
+
+	let local_idents = local_impl.items.iter()
+		.filter_map(impl_item_ident)
+		.cloned()
+		.collect::<HashSet<_>>();
+
+	for item in default_impl.items {
+		let type_ = match item {
+			syn::ImplItem::Type(type_) => type_,
+			item => {
+				let msg = "Invalid derive_impl, the default implementation must only contain \
+					associated types";
+				return Err(syn::Error::new(item.span(), msg));
+			},
+		};
+
+		let ident = &type_.ident;
+		if local_idents.contains(ident) {
+			continue
+		}
+
+		// A type of the runtime, e.g. `Call`, has the same name in the runtime as in the trait.
+		let item = if type_.attrs.iter().any(is_inject_runtime_type) {
+			quote::quote_spanned!(ident.span() => type #ident = #ident;)
+		} else {
+			quote::quote_spanned!(ident.span() =>
+				type #ident = <#default_impl_path as #disambiguation_path>::#ident;
+			)
+		};
+		local_impl.items.push(syn::parse2(item)?);
+	}
+
+	Ok(local_impl.to_token_stream())
+}
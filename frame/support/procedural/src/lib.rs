@@ -30,6 +30,8 @@ mod partial_eq_no_bound;
 mod default_no_bound;
 mod max_encoded_len;
 mod key_prefix;
+mod storage_alias;
+mod derive_impl;
 
 pub(crate) use storage::INHERENT_INSTANCE_NAME;
 use proc_macro::TokenStream;
@@ -299,6 +301,8 @@ pub fn decl_storage(input: TokenStream) -> TokenStream {
 /// - `Config` or `Config<T>` (if the config is generic)
 /// - `Inherent` - If the module provides/can check inherents.
 /// - `ValidateUnsigned` - If the module validates unsigned extrinsics.
+/// - `HoldReason`, `FreezeReason`, `SlashReason` - If the pallet declares the composite enum of
+///   the same name, which is aggregated into the runtime enum of that name.
 ///
 /// `= $n` is an optional part allowing to define at which index the module variants in
 /// `OriginCaller`, `Call` and `Event` are encoded, and to define the ModuleToIndex value.
@@ -333,10 +337,15 @@ pub fn pallet(attr: TokenStream, item: TokenStream) -> TokenStream {
 	pallet::pallet(attr, item)
 }
 
-/// Execute the annotated function in a new storage transaction.
+/// Execute the annotated function in a new storage layer.
 ///
-/// The return type of the annotated function must be `Result`. All changes to storage performed
-/// by the annotated function are discarded if it returns `Err`, or committed if `Ok`.
+/// The return type of the annotated function must be `Result`, with an error convertible from
+/// `DispatchError`. All changes to storage performed by the annotated function are discarded if it
+/// returns `Err`, or committed if `Ok`.
+///
+/// Storage layers nest up to `frame_support::storage::transactional::TRANSACTIONAL_LIMIT` deep.
+/// Beyond that, the annotated function is not executed and returns the error
+/// `frame_support::storage::transactional::LIMIT_REACHED`.
 ///
 /// # Example
 ///
@@ -453,6 +462,42 @@ pub fn derive_max_encoded_len(input: TokenStream) -> TokenStream {
 	max_encoded_len::derive_max_encoded_len(input)
 }
 
+/// Declare a storage type with an explicit pallet prefix. Docs are at
+/// `frame_support::storage_alias`.
+#[proc_macro_attribute]
+pub fn storage_alias(attr: TokenStream, item: TokenStream) -> TokenStream {
+	storage_alias::storage_alias(attr.into(), item.into())
+		.unwrap_or_else(syn::Error::into_compile_error)
+		.into()
+}
+
+/// Export a default implementation of a trait, to be used by `derive_impl`. Docs are at
+/// `frame_support::register_default_impl`.
+#[proc_macro_attribute]
+pub fn register_default_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
+	derive_impl::register_default_impl(attr.into(), item.into())
+		.unwrap_or_else(syn::Error::into_compile_error)
+		.into()
+}
+
+/// Complete an implementation of a trait with the items of a registered default implementation.
+/// Docs are at `frame_support::derive_impl`.
+#[proc_macro_attribute]
+pub fn derive_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
+	derive_impl::derive_impl(attr.into(), item.into())
+		.unwrap_or_else(syn::Error::into_compile_error)
+		.into()
+}
+
+/// This macro is meant to be used by `derive_impl` only, through the macro exported by
+/// `register_default_impl`.
+#[proc_macro]
+pub fn __derive_impl_inner(input: TokenStream) -> TokenStream {
+	derive_impl::derive_impl_inner(input.into())
+		.unwrap_or_else(syn::Error::into_compile_error)
+		.into()
+}
+
 /// This macro is meant to be used by frame-support only.
 /// It implements the trait `HasKeyPrefix` and `HasReversibleKeyPrefix` for tuple of `Key`.
 #[proc_macro]
@@ -22,16 +22,16 @@ use syn::spanned::Spanned;
 /// * Generate enum call and implement various trait on it.
 /// * Implement Callable and call_function on `Pallet`
 pub fn expand_call(def: &mut Def) -> proc_macro2::TokenStream {
-	let (span, where_clause, methods, docs) = match def.call.as_ref() {
+	let (span, where_clause, methods, docs, transactional) = match def.call.as_ref() {
 		Some(call) => {
 			let span = call.attr_span;
 			let where_clause = call.where_clause.clone();
 			let methods = call.methods.clone();
 			let docs = call.docs.clone();
 
-			(span, where_clause, methods, docs)
+			(span, where_clause, methods, docs, call.transactional)
 		}
-		None => (def.pallet_struct.attr_span, None, Vec::new(), Vec::new()),
+		None => (def.pallet_struct.attr_span, None, Vec::new(), Vec::new(), false),
 	};
 	let frame_support = &def.frame_support;
 	let frame_system = &def.frame_system;
@@ -43,14 +43,31 @@ pub fn expand_call(def: &mut Def) -> proc_macro2::TokenStream {
 
 	let fn_name = methods.iter().map(|method| &method.name).collect::<Vec<_>>();
 
-	let fn_weight = methods.iter().map(|method| &method.weight);
-
-	let fn_doc = methods.iter().map(|method| &method.docs).collect::<Vec<_>>();
-
 	let args_name = methods.iter()
 		.map(|method| method.args.iter().map(|(_, name, _)| name.clone()).collect::<Vec<_>>())
 		.collect::<Vec<_>>();
 
+	let fn_dispatch = fn_name.iter().zip(args_name.iter()).map(|(fn_name, args_name)| {
+		let dispatch = quote::quote_spanned!(span =>
+			<#pallet_ident<#type_use_gen>>::#fn_name(origin, #( #args_name, )* )
+				.map(Into::into).map_err(Into::into)
+		);
+		if transactional {
+			quote::quote_spanned!(span =>
+				#frame_support::storage::with_storage_layer(|| { #dispatch })
+			)
+		} else {
+			dispatch
+		}
+	});
+
+	let fn_weight = methods.iter().map(|method| &method.weight);
+
+	let fn_call_index = methods.iter()
+		.map(|method| proc_macro2::Literal::u8_unsuffixed(method.call_index));
+
+	let fn_doc = methods.iter().map(|method| &method.docs).collect::<Vec<_>>();
+
 	let args_type = methods.iter()
 		.map(|method| method.args.iter().map(|(_, _, type_)| type_.clone()).collect::<Vec<_>>())
 		.collect::<Vec<_>>();
@@ -109,7 +126,11 @@ pub fn expand_call(def: &mut Def) -> proc_macro2::TokenStream {
 				#frame_support::sp_std::marker::PhantomData<(#type_use_gen,)>,
 				#frame_support::Never,
 			),
-			#( #( #[doc = #fn_doc] )* #fn_name( #( #args_compact_attr #args_type ),* ), )*
+			#(
+				#( #[doc = #fn_doc] )*
+				#[codec(index = #fn_call_index)]
+				#fn_name( #( #args_compact_attr #args_type ),* ),
+			)*
 		}
 
 		impl<#type_impl_gen> #frame_support::dispatch::GetDispatchInfo
@@ -178,8 +199,7 @@ pub fn expand_call(def: &mut Def) -> proc_macro2::TokenStream {
 							#frame_support::sp_tracing::enter_span!(
 								#frame_support::sp_tracing::trace_span!(stringify!(#fn_name))
 							);
-							<#pallet_ident<#type_use_gen>>::#fn_name(origin, #( #args_name, )* )
-								.map(Into::into).map_err(Into::into)
+							#fn_dispatch
 						},
 					)*
 					Self::__Ignore(_, _) => {
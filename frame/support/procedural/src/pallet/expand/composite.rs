@@ -0,0 +1,46 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::pallet::Def;
+
+/// * Derive the traits needed to store the composite enums, and to aggregate them in the runtime.
+pub fn expand_composites(def: &mut Def) {
+	let frame_support = &def.frame_support;
+	for composite in &def.composites {
+		let item = &mut def.item.content.as_mut()
+			.expect("Checked by def parser").1[composite.index];
+		let item = if let syn::Item::Enum(item) = item {
+			item
+		} else {
+			unreachable!("Checked by composite enum parser")
+		};
+
+		item.attrs.push(syn::parse_quote!(
+			#[derive(
+				Copy,
+				Clone,
+				Eq,
+				PartialEq,
+				Ord,
+				PartialOrd,
+				#frame_support::codec::Encode,
+				#frame_support::codec::Decode,
+				#frame_support::traits::MaxEncodedLen,
+				#frame_support::RuntimeDebug,
+			)]
+		));
+	}
+}
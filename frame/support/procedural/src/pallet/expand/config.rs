@@ -18,6 +18,7 @@
 use crate::pallet::{Def, parse::helper::get_doc_literals};
 
 /// * Generate default rust doc
+/// * Generate the `DefaultConfig` trait if `#[pallet::config(with_default)]` is used
 pub fn expand_config(def: &mut Def) -> proc_macro2::TokenStream {
 	let config = &def.config;
 	let config_item = {
@@ -39,5 +40,31 @@ pub fn expand_config(def: &mut Def) -> proc_macro2::TokenStream {
 		));
 	}
 
-	Default::default()
+	if let Some(default_sub_trait) = &config.default_sub_trait {
+		let items = default_sub_trait.items.iter().map(|(item, no_default_bounds)| {
+			let mut item = item.clone();
+			if *no_default_bounds {
+				if let syn::TraitItem::Type(type_) = &mut item {
+					type_.colon_token = None;
+					type_.bounds.clear();
+				}
+			}
+			item
+		});
+
+		quote::quote_spanned!(config.attr_span =>
+			/// Based on [`Config`], the associated types which can be given a default, e.g. by a
+			/// type registered with `frame_support::register_default_impl`.
+			///
+			/// An implementation of `Config` can then use `frame_support::derive_impl` and only
+			/// specify the associated types it overrides.
+			///
+			/// Generated by `pallet::config(with_default)` attribute macro.
+			pub trait DefaultConfig {
+				#( #items )*
+			}
+		)
+	} else {
+		Default::default()
+	}
 }
@@ -57,6 +57,19 @@ pub fn expand_event(def: &mut Def) -> proc_macro2::TokenStream {
 			)
 		});
 
+	let topics = event.topics.iter()
+		.map(|(variant, members)| {
+			let bindings = (0..members.len())
+				.map(|i| syn::Ident::new(&format!("__topic_{}", i), event.attr_span));
+			let bindings = bindings.collect::<Vec<_>>();
+			quote::quote_spanned!(event.attr_span =>
+				Self::#variant { #( #members: ref #bindings, )* .. } =>
+					#frame_support::sp_std::vec![
+						#( <__H as #frame_support::sp_runtime::traits::Hash>::hash_of(#bindings), )*
+					],
+			)
+		});
+
 	let event_item = {
 		let item = &mut def.item.content.as_mut().expect("Checked by def parser").1[event.index];
 		if let syn::Item::Enum(item) = item {
@@ -111,6 +124,8 @@ pub fn expand_event(def: &mut Def) -> proc_macro2::TokenStream {
 		quote::quote_spanned!(*fn_span =>
 			impl<#type_impl_gen> Pallet<#type_use_gen> #completed_where_clause {
 				#fn_vis fn deposit_event(event: Event<#event_use_gen>) {
+					let topics = event.topics::<<T as #frame_system::Config>::Hashing>();
+
 					let event = <
 						<T as Config#trait_use_gen>::Event as
 						From<Event<#event_use_gen>>
@@ -121,7 +136,7 @@ pub fn expand_event(def: &mut Def) -> proc_macro2::TokenStream {
 						Into<<T as #frame_system::Config>::Event>
 					>::into(event);
 
-					<#frame_system::Pallet<T>>::deposit_event(event)
+					<#frame_system::Pallet<T>>::deposit_event_indexed(&topics, event)
 				}
 			}
 		)
@@ -142,6 +157,19 @@ pub fn expand_event(def: &mut Def) -> proc_macro2::TokenStream {
 			pub fn metadata() -> &'static [#frame_support::event::EventMetadata] {
 				&[ #( #metadata )* ]
 			}
+
+			/// The topics under which the event is indexed, i.e. the hashes of its fields marked
+			/// with `#[pallet::indexed]`.
+			#[allow(dead_code)]
+			pub fn topics<__H: #frame_support::sp_runtime::traits::Hash>(&self)
+				-> #frame_support::sp_std::vec::Vec<__H::Output>
+			{
+				#[allow(unreachable_patterns)]
+				match *self {
+					#( #topics )*
+					_ => #frame_support::sp_std::vec::Vec::new(),
+				}
+			}
 		}
 	)
 }
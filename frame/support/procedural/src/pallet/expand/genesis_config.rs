@@ -31,6 +31,17 @@ pub fn expand_genesis_config(def: &mut Def) -> proc_macro2::TokenStream {
 
 	let serde_crate = format!("{}::serde", frame_support);
 
+	// The missing fields of a struct are taken from its `Default` implementation, so that a
+	// partially specified genesis config can be deserialized.
+	let serde_default_bound = match &*genesis_config_item {
+		syn::Item::Struct(item) if matches!(item.fields, syn::Fields::Named(_)) => {
+			let ident = &item.ident;
+			let (_, ty_generics, _) = item.generics.split_for_impl();
+			Some(quote::quote!(#ident #ty_generics: core::default::Default).to_string())
+		},
+		_ => None,
+	};
+
 	match genesis_config_item {
 		syn::Item::Enum(syn::ItemEnum { attrs, ..}) |
 		syn::Item::Struct(syn::ItemStruct { attrs, .. }) |
@@ -51,7 +62,12 @@ pub fn expand_genesis_config(def: &mut Def) -> proc_macro2::TokenStream {
 			attrs.push(syn::parse_quote!( #[serde(rename_all = "camelCase")] ));
 			attrs.push(syn::parse_quote!( #[serde(deny_unknown_fields)] ));
 			attrs.push(syn::parse_quote!( #[serde(bound(serialize = ""))] ));
-			attrs.push(syn::parse_quote!( #[serde(bound(deserialize = ""))] ));
+			if let Some(bound) = &serde_default_bound {
+				attrs.push(syn::parse_quote!( #[serde(default)] ));
+				attrs.push(syn::parse_quote!( #[serde(bound(deserialize = #bound))] ));
+			} else {
+				attrs.push(syn::parse_quote!( #[serde(bound(deserialize = ""))] ));
+			}
 			attrs.push(syn::parse_quote!( #[serde(crate = #serde_crate)] ));
 		},
 		_ => unreachable!("Checked by genesis_config parser"),
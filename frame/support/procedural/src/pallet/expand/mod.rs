@@ -18,6 +18,8 @@
 mod constants;
 mod pallet_struct;
 mod call;
+mod tasks;
+mod composite;
 mod config;
 mod error;
 mod event;
@@ -50,6 +52,7 @@ pub fn expand(mut def: Def) -> proc_macro2::TokenStream {
 	let constants = constants::expand_constants(&mut def);
 	let pallet_struct = pallet_struct::expand_pallet_struct(&mut def);
 	let config = config::expand_config(&mut def);
+	let tasks = tasks::expand_tasks(&mut def);
 	let call = call::expand_call(&mut def);
 	let error = error::expand_error(&mut def);
 	let event = event::expand_event(&mut def);
@@ -60,6 +63,7 @@ pub fn expand(mut def: Def) -> proc_macro2::TokenStream {
 	let genesis_build = genesis_build::expand_genesis_build(&mut def);
 	let genesis_config = genesis_config::expand_genesis_config(&mut def);
 	let type_values = type_value::expand_type_values(&mut def);
+	composite::expand_composites(&mut def);
 
 	if get_doc_literals(&def.item.attrs).is_empty() {
 		def.item.attrs.push(syn::parse_quote!(
@@ -76,6 +80,7 @@ pub fn expand(mut def: Def) -> proc_macro2::TokenStream {
 		#constants
 		#pallet_struct
 		#config
+		#tasks
 		#call
 		#error
 		#event
@@ -102,41 +102,50 @@ pub fn expand_pallet_struct(def: &mut Def) -> proc_macro2::TokenStream {
 		)
 	};
 
-	let storage_info = if let Some(storage_info_span) = def.pallet_struct.generate_storage_info {
-		let storage_names = &def.storages.iter().map(|storage| &storage.ident).collect::<Vec<_>>();
-		let storage_cfg_attrs = &def.storages.iter()
-			.map(|storage| &storage.cfg_attrs)
-			.collect::<Vec<_>>();
-
-		quote::quote_spanned!(storage_info_span =>
-			impl<#type_impl_gen> #frame_support::traits::StorageInfoTrait
-				for #pallet_ident<#type_use_gen>
-				#storages_where_clauses
-			{
-				fn storage_info()
-					-> #frame_support::sp_std::vec::Vec<#frame_support::traits::StorageInfo>
-				{
-					let mut res = #frame_support::sp_std::vec![];
-
-					#(
-						#(#storage_cfg_attrs)*
-						{
-							let mut storage_info = <
-								#storage_names<#type_use_gen>
-								as #frame_support::traits::StorageInfoTrait
-							>::storage_info();
-							res.append(&mut storage_info);
-						}
-					)*
-
-					res
-				}
-			}
-		)
+	// In dev mode, the storages are not required to have a max encoded len.
+	let generate_storage_info = def.pallet_struct.generate_storage_info.filter(|_| !def.dev_mode);
+	let storage_info_span = generate_storage_info.unwrap_or(def.pallet_struct.attr_span);
+
+	// Storages are only required to implement `StorageInfoTrait` when `generate_storage_info` is
+	// used, otherwise their max encoded len is not given.
+	let (storage_info_trait, storage_info_method) = if generate_storage_info.is_some() {
+		(quote::quote!(StorageInfoTrait), quote::quote!(storage_info))
 	} else {
-		Default::default()
+		(quote::quote!(PartialStorageInfoTrait), quote::quote!(partial_storage_info))
 	};
 
+	let storage_names = &def.storages.iter().map(|storage| &storage.ident).collect::<Vec<_>>();
+	let storage_cfg_attrs = &def.storages.iter()
+		.map(|storage| &storage.cfg_attrs)
+		.collect::<Vec<_>>();
+
+	let storage_info = quote::quote_spanned!(storage_info_span =>
+		impl<#type_impl_gen> #frame_support::traits::StorageInfoTrait
+			for #pallet_ident<#type_use_gen>
+			#storages_where_clauses
+		{
+			fn storage_info()
+				-> #frame_support::sp_std::vec::Vec<#frame_support::traits::StorageInfo>
+			{
+				#[allow(unused_mut)]
+				let mut res = #frame_support::sp_std::vec![];
+
+				#(
+					#(#storage_cfg_attrs)*
+					{
+						let mut storage_info = <
+							#storage_names<#type_use_gen>
+							as #frame_support::traits::#storage_info_trait
+						>::#storage_info_method();
+						res.append(&mut storage_info);
+					}
+				)*
+
+				res
+			}
+		}
+	);
+
 	quote::quote_spanned!(def.pallet_struct.attr_span =>
 		#module_error_metadata
 
@@ -0,0 +1,185 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::pallet::{Def, parse::call::{CallDef, CallVariantDef}};
+
+/// * Generate enum task and implement `Task` on it.
+/// * Implement `do_task` on `Pallet`, and add it to the calls of the pallet.
+pub fn expand_tasks(def: &mut Def) -> proc_macro2::TokenStream {
+	let tasks = match def.tasks.as_ref() {
+		Some(tasks) => tasks,
+		None => return Default::default(),
+	};
+
+	let span = tasks.attr_span;
+	let frame_support = &def.frame_support;
+	let frame_system = &def.frame_system;
+	let type_impl_gen = &def.type_impl_generics(span);
+	let type_decl_bounded_gen = &def.type_decl_bounded_generics(span);
+	let type_use_gen = &def.type_use_generics(span);
+	let task_ident = syn::Ident::new("Task", span);
+	let pallet_ident = &def.pallet_struct.pallet;
+	let where_clause = &tasks.where_clause;
+
+	let fn_name = tasks.tasks.iter().map(|task| &task.name).collect::<Vec<_>>();
+
+	let args_name = tasks.tasks.iter()
+		.map(|task| task.args.iter().map(|(name, _)| name.clone()).collect::<Vec<_>>())
+		.collect::<Vec<_>>();
+
+	let args_type = tasks.tasks.iter()
+		.map(|task| task.args.iter().map(|(_, type_)| type_.clone()).collect::<Vec<_>>());
+
+	// An item of the list is the argument of the task, or the tuple of its arguments.
+	let args_pattern = args_name.iter().map(|args_name| match &args_name[..] {
+		[arg_name] => quote::quote!(#arg_name),
+		args_name => quote::quote!(( #( #args_name ),* )),
+	});
+
+	let task_list = tasks.tasks.iter().map(|task| &task.list);
+	let task_condition = tasks.tasks.iter().map(|task| &task.condition);
+	let task_weight = tasks.tasks.iter().map(|task| &task.weight);
+	let task_index = tasks.tasks.iter()
+		.map(|task| proc_macro2::Literal::u8_unsuffixed(task.task_index))
+		.collect::<Vec<_>>();
+	let task_index_u32 = tasks.tasks.iter().map(|task| task.task_index as u32);
+	let task_doc = tasks.tasks.iter().map(|task| &task.docs);
+
+	// `do_task` is dispatched like any other call of the pallet.
+	let task_type: syn::Type = syn::parse_quote!(#task_ident<#type_use_gen>);
+	let do_task = CallVariantDef {
+		name: syn::Ident::new("do_task", span),
+		args: vec![(false, syn::Ident::new("task", span), Box::new(task_type))],
+		weight: syn::parse_quote!(#frame_support::traits::Task::weight(task)),
+		call_index: 0,
+		docs: vec![syn::parse_quote!(r" Run a valid task of the pallet, submitted by anyone.")],
+	};
+	let call = def.call.get_or_insert_with(|| CallDef {
+		where_clause: tasks.where_clause.clone(),
+		instances: vec![],
+		index: tasks.index,
+		methods: vec![],
+		attr_span: span,
+		docs: vec![],
+		transactional: false,
+	});
+	call.methods.push(CallVariantDef { call_index: call.methods.len() as u8, ..do_task });
+
+	quote::quote_spanned!(span =>
+		/// Contains one variant per task of the pallet, which anyone can submit through
+		/// `do_task` as long as it is valid.
+		#[derive(
+			#frame_support::RuntimeDebugNoBound,
+			#frame_support::CloneNoBound,
+			#frame_support::EqNoBound,
+			#frame_support::PartialEqNoBound,
+			#frame_support::codec::Encode,
+			#frame_support::codec::Decode,
+		)]
+		#[codec(encode_bound())]
+		#[codec(decode_bound())]
+		#[allow(non_camel_case_types)]
+		pub enum #task_ident<#type_decl_bounded_gen> #where_clause {
+			#[doc(hidden)]
+			#[codec(skip)]
+			__Ignore(
+				#frame_support::sp_std::marker::PhantomData<(#type_use_gen,)>,
+				#frame_support::Never,
+			),
+			#(
+				#( #[doc = #task_doc] )*
+				#[codec(index = #task_index)]
+				#fn_name( #( #args_type ),* ),
+			)*
+		}
+
+		impl<#type_impl_gen> #frame_support::traits::Task for #task_ident<#type_use_gen>
+			#where_clause
+		{
+			type Enumeration = #frame_support::sp_std::boxed::Box<
+				dyn #frame_support::sp_std::iter::Iterator<Item = Self>
+			>;
+
+			fn iter() -> Self::Enumeration {
+				let tasks = #frame_support::sp_std::iter::empty::<Self>();
+				#(
+					let tasks = tasks.chain(
+						#frame_support::sp_std::iter::IntoIterator::into_iter(#task_list)
+							.map(|#args_pattern| Self::#fn_name( #( #args_name ),* ))
+					);
+				)*
+				#frame_support::sp_std::boxed::Box::new(tasks)
+			}
+
+			fn is_valid(&self) -> bool {
+				match *self {
+					#(
+						Self::#fn_name( #( ref #args_name ),* ) =>
+							(#task_condition)( #( #args_name ),* ),
+					)*
+					Self::__Ignore(_, _) => unreachable!("__Ignore cannot be used"),
+				}
+			}
+
+			fn run(&self) -> #frame_support::dispatch::DispatchResult {
+				match *self {
+					#(
+						Self::#fn_name( #( ref #args_name ),* ) => {
+							let __pallet_result:
+								#frame_support::dispatch::DispatchResultWithPostInfo =
+								<#pallet_ident<#type_use_gen>>::#fn_name(
+									#( #args_name.clone() ),*
+								).map(Into::into).map_err(Into::into);
+							__pallet_result.map(|_| ()).map_err(|e| e.error)
+						},
+					)*
+					Self::__Ignore(_, _) => unreachable!("__Ignore cannot be used"),
+				}
+			}
+
+			fn weight(&self) -> #frame_support::weights::Weight {
+				match *self {
+					#( Self::#fn_name( #( ref #args_name ),* ) => #task_weight, )*
+					Self::__Ignore(_, _) => unreachable!("__Ignore cannot be used"),
+				}
+			}
+
+			fn task_index(&self) -> u32 {
+				match *self {
+					#( Self::#fn_name(..) => #task_index_u32, )*
+					Self::__Ignore(_, _) => unreachable!("__Ignore cannot be used"),
+				}
+			}
+		}
+
+		impl<#type_impl_gen> #pallet_ident<#type_use_gen> #where_clause {
+			/// Run a valid task of the pallet, submitted by anyone.
+			pub fn do_task(
+				origin: #frame_system::pallet_prelude::OriginFor<T>,
+				task: #task_ident<#type_use_gen>,
+			) -> #frame_support::dispatch::DispatchResultWithPostInfo {
+				#frame_system::ensure_signed(origin)?;
+				#frame_support::ensure!(
+					#frame_support::traits::Task::is_valid(&task),
+					#frame_system::Error::<T>::InvalidTask,
+				);
+				#frame_support::traits::Task::run(&task)?;
+				Ok(().into())
+			}
+		}
+	)
+}
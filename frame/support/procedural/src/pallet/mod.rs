@@ -31,19 +31,29 @@ mod expand;
 pub use parse::Def;
 use syn::spanned::Spanned;
 
+mod keyword {
+	syn::custom_keyword!(dev_mode);
+}
+
 pub fn pallet(
 	attr: proc_macro::TokenStream,
 	item: proc_macro::TokenStream
 ) -> proc_macro::TokenStream {
+	let mut dev_mode = false;
 	if !attr.is_empty() {
-		let msg = "Invalid pallet macro call: expected no attributes, e.g. macro call must be just \
-			`#[frame_support::pallet]` or `#[pallet]`";
-		let span = proc_macro2::TokenStream::from(attr).span();
-		return syn::Error::new(span, msg).to_compile_error().into();
+		if syn::parse::<keyword::dev_mode>(attr.clone()).is_ok() {
+			dev_mode = true;
+		} else {
+			let msg = "Invalid pallet macro call: expected no attributes or `dev_mode`, e.g. macro \
+				call must be just `#[frame_support::pallet]` or `#[pallet]`, or \
+				`#[frame_support::pallet(dev_mode)]` or `#[pallet(dev_mode)]`";
+			let span = proc_macro2::TokenStream::from(attr).span();
+			return syn::Error::new(span, msg).to_compile_error().into();
+		}
 	}
 
 	let item = syn::parse_macro_input!(item as syn::ItemMod);
-	match parse::Def::try_from(item) {
+	match parse::Def::try_from(item, dev_mode) {
 		Ok(def) => expand::expand(def).into(),
 		Err(e) => e.to_compile_error().into(),
 	}
@@ -24,6 +24,7 @@ mod keyword {
 	syn::custom_keyword!(Call);
 	syn::custom_keyword!(OriginFor);
 	syn::custom_keyword!(weight);
+	syn::custom_keyword!(call_index);
 	syn::custom_keyword!(compact);
 	syn::custom_keyword!(T);
 	syn::custom_keyword!(pallet);
@@ -43,6 +44,8 @@ pub struct CallDef {
 	pub attr_span: proc_macro2::Span,
 	/// Docs, specified on the impl Block.
 	pub docs: Vec<syn::Lit>,
+	/// Whether all calls are dispatched in a storage layer, i.e. `#[pallet::call(transactional)]`.
+	pub transactional: bool,
 }
 
 #[derive(Clone)]
@@ -54,14 +57,19 @@ pub struct CallVariantDef {
 	pub args: Vec<(bool, syn::Ident, Box<syn::Type>)>,
 	/// Weight formula.
 	pub weight: syn::Expr,
+	/// The index of the call in the `Call` enum, used in its encoding.
+	pub call_index: u8,
 	/// Docs, used for metadata.
 	pub docs: Vec<syn::Lit>,
 }
 
 /// Attributes for functions in call impl block.
-/// Parse for `#[pallet::weight(expr)]`
-pub struct FunctionAttr {
-	weight: syn::Expr,
+/// Parse for `#[pallet::weight(expr)]` or `#[pallet::call_index(expr)]`
+pub enum FunctionAttr {
+	/// Parse for `#[pallet::weight(expr)]`
+	Weight(syn::Expr),
+	/// Parse for `#[pallet::call_index(expr)]`
+	CallIndex(u8),
 }
 
 impl syn::parse::Parse for FunctionAttr {
@@ -71,13 +79,26 @@ impl syn::parse::Parse for FunctionAttr {
 		syn::bracketed!(content in input);
 		content.parse::<keyword::pallet>()?;
 		content.parse::<syn::Token![::]>()?;
-		content.parse::<keyword::weight>()?;
 
-		let weight_content;
-		syn::parenthesized!(weight_content in content);
-		Ok(FunctionAttr {
-			weight: weight_content.parse::<syn::Expr>()?,
-		})
+		let lookahead = content.lookahead1();
+		if lookahead.peek(keyword::weight) {
+			content.parse::<keyword::weight>()?;
+			let weight_content;
+			syn::parenthesized!(weight_content in content);
+			Ok(FunctionAttr::Weight(weight_content.parse::<syn::Expr>()?))
+		} else if lookahead.peek(keyword::call_index) {
+			content.parse::<keyword::call_index>()?;
+			let call_index_content;
+			syn::parenthesized!(call_index_content in content);
+			let index = call_index_content.parse::<syn::LitInt>()?;
+			if !index.suffix().is_empty() {
+				let msg = "Number literal must not have a suffix";
+				return Err(syn::Error::new(index.span(), msg));
+			}
+			Ok(FunctionAttr::CallIndex(index.base10_parse()?))
+		} else {
+			Err(lookahead.error())
+		}
 	}
 }
 
@@ -128,7 +149,9 @@ impl CallDef {
 	pub fn try_from(
 		attr_span: proc_macro2::Span,
 		index: usize,
-		item: &mut syn::Item
+		item: &mut syn::Item,
+		transactional: bool,
+		dev_mode: bool,
 	) -> syn::Result<Self> {
 		let item = if let syn::Item::Impl(item) = item {
 			item
@@ -147,6 +170,8 @@ impl CallDef {
 		}
 
 		let mut methods = vec![];
+		let mut indices = std::collections::HashMap::new();
+		let mut last_index: Option<u8> = None;
 		for impl_item in &mut item.items {
 			if let syn::ImplItem::Method(method) = impl_item {
 				match method.sig.inputs.first() {
@@ -172,18 +197,60 @@ impl CallDef {
 					return Err(syn::Error::new(method.sig.span(), msg));
 				}
 
-				let mut call_var_attrs: Vec<FunctionAttr> =
+				let call_var_attrs: Vec<FunctionAttr> =
 					helper::take_item_pallet_attrs(&mut method.attrs)?;
 
-				if call_var_attrs.len() != 1 {
-					let msg = if call_var_attrs.is_empty() {
+				let mut weight_attrs = vec![];
+				let mut call_index_attrs = vec![];
+				for attr in call_var_attrs {
+					match attr {
+						FunctionAttr::Weight(weight) => weight_attrs.push(weight),
+						FunctionAttr::CallIndex(index) => call_index_attrs.push(index),
+					}
+				}
+
+				// In dev mode, the weight of a call defaults to 0.
+				if weight_attrs.is_empty() && dev_mode {
+					weight_attrs.push(syn::parse_quote!(0));
+				}
+				if weight_attrs.len() != 1 {
+					let msg = if weight_attrs.is_empty() {
 						"Invalid pallet::call, requires weight attribute i.e. `#[pallet::weight($expr)]`"
 					} else {
 						"Invalid pallet::call, too many weight attributes given"
 					};
 					return Err(syn::Error::new(method.sig.span(), msg));
 				}
-				let weight = call_var_attrs.pop().unwrap().weight;
+				let weight = weight_attrs.pop().unwrap();
+
+				if call_index_attrs.len() > 1 {
+					let msg = "Invalid pallet::call, too many call_index attributes given";
+					return Err(syn::Error::new(method.sig.span(), msg));
+				}
+				// Without an explicit index, a call follows the previous one.
+				let call_index = match call_index_attrs.pop() {
+					Some(index) => index,
+					None => match last_index {
+						None => 0,
+						Some(last) => last.checked_add(1).ok_or_else(|| {
+							let msg = "Invalid pallet::call, call index overflows `u8`";
+							syn::Error::new(method.sig.span(), msg)
+						})?,
+					},
+				};
+				last_index = Some(call_index);
+
+				if let Some(used_by) = indices.insert(call_index, method.sig.ident.clone()) {
+					let msg = format!(
+						"Call indices are conflicting: Both functions {} and {} are at index {}",
+						used_by,
+						method.sig.ident,
+						call_index,
+					);
+					let mut err = syn::Error::new(used_by.span(), &msg);
+					err.combine(syn::Error::new(method.sig.ident.span(), msg));
+					return Err(err);
+				}
 
 				let mut args = vec![];
 				for arg in method.sig.inputs.iter_mut().skip(1) {
@@ -216,6 +283,7 @@ impl CallDef {
 				methods.push(CallVariantDef {
 					name: method.sig.ident.clone(),
 					weight,
+					call_index,
 					args,
 					docs,
 				});
@@ -225,6 +293,19 @@ impl CallDef {
 			}
 		}
 
+		// The metadata lists the calls without their index, so clients derive the index of a call
+		// from its position in the list. With a gap, every call after it would be encoded with the
+		// wrong index by them, hence indices must be contiguous.
+		methods.sort_by_key(|method| method.call_index);
+		let gap = methods.iter().enumerate().find(|(i, method)| method.call_index as usize != *i);
+		if let Some((missing, method)) = gap {
+			let msg = format!(
+				"Invalid pallet::call, call indices must be contiguous from 0, but {} is unused",
+				missing,
+			);
+			return Err(syn::Error::new(method.name.span(), msg));
+		}
+
 		Ok(Self {
 			index,
 			attr_span,
@@ -232,6 +313,7 @@ impl CallDef {
 			methods,
 			where_clause: item.generics.where_clause.clone(),
 			docs: helper::get_doc_literals(&item.attrs),
+			transactional,
 		})
 	}
 }
@@ -0,0 +1,72 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use syn::spanned::Spanned;
+
+/// The names a composite enum may have, each aggregated by `construct_runtime` into the runtime
+/// enum of the same name.
+pub const COMPOSITE_ENUM_NAMES: &[&str] = &["HoldReason", "FreezeReason", "SlashReason"];
+
+/// Definition of a composite enum, such as `HoldReason`.
+///
+/// Composite enums are non generic and public, and have unit variants only, such that they can
+/// be stored along with the funds they account for.
+pub struct CompositeDef {
+	/// The index of the enum item in pallet module.
+	pub index: usize,
+	/// The name of the enum, one of `COMPOSITE_ENUM_NAMES`.
+	pub ident: syn::Ident,
+	/// The span of the pallet::composite_enum attribute.
+	pub attr_span: proc_macro2::Span,
+}
+
+impl CompositeDef {
+	pub fn try_from(
+		attr_span: proc_macro2::Span,
+		index: usize,
+		item: &mut syn::Item,
+	) -> syn::Result<Self> {
+		let item = if let syn::Item::Enum(item) = item {
+			item
+		} else {
+			let msg = "Invalid pallet::composite_enum, expected item enum";
+			return Err(syn::Error::new(item.span(), msg));
+		};
+		if !matches!(item.vis, syn::Visibility::Public(_)) {
+			let msg = "Invalid pallet::composite_enum, composite enums must be public";
+			return Err(syn::Error::new(item.span(), msg));
+		}
+		if !COMPOSITE_ENUM_NAMES.iter().any(|name| item.ident == name) {
+			let msg = format!(
+				"Invalid pallet::composite_enum, expected one of `{}`",
+				COMPOSITE_ENUM_NAMES.join("`, `"),
+			);
+			return Err(syn::Error::new(item.ident.span(), msg));
+		}
+		if !item.generics.params.is_empty() || item.generics.where_clause.is_some() {
+			let msg = "Invalid pallet::composite_enum, composite enums must not be generic";
+			return Err(syn::Error::new(item.generics.span(), msg));
+		}
+		for variant in &item.variants {
+			if !matches!(variant.fields, syn::Fields::Unit) {
+				let msg = "Invalid pallet::composite_enum, unexpected fields, must be `Unit`";
+				return Err(syn::Error::new(variant.fields.span(), msg));
+			}
+		}
+
+		Ok(CompositeDef { index, ident: item.ident.clone(), attr_span })
+	}
+}
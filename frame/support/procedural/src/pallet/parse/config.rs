@@ -30,6 +30,8 @@ mod keyword {
 	syn::custom_keyword!(IsType);
 	syn::custom_keyword!(Event);
 	syn::custom_keyword!(constant);
+	syn::custom_keyword!(no_default);
+	syn::custom_keyword!(no_default_bounds);
 	syn::custom_keyword!(frame_system);
 	syn::custom_keyword!(disable_frame_system_supertrait_check);
 }
@@ -50,6 +52,16 @@ pub struct ConfigDef {
 	pub where_clause: Option<syn::WhereClause>,
 	/// The span of the pallet::config attribute.
 	pub attr_span: proc_macro2::Span,
+	/// The `DefaultConfig` trait, if `#[pallet::config(with_default)]` is used.
+	pub default_sub_trait: Option<DefaultTrait>,
+}
+
+/// Input definition for the `DefaultConfig` trait generated by `#[pallet::config(with_default)]`.
+pub struct DefaultTrait {
+	/// The associated types of `Config` given a default, i.e. not marked `#[pallet::no_default]`,
+	/// along with whether their bounds are dropped, i.e. they are marked
+	/// `#[pallet::no_default_bounds]`.
+	pub items: Vec<(syn::TraitItem, bool)>,
 }
 
 /// Input definition for a constant in pallet config.
@@ -96,16 +108,22 @@ impl syn::parse::Parse for DisableFrameSystemSupertraitCheck {
 	}
 }
 
-/// Parse for `#[pallet::constant]`
-pub struct TypeAttrConst(proc_macro2::Span);
+/// Parse for `#[pallet::constant]`, `#[pallet::no_default]` or `#[pallet::no_default_bounds]`
+pub enum TypeAttr {
+	Constant(proc_macro2::Span),
+	NoDefault(proc_macro2::Span),
+	NoDefaultBounds(proc_macro2::Span),
+}
 
-impl Spanned for TypeAttrConst {
+impl Spanned for TypeAttr {
 	fn span(&self) -> proc_macro2::Span {
-		self.0
+		match self {
+			Self::Constant(span) | Self::NoDefault(span) | Self::NoDefaultBounds(span) => *span,
+		}
 	}
 }
 
-impl syn::parse::Parse for TypeAttrConst {
+impl syn::parse::Parse for TypeAttr {
 	fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
 		input.parse::<syn::Token![#]>()?;
 		let content;
@@ -113,7 +131,16 @@ impl syn::parse::Parse for TypeAttrConst {
 		content.parse::<syn::Ident>()?;
 		content.parse::<syn::Token![::]>()?;
 
-		Ok(TypeAttrConst(content.parse::<keyword::constant>()?.span()))
+		let lookahead = content.lookahead1();
+		if lookahead.peek(keyword::constant) {
+			Ok(Self::Constant(content.parse::<keyword::constant>()?.span()))
+		} else if lookahead.peek(keyword::no_default_bounds) {
+			Ok(Self::NoDefaultBounds(content.parse::<keyword::no_default_bounds>()?.span()))
+		} else if lookahead.peek(keyword::no_default) {
+			Ok(Self::NoDefault(content.parse::<keyword::no_default>()?.span()))
+		} else {
+			Err(lookahead.error())
+		}
 	}
 }
 
@@ -270,6 +297,7 @@ impl ConfigDef {
 		attr_span: proc_macro2::Span,
 		index: usize,
 		item: &mut syn::Item,
+		with_default: bool,
 	) -> syn::Result<Self> {
 		let item = if let syn::Item::Trait(item) = item {
 			item
@@ -307,17 +335,59 @@ impl ConfigDef {
 
 		let mut has_event_type = false;
 		let mut consts_metadata = vec![];
+		let mut default_items = vec![];
 		for trait_item in &mut item.items {
 			// Parse for event
 			has_event_type = has_event_type
 				|| check_event_type(frame_system, trait_item, has_instance)?;
 
-			// Parse for constant
-			let type_attrs_const: Vec<TypeAttrConst> = helper::take_item_pallet_attrs(trait_item)?;
+			let type_attrs: Vec<TypeAttr> = helper::take_item_pallet_attrs(trait_item)?;
+
+			let mut type_attrs_const = vec![];
+			let mut no_default = None;
+			let mut no_default_bounds = None;
+			for attr in type_attrs {
+				match attr {
+					TypeAttr::Constant(span) => type_attrs_const.push(span),
+					TypeAttr::NoDefault(span) if no_default.is_none() => no_default = Some(span),
+					TypeAttr::NoDefaultBounds(span) if no_default_bounds.is_none() =>
+						no_default_bounds = Some(span),
+					attr => {
+						let msg = "Invalid duplicated attribute in pallet::config";
+						return Err(syn::Error::new(attr.span(), msg));
+					},
+				}
+			}
+
+			if let Some(span) = no_default.or(no_default_bounds).filter(|_| !with_default) {
+				let msg = "Invalid attribute in pallet::config, `#[pallet::no_default]` and \
+					`#[pallet::no_default_bounds]` require `#[pallet::config(with_default)]`";
+				return Err(syn::Error::new(span, msg));
+			}
 
+			if let (Some(span), Some(_)) = (no_default_bounds, no_default) {
+				let msg = "Invalid attribute in pallet::config, `#[pallet::no_default_bounds]` is \
+					useless on an item without default";
+				return Err(syn::Error::new(span, msg));
+			}
+
+			if with_default && no_default.is_none() {
+				match trait_item {
+					syn::TraitItem::Type(_) => {
+						default_items.push((trait_item.clone(), no_default_bounds.is_some()))
+					},
+					_ => {
+						let msg = "Invalid item in pallet::config(with_default), only associated \
+							types can be given a default, use `#[pallet::no_default]` otherwise";
+						return Err(syn::Error::new(trait_item.span(), msg));
+					},
+				}
+			}
+
+			// Parse for constant
 			if type_attrs_const.len() > 1 {
 				let msg = "Invalid attribute in pallet::config, only one attribute is expected";
-				return Err(syn::Error::new(type_attrs_const[1].span(), msg));
+				return Err(syn::Error::new(type_attrs_const[1], msg));
 			}
 
 			if type_attrs_const.len() == 1 {
@@ -386,6 +456,11 @@ impl ConfigDef {
 			has_event_type,
 			where_clause,
 			attr_span,
+			default_sub_trait: if with_default {
+				Some(DefaultTrait { items: default_items })
+			} else {
+				None
+			},
 		})
 	}
 }
@@ -27,6 +27,7 @@ mod keyword {
 	syn::custom_keyword!(pallet);
 	syn::custom_keyword!(generate_deposit);
 	syn::custom_keyword!(deposit_event);
+	syn::custom_keyword!(indexed);
 }
 
 /// Definition for pallet event enum.
@@ -37,6 +38,8 @@ pub struct EventDef {
 	pub event: keyword::Event,
 	/// Event metadatas: `(name, args, docs)`.
 	pub metadata: Vec<(syn::Ident, Vec<String>, Vec<syn::Lit>)>,
+	/// The fields indexed as topics, i.e. with `#[pallet::indexed]`: `(variant, fields)`.
+	pub topics: Vec<(syn::Ident, Vec<syn::Member>)>,
 	/// A set of usage of instance, must be check for consistency with trait.
 	pub instances: Vec<helper::InstanceUsage>,
 	/// The kind of generic the type `Event` has.
@@ -78,6 +81,21 @@ impl PalletEventAttr {
 	}
 }
 
+/// Attribute for a field of an event variant: `#[pallet::indexed]`.
+pub struct PalletEventFieldAttrIndexed;
+
+impl syn::parse::Parse for PalletEventFieldAttrIndexed {
+	fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+		input.parse::<syn::Token![#]>()?;
+		let content;
+		syn::bracketed!(content in input);
+		content.parse::<keyword::pallet>()?;
+		content.parse::<syn::Token![::]>()?;
+		content.parse::<keyword::indexed>()?;
+		Ok(PalletEventFieldAttrIndexed)
+	}
+}
+
 /// Parse for syntax `$Type = "$SomeString"`.
 fn parse_event_metadata_element(
 	input: syn::parse::ParseStream
@@ -195,6 +213,28 @@ impl EventDef {
 
 		let event = syn::parse2::<keyword::Event>(item.ident.to_token_stream())?;
 
+		let mut topics = vec![];
+		for variant in item.variants.iter_mut() {
+			let mut indexed = vec![];
+			for (i, field) in variant.fields.iter_mut().enumerate() {
+				let attrs: Vec<PalletEventFieldAttrIndexed> =
+					helper::take_item_pallet_attrs(&mut field.attrs)?;
+				if attrs.len() > 1 {
+					let msg = "Invalid pallet::event, duplicated `#[pallet::indexed]` attribute";
+					return Err(syn::Error::new(field.span(), msg));
+				}
+				if !attrs.is_empty() {
+					indexed.push(match &field.ident {
+						Some(ident) => syn::Member::Named(ident.clone()),
+						None => syn::Member::Unnamed(i.into()),
+					});
+				}
+			}
+			if !indexed.is_empty() {
+				topics.push((variant.ident.clone(), indexed));
+			}
+		}
+
 		let metadata = item.variants.iter()
 			.map(|variant| {
 				let name = variant.ident.clone();
@@ -217,6 +257,7 @@ impl EventDef {
 			attr_span,
 			index,
 			metadata,
+			topics,
 			instances,
 			deposit_event,
 			event,
@@ -23,6 +23,8 @@ pub mod config;
 pub mod pallet_struct;
 pub mod hooks;
 pub mod call;
+pub mod tasks;
+pub mod composite;
 pub mod error;
 pub mod origin;
 pub mod inherent;
@@ -47,6 +49,7 @@ pub struct Def {
 	pub pallet_struct: pallet_struct::PalletStructDef,
 	pub hooks: Option<hooks::HooksDef>,
 	pub call: Option<call::CallDef>,
+	pub tasks: Option<tasks::TasksDef>,
 	pub storages: Vec<storage::StorageDef>,
 	pub error: Option<error::ErrorDef>,
 	pub event: Option<event::EventDef>,
@@ -57,12 +60,15 @@ pub struct Def {
 	pub validate_unsigned: Option<validate_unsigned::ValidateUnsignedDef>,
 	pub extra_constants: Option<extra_constants::ExtraConstantsDef>,
 	pub type_values: Vec<type_value::TypeValueDef>,
+	pub composites: Vec<composite::CompositeDef>,
 	pub frame_system: syn::Ident,
 	pub frame_support: syn::Ident,
+	/// Whether the pallet is declared with `#[pallet(dev_mode)]`, which relaxes some requirements.
+	pub dev_mode: bool,
 }
 
 impl Def {
-	pub fn try_from(mut item: syn::ItemMod) -> syn::Result<Self> {
+	pub fn try_from(mut item: syn::ItemMod, dev_mode: bool) -> syn::Result<Self> {
 		let frame_system = generate_crate_access_2018("frame-system")?;
 		let frame_support = generate_crate_access_2018("frame-support")?;
 
@@ -77,6 +83,7 @@ impl Def {
 		let mut pallet_struct = None;
 		let mut hooks = None;
 		let mut call = None;
+		let mut tasks = None;
 		let mut error = None;
 		let mut event = None;
 		let mut origin = None;
@@ -87,13 +94,20 @@ impl Def {
 		let mut extra_constants = None;
 		let mut storages = vec![];
 		let mut type_values = vec![];
+		let mut composites: Vec<composite::CompositeDef> = vec![];
 
 		for (index, item) in items.iter_mut().enumerate() {
 			let pallet_attr: Option<PalletAttr> = helper::take_first_item_pallet_attr(item)?;
 
 			match pallet_attr {
-				Some(PalletAttr::Config(span)) if config.is_none() =>
-					config = Some(config::ConfigDef::try_from(&frame_system, span, index, item)?),
+				Some(PalletAttr::Config(span, with_default)) if config.is_none() =>
+					config = Some(config::ConfigDef::try_from(
+						&frame_system,
+						span,
+						index,
+						item,
+						with_default,
+					)?),
 				Some(PalletAttr::Pallet(span)) if pallet_struct.is_none() => {
 					let p = pallet_struct::PalletStructDef::try_from(span, index, item)?;
 					pallet_struct = Some(p);
@@ -102,8 +116,12 @@ impl Def {
 					let m = hooks::HooksDef::try_from(span, index, item)?;
 					hooks = Some(m);
 				},
-				Some(PalletAttr::Call(span)) if call.is_none() =>
-					call = Some(call::CallDef::try_from(span, index, item)?),
+				Some(PalletAttr::Call(span, transactional)) if call.is_none() =>
+					call = Some(
+						call::CallDef::try_from(span, index, item, transactional, dev_mode)?,
+					),
+				Some(PalletAttr::Tasks(span)) if tasks.is_none() =>
+					tasks = Some(tasks::TasksDef::try_from(span, index, item)?),
 				Some(PalletAttr::Error(span)) if error.is_none() =>
 					error = Some(error::ErrorDef::try_from(span, index, item)?),
 				Some(PalletAttr::Event(span)) if event.is_none() =>
@@ -121,7 +139,7 @@ impl Def {
 				Some(PalletAttr::Inherent(_)) if inherent.is_none() =>
 					inherent = Some(inherent::InherentDef::try_from(index, item)?),
 				Some(PalletAttr::Storage(span)) =>
-					storages.push(storage::StorageDef::try_from(span, index, item)?),
+					storages.push(storage::StorageDef::try_from(span, index, item, dev_mode)?),
 				Some(PalletAttr::ValidateUnsigned(_)) if validate_unsigned.is_none() => {
 					let v = validate_unsigned::ValidateUnsignedDef::try_from(index, item)?;
 					validate_unsigned = Some(v);
@@ -132,6 +150,14 @@ impl Def {
 					extra_constants =
 						Some(extra_constants::ExtraConstantsDef::try_from(index, item)?)
 				},
+				Some(PalletAttr::CompositeEnum(span)) => {
+					let composite = composite::CompositeDef::try_from(span, index, item)?;
+					if composites.iter().any(|other| other.ident == composite.ident) {
+						let msg = "Invalid duplicated composite enum";
+						return Err(syn::Error::new(composite.ident.span(), msg));
+					}
+					composites.push(composite);
+				},
 				Some(attr) => {
 					let msg = "Invalid duplicated attribute";
 					return Err(syn::Error::new(attr.span(), msg));
@@ -158,6 +184,7 @@ impl Def {
 				.ok_or_else(|| syn::Error::new(item_span, "Missing `#[pallet::pallet]`"))?,
 			hooks,
 			call,
+			tasks,
 			extra_constants,
 			genesis_config,
 			genesis_build,
@@ -168,8 +195,10 @@ impl Def {
 			inherent,
 			storages,
 			type_values,
+			composites,
 			frame_system,
 			frame_support,
+			dev_mode,
 		};
 
 		def.check_instance_usage()?;
@@ -210,6 +239,9 @@ impl Def {
 		if let Some(call) = &self.call {
 			instances.extend_from_slice(&call.instances[..]);
 		}
+		if let Some(tasks) = &self.tasks {
+			instances.extend_from_slice(&tasks.instances[..]);
+		}
 		if let Some(hooks) = &self.hooks {
 			instances.extend_from_slice(&hooks.instances[..]);
 		}
@@ -368,6 +400,7 @@ impl GenericKind {
 mod keyword {
 	syn::custom_keyword!(origin);
 	syn::custom_keyword!(call);
+	syn::custom_keyword!(tasks);
 	syn::custom_keyword!(event);
 	syn::custom_keyword!(config);
 	syn::custom_keyword!(hooks);
@@ -382,15 +415,21 @@ mod keyword {
 	syn::custom_keyword!(generate_store);
 	syn::custom_keyword!(Store);
 	syn::custom_keyword!(extra_constants);
+	syn::custom_keyword!(composite_enum);
+	syn::custom_keyword!(transactional);
+	syn::custom_keyword!(with_default);
 }
 
 /// Parse attributes for item in pallet module
 /// syntax must be `pallet::` (e.g. `#[pallet::config]`)
 enum PalletAttr {
-	Config(proc_macro2::Span),
+	/// The span of the attribute and whether a `DefaultConfig` trait is generated.
+	Config(proc_macro2::Span, bool),
 	Pallet(proc_macro2::Span),
 	Hooks(proc_macro2::Span),
-	Call(proc_macro2::Span),
+	/// The span of the attribute and whether all calls are dispatched in a storage layer.
+	Call(proc_macro2::Span, bool),
+	Tasks(proc_macro2::Span),
 	Error(proc_macro2::Span),
 	Event(proc_macro2::Span),
 	Origin(proc_macro2::Span),
@@ -401,15 +440,17 @@ enum PalletAttr {
 	ValidateUnsigned(proc_macro2::Span),
 	TypeValue(proc_macro2::Span),
 	ExtraConstants(proc_macro2::Span),
+	CompositeEnum(proc_macro2::Span),
 }
 
 impl PalletAttr {
 	fn span(&self) -> proc_macro2::Span {
 		match self {
-			Self::Config(span) => *span,
+			Self::Config(span, _) => *span,
 			Self::Pallet(span) => *span,
 			Self::Hooks(span) => *span,
-			Self::Call(span) => *span,
+			Self::Call(span, _) => *span,
+			Self::Tasks(span) => *span,
 			Self::Error(span) => *span,
 			Self::Event(span) => *span,
 			Self::Origin(span) => *span,
@@ -420,6 +461,7 @@ impl PalletAttr {
 			Self::ValidateUnsigned(span) => *span,
 			Self::TypeValue(span) => *span,
 			Self::ExtraConstants(span) => *span,
+			Self::CompositeEnum(span) => *span,
 		}
 	}
 }
@@ -434,13 +476,33 @@ impl syn::parse::Parse for PalletAttr {
 
 		let lookahead = content.lookahead1();
 		if lookahead.peek(keyword::config) {
-			Ok(PalletAttr::Config(content.parse::<keyword::config>()?.span()))
+			let span = content.parse::<keyword::config>()?.span();
+			let with_default = if content.peek(syn::token::Paren) {
+				let args;
+				syn::parenthesized!(args in content);
+				args.parse::<keyword::with_default>()?;
+				true
+			} else {
+				false
+			};
+			Ok(PalletAttr::Config(span, with_default))
 		} else if lookahead.peek(keyword::pallet) {
 			Ok(PalletAttr::Pallet(content.parse::<keyword::pallet>()?.span()))
 		} else if lookahead.peek(keyword::hooks) {
 			Ok(PalletAttr::Hooks(content.parse::<keyword::hooks>()?.span()))
 		} else if lookahead.peek(keyword::call) {
-			Ok(PalletAttr::Call(content.parse::<keyword::call>()?.span()))
+			let span = content.parse::<keyword::call>()?.span();
+			let transactional = if content.peek(syn::token::Paren) {
+				let args;
+				syn::parenthesized!(args in content);
+				args.parse::<keyword::transactional>()?;
+				true
+			} else {
+				false
+			};
+			Ok(PalletAttr::Call(span, transactional))
+		} else if lookahead.peek(keyword::tasks) {
+			Ok(PalletAttr::Tasks(content.parse::<keyword::tasks>()?.span()))
 		} else if lookahead.peek(keyword::error) {
 			Ok(PalletAttr::Error(content.parse::<keyword::error>()?.span()))
 		} else if lookahead.peek(keyword::event) {
@@ -461,6 +523,8 @@ impl syn::parse::Parse for PalletAttr {
 			Ok(PalletAttr::TypeValue(content.parse::<keyword::type_value>()?.span()))
 		} else if lookahead.peek(keyword::extra_constants) {
 			Ok(PalletAttr::ExtraConstants(content.parse::<keyword::extra_constants>()?.span()))
+		} else if lookahead.peek(keyword::composite_enum) {
+			Ok(PalletAttr::CompositeEnum(content.parse::<keyword::composite_enum>()?.span()))
 		} else {
 			Err(lookahead.error())
 		}
@@ -16,6 +16,7 @@
 // limitations under the License.
 
 use super::helper;
+use frame_support_procedural_tools::generate_crate_access_2018;
 use syn::spanned::Spanned;
 use quote::ToTokens;
 use std::collections::HashMap;
@@ -54,6 +55,7 @@ impl syn::parse::Parse for PalletStorageAttr {
 pub enum Metadata {
 	Value { value: syn::Type },
 	Map { value: syn::Type, key: syn::Type },
+	CountedMap { value: syn::Type, key: syn::Type },
 	DoubleMap {
 		value: syn::Type,
 		key1: syn::Type,
@@ -127,6 +129,14 @@ pub enum StorageGenerics {
 		on_empty: Option<syn::Type>,
 		max_values: Option<syn::Type>,
 	},
+	CountedMap {
+		hasher: syn::Type,
+		key: syn::Type,
+		value: syn::Type,
+		query_kind: Option<syn::Type>,
+		on_empty: Option<syn::Type>,
+		max_values: Option<syn::Type>,
+	},
 	Value {
 		value: syn::Type,
 		query_kind: Option<syn::Type>,
@@ -147,6 +157,7 @@ impl StorageGenerics {
 		let res = match self.clone() {
 			Self::DoubleMap { value, key1, key2, .. } => Metadata::DoubleMap { value, key1, key2 },
 			Self::Map { value, key, .. } => Metadata::Map { value, key },
+			Self::CountedMap { value, key, .. } => Metadata::CountedMap { value, key },
 			Self::Value { value, .. } => Metadata::Value { value },
 			Self::NMap { keygen, value, .. } => Metadata::NMap {
 				keys: collect_keys(&keygen)?,
@@ -163,6 +174,7 @@ impl StorageGenerics {
 		match &self {
 			Self::DoubleMap { query_kind, .. }
 			| Self::Map { query_kind, .. }
+			| Self::CountedMap { query_kind, .. }
 			| Self::Value { query_kind, .. }
 			| Self::NMap { query_kind, .. }
 				=> query_kind.clone(),
@@ -173,6 +185,7 @@ impl StorageGenerics {
 enum StorageKind {
 	Value,
 	Map,
+	CountedMap,
 	DoubleMap,
 	NMap,
 }
@@ -300,6 +313,30 @@ fn process_named_generics(
 				max_values: parsed.remove("MaxValues").map(|binding| binding.ty),
 			}
 		}
+		StorageKind::CountedMap => {
+			check_generics(
+				&parsed,
+				&["Hasher", "Key", "Value"],
+				&["QueryKind", "OnEmpty", "MaxValues"],
+				"CountedStorageMap",
+				args_span,
+			)?;
+
+			StorageGenerics::CountedMap {
+				hasher: parsed.remove("Hasher")
+					.map(|binding| binding.ty)
+					.expect("checked above as mandatory generic"),
+				key: parsed.remove("Key")
+					.map(|binding| binding.ty)
+					.expect("checked above as mandatory generic"),
+				value: parsed.remove("Value")
+					.map(|binding| binding.ty)
+					.expect("checked above as mandatory generic"),
+				query_kind: parsed.remove("QueryKind").map(|binding| binding.ty),
+				on_empty: parsed.remove("OnEmpty").map(|binding| binding.ty),
+				max_values: parsed.remove("MaxValues").map(|binding| binding.ty),
+			}
+		}
 		StorageKind::DoubleMap => {
 			check_generics(
 				&parsed,
@@ -403,6 +440,14 @@ fn process_unnamed_generics(
 			},
 			retrieve_arg(4).ok(),
 		),
+		StorageKind::CountedMap => (
+			None,
+			Metadata::CountedMap {
+				key: retrieve_arg(2)?,
+				value: retrieve_arg(3)?,
+			},
+			retrieve_arg(4).ok(),
+		),
 		StorageKind::DoubleMap => (
 			None,
 			Metadata::DoubleMap {
@@ -437,13 +482,14 @@ fn process_generics(
 	let storage_kind = match &*segment.ident.to_string() {
 		"StorageValue" => StorageKind::Value,
 		"StorageMap" => StorageKind::Map,
+		"CountedStorageMap" => StorageKind::CountedMap,
 		"StorageDoubleMap" => StorageKind::DoubleMap,
 		"StorageNMap" => StorageKind::NMap,
 		found => {
 			let msg = format!(
-				"Invalid pallet::storage, expected ident: `StorageValue` or \
-				`StorageMap` or `StorageDoubleMap` or `StorageNMap` in order to expand metadata, \
-				found `{}`.",
+				"Invalid pallet::storage, expected ident: `StorageValue` or `StorageMap` or \
+				`CountedStorageMap` or `StorageDoubleMap` or `StorageNMap` in order to expand \
+				metadata, found `{}`.",
 				found,
 			);
 			return Err(syn::Error::new(segment.ident.span(), msg));
@@ -540,11 +586,65 @@ fn extract_key(ty: &syn::Type) -> syn::Result<syn::Type> {
 	Ok(key)
 }
 
+/// Replace the hashers given as `_` in the generics of the storage by `hasher`.
+fn replace_infer_hashers(segment: &mut syn::PathSegment, hasher: &syn::Type) {
+	let storage = segment.ident.to_string();
+	let args = match &mut segment.arguments {
+		syn::PathArguments::AngleBracketed(args) => &mut args.args,
+		_ => return,
+	};
+
+	for (i, arg) in args.iter_mut().enumerate() {
+		match arg {
+			syn::GenericArgument::Type(ty) => match (storage.as_str(), i) {
+				("StorageMap", 1) | ("CountedStorageMap", 1) | ("StorageDoubleMap", 1)
+				| ("StorageDoubleMap", 3) => replace_infer(ty, hasher),
+				("StorageNMap", 1) => replace_infer_in_keygen(ty, hasher),
+				_ => (),
+			},
+			syn::GenericArgument::Binding(binding) => match binding.ident.to_string().as_str() {
+				"Hasher" | "Hasher1" | "Hasher2" => replace_infer(&mut binding.ty, hasher),
+				"Key" if storage == "StorageNMap" =>
+					replace_infer_in_keygen(&mut binding.ty, hasher),
+				_ => (),
+			},
+			_ => (),
+		}
+	}
+}
+
+/// Replace the hashers given as `_` in the `Key<_, K>` of a `StorageNMap` by `hasher`.
+fn replace_infer_in_keygen(keygen: &mut syn::Type, hasher: &syn::Type) {
+	let keys: Vec<&mut syn::Type> = match keygen {
+		syn::Type::Tuple(tup) => tup.elems.iter_mut().collect(),
+		key => vec![key],
+	};
+
+	for key in keys {
+		let args = match key {
+			syn::Type::Path(key) => key.path.segments.last_mut().map(|s| &mut s.arguments),
+			_ => None,
+		};
+		if let Some(syn::PathArguments::AngleBracketed(args)) = args {
+			if let Some(syn::GenericArgument::Type(ty)) = args.args.first_mut() {
+				replace_infer(ty, hasher);
+			}
+		}
+	}
+}
+
+fn replace_infer(ty: &mut syn::Type, hasher: &syn::Type) {
+	if let syn::Type::Infer(_) = ty {
+		*ty = hasher.clone();
+	}
+}
+
 impl StorageDef {
 	pub fn try_from(
 		attr_span: proc_macro2::Span,
 		index: usize,
 		item: &mut syn::Item,
+		dev_mode: bool,
 	) -> syn::Result<Self> {
 		let item = if let syn::Item::Type(item) = item {
 			item
@@ -567,6 +667,16 @@ impl StorageDef {
 		let where_clause = item.generics.where_clause.clone();
 		let docs = helper::get_doc_literals(&item.attrs);
 
+		if dev_mode {
+			if let syn::Type::Path(typ) = &mut *item.ty {
+				if let Some(segment) = typ.path.segments.last_mut() {
+					let frame_support = generate_crate_access_2018("frame-support")?;
+					let hasher: syn::Type = syn::parse_quote!(#frame_support::Blake2_128Concat);
+					replace_infer_hashers(segment, &hasher);
+				}
+			}
+		}
+
 		let typ = if let syn::Type::Path(typ) = &*item.ty {
 			typ
 		} else {
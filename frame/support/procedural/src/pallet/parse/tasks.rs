@@ -0,0 +1,249 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::helper;
+use syn::spanned::Spanned;
+
+/// List of additional token to be used for parsing.
+mod keyword {
+	syn::custom_keyword!(pallet);
+	syn::custom_keyword!(task_list);
+	syn::custom_keyword!(task_condition);
+	syn::custom_keyword!(task_weight);
+	syn::custom_keyword!(task_index);
+}
+
+/// Definition of the tasks of the pallet, typically `impl<T: Config> Pallet<T> { ... }`
+pub struct TasksDef {
+	/// The index of the tasks item in pallet module.
+	pub index: usize,
+	/// The span of the pallet::tasks attribute.
+	pub attr_span: proc_macro2::Span,
+	/// The where_clause used.
+	pub where_clause: Option<syn::WhereClause>,
+	/// A set of usage of instance, must be check for consistency with trait.
+	pub instances: Vec<helper::InstanceUsage>,
+	/// The tasks, one per method.
+	pub tasks: Vec<TaskDef>,
+}
+
+/// Definition of a task, typically:
+/// ```ignore
+/// #[pallet::task_list($expr)]
+/// #[pallet::task_condition($closure)]
+/// #[pallet::task_weight($expr)]
+/// fn foo(param1: ..) -> DispatchResult
+/// ```
+pub struct TaskDef {
+	/// Function name.
+	pub name: syn::Ident,
+	/// Information on args: `(name, type)`
+	pub args: Vec<(syn::Ident, Box<syn::Type>)>,
+	/// The expression listing the arguments of the tasks which may be valid.
+	pub list: syn::Expr,
+	/// The closure telling whether the task with the given arguments is valid.
+	pub condition: syn::Expr,
+	/// Weight formula.
+	pub weight: syn::Expr,
+	/// The index of the task in the `Task` enum, used in its encoding.
+	pub task_index: u8,
+	/// Docs, used for the `Task` enum.
+	pub docs: Vec<syn::Lit>,
+}
+
+/// Attributes for functions in tasks impl block.
+pub enum TaskAttr {
+	/// Parse for `#[pallet::task_list(expr)]`
+	List(syn::Expr),
+	/// Parse for `#[pallet::task_condition(expr)]`
+	Condition(syn::Expr),
+	/// Parse for `#[pallet::task_weight(expr)]`
+	Weight(syn::Expr),
+	/// Parse for `#[pallet::task_index(expr)]`
+	Index(u8),
+}
+
+impl syn::parse::Parse for TaskAttr {
+	fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+		input.parse::<syn::Token![#]>()?;
+		let content;
+		syn::bracketed!(content in input);
+		content.parse::<keyword::pallet>()?;
+		content.parse::<syn::Token![::]>()?;
+
+		let lookahead = content.lookahead1();
+		let attr = if lookahead.peek(keyword::task_list) {
+			content.parse::<keyword::task_list>()?;
+			TaskAttr::List
+		} else if lookahead.peek(keyword::task_condition) {
+			content.parse::<keyword::task_condition>()?;
+			TaskAttr::Condition
+		} else if lookahead.peek(keyword::task_weight) {
+			content.parse::<keyword::task_weight>()?;
+			TaskAttr::Weight
+		} else if lookahead.peek(keyword::task_index) {
+			content.parse::<keyword::task_index>()?;
+			let index_content;
+			syn::parenthesized!(index_content in content);
+			let index = index_content.parse::<syn::LitInt>()?;
+			if !index.suffix().is_empty() {
+				let msg = "Number literal must not have a suffix";
+				return Err(syn::Error::new(index.span(), msg));
+			}
+			return Ok(TaskAttr::Index(index.base10_parse()?))
+		} else {
+			return Err(lookahead.error())
+		};
+
+		let expr_content;
+		syn::parenthesized!(expr_content in content);
+		Ok(attr(expr_content.parse::<syn::Expr>()?))
+	}
+}
+
+impl TasksDef {
+	pub fn try_from(
+		attr_span: proc_macro2::Span,
+		index: usize,
+		item: &mut syn::Item,
+	) -> syn::Result<Self> {
+		let item = if let syn::Item::Impl(item) = item {
+			item
+		} else {
+			return Err(syn::Error::new(item.span(), "Invalid pallet::tasks, expected item impl"));
+		};
+
+		let mut instances = vec![];
+		instances.push(helper::check_impl_gen(&item.generics, item.impl_token.span())?);
+		instances.push(helper::check_pallet_struct_usage(&item.self_ty)?);
+
+		if let Some((_, _, for_)) = item.trait_ {
+			let msg = "Invalid pallet::tasks, expected no trait ident as in \
+				`impl<..> Pallet<..> { .. }`";
+			return Err(syn::Error::new(for_.span(), msg))
+		}
+
+		let mut tasks = vec![];
+		let mut indices = std::collections::HashMap::new();
+		let mut last_index: Option<u8> = None;
+		for impl_item in &mut item.items {
+			let method = if let syn::ImplItem::Method(method) = impl_item {
+				method
+			} else {
+				let msg = "Invalid pallet::tasks, only method accepted";
+				return Err(syn::Error::new(impl_item.span(), msg));
+			};
+
+			if let syn::ReturnType::Type(_, type_) = &method.sig.output {
+				helper::check_pallet_call_return_type(type_)?;
+			} else {
+				let msg = "Invalid pallet::tasks, require return type DispatchResult";
+				return Err(syn::Error::new(method.sig.span(), msg));
+			}
+
+			let mut list = None;
+			let mut condition = None;
+			let mut weight = None;
+			let mut task_index = None;
+			for attr in helper::take_item_pallet_attrs(&mut method.attrs)? {
+				let duplicated = match attr {
+					TaskAttr::List(expr) => list.replace(expr).is_some(),
+					TaskAttr::Condition(expr) => condition.replace(expr).is_some(),
+					TaskAttr::Weight(expr) => weight.replace(expr).is_some(),
+					TaskAttr::Index(index) => task_index.replace(index).is_some(),
+				};
+				if duplicated {
+					let msg = "Invalid pallet::tasks, duplicated attribute";
+					return Err(syn::Error::new(method.sig.span(), msg));
+				}
+			}
+
+			let missing = |attr| {
+				let msg = format!(
+					"Invalid pallet::tasks, requires attribute `#[pallet::{}($expr)]`",
+					attr,
+				);
+				syn::Error::new(method.sig.span(), msg)
+			};
+			let list = list.ok_or_else(|| missing("task_list"))?;
+			let condition = condition.ok_or_else(|| missing("task_condition"))?;
+			let weight = weight.ok_or_else(|| missing("task_weight"))?;
+
+			// Without an explicit index, a task follows the previous one.
+			let task_index = match task_index {
+				Some(index) => index,
+				None => match last_index {
+					None => 0,
+					Some(last) => last.checked_add(1).ok_or_else(|| {
+						let msg = "Invalid pallet::tasks, task index overflows `u8`";
+						syn::Error::new(method.sig.span(), msg)
+					})?,
+				},
+			};
+			last_index = Some(task_index);
+
+			if let Some(used_by) = indices.insert(task_index, method.sig.ident.clone()) {
+				let msg = format!(
+					"Task indices are conflicting: Both functions {} and {} are at index {}",
+					used_by,
+					method.sig.ident,
+					task_index,
+				);
+				let mut err = syn::Error::new(used_by.span(), &msg);
+				err.combine(syn::Error::new(method.sig.ident.span(), msg));
+				return Err(err);
+			}
+
+			let mut args = vec![];
+			for arg in method.sig.inputs.iter() {
+				let arg = if let syn::FnArg::Typed(arg) = arg {
+					arg
+				} else {
+					let msg = "Invalid pallet::tasks, a task can't have a receiver";
+					return Err(syn::Error::new(arg.span(), msg));
+				};
+
+				let arg_ident = if let syn::Pat::Ident(pat) = &*arg.pat {
+					pat.ident.clone()
+				} else {
+					let msg = "Invalid pallet::tasks, argument must be ident";
+					return Err(syn::Error::new(arg.pat.span(), msg));
+				};
+
+				args.push((arg_ident, arg.ty.clone()));
+			}
+
+			tasks.push(TaskDef {
+				name: method.sig.ident.clone(),
+				args,
+				list,
+				condition,
+				weight,
+				task_index,
+				docs: helper::get_doc_literals(&method.attrs),
+			});
+		}
+
+		Ok(Self {
+			index,
+			attr_span,
+			where_clause: item.generics.where_clause.clone(),
+			instances,
+			tasks,
+		})
+	}
+}
@@ -22,21 +22,22 @@ use quote::quote;
 use super::DeclStorageDefExt;
 
 pub fn impl_storage_info(def: &DeclStorageDefExt) -> TokenStream {
-	if !def.generate_storage_info {
-		return Default::default()
-	}
-
 	let scrate = &def.hidden_crate;
 
+	// Without `generate_storage_info`, storages only give partial information.
+	let (trait_, method) = if def.generate_storage_info {
+		(quote!(#scrate::traits::StorageInfoTrait), quote!(storage_info))
+	} else {
+		(quote!(#scrate::traits::PartialStorageInfoTrait), quote!(partial_storage_info))
+	};
+
 	let mut res_append_storage = TokenStream::new();
 
 	for line in def.storage_lines.iter() {
 		let storage_struct = &line.storage_struct;
 
 		res_append_storage.extend(quote!(
-			let mut storage_info = <
-				#storage_struct as #scrate::traits::StorageInfoTrait
-			>::storage_info();
+			let mut storage_info = <#storage_struct as #trait_>::#method();
 			res.append(&mut storage_info);
 		));
 	}
@@ -48,6 +49,7 @@ pub fn impl_storage_info(def: &DeclStorageDefExt) -> TokenStream {
 	quote!(
 		impl#module_impl #scrate::traits::StorageInfoTrait for #module_struct #where_clause {
 			fn storage_info() -> #scrate::sp_std::vec::Vec<#scrate::traits::StorageInfo> {
+				#[allow(unused_mut)]
 				let mut res = #scrate::sp_std::vec![];
 				#res_append_storage
 				res
@@ -399,7 +399,44 @@ pub fn decl_and_impl(def: &DeclStorageDefExt) -> TokenStream {
 				},
 			}
 		} else {
-			TokenStream::default()
+			// Without `generate_storage_info`, the value is not required to have a max encoded
+			// len, thus only the partial information is given.
+			let (prefix, max_values) = match &line.storage_type {
+				StorageLineTypeDef::Simple(_) => (
+					quote!(
+						<
+							#storage_struct as #scrate::#storage_generator_trait
+						>::storage_value_final_key()
+					),
+					quote!(Some(1)),
+				),
+				_ => (
+					quote!(
+						<
+							#storage_struct as #scrate::storage::StoragePrefixedMap<#value_type>
+						>::final_prefix()
+					),
+					max_values,
+				),
+			};
+
+			quote!(
+				impl<#impl_trait> #scrate::traits::PartialStorageInfoTrait for #storage_struct
+				#optional_storage_where_clause
+				{
+					fn partial_storage_info()
+						-> #scrate::sp_std::vec::Vec<#scrate::traits::StorageInfo>
+					{
+						#scrate::sp_std::vec![
+							#scrate::traits::StorageInfo {
+								prefix: #prefix,
+								max_values: #max_values,
+								max_size: None,
+							}
+						]
+					}
+				}
+			)
 		};
 
 		impls.extend(quote!(
@@ -0,0 +1,116 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementation of the `storage_alias` attribute macro.
+
+use frame_support_procedural_tools::generate_crate_access_2018;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+	GenericArgument, GenericParam, ItemType, PathArguments, Result, Type, TypePath,
+	parse_quote, spanned::Spanned,
+};
+
+/// The storage types which can be aliased.
+const STORAGE_TYPES: &[&str] = &["StorageValue", "StorageMap", "StorageDoubleMap", "StorageNMap"];
+
+pub fn storage_alias(attr: TokenStream, item: TokenStream) -> Result<TokenStream> {
+	if !attr.is_empty() {
+		return Err(syn::Error::new(attr.span(), "`storage_alias` does not take any arguments"));
+	}
+	let ItemType { attrs, vis, ident, generics, ty, .. } = syn::parse2(item)?;
+	let mut type_params = Vec::new();
+	for param in &generics.params {
+		match param {
+			GenericParam::Type(param) => type_params.push(param.ident.clone()),
+			_ => return Err(syn::Error::new(param.span(), "only type parameters are supported")),
+		}
+	}
+
+	let crate_ = generate_crate_access_2018("frame-support")?;
+	let mut storage = match *ty {
+		Type::Path(TypePath { qself: None, path }) => path,
+		ty => return Err(syn::Error::new(
+			ty.span(),
+			"expected a storage type, such as `StorageValue<Prefix, u32>`",
+		)),
+	};
+	let storage_span = storage.span();
+	let segment = storage.segments.last_mut().expect("paths have at least one segment; qed");
+	if !STORAGE_TYPES.contains(&segment.ident.to_string().as_str()) {
+		return Err(syn::Error::new(
+			segment.ident.span(),
+			"expected `StorageValue`, `StorageMap`, `StorageDoubleMap` or `StorageNMap`",
+		));
+	}
+	let args = match &mut segment.arguments {
+		PathArguments::AngleBracketed(args) => &mut args.args,
+		_ => return Err(syn::Error::new(
+			storage_span,
+			"expected the generic arguments of the storage",
+		)),
+	};
+	let prefix = match args.first() {
+		Some(GenericArgument::Type(prefix)) => prefix.clone(),
+		_ => return Err(syn::Error::new(
+			args.span(),
+			"expected the prefix of the pallet as first generic argument",
+		)),
+	};
+
+	// a plain identifier is the prefix itself, anything else is a pallet providing it.
+	let pallet_prefix = match &prefix {
+		Type::Path(TypePath { qself: None, path })
+			if path.get_ident().map_or(false, |prefix| !type_params.contains(prefix)) =>
+		{
+			let prefix = path.get_ident().expect("checked above; qed").to_string();
+			quote!(#prefix)
+		},
+		prefix => quote!(<#prefix as #crate_::traits::PalletInfoAccess>::name()),
+	};
+
+	let instance = format_ident!("{}Instance", ident);
+	let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+	args[0] = GenericArgument::Type(parse_quote!(#instance #ty_generics));
+	if storage.segments.len() == 1 {
+		storage = parse_quote!(#crate_::storage::types::#storage);
+	}
+
+	let fields = if type_params.is_empty() {
+		quote!()
+	} else {
+		quote!((#crate_::sp_std::marker::PhantomData<(#( #type_params, )*)>))
+	};
+	let storage_prefix = ident.to_string();
+	Ok(quote! {
+		#[doc(hidden)]
+		#vis struct #instance #generics #fields #where_clause;
+
+		impl #impl_generics #crate_::traits::StorageInstance for #instance #ty_generics
+			#where_clause
+		{
+			fn pallet_prefix() -> &'static str {
+				#pallet_prefix
+			}
+			const STORAGE_PREFIX: &'static str = #storage_prefix;
+		}
+
+		#( #attrs )*
+		#[allow(type_alias_bounds)]
+		#vis type #ident #generics #where_clause = #storage;
+	})
+}
@@ -27,15 +27,7 @@ pub fn transactional(_attr: TokenStream, input: TokenStream) -> Result<TokenStre
 	let output = quote! {
 		#(#attrs)*
 		#vis #sig {
-			use #crate_::storage::{with_transaction, TransactionOutcome};
-			with_transaction(|| {
-				let r = (|| { #block })();
-				if r.is_ok() {
-					TransactionOutcome::Commit(r)
-				} else {
-					TransactionOutcome::Rollback(r)
-				}
-			})
+			#crate_::storage::with_storage_layer(|| { #block })
 		}
 	};
 
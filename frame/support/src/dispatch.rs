@@ -1468,14 +1468,23 @@ macro_rules! decl_module {
 			}
 
 			#[cfg(feature = "try-runtime")]
-			fn pre_upgrade() -> Result<(), &'static str> {
-				Ok(())
+			fn pre_upgrade() -> Result<$crate::sp_std::vec::Vec<u8>, &'static str> {
+				Ok($crate::sp_std::vec::Vec::new())
 			}
 
 			#[cfg(feature = "try-runtime")]
-			fn post_upgrade() -> Result<(), &'static str> {
+			fn post_upgrade(_: $crate::sp_std::vec::Vec<u8>) -> Result<(), &'static str> {
 				Ok(())
 			}
+
+			#[cfg(feature = "try-runtime")]
+			fn upgrade_name() -> &'static str {
+				<<
+					$trait_instance
+					as
+					$system::Config
+				>::PalletInfo as $crate::traits::PalletInfo>::name::<Self>().unwrap_or("<unknown pallet name>")
+			}
 		}
 	};
 
@@ -1511,14 +1520,23 @@ macro_rules! decl_module {
 			}
 
 			#[cfg(feature = "try-runtime")]
-			fn pre_upgrade() -> Result<(), &'static str> {
-				Ok(())
+			fn pre_upgrade() -> Result<$crate::sp_std::vec::Vec<u8>, &'static str> {
+				Ok($crate::sp_std::vec::Vec::new())
 			}
 
 			#[cfg(feature = "try-runtime")]
-			fn post_upgrade() -> Result<(), &'static str> {
+			fn post_upgrade(_: $crate::sp_std::vec::Vec<u8>) -> Result<(), &'static str> {
 				Ok(())
 			}
+
+			#[cfg(feature = "try-runtime")]
+			fn upgrade_name() -> &'static str {
+				<<
+					$trait_instance
+					as
+					$system::Config
+				>::PalletInfo as $crate::traits::PalletInfo>::name::<Self>().unwrap_or("<unknown pallet name>")
+			}
 		}
 	};
 
@@ -1896,6 +1914,13 @@ macro_rules! decl_module {
 			$( $on_idle )*
 		}
 
+		// `on_poll` is only supported by the pallet macro.
+		impl<$trait_instance: $system::Config + $trait_name$(<I>, $instance: $instantiable)?>
+			$crate::traits::OnPoll<<$trait_instance as $system::Config>::BlockNumber>
+			for $mod_type<$trait_instance$(, $instance)?> where $( $other_where_bounds )*
+		{
+		}
+
 		$crate::decl_module! {
 			@impl_offchain
 			{ $system }
@@ -1918,6 +1943,18 @@ macro_rules! decl_module {
 			$( $integrity_test )*
 		}
 
+		#[cfg(feature = "try-runtime")]
+		impl<$trait_instance: $system::Config + $trait_name $(<I>, $instance: $instantiable)?>
+			$crate::traits::TryState<<$trait_instance as $system::Config>::BlockNumber>
+			for $mod_type<$trait_instance $(, $instance)?> where $( $other_where_bounds )*
+		{
+			fn try_state(
+				_: <$trait_instance as $system::Config>::BlockNumber,
+			) -> Result<(), &'static str> {
+				Ok(())
+			}
+		}
+
 		/// Can also be called using [`Call`].
 		///
 		/// [`Call`]: enum.Call.html
@@ -82,6 +82,7 @@ macro_rules! impl_outer_config {
 			#[derive($crate::serde::Serialize, $crate::serde::Deserialize, Default)]
 			#[serde(rename_all = "camelCase")]
 			#[serde(deny_unknown_fields)]
+			#[serde(default)]
 			#[serde(crate = "__genesis_config_serde_import__")]
 			pub struct $main {
 				$(
@@ -77,6 +77,8 @@ pub use self::storage::{
 	StorageValue, StorageMap, StorageDoubleMap, StorageNMap, StoragePrefixedMap,
 	IterableStorageMap, IterableStorageDoubleMap, IterableStorageNMap, migration,
 	bounded_vec::{self, BoundedVec},
+	bounded_btree_map::{self, BoundedBTreeMap},
+	bounded_btree_set::{self, BoundedBTreeSet},
 };
 pub use self::dispatch::{Parameter, Callable};
 pub use sp_runtime::{self, ConsensusEngineId, print, traits::Printable};
@@ -594,6 +596,9 @@ pub use frame_support_procedural::DebugNoBound;
 ///		c: T::C,
 /// }
 /// ```
+///
+/// For an enum, the default value is its first variant, whose fields are all defaulted; an enum
+/// without any variant is rejected.
 pub use frame_support_procedural::DefaultNoBound;
 
 /// Assert the annotated function is executed within a storage transaction.
@@ -642,6 +647,85 @@ pub use frame_support_procedural::require_transactional;
 /// ```
 pub use frame_support_procedural::crate_to_pallet_version;
 
+/// Declare a standalone [`StorageValue`](storage::types::StorageValue),
+/// [`StorageMap`](storage::types::StorageMap),
+/// [`StorageDoubleMap`](storage::types::StorageDoubleMap) or
+/// [`StorageNMap`](storage::types::StorageNMap), with an explicit pallet prefix.
+///
+/// Useful for migrations to read, write or remove the storage of an old layout without keeping
+/// its definition around in the pallet. The first generic argument of the storage is its pallet
+/// prefix: either an identifier, taken as the prefix itself, or a type implementing
+/// [`PalletInfoAccess`](traits::PalletInfoAccess), such as `Pallet<T>`, whose name is taken. The
+/// name of the type alias is the storage prefix.
+///
+/// # Example
+///
+/// ```
+/// # use frame_support::{codec, storage_alias, Blake2_128Concat, Twox64Concat};
+/// # use frame_support::pallet_prelude::*;
+/// // the value at `twox_128(b"OldPallet") ++ twox_128(b"Counter")`.
+/// #[storage_alias]
+/// type Counter = StorageValue<OldPallet, u32, ValueQuery>;
+///
+/// // a map of the pallet configured as `P` in the runtime.
+/// trait Config { type AccountId: codec::FullCodec; }
+/// #[storage_alias]
+/// type Balances<T: Config, P: frame_support::traits::PalletInfoAccess> =
+/// 	StorageMap<P, Blake2_128Concat, <T as Config>::AccountId, u64>;
+///
+/// #[storage_alias]
+/// pub type Approvals = StorageDoubleMap<OldPallet, Twox64Concat, u32, Twox64Concat, u32, bool>;
+/// # fn main() {}
+/// ```
+pub use frame_support_procedural::storage_alias;
+
+/// Export an implementation of a `DefaultConfig` trait, generated by
+/// `#[pallet::config(with_default)]`, so that [`derive_impl`] can use it.
+///
+/// The argument is the type the trait is implemented for. Associated types which are types of the
+/// runtime, such as `Call` or `Event`, are marked with `#[inject_runtime_type]`: [`derive_impl`]
+/// sets them to the runtime type of the same name, whatever their value here.
+///
+/// # Example
+///
+/// ```ignore
+/// pub mod config_preludes {
+/// 	use super::DefaultConfig;
+///
+/// 	pub struct TestDefaultConfig;
+///
+/// 	#[frame_support::register_default_impl(TestDefaultConfig)]
+/// 	impl DefaultConfig for TestDefaultConfig {
+/// 		type AccountId = u64;
+/// 		#[inject_runtime_type]
+/// 		type Event = ();
+/// 	}
+/// }
+/// ```
+pub use frame_support_procedural::register_default_impl;
+
+/// Complete an implementation of a trait with the associated types of a default implementation
+/// registered with [`register_default_impl`].
+///
+/// The argument is the path of the default implementation, optionally followed by `as` and the
+/// path of the trait it implements, which defaults to `DefaultConfig` next to the implemented
+/// trait, e.g. `frame_system::DefaultConfig` for `frame_system::Config`. Only the associated
+/// types which are not in the implementation are taken from the default one.
+///
+/// # Example
+///
+/// ```ignore
+/// #[frame_support::derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+/// impl frame_system::Config for Runtime {
+/// 	// overrides the default account id, everything else is inherited.
+/// 	type AccountId = u32;
+/// }
+/// ```
+pub use frame_support_procedural::derive_impl;
+
+#[doc(hidden)]
+pub use frame_support_procedural::__derive_impl_inner;
+
 /// Return Err of the expression: `return Err($expression);`.
 ///
 /// Used as `fail!(expression)`.
@@ -664,6 +748,28 @@ macro_rules! ensure {
 	}}
 }
 
+/// Report that a branch which should never be taken was taken.
+///
+/// The failure is logged and counted (see [`traits::defensive_failures`]) in production, and
+/// panics when `debug_assertions` are enabled, e.g. in tests.
+///
+/// Used as `defensive!()` or `defensive!(error_or_context)`, where the argument implements
+/// `Debug`.
+#[macro_export]
+macro_rules! defensive {
+	() => {{
+		$crate::traits::note_defensive_failure();
+		$crate::log::error!(target: $crate::LOG_TARGET, "Defensive failure");
+		debug_assert!(false, "Defensive failure");
+	}};
+	( $error:expr $(,)? ) => {{
+		let error = &$error;
+		$crate::traits::note_defensive_failure();
+		$crate::log::error!(target: $crate::LOG_TARGET, "Defensive failure: {:?}", error);
+		debug_assert!(false, "Defensive failure: {:?}", error);
+	}};
+}
+
 /// Evaluate an expression, assert it returns an expected `Err` value and that
 /// runtime storage has not been mutated (i.e. expression is a no-operation).
 ///
@@ -1245,9 +1351,12 @@ pub mod pallet_prelude {
 		weights::{DispatchClass, Pays, Weight},
 		storage::types::{
 			Key as NMapKey, StorageDoubleMap, StorageMap, StorageNMap, StorageValue, ValueQuery,
-			OptionQuery,
+			OptionQuery, CountedStorageMap,
+		},
+		storage::{
+			bounded_vec::BoundedVec, bounded_btree_map::BoundedBTreeMap,
+			bounded_btree_set::BoundedBTreeSet,
 		},
-		storage::bounded_vec::BoundedVec,
 	};
 	pub use codec::{Encode, Decode};
 	pub use crate::inherent::{InherentData, InherentIdentifier, ProvideInherent};
@@ -1288,6 +1397,19 @@ pub mod pallet_prelude {
 /// }
 /// ```
 ///
+/// # Dev mode: `#[pallet(dev_mode)]`
+///
+/// For prototyping and tests, the pallet can be declared with `#[frame_support::pallet(dev_mode)]`
+/// or `#[pallet(dev_mode)]`, which relaxes some requirements:
+/// * a dispatchable without `#[pallet::weight($expr)]` has a weight of 0,
+/// * the hashers of a storage can be `_`, e.g. `StorageMap<_, _, u32, u32>`, in which case they
+///   are `Blake2_128Concat`,
+/// * `#[pallet::generate_storage_info]` is ignored, so the storages don't need to implement
+///   `MaxEncodedLen`.
+///
+/// Dev mode pallets are not meant for production: their weights are meaningless, and their
+/// storage is not bounded.
+///
 /// # Config trait: `#[pallet::config]` mandatory
 ///
 /// The trait defining generics of the pallet.
@@ -1324,10 +1446,30 @@ pub mod pallet_prelude {
 /// pub trait Config: pallet_timestamp::Config {}
 /// ```
 ///
+/// To let implementations of the trait inherit default associated types, use
+/// `#[pallet::config(with_default)]`. Associated types which can't have a default are marked
+/// `#[pallet::no_default]`, and those whose bounds refer to items without default, e.g. to a
+/// supertrait, are marked `#[pallet::no_default_bounds]`:
+/// ```ignore
+/// #[pallet::config(with_default)]
+/// pub trait Config: frame_system::Config {
+///		#[pallet::no_default]
+///		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+///		#[pallet::no_default_bounds]
+///		type OnSomething: OnSomething<Self::AccountId>;
+///		type Limit: Get<u32>;
+/// }
+/// ```
+/// See [`register_default_impl`] and [`derive_impl`] to give and use the defaults.
+///
 /// ### Macro expansion:
 ///
 /// The macro expand pallet constant metadata with the information given by `#[pallet::constant]`.
 ///
+/// With `#[pallet::config(with_default)]`, the macro generates the trait `DefaultConfig`, which
+/// has the associated types of `Config` not marked `#[pallet::no_default]`, without bounds for
+/// those marked `#[pallet::no_default_bounds]`.
+///
 /// # Pallet struct placeholder: `#[pallet::pallet]` mandatory
 ///
 /// The placeholder struct, on which is implemented pallet informations.
@@ -1353,16 +1495,19 @@ pub mod pallet_prelude {
 /// `<Pallet as Store>::Foo`.
 ///
 /// To generate the full storage info (used for PoV calculation) use the attribute
-/// `#[pallet::set_storage_max_encoded_len]`, e.g.:
+/// `#[pallet::generate_storage_info]`, e.g.:
 /// ```ignore
 /// #[pallet::pallet]
-/// #[pallet::set_storage_max_encoded_len]
+/// #[pallet::generate_storage_info]
 /// pub struct Pallet<T>(_);
 /// ```
 ///
 /// This require all storage to implement the trait [`traits::StorageInfoTrait`], thus all keys
 /// and value types must bound [`traits::MaxEncodedLen`].
 ///
+/// Without this attribute, the storage info is still given but without the maximum size of the
+/// storages, see [`traits::PartialStorageInfoTrait`].
+///
 /// ### Macro expansion:
 ///
 /// The macro add this attribute to the struct definition:
@@ -1392,9 +1537,10 @@ pub mod pallet_prelude {
 /// If the attribute generate_store is set then the macro creates the trait `Store` and implements
 /// it on `Pallet`.
 ///
-/// If the attribute set_storage_max_encoded_len is set then the macro call
+/// If the attribute generate_storage_info is set then the macro call
 /// [`traits::StorageInfoTrait`] for each storage in the implementation of
-/// [`traits::StorageInfoTrait`] for the pallet.
+/// [`traits::StorageInfoTrait`] for the pallet, otherwise it calls
+/// [`traits::PartialStorageInfoTrait`] for each storage.
 ///
 /// # Hooks: `#[pallet::hooks]` optional
 ///
@@ -1419,14 +1565,14 @@ pub mod pallet_prelude {
 ///
 /// ### Macro expansion:
 ///
-/// The macro implements the traits `OnInitialize`, `OnIdle`, `OnFinalize`, `OnRuntimeUpgrade`,
-/// `OffchainWorker`, `IntegrityTest` using `Hooks` implementation.
+/// The macro implements the traits `OnInitialize`, `OnPoll`, `OnIdle`, `OnFinalize`,
+/// `OnRuntimeUpgrade`, `OffchainWorker`, `IntegrityTest` using `Hooks` implementation.
 ///
 /// NOTE: OnRuntimeUpgrade is implemented with `Hooks::on_runtime_upgrade` and some additional
 /// logic. E.g. logic to write pallet version into storage.
 ///
 /// NOTE: The macro also adds some tracing logic when implementing the above traits. The following
-///  hooks emit traces: `on_initialize`, `on_finalize` and `on_runtime_upgrade`.
+///  hooks emit traces: `on_initialize`, `on_poll`, `on_finalize` and `on_runtime_upgrade`.
 ///
 /// # Call: `#[pallet::call]` optional
 ///
@@ -1457,6 +1603,13 @@ pub mod pallet_prelude {
 /// using `#[pallet::compact]`, function must return `DispatchResultWithPostInfo` or
 /// `DispatchResult`.
 ///
+/// The index of a dispatchable in the `Call` enum, which is part of the encoding of the call, can
+/// be fixed with `#[pallet::call_index($idx)]`, `$idx` being a `u8` literal. A dispatchable
+/// without it takes the index following the one of the previous dispatchable, or 0 for the
+/// first one. Indices must not conflict, and must cover `0..` the number of dispatchables
+/// without gaps, as the metadata describes the dispatchables by their position. With explicit
+/// indices, dispatchables can be reordered without changing the encoding of the calls.
+///
 /// All arguments must implement `Debug`, `PartialEq`, `Eq`, `Decode`, `Encode`, `Clone`. For ease
 /// of use, bound the trait `Member` available in frame_support::pallet_prelude.
 ///
@@ -1467,6 +1620,10 @@ pub mod pallet_prelude {
 /// impl<T: Config> Pallet<T> {}
 /// ```
 ///
+/// With `#[pallet::call(transactional)]`, every dispatchable is executed in a new storage layer
+/// (see [`storage::with_storage_layer`]): its changes to storage are discarded if it returns an
+/// error. A single dispatchable can be made transactional with `#[transactional]` instead.
+///
 /// **WARNING**: modifying dispatchables, changing their order, removing some must be done with
 /// care. Indeed this will change the outer runtime call type (which is an enum with one variant
 /// per pallet), this outer runtime call can be stored on-chain (e.g. in pallet-scheduler).
@@ -1481,6 +1638,49 @@ pub mod pallet_prelude {
 /// The macro implement on `Pallet`, the `Callable` trait and a function `call_functions` which
 /// returns the dispatchable metadatas.
 ///
+/// # Tasks: `#[pallet::tasks]` optional
+///
+/// Allow to define units of work, e.g. cleaning up some storage, which anyone can submit as long
+/// as they are valid.
+///
+/// Item must be defined as:
+/// ```ignore
+/// #[pallet::tasks]
+/// impl<T: Config> Pallet<T> {
+/// 	/// $some_doc
+/// 	#[pallet::task_list($ExpressionResultingInIntoIteratorOfArgs)]
+/// 	#[pallet::task_condition(|$some_arg: &$some_type, ...| $ExpressionResultingInBool)]
+/// 	#[pallet::task_weight($ExpressionResultingInWeight)]
+/// 	$vis fn $fn_name($some_arg: $some_type, ...) -> DispatchResult {
+/// 		...
+/// 	}
+/// 	...
+/// }
+/// ```
+/// I.e. a regular type implementation, with generic `T: Config`, on type `Pallet<T>`, with
+/// optional where clause, whose functions have no origin.
+///
+/// `task_list` gives the arguments of the tasks which may be valid: the argument itself for a
+/// function with a single argument, or the tuple of the arguments otherwise. `task_condition` is
+/// a closure called with references to the arguments, telling whether the task is valid. The
+/// weight of `task_weight` is computed with the arguments in scope by reference. The index of a
+/// task in the `Task` enum can be fixed with `#[pallet::task_index($idx)]`, like with
+/// `#[pallet::call_index($idx)]`.
+///
+/// ### Macro expansion
+///
+/// The macro create an enum `Task` with one variant per function, implementing
+/// [`traits::Task`]. The functions are kept on `Pallet`.
+///
+/// The macro also adds a dispatchable `do_task(origin, task: Task<T>)` after the other ones, which
+/// runs the task if it is valid, and fails with `frame_system::Error::InvalidTask` otherwise.
+/// Its weight is the one of the task. The tasks which are valid are listed lazily by
+/// [`traits::Task::valid_tasks`]; submitting them, in signed transactions, is left to the caller.
+///
+/// Submitting tasks from an offchain worker is not supported: `do_task` requires a signed origin,
+/// and no `ValidateUnsigned` is generated for it. An offchain worker can still list the valid
+/// tasks and submit them signed, e.g. through `frame_system::offchain::Signer`.
+///
 /// # Extra constants: `#[pallet::extra_constants]` optional
 ///
 /// Allow to define some extra constants to put into constant metadata.
@@ -1575,6 +1775,11 @@ pub mod pallet_prelude {
 /// The attribute `#[pallet::generate_deposit($visibility fn deposit_event)]` generate a helper
 /// function on `Pallet` to deposit event.
 ///
+/// A field can be marked with `#[pallet::indexed]`, e.g. `Transfer(#[pallet::indexed]
+/// T::AccountId, Balance)`: the event is then deposited under a topic, the hash of the field,
+/// in `frame_system::EventTopics`. This allows light clients and indexers to find the events
+/// related to e.g. an account without decoding every event.
+///
 /// NOTE: For instantiable pallet, event must be generic over T and I.
 ///
 /// ### Macro expansion:
@@ -1589,9 +1794,11 @@ pub mod pallet_prelude {
 ///
 /// Macro implements `From<Event<..>>` for ().
 ///
-/// Macro implements metadata function on `Event` returning the `EventMetadata`.
+/// Macro implements metadata function on `Event` returning the `EventMetadata`, and `topics`
+/// function returning the hashes of the indexed fields.
 ///
-/// If `#[pallet::generate_deposit]` then macro implement `fn deposit_event` on `Pallet`.
+/// If `#[pallet::generate_deposit]` then macro implement `fn deposit_event` on `Pallet`, which
+/// deposits the event with its topics.
 ///
 /// # Storage: `#[pallet::storage]` optional
 ///
@@ -1620,6 +1827,8 @@ pub mod pallet_prelude {
 /// * [`pallet_prelude::StorageValue`] expect `Value` and optionally `QueryKind` and `OnEmpty`,
 /// * [`pallet_prelude::StorageMap`] expect `Hasher`, `Key`, `Value` and optionally `QueryKind` and
 ///   `OnEmpty`,
+/// * [`pallet_prelude::CountedStorageMap`] expect `Hasher`, `Key`, `Value` and optionally
+///   `QueryKind` and `OnEmpty`,
 /// * [`pallet_prelude::StorageDoubleMap`] expect `Hasher1`, `Key1`, `Hasher2`, `Key2`, `Value` and
 ///   optionally `QueryKind` and `OnEmpty`.
 ///
@@ -1631,6 +1840,11 @@ pub mod pallet_prelude {
 /// E.g. if runtime names the pallet "MyExample" then the storage `type Foo<T> = ...` use the
 /// prefix: `Twox128(b"MyExample") ++ Twox128(b"Foo")`.
 ///
+/// For a `CountedStorageMap`, the counter of its values is stored at the prefix of the storage
+/// named `CounterFor` followed by the name of the storage type, e.g.
+/// `Twox128(b"MyExample") ++ Twox128(b"CounterForFoo")`. The counter appears in the metadata as a
+/// `StorageValue` of that name.
+///
 /// The optional attribute `#[pallet::getter(fn $my_getter_fn_name)]` allow to define a
 /// getter function on `Pallet`.
 ///
@@ -1734,7 +1948,12 @@ pub mod pallet_prelude {
 /// * `#[serde(rename_all = "camelCase")]`
 /// * `#[serde(deny_unknown_fields)]`
 /// * `#[serde(bound(serialize = ""))]`
-/// * `#[serde(bound(deserialize = ""))]`
+/// * `#[serde(bound(deserialize = ""))]`, or for a struct with named fields:
+///   `#[serde(default)]` and `#[serde(bound(deserialize = "GenesisConfig<..>: Default"))]`
+///
+/// Thus the fields missing from the genesis config of a chain spec take the value they have in
+/// `GenesisConfig::default()`. Serde attributes can be used on the fields, e.g.
+/// `#[serde(default = "path")]`, and their documentation is kept.
 ///
 /// # Genesis build: `#[pallet::genesis_build]` optional
 ///
@@ -1825,6 +2044,31 @@ pub mod pallet_prelude {
 ///
 /// NOTE: for instantiable pallet, origin must be generic over T and I.
 ///
+/// # Composite enum: `#[pallet::composite_enum]` optional
+///
+/// Allow to define the reasons for which the pallet holds, freezes or slashes funds, aggregated by
+/// `construct_runtime` into the runtime enum of the same name, similar to the events.
+///
+/// Item must be a public enum named `HoldReason`, `FreezeReason` or `SlashReason`, without
+/// generics, and with unit variants only. A pallet can declare each of them at most once.
+///
+/// E.g.:
+/// ```ignore
+/// #[pallet::composite_enum]
+/// pub enum HoldReason {
+/// 	Staking,
+/// }
+/// ```
+///
+/// ### Macro expansion
+///
+/// The macro derives `Copy`, `Clone`, `Eq`, `PartialEq`, `Ord`, `PartialOrd`, `Encode`, `Decode`,
+/// `MaxEncodedLen` and `RuntimeDebug` for the enum.
+///
+/// `construct_runtime` declares the runtime enum if any pallet has the part of that name, e.g.
+/// `HoldReason`, with a variant per such pallet named after it and encoded at its index, and
+/// implements `From` the enum of each pallet used without several instances.
+///
 /// # General notes on instantiable pallet
 ///
 /// An instantiable pallet is one where Config is generic, i.e. `Config<I>`. This allow runtime to
@@ -216,6 +216,18 @@ impl<K1, K2, V, G> storage::StorageDoubleMap<K1, K2, V> for G where
 		unhashed::kill_prefix(Self::storage_double_map_final_key1(k1).as_ref())
 	}
 
+	fn clear_prefix<KArg1>(
+		k1: KArg1,
+		limit: u32,
+		maybe_cursor: Option<&[u8]>,
+	) -> storage::KillStorageResult where KArg1: EncodeLike<K1> {
+		unhashed::clear_prefix(
+			Self::storage_double_map_final_key1(k1).as_ref(),
+			Some(limit),
+			maybe_cursor,
+		)
+	}
+
 	fn iter_prefix_values<KArg1>(k1: KArg1) -> storage::PrefixIterator<V> where
 		KArg1: ?Sized + EncodeLike<K1>
 	{
@@ -224,6 +236,7 @@ impl<K1, K2, V, G> storage::StorageDoubleMap<K1, K2, V> for G where
 			prefix: prefix.clone(),
 			previous_key: prefix,
 			drain: false,
+			phantom: Default::default(),
 			closure: |_raw_key, mut raw_value| V::decode(&mut raw_value),
 		}
 	}
@@ -348,6 +361,7 @@ impl<
 			prefix: prefix.clone(),
 			previous_key: prefix,
 			drain: false,
+			phantom: Default::default(),
 			closure: |raw_key_without_prefix, mut raw_value| {
 				let mut key_material = G::Hasher2::reverse(raw_key_without_prefix);
 				Ok((K2::decode(&mut key_material)?, V::decode(&mut raw_value)?))
@@ -367,6 +381,7 @@ impl<
 			prefix: prefix.clone(),
 			previous_key: prefix,
 			drain: false,
+			phantom: Default::default(),
 			closure: |raw_key_without_prefix, mut raw_value| {
 				let mut k1_k2_material = G::Hasher1::reverse(raw_key_without_prefix);
 				let k1 = K1::decode(&mut k1_k2_material)?;
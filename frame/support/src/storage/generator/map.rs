@@ -148,6 +148,7 @@ impl<
 			prefix: prefix.clone(),
 			previous_key: prefix,
 			drain: false,
+			phantom: Default::default(),
 			closure: |raw_key_without_prefix, mut raw_value| {
 				let mut key_material = G::Hasher::reverse(raw_key_without_prefix);
 				Ok((K::decode(&mut key_material)?, V::decode(&mut raw_value)?))
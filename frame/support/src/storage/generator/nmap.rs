@@ -37,7 +37,7 @@ use crate::{
 			EncodeLikeTuple, HasKeyPrefix, HasReversibleKeyPrefix, KeyGenerator,
 			ReversibleKeyGenerator, TupleToEncodedIter,
 		},
-		unhashed, PrefixIterator, StorageAppend,
+		unhashed, KillStorageResult, PrefixIterator, StorageAppend,
 	},
 	Never,
 };
@@ -203,6 +203,21 @@ where
 		unhashed::kill_prefix(&Self::storage_n_map_partial_key(partial_key));
 	}
 
+	fn clear_prefix<KP>(
+		partial_key: KP,
+		limit: u32,
+		maybe_cursor: Option<&[u8]>,
+	) -> KillStorageResult
+	where
+		K: HasKeyPrefix<KP>,
+	{
+		unhashed::clear_prefix(
+			&Self::storage_n_map_partial_key(partial_key),
+			Some(limit),
+			maybe_cursor,
+		)
+	}
+
 	fn iter_prefix_values<KP>(partial_key: KP) -> PrefixIterator<V>
 	where
 		K: HasKeyPrefix<KP>,
@@ -212,6 +227,7 @@ where
 			prefix: prefix.clone(),
 			previous_key: prefix,
 			drain: false,
+			phantom: Default::default(),
 			closure: |_raw_key, mut raw_value| V::decode(&mut raw_value),
 		}
 	}
@@ -321,6 +337,7 @@ impl<K: ReversibleKeyGenerator, V: FullCodec, G: StorageNMap<K, V>>
 			prefix: prefix.clone(),
 			previous_key: prefix,
 			drain: false,
+			phantom: Default::default(),
 			closure: |raw_key_without_prefix, mut raw_value| {
 				let partial_key = K::decode_partial_key(raw_key_without_prefix)?;
 				Ok((partial_key, V::decode(&mut raw_value)?))
@@ -343,6 +360,7 @@ impl<K: ReversibleKeyGenerator, V: FullCodec, G: StorageNMap<K, V>>
 			prefix: prefix.clone(),
 			previous_key: prefix,
 			drain: false,
+			phantom: Default::default(),
 			closure: |raw_key_without_prefix, mut raw_value| {
 				let (final_key, _) = K::decode_final_key(raw_key_without_prefix)?;
 				Ok((final_key, V::decode(&mut raw_value)?))
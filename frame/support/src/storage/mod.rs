@@ -29,7 +29,9 @@ use crate::{
 };
 use sp_runtime::generic::{Digest, DigestItem};
 pub use sp_runtime::TransactionOutcome;
+pub use sp_io::KillStorageResult;
 pub use types::Key;
+pub use transactional::with_storage_layer;
 
 pub mod unhashed;
 pub mod hashed;
@@ -42,48 +44,7 @@ pub mod child;
 pub mod generator;
 pub mod migration;
 pub mod types;
-
-#[cfg(all(feature = "std", any(test, debug_assertions)))]
-mod debug_helper {
-	use std::cell::RefCell;
-
-	thread_local! {
-		static TRANSACTION_LEVEL: RefCell<u32> = RefCell::new(0);
-	}
-
-	pub fn require_transaction() {
-		let level = TRANSACTION_LEVEL.with(|v| *v.borrow());
-		if level == 0 {
-			panic!("Require transaction not called within with_transaction");
-		}
-	}
-
-	pub struct TransactionLevelGuard;
-
-	impl Drop for TransactionLevelGuard {
-		fn drop(&mut self) {
-			TRANSACTION_LEVEL.with(|v| *v.borrow_mut() -= 1);
-		}
-	}
-
-	/// Increments the transaction level.
-	///
-	/// Returns a guard that when dropped decrements the transaction level automatically.
-	pub fn inc_transaction_level() -> TransactionLevelGuard {
-		TRANSACTION_LEVEL.with(|v| {
-			let mut val = v.borrow_mut();
-			*val += 1;
-			if *val > 10 {
-				log::warn!(
-					"Detected with_transaction with nest level {}. Nested usage of with_transaction is not recommended.",
-					*val
-				);
-			}
-		});
-
-		TransactionLevelGuard
-	}
-}
+pub mod transactional;
 
 /// Assert this method is called within a storage transaction.
 /// This will **panic** if is not called within a storage transaction.
@@ -91,7 +52,10 @@ mod debug_helper {
 /// This assertion is enabled for native execution and when `debug_assertions` are enabled.
 pub fn require_transaction() {
 	#[cfg(all(feature = "std", any(test, debug_assertions)))]
-	debug_helper::require_transaction();
+	assert!(
+		transactional::is_transactional(),
+		"Require transaction not called within with_transaction",
+	);
 }
 
 /// Execute the supplied function in a new storage transaction.
@@ -99,22 +63,10 @@ pub fn require_transaction() {
 /// All changes to storage performed by the supplied function are discarded if the returned
 /// outcome is `TransactionOutcome::Rollback`.
 ///
-/// Transactions can be nested to any depth. Commits happen to the parent transaction.
+/// Transactions can be nested to any depth. Commits happen to the parent transaction. To bound
+/// the depth of the nesting, use [`with_storage_layer`] instead.
 pub fn with_transaction<R>(f: impl FnOnce() -> TransactionOutcome<R>) -> R {
-	use sp_io::storage::{
-		start_transaction, commit_transaction, rollback_transaction,
-	};
-	use TransactionOutcome::*;
-
-	start_transaction();
-
-	#[cfg(all(feature = "std", any(test, debug_assertions)))]
-	let _guard = debug_helper::inc_transaction_level();
-
-	match f() {
-		Commit(res) => { commit_transaction(); res },
-		Rollback(res) => { rollback_transaction(); res },
-	}
+	transactional::with_unbounded_transaction(f)
 }
 
 /// A trait for working with macro-generated storage values under the substrate storage API.
@@ -466,6 +418,18 @@ pub trait StorageDoubleMap<K1: FullEncode, K2: FullEncode, V: FullCodec> {
 	/// Remove all values under the first key.
 	fn remove_prefix<KArg1>(k1: KArg1) where KArg1: ?Sized + EncodeLike<K1>;
 
+	/// Remove up to `limit` values under the first key, starting at `maybe_cursor`.
+	///
+	/// The cursor returned in [`KillStorageResult::SomeRemaining`] can be passed to a subsequent
+	/// call to continue the removal.
+	fn clear_prefix<KArg1>(
+		k1: KArg1,
+		limit: u32,
+		maybe_cursor: Option<&[u8]>,
+	) -> KillStorageResult
+	where
+		KArg1: ?Sized + EncodeLike<K1>;
+
 	/// Iterate over values that share the first key.
 	fn iter_prefix_values<KArg1>(k1: KArg1) -> PrefixIterator<V>
 		where KArg1: ?Sized + EncodeLike<K1>;
@@ -591,6 +555,18 @@ pub trait StorageNMap<K: KeyGenerator, V: FullCodec> {
 	/// Remove all values under the partial prefix key.
 	fn remove_prefix<KP>(partial_key: KP) where K: HasKeyPrefix<KP>;
 
+	/// Remove up to `limit` values under the partial prefix key, starting at `maybe_cursor`.
+	///
+	/// The cursor returned in [`KillStorageResult::SomeRemaining`] can be passed to a subsequent
+	/// call to continue the removal.
+	fn clear_prefix<KP>(
+		partial_key: KP,
+		limit: u32,
+		maybe_cursor: Option<&[u8]>,
+	) -> KillStorageResult
+	where
+		K: HasKeyPrefix<KP>;
+
 	/// Iterate over values that share the partial prefix key.
 	fn iter_prefix_values<KP>(partial_key: KP) -> PrefixIterator<V> where K: HasKeyPrefix<KP>;
 
@@ -666,10 +642,23 @@ pub trait StorageNMap<K: KeyGenerator, V: FullCodec> {
 		KArg: EncodeLikeTuple<K::KArg> + TupleToEncodedIter;
 }
 
+/// Logic run by a draining [`PrefixIterator`] for each key it removes.
+pub trait PrefixIteratorOnRemoval {
+	/// Called with the raw key and the raw value of each removed key.
+	fn on_removal(key: &[u8], value: &[u8]);
+}
+
+/// No logic on removal.
+impl PrefixIteratorOnRemoval for () {
+	fn on_removal(_key: &[u8], _value: &[u8]) {}
+}
+
 /// Iterate over a prefix and decode raw_key and raw_value into `T`.
 ///
 /// If any decoding fails it skips it and continues to the next key.
-pub struct PrefixIterator<T> {
+///
+/// If draining, then the hook `OnRemoval::on_removal` is called after each removal.
+pub struct PrefixIterator<T, OnRemoval = ()> {
 	prefix: Vec<u8>,
 	previous_key: Vec<u8>,
 	/// If true then value are removed while iterating
@@ -677,17 +666,29 @@ pub struct PrefixIterator<T> {
 	/// Function that take `(raw_key_without_prefix, raw_value)` and decode `T`.
 	/// `raw_key_without_prefix` is the raw storage key without the prefix iterated on.
 	closure: fn(&[u8], &[u8]) -> Result<T, codec::Error>,
+	phantom: core::marker::PhantomData<OnRemoval>,
 }
 
-impl<T> PrefixIterator<T> {
+impl<T, OnRemoval> PrefixIterator<T, OnRemoval> {
 	/// Mutate this iterator into a draining iterator; items iterated are removed from storage.
 	pub fn drain(mut self) -> Self {
 		self.drain = true;
 		self
 	}
+
+	/// Convert into the same iterator, running the logic of `OnRemoval2` on removal instead.
+	pub fn convert_on_removal<OnRemoval2>(self) -> PrefixIterator<T, OnRemoval2> {
+		PrefixIterator {
+			prefix: self.prefix,
+			previous_key: self.previous_key,
+			drain: self.drain,
+			closure: self.closure,
+			phantom: Default::default(),
+		}
+	}
 }
 
-impl<T> Iterator for PrefixIterator<T> {
+impl<T, OnRemoval: PrefixIteratorOnRemoval> Iterator for PrefixIterator<T, OnRemoval> {
 	type Item = T;
 
 	fn next(&mut self) -> Option<Self::Item> {
@@ -708,7 +709,8 @@ impl<T> Iterator for PrefixIterator<T> {
 						}
 					};
 					if self.drain {
-						unhashed::kill(&self.previous_key)
+						unhashed::kill(&self.previous_key);
+						OnRemoval::on_removal(&self.previous_key, &raw_value);
 					}
 					let raw_key_without_prefix = &self.previous_key[self.prefix.len()..];
 					let item = match (self.closure)(raw_key_without_prefix, &raw_value[..]) {
@@ -881,7 +883,15 @@ pub trait StoragePrefixedMap<Value: FullCodec> {
 
 	/// Remove all value of the storage.
 	fn remove_all() {
-		sp_io::storage::clear_prefix(&Self::final_prefix())
+		unhashed::kill_prefix(&Self::final_prefix())
+	}
+
+	/// Remove up to `limit` values of the storage, starting at `maybe_cursor`.
+	///
+	/// The cursor returned in [`KillStorageResult::SomeRemaining`] can be passed to a subsequent
+	/// call to continue the removal, e.g. in a later block.
+	fn clear(limit: u32, maybe_cursor: Option<&[u8]>) -> KillStorageResult {
+		unhashed::clear_prefix(&Self::final_prefix(), Some(limit), maybe_cursor)
 	}
 
 	/// Iter over all value of the storage.
@@ -893,6 +903,7 @@ pub trait StoragePrefixedMap<Value: FullCodec> {
 			prefix: prefix.to_vec(),
 			previous_key: prefix.to_vec(),
 			drain: false,
+			phantom: Default::default(),
 			closure: |_raw_key, mut raw_value| Value::decode(&mut raw_value),
 		}
 	}
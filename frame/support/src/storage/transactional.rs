@@ -0,0 +1,164 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Nested storage layers, each of them a storage transaction which is committed or rolled back
+//! as a whole.
+//!
+//! The number of nested layers is tracked in storage, under a key which is removed again once the
+//! outermost layer is done, so that it never ends up in the state.
+
+use sp_io::storage::{start_transaction, commit_transaction, rollback_transaction};
+use sp_runtime::{DispatchError, TransactionOutcome};
+use super::unhashed;
+
+/// The type used to count the nested storage layers.
+pub type Layer = u32;
+
+/// The maximum number of nested storage layers.
+///
+/// [`with_storage_layer`] refuses to spawn a layer beyond it.
+pub const TRANSACTIONAL_LIMIT: Layer = 255;
+
+/// The error returned by [`with_storage_layer`] when [`TRANSACTIONAL_LIMIT`] is reached.
+pub const LIMIT_REACHED: DispatchError = DispatchError::Other("Transactional limit reached");
+
+/// The key under which the number of nested storage layers is stored.
+const TRANSACTION_LEVEL_KEY: &[u8] = b":transaction_level:";
+
+/// The number of storage layers the caller is nested in.
+pub fn get_transaction_level() -> Layer {
+	unhashed::get_or_default::<Layer>(TRANSACTION_LEVEL_KEY)
+}
+
+/// Whether the caller is nested in at least one storage layer.
+pub fn is_transactional() -> bool {
+	get_transaction_level() > 0
+}
+
+fn set_transaction_level(level: Layer) {
+	if level == 0 {
+		unhashed::kill(TRANSACTION_LEVEL_KEY);
+	} else {
+		unhashed::put(TRANSACTION_LEVEL_KEY, &level);
+	}
+}
+
+/// Decrements the transaction level when dropped.
+struct StorageLayerGuard;
+
+impl Drop for StorageLayerGuard {
+	fn drop(&mut self) {
+		set_transaction_level(get_transaction_level().saturating_sub(1))
+	}
+}
+
+/// Increments the transaction level, unless it is at `limit` already.
+///
+/// Returns a guard that decrements the transaction level again once dropped.
+fn inc_transaction_level(limit: Layer) -> Option<StorageLayerGuard> {
+	let level = get_transaction_level();
+	if level >= limit {
+		return None;
+	}
+	set_transaction_level(level + 1);
+	Some(StorageLayerGuard)
+}
+
+/// Execute `f` in a new storage transaction, tracked as a storage layer.
+///
+/// Unlike [`with_storage_layer`], it is not bounded by [`TRANSACTIONAL_LIMIT`].
+pub(crate) fn with_unbounded_transaction<R>(f: impl FnOnce() -> TransactionOutcome<R>) -> R {
+	// the guard is dropped after the transaction is done, so the level is not rolled back.
+	let _guard = inc_transaction_level(Layer::MAX);
+	start_transaction();
+	match f() {
+		TransactionOutcome::Commit(res) => { commit_transaction(); res },
+		TransactionOutcome::Rollback(res) => { rollback_transaction(); res },
+	}
+}
+
+/// Execute `f` in a new storage layer.
+///
+/// All changes to storage performed by `f` are committed if it returns `Ok`, and discarded if it
+/// returns `Err`.
+///
+/// Returns [`LIMIT_REACHED`] without calling `f` if the caller is nested in
+/// [`TRANSACTIONAL_LIMIT`] storage layers already.
+pub fn with_storage_layer<R, E>(f: impl FnOnce() -> Result<R, E>) -> Result<R, E>
+where
+	E: From<DispatchError>,
+{
+	let _guard = inc_transaction_level(TRANSACTIONAL_LIMIT).ok_or(LIMIT_REACHED)?;
+	start_transaction();
+	let res = f();
+	if res.is_ok() {
+		commit_transaction();
+	} else {
+		rollback_transaction();
+	}
+	res
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_io::TestExternalities;
+
+	#[test]
+	fn storage_layers_are_counted() {
+		TestExternalities::default().execute_with(|| {
+			assert!(!is_transactional());
+			let res = with_storage_layer(|| {
+				assert_eq!(get_transaction_level(), 1);
+				with_unbounded_transaction(|| {
+					assert_eq!(get_transaction_level(), 2);
+					TransactionOutcome::Rollback(())
+				});
+				assert_eq!(get_transaction_level(), 1);
+				Err::<(), DispatchError>("nah".into())
+			});
+			assert_eq!(res, Err("nah".into()));
+			assert_eq!(get_transaction_level(), 0);
+			// the level is not left in storage.
+			assert_eq!(unhashed::get_raw(TRANSACTION_LEVEL_KEY), None);
+		});
+	}
+
+	#[test]
+	fn storage_layers_are_bounded() {
+		// nests layers until the limit is reached, which fails all of them.
+		fn nest(depth: Layer) -> Result<(), DispatchError> {
+			with_storage_layer(|| {
+				unhashed::put(&depth.to_le_bytes(), &depth);
+				nest(depth + 1)
+			})
+		}
+
+		TestExternalities::default().execute_with(|| {
+			assert_eq!(nest(1), Err(LIMIT_REACHED));
+			// all layers are rolled back.
+			assert_eq!(unhashed::get::<Layer>(&1u32.to_le_bytes()), None);
+			assert_eq!(get_transaction_level(), 0);
+
+			with_storage_layer(|| {
+				unhashed::put(&0u32.to_le_bytes(), &0u32);
+				Ok::<(), DispatchError>(())
+			}).unwrap();
+			assert_eq!(unhashed::get::<Layer>(&0u32.to_le_bytes()), Some(0));
+		});
+	}
+}
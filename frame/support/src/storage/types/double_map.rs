@@ -209,6 +209,25 @@ where
 		<Self as crate::storage::StorageDoubleMap<Key1, Key2, Value>>::remove_prefix(k1)
 	}
 
+	/// Remove up to `limit` values under the first key, starting at `maybe_cursor`.
+	///
+	/// The cursor returned in [`crate::storage::KillStorageResult::SomeRemaining`] can be passed
+	/// to a subsequent call to continue the removal, e.g. in a later block.
+	pub fn clear_prefix<KArg1>(
+		k1: KArg1,
+		limit: u32,
+		maybe_cursor: Option<&[u8]>,
+	) -> crate::storage::KillStorageResult
+	where
+		KArg1: ?Sized + EncodeLike<Key1>,
+	{
+		<Self as crate::storage::StorageDoubleMap<Key1, Key2, Value>>::clear_prefix(
+			k1,
+			limit,
+			maybe_cursor,
+		)
+	}
+
 	/// Iterate over values that share the first key.
 	pub fn iter_prefix_values<KArg1>(k1: KArg1) -> crate::storage::PrefixIterator<Value>
 	where KArg1: ?Sized + EncodeLike<Key1>
@@ -320,6 +339,14 @@ where
 		<Self as crate::storage::StoragePrefixedMap<Value>>::remove_all()
 	}
 
+	/// Remove up to `limit` values of the storage, starting at `maybe_cursor`.
+	///
+	/// The cursor returned in [`crate::storage::KillStorageResult::SomeRemaining`] can be passed
+	/// to a subsequent call to continue the removal, e.g. in a later block.
+	pub fn clear(limit: u32, maybe_cursor: Option<&[u8]>) -> crate::storage::KillStorageResult {
+		<Self as crate::storage::StoragePrefixedMap<Value>>::clear(limit, maybe_cursor)
+	}
+
 	/// Iter over all value of the storage.
 	///
 	/// NOTE: If a value failed to decode becaues storage is corrupted then it is skipped.
@@ -482,12 +509,38 @@ where
 	}
 }
 
+/// It doesn't require to implement `MaxEncodedLen` and give no information for `max_size`.
+impl<Prefix, Hasher1, Hasher2, Key1, Key2, Value, QueryKind, OnEmpty, MaxValues>
+	crate::traits::PartialStorageInfoTrait for
+	StorageDoubleMap<Prefix, Hasher1, Key1, Hasher2, Key2, Value, QueryKind, OnEmpty, MaxValues>
+where
+	Prefix: StorageInstance,
+	Hasher1: crate::hash::StorageHasher,
+	Hasher2: crate::hash::StorageHasher,
+	Key1: FullCodec,
+	Key2: FullCodec,
+	Value: FullCodec,
+	QueryKind: QueryKindTrait<Value, OnEmpty>,
+	OnEmpty: Get<QueryKind::Query> + 'static,
+	MaxValues: Get<Option<u32>>,
+{
+	fn partial_storage_info() -> Vec<StorageInfo> {
+		vec![
+			StorageInfo {
+				prefix: Self::final_prefix(),
+				max_values: MaxValues::get(),
+				max_size: None,
+			}
+		]
+	}
+}
+
 #[cfg(test)]
 mod test {
 	use super::*;
 	use sp_io::{TestExternalities, hashing::twox_128};
 	use crate::hash::*;
-	use crate::storage::types::ValueQuery;
+	use crate::storage::{KillStorageResult, types::ValueQuery};
 	use frame_metadata::StorageEntryModifier;
 
 	struct Prefix;
@@ -678,4 +731,35 @@ mod test {
 			assert_eq!(A::drain_prefix(4).collect::<Vec<_>>(), vec![]);
 		})
 	}
+
+	#[test]
+	fn clear_prefix_works() {
+		type A = StorageDoubleMap<
+			Prefix, Blake2_128Concat, u16, Twox64Concat, u8, u32, OptionQuery
+		>;
+
+		let mut ext = TestExternalities::default();
+		ext.execute_with(|| {
+			for k2 in 0..3 {
+				A::insert(5, k2, 1);
+			}
+			A::insert(6, 0, 1);
+		});
+		// only the keys of the backend count towards the limit.
+		ext.commit_all().unwrap();
+
+		ext.execute_with(|| {
+			let cursor = match A::clear_prefix(5, 2, None) {
+				KillStorageResult::SomeRemaining { removed: 2, cursor } => cursor,
+				_ => panic!("2 of the 3 values are removed"),
+			};
+			assert_eq!(A::iter_prefix(5).count(), 1);
+			assert!(matches!(
+				A::clear_prefix(5, 2, Some(&cursor)),
+				KillStorageResult::AllRemoved(1),
+			));
+			assert_eq!(A::iter_prefix(5).count(), 0);
+			assert_eq!(A::iter_prefix(6).count(), 1);
+		})
+	}
 }
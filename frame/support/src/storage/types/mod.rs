@@ -21,12 +21,16 @@
 use codec::FullCodec;
 use frame_metadata::{DefaultByte, StorageEntryModifier};
 
+mod counted_map;
 mod double_map;
 mod key;
 mod map;
 mod nmap;
 mod value;
 
+pub use counted_map::{
+	CountedStorageMap, CountedStorageMapInstance, CountedStorageMapMetadata, OnRemovalCounterUpdate,
+};
 pub use double_map::{StorageDoubleMap, StorageDoubleMapMetadata};
 pub use key::{
 	EncodeLikeTuple, HasKeyPrefix, HasReversibleKeyPrefix, Key, KeyGenerator,
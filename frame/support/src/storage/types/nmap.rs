@@ -173,6 +173,25 @@ where
 		<Self as crate::storage::StorageNMap<Key, Value>>::remove_prefix(partial_key)
 	}
 
+	/// Remove up to `limit` values under the first key, starting at `maybe_cursor`.
+	///
+	/// The cursor returned in [`crate::storage::KillStorageResult::SomeRemaining`] can be passed
+	/// to a subsequent call to continue the removal, e.g. in a later block.
+	pub fn clear_prefix<KP>(
+		partial_key: KP,
+		limit: u32,
+		maybe_cursor: Option<&[u8]>,
+	) -> crate::storage::KillStorageResult
+	where
+		Key: HasKeyPrefix<KP>,
+	{
+		<Self as crate::storage::StorageNMap<Key, Value>>::clear_prefix(
+			partial_key,
+			limit,
+			maybe_cursor,
+		)
+	}
+
 	/// Iterate over values that share the first key.
 	pub fn iter_prefix_values<KP>(partial_key: KP) -> PrefixIterator<Value>
 	where
@@ -270,6 +289,14 @@ where
 		<Self as crate::storage::StoragePrefixedMap<Value>>::remove_all()
 	}
 
+	/// Remove up to `limit` values of the storage, starting at `maybe_cursor`.
+	///
+	/// The cursor returned in [`crate::storage::KillStorageResult::SomeRemaining`] can be passed
+	/// to a subsequent call to continue the removal, e.g. in a later block.
+	pub fn clear(limit: u32, maybe_cursor: Option<&[u8]>) -> crate::storage::KillStorageResult {
+		<Self as crate::storage::StoragePrefixedMap<Value>>::clear(limit, maybe_cursor)
+	}
+
 	/// Iter over all value of the storage.
 	///
 	/// NOTE: If a value failed to decode becaues storage is corrupted then it is skipped.
@@ -410,6 +437,29 @@ where
 	}
 }
 
+/// It doesn't require to implement `MaxEncodedLen` and give no information for `max_size`.
+impl<Prefix, Key, Value, QueryKind, OnEmpty, MaxValues>
+	crate::traits::PartialStorageInfoTrait for
+	StorageNMap<Prefix, Key, Value, QueryKind, OnEmpty, MaxValues>
+where
+	Prefix: StorageInstance,
+	Key: super::key::KeyGenerator,
+	Value: FullCodec,
+	QueryKind: QueryKindTrait<Value, OnEmpty>,
+	OnEmpty: Get<QueryKind::Query> + 'static,
+	MaxValues: Get<Option<u32>>,
+{
+	fn partial_storage_info() -> Vec<StorageInfo> {
+		vec![
+			StorageInfo {
+				prefix: Self::final_prefix(),
+				max_values: MaxValues::get(),
+				max_size: None,
+			}
+		]
+	}
+}
+
 #[cfg(test)]
 mod test {
 	use super::*;
@@ -19,6 +19,7 @@
 
 use sp_std::prelude::*;
 use codec::{Encode, Decode};
+use sp_io::KillStorageResult;
 
 /// Return the value of the item in storage under `key`, or `None` if there is no explicit entry.
 pub fn get<T: Decode + Sized>(key: &[u8]) -> Option<T> {
@@ -92,8 +93,24 @@ pub fn kill(key: &[u8]) {
 }
 
 /// Ensure keys with the given `prefix` have no entries in storage.
+///
+/// This is unbounded, use [`clear_prefix`] to remove the keys in several steps.
 pub fn kill_prefix(prefix: &[u8]) {
-	sp_io::storage::clear_prefix(prefix);
+	sp_io::storage::clear_prefix(prefix, None, None);
+}
+
+/// Remove up to `limit` keys with the given `prefix` from the storage, starting at
+/// `maybe_cursor`.
+///
+/// Pass the cursor returned in [`KillStorageResult::SomeRemaining`] to a subsequent call to
+/// continue the removal, e.g. in a later block. See [`sp_io::storage::clear_prefix`] for the
+/// details.
+pub fn clear_prefix(
+	prefix: &[u8],
+	limit: Option<u32>,
+	maybe_cursor: Option<&[u8]>,
+) -> KillStorageResult {
+	sp_io::storage::clear_prefix(prefix, limit, maybe_cursor.map(|cursor| cursor.to_vec()))
 }
 
 /// Get a Vec of bytes from storage.
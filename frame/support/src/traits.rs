@@ -22,6 +22,7 @@
 pub mod tokens;
 pub use tokens::fungible;
 pub use tokens::fungibles;
+pub use tokens::nonfungibles;
 pub use tokens::currency::{
 	Currency, LockIdentifier, LockableCurrency, ReservableCurrency, VestingSchedule,
 };
@@ -31,7 +32,7 @@ pub use tokens::{ExistenceRequirement, WithdrawReasons, BalanceStatus};
 mod members;
 pub use members::{
 	Contains, ContainsLengthBound, SortedMembers, InitializeMembers, ChangeMembers, All, IsInVec,
-	AsContains,
+	AsContains, RankedMembers, GetSalary,
 };
 
 mod validation;
@@ -50,7 +51,8 @@ mod misc;
 pub use misc::{
 	Len, Get, GetDefault, HandleLifetime, TryDrop, Time, UnixTime, IsType, IsSubType, ExecuteBlock,
 	SameOrOther, OnNewAccount, OnKilledAccount, OffchainWorker, GetBacking, Backing, ExtrinsicCall,
-	EnsureInherentsAreFirst, ConstU32,
+	EnsureInherentsAreFirst, ConstU32, Defensive, DefensiveOption, DefensiveResult,
+	defensive_failures, note_defensive_failure,
 };
 
 mod stored_map;
@@ -65,18 +67,34 @@ pub use metadata::{
 };
 
 mod hooks;
-pub use hooks::{Hooks, OnGenesis, OnInitialize, OnFinalize, OnIdle, OnRuntimeUpgrade, OnTimestampSet};
+pub use hooks::{
+	Hooks, OnGenesis, OnInitialize, OnFinalize, OnIdle, OnPoll, OnRuntimeUpgrade, OnTimestampSet,
+	UpgradeCheckSelect,
+};
 #[cfg(feature = "try-runtime")]
-pub use hooks::{OnRuntimeUpgradeHelpersExt, ON_RUNTIME_UPGRADE_PREFIX};
+pub use hooks::{OnRuntimeUpgradeHelpersExt, ON_RUNTIME_UPGRADE_PREFIX, TryState};
 #[cfg(feature = "std")]
 pub use hooks::GenesisBuild;
 
+mod migrations;
+pub use migrations::{SteppedMigration, SteppedMigrationError, SteppedMigrations};
+
 pub mod schedule;
+mod preimages;
+pub use preimages::{Bounded, BoundedInline, FetchResult, QueryPreimage, StorePreimage};
 mod storage;
-pub use storage::{Instance, StorageInstance, StorageInfo, StorageInfoTrait};
+pub use storage::{
+	Instance, StorageInstance, StorageInfo, StorageInfoTrait, PartialStorageInfoTrait,
+};
+
+mod tasks;
+pub use tasks::Task;
 
 mod dispatch;
-pub use dispatch::{EnsureOrigin, OriginTrait, UnfilteredDispatchable};
+pub use dispatch::{
+	AsEnsureOriginWithArg, EitherOf, EnsureOrigin, EnsureOriginWithArg, EqualPrivilegeOnly,
+	MapSuccess, OriginTrait, PrivilegeCmp, TryMapSuccess, UnfilteredDispatchable,
+};
 
 mod voting;
 pub use voting::{CurrencyToVote, SaturatingCurrencyToVote, U128CurrencyToVote};
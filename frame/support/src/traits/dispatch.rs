@@ -18,7 +18,8 @@
 //! Traits for dealing with dispatching calls and the origin from which they are dispatched.
 
 use crate::dispatch::DispatchResultWithPostInfo;
-use sp_runtime::traits::BadOrigin;
+use sp_runtime::traits::{BadOrigin, Morph, TryMorph};
+use sp_std::{cmp::Ordering, marker::PhantomData};
 
 /// Some sort of check on the origin is performed by this object.
 pub trait EnsureOrigin<OuterOrigin> {
@@ -38,6 +39,177 @@ pub trait EnsureOrigin<OuterOrigin> {
 	fn successful_origin() -> OuterOrigin;
 }
 
+/// Some sort of check on the origin is performed by this object, depending on an argument.
+///
+/// This allows for checks such as "signed by the owner of the given asset".
+pub trait EnsureOriginWithArg<OuterOrigin, Argument> {
+	/// A return type.
+	type Success;
+	/// Perform the origin check.
+	fn ensure_origin(o: OuterOrigin, a: &Argument) -> Result<Self::Success, BadOrigin> {
+		Self::try_origin(o, a).map_err(|_| BadOrigin)
+	}
+	/// Perform the origin check, returning the origin value if unsuccessful.
+	fn try_origin(o: OuterOrigin, a: &Argument) -> Result<Self::Success, OuterOrigin>;
+
+	/// Returns an outer origin capable of passing `try_origin` check for the argument `a`.
+	///
+	/// ** Should be used for benchmarking only!!! **
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin(a: &Argument) -> OuterOrigin;
+}
+
+/// Turn an `EnsureOrigin` into an `EnsureOriginWithArg` ignoring its argument.
+pub struct AsEnsureOriginWithArg<EO>(PhantomData<EO>);
+impl<OuterOrigin, Argument, EO: EnsureOrigin<OuterOrigin>>
+	EnsureOriginWithArg<OuterOrigin, Argument> for AsEnsureOriginWithArg<EO>
+{
+	type Success = EO::Success;
+	fn try_origin(o: OuterOrigin, _: &Argument) -> Result<Self::Success, OuterOrigin> {
+		EO::try_origin(o)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin(_: &Argument) -> OuterOrigin {
+		EO::successful_origin()
+	}
+}
+
+/// The "OR gate" of two origin checks with the same `Success` type.
+///
+/// The check passes if `L` or `R` passes, `L` being tested first. Use
+/// `frame_system::EnsureOneOf` for checks with different `Success` types.
+pub struct EitherOf<L, R>(PhantomData<(L, R)>);
+impl<OuterOrigin, L, R> EnsureOrigin<OuterOrigin> for EitherOf<L, R>
+where
+	L: EnsureOrigin<OuterOrigin>,
+	R: EnsureOrigin<OuterOrigin, Success = L::Success>,
+{
+	type Success = L::Success;
+	fn try_origin(o: OuterOrigin) -> Result<Self::Success, OuterOrigin> {
+		L::try_origin(o).or_else(R::try_origin)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> OuterOrigin {
+		L::successful_origin()
+	}
+}
+
+impl<OuterOrigin, Argument, L, R> EnsureOriginWithArg<OuterOrigin, Argument> for EitherOf<L, R>
+where
+	L: EnsureOriginWithArg<OuterOrigin, Argument>,
+	R: EnsureOriginWithArg<OuterOrigin, Argument, Success = L::Success>,
+{
+	type Success = L::Success;
+	fn try_origin(o: OuterOrigin, a: &Argument) -> Result<Self::Success, OuterOrigin> {
+		L::try_origin(o, a).or_else(|o| R::try_origin(o, a))
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin(a: &Argument) -> OuterOrigin {
+		L::successful_origin(a)
+	}
+}
+
+/// An origin check which passes if `Original` passes, its `Success` value being mutated by
+/// `Mutator`.
+pub struct MapSuccess<Original, Mutator>(PhantomData<(Original, Mutator)>);
+impl<OuterOrigin, Original, Mutator> EnsureOrigin<OuterOrigin> for MapSuccess<Original, Mutator>
+where
+	Original: EnsureOrigin<OuterOrigin>,
+	Mutator: Morph<Original::Success>,
+{
+	type Success = Mutator::Outcome;
+	fn try_origin(o: OuterOrigin) -> Result<Self::Success, OuterOrigin> {
+		Original::try_origin(o).map(Mutator::morph)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> OuterOrigin {
+		Original::successful_origin()
+	}
+}
+
+impl<OuterOrigin, Argument, Original, Mutator> EnsureOriginWithArg<OuterOrigin, Argument>
+	for MapSuccess<Original, Mutator>
+where
+	Original: EnsureOriginWithArg<OuterOrigin, Argument>,
+	Mutator: Morph<Original::Success>,
+{
+	type Success = Mutator::Outcome;
+	fn try_origin(o: OuterOrigin, a: &Argument) -> Result<Self::Success, OuterOrigin> {
+		Original::try_origin(o, a).map(Mutator::morph)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin(a: &Argument) -> OuterOrigin {
+		Original::successful_origin(a)
+	}
+}
+
+/// An origin check which passes if `Original` passes and `Mutator` manages to mutate its
+/// `Success` value.
+///
+/// The origin is checked twice if the mutation fails, since `Original` consumes it.
+pub struct TryMapSuccess<Original, Mutator>(PhantomData<(Original, Mutator)>);
+impl<OuterOrigin, Original, Mutator> EnsureOrigin<OuterOrigin> for TryMapSuccess<Original, Mutator>
+where
+	OuterOrigin: Clone,
+	Original: EnsureOrigin<OuterOrigin>,
+	Mutator: TryMorph<Original::Success>,
+{
+	type Success = Mutator::Outcome;
+	fn try_origin(o: OuterOrigin) -> Result<Self::Success, OuterOrigin> {
+		let orig = o.clone();
+		Mutator::try_morph(Original::try_origin(o)?).map_err(|()| orig)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> OuterOrigin {
+		Original::successful_origin()
+	}
+}
+
+impl<OuterOrigin, Argument, Original, Mutator> EnsureOriginWithArg<OuterOrigin, Argument>
+	for TryMapSuccess<Original, Mutator>
+where
+	OuterOrigin: Clone,
+	Original: EnsureOriginWithArg<OuterOrigin, Argument>,
+	Mutator: TryMorph<Original::Success>,
+{
+	type Success = Mutator::Outcome;
+	fn try_origin(o: OuterOrigin, a: &Argument) -> Result<Self::Success, OuterOrigin> {
+		let orig = o.clone();
+		Mutator::try_morph(Original::try_origin(o, a)?).map_err(|()| orig)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin(a: &Argument) -> OuterOrigin {
+		Original::successful_origin(a)
+	}
+}
+
+/// Compares the privilege of two origins.
+///
+/// This is used to decide whether an origin may act on an item created by another origin, e.g.
+/// cancel a call scheduled by it.
+pub trait PrivilegeCmp<Origin> {
+	/// Compare the `left` origin to the `right` one, from the point of view of `left`.
+	///
+	/// Returns `None` when the origins can't be compared.
+	fn cmp_privilege(left: &Origin, right: &Origin) -> Option<Ordering>;
+}
+
+/// Implementation of [`PrivilegeCmp`] which only compares equal origins, i.e. which returns
+/// either `Some(Ordering::Equal)` or `None`.
+pub struct EqualPrivilegeOnly;
+impl<Origin: PartialEq> PrivilegeCmp<Origin> for EqualPrivilegeOnly {
+	fn cmp_privilege(left: &Origin, right: &Origin) -> Option<Ordering> {
+		if left == right { Some(Ordering::Equal) } else { None }
+	}
+}
+
 /// Type that can be dispatched with an origin but without checking the origin filter.
 ///
 /// Implemented for pallet dispatchable type by `decl_module` and for runtime dispatchable by
@@ -91,3 +263,112 @@ pub trait OriginTrait: Sized {
 	/// Create with system signed origin and `frame-system::Config::BaseCallFilter`.
 	fn signed(by: Self::AccountId) -> Self;
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// origins are plain numbers in these tests.
+	struct EnsureSmall;
+	impl EnsureOrigin<u32> for EnsureSmall {
+		type Success = u32;
+		fn try_origin(o: u32) -> Result<u32, u32> {
+			if o < 10 { Ok(o) } else { Err(o) }
+		}
+
+		#[cfg(feature = "runtime-benchmarks")]
+		fn successful_origin() -> u32 {
+			0
+		}
+	}
+
+	struct EnsureEven;
+	impl EnsureOrigin<u32> for EnsureEven {
+		type Success = u32;
+		fn try_origin(o: u32) -> Result<u32, u32> {
+			if o % 2 == 0 { Ok(o) } else { Err(o) }
+		}
+
+		#[cfg(feature = "runtime-benchmarks")]
+		fn successful_origin() -> u32 {
+			0
+		}
+	}
+
+	/// Passes if the origin is the argument, i.e. "the owner of the argument".
+	struct EnsureOwner;
+	impl EnsureOriginWithArg<u32, u32> for EnsureOwner {
+		type Success = u32;
+		fn try_origin(o: u32, a: &u32) -> Result<u32, u32> {
+			if o == *a { Ok(o) } else { Err(o) }
+		}
+
+		#[cfg(feature = "runtime-benchmarks")]
+		fn successful_origin(a: &u32) -> u32 {
+			*a
+		}
+	}
+
+	struct Double;
+	impl Morph<u32> for Double {
+		type Outcome = u64;
+		fn morph(a: u32) -> u64 {
+			a as u64 * 2
+		}
+	}
+
+	struct NonZero;
+	impl TryMorph<u32> for NonZero {
+		type Outcome = u32;
+		fn try_morph(a: u32) -> Result<u32, ()> {
+			if a == 0 { Err(()) } else { Ok(a) }
+		}
+	}
+
+	#[test]
+	fn either_of_works() {
+		type Origin = EitherOf<EnsureSmall, EnsureEven>;
+		assert_eq!(<Origin as EnsureOrigin<u32>>::try_origin(3), Ok(3));
+		assert_eq!(<Origin as EnsureOrigin<u32>>::try_origin(12), Ok(12));
+		assert_eq!(<Origin as EnsureOrigin<u32>>::try_origin(13), Err(13));
+
+		type WithArg = EitherOf<EnsureOwner, AsEnsureOriginWithArg<EnsureSmall>>;
+		assert_eq!(<WithArg as EnsureOriginWithArg<u32, u32>>::try_origin(3, &20), Ok(3));
+		assert_eq!(<WithArg as EnsureOriginWithArg<u32, u32>>::try_origin(20, &20), Ok(20));
+		assert_eq!(<WithArg as EnsureOriginWithArg<u32, u32>>::try_origin(20, &21), Err(20));
+	}
+
+	#[test]
+	fn map_success_works() {
+		type Origin = MapSuccess<EnsureSmall, Double>;
+		assert_eq!(<Origin as EnsureOrigin<u32>>::try_origin(3), Ok(6));
+		assert_eq!(<Origin as EnsureOrigin<u32>>::try_origin(13), Err(13));
+
+		type WithArg = MapSuccess<EnsureOwner, Double>;
+		assert_eq!(<WithArg as EnsureOriginWithArg<u32, u32>>::try_origin(20, &20), Ok(40));
+		assert_eq!(
+			<WithArg as EnsureOriginWithArg<u32, u32>>::ensure_origin(20, &21),
+			Err(BadOrigin),
+		);
+	}
+
+	#[test]
+	fn try_map_success_works() {
+		type Origin = TryMapSuccess<EnsureSmall, NonZero>;
+		assert_eq!(<Origin as EnsureOrigin<u32>>::try_origin(3), Ok(3));
+		// the origin is returned if either the check or the mutation fails.
+		assert_eq!(<Origin as EnsureOrigin<u32>>::try_origin(0), Err(0));
+		assert_eq!(<Origin as EnsureOrigin<u32>>::try_origin(13), Err(13));
+
+		type WithArg = TryMapSuccess<EnsureOwner, NonZero>;
+		assert_eq!(<WithArg as EnsureOriginWithArg<u32, u32>>::try_origin(20, &20), Ok(20));
+		assert_eq!(<WithArg as EnsureOriginWithArg<u32, u32>>::try_origin(0, &0), Err(0));
+	}
+
+	#[test]
+	fn equal_privilege_only_works() {
+		assert_eq!(EqualPrivilegeOnly::cmp_privilege(&3u32, &3), Some(Ordering::Equal));
+		assert_eq!(EqualPrivilegeOnly::cmp_privilege(&3u32, &4), None);
+		assert_eq!(EqualPrivilegeOnly::cmp_privilege(&4u32, &3), None);
+	}
+}
@@ -17,9 +17,12 @@
 
 //! Traits for hooking tasks to events in a blockchain's lifecycle.
 
+use codec::{Decode, Encode};
 use sp_arithmetic::traits::Saturating;
-use sp_runtime::traits::MaybeSerializeDeserialize;
+use sp_runtime::{RuntimeDebug, traits::MaybeSerializeDeserialize};
 use impl_trait_for_tuples::impl_for_tuples;
+#[cfg(feature = "try-runtime")]
+use sp_std::prelude::*;
 
 /// The block initialization trait.
 ///
@@ -89,6 +92,28 @@ impl<BlockNumber: Clone> OnIdle<BlockNumber> for Tuple {
 	}
 }
 
+/// The block's on poll trait.
+///
+/// Implementing this lets you express some background work that should be done piece by piece
+/// in each block, with the weight left by the extrinsics of the block.
+pub trait OnPoll<BlockNumber> {
+	/// Do some work with the weight left in the block.
+	///
+	/// Weight must be consumed from `weight` before doing any work, and no work should be done
+	/// once it fails to be consumed. Any remaining work is picked up in the next block.
+	///
+	/// NOTE: This function is called AFTER ALL extrinsics - including inherent extrinsics -
+	/// in a block are applied but before `on_idle` is executed.
+	fn on_poll(_n: BlockNumber, _weight: &mut crate::weights::WeightMeter) {}
+}
+
+#[impl_for_tuples(30)]
+impl<BlockNumber: Clone> OnPoll<BlockNumber> for Tuple {
+	fn on_poll(n: BlockNumber, weight: &mut crate::weights::WeightMeter) {
+		for_tuples!( #( Tuple::on_poll(n.clone(), weight); )* );
+	}
+}
+
 /// A trait that will be called at genesis.
 ///
 /// Implementing this trait for a pallet let's you express operations that should
@@ -168,15 +193,75 @@ pub trait OnRuntimeUpgrade {
 
 	/// Execute some pre-checks prior to a runtime upgrade.
 	///
+	/// Return some state, e.g. an encoded snapshot of the storage about to be migrated, which is
+	/// handed to [`Self::post_upgrade`] once the upgrade is done.
+	///
 	/// This hook is never meant to be executed on-chain but is meant to be used by testing tools.
 	#[cfg(feature = "try-runtime")]
-	fn pre_upgrade() -> Result<(), &'static str> { Ok(()) }
+	fn pre_upgrade() -> Result<Vec<u8>, &'static str> { Ok(Vec::new()) }
 
 	/// Execute some post-checks after a runtime upgrade.
 	///
+	/// `state` is the one returned by [`Self::pre_upgrade`] prior to the upgrade.
+	///
+	/// This hook is never meant to be executed on-chain but is meant to be used by testing tools.
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> { Ok(()) }
+
+	/// The name of this upgrade, for testing tools to label and select it by: the name of the
+	/// pallet it belongs to, or its type name otherwise.
+	#[cfg(feature = "try-runtime")]
+	fn upgrade_name() -> &'static str {
+		sp_std::any::type_name::<Self>()
+	}
+
+	/// Perform the module upgrade like [`Self::on_runtime_upgrade`], and return the weight
+	/// consumed by each of the upgrades it is made of, labelled with their
+	/// [`Self::upgrade_name`].
+	///
+	/// Only the upgrades whose name `filter` accepts are performed.
+	///
+	/// This hook is never meant to be executed on-chain but is meant to be used by testing tools,
+	/// e.g. to find out which migration does not fit in a block.
+	#[cfg(feature = "try-runtime")]
+	fn on_runtime_upgrade_breakdown(
+		filter: &dyn Fn(&str) -> bool,
+	) -> Vec<(&'static str, crate::weights::Weight)> {
+		if filter(Self::upgrade_name()) {
+			vec![(Self::upgrade_name(), Self::on_runtime_upgrade())]
+		} else {
+			Vec::new()
+		}
+	}
+
+	/// Like [`Self::pre_upgrade`], but only for the upgrades whose name `filter` accepts.
+	///
 	/// This hook is never meant to be executed on-chain but is meant to be used by testing tools.
 	#[cfg(feature = "try-runtime")]
-	fn post_upgrade() -> Result<(), &'static str> { Ok(()) }
+	fn pre_upgrade_filtered(filter: &dyn Fn(&str) -> bool) -> Result<Vec<u8>, &'static str> {
+		if filter(Self::upgrade_name()) {
+			Self::pre_upgrade()
+		} else {
+			Ok(Vec::new())
+		}
+	}
+
+	/// Like [`Self::post_upgrade`], but only for the upgrades whose name `filter` accepts.
+	///
+	/// `state` is the one returned by [`Self::pre_upgrade_filtered`] with the same `filter`.
+	///
+	/// This hook is never meant to be executed on-chain but is meant to be used by testing tools.
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade_filtered(
+		state: Vec<u8>,
+		filter: &dyn Fn(&str) -> bool,
+	) -> Result<(), &'static str> {
+		if filter(Self::upgrade_name()) {
+			Self::post_upgrade(state)
+		} else {
+			Ok(())
+		}
+	}
 }
 
 #[impl_for_tuples(30)]
@@ -187,18 +272,114 @@ impl OnRuntimeUpgrade for Tuple {
 		weight
 	}
 
+	/// The states of all elements, encoded as a `Vec<Vec<u8>>` in tuple order.
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+		Self::pre_upgrade_filtered(&|_| true)
+	}
+
+	/// Hands each element its own state, as returned by [`Self::pre_upgrade`].
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+		Self::post_upgrade_filtered(state, &|_| true)
+	}
+
+	/// The upgrades of all elements, in tuple order.
+	#[cfg(feature = "try-runtime")]
+	fn on_runtime_upgrade_breakdown(
+		filter: &dyn Fn(&str) -> bool,
+	) -> Vec<(&'static str, crate::weights::Weight)> {
+		let mut breakdown = Vec::new();
+		for_tuples!( #( breakdown.extend(Tuple::on_runtime_upgrade_breakdown(filter)); )* );
+		breakdown
+	}
+
+	/// The states of all elements, encoded as a `Vec<Vec<u8>>` in tuple order.
 	#[cfg(feature = "try-runtime")]
-	fn pre_upgrade() -> Result<(), &'static str> {
-		let mut result = Ok(());
-		for_tuples!( #( result = result.and(Tuple::pre_upgrade()); )* );
-		result
+	fn pre_upgrade_filtered(filter: &dyn Fn(&str) -> bool) -> Result<Vec<u8>, &'static str> {
+		let mut states: Vec<Vec<u8>> = Vec::new();
+		for_tuples!( #( states.push(Tuple::pre_upgrade_filtered(filter)?); )* );
+		Ok(codec::Encode::encode(&states))
 	}
 
+	/// Hands each element its own state, as returned by [`Self::pre_upgrade_filtered`].
 	#[cfg(feature = "try-runtime")]
-	fn post_upgrade() -> Result<(), &'static str> {
-		let mut result = Ok(());
-		for_tuples!( #( result = result.and(Tuple::post_upgrade()); )* );
-		result
+	fn post_upgrade_filtered(
+		state: Vec<u8>,
+		filter: &dyn Fn(&str) -> bool,
+	) -> Result<(), &'static str> {
+		let states: Vec<Vec<u8>> = codec::Decode::decode(&mut &state[..])
+			.map_err(|_| "state is not the one returned by `pre_upgrade`")?;
+		let mut states = states.into_iter();
+		for_tuples!( #(
+			Tuple::post_upgrade_filtered(
+				states.next().ok_or("state is not the one returned by `pre_upgrade`")?,
+				filter,
+			)?;
+		)* );
+		Ok(())
+	}
+}
+
+/// The checks to run along with migrations and blocks executed by try-runtime.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum UpgradeCheckSelect {
+	/// Run no checks.
+	None,
+	/// Run the `pre_upgrade` and `post_upgrade` checks of the migrations.
+	PreAndPost,
+	/// Run the `try_state` checks of all pallets.
+	TryState,
+	/// Run all of the above.
+	All,
+}
+
+impl UpgradeCheckSelect {
+	/// Whether the `pre_upgrade` and `post_upgrade` checks should run.
+	pub fn pre_and_post(&self) -> bool {
+		matches!(self, Self::PreAndPost | Self::All)
+	}
+
+	/// Whether the `try_state` checks should run.
+	pub fn try_state(&self) -> bool {
+		matches!(self, Self::TryState | Self::All)
+	}
+}
+
+#[cfg(feature = "std")]
+impl sp_std::str::FromStr for UpgradeCheckSelect {
+	type Err = &'static str;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.to_lowercase().as_str() {
+			"none" => Ok(Self::None),
+			"pre-and-post" => Ok(Self::PreAndPost),
+			"try-state" => Ok(Self::TryState),
+			"all" => Ok(Self::All),
+			_ => Err("expected one of `none`, `pre-and-post`, `try-state` or `all`"),
+		}
+	}
+}
+
+/// The state invariants trait.
+///
+/// Implementing this lets you express the invariants that must hold over the storage of your
+/// pallet at all times, e.g. that the total issuance equals the sum of all balances.
+///
+/// This hook is never meant to be executed on-chain but is meant to be used by testing tools.
+#[cfg(feature = "try-runtime")]
+pub trait TryState<BlockNumber> {
+	/// Check the invariants of the state at block `n`, without altering it.
+	fn try_state(n: BlockNumber) -> Result<(), &'static str>;
+}
+
+#[cfg(feature = "try-runtime")]
+#[impl_for_tuples(30)]
+impl<BlockNumber: Clone> TryState<BlockNumber> for Tuple {
+	/// Checks all elements in tuple order, stopping at the first failing one.
+	fn try_state(n: BlockNumber) -> Result<(), &'static str> {
+		for_tuples!( #( Tuple::try_state(n.clone())?; )* );
+		Ok(())
 	}
 }
 
@@ -224,6 +405,14 @@ pub trait Hooks<BlockNumber> {
 	/// Return the non-negotiable weight consumed in the block.
 	fn on_initialize(_n: BlockNumber) -> crate::weights::Weight { 0 }
 
+	/// This will be run in every block once the extrinsics are applied (before `on_idle`).
+	/// Implement to process some work piece by piece, e.g. a queue, with the weight left in the
+	/// block.
+	///
+	/// Weight must be consumed from `weight` before doing a piece of work, and no more work
+	/// should be done once it fails to be consumed.
+	fn on_poll(_n: BlockNumber, _weight: &mut crate::weights::WeightMeter) {}
+
 	/// Perform a module upgrade.
 	///
 	/// NOTE: this doesn't include all pallet logic triggered on runtime upgrade. For instance it
@@ -242,17 +431,29 @@ pub trait Hooks<BlockNumber> {
 
 	/// Execute some pre-checks prior to a runtime upgrade.
 	///
+	/// See [`OnRuntimeUpgrade::pre_upgrade`].
+	///
 	/// This hook is never meant to be executed on-chain but is meant to be used by testing tools.
 	#[cfg(feature = "try-runtime")]
-	fn pre_upgrade() -> Result<(), &'static str> {
-		Ok(())
+	fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+		Ok(Vec::new())
 	}
 
 	/// Execute some post-checks after a runtime upgrade.
 	///
+	/// See [`OnRuntimeUpgrade::post_upgrade`].
+	///
 	/// This hook is never meant to be executed on-chain but is meant to be used by testing tools.
 	#[cfg(feature = "try-runtime")]
-	fn post_upgrade() -> Result<(), &'static str> {
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+		Ok(())
+	}
+
+	/// Check the invariants of the storage of the pallet at block `n`.
+	///
+	/// See [`TryState`].
+	#[cfg(feature = "try-runtime")]
+	fn try_state(_n: BlockNumber) -> Result<(), &'static str> {
 		Ok(())
 	}
 
@@ -314,6 +515,20 @@ pub trait OnTimestampSet<Moment> {
 mod tests {
 	use super::*;
 	use crate::traits::metadata::PalletVersion;
+	use codec::Encode;
+
+	#[test]
+	fn upgrade_check_select_from_str_works() {
+		assert_eq!("none".parse(), Ok(UpgradeCheckSelect::None));
+		assert_eq!("Pre-And-Post".parse(), Ok(UpgradeCheckSelect::PreAndPost));
+		assert_eq!("try-state".parse(), Ok(UpgradeCheckSelect::TryState));
+		assert_eq!("all".parse(), Ok(UpgradeCheckSelect::All));
+		assert!("some".parse::<UpgradeCheckSelect>().is_err());
+
+		assert!(UpgradeCheckSelect::All.pre_and_post() && UpgradeCheckSelect::All.try_state());
+		assert!(!UpgradeCheckSelect::PreAndPost.try_state());
+		assert!(!UpgradeCheckSelect::TryState.pre_and_post());
+	}
 
 	#[test]
 	fn on_initialize_and_on_runtime_upgrade_weight_merge_works() {
@@ -333,6 +548,88 @@ mod tests {
 		assert_eq!(<(Test, Test)>::on_runtime_upgrade(), 40);
 	}
 
+	#[cfg(feature = "try-runtime")]
+	#[test]
+	fn try_state_stops_at_first_failure() {
+		struct Test<const N: u8>;
+		impl<const N: u8> TryState<u8> for Test<N> {
+			fn try_state(n: u8) -> Result<(), &'static str> {
+				if n == N { Err("invariant broken") } else { Ok(()) }
+			}
+		}
+
+		assert_eq!(<(Test<1>, Test<2>)>::try_state(0), Ok(()));
+		assert_eq!(<(Test<1>, Test<2>)>::try_state(2), Err("invariant broken"));
+	}
+
+	#[cfg(feature = "try-runtime")]
+	#[test]
+	fn on_runtime_upgrade_breakdown_lists_tuple_elements() {
+		struct Test<const N: u64>;
+		impl<const N: u64> OnRuntimeUpgrade for Test<N> {
+			fn on_runtime_upgrade() -> crate::weights::Weight {
+				N
+			}
+		}
+
+		let breakdown = <(Test<1>, (Test<2>, Test<3>))>::on_runtime_upgrade_breakdown(&|_| true);
+		assert_eq!(breakdown.iter().map(|(_, weight)| *weight).collect::<Vec<_>>(), vec![1, 2, 3]);
+		assert_eq!(breakdown[0].0, sp_std::any::type_name::<Test<1>>());
+
+		let name = sp_std::any::type_name::<Test<2>>();
+		let breakdown =
+			<(Test<1>, (Test<2>, Test<3>))>::on_runtime_upgrade_breakdown(&|n| n == name);
+		assert_eq!(breakdown, vec![(name, 2)]);
+	}
+
+	#[cfg(feature = "try-runtime")]
+	#[test]
+	fn filtered_checks_skip_unselected_upgrades() {
+		struct Test<const N: u8>;
+		impl<const N: u8> OnRuntimeUpgrade for Test<N> {
+			fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+				if N == 2 { Err("pre-upgrade failed") } else { Ok(vec![N]) }
+			}
+
+			fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+				if state == vec![N] { Ok(()) } else { Err("unexpected state") }
+			}
+		}
+
+		let name = sp_std::any::type_name::<Test<1>>();
+		assert_eq!(<(Test<1>, Test<2>)>::pre_upgrade(), Err("pre-upgrade failed"));
+		let state = <(Test<1>, Test<2>)>::pre_upgrade_filtered(&|n| n == name).unwrap();
+		assert_eq!(state, vec![vec![1u8], vec![]].encode());
+		assert_eq!(<(Test<1>, Test<2>)>::post_upgrade_filtered(state, &|n| n == name), Ok(()));
+	}
+
+	#[cfg(feature = "try-runtime")]
+	#[test]
+	fn pre_upgrade_state_is_passed_to_post_upgrade() {
+		struct Test<const N: u8>;
+		impl<const N: u8> OnRuntimeUpgrade for Test<N> {
+			fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+				Ok(vec![N])
+			}
+
+			fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+				if state == vec![N] { Ok(()) } else { Err("unexpected state") }
+			}
+		}
+
+		let state = <(Test<1>, Test<2>)>::pre_upgrade().unwrap();
+		assert_eq!(state, vec![vec![1u8], vec![2u8]].encode());
+		assert_eq!(<(Test<1>, Test<2>)>::post_upgrade(state), Ok(()));
+		assert_eq!(
+			<(Test<1>, Test<2>)>::post_upgrade(vec![vec![2u8], vec![1u8]].encode()),
+			Err("unexpected state"),
+		);
+		assert_eq!(
+			<(Test<1>, Test<2>)>::post_upgrade(vec![vec![1u8]].encode()),
+			Err("state is not the one returned by `pre_upgrade`"),
+		);
+	}
+
 	#[test]
 	fn check_pallet_version_ordering() {
 		let version = PalletVersion::new(1, 0, 0);
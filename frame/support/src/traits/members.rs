@@ -18,6 +18,7 @@
 //! Traits for dealing with the idea of membership.
 
 use sp_std::{prelude::*, marker::PhantomData};
+use sp_runtime::DispatchResult;
 
 /// A trait for querying whether a type can be said to "contain" a value.
 pub trait Contains<T> {
@@ -105,6 +106,37 @@ impl<X: Ord + PartialOrd, T: super::Get<Vec<X>>> SortedMembers<X> for IsInVec<T>
 	fn sorted_members() -> Vec<X> { let mut r = T::get(); r.sort(); r }
 }
 
+/// A collection of accounts, each of which holds a rank. Ranks start at zero for the most junior
+/// member and increment by one at a time.
+pub trait RankedMembers {
+	/// The account identifier type.
+	type AccountId;
+	/// The rank type; a zero value is the lowest rank.
+	type Rank: Copy + Ord + Default;
+
+	/// The lowest rank possible in this membership organisation.
+	fn min_rank() -> Self::Rank;
+
+	/// Return the rank of the given account, or `None` if the account is not a member.
+	fn rank_of(who: &Self::AccountId) -> Option<Self::Rank>;
+
+	/// Add a member to the group at the `min_rank()`.
+	fn induct(who: &Self::AccountId) -> DispatchResult;
+
+	/// Promote a member to the next higher rank.
+	fn promote(who: &Self::AccountId) -> DispatchResult;
+
+	/// Demote a member to the next lower rank; demoting beyond the `min_rank` removes the
+	/// member entirely.
+	fn demote(who: &Self::AccountId) -> DispatchResult;
+}
+
+/// The salary of the members of a ranked collective, e.g. for a pallet paying it out.
+pub trait GetSalary<Rank, AccountId, Balance> {
+	/// The salary of `who`, a member of rank `rank`, for one payout period.
+	fn get_salary(rank: Rank, who: &AccountId) -> Balance;
+}
+
 /// A trait for querying bound for the length of an implementation of `Contains`
 pub trait ContainsLengthBound {
 	/// Minimum number of elements contained
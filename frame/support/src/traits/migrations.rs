@@ -0,0 +1,237 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Traits for migrations which run over multiple blocks.
+
+use codec::{Decode, Encode, FullCodec};
+use impl_trait_for_tuples::impl_for_tuples;
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+use crate::weights::Weight;
+
+/// The reason why a [`SteppedMigration`] could not make a step.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum SteppedMigrationError {
+	/// The weight left does not suffice for the next step, which requires `required`.
+	///
+	/// The step is retried in a later block, unless it would not even fit into an empty one.
+	InsufficientWeight {
+		/// The weight the next step requires.
+		required: Weight,
+	},
+	/// The cursor to continue from could not be decoded.
+	InvalidCursor,
+	/// The migration failed and can not make any further progress.
+	Failed,
+}
+
+/// A migration which runs over multiple blocks, one step at a time.
+///
+/// Each step is bounded by the weight it is given, and returns a cursor to continue from in the
+/// next step. The cursor is kept in storage in between steps, so it should be small.
+pub trait SteppedMigration {
+	/// The progress of the migration, kept in between steps.
+	type Cursor: FullCodec;
+
+	/// The unique identifier of the migration.
+	///
+	/// It is recorded once the migration completes, so that it never runs twice.
+	fn id() -> Vec<u8>;
+
+	/// Make a step from `cursor`, or from the start if `None`, consuming at most `limit`.
+	///
+	/// Returns the cursor to continue from, or `None` if the migration is complete, along with
+	/// the weight consumed.
+	fn step(
+		cursor: Option<Self::Cursor>,
+		limit: Weight,
+	) -> Result<(Option<Self::Cursor>, Weight), SteppedMigrationError>;
+
+	/// Execute some pre-checks prior to the first step of the migration.
+	///
+	/// The returned state is handed to [`Self::post_upgrade`].
+	///
+	/// This hook is never meant to be executed on-chain but is meant to be used by testing tools.
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+		Ok(Vec::new())
+	}
+
+	/// Execute some post-checks once the migration is complete.
+	///
+	/// This hook is never meant to be executed on-chain but is meant to be used by testing tools.
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+		Ok(())
+	}
+}
+
+/// An ordered list of [`SteppedMigration`]s, addressed by index, with encoded cursors.
+///
+/// Implemented for tuples of [`SteppedMigration`]s, such as `(MigrationA, MigrationB)`.
+pub trait SteppedMigrations {
+	/// The number of migrations.
+	fn len() -> u32;
+
+	/// The identifier of the `n`th migration, if any.
+	fn nth_id(n: u32) -> Option<Vec<u8>>;
+
+	/// Make a step of the `n`th migration, if any, from the encoded `cursor`.
+	///
+	/// See [`SteppedMigration::step`].
+	fn nth_step(
+		n: u32,
+		cursor: Option<Vec<u8>>,
+		limit: Weight,
+	) -> Option<Result<(Option<Vec<u8>>, Weight), SteppedMigrationError>>;
+
+	/// Execute the pre-checks of the `n`th migration, if any.
+	#[cfg(feature = "try-runtime")]
+	fn nth_pre_upgrade(n: u32) -> Option<Result<Vec<u8>, &'static str>>;
+
+	/// Execute the post-checks of the `n`th migration, if any.
+	#[cfg(feature = "try-runtime")]
+	fn nth_post_upgrade(n: u32, state: Vec<u8>) -> Option<Result<(), &'static str>>;
+}
+
+/// Make a step of `M` from the encoded `cursor`.
+fn step_encoded<M: SteppedMigration>(
+	cursor: Option<Vec<u8>>,
+	limit: Weight,
+) -> Result<(Option<Vec<u8>>, Weight), SteppedMigrationError> {
+	let cursor = cursor
+		.map(|cursor| M::Cursor::decode(&mut &cursor[..]))
+		.transpose()
+		.map_err(|_| SteppedMigrationError::InvalidCursor)?;
+	M::step(cursor, limit).map(|(cursor, weight)| (cursor.map(|cursor| cursor.encode()), weight))
+}
+
+#[impl_for_tuples(30)]
+#[tuple_types_custom_trait_bound(SteppedMigration)]
+impl SteppedMigrations for Tuple {
+	#[allow(unused_mut)]
+	fn len() -> u32 {
+		let mut len = 0;
+		for_tuples!( #(
+			let _ = sp_std::marker::PhantomData::<Tuple>;
+			len += 1;
+		)* );
+		len
+	}
+
+	#[allow(unused_mut, unused_assignments)]
+	fn nth_id(n: u32) -> Option<Vec<u8>> {
+		let mut index = 0;
+		for_tuples!( #(
+			if index == n {
+				return Some(Tuple::id());
+			}
+			index += 1;
+		)* );
+		None
+	}
+
+	#[allow(unused_mut, unused_assignments)]
+	fn nth_step(
+		n: u32,
+		cursor: Option<Vec<u8>>,
+		limit: Weight,
+	) -> Option<Result<(Option<Vec<u8>>, Weight), SteppedMigrationError>> {
+		let mut index = 0;
+		for_tuples!( #(
+			if index == n {
+				return Some(step_encoded::<Tuple>(cursor, limit));
+			}
+			index += 1;
+		)* );
+		None
+	}
+
+	#[cfg(feature = "try-runtime")]
+	#[allow(unused_mut, unused_assignments)]
+	fn nth_pre_upgrade(n: u32) -> Option<Result<Vec<u8>, &'static str>> {
+		let mut index = 0;
+		for_tuples!( #(
+			if index == n {
+				return Some(Tuple::pre_upgrade());
+			}
+			index += 1;
+		)* );
+		None
+	}
+
+	#[cfg(feature = "try-runtime")]
+	#[allow(unused_mut, unused_assignments)]
+	fn nth_post_upgrade(n: u32, state: Vec<u8>) -> Option<Result<(), &'static str>> {
+		let mut index = 0;
+		for_tuples!( #(
+			if index == n {
+				return Some(Tuple::post_upgrade(state));
+			}
+			index += 1;
+		)* );
+		None
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Counts up to `N`, one at a time, for a weight of one per step.
+	struct CountTo<const N: u32>;
+	impl<const N: u32> SteppedMigration for CountTo<N> {
+		type Cursor = u32;
+
+		fn id() -> Vec<u8> {
+			N.encode()
+		}
+
+		fn step(
+			cursor: Option<u32>,
+			limit: Weight,
+		) -> Result<(Option<u32>, Weight), SteppedMigrationError> {
+			if limit < 1 {
+				return Err(SteppedMigrationError::InsufficientWeight { required: 1 });
+			}
+			let next = cursor.unwrap_or_default() + 1;
+			Ok(((next < N).then(|| next), 1))
+		}
+	}
+
+	#[test]
+	fn tuples_dispatch_by_index() {
+		type Migrations = (CountTo<1>, CountTo<3>);
+		assert_eq!(Migrations::len(), 2);
+		assert_eq!(<()>::len(), 0);
+		assert_eq!(Migrations::nth_id(1), Some(3u32.encode()));
+		assert_eq!(Migrations::nth_id(2), None);
+
+		assert_eq!(Migrations::nth_step(0, None, 10), Some(Ok((None, 1))));
+		assert_eq!(Migrations::nth_step(1, None, 10), Some(Ok((Some(1u32.encode()), 1))));
+		assert_eq!(Migrations::nth_step(1, Some(2u32.encode()), 10), Some(Ok((None, 1))));
+		assert_eq!(
+			Migrations::nth_step(1, None, 0),
+			Some(Err(SteppedMigrationError::InsufficientWeight { required: 1 })),
+		);
+		assert_eq!(
+			Migrations::nth_step(1, Some(vec![1]), 10),
+			Some(Err(SteppedMigrationError::InvalidCursor)),
+		);
+		assert_eq!(Migrations::nth_step(2, None, 10), None);
+	}
+}
@@ -19,6 +19,7 @@
 
 use sp_runtime::traits::{StoredMapError, Block as BlockT};
 use sp_arithmetic::traits::AtLeast32Bit;
+use core::sync::atomic::{AtomicU32, Ordering};
 use crate::dispatch::Parameter;
 
 /// Anything that can have a `::len()` method.
@@ -336,3 +337,162 @@ where
 		&self.function
 	}
 }
+
+/// The number of defensive failures reported so far, see [`defensive_failures`].
+static DEFENSIVE_FAILURES: AtomicU32 = AtomicU32::new(0);
+
+/// The number of defensive failures reported by this instance of the runtime, i.e. the number of
+/// times a branch which should never be taken was taken.
+///
+/// Failures are reported by [`crate::defensive`] and the [`Defensive`], [`DefensiveOption`] and
+/// [`DefensiveResult`] traits.
+pub fn defensive_failures() -> u32 {
+	DEFENSIVE_FAILURES.load(Ordering::Relaxed)
+}
+
+/// Count a defensive failure. Use [`crate::defensive`] instead, which also logs it.
+#[doc(hidden)]
+pub fn note_defensive_failure() {
+	DEFENSIVE_FAILURES.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Unwrapping which reports a defensive failure (see [`crate::defensive`]) instead of failing
+/// silently when there is nothing to unwrap.
+///
+/// Meant for values which are present unless there is a bug, where panicking in production is not
+/// an option.
+pub trait Defensive<T> {
+	/// Like `unwrap_or`, but reports a defensive failure if `self` has nothing to unwrap.
+	fn defensive_unwrap_or(self, other: T) -> T;
+
+	/// Like `unwrap_or_else`, but reports a defensive failure if `self` has nothing to unwrap.
+	fn defensive_unwrap_or_else<F: FnOnce() -> T>(self, f: F) -> T;
+
+	/// Like `unwrap_or_default`, but reports a defensive failure if `self` has nothing to unwrap.
+	fn defensive_unwrap_or_default(self) -> T where T: Default;
+
+	/// Reports a defensive failure if `self` has nothing to unwrap, and returns it unchanged.
+	fn defensive(self) -> Self;
+}
+
+/// Conversions of an `Option` which report a defensive failure on `None`.
+pub trait DefensiveOption<T> {
+	/// Like `ok_or`, but reports a defensive failure on `None`.
+	fn defensive_ok_or<E>(self, err: E) -> Result<T, E>;
+
+	/// Like `ok_or_else`, but reports a defensive failure on `None`.
+	fn defensive_ok_or_else<E, F: FnOnce() -> E>(self, err: F) -> Result<T, E>;
+
+	/// Like `map_or`, but reports a defensive failure on `None`.
+	fn defensive_map_or<U, F: FnOnce(T) -> U>(self, default: U, f: F) -> U;
+}
+
+/// Conversions of a `Result` which report a defensive failure on `Err`.
+pub trait DefensiveResult<T, E> {
+	/// Like `map_err`, but reports a defensive failure on `Err`.
+	fn defensive_map_err<F, O: FnOnce(E) -> F>(self, o: O) -> Result<T, F>;
+
+	/// Like `ok`, but reports a defensive failure on `Err`.
+	fn defensive_ok(self) -> Option<T>;
+}
+
+impl<T> Defensive<T> for Option<T> {
+	fn defensive_unwrap_or(self, other: T) -> T {
+		self.defensive_unwrap_or_else(|| other)
+	}
+
+	fn defensive_unwrap_or_else<F: FnOnce() -> T>(self, f: F) -> T {
+		match self {
+			Some(inner) => inner,
+			None => {
+				crate::defensive!("unwrapped `None`");
+				f()
+			},
+		}
+	}
+
+	fn defensive_unwrap_or_default(self) -> T where T: Default {
+		self.defensive_unwrap_or_else(Default::default)
+	}
+
+	fn defensive(self) -> Self {
+		if self.is_none() {
+			crate::defensive!("unwrapped `None`");
+		}
+		self
+	}
+}
+
+impl<T, E: sp_std::fmt::Debug> Defensive<T> for Result<T, E> {
+	fn defensive_unwrap_or(self, other: T) -> T {
+		self.defensive_unwrap_or_else(|| other)
+	}
+
+	fn defensive_unwrap_or_else<F: FnOnce() -> T>(self, f: F) -> T {
+		match self {
+			Ok(inner) => inner,
+			Err(e) => {
+				crate::defensive!(e);
+				f()
+			},
+		}
+	}
+
+	fn defensive_unwrap_or_default(self) -> T where T: Default {
+		self.defensive_unwrap_or_else(Default::default)
+	}
+
+	fn defensive(self) -> Self {
+		if let Err(e) = &self {
+			crate::defensive!(e);
+		}
+		self
+	}
+}
+
+impl<T> DefensiveOption<T> for Option<T> {
+	fn defensive_ok_or<E>(self, err: E) -> Result<T, E> {
+		self.defensive_ok_or_else(|| err)
+	}
+
+	fn defensive_ok_or_else<E, F: FnOnce() -> E>(self, err: F) -> Result<T, E> {
+		self.ok_or_else(|| {
+			crate::defensive!("unwrapped `None`");
+			err()
+		})
+	}
+
+	fn defensive_map_or<U, F: FnOnce(T) -> U>(self, default: U, f: F) -> U {
+		self.defensive().map_or(default, f)
+	}
+}
+
+impl<T, E: sp_std::fmt::Debug> DefensiveResult<T, E> for Result<T, E> {
+	fn defensive_map_err<F, O: FnOnce(E) -> F>(self, o: O) -> Result<T, F> {
+		self.defensive().map_err(o)
+	}
+
+	fn defensive_ok(self) -> Option<T> {
+		self.defensive().ok()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn defensive_passes_values_through() {
+		assert_eq!(Some(1).defensive_unwrap_or(2), 1);
+		assert_eq!(Ok::<_, ()>(1).defensive_unwrap_or_default(), 1);
+		assert_eq!(Some(1).defensive_ok_or(()), Ok(1));
+		assert_eq!(Ok::<_, ()>(1).defensive_ok(), Some(1));
+	}
+
+	#[test]
+	#[cfg(debug_assertions)]
+	#[should_panic(expected = "Defensive failure: \"unwrapped `None`\"")]
+	fn defensive_panics_with_debug_assertions() {
+		None::<u32>.defensive_unwrap_or(2);
+	}
+}
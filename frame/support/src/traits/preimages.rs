@@ -0,0 +1,311 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Traits for storing the preimages of values, e.g. calls, and looking them up by their hash.
+//!
+//! Values are stored in a [`Bounded`], which holds small values inline and only the hash and
+//! length of the others, their preimage being kept by a [`StorePreimage`].
+
+use sp_std::{prelude::*, borrow::Cow, convert::TryFrom, marker::PhantomData};
+use codec::{Decode, Encode, EncodeLike};
+use sp_core::H256;
+use sp_runtime::{DispatchError, RuntimeDebug, traits::{BlakeTwo256, Hash as HashT}};
+use crate::{storage::bounded_vec::BoundedVec, traits::{ConstU32, MaxEncodedLen}};
+
+/// The hash of a preimage.
+pub type Hash = H256;
+
+/// The maximal length of a value held inline by a [`Bounded`].
+pub const MAX_INLINE_LEN: u32 = 128;
+
+/// The bytes of a value held inline by a [`Bounded`].
+pub type BoundedInline = BoundedVec<u8, ConstU32<MAX_INLINE_LEN>>;
+
+/// The result of fetching a preimage.
+pub type FetchResult = Result<Cow<'static, [u8]>, DispatchError>;
+
+/// A value of type `T`, either held inline if it is small enough or referenced by the hash of
+/// its encoding otherwise.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+pub enum Bounded<T> {
+	/// The hash of the encoded value, whose length is unknown.
+	///
+	/// Only meant for values stored before their length was recorded.
+	Legacy {
+		/// The hash of the encoded value.
+		hash: Hash,
+		/// Keeps track of the type of the value.
+		dummy: PhantomData<T>,
+	},
+	/// The encoded value, not longer than [`MAX_INLINE_LEN`].
+	Inline(BoundedInline),
+	/// The hash and length of the encoded value.
+	Lookup {
+		/// The hash of the encoded value.
+		hash: Hash,
+		/// The length of the encoded value.
+		len: u32,
+	},
+}
+
+impl<T> Bounded<T> {
+	/// Changes the type of the value, keeping its encoding.
+	pub fn transmute<S: Encode>(self) -> Bounded<S>
+	where
+		T: Encode + EncodeLike<S>,
+	{
+		use Bounded::*;
+		match self {
+			Legacy { hash, .. } => Legacy { hash, dummy: PhantomData },
+			Inline(data) => Inline(data),
+			Lookup { hash, len } => Lookup { hash, len },
+		}
+	}
+
+	/// The hash of the encoded value.
+	pub fn hash(&self) -> Hash {
+		use Bounded::*;
+		match self {
+			Legacy { hash, .. } | Lookup { hash, .. } => *hash,
+			Inline(data) => BlakeTwo256::hash(&data[..]),
+		}
+	}
+
+	/// The hash of the encoded value, if it has to be looked up.
+	pub fn lookup_hash(&self) -> Option<Hash> {
+		use Bounded::*;
+		match self {
+			Legacy { hash, .. } | Lookup { hash, .. } => Some(*hash),
+			Inline(_) => None,
+		}
+	}
+
+	/// The length of the encoded value, if it is known.
+	pub fn len(&self) -> Option<u32> {
+		match self {
+			Self::Legacy { .. } => None,
+			Self::Inline(data) => Some(data.len() as u32),
+			Self::Lookup { len, .. } => Some(*len),
+		}
+	}
+
+	/// Whether the value has to be looked up.
+	pub fn lookup_needed(&self) -> bool {
+		!matches!(self, Self::Inline(_))
+	}
+
+	/// The length of the encoded value, if it has to be looked up and is known.
+	pub fn lookup_len(&self) -> Option<u32> {
+		match self {
+			Self::Lookup { len, .. } => Some(*len),
+			Self::Legacy { .. } | Self::Inline(_) => None,
+		}
+	}
+}
+
+// implemented by hand, since the derive would require `T: MaxEncodedLen`.
+impl<T> MaxEncodedLen for Bounded<T> {
+	fn max_encoded_len() -> usize {
+		// the index of the variant, followed by its largest content; a `Hash` encodes like
+		// `[u8; 32]`.
+		let lookup = <[u8; 32]>::max_encoded_len() + u32::max_encoded_len();
+		1 + BoundedInline::max_encoded_len().max(lookup)
+	}
+}
+
+/// Looks up preimages by their hash, and manages their lifetime by requesting them.
+pub trait QueryPreimage {
+	/// The length of the preimage of `hash`, if it is available and its length is known.
+	fn len(hash: &Hash) -> Option<u32>;
+
+	/// Fetches the preimage of `hash`, whose length is `len` if it is known.
+	fn fetch(hash: &Hash, len: Option<u32>) -> FetchResult;
+
+	/// Whether the preimage of `hash` has been requested.
+	fn is_requested(hash: &Hash) -> bool;
+
+	/// Requests the preimage of `hash`, so that it is kept until it is unrequested.
+	///
+	/// Requests are counted: a preimage is only released once each request is withdrawn.
+	fn request(hash: &Hash);
+
+	/// Withdraws a request of the preimage of `hash`.
+	fn unrequest(hash: &Hash);
+
+	/// Keeps the preimage of `bounded`, if any, until it is dropped with [`Self::drop`].
+	fn hold<T>(bounded: &Bounded<T>) {
+		if let Some(hash) = bounded.lookup_hash() {
+			Self::request(&hash)
+		}
+	}
+
+	/// Releases the preimage of `bounded`, if any, kept by [`Self::hold`] or [`Self::pick`].
+	fn drop<T>(bounded: &Bounded<T>) {
+		if let Some(hash) = bounded.lookup_hash() {
+			Self::unrequest(&hash)
+		}
+	}
+
+	/// Whether the value of `bounded` is available, i.e. if it is inline or has its preimage.
+	fn have<T>(bounded: &Bounded<T>) -> bool {
+		match bounded.lookup_hash() {
+			Some(hash) => Self::len(&hash).is_some(),
+			None => true,
+		}
+	}
+
+	/// Creates a `Bounded` referencing the preimage of `hash`, which is requested.
+	///
+	/// The preimage does not have to be available yet.
+	fn pick<T>(hash: Hash, len: u32) -> Bounded<T> {
+		Self::request(&hash);
+		Bounded::Lookup { hash, len }
+	}
+
+	/// Decodes the value of `bounded`, along with the length of its encoding if it was looked
+	/// up.
+	///
+	/// The preimage of the value, if any, is kept.
+	fn peek<T: Decode>(bounded: &Bounded<T>) -> Result<(T, Option<u32>), DispatchError> {
+		use Bounded::*;
+		match bounded {
+			Inline(data) => T::decode(&mut &data[..]).ok().map(|value| (value, None)),
+			Lookup { hash, len } => {
+				let data = Self::fetch(hash, Some(*len))?;
+				T::decode(&mut &data[..]).ok().map(|value| (value, Some(data.len() as u32)))
+			},
+			Legacy { hash, .. } => {
+				let data = Self::fetch(hash, None)?;
+				T::decode(&mut &data[..]).ok().map(|value| (value, Some(data.len() as u32)))
+			},
+		}
+		.ok_or(DispatchError::CannotLookup)
+	}
+
+	/// Decodes the value of `bounded` like [`Self::peek`], releasing its preimage.
+	fn realize<T: Decode>(bounded: &Bounded<T>) -> Result<(T, Option<u32>), DispatchError> {
+		let value = Self::peek(bounded)?;
+		Self::drop(bounded);
+		Ok(value)
+	}
+}
+
+/// Stores preimages.
+pub trait StorePreimage: QueryPreimage {
+	/// The maximal length of a preimage which can be stored.
+	const MAX_LENGTH: usize;
+
+	/// Stores `bytes` as a preimage, and returns its hash.
+	///
+	/// The preimage is kept until [`Self::unnote`] is called.
+	fn note(bytes: Cow<[u8]>) -> Result<Hash, DispatchError>;
+
+	/// Releases the preimage of `hash` stored with [`Self::note`].
+	fn unnote(hash: &Hash) {
+		Self::unrequest(hash)
+	}
+
+	/// Bounds `value`, storing its encoding as a preimage if it is too large to be held inline.
+	fn bound<T: Encode>(value: T) -> Result<Bounded<T>, DispatchError> {
+		let data = value.encode();
+		let len = data.len() as u32;
+		Ok(match BoundedInline::try_from(data) {
+			Ok(data) => Bounded::Inline(data),
+			Err(data) => Bounded::Lookup { hash: Self::note(data.into())?, len },
+		})
+	}
+}
+
+impl QueryPreimage for () {
+	fn len(_: &Hash) -> Option<u32> {
+		None
+	}
+
+	fn fetch(_: &Hash, _: Option<u32>) -> FetchResult {
+		Err(DispatchError::CannotLookup)
+	}
+
+	fn is_requested(_: &Hash) -> bool {
+		false
+	}
+
+	fn request(_: &Hash) {}
+
+	fn unrequest(_: &Hash) {}
+}
+
+impl StorePreimage for () {
+	const MAX_LENGTH: usize = 0;
+
+	fn note(_: Cow<[u8]>) -> Result<Hash, DispatchError> {
+		Err(DispatchError::Other("Preimages are not stored"))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_std::convert::TryInto;
+
+	#[test]
+	fn bounded_size_is_correct() {
+		assert_eq!(<Bounded<Vec<u8>> as MaxEncodedLen>::max_encoded_len(), 131);
+	}
+
+	#[test]
+	fn bounded_basic_works() {
+		let data: BoundedInline = vec![0; 3].try_into().unwrap();
+		let len = data.len() as u32;
+		let h = BlakeTwo256::hash(&data[..]);
+
+		let inline = Bounded::<Vec<u8>>::Inline(data);
+		assert_eq!(inline.hash(), h);
+		assert_eq!((inline.len(), inline.lookup_len()), (Some(len), None));
+		assert!(!inline.lookup_needed());
+
+		let lookup = Bounded::<Vec<u8>>::Lookup { hash: h, len };
+		assert_eq!(lookup.hash(), h);
+		assert_eq!((lookup.len(), lookup.lookup_len()), (Some(len), Some(len)));
+		assert!(lookup.lookup_needed());
+
+		let legacy = Bounded::<Vec<u8>>::Legacy { hash: h, dummy: Default::default() };
+		assert_eq!(legacy.hash(), h);
+		assert_eq!((legacy.len(), legacy.lookup_len()), (None, None));
+		assert!(legacy.lookup_needed());
+	}
+
+	#[test]
+	fn bounded_transmuting_works() {
+		let data: BoundedInline = vec![0; 3].try_into().unwrap();
+		let x = Bounded::<Vec<u8>>::Inline(data.clone());
+		let y: Bounded<&[u8]> = x.transmute();
+		assert_eq!(y, Bounded::Inline(data));
+	}
+
+	#[test]
+	fn small_values_are_held_inline() {
+		// `()` stores no preimage, so only the values held inline can be bounded.
+		let small = vec![1u8; MAX_INLINE_LEN as usize - 2];
+		let bounded = <() as StorePreimage>::bound(small.clone()).unwrap();
+		assert!(!bounded.lookup_needed());
+		assert!(<() as QueryPreimage>::have(&bounded));
+		assert_eq!(<() as QueryPreimage>::realize(&bounded), Ok((small, None)));
+
+		let large = vec![1u8; MAX_INLINE_LEN as usize];
+		assert!(<() as StorePreimage>::bound(large).is_err());
+	}
+}
@@ -74,3 +74,11 @@ impl StorageInfoTrait for Tuple {
 		res
 	}
 }
+
+/// Similar to [`StorageInfoTrait`], a trait to give partial information about storage.
+///
+/// It is implemented by storages whose key or value may not implement `MaxEncodedLen`, in which
+/// case the maximum size is unknown.
+pub trait PartialStorageInfoTrait {
+	fn partial_storage_info() -> Vec<StorageInfo>;
+}
@@ -0,0 +1,57 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Traits for the tasks of a pallet, i.e. units of work which anyone can submit.
+
+use sp_std::fmt::Debug;
+use codec::FullCodec;
+use sp_runtime::DispatchResult;
+use crate::weights::Weight;
+
+/// A unit of work of a pallet, which anyone can submit as long as it is valid.
+///
+/// It is usually generated by `#[pallet::tasks]`, and submitted through the `do_task` call of
+/// the pallet.
+pub trait Task: Sized + FullCodec + Clone + Debug + PartialEq + Eq {
+	/// An iterator over tasks.
+	type Enumeration: Iterator<Item = Self>;
+
+	/// All the tasks which may currently be valid.
+	///
+	/// The tasks are not required to be valid, only the valid ones have to be listed. They are
+	/// produced as the iterator is advanced, such that only the tasks needed are read from storage.
+	fn iter() -> Self::Enumeration;
+
+	/// Whether the task can be run.
+	fn is_valid(&self) -> bool;
+
+	/// Run the task.
+	///
+	/// It is only meant to be called on a valid task.
+	fn run(&self) -> DispatchResult;
+
+	/// The weight of running the task.
+	fn weight(&self) -> Weight;
+
+	/// The index of the task among the tasks of the pallet.
+	fn task_index(&self) -> u32;
+
+	/// The tasks which are valid.
+	fn valid_tasks() -> sp_std::iter::Filter<Self::Enumeration, fn(&Self) -> bool> {
+		Self::iter().filter(Self::is_valid as fn(&Self) -> bool)
+	}
+}
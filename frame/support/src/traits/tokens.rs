@@ -19,6 +19,7 @@
 
 pub mod fungible;
 pub mod fungibles;
+pub mod nonfungibles;
 pub mod currency;
 pub mod imbalance;
 mod misc;
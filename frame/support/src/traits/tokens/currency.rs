@@ -28,6 +28,8 @@ mod reservable;
 pub use reservable::ReservableCurrency;
 mod lockable;
 pub use lockable::{LockableCurrency, VestingSchedule, LockIdentifier};
+mod adapter;
+pub use adapter::FungibleAdapter;
 
 /// Abstraction over a fungible assets system.
 pub trait Currency<AccountId> {
@@ -0,0 +1,238 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An adapter implementing the `Currency` traits on top of the `fungible` traits.
+
+use sp_std::marker::PhantomData;
+use sp_runtime::{TokenError, traits::{MaybeSerializeDeserialize, Saturating, Zero}};
+use crate::dispatch::{DispatchResult, DispatchError};
+use crate::traits::misc::Get;
+use super::{Currency, ReservableCurrency, LockableCurrency, LockIdentifier};
+use super::super::{
+	fungible,
+	imbalance::SignedImbalance,
+	misc::{BalanceStatus, ExistenceRequirement, WithdrawReasons},
+};
+
+/// Logs the failure of a lock operation, which the legacy traits can't report, e.g. because the
+/// account has too many freezes.
+fn note_lock_failure(operation: &str, id: LockIdentifier, result: DispatchResult) {
+	if let Err(e) = result {
+		crate::log::warn!(
+			target: crate::LOG_TARGET,
+			"Failed to {} lock {:?}: {:?}",
+			operation,
+			id,
+			e,
+		);
+	}
+}
+
+/// Implements the legacy [`Currency`], [`ReservableCurrency`] and [`LockableCurrency`] traits for
+/// a fungible token class `F` implementing the `fungible` traits.
+///
+/// This lets a runtime move its native token to the `fungible` traits, with holds and freezes,
+/// while the pallets still using the legacy traits are migrated one by one. The legacy traits
+/// are meant to be removed once no pallet uses them anymore.
+///
+/// The correspondence is:
+/// * the free balance is the balance of `F` which is not on hold for any reason, and reserves are
+///   holds for the reason `HoldReason`;
+/// * a lock is a freeze of the same identifier. Freezes restrict every withdrawal, so the
+///   `WithdrawReasons` of a lock are ignored.
+///
+/// `Moment` and `MaxFreezes` are the `Moment` and `MaxLocks` of [`LockableCurrency`].
+pub struct FungibleAdapter<F, HoldReason = (), Moment = (), MaxFreezes = ()>(
+	PhantomData<(F, HoldReason, Moment, MaxFreezes)>
+);
+
+impl<AccountId, F, HoldReason, Moment, MaxFreezes> Currency<AccountId>
+	for FungibleAdapter<F, HoldReason, Moment, MaxFreezes>
+where
+	F: fungible::Balanced<AccountId> + fungible::Transfer<AccountId>
+		+ fungible::InspectHold<AccountId>,
+	F::Balance: MaybeSerializeDeserialize,
+{
+	type Balance = F::Balance;
+	type PositiveImbalance = fungible::DebtOf<AccountId, F>;
+	type NegativeImbalance = fungible::CreditOf<AccountId, F>;
+
+	fn total_balance(who: &AccountId) -> Self::Balance {
+		F::balance(who)
+	}
+
+	fn can_slash(who: &AccountId, value: Self::Balance) -> bool {
+		Self::free_balance(who) >= value
+	}
+
+	fn total_issuance() -> Self::Balance {
+		F::total_issuance()
+	}
+
+	fn minimum_balance() -> Self::Balance {
+		F::minimum_balance()
+	}
+
+	fn burn(amount: Self::Balance) -> Self::PositiveImbalance {
+		F::rescind(amount)
+	}
+
+	fn issue(amount: Self::Balance) -> Self::NegativeImbalance {
+		F::issue(amount)
+	}
+
+	fn free_balance(who: &AccountId) -> Self::Balance {
+		F::balance(who).saturating_sub(F::total_balance_on_hold(who))
+	}
+
+	fn ensure_can_withdraw(
+		who: &AccountId,
+		amount: Self::Balance,
+		_reasons: WithdrawReasons,
+		_new_balance: Self::Balance,
+	) -> DispatchResult {
+		F::can_withdraw(who, amount).into_result().map(|_| ())
+	}
+
+	fn transfer(
+		source: &AccountId,
+		dest: &AccountId,
+		value: Self::Balance,
+		existence_requirement: ExistenceRequirement,
+	) -> DispatchResult {
+		let keep_alive = existence_requirement == ExistenceRequirement::KeepAlive;
+		F::transfer(source, dest, value, keep_alive).map(|_| ())
+	}
+
+	fn slash(who: &AccountId, value: Self::Balance) -> (Self::NegativeImbalance, Self::Balance) {
+		F::slash(who, value)
+	}
+
+	fn deposit_into_existing(
+		who: &AccountId,
+		value: Self::Balance,
+	) -> Result<Self::PositiveImbalance, DispatchError> {
+		if F::balance(who).is_zero() {
+			return Err(TokenError::CannotCreate.into());
+		}
+		F::deposit(who, value)
+	}
+
+	fn deposit_creating(who: &AccountId, value: Self::Balance) -> Self::PositiveImbalance {
+		F::deposit(who, value).unwrap_or_default()
+	}
+
+	fn withdraw(
+		who: &AccountId,
+		value: Self::Balance,
+		_reasons: WithdrawReasons,
+		liveness: ExistenceRequirement,
+	) -> Result<Self::NegativeImbalance, DispatchError> {
+		let keep_alive = liveness == ExistenceRequirement::KeepAlive;
+		if F::reducible_balance(who, keep_alive) < value {
+			F::can_withdraw(who, value).into_result()?;
+			let error = if keep_alive { TokenError::WouldDie } else { TokenError::NoFunds };
+			return Err(error.into());
+		}
+		F::withdraw(who, value)
+	}
+
+	fn make_free_balance_be(
+		who: &AccountId,
+		balance: Self::Balance,
+	) -> SignedImbalance<Self::Balance, Self::PositiveImbalance> {
+		let free = Self::free_balance(who);
+		if balance >= free {
+			SignedImbalance::Positive(Self::deposit_creating(who, balance - free))
+		} else {
+			SignedImbalance::Negative(F::slash(who, free - balance).0)
+		}
+	}
+}
+
+impl<AccountId, F, HoldReason, Moment, MaxFreezes> ReservableCurrency<AccountId>
+	for FungibleAdapter<F, HoldReason, Moment, MaxFreezes>
+where
+	F: fungible::Balanced<AccountId> + fungible::MutateHold<AccountId>,
+	F::Balance: MaybeSerializeDeserialize,
+	HoldReason: Get<F::Reason>,
+{
+	fn can_reserve(who: &AccountId, value: Self::Balance) -> bool {
+		F::can_hold(&HoldReason::get(), who, value)
+	}
+
+	fn slash_reserved(
+		who: &AccountId,
+		value: Self::Balance,
+	) -> (Self::NegativeImbalance, Self::Balance) {
+		<F as fungible::BalancedHold<AccountId>>::slash_held(&HoldReason::get(), who, value)
+	}
+
+	fn reserved_balance(who: &AccountId) -> Self::Balance {
+		F::balance_on_hold(&HoldReason::get(), who)
+	}
+
+	fn reserve(who: &AccountId, value: Self::Balance) -> DispatchResult {
+		F::hold(&HoldReason::get(), who, value)
+	}
+
+	fn unreserve(who: &AccountId, value: Self::Balance) -> Self::Balance {
+		let released = F::release(&HoldReason::get(), who, value, true).unwrap_or_default();
+		value.saturating_sub(released)
+	}
+
+	fn repatriate_reserved(
+		slashed: &AccountId,
+		beneficiary: &AccountId,
+		value: Self::Balance,
+		status: BalanceStatus,
+	) -> Result<Self::Balance, DispatchError> {
+		let on_hold = status == BalanceStatus::Reserved;
+		F::transfer_held(&HoldReason::get(), slashed, beneficiary, value, true, on_hold)
+			.map(|actual| value.saturating_sub(actual))
+	}
+}
+
+impl<AccountId, F, HoldReason, Moment, MaxFreezes> LockableCurrency<AccountId>
+	for FungibleAdapter<F, HoldReason, Moment, MaxFreezes>
+where
+	F: fungible::Balanced<AccountId> + fungible::Transfer<AccountId>
+		+ fungible::InspectHold<AccountId>
+		+ fungible::MutateFreeze<AccountId, Id = LockIdentifier>,
+	F::Balance: MaybeSerializeDeserialize,
+	MaxFreezes: Get<u32>,
+{
+	type Moment = Moment;
+	type MaxLocks = MaxFreezes;
+
+	fn set_lock(id: LockIdentifier, who: &AccountId, amount: Self::Balance, _: WithdrawReasons) {
+		let result = if amount.is_zero() {
+			F::thaw(&id, who)
+		} else {
+			F::set_freeze(&id, who, amount)
+		};
+		note_lock_failure("set", id, result);
+	}
+
+	fn extend_lock(id: LockIdentifier, who: &AccountId, amount: Self::Balance, _: WithdrawReasons) {
+		note_lock_failure("extend", id, F::extend_freeze(&id, who, amount));
+	}
+
+	fn remove_lock(id: LockIdentifier, who: &AccountId) {
+		note_lock_failure("remove", id, F::thaw(&id, who));
+	}
+}
@@ -18,9 +18,9 @@
 //! The traits for dealing with a single fungible token class and any associated types.
 
 use super::*;
-use sp_runtime::traits::Saturating;
+use sp_runtime::traits::{Saturating, Zero};
 use crate::traits::misc::Get;
-use crate::dispatch::{DispatchResult, DispatchError};
+use crate::dispatch::{DispatchResult, DispatchError, Parameter};
 use super::misc::{DepositConsequence, WithdrawConsequence, Balance};
 
 mod balanced;
@@ -109,34 +109,43 @@ pub trait Transfer<AccountId>: Inspect<AccountId> {
 	) -> Result<Self::Balance, DispatchError>;
 }
 
-/// Trait for inspecting a fungible asset which can be reserved.
+/// Trait for inspecting a fungible asset which can be placed on hold.
+///
+/// Holds are identified by a `Reason` and add up: the amount on hold on an account is the sum of
+/// its holds.
 pub trait InspectHold<AccountId>: Inspect<AccountId> {
-	/// Amount of funds held in reserve by `who`.
-	fn balance_on_hold(who: &AccountId) -> Self::Balance;
+	/// The reason for which funds are held, e.g. the pallet holding them.
+	type Reason: Parameter;
 
-	/// Check to see if some `amount` of funds of `who` may be placed on hold.
-	fn can_hold(who: &AccountId, amount: Self::Balance) -> bool;
+	/// Amount of funds of `who` held for `reason`.
+	fn balance_on_hold(reason: &Self::Reason, who: &AccountId) -> Self::Balance;
+
+	/// Amount of funds of `who` held for any reason.
+	fn total_balance_on_hold(who: &AccountId) -> Self::Balance;
+
+	/// Check to see if some `amount` of funds of `who` may be placed on hold for `reason`.
+	fn can_hold(reason: &Self::Reason, who: &AccountId, amount: Self::Balance) -> bool;
 }
 
-/// Trait for mutating a fungible asset which can be reserved.
+/// Trait for mutating a fungible asset which can be placed on hold.
 pub trait MutateHold<AccountId>: InspectHold<AccountId> + Transfer<AccountId> {
-	/// Hold some funds in an account.
-	fn hold(who: &AccountId, amount: Self::Balance) -> DispatchResult;
+	/// Hold some funds in an account for `reason`.
+	fn hold(reason: &Self::Reason, who: &AccountId, amount: Self::Balance) -> DispatchResult;
 
-	/// Release up to `amount` held funds in an account.
+	/// Release up to `amount` funds held for `reason` in an account.
 	///
 	/// The actual amount released is returned with `Ok`.
 	///
 	/// If `best_effort` is `true`, then the amount actually unreserved and returned as the inner
 	/// value of `Ok` may be smaller than the `amount` passed.
-	fn release(who: &AccountId, amount: Self::Balance, best_effort: bool)
+	fn release(reason: &Self::Reason, who: &AccountId, amount: Self::Balance, best_effort: bool)
 		-> Result<Self::Balance, DispatchError>;
 
-	/// Transfer held funds into a destination account.
+	/// Transfer funds held for `reason` into a destination account.
 	///
 	/// If `on_hold` is `true`, then the destination account must already exist and the assets
-	/// transferred will still be on hold in the destination account. If not, then the destination
-	/// account need not already exist, but must be creatable.
+	/// transferred will still be on hold for `reason` in the destination account. If not, then the
+	/// destination account need not already exist, but must be creatable.
 	///
 	/// If `best_effort` is `true`, then an amount less than `amount` may be transferred without
 	/// error.
@@ -144,6 +153,7 @@ pub trait MutateHold<AccountId>: InspectHold<AccountId> + Transfer<AccountId> {
 	/// The actual amount transferred is returned, or `Err` in the case of error and nothing is
 	/// changed.
 	fn transfer_held(
+		reason: &Self::Reason,
 		source: &AccountId,
 		dest: &AccountId,
 		amount: Self::Balance,
@@ -152,15 +162,15 @@ pub trait MutateHold<AccountId>: InspectHold<AccountId> + Transfer<AccountId> {
 	) -> Result<Self::Balance, DispatchError>;
 }
 
-/// Trait for slashing a fungible asset which can be reserved.
+/// Trait for slashing a fungible asset which can be placed on hold.
 pub trait BalancedHold<AccountId>: Balanced<AccountId> + MutateHold<AccountId> {
-	/// Reduce the balance of some funds on hold in an account.
+	/// Reduce the balance of some funds held for `reason` in an account.
 	///
 	/// The resulting imbalance is the first item of the tuple returned.
 	///
 	/// As much funds that are on hold up to `amount` will be deducted as possible. If this is less
 	/// than `amount`, then a non-zero second item will be returned.
-	fn slash_held(who: &AccountId, amount: Self::Balance)
+	fn slash_held(reason: &Self::Reason, who: &AccountId, amount: Self::Balance)
 		-> (CreditOf<AccountId, Self>, Self::Balance);
 }
 
@@ -168,10 +178,10 @@ impl<
 	AccountId,
 	T: Balanced<AccountId> + MutateHold<AccountId>,
 > BalancedHold<AccountId> for T {
-	fn slash_held(who: &AccountId, amount: Self::Balance)
+	fn slash_held(reason: &Self::Reason, who: &AccountId, amount: Self::Balance)
 		-> (CreditOf<AccountId, Self>, Self::Balance)
 	{
-		let actual = match Self::release(who, amount, true) {
+		let actual = match Self::release(reason, who, amount, true) {
 			Ok(x) => x,
 			Err(_) => return (Imbalance::default(), amount),
 		};
@@ -179,6 +189,59 @@ impl<
 	}
 }
 
+/// Trait for inspecting a fungible asset which can be frozen.
+///
+/// Unlike held funds, frozen funds remain part of the balance of the account; they only can't be
+/// withdrawn or transferred. Freezes are identified by an `Id` and overlap: the amount frozen on
+/// an account is the largest amount of its freezes, not their sum.
+pub trait InspectFreeze<AccountId>: Inspect<AccountId> {
+	/// The identifier of a freeze, e.g. the reason for it.
+	type Id: Parameter;
+
+	/// Amount of funds of `who` frozen by the freeze `id`.
+	fn balance_frozen(id: &Self::Id, who: &AccountId) -> Self::Balance;
+
+	/// Amount of funds of `who` which can be frozen, including the funds already frozen.
+	fn balance_freezable(who: &AccountId) -> Self::Balance {
+		Self::balance(who)
+	}
+
+	/// Check to see if the freeze `id` may be placed on the account of `who`, e.g. it may not if
+	/// the account has too many freezes already.
+	fn can_freeze(id: &Self::Id, who: &AccountId) -> bool;
+}
+
+/// Trait for mutating a fungible asset which can be frozen.
+pub trait MutateFreeze<AccountId>: InspectFreeze<AccountId> {
+	/// Set the freeze `id` on the account of `who` to `amount`, replacing its previous amount.
+	///
+	/// The amount may be more than the balance of `who`, in which case all of it is frozen.
+	fn set_freeze(id: &Self::Id, who: &AccountId, amount: Self::Balance) -> DispatchResult;
+
+	/// Remove the freeze `id` from the account of `who`.
+	fn thaw(id: &Self::Id, who: &AccountId) -> DispatchResult;
+
+	/// Set the freeze `id` on the account of `who` to `amount`, unless it is larger already.
+	fn extend_freeze(id: &Self::Id, who: &AccountId, amount: Self::Balance) -> DispatchResult {
+		Self::set_freeze(id, who, Self::balance_frozen(id, who).max(amount))
+	}
+
+	/// Increase the freeze `id` on the account of `who` by `amount`.
+	fn increase_frozen(id: &Self::Id, who: &AccountId, amount: Self::Balance) -> DispatchResult {
+		Self::set_freeze(id, who, Self::balance_frozen(id, who).saturating_add(amount))
+	}
+
+	/// Decrease the freeze `id` on the account of `who` by `amount`, removing it once it is zero.
+	fn decrease_frozen(id: &Self::Id, who: &AccountId, amount: Self::Balance) -> DispatchResult {
+		let frozen = Self::balance_frozen(id, who).saturating_sub(amount);
+		if frozen.is_zero() {
+			Self::thaw(id, who)
+		} else {
+			Self::set_freeze(id, who, frozen)
+		}
+	}
+}
+
 /// Convert a `fungibles` trait implementation into a `fungible` trait implementation by identifying
 /// a single item.
 pub struct ItemOf<
@@ -245,11 +308,15 @@ impl<
 	A: Get<<F as fungibles::Inspect<AccountId>>::AssetId>,
 	AccountId,
 > InspectHold<AccountId> for ItemOf<F, A, AccountId> {
-	fn balance_on_hold(who: &AccountId) -> Self::Balance {
-		<F as fungibles::InspectHold<AccountId>>::balance_on_hold(A::get(), who)
+	type Reason = <F as fungibles::InspectHold<AccountId>>::Reason;
+	fn balance_on_hold(reason: &Self::Reason, who: &AccountId) -> Self::Balance {
+		<F as fungibles::InspectHold<AccountId>>::balance_on_hold(A::get(), reason, who)
 	}
-	fn can_hold(who: &AccountId, amount: Self::Balance) -> bool {
-		<F as fungibles::InspectHold<AccountId>>::can_hold(A::get(), who, amount)
+	fn total_balance_on_hold(who: &AccountId) -> Self::Balance {
+		<F as fungibles::InspectHold<AccountId>>::total_balance_on_hold(A::get(), who)
+	}
+	fn can_hold(reason: &Self::Reason, who: &AccountId, amount: Self::Balance) -> bool {
+		<F as fungibles::InspectHold<AccountId>>::can_hold(A::get(), reason, who, amount)
 	}
 }
 
@@ -258,15 +325,16 @@ impl<
 	A: Get<<F as fungibles::Inspect<AccountId>>::AssetId>,
 	AccountId,
 > MutateHold<AccountId> for ItemOf<F, A, AccountId> {
-	fn hold(who: &AccountId, amount: Self::Balance) -> DispatchResult {
-		<F as fungibles::MutateHold<AccountId>>::hold(A::get(), who, amount)
+	fn hold(reason: &Self::Reason, who: &AccountId, amount: Self::Balance) -> DispatchResult {
+		<F as fungibles::MutateHold<AccountId>>::hold(A::get(), reason, who, amount)
 	}
-	fn release(who: &AccountId, amount: Self::Balance, best_effort: bool)
+	fn release(reason: &Self::Reason, who: &AccountId, amount: Self::Balance, best_effort: bool)
 		-> Result<Self::Balance, DispatchError>
 	{
-		<F as fungibles::MutateHold<AccountId>>::release(A::get(), who, amount, best_effort)
+		<F as fungibles::MutateHold<AccountId>>::release(A::get(), reason, who, amount, best_effort)
 	}
 	fn transfer_held(
+		reason: &Self::Reason,
 		source: &AccountId,
 		dest: &AccountId,
 		amount: Self::Balance,
@@ -275,6 +343,7 @@ impl<
 	) -> Result<Self::Balance, DispatchError> {
 		<F as fungibles::MutateHold<AccountId>>::transfer_held(
 			A::get(),
+			reason,
 			source,
 			dest,
 			amount,
@@ -284,6 +353,45 @@ impl<
 	}
 }
 
+impl<
+	F: fungibles::InspectFreeze<AccountId>,
+	A: Get<<F as fungibles::Inspect<AccountId>>::AssetId>,
+	AccountId,
+> InspectFreeze<AccountId> for ItemOf<F, A, AccountId> {
+	type Id = <F as fungibles::InspectFreeze<AccountId>>::Id;
+	fn balance_frozen(id: &Self::Id, who: &AccountId) -> Self::Balance {
+		<F as fungibles::InspectFreeze<AccountId>>::balance_frozen(A::get(), id, who)
+	}
+	fn balance_freezable(who: &AccountId) -> Self::Balance {
+		<F as fungibles::InspectFreeze<AccountId>>::balance_freezable(A::get(), who)
+	}
+	fn can_freeze(id: &Self::Id, who: &AccountId) -> bool {
+		<F as fungibles::InspectFreeze<AccountId>>::can_freeze(A::get(), id, who)
+	}
+}
+
+impl<
+	F: fungibles::MutateFreeze<AccountId>,
+	A: Get<<F as fungibles::Inspect<AccountId>>::AssetId>,
+	AccountId,
+> MutateFreeze<AccountId> for ItemOf<F, A, AccountId> {
+	fn set_freeze(id: &Self::Id, who: &AccountId, amount: Self::Balance) -> DispatchResult {
+		<F as fungibles::MutateFreeze<AccountId>>::set_freeze(A::get(), id, who, amount)
+	}
+	fn thaw(id: &Self::Id, who: &AccountId) -> DispatchResult {
+		<F as fungibles::MutateFreeze<AccountId>>::thaw(A::get(), id, who)
+	}
+	fn extend_freeze(id: &Self::Id, who: &AccountId, amount: Self::Balance) -> DispatchResult {
+		<F as fungibles::MutateFreeze<AccountId>>::extend_freeze(A::get(), id, who, amount)
+	}
+	fn increase_frozen(id: &Self::Id, who: &AccountId, amount: Self::Balance) -> DispatchResult {
+		<F as fungibles::MutateFreeze<AccountId>>::increase_frozen(A::get(), id, who, amount)
+	}
+	fn decrease_frozen(id: &Self::Id, who: &AccountId, amount: Self::Balance) -> DispatchResult {
+		<F as fungibles::MutateFreeze<AccountId>>::decrease_frozen(A::get(), id, who, amount)
+	}
+}
+
 impl<
 	F: fungibles::Unbalanced<AccountId>,
 	A: Get<<F as fungibles::Inspect<AccountId>>::AssetId>,
@@ -18,9 +18,9 @@
 //! The traits for sets of fungible tokens and any associated types.
 
 use super::*;
-use crate::dispatch::{DispatchError, DispatchResult};
+use crate::dispatch::{DispatchError, DispatchResult, Parameter};
 use super::misc::{AssetId, Balance};
-use sp_runtime::traits::Saturating;
+use sp_runtime::traits::{Saturating, Zero};
 
 mod balanced;
 pub use balanced::{Balanced, Unbalanced};
@@ -141,31 +141,52 @@ pub trait Transfer<AccountId>: Inspect<AccountId> {
 }
 
 /// Trait for inspecting a set of named fungible assets which can be placed on hold.
+///
+/// Holds are identified by a `Reason` and add up: the amount of an asset on hold on an account is
+/// the sum of its holds.
 pub trait InspectHold<AccountId>: Inspect<AccountId> {
-	/// Amount of funds held in hold.
-	fn balance_on_hold(asset: Self::AssetId, who: &AccountId) -> Self::Balance;
+	/// The reason for which funds are held, e.g. the pallet holding them.
+	type Reason: Parameter;
 
-	/// Check to see if some `amount` of `asset` may be held on the account of `who`.
-	fn can_hold(asset: Self::AssetId, who: &AccountId, amount: Self::Balance) -> bool;
+	/// Amount of funds of `asset` of `who` held for `reason`.
+	fn balance_on_hold(asset: Self::AssetId, reason: &Self::Reason, who: &AccountId)
+		-> Self::Balance;
+
+	/// Amount of funds of `asset` of `who` held for any reason.
+	fn total_balance_on_hold(asset: Self::AssetId, who: &AccountId) -> Self::Balance;
+
+	/// Check to see if some `amount` of `asset` may be held on the account of `who` for `reason`.
+	fn can_hold(
+		asset: Self::AssetId,
+		reason: &Self::Reason,
+		who: &AccountId,
+		amount: Self::Balance,
+	) -> bool;
 }
 
 /// Trait for mutating a set of named fungible assets which can be placed on hold.
 pub trait MutateHold<AccountId>: InspectHold<AccountId> + Transfer<AccountId> {
-	/// Hold some funds in an account.
-	fn hold(asset: Self::AssetId, who: &AccountId, amount: Self::Balance) -> DispatchResult;
+	/// Hold some funds in an account for `reason`.
+	fn hold(asset: Self::AssetId, reason: &Self::Reason, who: &AccountId, amount: Self::Balance)
+		-> DispatchResult;
 
-	/// Release some funds in an account from being on hold.
+	/// Release some funds held for `reason` in an account from being on hold.
 	///
 	/// If `best_effort` is `true`, then the amount actually released and returned as the inner
 	/// value of `Ok` may be smaller than the `amount` passed.
-	fn release(asset: Self::AssetId, who: &AccountId, amount: Self::Balance, best_effort: bool)
-		-> Result<Self::Balance, DispatchError>;
+	fn release(
+		asset: Self::AssetId,
+		reason: &Self::Reason,
+		who: &AccountId,
+		amount: Self::Balance,
+		best_effort: bool,
+	) -> Result<Self::Balance, DispatchError>;
 
-	/// Transfer held funds into a destination account.
+	/// Transfer funds held for `reason` into a destination account.
 	///
 	/// If `on_hold` is `true`, then the destination account must already exist and the assets
-	/// transferred will still be on hold in the destination account. If not, then the destination
-	/// account need not already exist, but must be creatable.
+	/// transferred will still be on hold for `reason` in the destination account. If not, then the
+	/// destination account need not already exist, but must be creatable.
 	///
 	/// If `best_effort` is `true`, then an amount less than `amount` may be transferred without
 	/// error.
@@ -174,6 +195,7 @@ pub trait MutateHold<AccountId>: InspectHold<AccountId> + Transfer<AccountId> {
 	/// changed.
 	fn transfer_held(
 		asset: Self::AssetId,
+		reason: &Self::Reason,
 		source: &AccountId,
 		dest: &AccountId,
 		amount: Self::Balance,
@@ -184,27 +206,100 @@ pub trait MutateHold<AccountId>: InspectHold<AccountId> + Transfer<AccountId> {
 
 /// Trait for mutating one of several types of fungible assets which can be held.
 pub trait BalancedHold<AccountId>: Balanced<AccountId> + MutateHold<AccountId> {
-	/// Release and slash some funds in an account.
+	/// Release and slash some funds held for `reason` in an account.
 	///
 	/// The resulting imbalance is the first item of the tuple returned.
 	///
 	/// As much funds up to `amount` will be deducted as possible. If this is less than `amount`,
 	/// then a non-zero second item will be returned.
-	fn slash_held(asset: Self::AssetId, who: &AccountId, amount: Self::Balance)
-		-> (CreditOf<AccountId, Self>, Self::Balance);
+	fn slash_held(
+		asset: Self::AssetId,
+		reason: &Self::Reason,
+		who: &AccountId,
+		amount: Self::Balance,
+	) -> (CreditOf<AccountId, Self>, Self::Balance);
 }
 
 impl<
 	AccountId,
 	T: Balanced<AccountId> + MutateHold<AccountId>,
 > BalancedHold<AccountId> for T {
-	fn slash_held(asset: Self::AssetId, who: &AccountId, amount: Self::Balance)
-		-> (CreditOf<AccountId, Self>, Self::Balance)
-	{
-		let actual = match Self::release(asset, who, amount, true) {
+	fn slash_held(
+		asset: Self::AssetId,
+		reason: &Self::Reason,
+		who: &AccountId,
+		amount: Self::Balance,
+	) -> (CreditOf<AccountId, Self>, Self::Balance) {
+		let actual = match Self::release(asset, reason, who, amount, true) {
 			Ok(x) => x,
 			Err(_) => return (Imbalance::zero(asset), amount),
 		};
 		<Self as fungibles::Balanced<AccountId>>::slash(asset, who, actual)
 	}
 }
+
+/// Trait for inspecting a set of named fungible assets which can be frozen.
+///
+/// Unlike held funds, frozen funds remain part of the balance of the account; they only can't be
+/// withdrawn or transferred. Freezes are identified by an `Id` and overlap: the amount frozen on
+/// an account is the largest amount of its freezes, not their sum.
+pub trait InspectFreeze<AccountId>: Inspect<AccountId> {
+	/// The identifier of a freeze, e.g. the reason for it.
+	type Id: Parameter;
+
+	/// Amount of funds of `asset` of `who` frozen by the freeze `id`.
+	fn balance_frozen(asset: Self::AssetId, id: &Self::Id, who: &AccountId) -> Self::Balance;
+
+	/// Amount of funds of `asset` of `who` which can be frozen, including the funds already
+	/// frozen.
+	fn balance_freezable(asset: Self::AssetId, who: &AccountId) -> Self::Balance {
+		Self::balance(asset, who)
+	}
+
+	/// Check to see if the freeze `id` of `asset` may be placed on the account of `who`, e.g. it
+	/// may not if the account has too many freezes already.
+	fn can_freeze(asset: Self::AssetId, id: &Self::Id, who: &AccountId) -> bool;
+}
+
+/// Trait for mutating a set of named fungible assets which can be frozen.
+pub trait MutateFreeze<AccountId>: InspectFreeze<AccountId> {
+	/// Set the freeze `id` of `asset` on the account of `who` to `amount`, replacing its previous
+	/// amount.
+	///
+	/// The amount may be more than the balance of `who`, in which case all of it is frozen.
+	fn set_freeze(asset: Self::AssetId, id: &Self::Id, who: &AccountId, amount: Self::Balance)
+		-> DispatchResult;
+
+	/// Remove the freeze `id` of `asset` from the account of `who`.
+	fn thaw(asset: Self::AssetId, id: &Self::Id, who: &AccountId) -> DispatchResult;
+
+	/// Set the freeze `id` of `asset` on the account of `who` to `amount`, unless it is larger
+	/// already.
+	fn extend_freeze(asset: Self::AssetId, id: &Self::Id, who: &AccountId, amount: Self::Balance)
+		-> DispatchResult
+	{
+		let frozen = Self::balance_frozen(asset, id, who).max(amount);
+		Self::set_freeze(asset, id, who, frozen)
+	}
+
+	/// Increase the freeze `id` of `asset` on the account of `who` by `amount`.
+	fn increase_frozen(asset: Self::AssetId, id: &Self::Id, who: &AccountId, amount: Self::Balance)
+		-> DispatchResult
+	{
+		let frozen = Self::balance_frozen(asset, id, who).saturating_add(amount);
+		Self::set_freeze(asset, id, who, frozen)
+	}
+
+	/// Decrease the freeze `id` of `asset` on the account of `who` by `amount`, removing it once
+	/// it is zero.
+	fn decrease_frozen(asset: Self::AssetId, id: &Self::Id, who: &AccountId, amount: Self::Balance)
+		-> DispatchResult
+	{
+		let frozen = Self::balance_frozen(asset, id, who).saturating_sub(amount);
+		if frozen.is_zero() {
+			Self::thaw(asset, id, who)
+		} else {
+			Self::set_freeze(asset, id, who, frozen)
+		}
+	}
+}
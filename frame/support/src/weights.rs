@@ -143,6 +143,12 @@ pub use sp_runtime::transaction_validity::TransactionPriority;
 /// Numeric range of a transaction weight.
 pub type Weight = u64;
 
+mod weight_v2;
+pub use weight_v2::{WeightV2, RefTimeWeight, ProofSizeWeight};
+
+mod weight_meter;
+pub use weight_meter::WeightMeter;
+
 /// These constants are specific to FRAME, and the current implementation of its various components.
 /// For example: FRAME System, FRAME Executive, our FRAME support libraries, etc...
 pub mod constants {
@@ -0,0 +1,119 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tracking of the weight consumed against a limit.
+
+use sp_runtime::{Perbill, RuntimeDebug};
+use super::Weight;
+
+/// Meters the weight consumed by some work against a limit.
+///
+/// Weight is only ever consumed within the limit, so that work can be done piece by piece until
+/// the limit is reached.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct WeightMeter {
+	/// The weight consumed so far.
+	consumed: Weight,
+	/// The maximal weight which can be consumed.
+	limit: Weight,
+}
+
+impl WeightMeter {
+	/// Creates a new meter with the given limit, and nothing consumed.
+	pub fn from_limit(limit: Weight) -> Self {
+		Self { consumed: 0, limit }
+	}
+
+	/// Creates a new meter with the largest possible limit.
+	pub fn max_limit() -> Self {
+		Self::from_limit(Weight::max_value())
+	}
+
+	/// The weight consumed so far.
+	pub fn consumed(&self) -> Weight {
+		self.consumed
+	}
+
+	/// The maximal weight which can be consumed.
+	pub fn limit(&self) -> Weight {
+		self.limit
+	}
+
+	/// The weight which can still be consumed.
+	pub fn remaining(&self) -> Weight {
+		self.limit.saturating_sub(self.consumed)
+	}
+
+	/// The ratio of the limit which has been consumed, e.g. to stop doing optional work once
+	/// most of it is used.
+	pub fn consumed_ratio(&self) -> Perbill {
+		Perbill::from_rational(self.consumed, self.limit)
+	}
+
+	/// Whether `w` can be consumed without going over the limit.
+	pub fn can_consume(&self, w: Weight) -> bool {
+		self.consumed.checked_add(w).map_or(false, |consumed| consumed <= self.limit)
+	}
+
+	/// Consumes `w` if it does not go over the limit, and returns `Err` otherwise.
+	pub fn try_consume(&mut self, w: Weight) -> Result<(), ()> {
+		if !self.can_consume(w) {
+			return Err(());
+		}
+		self.consumed += w;
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn weight_is_consumed_within_the_limit() {
+		let mut meter = WeightMeter::from_limit(10);
+		assert_eq!(meter.try_consume(4), Ok(()));
+		assert_eq!((meter.consumed(), meter.remaining()), (4, 6));
+
+		assert!(!meter.can_consume(7));
+		assert_eq!(meter.try_consume(7), Err(()));
+		assert_eq!(meter.consumed(), 4);
+
+		assert_eq!(meter.try_consume(6), Ok(()));
+		assert_eq!((meter.consumed(), meter.remaining()), (10, 0));
+		assert_eq!(meter.try_consume(0), Ok(()));
+		assert_eq!(meter.try_consume(1), Err(()));
+	}
+
+	#[test]
+	fn consumed_ratio_works() {
+		let mut meter = WeightMeter::from_limit(10);
+		assert_eq!(meter.consumed_ratio(), Perbill::zero());
+		assert_eq!(meter.try_consume(5), Ok(()));
+		assert_eq!(meter.consumed_ratio(), Perbill::from_percent(50));
+		assert_eq!(meter.try_consume(5), Ok(()));
+		assert_eq!(meter.consumed_ratio(), Perbill::one());
+	}
+
+	#[test]
+	fn consumption_does_not_overflow() {
+		let mut meter = WeightMeter::max_limit();
+		assert_eq!(meter.try_consume(Weight::max_value() - 1), Ok(()));
+		assert_eq!(meter.try_consume(2), Err(()));
+		assert_eq!(meter.remaining(), 1);
+	}
+}
@@ -0,0 +1,248 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A weight in two dimensions: the time an operation takes to compute, and the size of the proof
+//! of the state it accesses, which is what a parachain block is limited by in addition to its
+//! execution time.
+//!
+//! This only provides the type and its arithmetic. [`Weight`] is still a plain `u64`, used by the
+//! `WeightInfo` traits and enforced by `frame-system` and `frame-executive` as before, which only
+//! limit the computation time. Replacing it with [`WeightV2`] is left to a later migration.
+
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
+use codec::{Compact, Encode, Decode};
+use sp_runtime::RuntimeDebug;
+use sp_arithmetic::traits::Zero;
+use crate::traits::MaxEncodedLen;
+use super::Weight;
+
+/// The weight in computation time, as measured by [`Weight`].
+pub type RefTimeWeight = Weight;
+
+/// The weight in size of the proof, in bytes.
+pub type ProofSizeWeight = u64;
+
+/// The weight of an operation, in computation time and proof size.
+///
+/// Unlike a [`Weight`], it is not totally ordered: a weight fits a limit when both of its
+/// components do, see [`Self::all_lte`].
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct WeightV2 {
+	/// The computation time, see [`crate::weights::constants::WEIGHT_PER_SECOND`].
+	#[codec(compact)]
+	ref_time: RefTimeWeight,
+	/// The size of the proof, in bytes.
+	#[codec(compact)]
+	proof_size: ProofSizeWeight,
+}
+
+impl WeightV2 {
+	/// A weight of zero in both dimensions.
+	pub const fn zero() -> Self {
+		Self { ref_time: 0, proof_size: 0 }
+	}
+
+	/// The largest weight in both dimensions.
+	pub const fn max_value() -> Self {
+		Self { ref_time: RefTimeWeight::MAX, proof_size: ProofSizeWeight::MAX }
+	}
+
+	/// A weight of the given computation time and proof size.
+	pub const fn from_parts(ref_time: RefTimeWeight, proof_size: ProofSizeWeight) -> Self {
+		Self { ref_time, proof_size }
+	}
+
+	/// A weight of the given computation time, and no proof size.
+	pub const fn from_ref_time(ref_time: RefTimeWeight) -> Self {
+		Self { ref_time, proof_size: 0 }
+	}
+
+	/// A weight of the given proof size, and no computation time.
+	pub const fn from_proof_size(proof_size: ProofSizeWeight) -> Self {
+		Self { ref_time: 0, proof_size }
+	}
+
+	/// The computation time.
+	pub const fn ref_time(&self) -> RefTimeWeight {
+		self.ref_time
+	}
+
+	/// The proof size.
+	pub const fn proof_size(&self) -> ProofSizeWeight {
+		self.proof_size
+	}
+
+	/// The weight with its computation time set to `ref_time`.
+	pub const fn set_ref_time(mut self, ref_time: RefTimeWeight) -> Self {
+		self.ref_time = ref_time;
+		self
+	}
+
+	/// The weight with its proof size set to `proof_size`.
+	pub const fn set_proof_size(mut self, proof_size: ProofSizeWeight) -> Self {
+		self.proof_size = proof_size;
+		self
+	}
+
+	/// Add `other`, saturating each component at its maximum.
+	pub const fn saturating_add(self, other: Self) -> Self {
+		Self {
+			ref_time: self.ref_time.saturating_add(other.ref_time),
+			proof_size: self.proof_size.saturating_add(other.proof_size),
+		}
+	}
+
+	/// Subtract `other`, saturating each component at zero.
+	pub const fn saturating_sub(self, other: Self) -> Self {
+		Self {
+			ref_time: self.ref_time.saturating_sub(other.ref_time),
+			proof_size: self.proof_size.saturating_sub(other.proof_size),
+		}
+	}
+
+	/// Multiply both components by `scalar`, saturating each of them at its maximum.
+	pub const fn saturating_mul(self, scalar: u64) -> Self {
+		Self {
+			ref_time: self.ref_time.saturating_mul(scalar),
+			proof_size: self.proof_size.saturating_mul(scalar),
+		}
+	}
+
+	/// Increment `self` by `other`, saturating each component at its maximum.
+	pub fn saturating_accrue(&mut self, other: Self) {
+		*self = self.saturating_add(other);
+	}
+
+	/// Add `other`, or `None` if any component overflows.
+	pub fn checked_add(&self, other: &Self) -> Option<Self> {
+		Some(Self {
+			ref_time: self.ref_time.checked_add(other.ref_time)?,
+			proof_size: self.proof_size.checked_add(other.proof_size)?,
+		})
+	}
+
+	/// Subtract `other`, or `None` if any component underflows.
+	pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+		Some(Self {
+			ref_time: self.ref_time.checked_sub(other.ref_time)?,
+			proof_size: self.proof_size.checked_sub(other.proof_size)?,
+		})
+	}
+
+	/// Multiply both components by `scalar`, or `None` if any of them overflows.
+	pub fn checked_mul(&self, scalar: u64) -> Option<Self> {
+		Some(Self {
+			ref_time: self.ref_time.checked_mul(scalar)?,
+			proof_size: self.proof_size.checked_mul(scalar)?,
+		})
+	}
+
+	/// The smallest of each component of `self` and `other`.
+	pub fn min(&self, other: Self) -> Self {
+		Self {
+			ref_time: self.ref_time.min(other.ref_time),
+			proof_size: self.proof_size.min(other.proof_size),
+		}
+	}
+
+	/// The largest of each component of `self` and `other`.
+	pub fn max(&self, other: Self) -> Self {
+		Self {
+			ref_time: self.ref_time.max(other.ref_time),
+			proof_size: self.proof_size.max(other.proof_size),
+		}
+	}
+
+	/// Whether both components are at most the ones of `other`, e.g. whether `self` fits the
+	/// limit `other`.
+	pub fn all_lte(&self, other: Self) -> bool {
+		self.ref_time <= other.ref_time && self.proof_size <= other.proof_size
+	}
+
+	/// Whether any component is greater than the one of `other`, e.g. whether `self` exceeds the
+	/// limit `other`.
+	pub fn any_gt(&self, other: Self) -> bool {
+		!self.all_lte(other)
+	}
+}
+
+impl Zero for WeightV2 {
+	fn zero() -> Self {
+		Self::zero()
+	}
+
+	fn is_zero(&self) -> bool {
+		self.ref_time == 0 && self.proof_size == 0
+	}
+}
+
+/// Saturating, like the rest of the arithmetic of weights.
+impl sp_std::ops::Add for WeightV2 {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self {
+		self.saturating_add(rhs)
+	}
+}
+
+impl From<RefTimeWeight> for WeightV2 {
+	fn from(ref_time: RefTimeWeight) -> Self {
+		Self::from_ref_time(ref_time)
+	}
+}
+
+impl MaxEncodedLen for WeightV2 {
+	fn max_encoded_len() -> usize {
+		Compact::<RefTimeWeight>::max_encoded_len() + Compact::<ProofSizeWeight>::max_encoded_len()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn arithmetic_is_per_component() {
+		let a = WeightV2::from_parts(10, 1);
+		let b = WeightV2::from_parts(1, 10);
+		assert_eq!(a.saturating_add(b), WeightV2::from_parts(11, 11));
+		assert_eq!(a.saturating_sub(b), WeightV2::from_parts(9, 0));
+		assert_eq!(a.checked_sub(&b), None);
+		assert_eq!(a.checked_mul(2), Some(WeightV2::from_parts(20, 2)));
+		assert_eq!(WeightV2::max_value().saturating_mul(2), WeightV2::max_value());
+		assert_eq!(a.min(b), WeightV2::from_parts(1, 1));
+		assert_eq!(a.max(b), WeightV2::from_parts(10, 10));
+		assert_eq!(a + b, WeightV2::from_parts(11, 11));
+		assert_eq!(WeightV2::max_value() + a, WeightV2::max_value());
+	}
+
+	#[test]
+	fn limits_are_checked_on_both_components() {
+		let limit = WeightV2::from_parts(10, 10);
+		assert!(WeightV2::from_parts(10, 10).all_lte(limit));
+		assert!(WeightV2::from_parts(11, 0).any_gt(limit));
+		assert!(WeightV2::from_parts(0, 11).any_gt(limit));
+		assert!(WeightV2::from(10).all_lte(limit));
+	}
+
+	#[test]
+	fn max_encoded_len_is_correct() {
+		assert_eq!(WeightV2::max_value().encode().len(), WeightV2::max_encoded_len());
+	}
+}
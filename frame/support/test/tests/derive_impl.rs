@@ -0,0 +1,110 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use frame_support::traits::Get;
+use sp_io::TestExternalities;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::pallet_prelude::*;
+
+	#[pallet::config(with_default)]
+	pub trait Config: frame_system::Config {
+		#[pallet::no_default]
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		#[pallet::no_default_bounds]
+		type Balance: Parameter + From<Self::BlockNumber>;
+
+		#[pallet::constant]
+		type Limit: Get<u32>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::event]
+	pub enum Event<T: Config> {
+		Something(T::Balance),
+	}
+}
+
+/// The default config of the pallet, implemented by hand.
+pub struct PalletDefaultConfig;
+
+impl pallet::DefaultConfig for PalletDefaultConfig {
+	// no bounds are required on the default.
+	type Balance = ();
+	type Limit = frame_support::traits::ConstU32<3>;
+}
+
+#[frame_support::derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Runtime {
+	type AccountId = u32;
+	type AccountData = u32;
+}
+
+impl pallet::Config for Runtime {
+	type Event = Event;
+	type Balance = u64;
+	type Limit = <PalletDefaultConfig as pallet::DefaultConfig>::Limit;
+}
+
+pub type Header = sp_runtime::generic::Header<u64, sp_runtime::traits::BlakeTwo256>;
+pub type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
+pub type UncheckedExtrinsic = sp_runtime::generic::UncheckedExtrinsic<u32, Call, (), ()>;
+
+frame_support::construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic
+	{
+		System: frame_system::{Pallet, Call, Event<T>},
+		Example: pallet::{Pallet, Event<T>},
+	}
+);
+
+#[test]
+fn derive_impl_takes_the_missing_types_from_the_default() {
+	type SystemDefault = frame_system::config_preludes::TestDefaultConfig;
+
+	// overridden types.
+	let account: <Runtime as frame_system::Config>::AccountId = 3u32;
+	let data: <Runtime as frame_system::Config>::AccountData = 4u32;
+	assert_eq!((account, data), (3, 4));
+
+	// inherited types.
+	let number: <Runtime as frame_system::Config>::BlockNumber = 5u64;
+	assert_eq!(number, 5);
+	assert_eq!(
+		<Runtime as frame_system::Config>::BlockHashCount::get(),
+		<SystemDefault as frame_system::DefaultConfig>::BlockHashCount::get(),
+	);
+	assert_eq!(<Runtime as frame_system::Config>::SS58Prefix::get(), 42);
+	assert_eq!(<Runtime as pallet::Config>::Limit::get(), 3);
+
+	// the runtime types are injected.
+	TestExternalities::default().execute_with(|| {
+		frame_system::Pallet::<Runtime>::set_block_number(1);
+		frame_system::Pallet::<Runtime>::deposit_event(pallet::Event::<Runtime>::Something(7));
+		assert_eq!(
+			frame_system::Pallet::<Runtime>::events()[0].event,
+			Event::Example(pallet::Event::Something(7)),
+		);
+	});
+}
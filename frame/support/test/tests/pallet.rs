@@ -16,10 +16,10 @@
 // limitations under the License.
 
 use frame_support::{
-	weights::{DispatchInfo, DispatchClass, Pays, GetDispatchInfo},
+	weights::{DispatchInfo, DispatchClass, Pays, GetDispatchInfo, WeightMeter},
 	traits::{
-		GetCallName, OnInitialize, OnFinalize, OnRuntimeUpgrade, GetPalletVersion, OnGenesis,
-		MaxEncodedLen,
+		GetCallName, OnInitialize, OnPoll, OnFinalize, OnRuntimeUpgrade, GetPalletVersion,
+		OnGenesis, MaxEncodedLen,
 	},
 	dispatch::{UnfilteredDispatchable, Parameter},
 	storage::unhashed,
@@ -119,6 +119,14 @@ pub mod pallet {
 			T::AccountId::from(SomeType2); // Test for where clause
 			Self::deposit_event(Event::Something(20));
 		}
+		fn on_poll(_: BlockNumberFor<T>, weight: &mut frame_support::weights::WeightMeter) {
+			T::AccountId::from(SomeType1); // Test for where clause
+			T::AccountId::from(SomeType2); // Test for where clause
+			// increments `Value` as many times as the weight allows.
+			while weight.try_consume(5).is_ok() {
+				Value::<T>::mutate(|v| *v = Some(v.unwrap_or(0) + 1));
+			}
+		}
 		fn on_runtime_upgrade() -> Weight {
 			T::AccountId::from(SomeType1); // Test for where clause
 			T::AccountId::from(SomeType2); // Test for where clause
@@ -135,8 +143,18 @@ pub mod pallet {
 	impl<T: Config> Pallet<T>
 	where T::AccountId: From<SomeType1> + From<SomeType3> + SomeAssociation1
 	{
+		// Test for DispatchResult return type, and for a call declared out of order
+		#[pallet::weight(1)]
+		#[pallet::call_index(2)]
+		fn foo_no_post_info(
+			_origin: OriginFor<T>,
+		) -> DispatchResult {
+			Ok(())
+		}
+
 		/// Doc comment put in metadata
 		#[pallet::weight(Weight::from(*_foo))]
+		#[pallet::call_index(0)]
 		fn foo(
 			origin: OriginFor<T>,
 			#[pallet::compact] _foo: u32,
@@ -163,14 +181,6 @@ pub mod pallet {
 
 			Ok(().into())
 		}
-
-		// Test for DispatchResult return type
-		#[pallet::weight(1)]
-		fn foo_no_post_info(
-			_origin: OriginFor<T>,
-		) -> DispatchResult {
-			Ok(())
-		}
 	}
 
 	#[pallet::error]
@@ -238,6 +248,10 @@ pub mod pallet {
 		MaxValues = ConstU32<11>,
 	>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn counted_map)]
+	pub type CountedMap<T> = CountedStorageMap<_, Blake2_128Concat, u8, u32>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn conditional_value)]
 	#[cfg(feature = "conditional-storage")]
@@ -360,24 +374,45 @@ pub mod pallet2 {
 	#[pallet::generate_store(pub(crate) trait Store)]
 	pub struct Pallet<T>(_);
 
+	// `Vec<u32>` has no max encoded len, the pallet doesn't use `generate_storage_info`.
+	#[pallet::storage]
+	pub type SomeValue<T> = StorageValue<_, Vec<u32>>;
+
+	#[pallet::storage]
+	pub type SomeMap<T> =
+		StorageMap<_, Twox64Concat, u32, Vec<u32>, OptionQuery, GetDefault, ConstU32<4>>;
+
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T>
 	where T::AccountId: From<SomeType1> + SomeAssociation1,
 	{
 	}
 
-	#[pallet::call]
+	#[pallet::call(transactional)]
 	impl<T: Config> Pallet<T>
 	where T::AccountId: From<SomeType1> + SomeAssociation1,
 	{
+		#[pallet::weight(1)]
+		pub fn something_if_even(_origin: OriginFor<T>, v: u32) -> DispatchResult {
+			Self::deposit_event(Event::Something(v));
+			ensure!(v % 2 == 0, DispatchError::Other("odd"));
+			Ok(())
+		}
 	}
 
 	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
 	pub enum Event {
 		/// Something
 		Something(u32),
 	}
 
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		Staking,
+		Voting,
+	}
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config>
 	where T::AccountId: From<SomeType1> + SomeAssociation1,
@@ -469,7 +504,7 @@ frame_support::construct_runtime!(
 	{
 		System: frame_system::{Pallet, Call, Event<T>},
 		Example: pallet::{Pallet, Call, Event<T>, Config, Storage, Inherent, Origin<T>, ValidateUnsigned},
-		Example2: pallet2::{Pallet, Call, Event, Config<T>, Storage},
+		Example2: pallet2::{Pallet, Call, Event, Config<T>, Storage, HoldReason},
 	}
 );
 
@@ -490,6 +525,24 @@ fn transactional_works() {
 	})
 }
 
+#[test]
+fn transactional_call_works() {
+	TestExternalities::default().execute_with(|| {
+		frame_system::Pallet::<Runtime>::set_block_number(1);
+
+		pallet2::Call::<Runtime>::something_if_even(1).dispatch_bypass_filter(None.into())
+			.err().unwrap();
+		assert!(frame_system::Pallet::<Runtime>::events().is_empty());
+
+		pallet2::Call::<Runtime>::something_if_even(2).dispatch_bypass_filter(None.into())
+			.unwrap();
+		assert_eq!(
+			frame_system::Pallet::<Runtime>::events().iter().map(|e| &e.event).collect::<Vec<_>>(),
+			vec![&Event::pallet2(pallet2::Event::Something(2))],
+		);
+	})
+}
+
 #[test]
 fn call_expand() {
 	let call_foo = pallet::Call::<Runtime>::foo(3, 0);
@@ -506,6 +559,9 @@ fn call_expand() {
 		pallet::Call::<Runtime>::get_call_names(),
 		&["foo", "foo_transactional", "foo_no_post_info"],
 	);
+	assert_eq!(codec::Encode::encode(&call_foo)[0], 0);
+	assert_eq!(codec::Encode::encode(&pallet::Call::<Runtime>::foo_transactional(0))[0], 1);
+	assert_eq!(codec::Encode::encode(&pallet::Call::<Runtime>::foo_no_post_info())[0], 2);
 }
 
 #[test]
@@ -616,6 +672,15 @@ fn storage_expand() {
 		assert_eq!(unhashed::get::<u64>(&k), Some(3u64));
 		assert_eq!(&k[..32], &<pallet::NMap2<Runtime>>::final_prefix());
 
+		pallet::CountedMap::<Runtime>::insert(1, 2);
+		let mut k = [twox_128(b"Example"), twox_128(b"CountedMap")].concat();
+		k.extend(1u8.using_encoded(blake2_128_concat));
+		assert_eq!(unhashed::get::<u32>(&k), Some(2u32));
+		assert_eq!(Example::counted_map(1), Some(2));
+		let k = [twox_128(b"Example"), twox_128(b"CounterForCountedMap")].concat();
+		assert_eq!(unhashed::get::<u32>(&k), Some(1u32));
+		assert_eq!(pallet::CountedMap::<Runtime>::count(), 1);
+
 		#[cfg(feature = "conditional-storage")]
 		{
 			pallet::ConditionalValue::<Runtime>::put(1);
@@ -632,6 +697,10 @@ fn pallet_hooks_expand() {
 		frame_system::Pallet::<Runtime>::set_block_number(1);
 
 		assert_eq!(AllPallets::on_initialize(1), 10);
+		let mut meter = WeightMeter::from_limit(12);
+		AllPallets::on_poll(1, &mut meter);
+		assert_eq!(meter.consumed(), 10);
+		assert_eq!(pallet::Value::<Runtime>::get(), Some(2));
 		AllPallets::on_finalize(1);
 
 		assert_eq!(pallet::Pallet::<Runtime>::storage_version(), None);
@@ -777,6 +846,27 @@ fn metadata() {
 					default: DecodeDifferent::Decoded(vec![0]),
 					documentation: DecodeDifferent::Decoded(vec![]),
 				},
+				StorageEntryMetadata {
+					name: DecodeDifferent::Decoded("CountedMap".to_string()),
+					modifier: StorageEntryModifier::Optional,
+					ty: StorageEntryType::Map {
+						key: DecodeDifferent::Decoded("u8".to_string()),
+						value: DecodeDifferent::Decoded("u32".to_string()),
+						hasher: StorageHasher::Blake2_128Concat,
+						unused: false,
+					},
+					default: DecodeDifferent::Decoded(vec![0]),
+					documentation: DecodeDifferent::Decoded(vec![]),
+				},
+				StorageEntryMetadata {
+					name: DecodeDifferent::Decoded("CounterForCountedMap".to_string()),
+					modifier: StorageEntryModifier::Default,
+					ty: StorageEntryType::Plain(DecodeDifferent::Decoded("u32".to_string())),
+					default: DecodeDifferent::Decoded(vec![0, 0, 0, 0]),
+					documentation: DecodeDifferent::Decoded(vec![
+						" Counter for the related counted storage map, `CountedMap`.".to_string(),
+					]),
+				},
 				#[cfg(feature = "conditional-storage")] StorageEntryMetadata {
 					name: DecodeDifferent::Decoded("ConditionalValue".to_string()),
 					modifier: StorageEntryModifier::Optional,
@@ -954,6 +1044,17 @@ fn metadata() {
 	pretty_assertions::assert_eq!(pallet_metadata, expected_pallet_metadata);
 }
 
+#[test]
+fn composite_enum_expand() {
+	use codec::Encode;
+
+	let reason: HoldReason = pallet2::HoldReason::Voting.into();
+	assert_eq!(reason, HoldReason::Example2(pallet2::HoldReason::Voting));
+	// encoded at the index of the pallet.
+	assert_eq!(reason.encode(), vec![2, 1]);
+	assert_eq!(HoldReason::max_encoded_len(), 2);
+}
+
 #[test]
 fn test_pallet_info_access() {
 	assert_eq!(<System as frame_support::traits::PalletInfoAccess>::name(), "System");
@@ -1023,6 +1124,16 @@ fn test_storage_info() {
 				max_values: Some(11),
 				max_size: Some(14 + 8 + 16),
 			},
+			StorageInfo {
+				prefix: prefix(b"Example", b"CountedMap"),
+				max_values: None,
+				max_size: Some(5 + 16),
+			},
+			StorageInfo {
+				prefix: prefix(b"Example", b"CounterForCountedMap"),
+				max_values: Some(1),
+				max_size: Some(4),
+			},
 			#[cfg(feature = "conditional-storage")]
 			{
 				StorageInfo {
@@ -1058,3 +1169,41 @@ fn test_storage_info() {
 		],
 	);
 }
+
+#[test]
+fn test_partial_storage_info() {
+	use frame_support::{
+		StorageHasher,
+		traits::{StorageInfoTrait, StorageInfo},
+		pallet_prelude::*,
+	};
+
+	let prefix = |pallet_name, storage_name| {
+		let mut res = [0u8; 32];
+		res[0..16].copy_from_slice(&Twox128::hash(pallet_name));
+		res[16..32].copy_from_slice(&Twox128::hash(storage_name));
+		res
+	};
+
+	let example2_storage_info = vec![
+		StorageInfo {
+			prefix: prefix(b"Example2", b"SomeValue"),
+			max_values: Some(1),
+			max_size: None,
+		},
+		StorageInfo {
+			prefix: prefix(b"Example2", b"SomeMap"),
+			max_values: Some(4),
+			max_size: None,
+		},
+	];
+	assert_eq!(Example2::storage_info(), example2_storage_info);
+
+	// The storage info of all pallets is aggregated by the runtime, in reverse order.
+	let all_storage_info = AllPalletsWithSystem::storage_info();
+	assert_eq!(
+		all_storage_info.len(),
+		System::storage_info().len() + Example::storage_info().len() + example2_storage_info.len(),
+	);
+	assert!(all_storage_info.starts_with(&example2_storage_info));
+}
@@ -0,0 +1,110 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use frame_support::{
+	Blake2_128Concat, StorageHasher,
+	dispatch::UnfilteredDispatchable,
+	storage::unhashed,
+	weights::GetDispatchInfo,
+};
+use sp_io::{TestExternalities, hashing::twox_128};
+
+#[frame_support::pallet(dev_mode)]
+pub mod pallet {
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {}
+
+	#[pallet::pallet]
+	#[pallet::generate_storage_info]
+	pub struct Pallet<T>(_);
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		pub fn add_dummy(_origin: OriginFor<T>, value: u32) -> DispatchResult {
+			Dummy::<T>::append(value);
+			Bar::<T>::insert(value, value);
+			Ok(())
+		}
+	}
+
+	// Unbounded, which is fine in dev mode despite `generate_storage_info`.
+	#[pallet::storage]
+	pub type Dummy<T> = StorageValue<_, Vec<u32>>;
+
+	#[pallet::storage]
+	pub type Bar<T> = StorageMap<_, _, u32, u32>;
+}
+
+impl frame_system::Config for Runtime {
+	type BaseCallFilter = ();
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u32;
+	type Call = Call;
+	type Hash = sp_runtime::testing::H256;
+	type Hashing = sp_runtime::traits::BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = sp_runtime::traits::IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = frame_support::traits::ConstU32<250>;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+impl pallet::Config for Runtime {}
+
+pub type Header = sp_runtime::generic::Header<u32, sp_runtime::traits::BlakeTwo256>;
+pub type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
+pub type UncheckedExtrinsic = sp_runtime::generic::UncheckedExtrinsic<u32, Call, (), ()>;
+
+frame_support::construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic
+	{
+		System: frame_system::{Pallet, Call, Event<T>},
+		Example: pallet::{Pallet, Call, Storage},
+	}
+);
+
+#[test]
+fn dev_mode_relaxes_weights_and_hashers() {
+	TestExternalities::default().execute_with(|| {
+		let call = pallet::Call::<Runtime>::add_dummy(3);
+		assert_eq!(call.get_dispatch_info().weight, 0);
+		call.dispatch_bypass_filter(None.into()).unwrap();
+
+		assert_eq!(pallet::Dummy::<Runtime>::get(), Some(vec![3]));
+		let mut k = [twox_128(b"Example"), twox_128(b"Bar")].concat();
+		k.extend(Blake2_128Concat::hash(&3u32.to_le_bytes()));
+		assert_eq!(unhashed::get::<u32>(&k), Some(3));
+	});
+}
@@ -0,0 +1,97 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use sp_io::TestExternalities;
+use sp_runtime::traits::{BlakeTwo256, Hash};
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Indexed by the sender and the receiver.
+		Transferred(#[pallet::indexed] T::AccountId, #[pallet::indexed] T::AccountId, u64),
+		/// Indexed by the account.
+		Named { amount: u64, #[pallet::indexed] who: T::AccountId },
+		/// Not indexed.
+		Something(u32),
+	}
+}
+
+#[frame_support::derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Runtime {}
+
+impl pallet::Config for Runtime {
+	type Event = Event;
+}
+
+pub type Header = sp_runtime::generic::Header<u64, sp_runtime::traits::BlakeTwo256>;
+pub type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
+pub type UncheckedExtrinsic = sp_runtime::generic::UncheckedExtrinsic<u64, Call, (), ()>;
+
+frame_support::construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic
+	{
+		System: frame_system::{Pallet, Call, Event<T>},
+		Example: pallet::{Pallet, Event<T>},
+	}
+);
+
+#[test]
+fn topics_are_the_hashes_of_the_indexed_fields() {
+	let (alice, bob) = (BlakeTwo256::hash_of(&1u64), BlakeTwo256::hash_of(&2u64));
+
+	let event = pallet::Event::<Runtime>::Transferred(1, 2, 100);
+	assert_eq!(event.topics::<BlakeTwo256>(), vec![alice, bob]);
+	let event = pallet::Event::<Runtime>::Named { amount: 100, who: 2 };
+	assert_eq!(event.topics::<BlakeTwo256>(), vec![bob]);
+	let event = pallet::Event::<Runtime>::Something(3);
+	assert!(event.topics::<BlakeTwo256>().is_empty());
+}
+
+#[test]
+fn deposit_event_indexes_the_topics() {
+	TestExternalities::default().execute_with(|| {
+		let (alice, bob) = (BlakeTwo256::hash_of(&1u64), BlakeTwo256::hash_of(&2u64));
+		frame_system::Pallet::<Runtime>::set_block_number(1);
+
+		pallet::Pallet::<Runtime>::deposit_event(pallet::Event::Transferred(1, 2, 100));
+		pallet::Pallet::<Runtime>::deposit_event(pallet::Event::Something(3));
+		pallet::Pallet::<Runtime>::deposit_event(pallet::Event::Named { amount: 10, who: 2 });
+
+		let events = frame_system::Pallet::<Runtime>::events();
+		assert_eq!(events[0].topics, vec![alice, bob]);
+		assert!(events[1].topics.is_empty());
+		assert_eq!(events[2].topics, vec![bob]);
+
+		assert_eq!(frame_system::Pallet::<Runtime>::event_topics(alice), vec![(1, 0)]);
+		assert_eq!(frame_system::Pallet::<Runtime>::event_topics(bob), vec![(1, 0), (1, 2)]);
+	});
+}
@@ -0,0 +1,166 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use frame_support::{
+	dispatch::{GetDispatchInfo, UnfilteredDispatchable},
+	traits::Task as _,
+};
+use codec::Encode;
+use sp_io::TestExternalities;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::storage]
+	pub type Numbers<T> = StorageMap<_, Twox64Concat, u32, u32>;
+
+	#[pallet::storage]
+	pub type Total<T> = StorageValue<_, (u32, u32), ValueQuery>;
+
+	#[pallet::tasks]
+	impl<T: Config> Pallet<T> {
+		/// Add a pair of numbers into the total.
+		#[pallet::task_list(Numbers::<T>::iter_keys())]
+		#[pallet::task_condition(|i: &u32| Numbers::<T>::contains_key(i))]
+		#[pallet::task_weight(10)]
+		fn add_number_into_total(i: u32) -> DispatchResult {
+			let v = Numbers::<T>::take(i).ok_or("Number not found")?;
+			Total::<T>::mutate(|(total_keys, total_values)| {
+				*total_keys += i;
+				*total_values += v;
+			});
+			Ok(())
+		}
+
+		/// Clear the total, once the numbers are all added.
+		#[pallet::task_index(3)]
+		#[pallet::task_list(core::iter::once(()))]
+		#[pallet::task_condition(|| Numbers::<T>::iter_keys().next().is_none())]
+		#[pallet::task_weight(20)]
+		fn reset_total() -> DispatchResult {
+			Total::<T>::kill();
+			Ok(())
+		}
+	}
+}
+
+#[frame_support::derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Runtime {}
+
+impl pallet::Config for Runtime {}
+
+pub type Header = sp_runtime::generic::Header<u64, sp_runtime::traits::BlakeTwo256>;
+pub type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
+pub type UncheckedExtrinsic = sp_runtime::generic::UncheckedExtrinsic<u64, Call, (), ()>;
+
+frame_support::construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic
+	{
+		System: frame_system::{Pallet, Call, Event<T>},
+		Example: pallet::{Pallet, Call, Storage},
+	}
+);
+
+type Task = pallet::Task<Runtime>;
+
+fn do_task(task: Task) -> Result<(), sp_runtime::DispatchError> {
+	pallet::Call::<Runtime>::do_task(task)
+		.dispatch_bypass_filter(Origin::signed(1))
+		.map(|_| ())
+		.map_err(|e| e.error)
+}
+
+#[test]
+fn tasks_are_listed_and_validated() {
+	TestExternalities::default().execute_with(|| {
+		pallet::Numbers::<Runtime>::insert(1, 10);
+		pallet::Numbers::<Runtime>::insert(2, 20);
+
+		let mut tasks = Task::iter().collect::<Vec<_>>();
+		tasks.sort_by_key(|task| task.encode());
+		assert_eq!(tasks, vec![
+			Task::add_number_into_total(1),
+			Task::add_number_into_total(2),
+			Task::reset_total(),
+		]);
+
+		assert!(Task::add_number_into_total(1).is_valid());
+		assert!(!Task::add_number_into_total(3).is_valid());
+		assert!(!Task::reset_total().is_valid());
+		assert_eq!(Task::valid_tasks().count(), 2);
+
+		assert_eq!(Task::add_number_into_total(1).weight(), 10);
+		assert_eq!(Task::reset_total().weight(), 20);
+		assert_eq!(Task::add_number_into_total(1).task_index(), 0);
+		assert_eq!(Task::reset_total().task_index(), 3);
+		assert_eq!(Task::reset_total().encode(), vec![3]);
+	});
+}
+
+#[test]
+fn tasks_are_listed_lazily() {
+	TestExternalities::default().execute_with(|| {
+		let tasks = Task::iter();
+		// nothing is read before the iterator is advanced.
+		pallet::Numbers::<Runtime>::insert(1, 10);
+		assert_eq!(tasks.collect::<Vec<_>>(), vec![
+			Task::add_number_into_total(1),
+			Task::reset_total(),
+		]);
+	});
+}
+
+#[test]
+fn do_task_runs_valid_tasks_only() {
+	TestExternalities::default().execute_with(|| {
+		pallet::Numbers::<Runtime>::insert(1, 10);
+		pallet::Numbers::<Runtime>::insert(2, 20);
+
+		let call = pallet::Call::<Runtime>::do_task(Task::add_number_into_total(1));
+		assert_eq!(call.get_dispatch_info().weight, 10);
+
+		let invalid_task = Err(frame_system::Error::<Runtime>::InvalidTask.into());
+		assert_eq!(do_task(Task::reset_total()), invalid_task);
+		assert_eq!(do_task(Task::add_number_into_total(1)), Ok(()));
+		assert_eq!(do_task(Task::add_number_into_total(1)), invalid_task);
+		assert_eq!(do_task(Task::add_number_into_total(2)), Ok(()));
+		assert_eq!(pallet::Total::<Runtime>::get(), (3, 30));
+
+		assert_eq!(do_task(Task::reset_total()), Ok(()));
+		assert_eq!(pallet::Total::<Runtime>::get(), (0, 0));
+	});
+}
+
+#[test]
+fn do_task_requires_a_signed_origin() {
+	TestExternalities::default().execute_with(|| {
+		pallet::Numbers::<Runtime>::insert(1, 10);
+		let result = pallet::Call::<Runtime>::do_task(Task::add_number_into_total(1))
+			.dispatch_bypass_filter(Origin::none());
+		assert_eq!(result.map_err(|e| e.error), Err(sp_runtime::DispatchError::BadOrigin));
+	});
+}
@@ -0,0 +1,107 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use codec::Encode;
+use frame_support::{
+	Blake2_128Concat, Twox64Concat, storage_alias,
+	storage::{types::{Key, ValueQuery}, unhashed},
+	traits::PalletInfoAccess,
+};
+use sp_io::{TestExternalities, hashing::{blake2_128, twox_64, twox_128}};
+
+#[storage_alias]
+type Value = StorageValue<OldPallet, u32, ValueQuery>;
+
+#[storage_alias]
+type Map = StorageMap<OldPallet, Blake2_128Concat, u32, u32>;
+
+#[storage_alias]
+type DoubleMap = StorageDoubleMap<OldPallet, Blake2_128Concat, u32, Twox64Concat, u32, u32>;
+
+#[storage_alias]
+type NMap = StorageNMap<OldPallet, (Key<Blake2_128Concat, u32>, Key<Twox64Concat, u32>), u32>;
+
+pub trait Config {
+	type Balance: codec::FullCodec;
+}
+
+struct Runtime;
+impl Config for Runtime {
+	type Balance = u64;
+}
+
+/// A pallet configured under the name `Renamed`.
+struct Pallet<T>(core::marker::PhantomData<T>);
+impl<T> PalletInfoAccess for Pallet<T> {
+	fn index() -> usize {
+		0
+	}
+
+	fn name() -> &'static str {
+		"Renamed"
+	}
+}
+
+#[storage_alias]
+type Total<T: Config> = StorageValue<Pallet<T>, <T as Config>::Balance>;
+
+fn blake2_128_concat(d: &[u8]) -> Vec<u8> {
+	[&blake2_128(d)[..], d].concat()
+}
+
+fn twox_64_concat(d: &[u8]) -> Vec<u8> {
+	[&twox_64(d)[..], d].concat()
+}
+
+#[test]
+fn storage_alias_uses_the_given_prefixes() {
+	TestExternalities::default().execute_with(|| {
+		Value::put(1);
+		let k = [twox_128(b"OldPallet"), twox_128(b"Value")].concat();
+		assert_eq!(unhashed::get::<u32>(&k), Some(1));
+
+		Map::insert(1, 2);
+		let mut k = [twox_128(b"OldPallet"), twox_128(b"Map")].concat();
+		k.extend(1u32.using_encoded(blake2_128_concat));
+		assert_eq!(unhashed::get::<u32>(&k), Some(2));
+
+		DoubleMap::insert(1, 2, 3);
+		let mut k = [twox_128(b"OldPallet"), twox_128(b"DoubleMap")].concat();
+		k.extend(1u32.using_encoded(blake2_128_concat));
+		k.extend(2u32.using_encoded(twox_64_concat));
+		assert_eq!(unhashed::get::<u32>(&k), Some(3));
+
+		NMap::insert((1, 2), 3);
+		let mut k = [twox_128(b"OldPallet"), twox_128(b"NMap")].concat();
+		k.extend(1u32.using_encoded(blake2_128_concat));
+		k.extend(2u32.using_encoded(twox_64_concat));
+		assert_eq!(unhashed::get::<u32>(&k), Some(3));
+	});
+}
+
+#[test]
+fn storage_alias_takes_the_prefix_of_a_pallet() {
+	TestExternalities::default().execute_with(|| {
+		Total::<Runtime>::put(5u64);
+		let k = [twox_128(b"Renamed"), twox_128(b"Total")].concat();
+		assert_eq!(unhashed::get::<u64>(&k), Some(5));
+
+		// migrations take the old storage out of the way.
+		assert_eq!(Total::<Runtime>::take(), Some(5));
+		assert_eq!(unhashed::get::<u64>(&k), None);
+	});
+}
@@ -0,0 +1,139 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::Config;
+use codec::{Encode, Decode};
+use sp_runtime::{
+	RuntimeDebug,
+	traits::SignedExtension,
+	transaction_validity::TransactionValidityError,
+};
+
+/// The hash of the metadata of the runtime, as 64 hexadecimal characters.
+///
+/// It is embedded at compile time from the `RUNTIME_METADATA_HASH` environment variable, which
+/// is meant to be set when building the runtime once its metadata is known.
+const RUNTIME_METADATA_HASH: Option<&str> = option_env!("RUNTIME_METADATA_HASH");
+
+/// Decodes a 32 bytes hash from its hexadecimal representation, optionally prefixed by `0x`.
+fn decode_hash(hex: &str) -> Option<[u8; 32]> {
+	fn nibble(c: u8) -> Option<u8> {
+		match c {
+			b'0'..=b'9' => Some(c - b'0'),
+			b'a'..=b'f' => Some(c - b'a' + 10),
+			b'A'..=b'F' => Some(c - b'A' + 10),
+			_ => None,
+		}
+	}
+
+	let hex = hex.strip_prefix("0x").unwrap_or(hex).as_bytes();
+	if hex.len() != 64 {
+		return None;
+	}
+	let mut hash = [0u8; 32];
+	for (byte, pair) in hash.iter_mut().zip(hex.chunks(2)) {
+		*byte = nibble(pair[0])? << 4 | nibble(pair[1])?;
+	}
+	Some(hash)
+}
+
+/// Whether the metadata hash is included in the signing payload, chosen by the signer.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+pub enum Mode {
+	/// The metadata hash is not included.
+	Disabled,
+	/// The metadata hash is included.
+	Enabled,
+}
+
+/// Include the hash of the runtime metadata in the signing payload, if enabled by the signer.
+///
+/// This lets a signer, e.g. a hardware wallet, check that the metadata it used to decode the
+/// transaction is the one of the runtime: the signature is only valid if both agree on the hash.
+/// The hash is embedded in the runtime at compile time, see `RUNTIME_METADATA_HASH`. A
+/// transaction enabling the check is rejected by a runtime built without a hash.
+#[derive(Encode, Decode, Clone, Eq, PartialEq)]
+pub struct CheckMetadataHash<T: Config + Send + Sync> {
+	mode: Mode,
+	#[codec(skip)]
+	_phantom: sp_std::marker::PhantomData<T>,
+}
+
+impl<T: Config + Send + Sync> sp_std::fmt::Debug for CheckMetadataHash<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "CheckMetadataHash({:?})", self.mode)
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T: Config + Send + Sync> CheckMetadataHash<T> {
+	/// Create new `SignedExtension` to check the metadata hash, if `mode` enables it.
+	pub fn new(mode: Mode) -> Self {
+		Self { mode, _phantom: sp_std::marker::PhantomData }
+	}
+
+	/// The hash of the metadata of the runtime, if it was embedded at compile time.
+	pub fn metadata_hash() -> Option<[u8; 32]> {
+		RUNTIME_METADATA_HASH.and_then(decode_hash)
+	}
+}
+
+impl<T: Config + Send + Sync> SignedExtension for CheckMetadataHash<T> {
+	type AccountId = T::AccountId;
+	type Call = <T as Config>::Call;
+	type AdditionalSigned = Option<[u8; 32]>;
+	type Pre = ();
+	const IDENTIFIER: &'static str = "CheckMetadataHash";
+
+	fn additional_signed(&self) -> Result<Self::AdditionalSigned, TransactionValidityError> {
+		// without an embedded hash, the signature of an enabled check can't match.
+		Ok(match self.mode {
+			Mode::Disabled => None,
+			Mode::Enabled => Self::metadata_hash(),
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::Test;
+
+	#[test]
+	fn decode_hash_works() {
+		let hash = [0xab; 32];
+		let hex = "ab".repeat(32);
+		assert_eq!(decode_hash(&hex), Some(hash));
+		assert_eq!(decode_hash(&format!("0x{}", hex.to_uppercase())), Some(hash));
+		assert_eq!(decode_hash(&hex[2..]), None);
+		assert_eq!(decode_hash(&format!("{}zz", &hex[2..])), None);
+	}
+
+	#[test]
+	fn only_the_mode_is_encoded() {
+		let ext = CheckMetadataHash::<Test>::new(Mode::Enabled);
+		assert_eq!(ext.encode(), vec![1]);
+		let decoded = CheckMetadataHash::<Test>::decode(&mut &[0u8][..]).unwrap();
+		assert_eq!(decoded.mode, Mode::Disabled);
+		assert_eq!(CheckMetadataHash::<Test>::new(Mode::Disabled).additional_signed(), Ok(None));
+	}
+}
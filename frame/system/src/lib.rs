@@ -58,6 +58,8 @@
 //!     transaction.
 //!   - [`CheckTxVersion`]: Checks that the transaction version is the same as the one used to sign the
 //!     transaction.
+//!   - [`CheckMetadataHash`]: Checks, if enabled by the signer, that the hash of the runtime
+//!     metadata is the same as the one used to sign the transaction.
 //!
 //! Lookup the runtime aggregator file (e.g. `node/runtime`) to see the full list of signed
 //! extensions included in a chain.
@@ -71,6 +73,7 @@ use sp_std::prelude::*;
 use sp_std::map;
 use sp_std::marker::PhantomData;
 use sp_std::fmt::Debug;
+use sp_std::convert::TryInto;
 use sp_version::RuntimeVersion;
 use sp_runtime::{
 	RuntimeDebug, Perbill, DispatchError, Either, generic,
@@ -88,7 +91,7 @@ use frame_support::{
 	Parameter, storage,
 	traits::{
 		SortedMembers, Get, PalletInfo, OnNewAccount, OnKilledAccount, HandleLifetime,
-		StoredMap, EnsureOrigin, OriginTrait, Filter,
+		StoredMap, EnsureOrigin, OriginTrait, Filter, PrivilegeCmp,
 	},
 	weights::{
 		Weight, RuntimeDbWeight, DispatchInfo, DispatchClass,
@@ -119,7 +122,7 @@ pub mod mocking;
 pub use extensions::{
 	check_mortality::CheckMortality, check_genesis::CheckGenesis, check_nonce::CheckNonce,
 	check_spec_version::CheckSpecVersion, check_tx_version::CheckTxVersion,
-	check_weight::CheckWeight,
+	check_weight::CheckWeight, check_metadata_hash::CheckMetadataHash,
 };
 // Backward compatible re-export.
 pub use extensions::check_mortality::CheckMortality as CheckEra;
@@ -153,13 +156,66 @@ impl SetCode for () {
 	}
 }
 
+/// Default implementations of [`DefaultConfig`], which can be used to implement [`Config`] with
+/// [`frame_support::derive_impl`].
+pub mod config_preludes {
+	use super::DefaultConfig;
+	use frame_support::parameter_types;
+	use sp_core::H256;
+	use sp_runtime::{generic, traits::{BlakeTwo256, IdentityLookup}};
+
+	parameter_types! {
+		pub const TestBlockHashCount: u64 = 10;
+		pub const TestSS58Prefix: u8 = 42;
+	}
+
+	/// Provides a viable default config that can be used with
+	/// [`derive_impl`](`frame_support::derive_impl`) to derive a testing pallet config based on
+	/// this one.
+	///
+	/// The runtime types `Origin`, `Call`, `Event` and `PalletInfo` are the ones of the runtime
+	/// generated by `construct_runtime`.
+	pub struct TestDefaultConfig;
+
+	#[frame_support::register_default_impl(TestDefaultConfig)]
+	impl DefaultConfig for TestDefaultConfig {
+		type BaseCallFilter = ();
+		type BlockWeights = ();
+		type BlockLength = ();
+		#[inject_runtime_type]
+		type Origin = ();
+		#[inject_runtime_type]
+		type Call = ();
+		type Index = u64;
+		type BlockNumber = u64;
+		type Hash = H256;
+		type Hashing = BlakeTwo256;
+		type AccountId = u64;
+		type Lookup = IdentityLookup<u64>;
+		type Header = generic::Header<u64, BlakeTwo256>;
+		#[inject_runtime_type]
+		type Event = ();
+		type BlockHashCount = TestBlockHashCount;
+		type DbWeight = ();
+		type Version = ();
+		#[inject_runtime_type]
+		type PalletInfo = ();
+		type AccountData = ();
+		type OnNewAccount = ();
+		type OnKilledAccount = ();
+		type SystemWeightInfo = ();
+		type SS58Prefix = TestSS58Prefix;
+		type OnSetCode = ();
+	}
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use crate::{*, pallet_prelude::*, self as frame_system};
 	use frame_support::pallet_prelude::*;
 
 	/// System configuration trait. Implemented by runtime.
-	#[pallet::config]
+	#[pallet::config(with_default)]
 	#[pallet::disable_frame_system_supertrait_check]
 	pub trait Config: 'static + Eq + Clone {
 		/// The basic call filter to use in Origin. All origins are built with this filter as base,
@@ -175,6 +231,7 @@ pub mod pallet {
 		type BlockLength: Get<limits::BlockLength>;
 
 		/// The `Origin` type used by dispatchable calls.
+		#[pallet::no_default_bounds]
 		type Origin:
 			Into<Result<RawOrigin<Self::AccountId>, Self::Origin>>
 			+ From<RawOrigin<Self::AccountId>>
@@ -182,6 +239,7 @@ pub mod pallet {
 			+ OriginTrait<Call=Self::Call>;
 
 		/// The aggregated `Call` type.
+		#[pallet::no_default_bounds]
 		type Call: Dispatchable + Debug;
 
 		/// Account index (aka nonce) type. This stores the number of previous transactions associated
@@ -223,6 +281,7 @@ pub mod pallet {
 		>;
 
 		/// The aggregated event type of the runtime.
+		#[pallet::no_default_bounds]
 		type Event: Parameter + Member + From<Event<Self>> + Debug + IsType<<Self as frame_system::Config>::Event>;
 
 		/// Maximum number of block number to block hash mappings to keep (oldest pruned first).
@@ -243,6 +302,7 @@ pub mod pallet {
 		/// runtime.
 		///
 		/// For tests it is okay to use `()` as type, however it will provide "useless" data.
+		#[pallet::no_default_bounds]
 		type PalletInfo: PalletInfo;
 
 		/// Data to be associated with an account (other than nonce/transaction counter, which this
@@ -294,6 +354,15 @@ pub mod pallet {
 		}
 	}
 
+	#[pallet::extra_constants]
+	impl<T: Config> Pallet<T> {
+		/// The base weight of an extrinsic of each dispatch class, as in `BlockWeights`.
+		fn extrinsic_base_weights() -> PerDispatchClass<Weight> {
+			let weights = T::BlockWeights::get();
+			PerDispatchClass::new(|class| weights.get(class).base_extrinsic)
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// A dispatch that will fill the block weight up to the given ratio.
@@ -520,6 +589,8 @@ pub mod pallet {
 		NonDefaultComposite,
 		/// There is a non-zero reference count preventing the account from being purged.
 		NonZeroRefCount,
+		/// The submitted task is not valid.
+		InvalidTask,
 	}
 
 	/// Exposed trait-generic origin type.
@@ -948,6 +1019,24 @@ impl<
 	}
 }
 
+/// Implementation of [`PrivilegeCmp`] for which the root origin is more privileged than any other
+/// origin, and other origins are only comparable when they are equal.
+pub struct EqualOrGreatestRootCmp<AccountId>(sp_std::marker::PhantomData<AccountId>);
+impl<
+	O: PartialEq + Clone + TryInto<RawOrigin<AccountId>>,
+	AccountId,
+> PrivilegeCmp<O> for EqualOrGreatestRootCmp<AccountId> {
+	fn cmp_privilege(left: &O, right: &O) -> Option<sp_std::cmp::Ordering> {
+		let is_root = |o: &O| matches!(o.clone().try_into(), Ok(RawOrigin::Root));
+		match (left == right, is_root(left), is_root(right)) {
+			(true, _, _) => Some(sp_std::cmp::Ordering::Equal),
+			(false, true, _) => Some(sp_std::cmp::Ordering::Greater),
+			(false, _, true) => Some(sp_std::cmp::Ordering::Less),
+			(false, false, false) => None,
+		}
+	}
+}
+
 /// Ensure that the origin `o` represents a signed extrinsic (i.e. transaction).
 /// Returns `Ok` with the account that signed the extrinsic or an `Err` otherwise.
 pub fn ensure_signed<OuterOrigin, AccountId>(o: OuterOrigin) -> Result<AccountId, BadOrigin>
@@ -219,6 +219,25 @@ impl BlockWeights {
 		self.per_class.get(class)
 	}
 
+	/// Checks the weight consumed by each class at the end of a block against the `max_total` of
+	/// the class. The `Mandatory` class is never limited, since it has to be included regardless
+	/// of the block weight.
+	///
+	/// Whether an extrinsic may use the `reserved` pool of its class depends on the weight of the
+	/// block when it was applied, which is only known to `CheckWeight`. Once the block is over,
+	/// the total weight may well exceed `max_block` for a valid block, so it is not checked here.
+	///
+	/// Returns the first class going over its limits, if any.
+	pub fn check_consumed(&self, consumed: &crate::ConsumedWeight) -> Result<(), DispatchClass> {
+		for class in DispatchClass::non_mandatory().iter().copied() {
+			let max_total = self.get(class).max_total;
+			if max_total.map_or(false, |max| *consumed.get(class) > max) {
+				return Err(class);
+			}
+		}
+		Ok(())
+	}
+
 	/// Verifies correctness of this `BlockWeights` object.
 	pub fn validate(self) -> ValidationResult {
 		fn or_max(w: Option<Weight>) -> Weight {
@@ -439,4 +458,50 @@ mod tests {
 			.validate()
 			.unwrap();
 	}
+
+	#[test]
+	fn consumed_weight_is_checked_per_class() {
+		let weights = BlockWeights::builder()
+			.base_block(10)
+			.for_class(DispatchClass::all(), |weights| weights.base_extrinsic = 5)
+			.for_class(DispatchClass::Normal, |weights| weights.max_total = Some(500))
+			.for_class(DispatchClass::Operational, |weights| {
+				weights.max_total = Some(1000);
+				weights.reserved = Some(200);
+			})
+			.build_or_panic();
+		let consumed = |normal, operational, mandatory| {
+			let mut consumed = crate::ConsumedWeight::default();
+			consumed.add(normal, DispatchClass::Normal);
+			consumed.add(operational, DispatchClass::Operational);
+			consumed.add(mandatory, DispatchClass::Mandatory);
+			consumed
+		};
+
+		assert_eq!(weights.max_block, 1000);
+		assert_eq!(weights.check_consumed(&consumed(500, 400, 0)), Ok(()));
+		assert_eq!(weights.check_consumed(&consumed(501, 0, 0)), Err(DispatchClass::Normal));
+		assert_eq!(weights.check_consumed(&consumed(0, 1001, 0)), Err(DispatchClass::Operational));
+		// going over `max_block` is fine, e.g. for operational extrinsics in their reserved pool
+		// after the initialization of the block.
+		assert_eq!(weights.check_consumed(&consumed(500, 200, 100)), Ok(()));
+		assert_eq!(weights.check_consumed(&consumed(1, 0, 1000)), Ok(()));
+		// mandatory weight is never limited.
+		assert_eq!(weights.check_consumed(&consumed(0, 0, 5000)), Ok(()));
+
+		// a full block, whose initialization and operational extrinsics, applied once the normal
+		// ones had filled the block, take it over `max_block`.
+		let weights = BlockWeights::builder()
+			.base_block(10)
+			.for_class(DispatchClass::all(), |weights| weights.base_extrinsic = 5)
+			.for_class(DispatchClass::Normal, |weights| weights.max_total = Some(750))
+			.for_class(DispatchClass::Operational, |weights| {
+				weights.max_total = Some(1000);
+				weights.reserved = Some(250);
+			})
+			.build_or_panic();
+		assert_eq!(weights.max_block, 1000);
+		assert_eq!(weights.check_consumed(&consumed(750, 200, 100)), Ok(()));
+		assert_eq!(weights.check_consumed(&consumed(751, 200, 100)), Err(DispatchClass::Normal));
+	}
 }
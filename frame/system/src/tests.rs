@@ -528,6 +528,21 @@ fn ensure_one_of_works() {
 	assert!(ensure_root_or_signed(RawOrigin::None).is_err());
 }
 
+#[test]
+fn equal_or_greatest_root_cmp_works() {
+	use sp_std::cmp::Ordering;
+	fn cmp(left: RawOrigin<u64>, right: RawOrigin<u64>) -> Option<Ordering> {
+		EqualOrGreatestRootCmp::<u64>::cmp_privilege(&left, &right)
+	}
+
+	assert_eq!(cmp(RawOrigin::Root, RawOrigin::Root), Some(Ordering::Equal));
+	assert_eq!(cmp(RawOrigin::Signed(1), RawOrigin::Signed(1)), Some(Ordering::Equal));
+	assert_eq!(cmp(RawOrigin::Root, RawOrigin::Signed(1)), Some(Ordering::Greater));
+	assert_eq!(cmp(RawOrigin::None, RawOrigin::Root), Some(Ordering::Less));
+	assert_eq!(cmp(RawOrigin::Signed(1), RawOrigin::Signed(2)), None);
+	assert_eq!(cmp(RawOrigin::Signed(1), RawOrigin::None), None);
+}
+
 #[test]
 fn extrinsics_root_is_calculated_correctly() {
 	new_test_ext().execute_with(|| {
@@ -702,6 +702,10 @@ mod tests {
 		type ExistentialDeposit = ExistentialDeposit;
 		type AccountStore = System;
 		type MaxLocks = ();
+		type HoldIdentifier = ();
+		type FreezeIdentifier = ();
+		type MaxHolds = frame_support::traits::ConstU32<1>;
+		type MaxFreezes = frame_support::traits::ConstU32<1>;
 		type WeightInfo = ();
 	}
 
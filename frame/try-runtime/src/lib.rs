@@ -22,6 +22,8 @@
 use sp_std::prelude::*;
 use frame_support::weights::Weight;
 
+pub use frame_support::traits::UpgradeCheckSelect;
+
 sp_api::decl_runtime_apis! {
 	/// Runtime api for testing the execution of a runtime upgrade.
 	pub trait TryRuntime {
@@ -30,8 +32,32 @@ sp_api::decl_runtime_apis! {
 		/// This should do EXACTLY the same operations as the runtime would have done in the case of
 		/// a runtime upgrade (e.g. pallet ordering must be the same)
 		///
-		/// Returns the consumed weight of the migration in case of a successful one, combined with
-		/// the total allowed block weight of the runtime.
-		fn on_runtime_upgrade() -> Result<(Weight, Weight), sp_runtime::RuntimeString>;
+		/// Returns the weight consumed by each migration in case of a successful upgrade, labelled
+		/// with the name of the pallet it belongs to, combined with the total allowed block weight
+		/// of the runtime.
+		///
+		/// Runs the pre and post upgrade checks of the migrations, and the try-state invariants of
+		/// all pallets after them, as selected by `checks`.
+		///
+		/// Only the migrations of the pallets named in `pallets` run, or all of them if it is
+		/// empty.
+		fn on_runtime_upgrade(
+			checks: UpgradeCheckSelect,
+			pallets: Vec<Vec<u8>>,
+		) -> Result<(Vec<(sp_runtime::RuntimeString, Weight)>, Weight), sp_runtime::RuntimeString>;
+
+		/// Execute the given block on top of the current state, returning the weight it consumed.
+		///
+		/// Checking the state root and the signatures of the extrinsics can be skipped, e.g. to
+		/// re-execute a block with a runtime that is known to yield a different state.
+		///
+		/// Runs the pre and post upgrade checks, if the block upgrades the runtime, and the
+		/// try-state invariants of all pallets after the block, as selected by `checks`.
+		fn execute_block(
+			block: Block,
+			state_root_check: bool,
+			signature_check: bool,
+			checks: UpgradeCheckSelect,
+		) -> Weight;
 	}
 }
@@ -473,6 +473,10 @@ mod tests {
 		type ExistentialDeposit = ExistentialDeposit;
 		type AccountStore = System;
 		type MaxLocks = MaxLocks;
+		type HoldIdentifier = ();
+		type FreezeIdentifier = ();
+		type MaxHolds = frame_support::traits::ConstU32<1>;
+		type MaxFreezes = frame_support::traits::ConstU32<1>;
 		type WeightInfo = ();
 	}
 	parameter_types! {
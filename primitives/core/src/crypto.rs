@@ -605,9 +605,9 @@ pub fn set_default_ss58_version(version: Ss58AddressFormat) {
 lazy_static::lazy_static! {
 	static ref SS58_REGEX: Regex = Regex::new(r"^(?P<ss58>[\w\d ]+)?(?P<path>(//?[^/]+)*)$")
 		.expect("constructed from known-good static value; qed");
-	static ref SECRET_PHRASE_REGEX: Regex = Regex::new(r"^(?P<phrase>[\d\w ]+)?(?P<path>(//?[^/]+)*)(///(?P<password>.*))?$")
+	pub(crate) static ref SECRET_PHRASE_REGEX: Regex = Regex::new(r"^(?P<phrase>[\d\w ]+)?(?P<path>(//?[^/]+)*)(///(?P<password>.*))?$")
 		.expect("constructed from known-good static value; qed");
-	static ref JUNCTION_REGEX: Regex = Regex::new(r"/(/?[^/]+)")
+	pub(crate) static ref JUNCTION_REGEX: Regex = Regex::new(r"/(/?[^/]+)")
 		.expect("constructed from known-good static value; qed");
 }
 
@@ -56,6 +56,8 @@ pub mod hashing;
 pub use hashing::{blake2_128, blake2_256, twox_64, twox_128, twox_256, keccak_256};
 pub mod hexdisplay;
 pub mod crypto;
+#[cfg(feature = "std")]
+pub mod suri;
 
 pub mod u32_trait;
 
@@ -0,0 +1,365 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Secret URI (SURI) parsing and normalization.
+//!
+//! A SURI, as accepted by [`Pair::from_string`], is a mnemonic phrase or hex seed, followed by a
+//! derivation path of hard (`//`) and soft (`/`) junctions and an optional password (`///`). See
+//! [`Pair::from_string_with_seed`] for the full format.
+//!
+//! [`SecretUri`] exposes the parts of a SURI exactly as Substrate interprets them, and
+//! [`SecretUri::normalize`] maps SURIs which derive the same key onto the same string. Soft paths
+//! can be derived from public keys alone with [`derive_public`], and [`DerivationMetadata`]
+//! describes the derivation of a SURI, without any of its secrets, in a serializable form.
+
+use std::fmt;
+use serde::Serialize;
+use crate::{
+	crypto::{
+		DeriveJunction, Pair, Public, PublicError, SecretStringError, Ss58Codec, DEV_PHRASE,
+		JUNCTION_REGEX, SECRET_PHRASE_REGEX,
+	},
+	hexdisplay::HexDisplay,
+};
+
+/// A single junction of a derivation path, as written in a SURI.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Junction {
+	/// The junction code, without the leading slashes.
+	pub code: String,
+	/// Whether the junction is hard (`//`) or soft (`/`).
+	pub hard: bool,
+}
+
+impl Junction {
+	/// Parse a derivation path, such as `//polkadot/0`.
+	pub fn parse_path(path: &str) -> Result<Vec<Self>, SecretStringError> {
+		let junctions = JUNCTION_REGEX.captures_iter(path)
+			.map(|cap| match cap[1].strip_prefix('/') {
+				Some(code) => Junction { code: code.into(), hard: true },
+				None => Junction { code: cap[1].into(), hard: false },
+			})
+			.collect::<Vec<_>>();
+
+		// the junctions must make up the entire path.
+		if junctions.iter().map(|j| j.to_string().len()).sum::<usize>() != path.len() {
+			return Err(SecretStringError::InvalidPath);
+		}
+		Ok(junctions)
+	}
+
+	/// The canonical form of this junction.
+	///
+	/// Codes which are interpreted as integers are written without sign and leading zeros.
+	pub fn normalize(self) -> Self {
+		match self.code.parse::<u64>() {
+			Ok(index) => Junction { code: index.to_string(), ..self },
+			Err(_) => self,
+		}
+	}
+
+	/// The junction used for the actual derivation.
+	pub fn to_derive_junction(&self) -> DeriveJunction {
+		let junction = DeriveJunction::from(&self.code);
+		if self.hard { junction.harden() } else { junction }
+	}
+}
+
+impl fmt::Display for Junction {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}{}", if self.hard { "//" } else { "/" }, self.code)
+	}
+}
+
+/// The parts of a SURI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecretUri {
+	/// The mnemonic phrase or `0x` prefixed hex seed. `None` stands for [`DEV_PHRASE`].
+	pub phrase: Option<String>,
+	/// The derivation path.
+	pub path: Vec<Junction>,
+	/// The password, if any.
+	pub password: Option<String>,
+}
+
+impl SecretUri {
+	/// Parse the SURI `s`.
+	///
+	/// Only the format is checked, not whether the phrase or seed is valid.
+	pub fn parse(s: &str) -> Result<Self, SecretStringError> {
+		let cap = SECRET_PHRASE_REGEX.captures(s).ok_or(SecretStringError::InvalidFormat)?;
+		Ok(SecretUri {
+			phrase: cap.name("phrase").map(|m| m.as_str().into()),
+			path: Junction::parse_path(&cap["path"])?,
+			password: cap.name("password").map(|m| m.as_str().into()),
+		})
+	}
+
+	/// Whether the root secret is a hex seed rather than a mnemonic phrase.
+	pub fn is_seed(&self) -> bool {
+		self.phrase.as_ref().map_or(false, |phrase| phrase.starts_with("0x"))
+	}
+
+	/// Whether the derivation path only consists of soft junctions, such that the derived public
+	/// key can be derived from the root public key as well.
+	pub fn is_soft(&self) -> bool {
+		self.path.iter().all(|j| !j.hard)
+	}
+
+	/// The canonical form of this SURI, which derives the same key.
+	///
+	/// The [`DEV_PHRASE`] is left out, hex seeds are lowercase, junction codes are normalized as
+	/// by [`Junction::normalize`], and empty passwords as well as passwords of hex seeds, which
+	/// are ignored, are removed.
+	pub fn normalize(self) -> Self {
+		let is_seed = self.is_seed();
+		let phrase = match self.phrase {
+			Some(phrase) if phrase == DEV_PHRASE => None,
+			Some(phrase) if is_seed => Some(phrase.to_lowercase()),
+			phrase => phrase,
+		};
+		SecretUri {
+			phrase,
+			path: self.path.into_iter().map(Junction::normalize).collect(),
+			password: self.password.filter(|password| !is_seed && !password.is_empty()),
+		}
+	}
+
+	/// The key pair of this SURI.
+	pub fn pair<P: Pair>(&self) -> Result<P, SecretStringError> {
+		P::from_string(&self.to_string(), None)
+	}
+}
+
+impl fmt::Display for SecretUri {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}", self.phrase.as_deref().unwrap_or_default())?;
+		for junction in &self.path {
+			write!(f, "{}", junction)?;
+		}
+		match &self.password {
+			Some(password) => write!(f, "///{}", password),
+			None => Ok(()),
+		}
+	}
+}
+
+/// Derive the soft derivation `path` of `public`, without knowledge of the secret key.
+///
+/// Fails if `path` contains hard junctions, or if the crypto of `public` does not support soft
+/// derivation, which is only supported by sr25519.
+pub fn derive_public<T: Public>(public: &T, path: &[Junction]) -> Result<T, PublicError> {
+	if path.iter().any(|j| j.hard) {
+		return Err(PublicError::InvalidPath);
+	}
+	public.derive(path.iter().map(Junction::to_derive_junction)).ok_or(PublicError::InvalidPath)
+}
+
+/// The kind of root secret of a SURI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RootKind {
+	/// The [`DEV_PHRASE`].
+	Dev,
+	/// A mnemonic phrase.
+	Phrase,
+	/// A hex seed.
+	Seed,
+}
+
+/// A junction of [`DerivationMetadata`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JunctionMetadata {
+	/// The normalized junction code.
+	pub code: String,
+	/// Whether the junction is hard.
+	pub hard: bool,
+	/// The hex encoded chain code used for the derivation.
+	pub chain_code: String,
+}
+
+/// The derivation of a SURI, without its secrets.
+///
+/// Serializes into the JSON object:
+///
+/// ```json
+/// {
+///   "root": "dev" | "phrase" | "seed",
+///   "rootPublic": "0x…",
+///   "path": "//polkadot/0",
+///   "junctions": [{ "code": "polkadot", "hard": true, "chainCode": "0x…" }, …],
+///   "hasPassword": false,
+///   "softOnly": false,
+///   "public": "0x…",
+///   "ss58": "5…"
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DerivationMetadata {
+	/// The kind of root secret.
+	pub root: RootKind,
+	/// The hex encoded public key of the root secret.
+	pub root_public: String,
+	/// The normalized derivation path.
+	pub path: String,
+	/// The junctions of the derivation path.
+	pub junctions: Vec<JunctionMetadata>,
+	/// Whether a password is used.
+	pub has_password: bool,
+	/// Whether the public key can be derived from the root public key, see [`derive_public`].
+	pub soft_only: bool,
+	/// The hex encoded derived public key.
+	pub public: String,
+	/// The derived public key as SS58 address of the default version.
+	pub ss58: String,
+}
+
+impl DerivationMetadata {
+	/// Describe the derivation of `suri`, using the crypto of `P`.
+	pub fn new<P: Pair>(suri: &SecretUri) -> Result<Self, SecretStringError> {
+		let suri = suri.clone().normalize();
+		let root = SecretUri { path: Vec::new(), ..suri.clone() }.pair::<P>()?.public();
+		let public = suri.pair::<P>()?.public();
+		let hex = |bytes: &[u8]| format!("0x{}", HexDisplay::from(&bytes));
+
+		Ok(DerivationMetadata {
+			root: match &suri.phrase {
+				None => RootKind::Dev,
+				Some(_) if suri.is_seed() => RootKind::Seed,
+				Some(_) => RootKind::Phrase,
+			},
+			root_public: hex(root.as_ref()),
+			path: suri.path.iter().map(ToString::to_string).collect(),
+			junctions: suri.path.iter()
+				.map(|j| JunctionMetadata {
+					code: j.code.clone(),
+					hard: j.hard,
+					chain_code: hex(j.to_derive_junction().inner()),
+				})
+				.collect(),
+			has_password: suri.password.is_some(),
+			soft_only: suri.is_soft(),
+			public: hex(public.as_ref()),
+			ss58: public.to_ss58check(),
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{sr25519, ed25519};
+
+	const SEED: &str = "0x9D61B19DEFFD5A60BA844AF492EC2CC44449C5697B326919703BAC031CAE7F60";
+
+	#[test]
+	fn parses_suri() {
+		assert_eq!(
+			SecretUri::parse("//Alice/0///secret").unwrap(),
+			SecretUri {
+				phrase: None,
+				path: vec![
+					Junction { code: "Alice".into(), hard: true },
+					Junction { code: "0".into(), hard: false },
+				],
+				password: Some("secret".into()),
+			},
+		);
+
+		let suri = SecretUri::parse(&format!("{}//polkadot", SEED)).unwrap();
+		assert_eq!(suri.phrase.as_deref(), Some(SEED));
+		assert!(suri.is_seed());
+		assert!(!suri.is_soft());
+		assert_eq!(suri.to_string(), format!("{}//polkadot", SEED));
+	}
+
+	#[test]
+	fn parses_path() {
+		assert_eq!(
+			Junction::parse_path("//polkadot/0").unwrap(),
+			vec![
+				Junction { code: "polkadot".into(), hard: true },
+				Junction { code: "0".into(), hard: false },
+			],
+		);
+		assert_eq!(Junction::parse_path(""), Ok(vec![]));
+		assert_eq!(Junction::parse_path("polkadot"), Err(SecretStringError::InvalidPath));
+		assert_eq!(Junction::parse_path("///polkadot"), Err(SecretStringError::InvalidPath));
+	}
+
+	#[test]
+	fn normalizes_suri() {
+		let cases = [
+			(format!("{}//Alice//007/+1///", DEV_PHRASE), "//Alice//7/1".to_string()),
+			(format!("{}/x///secret", SEED), format!("{}/x", SEED.to_lowercase())),
+			("//Alice/18446744073709551616///secret".into(), "//Alice/18446744073709551616///secret".into()),
+		];
+
+		for (suri, normalized) in cases.iter() {
+			let parsed = SecretUri::parse(suri).unwrap();
+			assert_eq!(parsed.clone().normalize().to_string(), *normalized);
+			assert_eq!(
+				parsed.normalize().pair::<sr25519::Pair>().unwrap().public(),
+				sr25519::Pair::from_string(suri, None).unwrap().public(),
+			);
+		}
+	}
+
+	#[test]
+	fn derives_public_soft_path() {
+		let alice = sr25519::Pair::from_string("//Alice", None).unwrap();
+		let derived = sr25519::Pair::from_string("//Alice/polkadot/0", None).unwrap();
+		let path = Junction::parse_path("/polkadot/0").unwrap();
+
+		assert_eq!(derive_public(&alice.public(), &path), Ok(derived.public()));
+		assert_eq!(
+			derive_public(&alice.public(), &Junction::parse_path("//polkadot").unwrap()),
+			Err(PublicError::InvalidPath),
+		);
+
+		let alice = ed25519::Pair::from_string("//Alice", None).unwrap();
+		assert_eq!(derive_public(&alice.public(), &path), Err(PublicError::InvalidPath));
+	}
+
+	#[test]
+	fn exports_derivation_metadata() {
+		let suri = SecretUri::parse("//Alice/01///secret").unwrap();
+		let metadata = DerivationMetadata::new::<sr25519::Pair>(&suri).unwrap();
+		let pair = sr25519::Pair::from_string("//Alice/1///secret", None).unwrap();
+		let root = sr25519::Pair::from_string("///secret", None).unwrap();
+
+		let json = serde_json::to_value(&metadata).unwrap();
+		assert_eq!(json["root"], "dev");
+		assert_eq!(json["rootPublic"], format!("0x{}", HexDisplay::from(&root.public().0)));
+		assert_eq!(json["path"], "//Alice/1");
+		assert_eq!(json["junctions"][0]["code"], "Alice");
+		assert_eq!(json["junctions"][0]["hard"], true);
+		assert_eq!(
+			json["junctions"][0]["chainCode"],
+			format!("0x{}", HexDisplay::from(DeriveJunction::hard("Alice").inner())),
+		);
+		assert_eq!(json["junctions"][1]["code"], "1");
+		assert_eq!(json["hasPassword"], true);
+		assert_eq!(json["softOnly"], false);
+		assert_eq!(json["public"], format!("0x{}", HexDisplay::from(&pair.public().0)));
+		assert_eq!(json["ss58"], pair.public().to_ss58check());
+		// no secrets are exported.
+		assert!(!json.to_string().contains("secret"));
+	}
+}
@@ -151,7 +151,20 @@ pub trait Externalities: ExtensionStore {
 	fn kill_child_storage(&mut self, child_info: &ChildInfo, limit: Option<u32>) -> (bool, u32);
 
 	/// Clear storage entries which keys are start with the given prefix.
-	fn clear_prefix(&mut self, prefix: &[u8]);
+	///
+	/// Deletes all keys from the overlay and up to `limit` keys from the backend, starting at
+	/// `maybe_cursor` if given. No limit is applied if `limit` is `None`. Returns the cursor to
+	/// continue with, i.e. the first remaining key, or `None` if no key with the prefix remains,
+	/// along with the number of keys removed from the backend.
+	///
+	/// The cursor can be passed to a subsequent call, e.g. in a later block, to continue the
+	/// deletion without going over the keys which were removed already.
+	fn clear_prefix(
+		&mut self,
+		prefix: &[u8],
+		limit: Option<u32>,
+		maybe_cursor: Option<&[u8]>,
+	) -> (Option<Vec<u8>>, u32);
 
 	/// Clear child storage entries which keys are start with the given prefix.
 	fn clear_child_prefix(
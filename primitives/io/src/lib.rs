@@ -93,6 +93,21 @@ pub enum KillChildStorageResult {
 	SomeRemaining(u32),
 }
 
+/// The outcome of calling `clear_prefix` with a limit.
+#[derive(PassByCodec, Encode, Decode)]
+pub enum KillStorageResult {
+	/// No key with the prefix remains. Returns the number of keys removed from the backend.
+	AllRemoved(u32),
+	/// At least one key with the prefix remains due to the supplied limit.
+	SomeRemaining {
+		/// The number of keys removed from the backend.
+		removed: u32,
+		/// The first remaining key, which can be passed to a subsequent `clear_prefix` call to
+		/// continue the deletion.
+		cursor: Vec<u8>,
+	},
+}
+
 /// Interface for accessing the storage from within the runtime.
 #[runtime_interface]
 pub trait Storage {
@@ -133,7 +148,35 @@ pub trait Storage {
 
 	/// Clear the storage of each key-value pair where the key starts with the given `prefix`.
 	fn clear_prefix(&mut self, prefix: &[u8]) {
-		Externalities::clear_prefix(*self, prefix)
+		Externalities::clear_prefix(*self, prefix, None, None);
+	}
+
+	/// Clear the storage of each key-value pair where the key starts with the given `prefix`,
+	/// up to `limit` keys in the backend.
+	///
+	/// The deletion starts at `maybe_cursor` if given, which should be the cursor returned by a
+	/// previous call with the same `prefix`. This allows to delete a large prefix across several
+	/// blocks, or several calls of the same block, without going over the keys which were
+	/// removed already.
+	///
+	/// # Note
+	///
+	/// Please note that keys that are residing in the overlay for that prefix when issuing
+	/// this call are all deleted without counting towards the `limit`. Only keys written
+	/// during the current block are part of the overlay.
+	#[version(2)]
+	fn clear_prefix(
+		&mut self,
+		prefix: &[u8],
+		limit: Option<u32>,
+		maybe_cursor: Option<Vec<u8>>,
+	) -> KillStorageResult {
+		let (maybe_cursor, removed) =
+			Externalities::clear_prefix(*self, prefix, limit, maybe_cursor.as_deref());
+		match maybe_cursor {
+			None => KillStorageResult::AllRemoved(removed),
+			Some(cursor) => KillStorageResult::SomeRemaining { removed, cursor },
+		}
 	}
 
 	/// Append the encoded `value` to the storage item at `key`.
@@ -1498,7 +1541,10 @@ mod tests {
 		});
 
 		t.execute_with(|| {
-			storage::clear_prefix(b":abc");
+			assert!(matches!(
+				storage::clear_prefix(b":abc", None, None),
+				KillStorageResult::AllRemoved(2),
+			));
 
 			assert!(storage::get(b":a").is_some());
 			assert!(storage::get(b":abdd").is_some());
@@ -145,6 +145,23 @@ where
 			},
 		})
 	}
+
+	#[cfg(feature = "try-runtime")]
+	fn unchecked_into_checked_i_know_what_i_am_doing(
+		self,
+		lookup: &Lookup,
+	) -> Result<Self::Checked, TransactionValidityError> {
+		Ok(match self.signature {
+			Some((signed, _, extra)) => CheckedExtrinsic {
+				signed: Some((lookup.lookup(signed)?, extra)),
+				function: self.function,
+			},
+			None => CheckedExtrinsic {
+				signed: None,
+				function: self.function,
+			},
+		})
+	}
 }
 
 impl<Address, Call, Signature, Extra> ExtrinsicMetadata
@@ -544,6 +544,8 @@ pub enum TokenError {
 	UnknownAsset,
 	/// Funds exist but are frozen.
 	Frozen,
+	/// Operation is not supported by the asset.
+	Unsupported,
 }
 
 impl From<TokenError> for &'static str {
@@ -555,6 +557,7 @@ impl From<TokenError> for &'static str {
 			TokenError::CannotCreate => "Account cannot be created",
 			TokenError::UnknownAsset => "The asset in question is unknown",
 			TokenError::Frozen => "Funds exist but are frozen",
+			TokenError::Unsupported => "Operation is not supported by the asset",
 		}
 	}
 }
@@ -939,6 +942,7 @@ mod tests {
 			Token(TokenError::CannotCreate),
 			Token(TokenError::UnknownAsset),
 			Token(TokenError::Frozen),
+			Token(TokenError::Unsupported),
 			Arithmetic(ArithmeticError::Overflow),
 			Arithmetic(ArithmeticError::Underflow),
 			Arithmetic(ArithmeticError::DivisionByZero),
@@ -281,6 +281,36 @@ impl<T> Convert<T, T> for Identity {
 	fn convert(a: T) -> T { a }
 }
 
+/// Extensible conversion trait. Generic over the source type only, the destination type being
+/// defined by the implementation.
+pub trait Morph<A> {
+	/// The type into which `A` is mutated.
+	type Outcome;
+
+	/// Make conversion.
+	fn morph(a: A) -> Self::Outcome;
+}
+
+impl<T> Morph<T> for Identity {
+	type Outcome = T;
+	fn morph(a: T) -> T { a }
+}
+
+/// Extensible fallible conversion trait. Generic over the source type only, the destination
+/// type being defined by the implementation.
+pub trait TryMorph<A> {
+	/// The type into which `A` is mutated.
+	type Outcome;
+
+	/// Make conversion, returning `Err` if it is not possible.
+	fn try_morph(a: A) -> Result<Self::Outcome, ()>;
+}
+
+impl<T> TryMorph<T> for Identity {
+	type Outcome = T;
+	fn try_morph(a: T) -> Result<T, ()> { Ok(a) }
+}
+
 /// A structure that performs standard conversion using the standard Rust conversion traits.
 pub struct ConvertInto;
 impl<A, B: From<A>> Convert<A, B> for ConvertInto {
@@ -680,6 +710,20 @@ pub trait Checkable<Context>: Sized {
 
 	/// Check self, given an instance of Context.
 	fn check(self, c: &Context) -> Result<Self::Checked, TransactionValidityError>;
+
+	/// Check self like [`Checkable::check`], but without verifying any signature.
+	///
+	/// ## WARNING
+	///
+	/// Only meant to re-execute blocks in testing environments, where the signatures are of no
+	/// interest. A runtime built with the `try-runtime` feature must never be used in production,
+	/// and the name of this function is deliberately chosen such that it is never called in
+	/// consensus code paths.
+	#[cfg(feature = "try-runtime")]
+	fn unchecked_into_checked_i_know_what_i_am_doing(
+		self,
+		c: &Context,
+	) -> Result<Self::Checked, TransactionValidityError>;
 }
 
 /// A "checkable" piece of information, used by the standard Substrate Executive in order to
@@ -701,6 +745,15 @@ impl<T: BlindCheckable, Context> Checkable<Context> for T {
 	fn check(self, _c: &Context) -> Result<Self::Checked, TransactionValidityError> {
 		BlindCheckable::check(self)
 	}
+
+	#[cfg(feature = "try-runtime")]
+	fn unchecked_into_checked_i_know_what_i_am_doing(
+		self,
+		_c: &Context,
+	) -> Result<Self::Checked, TransactionValidityError> {
+		// there is no way to check a blindly checkable extrinsic without its signature.
+		BlindCheckable::check(self)
+	}
 }
 
 /// A lazy call (module function and argument values) that can be executed via its `dispatch`
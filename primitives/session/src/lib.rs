@@ -48,6 +48,23 @@ sp_api::decl_runtime_apis! {
 		/// Returns the list of public raw public keys + key type.
 		fn decode_session_keys(encoded: Vec<u8>) -> Option<Vec<(Vec<u8>, KeyTypeId)>>;
 	}
+
+	/// Session key ownership runtime api.
+	pub trait SessionKeyOwnership {
+		/// Generate a proof that the public session key `key` of type `key_type` is owned by
+		/// `validator` in the current session.
+		///
+		/// `validator` is the SCALE encoded validator id. The proof can be used in equivocation
+		/// reports for offences committed within the current session.
+		///
+		/// Returns `None` if the key is not a member of the current session or if it is owned by
+		/// another validator.
+		fn generate_key_ownership_proof(
+			key_type: KeyTypeId,
+			key: Vec<u8>,
+			validator: Vec<u8>,
+		) -> Option<MembershipProof>;
+	}
 }
 
 /// Number of validators in a given session.
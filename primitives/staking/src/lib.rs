@@ -20,7 +20,30 @@
 //! A crate which contains primitives that are useful for implementation that uses staking
 //! approaches in general. Definitions related to sessions, slashing, etc go here.
 
+use sp_std::collections::btree_map::BTreeMap;
+
 pub mod offence;
 
 /// Simple index type with which we can count sessions.
 pub type SessionIndex = u32;
+
+/// Counter for the number of eras that have passed.
+pub type EraIndex = u32;
+
+/// Something that needs to be notified when a staker is slashed, e.g. because it keeps its own
+/// accounting of the staked funds.
+pub trait OnStakerSlash<AccountId, Balance> {
+	/// A staker was slashed.
+	///
+	/// `slashed_active` is the new active balance of `stash`, and `slashed_unlocking` maps the
+	/// unlocking era of each of the slashed unlocking chunks of `stash` to their new balance.
+	fn on_slash(
+		stash: &AccountId,
+		slashed_active: Balance,
+		slashed_unlocking: &BTreeMap<EraIndex, Balance>,
+	);
+}
+
+impl<AccountId, Balance> OnStakerSlash<AccountId, Balance> for () {
+	fn on_slash(_: &AccountId, _: Balance, _: &BTreeMap<EraIndex, Balance>) {}
+}
@@ -216,24 +216,34 @@ impl Externalities for BasicExternalities {
 		(true, num_removed as u32)
 	}
 
-	fn clear_prefix(&mut self, prefix: &[u8]) {
+	fn clear_prefix(
+		&mut self,
+		prefix: &[u8],
+		limit: Option<u32>,
+		maybe_cursor: Option<&[u8]>,
+	) -> (Option<Vec<u8>>, u32) {
 		if is_child_storage_key(prefix) {
 			warn!(
 				target: "trie",
 				"Refuse to clear prefix that is part of child storage key via main storage"
 			);
-			return;
+			return (None, 0);
 		}
 
-		let to_remove = self.inner.top.range::<[u8], _>((Bound::Included(prefix), Bound::Unbounded))
+		let start = maybe_cursor.filter(|cursor| cursor > &prefix).unwrap_or(prefix);
+		let range = (Bound::Included(start), Bound::Unbounded);
+		let mut to_remove = self.inner.top.range::<[u8], _>(range)
 			.map(|(k, _)| k)
 			.take_while(|k| k.starts_with(prefix))
-			.cloned()
-			.collect::<Vec<_>>();
+			.cloned();
+		let limit = limit.map_or(usize::max_value(), |limit| limit as usize);
+		let removed = to_remove.by_ref().take(limit).collect::<Vec<_>>();
+		let cursor = to_remove.next();
 
-		for key in to_remove {
-			self.inner.top.remove(&key);
+		for key in &removed {
+			self.inner.top.remove(key);
 		}
+		(cursor, removed.len() as u32)
 	}
 
 	fn clear_child_prefix(
@@ -411,6 +421,27 @@ mod tests {
 		assert_eq!(ext.child_storage(child_info, b"doe"), None);
 	}
 
+	#[test]
+	fn clear_prefix_with_limit_and_cursor_works() {
+		let mut ext = BasicExternalities::new(Storage {
+			top: map![
+				b"aaa".to_vec() => b"0".to_vec(),
+				b"abb".to_vec() => b"1".to_vec(),
+				b"abc".to_vec() => b"2".to_vec(),
+				b"abd".to_vec() => b"3".to_vec(),
+				b"bbb".to_vec() => b"4".to_vec()
+			],
+			children_default: map![],
+		});
+
+		assert_eq!(ext.clear_prefix(b"ab", Some(2), None), (Some(b"abd".to_vec()), 2));
+		assert_eq!(ext.storage(b"abc"), None);
+		assert_eq!(ext.clear_prefix(b"ab", None, Some(b"abd")), (None, 1));
+		assert_eq!(ext.storage(b"abd"), None);
+		assert_eq!(ext.storage(b"aaa"), Some(b"0".to_vec()));
+		assert_eq!(ext.storage(b"bbb"), Some(b"4".to_vec()));
+	}
+
 	#[test]
 	fn kill_child_storage_returns_num_elements_removed() {
 		let child_info = ChildInfo::new_default(b"storage_key");
@@ -449,23 +449,45 @@ where
 		}
 	}
 
-	fn clear_prefix(&mut self, prefix: &[u8]) {
-		trace!(target: "state", "{:04x}: ClearPrefix {}",
+	fn clear_prefix(
+		&mut self,
+		prefix: &[u8],
+		limit: Option<u32>,
+		maybe_cursor: Option<&[u8]>,
+	) -> (Option<Vec<u8>>, u32) {
+		trace!(target: "state", "{:04x}: ClearPrefix {} limit: {:?} cursor: {:?}",
 			self.id,
 			HexDisplay::from(&prefix),
+			limit,
+			maybe_cursor.map(HexDisplay::from),
 		);
 		let _guard = guard();
 
 		if sp_core::storage::well_known_keys::starts_with_child_storage_key(prefix) {
 			warn!(target: "trie", "Refuse to directly clear prefix that is part or contains of child storage key");
-			return;
+			return (None, 0);
 		}
 
 		self.mark_dirty();
 		self.overlay.clear_prefix(prefix);
-		self.backend.for_keys_with_prefix(prefix, |key| {
-			self.overlay.set_storage(key.to_vec(), None);
-		});
+
+		// walks the keys of the backend from the cursor, which is the first key to remove.
+		let start = maybe_cursor.filter(|cursor| cursor > &prefix).unwrap_or(prefix);
+		let mut next_key = if self.backend.exists_storage(start).expect(EXT_NOT_ALLOWED_TO_FAIL) {
+			Some(start.to_vec())
+		} else {
+			self.backend.next_storage_key(start).expect(EXT_NOT_ALLOWED_TO_FAIL)
+		};
+		let mut num_deleted: u32 = 0;
+		while let Some(key) = next_key.filter(|key| key.starts_with(prefix)) {
+			if limit.map_or(false, |limit| num_deleted >= limit) {
+				return (Some(key), num_deleted);
+			}
+			next_key = self.backend.next_storage_key(&key).expect(EXT_NOT_ALLOWED_TO_FAIL);
+			self.overlay.set_storage(key, None);
+			num_deleted = num_deleted.saturating_add(1);
+		}
+		(None, num_deleted)
 	}
 
 	fn clear_child_prefix(
@@ -1086,14 +1108,14 @@ mod tests {
 		not_under_prefix.extend(b"path");
 		ext.set_storage(not_under_prefix.clone(), vec![10]);
 
-		ext.clear_prefix(&[]);
-		ext.clear_prefix(&well_known_keys::CHILD_STORAGE_KEY_PREFIX[..4]);
+		ext.clear_prefix(&[], None, None);
+		ext.clear_prefix(&well_known_keys::CHILD_STORAGE_KEY_PREFIX[..4], None, None);
 		let mut under_prefix = well_known_keys::CHILD_STORAGE_KEY_PREFIX.to_vec();
 		under_prefix.extend(b"path");
-		ext.clear_prefix(&well_known_keys::CHILD_STORAGE_KEY_PREFIX[..4]);
+		ext.clear_prefix(&well_known_keys::CHILD_STORAGE_KEY_PREFIX[..4], None, None);
 		assert_eq!(ext.child_storage(child_info, &[30]), Some(vec![40]));
 		assert_eq!(ext.storage(not_under_prefix.as_slice()), Some(vec![10]));
-		ext.clear_prefix(&not_under_prefix[..5]);
+		ext.clear_prefix(&not_under_prefix[..5], None, None);
 		assert_eq!(ext.storage(not_under_prefix.as_slice()), None);
 	}
 
@@ -1111,7 +1111,7 @@ mod tests {
 				changes_trie::disabled_state::<_, u64>(),
 				None,
 			);
-			ext.clear_prefix(b"ab");
+			ext.clear_prefix(b"ab", None, None);
 		}
 		overlay.commit_transaction().unwrap();
 
@@ -1130,6 +1130,42 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn limited_clear_prefix_in_ext_works() {
+		let initial: BTreeMap<_, _> = map![
+			b"aaa".to_vec() => b"0".to_vec(),
+			b"abb".to_vec() => b"1".to_vec(),
+			b"abc".to_vec() => b"2".to_vec(),
+			b"abd".to_vec() => b"3".to_vec(),
+			b"bbb".to_vec() => b"4".to_vec()
+		];
+		let mut state = InMemoryBackend::<BlakeTwo256>::from(initial);
+		let backend = state.as_trie_backend().unwrap();
+
+		let mut overlay = OverlayedChanges::default();
+		overlay.set_storage(b"aba".to_vec(), Some(b"1312".to_vec()));
+		let mut cache = StorageTransactionCache::default();
+		let mut ext = Ext::new(
+			&mut overlay,
+			&mut cache,
+			backend,
+			changes_trie::disabled_state::<_, u64>(),
+			None,
+		);
+
+		// the overlay is cleared regardless of the limit.
+		assert_eq!(ext.clear_prefix(b"ab", Some(2), None), (Some(b"abd".to_vec()), 2));
+		assert_eq!(ext.storage(b"aba"), None);
+		assert_eq!(ext.storage(b"abb"), None);
+		assert_eq!(ext.storage(b"abd"), Some(b"3".to_vec()));
+
+		// the cursor skips the keys removed from the backend already.
+		assert_eq!(ext.clear_prefix(b"ab", Some(2), Some(b"abd")), (None, 1));
+		assert_eq!(ext.storage(b"abd"), None);
+		assert_eq!(ext.storage(b"aaa"), Some(b"0".to_vec()));
+		assert_eq!(ext.storage(b"bbb"), Some(b"4".to_vec()));
+	}
+
 	#[test]
 	fn limited_child_kill_works() {
 		let child_info = ChildInfo::new_default(b"sub1");
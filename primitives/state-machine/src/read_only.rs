@@ -136,7 +136,12 @@ impl<'a, H: Hasher, B: 'a + Backend<H>> Externalities for ReadOnlyExternalities<
 		unimplemented!("kill_child_storage is not supported in ReadOnlyExternalities")
 	}
 
-	fn clear_prefix(&mut self, _prefix: &[u8]) {
+	fn clear_prefix(
+		&mut self,
+		_prefix: &[u8],
+		_limit: Option<u32>,
+		_maybe_cursor: Option<&[u8]>,
+	) -> (Option<Vec<u8>>, u32) {
 		unimplemented!("clear_prefix is not supported in ReadOnlyExternalities")
 	}
 
@@ -123,7 +123,12 @@ impl Externalities for AsyncExternalities {
 		panic!("`kill_child_storage`: should not be used in async externalities!")
 	}
 
-	fn clear_prefix(&mut self, _prefix: &[u8]) {
+	fn clear_prefix(
+		&mut self,
+		_prefix: &[u8],
+		_limit: Option<u32>,
+		_maybe_cursor: Option<&[u8]>,
+	) -> (Option<Vec<u8>>, u32) {
 		panic!("`clear_prefix`: should not be used in async externalities!")
 	}
 
@@ -833,6 +833,16 @@ cfg_if! {
 				}
 			}
 
+			impl sp_session::SessionKeyOwnership<Block> for Runtime {
+				fn generate_key_ownership_proof(
+					_key_type: sp_core::crypto::KeyTypeId,
+					_key: Vec<u8>,
+					_validator: Vec<u8>,
+				) -> Option<sp_session::MembershipProof> {
+					None
+				}
+			}
+
 			impl sp_finality_grandpa::GrandpaApi<Block> for Runtime {
 				fn grandpa_authorities() -> sp_finality_grandpa::AuthorityList {
 					Vec::new()
@@ -1090,6 +1100,16 @@ cfg_if! {
 				}
 			}
 
+			impl sp_session::SessionKeyOwnership<Block> for Runtime {
+				fn generate_key_ownership_proof(
+					_key_type: sp_core::crypto::KeyTypeId,
+					_key: Vec<u8>,
+					_validator: Vec<u8>,
+				) -> Option<sp_session::MembershipProof> {
+					None
+				}
+			}
+
 			impl frame_system_rpc_runtime_api::AccountNonceApi<Block, AccountId, Index> for Runtime {
 				fn account_nonce(_account: AccountId) -> Index {
 					0
@@ -27,11 +27,12 @@ use sp_externalities::Extensions;
 use sc_service::{Configuration, NativeExecutionDispatch};
 use sp_runtime::traits::{Block as BlockT, Header as HeaderT, NumberFor};
 use sp_core::offchain::{OffchainWorkerExt, testing::TestOffchainExt};
+use sp_core::storage::{Storage, well_known_keys};
 use sp_keystore::{
 	SyncCryptoStorePtr, KeystoreExt,
 	testing::KeyStore,
 };
-use std::fmt::Debug;
+use std::{collections::BTreeMap, fmt::Debug, path::Path};
 
 impl BenchmarkCmd {
 	/// Runs the command and benchmarks the chain.
@@ -56,11 +57,19 @@ impl BenchmarkCmd {
 			if !handlebars_template_file.is_file() { return Err("Handlebars template file is invalid!".into()) };
 		}
 
+		if let Some(state_snapshot) = &self.state_snapshot {
+			if !state_snapshot.is_file() { return Err("State snapshot file is invalid!".into()) };
+		}
+
 		let spec = config.chain_spec;
 		let wasm_method = self.wasm_method.into();
 		let strategy = self.execution.unwrap_or(ExecutionStrategy::Native);
 
 		let genesis_storage = spec.build_storage()?;
+		let genesis_storage = match &self.state_snapshot {
+			Some(path) => snapshot_storage(path, genesis_storage)?,
+			None => genesis_storage,
+		};
 		let mut changes = Default::default();
 		let cache_size = Some(self.database_cache_size as usize);
 		let state = BenchmarkingState::<BB>::new(genesis_storage, cache_size, self.record_proof)?;
@@ -180,6 +189,19 @@ impl BenchmarkCmd {
 	}
 }
 
+/// The state of the snapshot at `path`, running the runtime code of `genesis`.
+fn snapshot_storage(path: &Path, genesis: Storage) -> Result<Storage> {
+	let code = genesis.top.get(well_known_keys::CODE)
+		.cloned()
+		.ok_or("Chain spec does not contain runtime code!")?;
+	let pairs = remote_externalities::export::read_snapshot(path)
+		.map_err(|e| format!("Failed to read state snapshot: {}", e))?;
+
+	let mut top = pairs.into_iter().map(|(k, v)| (k.0, v.0)).collect::<BTreeMap<_, _>>();
+	top.insert(well_known_keys::CODE.to_vec(), code);
+	Ok(Storage { top, children_default: Default::default() })
+}
+
 impl CliConfiguration for BenchmarkCmd {
 	fn shared_params(&self) -> &SharedParams {
 		&self.shared_params
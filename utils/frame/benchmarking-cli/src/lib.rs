@@ -124,6 +124,14 @@ pub struct BenchmarkCmd {
 	)]
 	pub wasm_method: WasmExecutionMethod,
 
+	/// Benchmark over the state of this remote-externalities state snapshot rather than over the
+	/// genesis state of the chain spec, such that worst case weights reflect real storage sizes.
+	///
+	/// The runtime code of the chain spec replaces the one of the snapshot, as the latter is
+	/// usually built without benchmarks. Best used with a snapshot of the entire state.
+	#[structopt(long, value_name = "PATH")]
+	pub state_snapshot: Option<std::path::PathBuf>,
+
 	/// Limit the memory the database cache can use.
 	#[structopt(long = "db-cache", value_name = "MiB", default_value = "128")]
 	pub database_cache_size: u32,
@@ -0,0 +1,296 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Export of scraped state into formats that can be consumed by non-Rust tooling.
+//!
+//! The key-value pairs of a state snapshot ([`read_snapshot`]) or of any `TestExternalities`
+//! ([`pairs_of`]) can be written as a JSON map ([`to_json`]) or as CSV ([`to_csv`]), with keys and
+//! values hex encoded. Optionally, every key is labeled with the storage item it belongs to, as
+//! found in the runtime metadata ([`Labels`]). Two sets of pairs can be compared with [`diff`].
+
+use std::{collections::BTreeMap, fs, path::Path};
+use codec::Decode;
+use frame_support::metadata::{DecodeDifferent, RuntimeMetadata, RuntimeMetadataPrefixed};
+use sp_core::{
+	hashing::twox_128,
+	storage::{StorageKey, StorageData},
+};
+use crate::{KeyPair, TestExternalities};
+
+/// Labels of storage keys, derived from the storage prefixes of pallets and their items.
+#[derive(Debug, Clone, Default)]
+pub struct Labels {
+	/// Prefixes along with their label, each being either a pallet prefix or a storage item
+	/// prefix.
+	prefixes: Vec<(Vec<u8>, String)>,
+}
+
+impl Labels {
+	/// Create an empty set of labels.
+	pub fn new() -> Self {
+		Default::default()
+	}
+
+	/// Label all keys of the storage item `item` of the pallet with storage prefix `pallet`.
+	///
+	/// Keys of the pallet that belong to no known item are labeled with the pallet only.
+	pub fn with_item(mut self, pallet: &str, item: &str) -> Self {
+		let pallet_prefix = twox_128(pallet.as_bytes()).to_vec();
+		if !self.prefixes.iter().any(|(prefix, _)| *prefix == pallet_prefix) {
+			self.prefixes.push((pallet_prefix.clone(), pallet.to_string()));
+		}
+		let item_prefix = [pallet_prefix, twox_128(item.as_bytes()).to_vec()].concat();
+		self.prefixes.push((item_prefix, format!("{}::{}", pallet, item)));
+		self
+	}
+
+	/// Create labels for all storage items of the SCALE encoded runtime metadata, as returned by
+	/// the `state_getMetadata` RPC.
+	pub fn from_metadata(metadata: &[u8]) -> Result<Self, &'static str> {
+		let metadata = RuntimeMetadataPrefixed::decode(&mut &*metadata)
+			.map_err(|_| "metadata decode failed")?;
+		let modules = match metadata.1 {
+			RuntimeMetadata::V13(metadata) => metadata.modules,
+			_ => return Err("unsupported metadata version"),
+		};
+
+		let mut labels = Self::new();
+		// decoded metadata only ever holds `DecodeDifferent::Decoded`.
+		for module in decoded(modules)? {
+			let storage = match module.storage {
+				Some(storage) => decoded(storage)?,
+				None => continue,
+			};
+			let prefix = decoded(storage.prefix)?;
+			for entry in decoded(storage.entries)? {
+				labels = labels.with_item(&prefix, &decoded(entry.name)?);
+			}
+		}
+		Ok(labels)
+	}
+
+	/// The label of `key`, if any.
+	///
+	/// Well known keys, such as `:code`, are labeled with themselves.
+	pub fn label(&self, key: &[u8]) -> Option<String> {
+		if key.starts_with(b":") {
+			return String::from_utf8(key.to_vec()).ok();
+		}
+		self.prefixes
+			.iter()
+			.filter(|(prefix, _)| key.starts_with(prefix))
+			.max_by_key(|(prefix, _)| prefix.len())
+			.map(|(_, label)| label.clone())
+	}
+}
+
+fn decoded<B, O>(value: DecodeDifferent<B, O>) -> Result<O, &'static str> {
+	match value {
+		DecodeDifferent::Decoded(value) => Ok(value),
+		DecodeDifferent::Encode(_) => Err("metadata is not decoded"),
+	}
+}
+
+/// Read the key-value pairs of the state snapshot at `path`.
+pub fn read_snapshot(path: &Path) -> Result<Vec<(StorageKey, StorageData)>, &'static str> {
+	let bytes = fs::read(path).map_err(|_| "fs::read failed.")?;
+	// the header and block that may follow the key-value pairs are of no interest here.
+	Decode::decode(&mut crate::snapshot_payload(&bytes)?).map_err(|_| "decode failed")
+}
+
+/// All the top-level key-value pairs of `ext`, including uncommitted changes, in key order.
+pub fn pairs_of(ext: &mut TestExternalities) -> Vec<(StorageKey, StorageData)> {
+	ext.execute_with(|| {
+		let mut pairs: Vec<KeyPair> = Vec::new();
+		let mut key = Vec::new();
+		while let Some(next) = sp_io::storage::next_key(&key) {
+			if let Some(value) = sp_io::storage::get(&next) {
+				pairs.push((StorageKey(next.clone()), StorageData(value)));
+			}
+			key = next;
+		}
+		pairs
+	})
+}
+
+fn hex(bytes: &[u8]) -> String {
+	format!("0x{}", sp_core::hexdisplay::HexDisplay::from(&bytes))
+}
+
+/// Export `pairs` as a JSON map from hex encoded key to hex encoded value.
+///
+/// If `labels` are given, each key maps to an object holding the `value` and its `label`, which
+/// is `null` if the key is unknown.
+pub fn to_json(pairs: &[(StorageKey, StorageData)], labels: Option<&Labels>) -> String {
+	let map = pairs
+		.iter()
+		.map(|(k, v)| {
+			let value = serde_json::Value::String(hex(&v.0));
+			let value = match labels {
+				Some(labels) => serde_json::json!({ "value": value, "label": labels.label(&k.0) }),
+				None => value,
+			};
+			(hex(&k.0), value)
+		})
+		.collect::<serde_json::Map<_, _>>();
+	serde_json::Value::Object(map).to_string()
+}
+
+/// Export `pairs` as CSV with a header row and the hex encoded `key` and `value` as columns.
+///
+/// If `labels` are given, a third `label` column is added, which is empty if the key is unknown.
+pub fn to_csv(pairs: &[(StorageKey, StorageData)], labels: Option<&Labels>) -> String {
+	let mut csv = String::from(if labels.is_some() { "key,value,label\n" } else { "key,value\n" });
+	for (k, v) in pairs {
+		csv.push_str(&hex(&k.0));
+		csv.push(',');
+		csv.push_str(&hex(&v.0));
+		if let Some(labels) = labels {
+			csv.push(',');
+			csv.push_str(&labels.label(&k.0).unwrap_or_default());
+		}
+		csv.push('\n');
+	}
+	csv
+}
+
+/// The change of the value of a single key between two states.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+	/// The key only exists in the new state.
+	Added(StorageData),
+	/// The key only exists in the old state.
+	Removed(StorageData),
+	/// The key exists in both states, with the old and the new value being different.
+	Changed(StorageData, StorageData),
+}
+
+/// All keys whose value differs between `old` and `new`, along with the change, in key order.
+pub fn diff(
+	old: Vec<(StorageKey, StorageData)>,
+	new: Vec<(StorageKey, StorageData)>,
+) -> Vec<(StorageKey, Change)> {
+	let mut changes = old.into_iter()
+		.map(|(k, v)| (k, Change::Removed(v)))
+		.collect::<BTreeMap<_, _>>();
+	for (k, v) in new {
+		match changes.remove(&k) {
+			Some(Change::Removed(old)) if old == v => {},
+			Some(Change::Removed(old)) => { changes.insert(k, Change::Changed(old, v)); },
+			_ => { changes.insert(k, Change::Added(v)); },
+		}
+	}
+	changes.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn pairs() -> Vec<KeyPair> {
+		let number = [twox_128(b"System"), twox_128(b"Number")].concat();
+		let unknown = [twox_128(b"System"), twox_128(b"Unknown")].concat();
+		vec![
+			(StorageKey(b":code".to_vec()), StorageData(vec![1])),
+			(StorageKey(number), StorageData(vec![2, 0, 0, 0])),
+			(StorageKey(unknown), StorageData(vec![])),
+			(StorageKey(vec![0xff]), StorageData(vec![3])),
+		]
+	}
+
+	#[test]
+	fn labels_keys() {
+		let labels = Labels::new().with_item("System", "Number").with_item("System", "Account");
+		let pairs = pairs();
+		let labeled = pairs.iter().map(|(k, _)| labels.label(&k.0)).collect::<Vec<_>>();
+		assert_eq!(
+			labeled,
+			vec![
+				Some(":code".to_string()),
+				Some("System::Number".to_string()),
+				Some("System".to_string()),
+				None,
+			],
+		);
+	}
+
+	#[test]
+	fn exports_json() {
+		let pairs = pairs();
+		let json: serde_json::Value = serde_json::from_str(&to_json(&pairs, None)).unwrap();
+		assert_eq!(json.as_object().unwrap().len(), 4);
+		assert_eq!(json["0x3a636f6465"], "0x01");
+
+		let labels = Labels::new().with_item("System", "Number");
+		let json: serde_json::Value =
+			serde_json::from_str(&to_json(&pairs, Some(&labels))).unwrap();
+		assert_eq!(json["0x3a636f6465"]["value"], "0x01");
+		assert_eq!(json["0x3a636f6465"]["label"], ":code");
+		assert_eq!(json["0xff"]["label"], serde_json::Value::Null);
+	}
+
+	#[test]
+	fn exports_csv() {
+		let pairs = pairs();
+		let csv = to_csv(&pairs, None);
+		let mut lines = csv.lines();
+		assert_eq!(lines.next(), Some("key,value"));
+		assert_eq!(lines.next(), Some("0x3a636f6465,0x01"));
+		assert_eq!(lines.count(), 3);
+
+		let labels = Labels::new().with_item("System", "Number");
+		let csv = to_csv(&pairs, Some(&labels));
+		let lines = csv.lines().collect::<Vec<_>>();
+		assert_eq!(lines[0], "key,value,label");
+		assert!(lines[2].ends_with(",0x02000000,System::Number"));
+		assert_eq!(lines[4], "0xff,0x03,");
+	}
+
+	#[test]
+	fn exports_externalities() {
+		let mut ext = TestExternalities::new_empty();
+		for (k, v) in pairs().into_iter().rev() {
+			ext.insert(k.0, v.0);
+		}
+		ext.execute_with(|| sp_io::storage::set(b":extra", &[4]));
+
+		let exported = pairs_of(&mut ext);
+		assert_eq!(exported.len(), 5);
+		assert!(exported.windows(2).all(|w| w[0].0 < w[1].0));
+		assert!(exported.contains(&(StorageKey(b":extra".to_vec()), StorageData(vec![4]))));
+		assert!(pairs().iter().all(|pair| exported.contains(pair)));
+	}
+
+	#[test]
+	fn diffs_pairs() {
+		let old = pairs();
+		let mut new = pairs();
+		new[0].1 = StorageData(vec![5]);
+		new.remove(1);
+		new.push((StorageKey(vec![0xfe]), StorageData(vec![6])));
+
+		assert_eq!(
+			diff(old.clone(), new),
+			vec![
+				(old[1].0.clone(), Change::Removed(StorageData(vec![2, 0, 0, 0]))),
+				(StorageKey(b":code".to_vec()), Change::Changed(StorageData(vec![1]), StorageData(vec![5]))),
+				(StorageKey(vec![0xfe]), Change::Added(StorageData(vec![6]))),
+			],
+		);
+		assert!(diff(old.clone(), old).is_empty());
+	}
+}
@@ -0,0 +1,106 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Transaction fee estimation against scraped state.
+//!
+//! [`estimate_fee`] calls the `TransactionPaymentApi` of the runtime in `:code` of the
+//! externalities, such that fee calculations can be regression tested against real chain state.
+//! To test another runtime against the same state, inject its code under `:code` beforehand.
+//!
+//! The runtime api does not check the signature of the extrinsic, hence the fee of a call sent by
+//! any account can be estimated with an extrinsic that is signed by that account with a dummy
+//! signature:
+//!
+//! ```ignore
+//! let executor = NativeExecutor::<Executor>::new(WasmExecutionMethod::Interpreted, None, 1);
+//! let uxt = UncheckedExtrinsic::new_signed(call, account.into(), Signature::default(), extra);
+//! let estimate = fee::estimate_fee::<Block, Balance, _>(&ext, &executor, &uxt)?;
+//! println!("weight: {}, fee: {}", estimate.info.weight, estimate.final_fee());
+//! ```
+
+use codec::{Decode, Encode};
+use sp_core::traits::CodeExecutor;
+use sp_runtime::traits::{AtLeast32BitUnsigned, Block as BlockT, NumberFor};
+use sp_state_machine::{backend::BackendRuntimeCode, ExecutionStrategy, StateMachine};
+use crate::TestExternalities;
+
+pub use pallet_transaction_payment_rpc_runtime_api::{FeeDetails, InclusionFee, RuntimeDispatchInfo};
+
+/// The estimated fee of an extrinsic.
+#[derive(Debug, PartialEq, Eq)]
+pub struct FeeEstimate<Balance> {
+	/// The dispatch info, holding the weight and the partial fee.
+	pub info: RuntimeDispatchInfo<Balance>,
+	/// The breakdown of the fee.
+	pub details: FeeDetails<Balance>,
+}
+
+impl<Balance: AtLeast32BitUnsigned + Copy> FeeEstimate<Balance> {
+	/// The full fee to be paid for the extrinsic, excluding any tip.
+	pub fn final_fee(&self) -> Balance {
+		self.details.final_fee()
+	}
+}
+
+/// Estimate the fee of `uxt` in the state of `ext`, executing the runtime with `executor`.
+pub fn estimate_fee<B, Balance, E>(
+	ext: &TestExternalities,
+	executor: &E,
+	uxt: &B::Extrinsic,
+) -> Result<FeeEstimate<Balance>, &'static str>
+where
+	B: BlockT,
+	Balance: Decode,
+	E: CodeExecutor + Clone + 'static,
+{
+	let call_data = (uxt, uxt.encoded_size() as u32).encode();
+	let info = call_runtime::<B, _>(ext, executor, "TransactionPaymentApi_query_info", &call_data)?;
+	let details =
+		call_runtime::<B, _>(ext, executor, "TransactionPaymentApi_query_fee_details", &call_data)?;
+
+	Ok(FeeEstimate {
+		info: Decode::decode(&mut &*info).map_err(|_| "dispatch info decode failed")?,
+		details: Decode::decode(&mut &*details).map_err(|_| "fee details decode failed")?,
+	})
+}
+
+/// Call `method` of the runtime in `:code` of `ext`, discarding any changes made to the state.
+fn call_runtime<B: BlockT, E: CodeExecutor + Clone + 'static>(
+	ext: &TestExternalities,
+	executor: &E,
+	method: &str,
+	call_data: &[u8],
+) -> Result<Vec<u8>, &'static str> {
+	let runtime_code = BackendRuntimeCode::new(&ext.backend).runtime_code()?;
+	let mut changes = Default::default();
+	StateMachine::<_, _, NumberFor<B>, _>::new(
+		&ext.backend,
+		None,
+		&mut changes,
+		executor,
+		method,
+		call_data,
+		Default::default(),
+		&runtime_code,
+		sp_core::testing::TaskExecutor::new(),
+	)
+	.execute(ExecutionStrategy::AlwaysWasm)
+	.map_err(|e| {
+		log::error!(target: crate::LOG_TARGET, "failed to execute {}: {:?}", method, e);
+		"runtime call failed"
+	})
+}
@@ -0,0 +1,241 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Forking off a live chain into a private network.
+//!
+//! [`ForkOff`] turns scraped state into the genesis of a raw chain spec, which can be started as
+//! is to, for example, try out governance actions against production state. The scraped state is
+//! put into a base chain spec, typically the raw `dev` spec of a node running the same runtime,
+//! and a standard set of mutations is applied:
+//!
+//! - the storage of the pallets managing the validator set is taken from the base spec, such that
+//!   the authorities of the base spec can produce and finalize blocks;
+//! - the sudo key is replaced;
+//! - the runtime code is replaced, in which case the last runtime upgrade is forgotten as well, so
+//!   that migrations of the new code are executed.
+//!
+//! ```ignore
+//! let mut ext = Builder::<Block>::new().mode(Mode::Online(online_config)).build().await?;
+//! let spec = ForkOff::new()
+//! 	.sudo_key(alice.encode())
+//! 	.code(wasm)
+//! 	.fork(export::pairs_of(&mut ext), &fs::read_to_string("dev-raw.json")?)?;
+//! fs::write("fork-raw.json", spec)?;
+//! ```
+
+use std::collections::BTreeMap;
+use sp_core::{
+	hashing::twox_128,
+	storage::{well_known_keys, StorageKey, StorageData},
+};
+
+/// Pallets whose storage is taken from the base spec by default.
+pub const DEFAULT_VALIDATOR_PALLETS: &[&str] = &[
+	"Session", "Babe", "Grandpa", "Aura", "Authorship", "ImOnline", "AuthorityDiscovery",
+];
+
+/// Well known keys holding validator set information, taken from the base spec as well.
+const VALIDATOR_WELL_KNOWN_KEYS: &[&[u8]] = &[b":grandpa_authorities"];
+
+/// Configuration of the mutations applied when forking off.
+#[derive(Debug, Clone)]
+pub struct ForkOff {
+	/// Pallets whose storage is replaced by the one of the base spec.
+	validator_pallets: Vec<String>,
+	/// The encoded account id of the new sudo key, if any.
+	sudo_key: Option<Vec<u8>>,
+	/// The new runtime code, if any.
+	code: Option<Vec<u8>>,
+}
+
+impl Default for ForkOff {
+	fn default() -> Self {
+		Self {
+			validator_pallets: DEFAULT_VALIDATOR_PALLETS.iter().map(|p| p.to_string()).collect(),
+			sudo_key: None,
+			code: None,
+		}
+	}
+}
+
+impl ForkOff {
+	/// Create a new fork off configuration, taking [`DEFAULT_VALIDATOR_PALLETS`] from the base
+	/// spec.
+	pub fn new() -> Self {
+		Default::default()
+	}
+
+	/// Take the storage of the given pallets, instead of [`DEFAULT_VALIDATOR_PALLETS`], from the
+	/// base spec.
+	pub fn validator_pallets(mut self, pallets: Vec<String>) -> Self {
+		self.validator_pallets = pallets;
+		self
+	}
+
+	/// Replace the key of the sudo pallet with the given encoded account id.
+	pub fn sudo_key(mut self, who: Vec<u8>) -> Self {
+		self.sudo_key = Some(who);
+		self
+	}
+
+	/// Replace the runtime code with `code`.
+	pub fn code(mut self, code: Vec<u8>) -> Self {
+		self.code = Some(code);
+		self
+	}
+
+	/// Apply the mutations to `state` and put it as genesis into the raw chain spec `base_spec`.
+	///
+	/// Everything but the genesis storage is retained from the base spec. The resulting raw chain
+	/// spec is returned as JSON.
+	pub fn fork(
+		&self,
+		state: Vec<(StorageKey, StorageData)>,
+		base_spec: &str,
+	) -> Result<String, &'static str> {
+		let mut spec: serde_json::Value =
+			serde_json::from_str(base_spec).map_err(|_| "base spec is not valid JSON")?;
+		let top = spec
+			.pointer_mut("/genesis/raw/top")
+			.ok_or("base spec is not a raw chain spec")?;
+		let base: BTreeMap<StorageKey, StorageData> =
+			serde_json::from_value(top.take()).map_err(|_| "base spec storage decode failed")?;
+
+		let forked = self.mutate(state.into_iter().collect(), &base);
+		log::info!(target: crate::LOG_TARGET, "forked off state with {} keys", forked.len());
+		*top = serde_json::to_value(forked).map_err(|_| "forked storage encode failed")?;
+
+		serde_json::to_string_pretty(&spec).map_err(|_| "chain spec encode failed")
+	}
+
+	fn mutate(
+		&self,
+		mut state: BTreeMap<StorageKey, StorageData>,
+		base: &BTreeMap<StorageKey, StorageData>,
+	) -> BTreeMap<StorageKey, StorageData> {
+		let from_base = |key: &StorageKey| {
+			VALIDATOR_WELL_KNOWN_KEYS.iter().any(|k| *k == &key.0[..]) ||
+				self.validator_pallets
+					.iter()
+					.any(|pallet| key.0.starts_with(&twox_128(pallet.as_bytes())))
+		};
+		state.retain(|key, _| !from_base(key));
+		state.extend(base.iter().filter(|(key, _)| from_base(key)).map(|(k, v)| (k.clone(), v.clone())));
+
+		if let Some(who) = &self.sudo_key {
+			state.insert(storage_value_key("Sudo", "Key"), StorageData(who.clone()));
+		}
+
+		if let Some(code) = &self.code {
+			state.insert(StorageKey(well_known_keys::CODE.to_vec()), StorageData(code.clone()));
+			state.remove(&storage_value_key("System", "LastRuntimeUpgrade"));
+		}
+
+		state
+	}
+}
+
+fn storage_value_key(pallet: &str, item: &str) -> StorageKey {
+	StorageKey([twox_128(pallet.as_bytes()), twox_128(item.as_bytes())].concat())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn base_spec(top: &[(StorageKey, StorageData)]) -> String {
+		let top = top.iter().cloned().collect::<BTreeMap<_, _>>();
+		serde_json::json!({
+			"name": "Development",
+			"id": "dev",
+			"bootNodes": [],
+			"genesis": { "raw": { "top": top, "childrenDefault": {} } },
+		}).to_string()
+	}
+
+	fn top_of(spec: &str) -> BTreeMap<StorageKey, StorageData> {
+		let spec: serde_json::Value = serde_json::from_str(spec).unwrap();
+		serde_json::from_value(spec["genesis"]["raw"]["top"].clone()).unwrap()
+	}
+
+	#[test]
+	fn forks_off_with_mutations() {
+		let validators = storage_value_key("Session", "Validators");
+		let grandpa = StorageKey(b":grandpa_authorities".to_vec());
+		let account = storage_value_key("System", "Account");
+		let upgrade = storage_value_key("System", "LastRuntimeUpgrade");
+		let sudo = storage_value_key("Sudo", "Key");
+		let queued = storage_value_key("Session", "QueuedKeys");
+		let code = StorageKey(well_known_keys::CODE.to_vec());
+
+		let state = vec![
+			(validators.clone(), StorageData(vec![1])),
+			(queued.clone(), StorageData(vec![1])),
+			(grandpa.clone(), StorageData(vec![1])),
+			(account.clone(), StorageData(vec![1])),
+			(upgrade.clone(), StorageData(vec![1])),
+			(sudo.clone(), StorageData(vec![1])),
+			(code.clone(), StorageData(vec![1])),
+		];
+		let base = base_spec(&[
+			(validators.clone(), StorageData(vec![2])),
+			(grandpa.clone(), StorageData(vec![2])),
+			(account.clone(), StorageData(vec![2])),
+		]);
+
+		let spec = ForkOff::new().sudo_key(vec![3]).code(vec![4]).fork(state, &base).unwrap();
+		let top = top_of(&spec);
+
+		// validator set of the base spec, including the removal of keys it does not have.
+		assert_eq!(top.get(&validators), Some(&StorageData(vec![2])));
+		assert_eq!(top.get(&grandpa), Some(&StorageData(vec![2])));
+		assert_eq!(top.get(&queued), None);
+		// scraped state otherwise.
+		assert_eq!(top.get(&account), Some(&StorageData(vec![1])));
+		assert_eq!(top.get(&sudo), Some(&StorageData(vec![3])));
+		assert_eq!(top.get(&code), Some(&StorageData(vec![4])));
+		assert_eq!(top.get(&upgrade), None);
+		assert_eq!(top.len(), 5);
+
+		let spec: serde_json::Value = serde_json::from_str(&spec).unwrap();
+		assert_eq!(spec["id"], "dev");
+	}
+
+	#[test]
+	fn keeps_code_and_upgrade_if_not_replaced() {
+		let upgrade = storage_value_key("System", "LastRuntimeUpgrade");
+		let code = StorageKey(well_known_keys::CODE.to_vec());
+		let state = vec![
+			(upgrade.clone(), StorageData(vec![1])),
+			(code.clone(), StorageData(vec![1])),
+		];
+
+		let spec = ForkOff::new().fork(state, &base_spec(&[])).unwrap();
+		let top = top_of(&spec);
+		assert_eq!(top.get(&upgrade), Some(&StorageData(vec![1])));
+		assert_eq!(top.get(&code), Some(&StorageData(vec![1])));
+	}
+
+	#[test]
+	fn requires_raw_base_spec() {
+		let spec = serde_json::json!({ "genesis": { "runtime": {} } }).to_string();
+		assert_eq!(
+			ForkOff::new().fork(vec![], &spec),
+			Err("base spec is not a raw chain spec"),
+		);
+	}
+}
@@ -20,6 +20,9 @@
 //! An equivalent of `sp_io::TestExternalities` that can load its state from a remote substrate
 //! based chain, or a local state snapshot file.
 //!
+//! Memory-mapping snapshots, connecting through a proxy, exporting to a node database and
+//! estimating fees are behind the `mmap`, `proxy`, `node-db` and `fee` features respectively.
+//!
 //! #### Runtime to Test Against
 //!
 //! While not absolutely necessary, you most likely need a `Runtime` equivalent in your test setup
@@ -102,24 +105,74 @@
 //! ```
 
 use std::{
+	collections::BTreeMap,
+	convert::TryFrom,
+	fmt::Debug,
 	fs,
+	future::Future,
+	cmp::Ordering,
+	io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+	ops::{Deref, DerefMut},
 	path::{Path, PathBuf},
+	pin::Pin,
+	task::{Context, Poll},
+	time::{Duration, Instant},
 };
 use log::*;
-use sp_core::hashing::twox_128;
+use blake2_rfc::blake2b::Blake2b;
+use sp_core::{crypto::AccountId32, hashing::twox_128};
 pub use sp_io::TestExternalities;
 use sp_core::{
 	hexdisplay::HexDisplay,
-	storage::{StorageKey, StorageData},
+	storage::{
+		ChildInfo, PrefixedStorageKey, StorageKey, StorageData, StorageChangeSet,
+		well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX,
+	},
+};
+use codec::{Encode, Decode, Compact, FullCodec, FullEncode, EncodeLike};
+use frame_support::{
+	Blake2_128Concat, StorageHasher,
+	storage::{StorageValue, StorageMap, StorageDoubleMap},
 };
-use codec::{Encode, Decode};
-use sp_runtime::traits::Block as BlockT;
-use jsonrpsee_ws_client::{WsClientBuilder, WsClient};
+use sp_runtime::{
+	Justifications,
+	generic::SignedBlock,
+	traits::{Block as BlockT, Header as HeaderT, NumberFor},
+};
+use jsonrpsee_ws_client::{WsClientBuilder, WsClient, DeserializeOwned};
+use serde::Deserialize;
+use futures::future::{select, BoxFuture, Either};
+use futures_timer::Delay;
+use stats::StorageStats;
+#[cfg(feature = "proxy")]
+use proxy::Proxy;
+
+pub mod export;
+#[cfg(feature = "fee")]
+pub mod fee;
+pub mod fork_off;
+#[cfg(feature = "node-db")]
+pub mod node_db;
+#[cfg(feature = "proxy")]
+pub mod proxy;
+pub mod stats;
 
 type KeyPair = (StorageKey, StorageData);
+/// The key-value pairs of a default child trie, along with its storage key, without prefix.
+type ChildTrie = (Vec<u8>, Vec<KeyPair>);
 
 const LOG_TARGET: &str = "remote-ext";
 const DEFAULT_TARGET: &str = "wss://rpc.polkadot.io";
+/// Number of consecutive failed RPC calls after which we fail over to the next endpoint.
+const MAX_RPC_RETRIES: usize = 3;
+/// Default of [`OnlineConfig::request_timeout`].
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+/// Default of [`OnlineConfig::stall_timeout`].
+const DEFAULT_STALL_TIMEOUT: Duration = Duration::from_secs(300);
+/// Number of keys whose changes are queried at once when refreshing a base snapshot.
+const QUERY_STORAGE_BATCH: usize = 256;
+/// Number of key-value pairs inserted into the externalities at once.
+const INSERT_BATCH: usize = 1024;
 
 jsonrpsee_proc_macros::rpc_client_api! {
 	RpcApi<B: BlockT> {
@@ -132,11 +185,53 @@ jsonrpsee_proc_macros::rpc_client_api! {
 			start_key: Option<StorageKey>,
 			hash: Option<B::Hash>,
 		) -> Vec<StorageKey>;
+		#[rpc(method = "state_queryStorage", positional_params)]
+		fn query_storage(
+			keys: Vec<StorageKey>,
+			from: B::Hash,
+			to: Option<B::Hash>,
+		) -> Vec<StorageChangeSet<B::Hash>>;
 		#[rpc(method = "chain_getFinalizedHead", positional_params)]
 		fn finalized_head() -> B::Hash;
+		#[rpc(method = "chain_getBlockHash", positional_params)]
+		fn get_block_hash(number: Option<NumberFor<B>>) -> Option<B::Hash>;
+		#[rpc(method = "chain_getHeader", positional_params)]
+		fn get_header(hash: B::Hash) -> B::Header;
+		#[rpc(method = "chain_getBlock", positional_params)]
+		fn get_block(hash: Option<B::Hash>) -> Option<RpcSignedBlock<B::Header>>;
+		#[rpc(method = "state_getMetadata", positional_params)]
+		fn get_metadata(hash: Option<B::Hash>) -> sp_core::Bytes;
+		#[rpc(method = "childstate_getKeys", positional_params)]
+		fn get_child_keys(
+			child_storage_key: PrefixedStorageKey,
+			prefix: StorageKey,
+			hash: Option<B::Hash>,
+		) -> Vec<StorageKey>;
+		#[rpc(method = "childstate_getStorage", positional_params)]
+		fn get_child_storage(
+			child_storage_key: PrefixedStorageKey,
+			key: StorageKey,
+			hash: Option<B::Hash>,
+		) -> Option<StorageData>;
 	}
 }
 
+/// A signed block as returned by `chain_getBlock`, with the extrinsics still encoded.
+///
+/// Not all block types are deserializable, so the extrinsics are decoded separately.
+#[derive(Deserialize)]
+struct RpcSignedBlock<H> {
+	block: RpcBlock<H>,
+	justifications: Option<Justifications>,
+}
+
+/// The block part of [`RpcSignedBlock`].
+#[derive(Deserialize)]
+struct RpcBlock<H> {
+	header: H,
+	extrinsics: Vec<sp_core::Bytes>,
+}
+
 /// The execution mode.
 #[derive(Clone)]
 pub enum Mode<B: BlockT> {
@@ -152,6 +247,68 @@ impl<B: BlockT> Default for Mode<B> {
 	}
 }
 
+/// A filter over hex encoded keys, such as `0x26aa394eea5630e07c48ae0c9558cef7`.
+#[derive(Clone, Debug)]
+pub enum KeyFilter {
+	/// Keep the keys with a match of the regex anywhere in them.
+	#[cfg(feature = "regex")]
+	Regex(regex::Regex),
+	/// Keep the keys matching the glob as a whole, where `*` matches any number of characters and
+	/// `?` any single character.
+	Glob(String),
+}
+
+impl KeyFilter {
+	/// Whether `key` passes the filter.
+	pub fn matches(&self, key: &[u8]) -> bool {
+		let key = format!("0x{}", HexDisplay::from(&key));
+		match self {
+			#[cfg(feature = "regex")]
+			KeyFilter::Regex(regex) => regex.is_match(&key),
+			KeyFilter::Glob(glob) => glob_matches(glob.to_lowercase().as_bytes(), key.as_bytes()),
+		}
+	}
+}
+
+/// Whether `text` matches `glob` as a whole.
+fn glob_matches(glob: &[u8], text: &[u8]) -> bool {
+	// position of the last `*` in `glob` and of the text it was tried at, to backtrack to.
+	let (mut g, mut t, mut star) = (0, 0, None);
+	while t < text.len() {
+		match glob.get(g) {
+			Some(b'*') => {
+				star = Some((g, t));
+				g += 1;
+			},
+			Some(&c) if c == b'?' || c == text[t] => {
+				g += 1;
+				t += 1;
+			},
+			_ => match star {
+				Some((star_g, star_t)) => {
+					// let the last `*` match one more character.
+					g = star_g + 1;
+					t = star_t + 1;
+					star = Some((star_g, star_t + 1));
+				},
+				None => return false,
+			},
+		}
+	}
+	glob[g..].iter().all(|c| *c == b'*')
+}
+
+/// How the state of an additional source is combined with the state loaded before it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Merge {
+	/// Keys of the source overwrite equal keys loaded before it.
+	Overwrite,
+	/// Keys loaded before the source take precedence, its keys only fill the gaps.
+	KeepExisting,
+	/// Keys of the source are prefixed with the given namespace, keeping both states apart.
+	Namespace(Vec<u8>),
+}
+
 /// configuration of the online execution.
 ///
 /// A state snapshot config must be present.
@@ -162,24 +319,147 @@ pub struct OfflineConfig {
 }
 
 /// Description of the transport protocol.
+///
+/// Can hold multiple endpoints, which are failed over to in order if the one in use can not be
+/// connected to or keeps failing.
 #[derive(Debug)]
 pub struct Transport {
-	uri: String,
+	uris: Vec<String>,
+	/// Index of the endpoint in use in `uris`.
+	current: usize,
 	client: Option<WsClient>,
+	/// When the last RPC call succeeded, or the first connection was made.
+	last_progress: Option<Instant>,
+	/// Proxy to connect through, if any.
+	#[cfg(feature = "proxy")]
+	proxy: Option<Proxy>,
+	/// Task running the local tunnel through `proxy` to the endpoint in use.
+	#[cfg(feature = "proxy")]
+	tunnel: Option<tokio::task::JoinHandle<()>>,
+}
+
+impl Transport {
+	/// Connect to the endpoints through `proxy`.
+	///
+	/// Only `ws://` endpoints can be connected to through a proxy, see [`proxy`].
+	#[cfg(feature = "proxy")]
+	pub fn with_proxy(mut self, proxy: Proxy) -> Self {
+		self.proxy = Some(proxy);
+		self
+	}
+
+	fn uri(&self) -> &str {
+		&self.uris[self.current]
+	}
+
+	/// The uri to connect the ws client to for the endpoint in use.
+	///
+	/// With a proxy, this opens a local tunnel to the endpoint, replacing the previous one.
+	#[cfg(feature = "proxy")]
+	async fn connect_uri(&mut self) -> Result<String, &'static str> {
+		self.close_tunnel();
+		match self.proxy.as_ref() {
+			Some(p) => {
+				let (uri, tunnel) = proxy::tunnel(p, self.uri()).await?;
+				self.tunnel = Some(tunnel);
+				Ok(uri)
+			},
+			None => Ok(self.uri().to_string()),
+		}
+	}
+
+	#[cfg(not(feature = "proxy"))]
+	async fn connect_uri(&mut self) -> Result<String, &'static str> {
+		Ok(self.uri().to_string())
+	}
+
+	#[cfg(feature = "proxy")]
+	fn close_tunnel(&mut self) {
+		if let Some(tunnel) = self.tunnel.take() {
+			tunnel.abort();
+		}
+	}
 }
 
 impl Clone for Transport {
 	fn clone(&self) -> Self {
-		Self { uri: self.uri.clone(), client: None }
+		Self {
+			uris: self.uris.clone(),
+			current: self.current,
+			client: None,
+			last_progress: None,
+			#[cfg(feature = "proxy")]
+			proxy: self.proxy.clone(),
+			#[cfg(feature = "proxy")]
+			tunnel: None,
+		}
+	}
+}
+
+#[cfg(feature = "proxy")]
+impl Drop for Transport {
+	fn drop(&mut self) {
+		self.close_tunnel();
 	}
 }
 
 impl From<String> for Transport {
-	fn from(t: String) -> Self {
-		Self { uri: t, client: None }
+	fn from(uri: String) -> Self {
+		Self {
+			uris: vec![uri],
+			current: 0,
+			client: None,
+			last_progress: None,
+			#[cfg(feature = "proxy")]
+			proxy: None,
+			#[cfg(feature = "proxy")]
+			tunnel: None,
+		}
 	}
 }
 
+impl TryFrom<Vec<String>> for Transport {
+	type Error = &'static str;
+
+	/// Create a transport from a list of endpoints, tried in order. Fails if the list is empty.
+	fn try_from(uris: Vec<String>) -> Result<Self, Self::Error> {
+		let mut uris = uris.into_iter();
+		let first = uris.next().ok_or("at least one endpoint must be provided")?;
+		let mut transport = Self::from(first);
+		transport.uris.extend(uris);
+		Ok(transport)
+	}
+}
+
+/// The `System::Account` map, holding the nonce and balances of each account.
+pub const SYSTEM_ACCOUNT: (&str, &str) = ("System", "Account");
+/// The `Balances::Locks` map, holding the balance locks of each account.
+pub const BALANCES_LOCKS: (&str, &str) = ("Balances", "Locks");
+
+/// The key of the storage value `item` of `pallet`, which is also the prefix of the keys of the
+/// storage map `item`.
+pub fn storage_value_key(pallet: &str, item: &str) -> StorageKey {
+	StorageKey([twox_128(pallet.as_bytes()), twox_128(item.as_bytes())].concat())
+}
+
+/// The key of `key` in the storage map `item` of `pallet`, hashed with `H`.
+pub fn storage_map_key<H: StorageHasher, K: Encode>(
+	pallet: &str,
+	item: &str,
+	key: &K,
+) -> StorageKey {
+	let StorageKey(prefix) = storage_value_key(pallet, item);
+	StorageKey([&prefix[..], H::hash(&key.encode()).as_ref()].concat())
+}
+
+/// The key of `account` in the map `item` of `pallet`, keyed with `Blake2_128Concat`.
+///
+/// Used as a prefix, it also covers the entries of `account` in double maps keyed by account
+/// first.
+pub fn account_key(pallet: &str, item: &str, account: &AccountId32) -> StorageKey {
+	storage_map_key::<Blake2_128Concat, _>(pallet, item, account)
+}
+
 /// Configuration of the online execution.
 ///
 /// A state snapshot config may be present and will be written to in that case.
@@ -189,24 +469,94 @@ pub struct OnlineConfig<B: BlockT> {
 	pub at: Option<B::Hash>,
 	/// An optional state snapshot file to WRITE to, not for reading. Not written if set to `None`.
 	pub state_snapshot: Option<SnapshotConfig>,
-	/// The modules to scrape. If empty, entire chain state will be scraped.
+	/// The modules to scrape. If empty, and there are no `prefixes` either, entire chain state
+	/// will be scraped.
 	pub modules: Vec<String>,
-	/// Transport config.
+	/// Raw key prefixes to scrape in addition to the `modules`, e.g. the entries of some
+	/// accounts, see [`Self::with_accounts`].
+	pub prefixes: Vec<StorageKey>,
+	/// Transport config. If it holds multiple endpoints, the scrape fails over to the next one
+	/// when the current one fails, still at the same block.
 	pub transport: Transport,
+	/// Rebuild the state root from the downloaded keys and values and compare it against the
+	/// `state_root` of the block header at `at`, failing on mismatch.
+	///
+	/// Only possible if the entire chain state is scraped, i.e. `modules` and `prefixes` are empty
+	/// and there is no `key_filter`. Otherwise, building fails before anything is downloaded.
+	pub check_state_root: bool,
+	/// Also download the block at `at`, including its justifications, and store it in the state
+	/// snapshot, if any. This allows the block to be re-executed fully offline later on.
+	pub scrape_block: bool,
+	/// Also download the runtime metadata at `at` and store it in the state snapshot, if any, such
+	/// that the snapshot describes its own storage layout, e.g. to name its pallets.
+	pub scrape_metadata: bool,
+	/// Also scrape the default child tries whose roots are in the scraped state, e.g. those of
+	/// crowdloan contributions, and store them in the state snapshot, if any.
+	///
+	/// If only some `modules` or `prefixes` are scraped, the roots of all default child tries are
+	/// scraped along, as they are not stored under the prefix of the pallet owning them.
+	pub scrape_child_tries: bool,
+	/// Collect the number of keys and value bytes per pallet while scraping, exposed as
+	/// [`RemoteExternalities::stats`].
+	pub storage_stats: bool,
+	/// Only keep the keys matching this filter, applied before their values are downloaded.
+	pub key_filter: Option<KeyFilter>,
+	/// An existing state snapshot, taken at an earlier block of the same chain, to refresh rather
+	/// than downloading every value again.
+	///
+	/// Only the values of keys that are new since then are downloaded, all others are updated
+	/// with the changes reported by `state_queryStorage` between both blocks.
+	pub base_snapshot: Option<SnapshotConfig>,
+	/// Time after which a single RPC call is considered failed. `None` waits forever.
+	pub request_timeout: Option<Duration>,
+	/// Time without any successful RPC call after which scraping is aborted, rather than retried
+	/// any further. `None` retries as long as failing over allows.
+	pub stall_timeout: Option<Duration>,
 }
 
 impl<B: BlockT> Default for OnlineConfig<B> {
 	fn default() -> Self {
 		Self {
-			transport: Transport { uri: DEFAULT_TARGET.to_string(), client: None },
+			transport: DEFAULT_TARGET.to_string().into(),
 			at: None,
 			state_snapshot: None,
 			modules: vec![],
+			prefixes: vec![],
+			check_state_root: false,
+			scrape_block: false,
+			scrape_metadata: false,
+			scrape_child_tries: false,
+			storage_stats: false,
+			key_filter: None,
+			base_snapshot: None,
+			request_timeout: Some(DEFAULT_REQUEST_TIMEOUT),
+			stall_timeout: Some(DEFAULT_STALL_TIMEOUT),
 		}
 	}
 }
 
 impl<B: BlockT> OnlineConfig<B> {
+	/// Scrape the entries of each of `accounts` in each of the maps `items`, given as
+	/// `(pallet, item)` and keyed by account with `Blake2_128Concat`, such as [`SYSTEM_ACCOUNT`].
+	///
+	/// Unless any `modules` are configured as well, nothing else is scraped.
+	pub fn with_accounts(mut self, accounts: &[AccountId32], items: &[(&str, &str)]) -> Self {
+		for (pallet, item) in items {
+			self.prefixes.extend(accounts.iter().map(|account| account_key(pallet, item, account)));
+		}
+		self
+	}
+
+	/// Check that the options of this config can be combined, before anything is downloaded.
+	fn validate(&self) -> Result<(), &'static str> {
+		let partial =
+			!self.modules.is_empty() || !self.prefixes.is_empty() || self.key_filter.is_some();
+		if self.check_state_root && partial {
+			return Err("state root can only be checked when scraping the entire chain state");
+		}
+		Ok(())
+	}
+
 	/// Return rpc (ws) client.
 	fn rpc_client(&self) -> &WsClient {
 		self.transport.client.as_ref().expect("ws client must have been initialized by now; qed.")
@@ -218,17 +568,33 @@ impl<B: BlockT> OnlineConfig<B> {
 pub struct SnapshotConfig {
 	/// The path to the snapshot file.
 	pub path: PathBuf,
+	/// Memory-map the snapshot file when loading it, rather than reading it.
+	///
+	/// The file must not be modified while it is being loaded.
+	#[cfg(feature = "mmap")]
+	pub mmap: bool,
 }
 
 impl SnapshotConfig {
 	pub fn new<P: Into<PathBuf>>(path: P) -> Self {
-		Self { path: path.into() }
+		Self {
+			path: path.into(),
+			#[cfg(feature = "mmap")]
+			mmap: false,
+		}
+	}
+
+	/// Memory-map the snapshot file when loading it, see [`SnapshotConfig::mmap`].
+	#[cfg(feature = "mmap")]
+	pub fn with_mmap(mut self) -> Self {
+		self.mmap = true;
+		self
 	}
 }
 
 impl Default for SnapshotConfig {
 	fn default() -> Self {
-		Self { path: Path::new("SNAPSHOT").into() }
+		Self::new("SNAPSHOT")
 	}
 }
 
@@ -238,13 +604,422 @@ pub struct Builder<B: BlockT> {
 	inject: Vec<KeyPair>,
 	/// connectivity mode, online or offline.
 	mode: Mode<B>,
+	/// Additional remote sources, loaded in order after `mode`.
+	sources: Vec<(OnlineConfig<B>, Merge)>,
+	/// Callbacks handed over to the built externalities, or run if building fails.
+	teardown: Teardown,
+	/// Callbacks run inside the built externalities, in order.
+	post_build: Vec<Box<dyn FnOnce() + Send>>,
 }
 
 // NOTE: ideally we would use `DefaultNoBound` here, but not worth bringing in frame-support for
 // that.
 impl<B: BlockT> Default for Builder<B> {
 	fn default() -> Self {
-		Self { inject: Default::default(), mode: Default::default() }
+		Self {
+			inject: Default::default(),
+			mode: Default::default(),
+			sources: Default::default(),
+			teardown: Default::default(),
+			post_build: Default::default(),
+		}
+	}
+}
+
+/// The header, block and SCALE encoded runtime metadata loaded from remote or from a state
+/// snapshot, alongside the state.
+type Loaded<B> = (Option<<B as BlockT>::Header>, Option<SignedBlock<B>>, Option<Vec<u8>>);
+
+/// Insert `batch` into the backend of `ext`, leaving it empty.
+fn insert_batch(ext: &mut TestExternalities, batch: &mut Vec<KeyPair>) {
+	ext.backend.insert(vec![(None, batch.drain(..).map(|(k, v)| (k.0, Some(v.0))).collect())]);
+}
+
+/// Insert the child trie `trie` into the backend of `ext`, updating its root in the top trie.
+fn insert_child_trie(ext: &mut TestExternalities, (storage_key, pairs): &ChildTrie) {
+	let child_info = ChildInfo::new_default(storage_key);
+	let pairs = pairs.iter().map(|(k, v)| (k.0.clone(), Some(v.0.clone()))).collect();
+	ext.backend.insert(vec![(Some(child_info), pairs)]);
+}
+
+/// Await the RPC call `call`, failing it if it does not complete within `timeout`.
+async fn timed<T, E: Debug>(
+	timeout: Option<Duration>,
+	call: impl Future<Output = Result<T, E>>,
+) -> Result<T, String> {
+	match timeout {
+		Some(timeout) => {
+			futures::pin_mut!(call);
+			match select(call, Delay::new(timeout)).await {
+				Either::Left((result, _)) => result.map_err(|e| format!("{:?}", e)),
+				Either::Right(_) => Err(format!("timed out after {:?}", timeout)),
+			}
+		},
+		None => call.await.map_err(|e| format!("{:?}", e)),
+	}
+}
+
+/// Write `keys` to `path`, one hex encoded key per line.
+fn write_keys(path: &Path, keys: &[StorageKey]) -> Result<(), &'static str> {
+	let file = fs::File::create(path).map_err(|_| "fs::File::create failed.")?;
+	let mut writer = BufWriter::new(file);
+	for key in keys {
+		writeln!(writer, "0x{}", HexDisplay::from(&key.0)).map_err(|_| "fs::write failed.")?;
+	}
+	writer.flush().map_err(|_| "fs::write failed.")
+}
+
+/// Combine `pairs` with the state of `ext` according to `merge`.
+///
+/// Returns the number of pairs inserted.
+fn merge_into(ext: &mut TestExternalities, pairs: Vec<KeyPair>, merge: &Merge) -> usize {
+	let mut pairs = match merge {
+		Merge::Overwrite => pairs,
+		Merge::KeepExisting => ext.execute_with(|| {
+			pairs.into_iter().filter(|(k, _)| !sp_io::storage::exists(&k.0)).collect()
+		}),
+		Merge::Namespace(namespace) => pairs
+			.into_iter()
+			.map(|(k, v)| (StorageKey([&namespace[..], &k.0[..]].concat()), v))
+			.collect(),
+	};
+	let inserted = pairs.len();
+	insert_batch(ext, &mut pairs);
+	inserted
+}
+
+/// The distinct `(label, prefix)` pairs of `prefixes`, in key order.
+///
+/// Prefixes that are repeated, or that are covered by a shorter prefix, are merged into the
+/// prefix covering them, such that no key is scraped twice.
+fn dedup_prefixes(mut prefixes: Vec<(String, StorageKey)>) -> Vec<(String, StorageKey)> {
+	prefixes.sort_by(|a, b| a.1.cmp(&b.1));
+	let mut distinct: Vec<(String, StorageKey)> = Vec::with_capacity(prefixes.len());
+	for (label, prefix) in prefixes {
+		match distinct.last() {
+			Some((covering, last)) if prefix.0.starts_with(&last.0) => info!(
+				target: LOG_TARGET,
+				"merging prefix of {} ({:?}) into {} ({:?})",
+				label,
+				HexDisplay::from(&prefix),
+				covering,
+				HexDisplay::from(last),
+			),
+			_ => distinct.push((label, prefix)),
+		}
+	}
+	distinct
+}
+
+/// Remove repeated keys from `pairs`, keeping the last value of each, and return how many pairs
+/// were removed.
+///
+/// The remaining pairs are in key order.
+fn dedup_pairs(pairs: &mut Vec<KeyPair>) -> usize {
+	let count = pairs.len();
+	let distinct = pairs.drain(..).collect::<BTreeMap<_, _>>();
+	pairs.extend(distinct);
+	count - pairs.len()
+}
+
+/// Starts a state snapshot that carries an integrity checksum.
+///
+/// Its first byte can never start the key-value pair count of a snapshot without checksum, as
+/// written before checksums were introduced, so both can be told apart.
+const SNAPSHOT_MAGIC: [u8; 8] = *b"\xffrxsnap1";
+/// Length of the magic, the checksum and the payload length that precede the payload.
+const SNAPSHOT_PREAMBLE: usize = 8 + 32 + 8;
+
+/// Parse the start of a state snapshot of `len` bytes, returning the checksum and length of the
+/// payload, or `None` if the snapshot has no checksum.
+fn parse_preamble(start: &[u8], len: usize) -> Result<Option<([u8; 32], usize)>, &'static str> {
+	if !start.starts_with(&SNAPSHOT_MAGIC) {
+		return Ok(None);
+	}
+	if start.len() < SNAPSHOT_PREAMBLE {
+		return Err("state snapshot truncated");
+	}
+	let mut checksum = [0u8; 32];
+	checksum.copy_from_slice(&start[8..40]);
+	let mut payload_len = [0u8; 8];
+	payload_len.copy_from_slice(&start[40..SNAPSHOT_PREAMBLE]);
+	let payload_len = u64::from_le_bytes(payload_len) as usize;
+	match (len - SNAPSHOT_PREAMBLE).cmp(&payload_len) {
+		Ordering::Less => Err("state snapshot truncated"),
+		Ordering::Greater => Err("state snapshot has trailing data"),
+		Ordering::Equal => Ok(Some((checksum, payload_len))),
+	}
+}
+
+/// The payload of the state snapshot `bytes`, once its checksum is verified.
+pub(crate) fn snapshot_payload(bytes: &[u8]) -> Result<&[u8], &'static str> {
+	match parse_preamble(&bytes[..bytes.len().min(SNAPSHOT_PREAMBLE)], bytes.len())? {
+		Some((checksum, _)) => {
+			let payload = &bytes[SNAPSHOT_PREAMBLE..];
+			if sp_core::hashing::blake2_256(payload) != checksum {
+				return Err("state snapshot checksum mismatch");
+			}
+			Ok(payload)
+		},
+		None => {
+			warn!(target: LOG_TARGET, "state snapshot has no checksum, skipping integrity check");
+			Ok(bytes)
+		},
+	}
+}
+
+/// Open the state snapshot at `path` for decoding its payload, once its checksum is verified.
+///
+/// The payload is read twice, once for the checksum and once for decoding, such that it never
+/// needs to be held in memory all at once.
+fn open_snapshot(path: &Path) -> Result<FileInput, &'static str> {
+	let mut file = fs::File::open(path).map_err(|_| "fs::read failed.")?;
+	let len = file.metadata().map_err(|_| "fs::read failed.")?.len() as usize;
+	let mut start = vec![0u8; len.min(SNAPSHOT_PREAMBLE)];
+	file.read_exact(&mut start).map_err(|_| "fs::read failed.")?;
+
+	let remaining = match parse_preamble(&start, len)? {
+		Some((checksum, payload_len)) => {
+			let mut hasher = Blake2b::new(32);
+			let mut buf = vec![0u8; 64 * 1024];
+			loop {
+				match file.read(&mut buf).map_err(|_| "fs::read failed.")? {
+					0 => break,
+					n => hasher.update(&buf[..n]),
+				}
+			}
+			if hasher.finalize().as_bytes() != checksum {
+				return Err("state snapshot checksum mismatch");
+			}
+			file.seek(SeekFrom::Start(SNAPSHOT_PREAMBLE as u64)).map_err(|_| "fs::read failed.")?;
+			payload_len
+		},
+		None => {
+			warn!(target: LOG_TARGET, "state snapshot has no checksum, skipping integrity check");
+			file.seek(SeekFrom::Start(0)).map_err(|_| "fs::read failed.")?;
+			len
+		},
+	};
+	Ok(FileInput { reader: BufReader::new(file), remaining })
+}
+
+/// Writes a state snapshot incrementally, such that the key-value pairs never need to be held
+/// in memory all at once.
+///
+/// The snapshot is written to a temporary file next to its path, and only moved there once
+/// [`SnapshotWriter::finish`] succeeds. It starts with a preamble holding the blake2 checksum
+/// and the length of the payload that follows, which are filled in by the latter.
+struct SnapshotWriter {
+	file: BufWriter<fs::File>,
+	path: PathBuf,
+	tmp_path: PathBuf,
+	/// Number of key-value pairs announced, and pushed so far.
+	count: usize,
+	pushed: usize,
+	/// Checksum and length of the payload written so far.
+	hasher: Blake2b,
+	written: u64,
+	/// The child tries, written last.
+	child_tries: Vec<ChildTrie>,
+}
+
+impl SnapshotWriter {
+	/// Start writing a snapshot of `count` key-value pairs to `path`.
+	fn new(path: &Path, count: usize) -> Result<Self, &'static str> {
+		info!(target: LOG_TARGET, "writing to state snapshot file {:?}", path);
+		let tmp_path = path.with_extension("partial");
+		let file = fs::File::create(&tmp_path).map_err(|_| "fs::File::create failed.")?;
+		let mut file = BufWriter::new(file);
+		// the checksum and payload length are only known once finished.
+		file.write_all(&[&SNAPSHOT_MAGIC[..], &[0u8; SNAPSHOT_PREAMBLE - 8]].concat())
+			.map_err(|_| "fs::write failed.")?;
+		let mut writer = Self {
+			file,
+			path: path.into(),
+			tmp_path,
+			count,
+			pushed: 0,
+			hasher: Blake2b::new(32),
+			written: 0,
+			child_tries: Vec::new(),
+		};
+		// the encoding of a `Vec<KeyPair>` is its length followed by the pairs.
+		writer.write(&Compact(count as u32).encode())?;
+		Ok(writer)
+	}
+
+	fn write(&mut self, bytes: &[u8]) -> Result<(), &'static str> {
+		self.hasher.update(bytes);
+		self.written += bytes.len() as u64;
+		self.file.write_all(bytes).map_err(|_| "fs::write failed.")
+	}
+
+	/// Append the next key-value pair.
+	fn push(&mut self, pair: &KeyPair) -> Result<(), &'static str> {
+		self.pushed += 1;
+		self.write(&pair.encode())
+	}
+
+	/// Add the child tries of the state, to be written once finished.
+	fn push_child_trie(&mut self, trie: ChildTrie) {
+		self.child_tries.push(trie);
+	}
+
+	/// Append the header the state belongs to and optionally the block, the runtime metadata and
+	/// the child tries, and move the snapshot to its path.
+	///
+	/// These come after the key-value pairs, in the order in which they were introduced, such that
+	/// older snapshots, which end early, can still be loaded. The child tries are only written if
+	/// there are any.
+	fn finish<B: BlockT>(
+		mut self,
+		header: &B::Header,
+		block: Option<&SignedBlock<B>>,
+		metadata: Option<&[u8]>,
+	) -> Result<(), &'static str> {
+		if self.pushed != self.count {
+			return Err("state snapshot key count mismatch");
+		}
+		self.write(&header.encode())?;
+		self.write(&block.encode())?;
+		self.write(&metadata.encode())?;
+		if !self.child_tries.is_empty() {
+			let child_tries = std::mem::take(&mut self.child_tries);
+			self.write(&child_tries.encode())?;
+		}
+		let checksum = self.hasher.finalize();
+		self.file
+			.seek(SeekFrom::Start(SNAPSHOT_MAGIC.len() as u64))
+			.map_err(|_| "fs::write failed.")?;
+		self.file.write_all(checksum.as_bytes()).map_err(|_| "fs::write failed.")?;
+		self.file.write_all(&self.written.to_le_bytes()).map_err(|_| "fs::write failed.")?;
+		self.file.flush().map_err(|_| "fs::write failed.")?;
+		fs::rename(&self.tmp_path, &self.path).map_err(|_| "fs::rename failed.")
+	}
+}
+
+/// A [`codec::Input`] over a file, keeping track of the bytes left to read.
+struct FileInput {
+	reader: BufReader<fs::File>,
+	remaining: usize,
+}
+
+impl codec::Input for FileInput {
+	fn remaining_len(&mut self) -> Result<Option<usize>, codec::Error> {
+		Ok(Some(self.remaining))
+	}
+
+	fn read(&mut self, into: &mut [u8]) -> Result<(), codec::Error> {
+		if into.len() > self.remaining {
+			return Err("not enough data to fill buffer".into());
+		}
+		self.reader.read_exact(into).map_err(|_| "fs::read failed.")?;
+		self.remaining -= into.len();
+		Ok(())
+	}
+}
+
+/// The externalities built by [`Builder`], along with the block they were built at.
+///
+/// Derefs to the inner [`TestExternalities`].
+pub struct RemoteExternalities<B: BlockT> {
+	/// The inner externalities.
+	pub inner_ext: TestExternalities,
+	/// The header of the block at which the state was scraped.
+	///
+	/// Only `None` if loaded from a state snapshot that was created before headers were stored
+	/// in it.
+	pub header: Option<B::Header>,
+	/// The block at which the state was scraped, if it was requested through
+	/// [`OnlineConfig::scrape_block`] when scraping.
+	pub block: Option<SignedBlock<B>>,
+	/// The SCALE encoded runtime metadata at the block at which the state was scraped, if it was
+	/// requested through [`OnlineConfig::scrape_metadata`] when scraping.
+	pub metadata: Option<Vec<u8>>,
+	/// Storage statistics of the scraped state, if requested through
+	/// [`OnlineConfig::storage_stats`].
+	pub stats: Option<StorageStats>,
+	/// Callbacks run when these externalities are dropped.
+	pub teardown: Teardown,
+}
+
+/// Callbacks run when dropped, in reverse order of registration, also when unwinding from a
+/// panic. Meant to clean up after a test, e.g. to remove the temporary files it created.
+#[derive(Default)]
+pub struct Teardown(Vec<Box<dyn FnOnce() + Send>>);
+
+impl Teardown {
+	/// Register `hook` to be run on drop.
+	pub fn push<F: FnOnce() + Send + 'static>(&mut self, hook: F) {
+		self.0.push(Box::new(hook));
+	}
+
+	/// Register the removal of the file at `path` on drop, if it still exists by then.
+	pub fn remove_file<P: Into<PathBuf>>(&mut self, path: P) {
+		let path = path.into();
+		self.push(move || {
+			if path.exists() {
+				if let Err(e) = fs::remove_file(&path) {
+					warn!(target: LOG_TARGET, "failed to remove {:?} on teardown: {:?}", path, e);
+				}
+			}
+		});
+	}
+}
+
+impl Drop for Teardown {
+	fn drop(&mut self) {
+		while let Some(hook) = self.0.pop() {
+			// a panic in here while unwinding would abort the process, so it is contained.
+			if std::panic::catch_unwind(std::panic::AssertUnwindSafe(hook)).is_err() {
+				error!(target: LOG_TARGET, "teardown hook panicked");
+			}
+		}
+	}
+}
+
+impl<B: BlockT> RemoteExternalities<B> {
+	/// Execute the async closure `execute` while these externalities are set, and await it.
+	///
+	/// The externalities are set again every time the returned future is polled, such that the
+	/// closure can await other futures, e.g. further RPC calls, in between accessing storage.
+	/// Tasks spawned from within the closure do not see the externalities.
+	pub async fn execute_with_async<R, F: Future<Output = R>>(
+		&mut self,
+		execute: impl FnOnce() -> F,
+	) -> R {
+		let future = Box::pin(self.inner_ext.execute_with(execute));
+		WithExternalities { ext: &mut self.inner_ext, future }.await
+	}
+}
+
+/// A future polled while `ext` is set as externalities, see
+/// [`RemoteExternalities::execute_with_async`].
+struct WithExternalities<'a, F> {
+	ext: &'a mut TestExternalities,
+	future: Pin<Box<F>>,
+}
+
+impl<F: Future> Future for WithExternalities<'_, F> {
+	type Output = F::Output;
+
+	fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+		let this = self.get_mut();
+		let future = &mut this.future;
+		this.ext.execute_with(|| future.as_mut().poll(cx))
+	}
+}
+
+impl<B: BlockT> Deref for RemoteExternalities<B> {
+	type Target = TestExternalities;
+	fn deref(&self) -> &Self::Target {
+		&self.inner_ext
+	}
+}
+
+impl<B: BlockT> DerefMut for RemoteExternalities<B> {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		&mut self.inner_ext
 	}
 }
 
@@ -267,17 +1042,178 @@ impl<B: BlockT> Builder<B> {
 
 // RPC methods
 impl<B: BlockT> Builder<B> {
-	async fn rpc_get_head(&self) -> Result<B::Hash, &'static str> {
+	/// Connect to the endpoint in use, or to the next ones in order if that fails.
+	async fn connect(&mut self) -> Result<(), &'static str> {
+		let transport = &mut self.as_online_mut().transport;
+		for _ in 0..transport.uris.len() {
+			info!(target: LOG_TARGET, "initializing remote client to {:?}", transport.uri());
+			let uri = match transport.connect_uri().await {
+				Ok(uri) => uri,
+				Err(e) => {
+					warn!(target: LOG_TARGET, "failed to proxy {:?}: {}", transport.uri(), e);
+					transport.current = (transport.current + 1) % transport.uris.len();
+					continue;
+				},
+			};
+			match WsClientBuilder::default()
+				.max_request_body_size(u32::MAX)
+				.build(&uri)
+				.await
+			{
+				Ok(ws_client) => {
+					transport.client = Some(ws_client);
+					transport.last_progress.get_or_insert_with(Instant::now);
+					return Ok(());
+				},
+				Err(e) => {
+					warn!(target: LOG_TARGET, "failed to connect to {:?}: {:?}", transport.uri(), e);
+					transport.current = (transport.current + 1) % transport.uris.len();
+				},
+			}
+		}
+		Err("failed to build ws client")
+	}
+
+	/// Handle the failure of an RPC call, described by `err`, that has been failing `failures`
+	/// times in a row.
+	///
+	/// Retries on the same endpoint up to [`MAX_RPC_RETRIES`] times, then fails over to the next
+	/// one. Returns `err` once every endpoint has been tried.
+	async fn on_rpc_error(
+		&mut self,
+		failures: &mut usize,
+		err: &'static str,
+	) -> Result<(), &'static str> {
+		let config = self.as_online();
+		if let (Some(stall_timeout), Some(last_progress)) =
+			(config.stall_timeout, config.transport.last_progress)
+		{
+			if last_progress.elapsed() >= stall_timeout {
+				error!(
+					target: LOG_TARGET,
+					"no progress for {:?}, aborting after: {}",
+					last_progress.elapsed(),
+					err,
+				);
+				return Err("remote node stalled: no successful rpc call within the stall timeout");
+			}
+		}
+
+		*failures += 1;
+		let endpoints = self.as_online().transport.uris.len();
+		if *failures >= MAX_RPC_RETRIES * endpoints {
+			return Err(err);
+		}
+		if *failures % MAX_RPC_RETRIES == 0 {
+			let transport = &mut self.as_online_mut().transport;
+			transport.current = (transport.current + 1) % endpoints;
+			warn!(target: LOG_TARGET, "failing over to {:?}", transport.uri());
+			self.connect().await?;
+		}
+		Ok(())
+	}
+
+	/// Record that an RPC call succeeded, for stall detection.
+	fn progressed(&mut self) {
+		self.as_online_mut().transport.last_progress = Some(Instant::now());
+	}
+
+	/// Make the RPC call built by `call` on the endpoint in use, retrying it as described in
+	/// [`Self::on_rpc_error`], with `err` returned once every endpoint has failed.
+	///
+	/// `call` may be invoked once per attempt, so it has to clone any arguments it passes on.
+	async fn with_retry<T, E: Debug, F>(
+		&mut self,
+		err: &'static str,
+		call: F,
+	) -> Result<T, &'static str>
+	where
+		F: for<'a> Fn(&'a WsClient) -> BoxFuture<'a, Result<T, E>>,
+	{
+		let mut failures = 0;
+		loop {
+			let timeout = self.as_online().request_timeout;
+			match timed(timeout, call(self.as_online().rpc_client())).await {
+				Ok(result) => {
+					self.progressed();
+					return Ok(result);
+				},
+				Err(e) => {
+					error!(target: LOG_TARGET, "Error = {:?}", e);
+					self.on_rpc_error(&mut failures, err).await?;
+				},
+			}
+		}
+	}
+
+	async fn rpc_get_head(&mut self) -> Result<B::Hash, &'static str> {
 		trace!(target: LOG_TARGET, "rpc: finalized_head");
-		RpcApi::<B>::finalized_head(self.as_online().rpc_client()).await.map_err(|e| {
-			error!("Error = {:?}", e);
-			"rpc finalized_head failed."
+		self.with_retry("rpc finalized_head failed.", |client| {
+			Box::pin(RpcApi::<B>::finalized_head(client))
+		})
+		.await
+	}
+
+	async fn rpc_get_block_hash(&mut self, number: NumberFor<B>) -> Result<B::Hash, &'static str> {
+		trace!(target: LOG_TARGET, "rpc: get_block_hash");
+		self.with_retry("rpc get_block_hash failed.", |client| {
+			Box::pin(RpcApi::<B>::get_block_hash(client, Some(number)))
+		})
+		.await?
+		.ok_or("block not found")
+	}
+
+	async fn rpc_get_header(&mut self, at: B::Hash) -> Result<B::Header, &'static str>
+	where
+		B::Header: DeserializeOwned,
+	{
+		trace!(target: LOG_TARGET, "rpc: get_header");
+		self.with_retry("rpc get_header failed.", |client| {
+			Box::pin(RpcApi::<B>::get_header(client, at))
+		})
+		.await
+	}
+
+	async fn rpc_get_block(&mut self, at: B::Hash) -> Result<SignedBlock<B>, &'static str>
+	where
+		B::Header: DeserializeOwned,
+	{
+		trace!(target: LOG_TARGET, "rpc: get_block");
+		let signed = self
+			.with_retry("rpc get_block failed.", |client| {
+				Box::pin(RpcApi::<B>::get_block(client, Some(at)))
+			})
+			.await?
+			.ok_or("block not found")?;
+
+		let extrinsics = signed
+			.block
+			.extrinsics
+			.iter()
+			.map(|xt| Decode::decode(&mut &xt[..]))
+			.collect::<Result<Vec<B::Extrinsic>, _>>()
+			.map_err(|_| "failed to decode block extrinsics")?;
+
+		Ok(SignedBlock {
+			block: B::new(signed.block.header, extrinsics),
+			justifications: signed.justifications,
 		})
 	}
 
+	/// The SCALE encoded runtime metadata at `at`.
+	async fn rpc_get_metadata(&mut self, at: B::Hash) -> Result<Vec<u8>, &'static str> {
+		trace!(target: LOG_TARGET, "rpc: get_metadata");
+		let metadata = self
+			.with_retry("rpc get_metadata failed.", |client| {
+				Box::pin(RpcApi::<B>::get_metadata(client, Some(at)))
+			})
+			.await?;
+		Ok(metadata.0)
+	}
+
 	/// Get all the keys at `prefix` at `hash` using the paged, safe RPC methods.
 	async fn get_keys_paged(
-		&self,
+		&mut self,
 		prefix: StorageKey,
 		hash: B::Hash,
 	) -> Result<Vec<StorageKey>, &'static str> {
@@ -285,18 +1221,18 @@ impl<B: BlockT> Builder<B> {
 		let mut last_key: Option<StorageKey> = None;
 		let mut all_keys: Vec<StorageKey> = vec![];
 		let keys = loop {
-			let page = RpcApi::<B>::get_keys_paged(
-				self.as_online().rpc_client(),
-				Some(prefix.clone()),
-				PAGE,
-				last_key.clone(),
-				Some(hash),
-			)
-			.await
-			.map_err(|e| {
-				error!(target: LOG_TARGET, "Error = {:?}", e);
-				"rpc get_keys failed"
-			})?;
+			// a failed page is retried from `last_key`, possibly on another endpoint.
+			let page = self
+				.with_retry("rpc get_keys failed", |client| {
+					Box::pin(RpcApi::<B>::get_keys_paged(
+						client,
+						Some(prefix.clone()),
+						PAGE,
+						last_key.clone(),
+						Some(hash),
+					))
+				})
+				.await?;
 			let page_len = page.len();
 			all_keys.extend(page);
 
@@ -319,64 +1255,142 @@ impl<B: BlockT> Builder<B> {
 		Ok(keys)
 	}
 
-	/// Synonym of `rpc_get_pairs_unsafe` that uses paged queries to first get the keys, and then
-	/// map them to values one by one.
-	///
-	/// This can work with public nodes. But, expect it to be darn slow.
-	pub(crate) async fn rpc_get_pairs_paged(
-		&self,
-		prefix: StorageKey,
+	/// The changes of `keys` in the blocks from `from` to `to`, in block order.
+	async fn rpc_query_storage(
+		&mut self,
+		keys: Vec<StorageKey>,
+		from: B::Hash,
+		to: B::Hash,
+	) -> Result<Vec<StorageChangeSet<B::Hash>>, &'static str> {
+		self.with_retry("rpc query_storage failed", |client| {
+			Box::pin(RpcApi::<B>::query_storage(client, keys.clone(), from, Some(to)))
+		})
+		.await
+	}
+
+	/// Get all keys of the child trie at `child_storage_key` at `at`.
+	async fn rpc_get_child_keys(
+		&mut self,
+		child_storage_key: PrefixedStorageKey,
 		at: B::Hash,
-	) -> Result<Vec<KeyPair>, &'static str> {
-		let keys = self.get_keys_paged(prefix, at).await?;
-		let keys_count = keys.len();
-		info!(target: LOG_TARGET, "Querying a total of {} keys", keys.len());
+	) -> Result<Vec<StorageKey>, &'static str> {
+		self.with_retry("rpc get_child_keys failed", |client| {
+			Box::pin(RpcApi::<B>::get_child_keys(
+				client,
+				child_storage_key.clone(),
+				StorageKey(vec![]),
+				Some(at),
+			))
+		})
+		.await
+	}
 
-		let mut key_values: Vec<KeyPair> = vec![];
-		for key in keys {
-			let value =
-				RpcApi::<B>::get_storage(self.as_online().rpc_client(), key.clone(), Some(at))
-					.await
-					.map_err(|e| {
-						error!(target: LOG_TARGET, "Error = {:?}", e);
-						"rpc get_storage failed"
-					})?;
-			key_values.push((key, value));
-			if key_values.len() % 1000 == 0 {
-				let ratio: f64 = key_values.len() as f64 / keys_count as f64;
-				debug!(
-					target: LOG_TARGET,
-					"progress = {:.2} [{} / {}]",
-					ratio,
-					key_values.len(),
-					keys_count,
-				);
-			}
-		}
+	/// Get the value of `key` in the child trie at `child_storage_key` at `at`, if any.
+	async fn rpc_get_child_storage(
+		&mut self,
+		child_storage_key: PrefixedStorageKey,
+		key: StorageKey,
+		at: B::Hash,
+	) -> Result<Option<StorageData>, &'static str> {
+		self.with_retry("rpc get_child_storage failed", |client| {
+			Box::pin(RpcApi::<B>::get_child_storage(
+				client,
+				child_storage_key.clone(),
+				key.clone(),
+				Some(at),
+			))
+		})
+		.await
+	}
 
-		Ok(key_values)
+	/// Get the value of `key` at `at`. Storage that does not exist is returned as empty.
+	async fn rpc_get_storage(
+		&mut self,
+		key: StorageKey,
+		at: B::Hash,
+	) -> Result<StorageData, &'static str> {
+		self.with_retry("rpc get_storage failed", |client| {
+			Box::pin(RpcApi::<B>::get_storage(client, key.clone(), Some(at)))
+		})
+		.await
 	}
 }
 
 // Internal methods
-impl<B: BlockT> Builder<B> {
-	/// Save the given data as state snapshot.
-	fn save_state_snapshot(&self, data: &[KeyPair], path: &Path) -> Result<(), &'static str> {
-		info!(target: LOG_TARGET, "writing to state snapshot file {:?}", path);
-		fs::write(path, data.encode()).map_err(|_| "fs::write failed.")?;
-		Ok(())
+impl<B: BlockT> Builder<B>
+where
+	B::Header: DeserializeOwned,
+{
+	/// Insert the state of the snapshot of `config` into `ext`, page by page.
+	fn load_state_snapshot(
+		&self,
+		config: &SnapshotConfig,
+		ext: &mut TestExternalities,
+	) -> Result<Loaded<B>, &'static str> {
+		info!(target: LOG_TARGET, "scraping keypairs from state snapshot {:?}", config.path);
+		#[cfg(feature = "mmap")]
+		{
+			if config.mmap {
+				let file = fs::File::open(&config.path).map_err(|_| "fs::read failed.")?;
+				// SAFETY: the file must not be modified while mapped, as documented on
+				// `SnapshotConfig::mmap`.
+				let map = unsafe { memmap2::Mmap::map(&file) }.map_err(|_| "mmap failed")?;
+				return Self::decode_state_snapshot(&mut snapshot_payload(&map)?, ext);
+			}
+		}
+		Self::decode_state_snapshot(&mut open_snapshot(&config.path)?, ext)
 	}
 
-	/// initialize `Self` from state snapshot. Panics if the file does not exist.
-	fn load_state_snapshot(&self, path: &Path) -> Result<Vec<KeyPair>, &'static str> {
-		info!(target: LOG_TARGET, "scraping keypairs from state snapshot {:?}", path,);
-		let bytes = fs::read(path).map_err(|_| "fs::read failed.")?;
-		Decode::decode(&mut &*bytes).map_err(|_| "decode failed")
-	}
+	/// Decode a state snapshot from `input`, inserting its key-value pairs into `ext` page by
+	/// page.
+	fn decode_state_snapshot<I: codec::Input>(
+		input: &mut I,
+		ext: &mut TestExternalities,
+	) -> Result<Loaded<B>, &'static str> {
+		let count = <Compact<u32>>::decode(input).map_err(|_| "decode failed")?.0 as usize;
+		let mut batch = Vec::with_capacity(INSERT_BATCH.min(count));
+		for _ in 0..count {
+			batch.push(KeyPair::decode(input).map_err(|_| "decode failed")?);
+			if batch.len() == INSERT_BATCH {
+				insert_batch(ext, &mut batch);
+			}
+		}
+		insert_batch(ext, &mut batch);
 
-	/// Build `Self` from a network node denoted by `uri`.
-	async fn load_remote(&self) -> Result<Vec<KeyPair>, &'static str> {
-		let config = self.as_online();
+		if input.remaining_len().map_err(|_| "decode failed")? == Some(0) {
+			warn!(target: LOG_TARGET, "state snapshot does not contain a header");
+			return Ok((None, None, None));
+		}
+		let header = Decode::decode(input).map_err(|_| "header decode failed")?;
+		let block = Decode::decode(input).map_err(|_| "block decode failed")?;
+		// snapshots written before the metadata was stored end right after the block.
+		let metadata = if input.remaining_len().map_err(|_| "decode failed")? == Some(0) {
+			None
+		} else {
+			Decode::decode(input).map_err(|_| "metadata decode failed")?
+		};
+		// and snapshots without child tries right after the metadata.
+		if input.remaining_len().map_err(|_| "decode failed")? != Some(0) {
+			let child_tries = <Vec<ChildTrie>>::decode(input)
+				.map_err(|_| "child tries decode failed")?;
+			for trie in &child_tries {
+				insert_child_trie(ext, trie);
+			}
+		}
+		Ok((Some(header), block, metadata))
+	}
+
+	/// Insert the remote state at the block of `header` into `ext`, page by page, writing it to
+	/// `snapshot` and accounting for it in `stats` as well if given.
+	///
+	/// Only the keys are held in memory all at once.
+	async fn load_remote(
+		&mut self,
+		header: &B::Header,
+		ext: &mut TestExternalities,
+		snapshot: Option<&Path>,
+		mut stats: Option<&mut StorageStats>,
+	) -> Result<Option<SnapshotWriter>, &'static str> {
 		let at = self
 			.as_online()
 			.at
@@ -384,40 +1398,220 @@ impl<B: BlockT> Builder<B> {
 			.clone();
 		info!(target: LOG_TARGET, "scraping keypairs from remote @ {:?}", at);
 
-		let keys_and_values = if config.modules.len() > 0 {
-			let mut filtered_kv = vec![];
-			for f in config.modules.iter() {
-				let hashed_prefix = StorageKey(twox_128(f.as_bytes()).to_vec());
-				let module_kv = self.rpc_get_pairs_paged(hashed_prefix.clone(), at).await?;
+		let keys = self.remote_keys(at).await?;
+		let mut known = match self.as_online().base_snapshot.clone() {
+			Some(base) => self.refreshed_values(&base.path, &keys, at).await?,
+			None => Default::default(),
+		};
+		let keys_count = keys.len();
+		info!(
+			target: LOG_TARGET,
+			"Querying a total of {} keys, {} of them known from the base snapshot",
+			keys_count,
+			known.len(),
+		);
+		let mut snapshot = match snapshot {
+			Some(path) => Some(SnapshotWriter::new(path, keys_count)?),
+			None => None,
+		};
+
+		let mut child_roots = Vec::new();
+		let mut batch = Vec::with_capacity(INSERT_BATCH.min(keys_count));
+		for (index, key) in keys.into_iter().enumerate() {
+			if key.0.starts_with(DEFAULT_CHILD_STORAGE_KEY_PREFIX) {
+				child_roots.push(key.clone());
+			}
+			let value = match known.remove(&key) {
+				Some(value) => value,
+				None => self.rpc_get_storage(key.clone(), at).await?,
+			};
+			let pair = (key, value);
+			if let Some(snapshot) = snapshot.as_mut() {
+				snapshot.push(&pair)?;
+			}
+			if let Some(stats) = stats.as_mut() {
+				stats.add(&pair.0, &pair.1);
+			}
+			batch.push(pair);
+			if batch.len() == INSERT_BATCH {
+				insert_batch(ext, &mut batch);
+				let done = index + 1;
+				debug!(
+					target: LOG_TARGET,
+					"progress = {:.2} [{} / {}]",
+					done as f64 / keys_count as f64,
+					done,
+					keys_count,
+				);
+			}
+		}
+		insert_batch(ext, &mut batch);
+
+		if self.as_online().scrape_child_tries {
+			info!(target: LOG_TARGET, "scraping {} child tries", child_roots.len());
+			for root in child_roots {
+				let trie = self.load_child_trie(root, at).await?;
+				insert_child_trie(ext, &trie);
+				if let Some(snapshot) = snapshot.as_mut() {
+					snapshot.push_child_trie(trie);
+				}
+			}
+		}
+
+		if self.as_online().check_state_root {
+			self.check_state_root(ext, header)?;
+		}
+
+		Ok(snapshot)
+	}
+
+	/// All key-value pairs at `at` of the default child trie whose root is stored at `root`.
+	async fn load_child_trie(
+		&mut self,
+		root: StorageKey,
+		at: B::Hash,
+	) -> Result<ChildTrie, &'static str> {
+		let storage_key = root.0[DEFAULT_CHILD_STORAGE_KEY_PREFIX.len()..].to_vec();
+		let prefixed = PrefixedStorageKey::new(root.0);
+		let keys = self.rpc_get_child_keys(prefixed.clone(), at).await?;
+		debug!(
+			target: LOG_TARGET,
+			"found {} keys in child trie {:?}",
+			keys.len(),
+			HexDisplay::from(&storage_key),
+		);
+		let mut pairs = Vec::with_capacity(keys.len());
+		for key in keys {
+			// `None` only if the key vanished meanwhile, e.g. as the node pruned the block.
+			let value = self.rpc_get_child_storage(prefixed.clone(), key.clone(), at).await?;
+			if let Some(value) = value {
+				pairs.push((key, value));
+			}
+		}
+		Ok((storage_key, pairs))
+	}
+
+	/// The values at `at` of those of `keys` that are in the base snapshot at `path`.
+	///
+	/// The values of the base snapshot are updated with the changes since its block, rather than
+	/// downloaded again.
+	async fn refreshed_values(
+		&mut self,
+		path: &Path,
+		keys: &[StorageKey],
+		at: B::Hash,
+	) -> Result<BTreeMap<StorageKey, StorageData>, &'static str> {
+		info!(target: LOG_TARGET, "refreshing base snapshot {:?}", path);
+		let bytes = fs::read(path).map_err(|_| "fs::read failed.")?;
+		let input = &mut snapshot_payload(&bytes)?;
+		let pairs = <Vec<KeyPair>>::decode(input).map_err(|_| "decode failed")?;
+		if input.is_empty() {
+			return Err("base snapshot does not contain a header");
+		}
+		let base = <B::Header>::decode(input).map_err(|_| "header decode failed")?.hash();
+
+		// `keys` are sorted, and only keys that still exist are of interest.
+		let mut values = pairs
+			.into_iter()
+			.filter(|(k, _)| keys.binary_search(k).is_ok())
+			.collect::<BTreeMap<_, _>>();
+		if base == at {
+			return Ok(values);
+		}
+
+		let known = values.keys().cloned().collect::<Vec<_>>();
+		let mut changed = 0;
+		for batch in known.chunks(QUERY_STORAGE_BATCH) {
+			// change sets come in block order, such that the last change of a key wins.
+			for change_set in self.rpc_query_storage(batch.to_vec(), base, at).await? {
+				for (key, value) in change_set.changes {
+					changed += 1;
+					match value {
+						Some(value) => values.insert(key, value),
+						None => values.remove(&key),
+					};
+				}
+			}
+		}
+		debug!(target: LOG_TARGET, "applied {} changes since {:?}", changed, base);
+		Ok(values)
+	}
+
+	/// The distinct keys under the configured modules and prefixes at `at`, or all keys if there
+	/// are none, in key order.
+	async fn remote_keys(&mut self, at: B::Hash) -> Result<Vec<StorageKey>, &'static str> {
+		let modules = self.as_online().modules.clone();
+		let raw_prefixes = self.as_online().prefixes.clone();
+		let child_roots = self.as_online().scrape_child_tries.then(|| {
+			("child trie roots".to_string(), StorageKey(DEFAULT_CHILD_STORAGE_KEY_PREFIX.to_vec()))
+		});
+		let mut keys = if modules.len() > 0 || raw_prefixes.len() > 0 {
+			let prefixes = modules
+				.iter()
+				.map(|f| (f.clone(), StorageKey(twox_128(f.as_bytes()).to_vec())))
+				.chain(raw_prefixes.into_iter().map(|p| (format!("{:?}", HexDisplay::from(&p.0)), p)))
+				.chain(child_roots)
+				.collect();
+			let mut filtered_keys = vec![];
+			for (f, hashed_prefix) in dedup_prefixes(prefixes) {
+				let module_keys = self.get_keys_paged(hashed_prefix.clone(), at).await?;
 				info!(
 					target: LOG_TARGET,
-					"downloaded data for module {} (count: {} / prefix: {:?}).",
+					"found keys for module {} (count: {} / prefix: {:?}).",
 					f,
-					module_kv.len(),
+					module_keys.len(),
 					HexDisplay::from(&hashed_prefix),
 				);
-				filtered_kv.extend(module_kv);
+				filtered_keys.extend(module_keys);
 			}
-			filtered_kv
+			filtered_keys
 		} else {
 			info!(target: LOG_TARGET, "downloading data for all modules.");
-			self.rpc_get_pairs_paged(StorageKey(vec![]), at).await?
+			self.get_keys_paged(StorageKey(vec![]), at).await?
 		};
 
-		Ok(keys_and_values)
+		// pages of a node may overlap if the state changes underneath, e.g. on pruning.
+		let found = keys.len();
+		keys.sort();
+		keys.dedup();
+		if keys.len() < found {
+			info!(target: LOG_TARGET, "merged {} repeated keys", found - keys.len());
+		}
+
+		if let Some(filter) = self.as_online().key_filter.as_ref() {
+			let found = keys.len();
+			keys.retain(|key| filter.matches(&key.0));
+			info!(target: LOG_TARGET, "{} of {} keys pass the key filter", keys.len(), found);
+		}
+		Ok(keys)
 	}
 
-	pub(crate) async fn init_remote_client(&mut self) -> Result<(), &'static str> {
-		let mut online = self.as_online_mut();
-		info!(target: LOG_TARGET, "initializing remote client to {:?}", online.transport.uri);
+	/// Ensure that the state root of `ext` is equal to the state root of `header`.
+	fn check_state_root(
+		&self,
+		ext: &TestExternalities,
+		header: &B::Header,
+	) -> Result<(), &'static str> {
+		let expected = *header.state_root();
+		let computed = *ext.backend.root();
+
+		if computed.as_ref() != expected.as_ref() {
+			error!(
+				target: LOG_TARGET,
+				"state root mismatch: header = {:?}, computed = {:?}",
+				expected,
+				computed,
+			);
+			return Err("state root of the scraped state does not match the block header");
+		}
+
+		info!(target: LOG_TARGET, "state root of the scraped state verified: {:?}", computed);
+		Ok(())
+	}
 
+	pub(crate) async fn init_remote_client(&mut self) -> Result<(), &'static str> {
 		// First, initialize the ws client.
-		let ws_client = WsClientBuilder::default()
-			.max_request_body_size(u32::MAX)
-			.build(&online.transport.uri)
-			.await
-			.map_err(|_| "failed to build ws client")?;
-		online.transport.client = Some(ws_client);
+		self.connect().await?;
 
 		// Then, if `at` is not set, set it.
 		if self.as_online().at.is_none() {
@@ -428,31 +1622,91 @@ impl<B: BlockT> Builder<B> {
 		Ok(())
 	}
 
-	pub(crate) async fn pre_build(mut self) -> Result<Vec<KeyPair>, &'static str> {
-		let mut base_kv = match self.mode.clone() {
-			Mode::Offline(config) => self.load_state_snapshot(&config.state_snapshot.path)?,
+	/// All key-value pairs of the additional source `config`, writing them to its state snapshot
+	/// if configured.
+	async fn load_source(config: OnlineConfig<B>) -> Result<Vec<KeyPair>, &'static str> {
+		let snapshot_path = config.state_snapshot.as_ref().map(|c| c.path.clone());
+		let mut source = Self::new().mode(Mode::Online(config));
+		source.init_remote_client().await?;
+		let at = source.as_online().at.expect("initialized by init_remote_client; qed.");
+		let header = source.rpc_get_header(at).await?;
+
+		let mut ext = TestExternalities::new_empty();
+		let snapshot = source.load_remote(&header, &mut ext, snapshot_path.as_deref(), None).await?;
+		if let Some(snapshot) = snapshot {
+			snapshot.finish(&header, None, None)?;
+		}
+		Ok(export::pairs_of(&mut ext))
+	}
+
+	pub(crate) async fn pre_build(
+		mut self,
+		ext: &mut TestExternalities,
+		stats: &mut Option<StorageStats>,
+	) -> Result<Loaded<B>, &'static str> {
+		let (header, block, metadata) = match self.mode.clone() {
+			Mode::Offline(config) => self.load_state_snapshot(&config.state_snapshot, ext)?,
 			Mode::Online(config) => {
 				self.init_remote_client().await?;
-				let kp = self.load_remote().await?;
-				if let Some(c) = config.state_snapshot {
-					self.save_state_snapshot(&kp, &c.path)?;
+				let at = self.as_online().at.expect("initialized by init_remote_client; qed.");
+				let header = self.rpc_get_header(at).await?;
+				let snapshot_path = config.state_snapshot.as_ref().map(|c| c.path.as_path());
+				if config.storage_stats {
+					*stats = Some(StorageStats::new());
+				}
+				let snapshot = self.load_remote(&header, ext, snapshot_path, stats.as_mut()).await?;
+				let block = if config.scrape_block {
+					info!(target: LOG_TARGET, "downloading block @ {:?}", at);
+					Some(self.rpc_get_block(at).await?)
+				} else {
+					None
+				};
+				let metadata = if config.scrape_metadata {
+					info!(target: LOG_TARGET, "downloading runtime metadata @ {:?}", at);
+					Some(self.rpc_get_metadata(at).await?)
+				} else {
+					None
+				};
+				if let Some(snapshot) = snapshot {
+					snapshot.finish(&header, block.as_ref(), metadata.as_deref())?;
 				}
-				kp
+				(Some(header), block, metadata)
 			}
 		};
 
+		for (config, merge) in std::mem::take(&mut self.sources) {
+			let pairs = Self::load_source(config).await?;
+			let found = pairs.len();
+			let inserted = merge_into(ext, pairs, &merge);
+			info!(
+				target: LOG_TARGET,
+				"merged additional source ({:?}): {} of {} keys inserted",
+				merge,
+				inserted,
+				found,
+			);
+		}
+
 		info!(
 			target: LOG_TARGET,
 			"extending externalities with {} manually injected keys",
 			self.inject.len()
 		);
-		base_kv.extend(self.inject.clone());
-		Ok(base_kv)
+		let merged = dedup_pairs(&mut self.inject);
+		if merged > 0 {
+			info!(target: LOG_TARGET, "merged {} repeatedly injected keys, keeping the last", merged);
+		}
+		// injected keys take precedence over the loaded state, so they are inserted last.
+		insert_batch(ext, &mut self.inject);
+		Ok((header, block, metadata))
 	}
 }
 
 // Public methods
-impl<B: BlockT> Builder<B> {
+impl<B: BlockT> Builder<B>
+where
+	B::Header: DeserializeOwned,
+{
 	/// Create a new builder.
 	pub fn new() -> Self {
 		Default::default()
@@ -466,23 +1720,173 @@ impl<B: BlockT> Builder<B> {
 		self
 	}
 
+	/// Inject `value`, encoded, at the raw `key`, such as one built with [`storage_value_key`] or
+	/// [`storage_map_key`].
+	///
+	/// Like any injected key, this takes precedence over the scraped state.
+	pub fn inject_key_value<K: AsRef<[u8]>, V: Encode>(mut self, key: K, value: V) -> Self {
+		self.inject.push((StorageKey(key.as_ref().to_vec()), StorageData(value.encode())));
+		self
+	}
+
+	/// Override the value of the storage value `S` with `value`.
+	///
+	/// The final key is computed from the storage item itself, and the value is encoded, so this
+	/// can be used instead of hand-rolling the key/value pair passed to [`Self::inject`]. Like any
+	/// injected key, this takes precedence over the scraped state.
+	pub fn override_value<S, V>(mut self, value: V) -> Self
+	where
+		S: StorageValue<V>,
+		V: FullCodec,
+	{
+		self.inject.push((StorageKey(S::hashed_key().to_vec()), StorageData(value.encode())));
+		self
+	}
+
+	/// Override the value of `key` in the storage map `S` with `value`.
+	///
+	/// See [`Self::override_value`].
+	pub fn override_map_entry<S, K, V, KArg>(mut self, key: KArg, value: V) -> Self
+	where
+		S: StorageMap<K, V>,
+		K: FullEncode,
+		V: FullCodec,
+		KArg: EncodeLike<K>,
+	{
+		self.inject.push((StorageKey(S::hashed_key_for(key)), StorageData(value.encode())));
+		self
+	}
+
+	/// Override the value of `(key1, key2)` in the storage double map `S` with `value`.
+	///
+	/// See [`Self::override_value`].
+	pub fn override_double_map_entry<S, K1, K2, V, KArg1, KArg2>(
+		mut self,
+		key1: KArg1,
+		key2: KArg2,
+		value: V,
+	) -> Self
+	where
+		S: StorageDoubleMap<K1, K2, V>,
+		K1: FullEncode,
+		K2: FullEncode,
+		V: FullCodec,
+		KArg1: EncodeLike<K1>,
+		KArg2: EncodeLike<K2>,
+	{
+		self.inject.push((StorageKey(S::hashed_key_for(key1, key2)), StorageData(value.encode())));
+		self
+	}
+
 	/// Configure a state snapshot to be used.
 	pub fn mode(mut self, mode: Mode<B>) -> Self {
 		self.mode = mode;
 		self
 	}
 
+	/// Run `hook` inside the externalities right after they are built, once the scraped state and
+	/// the injected keys are in place, e.g. to run a runtime upgrade or to set the block number.
+	///
+	/// Hooks run in the order they are registered.
+	pub fn post_build<F: FnOnce() + Send + 'static>(mut self, hook: F) -> Self {
+		self.post_build.push(Box::new(hook));
+		self
+	}
+
+	/// Run `hook` when the built externalities are dropped, or when building them fails.
+	///
+	/// See [`Teardown`].
+	pub fn on_teardown<F: FnOnce() + Send + 'static>(mut self, hook: F) -> Self {
+		self.teardown.push(hook);
+		self
+	}
+
+	/// Additionally scrape the state of the remote source `config`, e.g. of another chain, and
+	/// combine it with the state loaded before it according to `merge`.
+	///
+	/// Sources are loaded in the order they are added, after the state of [`Self::mode`], which
+	/// alone determines the header and block of the built externalities. Injected keys still take
+	/// precedence over all of them.
+	pub fn source(mut self, config: OnlineConfig<B>, merge: Merge) -> Self {
+		self.sources.push((config, merge));
+		self
+	}
+
+	/// Scrape only the keys under the configured modules, without their values, in key order.
+	///
+	/// Only possible in online mode. Any state snapshot and injected keys are ignored.
+	pub async fn scrape_keys(mut self) -> Result<Vec<StorageKey>, &'static str> {
+		if let Mode::Offline(_) = self.mode {
+			return Err("keys can only be scraped from a remote node");
+		}
+		self.init_remote_client().await?;
+		let at = self.as_online().at.expect("initialized by init_remote_client; qed.");
+		info!(target: LOG_TARGET, "scraping keys from remote @ {:?}", at);
+		self.remote_keys(at).await
+	}
+
+	/// Like [`Self::scrape_keys`], but writes the keys to `path`, one hex encoded key per line.
+	///
+	/// Returns the number of keys written.
+	pub async fn scrape_keys_into<P: AsRef<Path>>(self, path: P) -> Result<usize, &'static str> {
+		let keys = self.scrape_keys().await?;
+		write_keys(path.as_ref(), &keys)?;
+		Ok(keys.len())
+	}
+
+	/// Fetch only the block at the configured block hash, or at the latest finalized block if
+	/// none is configured, without scraping any state.
+	///
+	/// Only possible in online mode.
+	pub async fn fetch_block(mut self) -> Result<SignedBlock<B>, &'static str> {
+		if let Mode::Offline(_) = self.mode {
+			return Err("blocks can only be fetched from a remote node");
+		}
+		self.init_remote_client().await?;
+		let at = self.as_online().at.expect("initialized by init_remote_client; qed.");
+		info!(target: LOG_TARGET, "fetching block @ {:?}", at);
+		self.rpc_get_block(at).await
+	}
+
+	/// Resolve the hash of the block with `number` on the canonical chain of the node, without
+	/// scraping any state.
+	///
+	/// Only possible in online mode.
+	pub async fn block_hash(mut self, number: NumberFor<B>) -> Result<B::Hash, &'static str> {
+		if let Mode::Offline(_) = self.mode {
+			return Err("blocks can only be resolved by a remote node");
+		}
+		self.connect().await?;
+		self.rpc_get_block_hash(number).await
+	}
+
 	/// Build the test externalities.
-	pub async fn build(self) -> Result<TestExternalities, &'static str> {
-		let kv = self.pre_build().await?;
+	///
+	/// The state is inserted into the externalities as it is loaded, rather than collected first.
+	pub async fn build(mut self) -> Result<RemoteExternalities<B>, &'static str> {
+		// taken out first, such that the hooks also run if building fails.
+		let teardown = std::mem::take(&mut self.teardown);
+		if let Mode::Online(config) = &self.mode {
+			config.validate()?;
+		}
+		for (config, _) in &self.sources {
+			config.validate()?;
+		}
+		let post_build = std::mem::take(&mut self.post_build);
 		let mut ext = TestExternalities::new_empty();
-
-		info!(target: LOG_TARGET, "injecting a total of {} keys", kv.len());
-		for (k, v) in kv {
-			let (k, v) = (k.0, v.0);
-			ext.insert(k, v);
+		let mut stats = None;
+		let (header, block, metadata) = self.pre_build(&mut ext, &mut stats).await?;
+		if let Some(stats) = stats.as_ref() {
+			info!(
+				target: LOG_TARGET,
+				"storage statistics of the scraped state:\n{}",
+				stats.report(None),
+			);
+		}
+		for hook in post_build {
+			ext.execute_with(hook);
 		}
-		Ok(ext)
+		Ok(RemoteExternalities { inner_ext: ext, header, block, metadata, stats, teardown })
 	}
 }
 
@@ -518,6 +1922,478 @@ mod tests {
 			.expect("Can't read state snapshot file")
 			.execute_with(|| {});
 	}
+
+	#[cfg(feature = "mmap")]
+	#[tokio::test]
+	async fn can_load_memory_mapped_state_snapshot() {
+		init_logger();
+		Builder::<Block>::new()
+			.mode(Mode::Offline(OfflineConfig {
+				state_snapshot: SnapshotConfig::new("test_data/proxy_test").with_mmap(),
+			}))
+			.build()
+			.await
+			.expect("Can't read state snapshot file")
+			.execute_with(|| {});
+	}
+
+	#[tokio::test]
+	async fn legacy_snapshot_has_no_header_or_block() {
+		init_logger();
+		let ext = Builder::<Block>::new()
+			.mode(Mode::Offline(OfflineConfig {
+				state_snapshot: SnapshotConfig::new("test_data/proxy_test"),
+			}))
+			.build()
+			.await
+			.expect("Can't read state snapshot file");
+		assert!(ext.header.is_none());
+		assert!(ext.block.is_none());
+	}
+
+	#[tokio::test]
+	async fn can_store_block_in_state_snapshot() {
+		use sp_runtime::testing::Header;
+		init_logger();
+		let path = std::env::temp_dir().join("remote_ext_block_snapshot_test.bin");
+		let block = SignedBlock {
+			block: Block::new(Header::new_from_number(7), vec![]),
+			justifications: Some(Justifications::from((*b"TEST", vec![1, 2, 3]))),
+		};
+		let kv = vec![(StorageKey(b"key".to_vec()), StorageData(b"value".to_vec()))];
+
+		let header = block.block.header.clone();
+		let metadata = vec![42u8; 3];
+		let save = |block: Option<&SignedBlock<Block>>, metadata: Option<&[u8]>| {
+			let mut writer = SnapshotWriter::new(&path, kv.len()).unwrap();
+			kv.iter().for_each(|pair| writer.push(pair).unwrap());
+			writer.finish::<Block>(&header, block, metadata).unwrap();
+		};
+
+		let builder = Builder::<Block>::new();
+		save(Some(&block), Some(&metadata));
+		// the incrementally written snapshot is encoded as a whole would be.
+		assert_eq!(
+			snapshot_payload(&std::fs::read(&path).unwrap()).unwrap(),
+			&[kv.encode(), header.encode(), Some(&block).encode(), Some(&metadata).encode()]
+				.concat()[..],
+		);
+		let mut configs = vec![SnapshotConfig::new(&path)];
+		#[cfg(feature = "mmap")]
+		configs.push(SnapshotConfig::new(&path).with_mmap());
+		for config in configs {
+			let mut ext = TestExternalities::new_empty();
+			assert_eq!(
+				builder.load_state_snapshot(&config, &mut ext).unwrap(),
+				(Some(header.clone()), Some(block.clone()), Some(metadata.clone())),
+			);
+			assert_eq!(export::pairs_of(&mut ext), kv);
+		}
+
+		save(None, None);
+		let mut ext = TestExternalities::new_empty();
+		assert_eq!(
+			builder.load_state_snapshot(&SnapshotConfig::new(&path), &mut ext).unwrap(),
+			(Some(header.clone()), None, None),
+		);
+
+		// snapshots written before the metadata was stored end right after the block.
+		let payload = [kv.encode(), header.encode(), Some(&block).encode()].concat();
+		let mut ext = TestExternalities::new_empty();
+		assert_eq!(
+			Builder::<Block>::decode_state_snapshot(&mut &payload[..], &mut ext).unwrap(),
+			(Some(header), Some(block), None),
+		);
+		std::fs::remove_file(path).unwrap();
+	}
+
+	#[tokio::test]
+	async fn can_store_child_tries_in_state_snapshot() {
+		init_logger();
+		let path = std::env::temp_dir().join("remote_ext_child_trie_snapshot_test.bin");
+		let header = sp_runtime::testing::Header::new_from_number(1);
+		let kv = vec![(StorageKey(b"key".to_vec()), StorageData(b"value".to_vec()))];
+		let trie = (b"trie".to_vec(), kv.clone());
+
+		let mut writer = SnapshotWriter::new(&path, kv.len()).unwrap();
+		writer.push(&kv[0]).unwrap();
+		writer.push_child_trie(trie.clone());
+		writer.finish::<Block>(&header, None, None).unwrap();
+		assert_eq!(
+			snapshot_payload(&std::fs::read(&path).unwrap()).unwrap(),
+			&[
+				kv.encode(),
+				header.encode(),
+				None::<SignedBlock<Block>>.encode(),
+				None::<Vec<u8>>.encode(),
+				vec![trie].encode(),
+			].concat()[..],
+		);
+
+		let mut ext = Builder::<Block>::new()
+			.mode(Mode::Offline(OfflineConfig { state_snapshot: SnapshotConfig::new(&path) }))
+			.build()
+			.await
+			.expect("Can't read state snapshot file");
+		ext.execute_with(|| {
+			assert_eq!(sp_io::default_child_storage::get(b"trie", b"key"), Some(b"value".to_vec()));
+			assert_eq!(sp_io::storage::get(b"key"), Some(b"value".to_vec()));
+		});
+		std::fs::remove_file(path).unwrap();
+	}
+
+	#[tokio::test]
+	async fn rejects_partial_state_root_check_before_connecting() {
+		// nothing listens there, so connecting first would fail with another error.
+		let config = OnlineConfig {
+			transport: "ws://127.0.0.1:1".to_owned().into(),
+			modules: vec!["System".to_owned()],
+			check_state_root: true,
+			..Default::default()
+		};
+		let result = Builder::<Block>::new().mode(Mode::Online(config)).build().await;
+		assert_eq!(
+			result.err(),
+			Some("state root can only be checked when scraping the entire chain state"),
+		);
+	}
+
+	#[test]
+	fn transport_needs_an_endpoint() {
+		assert!(Transport::try_from(Vec::new()).is_err());
+		let uris = vec!["ws://a".to_owned(), "ws://b".to_owned()];
+		let transport = Transport::try_from(uris.clone()).unwrap();
+		assert_eq!(transport.uris, uris);
+		assert_eq!(transport.uri(), "ws://a");
+	}
+
+	#[test]
+	fn snapshot_writer_checks_key_count() {
+		let path = std::env::temp_dir().join("remote_ext_key_count_snapshot_test.bin");
+		let header = sp_runtime::testing::Header::new_from_number(1);
+		let writer = SnapshotWriter::new(&path, 1).unwrap();
+		assert_eq!(
+			writer.finish::<Block>(&header, None, None),
+			Err("state snapshot key count mismatch"),
+		);
+		assert!(!path.exists());
+		std::fs::remove_file(path.with_extension("partial")).unwrap();
+	}
+
+	#[test]
+	fn detects_corrupted_and_truncated_snapshots() {
+		let path = std::env::temp_dir().join("remote_ext_checksum_snapshot_test.bin");
+		let header = sp_runtime::testing::Header::new_from_number(1);
+		let mut writer = SnapshotWriter::new(&path, 1).unwrap();
+		writer.push(&(StorageKey(b"key".to_vec()), StorageData(b"value".to_vec()))).unwrap();
+		writer.finish::<Block>(&header, None, None).unwrap();
+		let bytes = std::fs::read(&path).unwrap();
+		assert!(open_snapshot(&path).is_ok());
+
+		let mut corrupted = bytes.clone();
+		*corrupted.last_mut().unwrap() ^= 1;
+		assert_eq!(snapshot_payload(&corrupted), Err("state snapshot checksum mismatch"));
+		std::fs::write(&path, &corrupted).unwrap();
+		assert_eq!(open_snapshot(&path).err(), Some("state snapshot checksum mismatch"));
+
+		let truncated = &bytes[..bytes.len() - 1];
+		assert_eq!(snapshot_payload(truncated), Err("state snapshot truncated"));
+		std::fs::write(&path, truncated).unwrap();
+		assert_eq!(open_snapshot(&path).err(), Some("state snapshot truncated"));
+		std::fs::remove_file(path).unwrap();
+	}
+
+	#[test]
+	fn writes_keys_one_per_line() {
+		let path = std::env::temp_dir().join("remote_ext_keys_test.txt");
+		write_keys(&path, &[StorageKey(vec![0x26, 0xaa]), StorageKey(b":code".to_vec())]).unwrap();
+		assert_eq!(std::fs::read_to_string(&path).unwrap(), "0x26aa\n0x3a636f6465\n");
+		std::fs::remove_file(path).unwrap();
+	}
+
+	#[tokio::test]
+	async fn cannot_scrape_keys_offline() {
+		let keys = Builder::<Block>::new()
+			.mode(Mode::Offline(OfflineConfig {
+				state_snapshot: SnapshotConfig::new("test_data/proxy_test"),
+			}))
+			.scrape_keys()
+			.await;
+		assert_eq!(keys, Err("keys can only be scraped from a remote node"));
+	}
+
+	#[tokio::test]
+	async fn cannot_fetch_block_offline() {
+		let block = Builder::<Block>::new()
+			.mode(Mode::Offline(OfflineConfig {
+				state_snapshot: SnapshotConfig::new("test_data/proxy_test"),
+			}))
+			.fetch_block()
+			.await;
+		assert_eq!(block, Err("blocks can only be fetched from a remote node"));
+	}
+
+	#[test]
+	fn merges_sources() {
+		let pair = |k: &[u8], v: u8| (StorageKey(k.to_vec()), StorageData(vec![v]));
+		let get = |ext: &mut TestExternalities, k: &[u8]| {
+			ext.execute_with(|| sp_io::storage::get(k))
+		};
+		let mut ext = TestExternalities::new_empty();
+		insert_batch(&mut ext, &mut vec![pair(b"a", 1), pair(b"b", 1)]);
+
+		let pairs = vec![pair(b"a", 2), pair(b"c", 2)];
+		assert_eq!(merge_into(&mut ext, pairs, &Merge::KeepExisting), 1);
+		assert_eq!(get(&mut ext, b"a"), Some(vec![1]));
+		assert_eq!(get(&mut ext, b"c"), Some(vec![2]));
+
+		assert_eq!(merge_into(&mut ext, vec![pair(b"b", 3)], &Merge::Overwrite), 1);
+		assert_eq!(get(&mut ext, b"b"), Some(vec![3]));
+
+		let namespace = Merge::Namespace(b"other:".to_vec());
+		assert_eq!(merge_into(&mut ext, vec![pair(b"a", 4)], &namespace), 1);
+		assert_eq!(get(&mut ext, b"a"), Some(vec![1]));
+		assert_eq!(get(&mut ext, b"other:a"), Some(vec![4]));
+	}
+
+	#[test]
+	fn filters_hex_keys() {
+		let key = [0x26, 0xaa, 0x39, 0x4e];
+		let glob = |g: &str| KeyFilter::Glob(g.to_string()).matches(&key);
+		assert!(glob("0x26aa*"));
+		assert!(glob("0x26AA*"));
+		assert!(glob("*394e"));
+		assert!(glob("0x*a?39*"));
+		assert!(glob("*"));
+		assert!(!glob("0x26aa"));
+		assert!(!glob("*26ab*"));
+	}
+
+	#[cfg(feature = "regex")]
+	#[test]
+	fn filters_hex_keys_by_regex() {
+		let key = [0x26, 0xaa, 0x39, 0x4e];
+		let regex = |r: &str| KeyFilter::Regex(regex::Regex::new(r).unwrap()).matches(&key);
+		assert!(regex("aa39"));
+		assert!(regex("^0x26"));
+		assert!(!regex("^26"));
+	}
+
+	#[tokio::test]
+	async fn runs_teardown_hooks_on_drop() {
+		use std::sync::{Arc, Mutex};
+
+		let order = Arc::new(Mutex::new(vec![]));
+		let (first, second) = (order.clone(), order.clone());
+		let path = std::env::temp_dir().join("remote_ext_teardown_test.bin");
+		std::fs::write(&path, b"temp").unwrap();
+
+		let mut ext = Builder::<Block>::new()
+			.mode(Mode::Offline(OfflineConfig {
+				state_snapshot: SnapshotConfig::new("test_data/proxy_test"),
+			}))
+			.on_teardown(move || first.lock().unwrap().push(1))
+			.build()
+			.await
+			.expect("Can't read state snapshot file");
+		ext.teardown.push(|| panic!("contained"));
+		ext.teardown.push(move || second.lock().unwrap().push(2));
+		ext.teardown.remove_file(&path);
+
+		assert!(order.lock().unwrap().is_empty());
+		drop(ext);
+		assert_eq!(*order.lock().unwrap(), vec![2, 1]);
+		assert!(!path.exists());
+	}
+
+	#[tokio::test]
+	async fn runs_teardown_hooks_if_build_fails() {
+		use std::sync::{Arc, atomic::{AtomicBool, Ordering}};
+
+		let ran = Arc::new(AtomicBool::new(false));
+		let hook = ran.clone();
+		let result = Builder::<Block>::new()
+			.mode(Mode::Offline(OfflineConfig {
+				state_snapshot: SnapshotConfig::new("test_data/does_not_exist"),
+			}))
+			.on_teardown(move || hook.store(true, Ordering::SeqCst))
+			.build()
+			.await;
+		assert!(result.is_err());
+		assert!(ran.load(Ordering::SeqCst));
+	}
+
+	#[tokio::test]
+	async fn times_out_rpc_calls() {
+		let pending = std::future::pending::<Result<(), ()>>();
+		assert_eq!(
+			timed(Some(Duration::from_millis(10)), pending).await,
+			Err("timed out after 10ms".to_string()),
+		);
+		assert_eq!(timed(None, async { Err::<(), _>("failed") }).await, Err("\"failed\"".into()));
+		assert_eq!(timed(Some(Duration::from_secs(1)), async { Ok::<_, ()>(1) }).await, Ok(1));
+	}
+
+	#[test]
+	fn account_keys_match_storage_maps() {
+		use frame_support::generate_storage_alias;
+
+		generate_storage_alias!(System, Account => Map<(Blake2_128Concat, AccountId32), u32>);
+
+		let account = AccountId32::new([7; 32]);
+		assert_eq!(account_key("System", "Account", &account).0, Account::hashed_key_for(&account));
+
+		let accounts = [account.clone(), AccountId32::new([8; 32])];
+		let config = OnlineConfig::<Block>::default()
+			.with_accounts(&accounts, &[SYSTEM_ACCOUNT, BALANCES_LOCKS]);
+		assert_eq!(config.prefixes.len(), 4);
+		assert_eq!(config.prefixes[0], account_key("System", "Account", &account));
+	}
+
+	#[tokio::test]
+	async fn can_inject_typed_values() {
+		use frame_support::{generate_storage_alias, Twox64Concat};
+
+		generate_storage_alias!(Test, Value => Value<u64>);
+		generate_storage_alias!(Test, Items => Map<(Twox64Concat, u32), u64>);
+
+		init_logger();
+		Builder::<Block>::new()
+			.mode(Mode::Offline(OfflineConfig {
+				state_snapshot: SnapshotConfig::new("test_data/proxy_test"),
+			}))
+			.inject_key_value(storage_value_key("Test", "Value"), 42u64)
+			.inject_key_value(storage_map_key::<Twox64Concat, _>("Test", "Items", &7u32), 100u64)
+			.inject_key_value(b":raw", vec![1u8, 2])
+			.build()
+			.await
+			.expect("Can't read state snapshot file")
+			.execute_with(|| {
+				assert_eq!(Value::get(), Some(42));
+				assert_eq!(Items::get(7), Some(100));
+				assert_eq!(sp_io::storage::get(b":raw"), Some(vec![8, 1, 2]));
+			});
+	}
+
+	#[tokio::test]
+	async fn runs_post_build_hooks_in_order() {
+		init_logger();
+		let injected = StorageKey(b"injected".to_vec());
+		Builder::<Block>::new()
+			.mode(Mode::Offline(OfflineConfig {
+				state_snapshot: SnapshotConfig::new("test_data/proxy_test"),
+			}))
+			.inject(&[(injected.clone(), StorageData(vec![1]))])
+			.post_build(|| {
+				assert_eq!(sp_io::storage::get(b"injected"), Some(vec![1]));
+				sp_io::storage::set(b"injected", &[2]);
+			})
+			.post_build(|| {
+				let value = sp_io::storage::get(b"injected").unwrap();
+				sp_io::storage::set(b"injected", &[&value[..], &[3]].concat());
+			})
+			.build()
+			.await
+			.expect("Can't read state snapshot file")
+			.execute_with(|| assert_eq!(sp_io::storage::get(b"injected"), Some(vec![2, 3])));
+	}
+
+	#[tokio::test]
+	async fn can_execute_async_closures() {
+		init_logger();
+		let mut ext = Builder::<Block>::new()
+			.mode(Mode::Offline(OfflineConfig {
+				state_snapshot: SnapshotConfig::new("test_data/proxy_test"),
+			}))
+			.build()
+			.await
+			.expect("Can't read state snapshot file");
+
+		let value = ext
+			.execute_with_async(|| async {
+				sp_io::storage::set(b"async", &[1]);
+				tokio::time::sleep(Duration::from_millis(10)).await;
+				tokio::task::yield_now().await;
+				sp_io::storage::get(b"async")
+			})
+			.await;
+		assert_eq!(value, Some(vec![1]));
+		ext.execute_with(|| assert_eq!(sp_io::storage::get(b"async"), Some(vec![1])));
+	}
+
+	#[test]
+	fn dedups_prefixes() {
+		let prefix = |p: &[u8]| StorageKey(p.to_vec());
+		let prefixes = vec![
+			("System".to_string(), prefix(&[2, 1])),
+			("Raw".to_string(), prefix(&[2, 1, 3])),
+			("Other".to_string(), prefix(&[1])),
+			("System".to_string(), prefix(&[2, 1])),
+			("Sibling".to_string(), prefix(&[2, 2])),
+		];
+		assert_eq!(
+			dedup_prefixes(prefixes),
+			vec![
+				("Other".to_string(), prefix(&[1])),
+				("System".to_string(), prefix(&[2, 1])),
+				("Sibling".to_string(), prefix(&[2, 2])),
+			],
+		);
+	}
+
+	#[tokio::test]
+	async fn repeatedly_injected_keys_keep_last_value() {
+		init_logger();
+		let key = StorageKey(b"injected".to_vec());
+		let mut ext = Builder::<Block>::new()
+			.mode(Mode::Offline(OfflineConfig {
+				state_snapshot: SnapshotConfig::new("test_data/proxy_test"),
+			}))
+			.inject(&[(key.clone(), StorageData(vec![1])), (key.clone(), StorageData(vec![2]))])
+			.build()
+			.await
+			.expect("Can't read state snapshot file");
+		ext.execute_with(|| assert_eq!(sp_io::storage::get(&key.0), Some(vec![2])));
+
+		let mut pairs = vec![
+			(key.clone(), StorageData(vec![1])),
+			(StorageKey(vec![0]), StorageData(vec![3])),
+			(key.clone(), StorageData(vec![2])),
+		];
+		assert_eq!(dedup_pairs(&mut pairs), 1);
+		assert_eq!(
+			pairs,
+			vec![(StorageKey(vec![0]), StorageData(vec![3])), (key, StorageData(vec![2]))],
+		);
+	}
+
+	#[tokio::test]
+	async fn can_override_storage() {
+		use frame_support::{generate_storage_alias, Twox64Concat};
+		generate_storage_alias!(Test, Value => Value<u32>);
+		generate_storage_alias!(Test, Items => Map<(Twox64Concat, u32), u64>);
+		generate_storage_alias!(
+			Test, Pairs => DoubleMap<(Twox64Concat, u32), (Twox64Concat, u32), bool>
+		);
+
+		init_logger();
+		Builder::<Block>::new()
+			.mode(Mode::Offline(OfflineConfig {
+				state_snapshot: SnapshotConfig::new("test_data/proxy_test"),
+			}))
+			.override_value::<Value, _>(42)
+			.override_map_entry::<Items, _, _, _>(7u32, 100u64)
+			.override_double_map_entry::<Pairs, _, _, _, _, _>(1u32, 2u32, true)
+			.build()
+			.await
+			.expect("Can't read state snapshot file")
+			.execute_with(|| {
+				assert_eq!(Value::get(), Some(42));
+				assert_eq!(Items::get(7), Some(100));
+				assert_eq!(Items::get(8), None);
+				assert_eq!(Pairs::get(1, 2), Some(true));
+			});
+	}
 }
 
 #[cfg(all(test, feature = "remote-test"))]
@@ -566,6 +2442,222 @@ mod remote_tests {
 		}
 	}
 
+	#[tokio::test]
+	async fn can_create_state_snapshot_with_block() {
+		init_logger();
+		let path = "test_snapshot_with_block_to_remove.bin";
+		Builder::<Block>::new()
+			.mode(Mode::Online(OnlineConfig {
+				state_snapshot: Some(SnapshotConfig::new(path)),
+				modules: vec!["Proxy".to_owned()],
+				scrape_block: true,
+				..Default::default()
+			}))
+			.build()
+			.await
+			.expect("Can't reach the remote node. Is it running?");
+
+		let ext = Builder::<Block>::new()
+			.mode(Mode::Offline(OfflineConfig { state_snapshot: SnapshotConfig::new(path) }))
+			.build()
+			.await
+			.expect("Can't read state snapshot file");
+		let header = ext.header.clone().expect("snapshot was created with a header");
+		assert_eq!(ext.block.as_ref().map(|b| b.block.header()), Some(&header));
+
+		std::fs::remove_file(path).unwrap();
+	}
+
+	#[tokio::test]
+	async fn can_scrape_child_tries_of_one_pallet() {
+		init_logger();
+		Builder::<Block>::new()
+			.mode(Mode::Online(OnlineConfig {
+				modules: vec!["Crowdloan".to_owned()],
+				scrape_child_tries: true,
+				..Default::default()
+			}))
+			.build()
+			.await
+			.expect("Can't reach the remote node. Is it running?")
+			.execute_with(|| {
+				let root = sp_io::storage::next_key(DEFAULT_CHILD_STORAGE_KEY_PREFIX)
+					.expect("the crowdloans of the remote chain have child tries; qed.");
+				assert!(root.starts_with(DEFAULT_CHILD_STORAGE_KEY_PREFIX));
+			});
+	}
+
+	#[tokio::test]
+	async fn can_fetch_all_and_check_state_root() {
+		init_logger();
+		Builder::<Block>::new()
+			.mode(Mode::Online(OnlineConfig { check_state_root: true, ..Default::default() }))
+			.build()
+			.await
+			.expect("Can't reach the remote node, or state root mismatch")
+			.execute_with(|| {});
+	}
+
+	#[tokio::test]
+	async fn exposes_header_of_scraped_block() {
+		init_logger();
+		let mut ext = Builder::<Block>::new()
+			.mode(Mode::Online(OnlineConfig {
+				modules: vec!["System".to_owned()],
+				..Default::default()
+			}))
+			.build()
+			.await
+			.expect("Can't reach the remote node. Is it running?");
+
+		let header = ext.header.clone().expect("online mode always fetches the header");
+		// `System::Number` is left set to the number of the block that produced this state. The
+		// remote chain uses `u32` block numbers.
+		let number_key = [twox_128(b"System"), twox_128(b"Number")].concat();
+		ext.execute_with(|| {
+			let number: u32 = sp_io::storage::get(&number_key)
+				.and_then(|v| Decode::decode(&mut &*v).ok())
+				.expect("System::Number exists; qed.");
+			assert_eq!(number as u64, *header.number());
+		});
+	}
+
+	#[tokio::test]
+	async fn can_fail_over_to_next_endpoint() {
+		init_logger();
+		Builder::<Block>::new()
+			.mode(Mode::Online(OnlineConfig {
+				transport: Transport::try_from(vec![
+					"ws://127.0.0.1:1".to_owned(),
+					DEFAULT_TARGET.to_owned(),
+				]).unwrap(),
+				modules: vec!["Proxy".to_owned()],
+				..Default::default()
+			}))
+			.build()
+			.await
+			.expect("Can't reach the remote node. Is it running?")
+			.execute_with(|| {});
+	}
+
+	#[tokio::test]
+	async fn can_scrape_keys_only() {
+		init_logger();
+		let path = "test_keys_to_remove.txt";
+		let keys = Builder::<Block>::new()
+			.mode(Mode::Online(OnlineConfig {
+				modules: vec!["Proxy".to_owned()],
+				..Default::default()
+			}))
+			.scrape_keys_into(path)
+			.await
+			.expect("Can't reach the remote node. Is it running?");
+
+		let prefix = format!("0x{}", HexDisplay::from(&twox_128(b"Proxy")));
+		let written = std::fs::read_to_string(path).unwrap();
+		assert_eq!(written.lines().count(), keys);
+		assert!(written.lines().all(|key| key.starts_with(&prefix)));
+
+		std::fs::remove_file(path).unwrap();
+	}
+
+	#[tokio::test]
+	async fn can_collect_storage_stats() {
+		init_logger();
+		let ext = Builder::<Block>::new()
+			.mode(Mode::Online(OnlineConfig {
+				modules: vec!["Proxy".to_owned()],
+				storage_stats: true,
+				..Default::default()
+			}))
+			.build()
+			.await
+			.expect("Can't reach the remote node. Is it running?");
+
+		let stats = ext.stats.expect("storage stats were requested");
+		let prefix = twox_128(b"Proxy").to_vec();
+		assert_eq!(stats.pallets.keys().collect::<Vec<_>>(), vec![&prefix]);
+		assert_eq!(stats.pallets[&prefix], stats.total);
+	}
+
+	#[tokio::test]
+	async fn can_combine_sources() {
+		init_logger();
+		let namespace = b"second:".to_vec();
+		let mut ext = Builder::<Block>::new()
+			.mode(Mode::Online(OnlineConfig {
+				modules: vec!["Proxy".to_owned()],
+				..Default::default()
+			}))
+			.source(
+				OnlineConfig { modules: vec!["Proxy".to_owned()], ..Default::default() },
+				Merge::Namespace(namespace.clone()),
+			)
+			.build()
+			.await
+			.expect("Can't reach the remote node. Is it running?");
+
+		let pairs = export::pairs_of(&mut ext);
+		let namespaced = pairs.iter().filter(|(k, _)| k.0.starts_with(&namespace)).count();
+		assert!(namespaced > 0);
+		assert!(pairs.len() > namespaced);
+	}
+
+	#[tokio::test]
+	async fn can_scrape_selected_accounts() {
+		init_logger();
+		// the polkadot treasury account, which is known to exist.
+		let treasury = AccountId32::new(hex_literal::hex!(
+			"6d6f646c70792f74727372790000000000000000000000000000000000000000"
+		));
+		let mut ext = Builder::<Block>::new()
+			.mode(Mode::Online(
+				OnlineConfig::default().with_accounts(&[treasury.clone()], &[SYSTEM_ACCOUNT]),
+			))
+			.build()
+			.await
+			.expect("Can't reach the remote node. Is it running?");
+
+		let pairs = export::pairs_of(&mut ext);
+		assert_eq!(pairs.len(), 1);
+		assert_eq!(pairs[0].0, account_key("System", "Account", &treasury));
+	}
+
+	#[tokio::test]
+	async fn can_refresh_state_snapshot() {
+		init_logger();
+		let path = "test_base_snapshot_to_remove.bin";
+		let config = OnlineConfig { modules: vec!["Proxy".to_owned()], ..Default::default() };
+		Builder::<Block>::new()
+			.mode(Mode::Online(OnlineConfig {
+				state_snapshot: Some(SnapshotConfig::new(path)),
+				..config.clone()
+			}))
+			.build()
+			.await
+			.expect("Can't reach the remote node. Is it running?");
+
+		// give the chain some time to move on.
+		tokio::time::sleep(std::time::Duration::from_secs(30)).await;
+		let mut refreshed = Builder::<Block>::new()
+			.mode(Mode::Online(OnlineConfig {
+				base_snapshot: Some(SnapshotConfig::new(path)),
+				..config.clone()
+			}))
+			.build()
+			.await
+			.expect("Can't reach the remote node. Is it running?");
+		let at = refreshed.header.as_ref().map(|header| header.hash());
+		let mut scraped = Builder::<Block>::new()
+			.mode(Mode::Online(OnlineConfig { at, ..config }))
+			.build()
+			.await
+			.expect("Can't reach the remote node. Is it running?");
+
+		assert_eq!(export::pairs_of(&mut refreshed), export::pairs_of(&mut scraped));
+		std::fs::remove_file(path).unwrap();
+	}
+
 	#[tokio::test]
 	async fn can_fetch_all() {
 		init_logger();
@@ -0,0 +1,358 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Command line interface of remote externalities.
+//!
+//! Scrapes the state of a remote node into a snapshot file, and inspects or compares snapshot
+//! files, without writing a test just for that:
+//!
+//! ```sh
+//! remote-externalities scrape --uri wss://rpc.polkadot.io --pallets Staking --out staking.bin
+//! remote-externalities keys --uri wss://rpc.polkadot.io --pallets Staking --out staking-keys.txt
+//! remote-externalities scrape --pallets Staking --base staking.bin --out staking.bin
+//! remote-externalities scrape --accounts 13UVJyLnbVp9RBZYFwFGyDvVd1y27Tt8tkntv6Q7JVPhFsTB
+//! remote-externalities inspect staking.bin
+//! remote-externalities diff old.bin new.bin --keys
+//! ```
+//!
+//! The binary is only built with the `cli` feature, e.g. with
+//! `cargo install --path utils/frame/remote-externalities --features cli`.
+
+use std::{collections::BTreeMap, convert::TryFrom, fs, path::{Path, PathBuf}};
+use regex::Regex;
+use structopt::StructOpt;
+use remote_externalities::{
+	Builder, KeyFilter, Mode, OfflineConfig, OnlineConfig, SnapshotConfig, Transport,
+	export::{self, Change, Labels},
+	proxy::Proxy,
+};
+use sp_core::{
+	H256, crypto::AccountId32, hexdisplay::HexDisplay, storage::{StorageKey, StorageData},
+};
+use sp_runtime::{generic, traits::{BlakeTwo256, Header as HeaderT}, OpaqueExtrinsic};
+
+/// Block of a chain with `u32` block numbers and blake2 hashing, such as Polkadot or Kusama.
+type Block = generic::Block<generic::Header<u32, BlakeTwo256>, OpaqueExtrinsic>;
+
+/// Scrape, inspect and diff state snapshots.
+#[derive(StructOpt)]
+#[structopt(rename_all = "kebab-case")]
+enum Command {
+	/// Scrape the state of a remote node into a snapshot file.
+	Scrape {
+		#[allow(missing_docs)]
+		#[structopt(flatten)]
+		source: Source,
+		/// The snapshot file to write.
+		#[structopt(long, default_value = "snapshot.bin")]
+		out: PathBuf,
+		/// A snapshot file of an earlier block to refresh, only downloading what changed since.
+		/// May be the same as `out`.
+		#[structopt(long)]
+		base: Option<PathBuf>,
+		/// Check the scraped state against the state root of the block. Only possible if the
+		/// entire state is scraped.
+		#[structopt(long)]
+		check_state_root: bool,
+		/// Also scrape the block itself into the snapshot.
+		#[structopt(long)]
+		block: bool,
+		/// Print the number of keys and value bytes per pallet once scraped.
+		#[structopt(long)]
+		stats: bool,
+		/// A file holding the SCALE encoded runtime metadata, see `inspect`, to name the pallets
+		/// in the statistics.
+		#[structopt(long)]
+		metadata: Option<PathBuf>,
+	},
+	/// Scrape only the keys of the state of a remote node, without their values.
+	Keys {
+		#[allow(missing_docs)]
+		#[structopt(flatten)]
+		source: Source,
+		/// The file to write the keys to, one hex encoded key per line. If not given, they are
+		/// printed instead.
+		#[structopt(long)]
+		out: Option<PathBuf>,
+	},
+	/// Print a summary of a snapshot file.
+	Inspect {
+		/// The snapshot file to inspect.
+		snapshot: PathBuf,
+		/// A file holding the SCALE encoded runtime metadata, as raw bytes or as the hex string
+		/// returned by `state_getMetadata`, to break the summary down by pallet name. Defaults to
+		/// the metadata stored in the snapshot, if any.
+		#[structopt(long)]
+		metadata: Option<PathBuf>,
+		/// Memory-map the snapshot file rather than reading it, for very large snapshots.
+		#[structopt(long)]
+		mmap: bool,
+	},
+	/// Compare the state of two snapshot files.
+	Diff {
+		/// The old snapshot file.
+		old: PathBuf,
+		/// The new snapshot file.
+		new: PathBuf,
+		/// A file holding the SCALE encoded runtime metadata, see `inspect`.
+		#[structopt(long)]
+		metadata: Option<PathBuf>,
+		/// Print every changed key, not only the number of changes per pallet.
+		#[structopt(long)]
+		keys: bool,
+	},
+}
+
+/// The remote node to scrape, and the part of its state.
+#[derive(StructOpt)]
+#[structopt(rename_all = "kebab-case")]
+struct Source {
+	/// The ws uri of the node. If given multiple times, the next uri is failed over to when the
+	/// one in use fails.
+	#[structopt(long, default_value = "ws://localhost:9944")]
+	uri: Vec<String>,
+	/// The hash of the block to scrape at. Defaults to the latest finalized block.
+	#[structopt(long)]
+	at: Option<H256>,
+	/// The pallets to scrape. If none are given, and no accounts either, the entire state is
+	/// scraped.
+	#[structopt(long)]
+	pallets: Vec<String>,
+	/// The accounts to scrape the entries of, as SS58 addresses or hex encoded account ids.
+	#[structopt(long)]
+	accounts: Vec<AccountId32>,
+	/// The maps keyed by account to scrape the entries of `accounts` from, as `Pallet::Item`.
+	#[structopt(long, default_value = "System::Account")]
+	account_items: Vec<String>,
+	/// Only keep the keys matching this regex, anywhere in their `0x` prefixed hex encoding.
+	#[structopt(long, conflicts_with = "key-glob")]
+	key_regex: Option<String>,
+	/// Only keep the keys matching this glob, e.g. `0x26aa*`, as a whole.
+	#[structopt(long)]
+	key_glob: Option<String>,
+	/// Connect through this proxy, as `socks5://host:port` or `http://host:port`. Only `ws://`
+	/// uris can be proxied.
+	#[structopt(long)]
+	proxy: Option<String>,
+	/// Credentials for the proxy, as `username:password`.
+	#[structopt(long, requires = "proxy")]
+	proxy_auth: Option<String>,
+}
+
+impl Source {
+	fn into_config(self) -> Result<OnlineConfig<Block>, &'static str> {
+		let key_filter = match (self.key_regex, self.key_glob) {
+			(Some(regex), _) => Some(KeyFilter::Regex(
+				Regex::new(&regex).map_err(|_| "invalid key regex")?,
+			)),
+			(None, glob) => glob.map(KeyFilter::Glob),
+		};
+		let items = self.account_items
+			.iter()
+			.map(|item| {
+				let mut parts = item.splitn(2, "::");
+				match (parts.next(), parts.next()) {
+					(Some(pallet), Some(item)) => Ok((pallet, item)),
+					_ => Err("account items must be given as `Pallet::Item`"),
+				}
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+
+		let mut transport = Transport::try_from(self.uri)?;
+		if let Some(uri) = self.proxy {
+			let mut proxy = Proxy::new(uri);
+			if let Some(auth) = self.proxy_auth {
+				let mut parts = auth.splitn(2, ':');
+				match (parts.next(), parts.next()) {
+					(Some(username), Some(password)) =>
+						proxy = proxy.with_credentials(username, password),
+					_ => return Err("proxy credentials must be given as `username:password`"),
+				}
+			}
+			transport = transport.with_proxy(proxy);
+		}
+
+		let config = OnlineConfig {
+			transport,
+			at: self.at,
+			modules: self.pallets,
+			key_filter,
+			..Default::default()
+		};
+		Ok(config.with_accounts(&self.accounts, &items))
+	}
+}
+
+/// Number of keys and their total size in bytes, including values.
+#[derive(Default)]
+struct Usage {
+	keys: usize,
+	bytes: usize,
+}
+
+fn read_labels(metadata: Option<&Path>) -> Result<Option<Labels>, &'static str> {
+	let metadata = match metadata {
+		Some(path) => fs::read(path).map_err(|_| "fs::read failed.")?,
+		None => return Ok(None),
+	};
+	let metadata = match std::str::from_utf8(&metadata) {
+		Ok(hex) if hex.trim().starts_with("0x") =>
+			sp_core::bytes::from_hex(hex.trim()).map_err(|_| "metadata is not valid hex")?,
+		_ => metadata,
+	};
+	Labels::from_metadata(&metadata).map(Some)
+}
+
+/// The pallet of `key`, by name if known, by storage prefix otherwise.
+fn pallet_of(key: &[u8], labels: Option<&Labels>) -> String {
+	if let Some(label) = labels.and_then(|labels| labels.label(key)) {
+		return label.split("::").next().unwrap_or_default().to_string();
+	}
+	if key.starts_with(b":") {
+		return String::from_utf8_lossy(key).into_owned();
+	}
+	format!("0x{}", HexDisplay::from(&&key[..key.len().min(16)]))
+}
+
+fn scrape(
+	source: Source,
+	out: PathBuf,
+	base: Option<PathBuf>,
+	check_state_root: bool,
+	block: bool,
+	stats: bool,
+	metadata: Option<PathBuf>,
+) -> Result<(), &'static str> {
+	let labels = read_labels(metadata.as_deref())?;
+	let config = OnlineConfig {
+		state_snapshot: Some(SnapshotConfig::new(out)),
+		base_snapshot: base.map(SnapshotConfig::new),
+		check_state_root,
+		scrape_block: block,
+		storage_stats: stats,
+		..source.into_config()?
+	};
+	let runtime = tokio::runtime::Runtime::new().map_err(|_| "failed to start tokio runtime")?;
+	let ext = runtime.block_on(Builder::<Block>::new().mode(Mode::Online(config)).build())?;
+	if let Some(stats) = ext.stats.as_ref() {
+		print!("{}", stats.report(labels.as_ref()));
+	}
+	Ok(())
+}
+
+fn keys(source: Source, out: Option<PathBuf>) -> Result<(), &'static str> {
+	let builder = Builder::<Block>::new().mode(Mode::Online(source.into_config()?));
+	let runtime = tokio::runtime::Runtime::new().map_err(|_| "failed to start tokio runtime")?;
+	match out {
+		Some(out) => {
+			let count = runtime.block_on(builder.scrape_keys_into(&out))?;
+			println!("{} keys written to {}", count, out.display());
+		},
+		None => {
+			for key in runtime.block_on(builder.scrape_keys())? {
+				println!("0x{}", HexDisplay::from(&key.0));
+			}
+		},
+	}
+	Ok(())
+}
+
+fn inspect(snapshot: PathBuf, metadata: Option<PathBuf>, mmap: bool) -> Result<(), &'static str> {
+	let mut state_snapshot = SnapshotConfig::new(snapshot);
+	state_snapshot.mmap = mmap;
+	let config = OfflineConfig { state_snapshot };
+	let runtime = tokio::runtime::Runtime::new().map_err(|_| "failed to start tokio runtime")?;
+	let mut ext = runtime.block_on(Builder::<Block>::new().mode(Mode::Offline(config)).build())?;
+
+	match &ext.header {
+		Some(header) => println!("block: #{} ({:?})", header.number(), header.hash()),
+		None => println!("block: unknown"),
+	}
+	println!("block body: {}", if ext.block.is_some() { "included" } else { "not included" });
+	println!("metadata: {}", if ext.metadata.is_some() { "included" } else { "not included" });
+
+	// the given metadata takes precedence over the one stored in the snapshot.
+	let labels = match read_labels(metadata.as_deref())? {
+		Some(labels) => Some(labels),
+		None => ext.metadata.as_deref().map(Labels::from_metadata).transpose()?,
+	};
+
+	let mut total = Usage::default();
+	let mut pallets = BTreeMap::<String, Usage>::new();
+	for (k, v) in export::pairs_of(&mut ext) {
+		let bytes = k.0.len() + v.0.len();
+		let usage = pallets.entry(pallet_of(&k.0, labels.as_ref())).or_default();
+		usage.keys += 1;
+		usage.bytes += bytes;
+		total.keys += 1;
+		total.bytes += bytes;
+	}
+
+	println!("keys: {} ({} bytes)", total.keys, total.bytes);
+	for (pallet, usage) in pallets {
+		println!("  {}: {} keys ({} bytes)", pallet, usage.keys, usage.bytes);
+	}
+	Ok(())
+}
+
+fn diff(old: PathBuf, new: PathBuf, metadata: Option<PathBuf>, keys: bool) -> Result<(), &'static str> {
+	let labels = read_labels(metadata.as_deref())?;
+	let changes = export::diff(export::read_snapshot(&old)?, export::read_snapshot(&new)?);
+
+	// (added, removed, changed) per pallet.
+	let mut pallets = BTreeMap::<String, (usize, usize, usize)>::new();
+	for (key, change) in &changes {
+		let counts = pallets.entry(pallet_of(&key.0, labels.as_ref())).or_default();
+		match change {
+			Change::Added(_) => counts.0 += 1,
+			Change::Removed(_) => counts.1 += 1,
+			Change::Changed(..) => counts.2 += 1,
+		}
+	}
+
+	println!("changed keys: {}", changes.len());
+	for (pallet, (added, removed, changed)) in pallets {
+		println!("  {}: {} added, {} removed, {} changed", pallet, added, removed, changed);
+	}
+	if keys {
+		let hex = |data: &StorageData| format!("0x{}", HexDisplay::from(&data.0));
+		for (StorageKey(key), change) in changes {
+			let label = labels.as_ref().and_then(|labels| labels.label(&key)).unwrap_or_default();
+			let change = match change {
+				Change::Added(new) => format!("+ {}", hex(&new)),
+				Change::Removed(old) => format!("- {}", hex(&old)),
+				Change::Changed(old, new) => format!("{} -> {}", hex(&old), hex(&new)),
+			};
+			println!("0x{} {} {}", HexDisplay::from(&key), label, change);
+		}
+	}
+	Ok(())
+}
+
+fn main() -> Result<(), &'static str> {
+	env_logger::Builder::from_default_env()
+		.filter_module("remote-ext", log::LevelFilter::Info)
+		.init();
+
+	match Command::from_args() {
+		Command::Scrape { source, out, base, check_state_root, block, stats, metadata } =>
+			scrape(source, out, base, check_state_root, block, stats, metadata),
+		Command::Keys { source, out } => keys(source, out),
+		Command::Inspect { snapshot, metadata, mmap } => inspect(snapshot, metadata, mmap),
+		Command::Diff { old, new, metadata, keys } => diff(old, new, metadata, keys),
+	}
+}
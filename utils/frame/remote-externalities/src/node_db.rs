@@ -0,0 +1,162 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Export of scraped state into the database of a node.
+//!
+//! [`export_to_database`] writes scraped state as the genesis state of a new RocksDB or ParityDB
+//! database, in the layout a Substrate full node uses. A node can then be started directly on top
+//! of the scraped state, by pointing its `--base-path` at a directory containing the database at
+//! `chains/<chain id>/db/full` (RocksDB) or `chains/<chain id>/paritydb` (ParityDB).
+//!
+//! The node still needs a chain spec of the same chain id, but its genesis storage is ignored as
+//! the database already has a genesis block. Hence, the state should usually be mutated with
+//! [`crate::fork_off::ForkOff`] beforehand, such that the validators of the new chain are known.
+//!
+//! ```ignore
+//! let mut ext = Builder::<Block>::new().mode(Mode::Online(online_config)).build().await?;
+//! let genesis = node_db::export_to_database::<Block>(
+//! 	export::pairs_of(&mut ext),
+//! 	DatabaseSettingsSrc::RocksDb { path: "fork/chains/dev/db/full".into(), cache_size: 128 },
+//! 	PruningMode::default(),
+//! )?;
+//! ```
+
+use sc_client_api::{
+	backend::{Backend as _, BlockImportOperation as _, NewBlockState},
+	blockchain::HeaderBackend,
+};
+use sc_client_db::{
+	Backend, DatabaseSettings, DatabaseSettingsSrc, KeepBlocks, PruningMode, TransactionStorageMode,
+};
+use sp_core::storage::{Storage, StorageKey, StorageData};
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, Header as HeaderT, Hash as HashT, Zero},
+};
+
+/// Canonicalization delay of the exported database, as used by a full node.
+const CANONICALIZATION_DELAY: u64 = 4096;
+
+/// Write `state` as genesis state into the new database at `source`, returning the hash of the
+/// genesis block.
+///
+/// The genesis block only commits to the state root of `state`, hence it does not share its hash
+/// with any block of the chain the state was scraped from. `state_pruning` must match the pruning
+/// mode the node is started with.
+pub fn export_to_database<B: BlockT>(
+	state: Vec<(StorageKey, StorageData)>,
+	source: DatabaseSettingsSrc,
+	state_pruning: PruningMode,
+) -> Result<B::Hash, &'static str> {
+	let settings = DatabaseSettings {
+		state_cache_size: 0,
+		state_cache_child_ratio: None,
+		state_pruning,
+		source,
+		keep_blocks: KeepBlocks::All,
+		transaction_storage: TransactionStorageMode::BlockBody,
+	};
+	let backend = Backend::<B>::new(settings, CANONICALIZATION_DELAY)
+		.map_err(|_| "failed to open database")?;
+	if backend.blockchain().header(BlockId::Number(Zero::zero()))
+		.map_err(|_| "failed to read database")?
+		.is_some()
+	{
+		return Err("database is not empty");
+	}
+
+	let storage = Storage {
+		top: state.into_iter().map(|(k, v)| (k.0, v.0)).collect(),
+		children_default: Default::default(),
+	};
+	log::info!(target: crate::LOG_TARGET, "exporting {} keys into database", storage.top.len());
+
+	let mut op = backend.begin_operation().map_err(|_| "failed to begin operation")?;
+	backend.begin_state_operation(&mut op, BlockId::Hash(Default::default()))
+		.map_err(|_| "failed to begin operation")?;
+	let state_root = op.reset_storage(storage).map_err(|_| "invalid state")?;
+
+	// the same genesis header a node would construct from the state.
+	let extrinsics_root = <<B::Header as HeaderT>::Hashing as HashT>::trie_root(Vec::new());
+	let header = B::Header::new(
+		Zero::zero(),
+		extrinsics_root,
+		state_root,
+		Default::default(),
+		Default::default(),
+	);
+	let hash = header.hash();
+	op.set_block_data(header, Some(vec![]), None, NewBlockState::Final)
+		.map_err(|_| "failed to set genesis block")?;
+	backend.commit_operation(op).map_err(|_| "failed to commit genesis block")?;
+
+	log::info!(target: crate::LOG_TARGET, "exported state with genesis hash {:?}", hash);
+	Ok(hash)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sc_client_api::backend::StateBackend;
+	use crate::test_prelude::Block;
+
+	fn source(dir: &tempfile::TempDir) -> DatabaseSettingsSrc {
+		DatabaseSettingsSrc::ParityDb { path: dir.path().join("paritydb") }
+	}
+
+	fn open(dir: &tempfile::TempDir) -> Backend<Block> {
+		let settings = DatabaseSettings {
+			state_cache_size: 0,
+			state_cache_child_ratio: None,
+			state_pruning: PruningMode::default(),
+			source: source(dir),
+			keep_blocks: KeepBlocks::All,
+			transaction_storage: TransactionStorageMode::BlockBody,
+		};
+		Backend::new(settings, CANONICALIZATION_DELAY).unwrap()
+	}
+
+	#[test]
+	fn exports_state_as_genesis() {
+		let dir = tempfile::tempdir().unwrap();
+		let state = vec![
+			(StorageKey(b":code".to_vec()), StorageData(vec![1, 2, 3])),
+			(StorageKey(vec![0xff]), StorageData(vec![4])),
+		];
+
+		let hash = export_to_database::<Block>(state, source(&dir), PruningMode::default()).unwrap();
+
+		let backend = open(&dir);
+		let info = backend.blockchain().info();
+		assert_eq!(info.genesis_hash, hash);
+		assert_eq!(info.finalized_hash, hash);
+		let state = backend.state_at(BlockId::Hash(hash)).unwrap();
+		assert_eq!(state.storage(b":code").unwrap(), Some(vec![1, 2, 3]));
+		assert_eq!(state.storage(&[0xff]).unwrap(), Some(vec![4]));
+	}
+
+	#[test]
+	fn requires_empty_database() {
+		let dir = tempfile::tempdir().unwrap();
+		export_to_database::<Block>(vec![], source(&dir), PruningMode::default()).unwrap();
+
+		assert_eq!(
+			export_to_database::<Block>(vec![], source(&dir), PruningMode::default()),
+			Err("database is not empty"),
+		);
+	}
+}
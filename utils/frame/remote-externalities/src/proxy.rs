@@ -0,0 +1,294 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Connecting to remote nodes through a SOCKS5 or HTTP proxy.
+//!
+//! The ws client connects by itself, without a way to hand it an established connection. Hence a
+//! local tunnel is started instead, which forwards every connection made to it through the proxy
+//! to the remote node, and the ws client connects to the tunnel.
+//!
+//! As the tunnel is plain TCP, only `ws://` endpoints can be reached that way. For `wss://` ones,
+//! the TLS session would have to be established with the tunnel rather than with the node.
+
+use std::net::SocketAddr;
+use log::*;
+use tokio::{
+	io::{AsyncReadExt, AsyncWriteExt},
+	net::{TcpListener, TcpStream},
+	task::JoinHandle,
+};
+use crate::LOG_TARGET;
+
+/// Maximum size of the response of an HTTP proxy to a `CONNECT` request.
+const MAX_HTTP_RESPONSE: usize = 8 * 1024;
+
+/// A proxy to connect to remote nodes through.
+#[derive(Clone, Debug)]
+pub struct Proxy {
+	/// The uri of the proxy, either `socks5://host:port` or `http://host:port`.
+	pub uri: String,
+	/// Username and password to authenticate with, if any.
+	pub credentials: Option<(String, String)>,
+}
+
+impl Proxy {
+	/// A proxy at `uri`, without authentication.
+	pub fn new<S: Into<String>>(uri: S) -> Self {
+		Self { uri: uri.into(), credentials: None }
+	}
+
+	/// Authenticate with `username` and `password`.
+	pub fn with_credentials<S: Into<String>>(mut self, username: S, password: S) -> Self {
+		self.credentials = Some((username.into(), password.into()));
+		self
+	}
+}
+
+/// Kind of proxy, and its `host:port`.
+enum Kind<'a> {
+	Socks5(&'a str),
+	Http(&'a str),
+}
+
+fn kind(uri: &str) -> Result<Kind, &'static str> {
+	if let Some(addr) = uri.strip_prefix("socks5://") {
+		Ok(Kind::Socks5(addr.trim_end_matches('/')))
+	} else if let Some(addr) = uri.strip_prefix("http://") {
+		Ok(Kind::Http(addr.trim_end_matches('/')))
+	} else {
+		Err("proxy uri must start with socks5:// or http://")
+	}
+}
+
+/// Split a `ws://host:port/path` endpoint into its host, port and path.
+fn split_endpoint(uri: &str) -> Result<(&str, u16, &str), &'static str> {
+	let rest = match uri.strip_prefix("ws://") {
+		Some(rest) => rest,
+		None if uri.starts_with("wss://") => return Err("wss:// endpoints can not be proxied"),
+		None => return Err("endpoint must start with ws://"),
+	};
+	let (authority, path) = match rest.find('/') {
+		Some(index) => rest.split_at(index),
+		None => (rest, ""),
+	};
+	match authority.rfind(':') {
+		Some(index) if !authority.ends_with(']') => {
+			let port = authority[index + 1..].parse().map_err(|_| "invalid endpoint port")?;
+			Ok((&authority[..index], port, path))
+		},
+		_ => Ok((authority, 80, path)),
+	}
+}
+
+/// Start a local tunnel to the `ws://` endpoint `uri` through `proxy`.
+///
+/// Returns the endpoint to connect to instead, and the task running the tunnel, which runs until
+/// aborted.
+pub(crate) async fn tunnel(
+	proxy: &Proxy,
+	uri: &str,
+) -> Result<(String, JoinHandle<()>), &'static str> {
+	let (host, port, path) = split_endpoint(uri)?;
+	// fail early on a malformed proxy uri, rather than on every connection.
+	kind(&proxy.uri)?;
+
+	let listener = TcpListener::bind("127.0.0.1:0").await.map_err(|_| "failed to bind tunnel")?;
+	let local = listener.local_addr().map_err(|_| "failed to bind tunnel")?;
+	info!(target: LOG_TARGET, "tunneling {} through proxy {} at {}", uri, proxy.uri, local);
+
+	let (proxy, host) = (proxy.clone(), host.to_string());
+	let task = tokio::spawn(async move {
+		loop {
+			let inbound = match listener.accept().await {
+				Ok((inbound, _)) => inbound,
+				Err(e) => {
+					error!(target: LOG_TARGET, "tunnel failed to accept: {:?}", e);
+					return;
+				},
+			};
+			let (proxy, host) = (proxy.clone(), host.clone());
+			tokio::spawn(async move {
+				if let Err(e) = forward(inbound, &proxy, &host, port).await {
+					error!(target: LOG_TARGET, "tunnel to {}:{} failed: {}", host, port, e);
+				}
+			});
+		}
+	});
+
+	Ok((format!("ws://{}{}", local, path), task))
+}
+
+/// Forward `inbound` to `host:port` through `proxy`, until either side closes.
+async fn forward(
+	mut inbound: TcpStream,
+	proxy: &Proxy,
+	host: &str,
+	port: u16,
+) -> Result<(), &'static str> {
+	let mut outbound = match kind(&proxy.uri)? {
+		Kind::Socks5(addr) => socks5_connect(addr, proxy.credentials.as_ref(), host, port).await?,
+		Kind::Http(addr) => http_connect(addr, proxy.credentials.as_ref(), host, port).await?,
+	};
+	tokio::io::copy_bidirectional(&mut inbound, &mut outbound)
+		.await
+		.map(drop)
+		.map_err(|_| "connection closed unexpectedly")
+}
+
+async fn socks5_connect(
+	proxy: &str,
+	credentials: Option<&(String, String)>,
+	host: &str,
+	port: u16,
+) -> Result<TcpStream, &'static str> {
+	let io = |_| "socks5 proxy i/o failed";
+	let mut stream = TcpStream::connect(proxy).await.map_err(|_| "failed to reach proxy")?;
+
+	// greeting, offering no authentication or username/password.
+	let methods: &[u8] = if credentials.is_some() { &[0x00, 0x02] } else { &[0x00] };
+	stream.write_all(&[&[0x05, methods.len() as u8][..], methods].concat()).await.map_err(io)?;
+	let mut choice = [0u8; 2];
+	stream.read_exact(&mut choice).await.map_err(io)?;
+	match (choice[1], credentials) {
+		(0x00, _) => {},
+		(0x02, Some((username, password))) => {
+			if username.len() > 255 || password.len() > 255 {
+				return Err("socks5 credentials must be at most 255 bytes each");
+			}
+			let request = [
+				&[0x01, username.len() as u8][..],
+				username.as_bytes(),
+				&[password.len() as u8],
+				password.as_bytes(),
+			].concat();
+			stream.write_all(&request).await.map_err(io)?;
+			let mut status = [0u8; 2];
+			stream.read_exact(&mut status).await.map_err(io)?;
+			if status[1] != 0x00 {
+				return Err("socks5 proxy rejected the credentials");
+			}
+		},
+		_ => return Err("socks5 proxy requires an unsupported authentication method"),
+	}
+
+	// connect to the endpoint by domain name, resolved by the proxy.
+	if host.len() > 255 {
+		return Err("endpoint host too long for socks5");
+	}
+	let request = [
+		&[0x05, 0x01, 0x00, 0x03, host.len() as u8][..],
+		host.as_bytes(),
+		&port.to_be_bytes(),
+	].concat();
+	stream.write_all(&request).await.map_err(io)?;
+	let mut reply = [0u8; 4];
+	stream.read_exact(&mut reply).await.map_err(io)?;
+	if reply[1] != 0x00 {
+		return Err("socks5 proxy failed to connect to the endpoint");
+	}
+	// skip the bound address and port.
+	let address_len = match reply[3] {
+		0x01 => 4,
+		0x04 => 16,
+		0x03 => stream.read_u8().await.map_err(io)? as usize,
+		_ => return Err("socks5 proxy replied with an invalid address type"),
+	};
+	let mut bound = vec![0u8; address_len + 2];
+	stream.read_exact(&mut bound).await.map_err(io)?;
+	Ok(stream)
+}
+
+async fn http_connect(
+	proxy: &str,
+	credentials: Option<&(String, String)>,
+	host: &str,
+	port: u16,
+) -> Result<TcpStream, &'static str> {
+	let io = |_| "http proxy i/o failed";
+	let mut stream = TcpStream::connect(proxy).await.map_err(|_| "failed to reach proxy")?;
+
+	let mut request = format!("CONNECT {0}:{1} HTTP/1.1\r\nHost: {0}:{1}\r\n", host, port);
+	if let Some((username, password)) = credentials {
+		let token = base64::encode(format!("{}:{}", username, password));
+		request.push_str(&format!("Proxy-Authorization: Basic {}\r\n", token));
+	}
+	request.push_str("\r\n");
+	stream.write_all(request.as_bytes()).await.map_err(io)?;
+
+	// read byte by byte, so nothing the endpoint sends after the response is consumed.
+	let mut response = Vec::new();
+	while !response.ends_with(b"\r\n\r\n") {
+		if response.len() == MAX_HTTP_RESPONSE {
+			return Err("http proxy response too large");
+		}
+		response.push(stream.read_u8().await.map_err(io)?);
+	}
+	let status = response.split(|b| *b == b' ').nth(1);
+	if status != Some(&b"200"[..]) {
+		warn!(target: LOG_TARGET, "http proxy replied: {}", String::from_utf8_lossy(&response));
+		return Err("http proxy failed to connect to the endpoint");
+	}
+	Ok(stream)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn splits_endpoints() {
+		assert_eq!(split_endpoint("ws://localhost:9944"), Ok(("localhost", 9944, "")));
+		assert_eq!(split_endpoint("ws://node.io/rpc"), Ok(("node.io", 80, "/rpc")));
+		assert_eq!(split_endpoint("ws://[::1]"), Ok(("[::1]", 80, "")));
+		assert_eq!(split_endpoint("wss://node.io"), Err("wss:// endpoints can not be proxied"));
+	}
+
+	#[tokio::test]
+	async fn tunnels_through_http_proxy() {
+		// a proxy that accepts the `CONNECT` and then echoes.
+		let proxy = TcpListener::bind("127.0.0.1:0").await.unwrap();
+		let proxy_addr = proxy.local_addr().unwrap();
+		tokio::spawn(async move {
+			let (mut stream, _) = proxy.accept().await.unwrap();
+			let mut request = Vec::new();
+			while !request.ends_with(b"\r\n\r\n") {
+				request.push(stream.read_u8().await.unwrap());
+			}
+			assert!(request.starts_with(b"CONNECT node.io:9944 HTTP/1.1\r\n"));
+			stream.write_all(b"HTTP/1.1 200 Connection established\r\n\r\n").await.unwrap();
+			let mut buf = [0u8; 4];
+			stream.read_exact(&mut buf).await.unwrap();
+			stream.write_all(&buf).await.unwrap();
+		});
+
+		let proxy = Proxy::new(format!("http://{}", proxy_addr));
+		let (endpoint, task) = tunnel(&proxy, "ws://node.io:9944/rpc").await.unwrap();
+		let local: SocketAddr = endpoint
+			.strip_prefix("ws://")
+			.and_then(|e| e.strip_suffix("/rpc"))
+			.unwrap()
+			.parse()
+			.unwrap();
+
+		let mut client = TcpStream::connect(local).await.unwrap();
+		client.write_all(b"ping").await.unwrap();
+		let mut buf = [0u8; 4];
+		client.read_exact(&mut buf).await.unwrap();
+		assert_eq!(&buf, b"ping");
+		task.abort();
+	}
+}
@@ -0,0 +1,154 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage statistics of scraped state.
+//!
+//! Counts keys and value bytes per pallet prefix, to find out which pallets hold the most
+//! state. Collected while scraping if [`crate::OnlineConfig::storage_stats`] is set, or from any
+//! key-value pairs with [`StorageStats::from_pairs`].
+
+use std::{collections::BTreeMap, fmt::Write};
+use sp_core::{hexdisplay::HexDisplay, storage::{StorageKey, StorageData}};
+use crate::export::Labels;
+
+/// Length of a pallet prefix, i.e. of `twox_128(pallet)`.
+const PALLET_PREFIX_LEN: usize = 16;
+
+/// Number of keys and total size of their values.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Usage {
+	/// Number of keys.
+	pub keys: usize,
+	/// Total size of the values in bytes.
+	pub value_bytes: usize,
+}
+
+impl Usage {
+	fn add(&mut self, value: &StorageData) {
+		self.keys += 1;
+		self.value_bytes += value.0.len();
+	}
+}
+
+/// Storage usage per pallet prefix.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StorageStats {
+	/// Usage per pallet prefix. Well known keys, such as `:code`, are their own prefix.
+	pub pallets: BTreeMap<Vec<u8>, Usage>,
+	/// Usage of the entire state.
+	pub total: Usage,
+}
+
+impl StorageStats {
+	/// Create empty statistics.
+	pub fn new() -> Self {
+		Default::default()
+	}
+
+	/// Statistics of `pairs`.
+	pub fn from_pairs<'a>(pairs: impl IntoIterator<Item = &'a (StorageKey, StorageData)>) -> Self {
+		let mut stats = Self::new();
+		for (key, value) in pairs {
+			stats.add(key, value);
+		}
+		stats
+	}
+
+	/// Account for the pair of `key` and `value`.
+	pub fn add(&mut self, key: &StorageKey, value: &StorageData) {
+		let prefix = if key.0.starts_with(b":") {
+			&key.0[..]
+		} else {
+			&key.0[..key.0.len().min(PALLET_PREFIX_LEN)]
+		};
+		self.pallets.entry(prefix.to_vec()).or_default().add(value);
+		self.total.add(value);
+	}
+
+	/// A human readable report, listing the pallets by value bytes, largest first.
+	///
+	/// Pallets are named after their storage prefix if found in `labels`, and hex encoded
+	/// otherwise.
+	pub fn report(&self, labels: Option<&Labels>) -> String {
+		let name = |prefix: &[u8]| {
+			labels
+				.and_then(|labels| labels.label(prefix))
+				.unwrap_or_else(|| format!("0x{}", HexDisplay::from(&prefix)))
+		};
+		let mut pallets = self.pallets.iter().collect::<Vec<_>>();
+		pallets.sort_by(|(_, a), (_, b)| b.value_bytes.cmp(&a.value_bytes));
+
+		let mut report = String::new();
+		let _ = writeln!(
+			report,
+			"total: {} keys, {} value bytes",
+			self.total.keys,
+			self.total.value_bytes,
+		);
+		for (prefix, usage) in pallets {
+			let share = if self.total.value_bytes > 0 {
+				usage.value_bytes as f64 * 100.0 / self.total.value_bytes as f64
+			} else {
+				0.0
+			};
+			let _ = writeln!(
+				report,
+				"  {}: {} keys, {} value bytes ({:.2}%)",
+				name(prefix),
+				usage.keys,
+				usage.value_bytes,
+				share,
+			);
+		}
+		report
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::hashing::twox_128;
+
+	#[test]
+	fn reports_usage_per_pallet() {
+		let key = |pallet: &[u8], item: &[u8]| {
+			StorageKey([twox_128(pallet), twox_128(item)].concat())
+		};
+		let pairs = vec![
+			(key(b"System", b"Number"), StorageData(vec![0; 4])),
+			(key(b"System", b"Account"), StorageData(vec![0; 10])),
+			(key(b"Proxy", b"Proxies"), StorageData(vec![0; 30])),
+			(StorageKey(b":code".to_vec()), StorageData(vec![0; 2])),
+		];
+		let stats = StorageStats::from_pairs(&pairs);
+		assert_eq!(stats.total, Usage { keys: 4, value_bytes: 46 });
+		assert_eq!(stats.pallets.len(), 3);
+		assert_eq!(
+			stats.pallets[&twox_128(b"System").to_vec()],
+			Usage { keys: 2, value_bytes: 14 },
+		);
+
+		let labels = Labels::new().with_item("System", "Number").with_item("Proxy", "Proxies");
+		assert_eq!(
+			stats.report(Some(&labels)),
+			"total: 4 keys, 46 value bytes\n\
+			\x20 Proxy: 1 keys, 30 value bytes (65.22%)\n\
+			\x20 System: 2 keys, 14 value bytes (30.43%)\n\
+			\x20 :code: 1 keys, 2 value bytes (4.35%)\n",
+		);
+	}
+}
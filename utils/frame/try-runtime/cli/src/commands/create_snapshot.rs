@@ -0,0 +1,90 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The `create-snapshot` command.
+
+use std::{fmt::Debug, path::PathBuf, str::FromStr};
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+use remote_externalities::{Builder, Mode, OnlineConfig, SnapshotConfig};
+use crate::{parse_block_hash, parse_hash, parse_url};
+
+/// Scrape the state of a live chain into a snapshot file, without running anything on it.
+#[derive(Debug, structopt::StructOpt)]
+pub struct CreateSnapshotCmd {
+	/// The uri of the node to scrape.
+	#[structopt(
+		long,
+		default_value = "ws://localhost:9944",
+		parse(try_from_str = parse_url),
+	)]
+	pub uri: String,
+
+	/// The block hash at which to scrape. Will be latest finalized head if not provided.
+	#[structopt(long, multiple = false, parse(try_from_str = parse_hash))]
+	pub at: Option<String>,
+
+	/// The pallets to scrape. If empty, entire chain state will be scraped.
+	#[structopt(long, require_delimiter = true)]
+	pub pallets: Vec<String>,
+
+	/// Also store the block itself in the snapshot.
+	#[structopt(long)]
+	pub block: bool,
+
+	/// Also scrape the child tries of the state, e.g. those of crowdloan contributions.
+	#[structopt(long)]
+	pub child_tree: bool,
+
+	/// The snapshot file to write, to be used later on as `snap` state of any other command.
+	pub snapshot_path: PathBuf,
+}
+
+/// Scrape the state of the chain of `command` into its snapshot file.
+///
+/// Besides the state, the snapshot holds the header of its block and the runtime metadata at it,
+/// such that it can be inspected and reused on its own.
+pub(crate) async fn create_snapshot<B>(command: &CreateSnapshotCmd) -> sc_cli::Result<()>
+where
+	B: BlockT,
+	B::Header: serde::de::DeserializeOwned,
+	B::Hash: FromStr,
+	<B::Hash as FromStr>::Err: Debug,
+{
+	let at = command.at.as_deref().map(parse_block_hash::<B>).transpose()?;
+	let ext = Builder::<B>::new()
+		.mode(Mode::Online(OnlineConfig {
+			transport: command.uri.clone().into(),
+			at,
+			modules: command.pallets.clone(),
+			state_snapshot: Some(SnapshotConfig::new(&command.snapshot_path)),
+			scrape_block: command.block,
+			scrape_child_tries: command.child_tree,
+			scrape_metadata: true,
+			..Default::default()
+		}))
+		.build()
+		.await?;
+
+	let header = ext.header.as_ref().expect("scraped from a live chain, thus known; qed.");
+	log::info!(
+		"snapshot of block #{} ({:?}) written to {:?}",
+		header.number(),
+		header.hash(),
+		command.snapshot_path,
+	);
+	Ok(())
+}
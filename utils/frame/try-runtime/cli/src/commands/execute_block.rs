@@ -0,0 +1,178 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The `execute-block` command.
+
+use std::{fmt::Debug, str::FromStr};
+use parity_scale_codec::{Decode, Encode};
+use sc_service::{Configuration, NativeExecutionDispatch};
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT, UniqueSaturatedInto};
+use frame_try_runtime::UpgradeCheckSelect;
+use crate::{
+	BlockRef, SharedParams, State, build_executor, check_spec, commit_changes, fetch_block,
+	local_code, parse_block_ref, parse_url, resolve_block, state_machine_call,
+	compare::compare_native_wasm, output::ExecuteBlockOutput,
+};
+
+/// Execute a block with the local runtime.
+#[derive(Debug, structopt::StructOpt)]
+pub struct ExecuteBlockCmd {
+	/// Do not check the state root computed by the local runtime against the one of the block.
+	#[structopt(long)]
+	pub no_state_root_check: bool,
+
+	/// Do not verify the signatures of the extrinsics of the block.
+	#[structopt(long)]
+	pub no_signature_check: bool,
+
+	/// The checks to run along with the block: `none`, `pre-and-post` to run the pre and post
+	/// upgrade checks of the migrations if the block upgrades the runtime, `try-state` to check
+	/// the invariants of all pallets after the block, or `all`.
+	#[structopt(long, default_value = "none")]
+	pub checks: UpgradeCheckSelect,
+
+	/// Also execute the block with both the native and the wasm runtime, and fail if their
+	/// results, storage changes or state roots differ.
+	#[structopt(long)]
+	pub compare_native_wasm: bool,
+
+	/// The block to execute, by number such as `9_000_000` or by hash.
+	///
+	/// The block and the state of its parent are fetched from the node, resolving the number
+	/// first. Defaults to the block of a `live` state, or to the latest finalized block if none is
+	/// given. Required with a `snap` state, which must be the state of the parent of the block.
+	#[structopt(
+		long,
+		alias = "block-at",
+		multiple = false,
+		parse(try_from_str = parse_block_ref),
+	)]
+	pub block: Option<BlockRef>,
+
+	/// The uri of the node to fetch the block from.
+	///
+	/// Defaults to the uri of a `live` state. Required with a `snap` state.
+	#[structopt(long, parse(try_from_str = parse_url))]
+	pub block_uri: Option<String>,
+
+	/// The state to execute the block on. A `live` state is scraped at the parent of the block.
+	#[structopt(subcommand)]
+	pub state: State,
+}
+
+/// Execute the block of `command` with the local runtime on top of the state of its parent, and
+/// report the weight it consumed.
+pub(crate) async fn execute_block<B, ExecDispatch>(
+	shared: &SharedParams,
+	command: &ExecuteBlockCmd,
+	config: Configuration,
+) -> sc_cli::Result<()>
+where
+	B: BlockT,
+	B::Header: serde::de::DeserializeOwned,
+	B::Hash: FromStr,
+	<B::Hash as FromStr>::Err: Debug,
+	ExecDispatch: NativeExecutionDispatch + 'static,
+{
+	let executor = build_executor::<ExecDispatch>(shared, &config);
+	let code = local_code(&config)?;
+
+	let (block_uri, block) = match &command.state {
+		State::Live(live) => (
+			command.block_uri.clone().unwrap_or_else(|| live.uri.clone()),
+			command.block.clone().or_else(|| live.at.clone().map(BlockRef::Hash)),
+		),
+		State::Snap { .. } => (
+			command.block_uri.clone().ok_or("--block-uri is required with a snapshot")?,
+			Some(command.block.clone().ok_or("--block is required with a snapshot")?),
+		),
+	};
+	let block_at = match &block {
+		Some(block) => Some(resolve_block::<B>(&block_uri, block).await?),
+		None => None,
+	};
+	let block = fetch_block::<B>(block_uri, block_at).await?;
+	let parent = *block.header().parent_hash();
+	log::info!("executing block #{} ({:?})", block.header().number(), block.hash());
+
+	// inject the local code into the state of the parent.
+	let mut ext = command.state.builder_at::<B>(Some(parent))?.inject(&[code]).build().await?;
+	if let Some(header) = &ext.header {
+		if header.hash() != parent {
+			return Err(format!(
+				"the state is at {:?}, rather than at the parent of the block {:?}",
+				header.hash(),
+				parent,
+			).into());
+		}
+	}
+	check_spec(&ext.inner_ext, &executor, shared, false)?;
+
+	let mut output = ExecuteBlockOutput {
+		block_hash: format!("{:?}", block.hash()),
+		block_number: (*block.header().number()).unique_saturated_into(),
+		state_root_before: format!("{:?}", ext.inner_ext.backend.root()),
+		expected_state_root: format!("{:?}", block.header().state_root()),
+		..Default::default()
+	};
+	let payload = (
+		block,
+		!command.no_state_root_check,
+		!command.no_signature_check,
+		command.checks,
+	).encode();
+	if command.compare_native_wasm {
+		compare_native_wasm::<B, ExecDispatch>(
+			&ext.inner_ext,
+			&executor,
+			shared,
+			"TryRuntime_execute_block",
+			&payload,
+		)?;
+	}
+
+	let result = state_machine_call::<B, ExecDispatch>(
+		&mut ext.inner_ext,
+		&executor,
+		shared,
+		"TryRuntime_execute_block",
+		&payload,
+	)
+	.map_err(|e| e.to_string())
+	.and_then(|(changes, encoded_result)| {
+		let weight = <u64 as Decode>::decode(&mut &*encoded_result)
+			.map_err(|e| format!("failed to decode output due to {:?}", e))?;
+		let root = commit_changes::<B>(&mut ext.inner_ext, changes)
+			.map_err(|e| format!("failed to commit the changes of the block: {}", e))?;
+		Ok((weight, root))
+	});
+
+	match result {
+		Ok((weight, root)) => {
+			log::info!("block executed without errors. Consumed weight = {}", weight);
+			output.weight = Some(weight);
+			output.state_root_after = Some(format!("{:?}", root));
+			shared.output.emit(&output);
+			Ok(())
+		},
+		Err(e) => {
+			output.error = Some(e.clone());
+			shared.output.emit(&output);
+			Err(e.into())
+		},
+	}
+}
@@ -0,0 +1,177 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The `fast-forward` command.
+
+use std::{fmt::Debug, str::FromStr, time::{SystemTime, UNIX_EPOCH}};
+use parity_scale_codec::{Decode, Encode};
+use sc_executor::NativeExecutor;
+use sc_service::{Configuration, NativeExecutionDispatch};
+use sp_inherents::InherentData;
+use sp_runtime::{
+	ApplyExtrinsicResult,
+	generic::Digest,
+	traits::{Block as BlockT, Header as HeaderT, One, UniqueSaturatedInto},
+};
+use sp_state_machine::Backend;
+use remote_externalities::{TestExternalities, storage_value_key};
+use crate::{
+	BlockBuildingInfoProvider, SharedParams, State, build_executor, check_spec, commit_changes,
+	local_code, state_machine_call, output::AuthoredBlockOutput,
+};
+
+/// Author and execute empty blocks with the local runtime.
+#[derive(Debug, structopt::StructOpt)]
+pub struct FastForwardCmd {
+	/// The number of blocks to author.
+	#[structopt(long)]
+	pub n_blocks: u32,
+
+	/// The time between two blocks, in milliseconds.
+	#[structopt(long, default_value = "6000")]
+	pub blocktime: u64,
+
+	/// The state to author the blocks on top of. It must hold the header of its block, which a
+	/// `live` state always does.
+	#[structopt(subcommand)]
+	pub state: State,
+}
+
+/// Author the blocks of `command` with the local runtime, one after the other, on top of the
+/// state of `command`.
+///
+/// Each block only holds the inherents of the runtime, created from a timestamp that advances by
+/// the block time from the one in the state, and the digest items of `block_building`. As the
+/// local runtime is injected into the state, the first block runs its migrations, if any.
+pub(crate) async fn fast_forward<B, ExecDispatch, BBIP>(
+	shared: &SharedParams,
+	command: &FastForwardCmd,
+	config: Configuration,
+	block_building: BBIP,
+) -> sc_cli::Result<()>
+where
+	B: BlockT,
+	B::Header: serde::de::DeserializeOwned,
+	B::Hash: FromStr,
+	<B::Hash as FromStr>::Err: Debug,
+	ExecDispatch: NativeExecutionDispatch + 'static,
+	BBIP: BlockBuildingInfoProvider<B>,
+{
+	let executor = build_executor::<ExecDispatch>(shared, &config);
+	let code = local_code(&config)?;
+
+	let ext = command.state.builder::<B>()?.inject(&[code]).build().await?;
+	let mut parent = ext.header.clone().ok_or("the state snapshot does not contain a header")?;
+	let mut ext = ext.inner_ext;
+	check_spec(&ext, &executor, shared, false)?;
+
+	let now = ext.backend.storage(&storage_value_key("Timestamp", "Now").0)?;
+	let mut timestamp = match now.map(|now| <u64 as Decode>::decode(&mut &*now)) {
+		Some(Ok(now)) => now,
+		_ => {
+			log::warn!("no timestamp found in the state, starting from the current time");
+			SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64
+		},
+	};
+
+	for _ in 0..command.n_blocks {
+		timestamp += command.blocktime;
+		let number = *parent.number() + One::one();
+		let header = B::Header::new(
+			number,
+			Default::default(),
+			Default::default(),
+			parent.hash(),
+			Digest { logs: block_building.pre_digest(timestamp) },
+		);
+		call::<B, ExecDispatch>(
+			&mut ext,
+			&executor,
+			shared,
+			"Core_initialize_block",
+			&header.encode(),
+		)?;
+
+		let mut inherent_data = InherentData::new();
+		inherent_data
+			.put_data(sp_timestamp::INHERENT_IDENTIFIER, &timestamp)
+			.map_err(|e| format!("failed to create the inherent data: {:?}", e))?;
+		let encoded_result = call::<B, ExecDispatch>(
+			&mut ext,
+			&executor,
+			shared,
+			"BlockBuilder_inherent_extrinsics",
+			&inherent_data.encode(),
+		)?;
+		let inherents = <Vec<B::Extrinsic> as Decode>::decode(&mut &*encoded_result)
+			.map_err(|e| format!("failed to decode the inherents due to {:?}", e))?;
+
+		for inherent in inherents {
+			let encoded_result = call::<B, ExecDispatch>(
+				&mut ext,
+				&executor,
+				shared,
+				"BlockBuilder_apply_extrinsic",
+				&inherent.encode(),
+			)?;
+			match <ApplyExtrinsicResult as Decode>::decode(&mut &*encoded_result)
+				.map_err(|e| format!("failed to decode output due to {:?}", e))?
+			{
+				Ok(Ok(())) => {},
+				Ok(Err(e)) => log::warn!("an inherent of block #{} failed: {:?}", number, e),
+				Err(e) =>
+					return Err(format!("an inherent of block #{} is invalid: {:?}", number, e).into()),
+			}
+		}
+
+		let encoded_result =
+			call::<B, ExecDispatch>(&mut ext, &executor, shared, "BlockBuilder_finalize_block", &[])?;
+		let header = <B::Header as Decode>::decode(&mut &*encoded_result)
+			.map_err(|e| format!("failed to decode the header due to {:?}", e))?;
+		log::info!(
+			"authored block #{} ({:?}) at timestamp {}, state root = {:?}",
+			header.number(),
+			header.hash(),
+			timestamp,
+			header.state_root(),
+		);
+		shared.output.emit(&AuthoredBlockOutput {
+			block_hash: format!("{:?}", header.hash()),
+			block_number: (*header.number()).unique_saturated_into(),
+			timestamp,
+			state_root: format!("{:?}", header.state_root()),
+		});
+		parent = header;
+	}
+
+	Ok(())
+}
+
+/// Call the runtime api `method` with `data` on top of `ext`, and commit the changes it made.
+fn call<B: BlockT, ExecDispatch: NativeExecutionDispatch + 'static>(
+	ext: &mut TestExternalities,
+	executor: &NativeExecutor<ExecDispatch>,
+	shared: &SharedParams,
+	method: &'static str,
+	data: &[u8],
+) -> sc_cli::Result<Vec<u8>> {
+	let (changes, encoded_result) =
+		state_machine_call::<B, ExecDispatch>(ext, executor, shared, method, data)?;
+	commit_changes::<B>(ext, changes)
+		.map_err(|e| format!("failed to commit the changes of '{}': {}", method, e))?;
+	Ok(encoded_result)
+}
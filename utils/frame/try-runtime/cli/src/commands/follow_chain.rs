@@ -0,0 +1,194 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The `follow-chain` command.
+
+use parity_scale_codec::{Decode, Encode};
+use sc_service::{Configuration, NativeExecutionDispatch};
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT, UniqueSaturatedInto};
+use frame_try_runtime::UpgradeCheckSelect;
+use jsonrpsee_ws_client::{
+	WsClientBuilder, Subscription, traits::SubscriptionClient, v2::params::JsonRpcParams,
+};
+use remote_externalities::{Builder, Mode, OnlineConfig, TestExternalities};
+use crate::{
+	SharedParams, build_executor, check_spec, commit_changes, fetch_block, local_code, parse_url,
+	state_machine_call, output::ExecuteBlockOutput,
+};
+
+const SUB: &str = "chain_subscribeFinalizedHeads";
+const UN_SUB: &str = "chain_unsubscribeFinalizedHeads";
+
+/// Follow the finalized blocks of a live chain, executing each of them with the local runtime.
+#[derive(Debug, structopt::StructOpt)]
+pub struct FollowChainCmd {
+	/// The uri of the node to follow.
+	#[structopt(
+		long,
+		default_value = "ws://localhost:9944",
+		parse(try_from_str = parse_url),
+	)]
+	pub uri: String,
+
+	/// Also scrape the child tries of the initial state, e.g. those of crowdloan contributions.
+	#[structopt(long)]
+	pub child_tree: bool,
+
+	/// Do not verify the signatures of the extrinsics of the blocks.
+	#[structopt(long)]
+	pub no_signature_check: bool,
+
+	/// The checks to run along with each block: `none`, `pre-and-post` to run the pre and post
+	/// upgrade checks of the migrations if the block upgrades the runtime, `try-state` to check
+	/// the invariants of all pallets after the block, or `all`.
+	#[structopt(long, default_value = "none")]
+	pub checks: UpgradeCheckSelect,
+
+	/// Stop with an error at the first block whose state root differs from the one computed by
+	/// the local runtime, rather than only reporting it.
+	#[structopt(long)]
+	pub stop_on_mismatch: bool,
+}
+
+/// Execute every newly finalized block of the chain of `command` with the local runtime, on top
+/// of the state resulting from the previous one, and compare the state roots.
+///
+/// The state is scraped once, at the parent of the first finalized block, and maintained locally
+/// from there on.
+pub(crate) async fn follow_chain<B, ExecDispatch>(
+	shared: &SharedParams,
+	command: &FollowChainCmd,
+	config: Configuration,
+) -> sc_cli::Result<()>
+where
+	B: BlockT,
+	B::Header: serde::de::DeserializeOwned,
+	ExecDispatch: NativeExecutionDispatch + 'static,
+{
+	let executor = build_executor::<ExecDispatch>(shared, &config);
+	let code = local_code(&config)?;
+
+	let client = WsClientBuilder::default()
+		.max_request_body_size(u32::MAX)
+		.build(&command.uri)
+		.await
+		.map_err(|e| format!("failed to connect to {}: {:?}", command.uri, e))?;
+	let mut subscription: Subscription<B::Header> = client
+		.subscribe(SUB, JsonRpcParams::NoParams, UN_SUB)
+		.await
+		.map_err(|e| format!("failed to subscribe to finalized heads: {:?}", e))?;
+	log::info!("following the finalized blocks of {}", command.uri);
+
+	// the local state, along with the hash of the last block executed on top of it.
+	let mut state: Option<(TestExternalities, B::Hash)> = None;
+	let mut mismatches = 0usize;
+	while let Some(header) = subscription.next().await {
+		// finality may advance by more than one block at once, so the blocks in between are
+		// fetched as well, walking back from the finalized one.
+		let mut blocks = Vec::new();
+		let mut hash = header.hash();
+		loop {
+			let block = fetch_block::<B>(command.uri.clone(), Some(hash)).await?;
+			let parent = *block.header().parent_hash();
+			blocks.push(block);
+			match &state {
+				Some((_, last)) if *last != parent => hash = parent,
+				_ => break,
+			}
+		}
+
+		for block in blocks.into_iter().rev() {
+			if state.is_none() {
+				let parent = *block.header().parent_hash();
+				let ext = Builder::<B>::new()
+					.mode(Mode::Online(OnlineConfig {
+						transport: command.uri.clone().into(),
+						at: Some(parent),
+						scrape_child_tries: command.child_tree,
+						..Default::default()
+					}))
+					.inject(&[code.clone()])
+					.build()
+					.await?
+					.inner_ext;
+				check_spec(&ext, &executor, shared, false)?;
+				state = Some((ext, parent));
+			}
+			let (ext, last) = state.as_mut().expect("initialized above; qed");
+
+			let number = *block.header().number();
+			let expected_root = *block.header().state_root();
+			let hash = block.hash();
+			let mut output = ExecuteBlockOutput {
+				block_hash: format!("{:?}", hash),
+				block_number: number.unique_saturated_into(),
+				state_root_before: format!("{:?}", ext.backend.root()),
+				expected_state_root: format!("{:?}", expected_root),
+				..Default::default()
+			};
+
+			let payload = (block, false, !command.no_signature_check, command.checks).encode();
+			let result = state_machine_call::<B, ExecDispatch>(
+				ext,
+				&executor,
+				shared,
+				"TryRuntime_execute_block",
+				&payload,
+			)
+			.map_err(|e| e.to_string())
+			.and_then(|(changes, encoded_result)| {
+				let weight = <u64 as Decode>::decode(&mut &*encoded_result)
+					.map_err(|e| format!("failed to decode output due to {:?}", e))?;
+				let root = commit_changes::<B>(ext, changes).map_err(|e| {
+					format!("failed to apply the changes of block #{}: {}", number, e)
+				})?;
+				Ok((weight, root))
+			});
+			let (weight, root) = match result {
+				Ok(result) => result,
+				Err(e) => {
+					output.error = Some(e.clone());
+					shared.output.emit(&output);
+					return Err(e.into());
+				},
+			};
+			*last = hash;
+			output.weight = Some(weight);
+			output.state_root_after = Some(format!("{:?}", root));
+			shared.output.emit(&output);
+
+			if root.as_ref() == expected_root.as_ref() {
+				log::info!("executed block #{} ({:?}), consumed weight = {}", number, hash, weight);
+			} else {
+				mismatches += 1;
+				log::warn!(
+					"state root mismatch at block #{} ({:?}): expected {:?}, computed {:?}",
+					number,
+					hash,
+					expected_root,
+					root,
+				);
+				if command.stop_on_mismatch {
+					return Err(format!("state root mismatch at block #{}", number).into());
+				}
+			}
+		}
+	}
+
+	log::info!("subscription closed after {} state root mismatches", mismatches);
+	Ok(())
+}
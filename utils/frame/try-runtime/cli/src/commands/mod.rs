@@ -0,0 +1,38 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The commands of `try-runtime`, one module each.
+
+mod create_snapshot;
+mod execute_block;
+mod fast_forward;
+mod follow_chain;
+mod offchain_worker;
+mod on_runtime_upgrade;
+
+pub use create_snapshot::CreateSnapshotCmd;
+pub use execute_block::ExecuteBlockCmd;
+pub use fast_forward::FastForwardCmd;
+pub use follow_chain::FollowChainCmd;
+pub use offchain_worker::OffchainWorkerCmd;
+pub use on_runtime_upgrade::OnRuntimeUpgradeCmd;
+pub(crate) use create_snapshot::create_snapshot;
+pub(crate) use execute_block::execute_block;
+pub(crate) use fast_forward::fast_forward;
+pub(crate) use follow_chain::follow_chain;
+pub(crate) use offchain_worker::offchain_worker;
+pub(crate) use on_runtime_upgrade::on_runtime_upgrade;
@@ -0,0 +1,120 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The `offchain-worker` command.
+
+use std::{fmt::Debug, str::FromStr, sync::Arc, time::{SystemTime, UNIX_EPOCH}};
+use parity_scale_codec::Encode;
+use sc_service::{Configuration, NativeExecutionDispatch};
+use sp_core::{
+	hexdisplay::HexDisplay,
+	offchain::{
+		OffchainDbExt, OffchainWorkerExt, Timestamp, TransactionPoolExt,
+		testing::{TestOffchainExt, TestTransactionPoolExt},
+	},
+};
+use sp_keystore::{KeystoreExt, testing::KeyStore};
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+use crate::{
+	SharedParams, State, build_executor, check_spec, local_code, state_machine_call,
+	output::{HttpRequestOutput, OffchainWorkerOutput},
+};
+
+/// Run the offchain worker of the local runtime.
+#[derive(Debug, structopt::StructOpt)]
+pub struct OffchainWorkerCmd {
+	/// The state to run the offchain worker on. It must hold the header of its block, which a
+	/// `live` state always does.
+	#[structopt(subcommand)]
+	pub state: State,
+}
+
+/// Run the offchain worker of the local runtime at the block of the state of `command`, with
+/// mocked offchain externalities.
+///
+/// The offchain storage and the keystore start out empty and live in memory. HTTP requests are
+/// captured and reported, but never answered, and so are the transactions submitted to the pool.
+pub(crate) async fn offchain_worker<B, ExecDispatch>(
+	shared: &SharedParams,
+	command: &OffchainWorkerCmd,
+	config: Configuration,
+) -> sc_cli::Result<()>
+where
+	B: BlockT,
+	B::Header: serde::de::DeserializeOwned,
+	B::Hash: FromStr,
+	<B::Hash as FromStr>::Err: Debug,
+	ExecDispatch: NativeExecutionDispatch + 'static,
+{
+	let executor = build_executor::<ExecDispatch>(shared, &config);
+	let code = local_code(&config)?;
+
+	let mut ext = command.state.builder::<B>()?.inject(&[code]).build().await?;
+	check_spec(&ext.inner_ext, &executor, shared, false)?;
+	let header = ext.header.clone().ok_or("the state snapshot does not contain a header")?;
+	log::info!("running the offchain worker at block #{} ({:?})", header.number(), header.hash());
+
+	let (offchain, offchain_state) = TestOffchainExt::new();
+	let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+	offchain_state.write().timestamp = Timestamp::from_unix_millis(now.as_millis() as u64);
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	ext.register_extension(OffchainDbExt::new(offchain.clone()));
+	ext.register_extension(OffchainWorkerExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+	ext.register_extension(KeystoreExt(Arc::new(KeyStore::new())));
+
+	let result = state_machine_call::<B, ExecDispatch>(
+		&mut ext.inner_ext,
+		&executor,
+		shared,
+		"OffchainWorkerApi_offchain_worker",
+		&header.encode(),
+	);
+
+	// reported also if the offchain worker failed, e.g. while waiting for an http response.
+	let mut output = OffchainWorkerOutput {
+		block_hash: format!("{:?}", header.hash()),
+		..Default::default()
+	};
+	for request in offchain_state.read().requests.values() {
+		log::info!(
+			"captured http request: {} {} ({} body bytes)",
+			request.method,
+			request.uri,
+			request.body.len(),
+		);
+		output.http_requests.push(HttpRequestOutput {
+			method: request.method.clone(),
+			uri: request.uri.clone(),
+			body_len: request.body.len(),
+		});
+	}
+	for transaction in &pool_state.read().transactions {
+		let transaction = format!("0x{}", HexDisplay::from(transaction));
+		log::info!("submitted transaction: {}", transaction);
+		output.transactions.push(transaction);
+	}
+
+	if let Err(e) = result {
+		output.error = Some(e.to_string());
+		shared.output.emit(&output);
+		return Err(e);
+	}
+	shared.output.emit(&output);
+	log::info!("offchain worker executed without errors");
+	Ok(())
+}
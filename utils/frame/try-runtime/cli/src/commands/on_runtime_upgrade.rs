@@ -0,0 +1,186 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The `on-runtime-upgrade` command.
+
+use std::{fmt::Debug, str::FromStr};
+use parity_scale_codec::{Decode, Encode};
+use sc_executor::NativeExecutor;
+use sc_service::{Configuration, NativeExecutionDispatch};
+use sp_runtime::{RuntimeString, traits::Block as BlockT};
+use frame_try_runtime::UpgradeCheckSelect;
+use remote_externalities::{TestExternalities, export::Labels};
+use crate::{
+	SharedParams, State, build_executor, check_spec, commit_changes, local_code,
+	state_machine_call,
+	compare::compare_native_wasm,
+	measure::{breakdown, measured_state_machine_call, report},
+	output::OnRuntimeUpgradeOutput,
+};
+
+/// Execute the `OnRuntimeUpgrade` hooks of all pallets.
+#[derive(Debug, structopt::StructOpt)]
+pub struct OnRuntimeUpgradeCmd {
+	/// The checks to run along with the migration: `none`, `pre-and-post` to run the pre and post
+	/// upgrade checks of the migrations, `try-state` to check the invariants of all pallets after
+	/// them, or `all`.
+	#[structopt(long, default_value = "pre-and-post")]
+	pub checks: UpgradeCheckSelect,
+
+	/// The pallets to run the migrations of, e.g. `Staking,Balances`, along with their checks.
+	///
+	/// All pallets if none are given. Migrations which do not belong to a pallet are selected by
+	/// their type name.
+	#[structopt(long = "pallet", require_delimiter = true)]
+	pub pallets: Vec<String>,
+
+	/// Also run the migration with both the native and the wasm runtime, and fail if their
+	/// results, storage changes or state roots differ.
+	#[structopt(long)]
+	pub compare_native_wasm: bool,
+
+	/// The state to run the migration on.
+	#[structopt(subcommand)]
+	pub state: State,
+}
+
+/// Run the `OnRuntimeUpgrade` hooks of the local runtime on top of the state of `command`, and
+/// report the weight they consumed, along with their storage accesses, proof size and pallet
+/// version bumps, per pallet.
+pub(crate) async fn on_runtime_upgrade<B, ExecDispatch>(
+	shared: &SharedParams,
+	command: &OnRuntimeUpgradeCmd,
+	config: Configuration,
+) -> sc_cli::Result<()>
+where
+	B: BlockT,
+	B::Header: serde::de::DeserializeOwned,
+	B::Hash: FromStr,
+	<B::Hash as FromStr>::Err: Debug,
+	ExecDispatch: NativeExecutionDispatch + 'static,
+{
+	let executor = build_executor::<ExecDispatch>(shared, &config);
+	let code = local_code(&config)?;
+
+	// inject the local code into the scraped state.
+	let mut ext = command.state.builder::<B>()?.inject(&[code]).build().await?.inner_ext;
+	check_spec(&ext, &executor, shared, true)?;
+	let labels = runtime_labels::<B, ExecDispatch>(&mut ext, &executor, shared);
+
+	// strings encode just like the `Vec<u8>` names the runtime expects.
+	let payload = (command.checks, &command.pallets).encode();
+	if command.compare_native_wasm {
+		compare_native_wasm::<B, ExecDispatch>(
+			&ext,
+			&executor,
+			shared,
+			"TryRuntime_on_runtime_upgrade",
+			&payload,
+		)?;
+	}
+
+	let mut output = OnRuntimeUpgradeOutput {
+		state_root_before: format!("{:?}", ext.backend.root()),
+		..Default::default()
+	};
+	let result = measured_state_machine_call::<B, ExecDispatch>(
+		&mut ext,
+		&executor,
+		shared,
+		"TryRuntime_on_runtime_upgrade",
+		&payload,
+	)
+	.map_err(|e| e.to_string())
+	.and_then(|(changes, encoded_result, measurements)| {
+		let decoded = <Result<(Vec<(String, u64)>, u64), RuntimeString> as Decode>::decode(
+			&mut &*encoded_result,
+		);
+		let (weights, total_weight) = decoded
+			.map_err(|e| format!("failed to decode output due to {:?}", e))?
+			.map_err(|e| format!("migration failed: {}", e))?;
+		Ok((changes, weights, total_weight, measurements))
+	});
+	let (changes, weights, total_weight, measurements) = match result {
+		Ok(result) => result,
+		Err(e) => {
+			output.error = Some(e.clone());
+			shared.output.emit(&output);
+			return Err(e.into());
+		},
+	};
+
+	let weight = weights.iter().map(|(_, weight)| weight).sum::<u64>();
+	log::info!(
+		"try-runtime executed without errors. Consumed weight = {}, total weight = {} ({})",
+		weight,
+		total_weight,
+		weight as f64 / total_weight as f64
+	);
+	let pallets = breakdown(&weights, total_weight, &measurements, labels.as_ref());
+	log::info!(
+		"summary of the migrations per pallet:\n{}",
+		report(&pallets, total_weight, &measurements),
+	);
+
+	let state_root_after = commit_changes::<B>(&mut ext, changes)
+		.map_err(|e| format!("failed to commit the changes of the migrations: {}", e))?;
+	shared.output.emit(&OnRuntimeUpgradeOutput {
+		state_root_after: Some(format!("{:?}", state_root_after)),
+		migrations: pallets,
+		total_weight: weight,
+		max_block_weight: total_weight,
+		proof_size: measurements.proof_size,
+		..output
+	});
+
+	Ok(())
+}
+
+/// Labels of the storage of the local runtime, from its metadata, to name pallets after.
+///
+/// `None` if the metadata can not be obtained or is of an unsupported version, in which case
+/// pallets are named after their storage prefix.
+fn runtime_labels<B, ExecDispatch>(
+	ext: &mut TestExternalities,
+	executor: &NativeExecutor<ExecDispatch>,
+	shared: &SharedParams,
+) -> Option<Labels>
+where
+	B: BlockT,
+	ExecDispatch: NativeExecutionDispatch + 'static,
+{
+	let labels = state_machine_call::<B, ExecDispatch>(
+		ext,
+		executor,
+		shared,
+		"Metadata_metadata",
+		&[],
+	)
+	.map_err(|e| format!("{:?}", e))
+	.and_then(|(_, encoded)| {
+		<Vec<u8> as Decode>::decode(&mut &*encoded).map_err(|e| format!("{:?}", e))
+	})
+	.and_then(|metadata| Labels::from_metadata(&metadata).map_err(String::from));
+
+	match labels {
+		Ok(labels) => Some(labels),
+		Err(e) => {
+			log::warn!("failed to read the runtime metadata, pallets are left unnamed: {}", e);
+			None
+		},
+	}
+}
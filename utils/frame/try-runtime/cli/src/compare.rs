@@ -0,0 +1,142 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Comparing the execution of a runtime call by the native runtime with the one by the wasm
+//! runtime, which is what ends up running on chain.
+
+use std::collections::{BTreeMap, BTreeSet};
+use sc_cli::ExecutionStrategy;
+use sc_executor::NativeExecutor;
+use sc_service::NativeExecutionDispatch;
+use sp_core::{Blake2Hasher, hexdisplay::HexDisplay};
+use sp_runtime::traits::{Block as BlockT, NumberFor};
+use sp_state_machine::{OverlayedChanges, backend::BackendRuntimeCode};
+use remote_externalities::TestExternalities;
+use crate::{SharedParams, state_machine_call_on};
+
+/// The storage changes of `changes`, with the keys of child tries prefixed by the storage key of
+/// their trie, and `None` for removed keys.
+fn flatten(changes: &OverlayedChanges) -> BTreeMap<Vec<u8>, Option<Vec<u8>>> {
+	let mut flat = BTreeMap::new();
+	for (key, value) in changes.changes() {
+		flat.insert(key.clone(), value.value().cloned());
+	}
+	for (child_changes, child_info) in changes.children() {
+		let prefixed = child_info.prefixed_storage_key().into_inner();
+		for (key, value) in child_changes {
+			flat.insert([&prefixed[..], key].concat(), value.value().cloned());
+		}
+	}
+	flat
+}
+
+/// Hex encoded `value`, if any.
+fn display(value: Option<&Option<Vec<u8>>>) -> String {
+	match value {
+		Some(Some(value)) => format!("0x{}", HexDisplay::from(value)),
+		Some(None) => "removed".into(),
+		None => "unchanged".into(),
+	}
+}
+
+/// Call the runtime api `method` with `data` on top of `ext` twice, once with the native runtime
+/// and once with the wasm runtime, and compare their results, the storage changes they made and
+/// the resulting state roots.
+///
+/// `ext` is left untouched, and neither call gets its extensions. The native runtime is only used
+/// if it is compatible with the wasm runtime in the state, otherwise both calls run the latter.
+///
+/// Returns an error if the two executions diverge. If both fail, they are considered to agree.
+pub(crate) fn compare_native_wasm<B, ExecDispatch>(
+	ext: &TestExternalities,
+	executor: &NativeExecutor<ExecDispatch>,
+	shared: &SharedParams,
+	method: &'static str,
+	data: &[u8],
+) -> sc_cli::Result<()>
+where
+	B: BlockT,
+	ExecDispatch: NativeExecutionDispatch + 'static,
+{
+	let runtime_code = BackendRuntimeCode::new(&ext.backend).runtime_code()?;
+	let call = |strategy| {
+		state_machine_call_on::<B, _, ExecDispatch>(
+			&ext.backend,
+			&runtime_code,
+			Default::default(),
+			executor,
+			shared,
+			strategy,
+			method,
+			data,
+		)
+	};
+	let root = |changes: &OverlayedChanges| {
+		changes.storage_root::<Blake2Hasher, NumberFor<B>, _>(&ext.backend, &mut Default::default())
+	};
+
+	let (native, wasm) = match (call(ExecutionStrategy::Native), call(ExecutionStrategy::Wasm)) {
+		(Ok(native), Ok(wasm)) => (native, wasm),
+		(Err(native), Err(wasm)) => {
+			log::info!("'{}' failed both natively ({}) and in wasm ({})", method, native, wasm);
+			return Ok(());
+		},
+		(Ok(_), Err(e)) => return Err(format!("'{}' failed in wasm only: {}", method, e).into()),
+		(Err(e), Ok(_)) => return Err(format!("'{}' failed natively only: {}", method, e).into()),
+	};
+
+	let mut divergent = 0usize;
+	if native.1 != wasm.1 {
+		divergent += 1;
+		log::warn!(
+			"the result of '{}' differs: native 0x{}, wasm 0x{}",
+			method,
+			HexDisplay::from(&native.1),
+			HexDisplay::from(&wasm.1),
+		);
+	}
+
+	let (native_changes, wasm_changes) = (flatten(&native.0), flatten(&wasm.0));
+	let keys = native_changes.keys().chain(wasm_changes.keys()).collect::<BTreeSet<_>>();
+	for key in keys {
+		let (native_value, wasm_value) = (native_changes.get(key), wasm_changes.get(key));
+		if native_value != wasm_value {
+			divergent += 1;
+			log::warn!(
+				"the change of key 0x{} differs: native {}, wasm {}",
+				HexDisplay::from(key),
+				display(native_value),
+				display(wasm_value),
+			);
+		}
+	}
+
+	let (native_root, wasm_root) = (root(&native.0), root(&wasm.0));
+	if divergent > 0 || native_root != wasm_root {
+		return Err(format!(
+			"native and wasm execution of '{}' diverge: {} differences, state root {:?} natively \
+			and {:?} in wasm",
+			method,
+			divergent,
+			native_root,
+			wasm_root,
+		).into());
+	}
+
+	log::info!("native and wasm execution of '{}' agree, state root {:?}", method, native_root);
+	Ok(())
+}
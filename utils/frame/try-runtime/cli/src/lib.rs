@@ -15,23 +15,54 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-//! `Structopt`-ready struct for `try-runtime`.
+//! `Structopt`-ready structs for `try-runtime`.
+//!
+//! Each [`Command`] tries out the locally built runtime, taken from the chain spec, on top of
+//! some [`State`], either scraped from a live chain or loaded from a snapshot.
 
-use parity_scale_codec::Decode;
-use std::{fmt::Debug, path::PathBuf, str::FromStr};
-use sc_service::Configuration;
+use std::{convert::TryFrom, fmt::Debug, path::PathBuf, str::FromStr};
+use sc_service::{Configuration, NativeExecutionDispatch};
 use sc_cli::{CliConfiguration, ExecutionStrategy, WasmExecutionMethod};
 use sc_executor::NativeExecutor;
-use sc_service::NativeExecutionDispatch;
-use sp_state_machine::StateMachine;
-use sp_runtime::traits::{Block as BlockT, NumberFor};
-use sp_core::storage::{StorageData, StorageKey, well_known_keys};
+use parity_scale_codec::{Compact, Decode};
+use sp_state_machine::{
+	Backend, BasicExternalities, OverlayedChanges, StateMachine, backend::BackendRuntimeCode,
+};
+use sp_externalities::Extensions;
+use sp_runtime::traits::{Block as BlockT, DigestItemFor, NumberFor, UniqueSaturatedInto};
+use sp_core::{
+	Blake2Hasher, H256,
+	storage::{StorageData, StorageKey, well_known_keys},
+	traits::{ReadRuntimeVersion, RuntimeCode},
+};
+use sp_version::RuntimeVersion;
+use remote_externalities::{
+	Builder, Mode, OfflineConfig, OnlineConfig, SnapshotConfig, TestExternalities, Transport,
+	storage_value_key,
+};
+
+mod commands;
+mod compare;
+mod measure;
+mod output;
+
+pub use output::OutputFormat;
 
 /// Various commands to try out the new runtime, over configurable states.
-///
-/// For now this only assumes running the `on_runtime_upgrade` hooks.
 #[derive(Debug, structopt::StructOpt)]
 pub struct TryRuntimeCmd {
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared: SharedParams,
+
+	/// The command to run.
+	#[structopt(subcommand)]
+	pub command: Command,
+}
+
+/// Parameters shared by all commands.
+#[derive(Debug, structopt::StructOpt)]
+pub struct SharedParams {
 	/// The shared parameters
 	#[allow(missing_docs)]
 	#[structopt(flatten)]
@@ -47,48 +78,182 @@ pub struct TryRuntimeCmd {
 	)]
 	pub execution: ExecutionStrategy,
 
-	/// Method for executing Wasm runtime code.
+	/// Method for executing Wasm runtime code, either `interpreted` or `compiled`.
+	///
+	/// The latter is only available if built with the `wasmtime` feature of `sc-cli`.
 	#[structopt(
 		long = "wasm-execution",
 		value_name = "METHOD",
-		possible_values = &WasmExecutionMethod::variants(),
-		case_insensitive = true,
-		default_value = "Interpreted"
+		default_value = "interpreted",
+		parse(try_from_str = parse_wasm_method),
 	)]
 	pub wasm_method: WasmExecutionMethod,
 
-	/// The state to use to run the migration.
-	#[structopt(subcommand)]
-	pub state: State,
+	/// The number of 64KB pages to allocate for Wasm execution, e.g. to run migrations over a
+	/// state too large for the default.
+	///
+	/// Takes precedence over the heap pages stored in the state. Defaults to those, or to the
+	/// default of the node if there are none.
+	#[structopt(long = "heap-pages", value_name = "COUNT")]
+	pub heap_pages: Option<u64>,
+
+	/// The format to report the results in, either `text` or `json`.
+	///
+	/// With `json`, the results of the command are printed to stdout as JSON, one object per
+	/// line, e.g. for CI pipelines to check, while the logs keep going to stderr.
+	#[structopt(long = "output", value_name = "FORMAT", default_value = "text")]
+	pub output: OutputFormat,
+
+	/// Only warn, instead of failing, if the spec name of the local runtime differs from the one
+	/// of the runtime in the state.
+	#[structopt(long)]
+	pub disable_spec_name_check: bool,
+
+	/// Only warn, instead of failing, if the spec version of the local runtime is not bumped
+	/// relative to the one of the runtime in the state, e.g. when trying out a locally modified
+	/// runtime before its release.
+	#[structopt(long)]
+	pub disable_spec_version_check: bool,
+}
+
+/// The commands of `try-runtime`.
+#[derive(Debug, structopt::StructOpt)]
+pub enum Command {
+	/// Execute the `OnRuntimeUpgrade` hooks of all pallets on top of some state, and report the
+	/// weight they consumed.
+	OnRuntimeUpgrade(commands::OnRuntimeUpgradeCmd),
+
+	/// Execute a block on top of the state of its parent, and report the weight it consumed.
+	ExecuteBlock(commands::ExecuteBlockCmd),
+
+	/// Follow the finalized blocks of a live chain, executing each of them on top of locally
+	/// maintained state and comparing the resulting state roots.
+	FollowChain(commands::FollowChainCmd),
+
+	/// Run the offchain worker at the block of some state, with mocked offchain externalities.
+	OffchainWorker(commands::OffchainWorkerCmd),
+
+	/// Scrape the state of a live chain into a snapshot file, along with the header and runtime
+	/// metadata of its block, to be reused as `snap` state by the other commands.
+	CreateSnapshot(commands::CreateSnapshotCmd),
+
+	/// Author and execute empty blocks on top of some state, to advance time dependent logic,
+	/// such as sessions and eras, without a network.
+	FastForward(commands::FastForwardCmd),
+}
+
+/// The chain specific parts of authoring blocks, as needed by the `fast-forward` command.
+pub trait BlockBuildingInfoProvider<B: BlockT> {
+	/// The digest items to put into the header of a block authored at `timestamp`, in
+	/// milliseconds, e.g. the pre-runtime digest of the consensus engine.
+	fn pre_digest(&self, timestamp: u64) -> Vec<DigestItemFor<B>>;
 }
 
-/// The state to use for a migration dry-run.
+/// No digest items, for chains whose runtime does not require any.
+impl<B: BlockT> BlockBuildingInfoProvider<B> for () {
+	fn pre_digest(&self, _timestamp: u64) -> Vec<DigestItemFor<B>> {
+		Vec::new()
+	}
+}
+
+/// The state to run a command on, either scraped from a live chain or loaded from a snapshot.
 #[derive(Debug, structopt::StructOpt)]
 pub enum State {
-	/// Use a state snapshot as state to run the migration.
+	/// Use a state snapshot, as written by `create-snapshot` or by a `live` state.
 	Snap {
+		/// The state snapshot file to read.
 		snapshot_path: PathBuf,
 	},
 
-	/// Use a live chain to run the migration.
-	Live {
-		/// An optional state snapshot file to WRITE to. Not written if set to `None`.
-		#[structopt(short, long)]
-		snapshot_path: Option<PathBuf>,
+	/// Scrape the state of a live chain.
+	Live(LiveState),
+}
 
-		/// The block hash at which to connect.
-		/// Will be latest finalized head if not provided.
-		#[structopt(short, long, multiple = false, parse(try_from_str = parse_hash))]
-		block_at: Option<String>,
+/// The parameters of scraping the state of a live chain, shared by all commands.
+#[derive(Debug, structopt::StructOpt)]
+pub struct LiveState {
+	/// The uri of the node to connect to.
+	#[structopt(
+		long,
+		default_value = "ws://localhost:9944",
+		parse(try_from_str = parse_url),
+	)]
+	pub uri: String,
 
-		/// The modules to scrape. If empty, entire chain state will be scraped.
-		#[structopt(short, long, require_delimiter = true)]
-		modules: Option<Vec<String>>,
+	/// Further uris of the same chain, failed over to in order when the connection to `uri`
+	/// fails.
+	#[structopt(long, parse(try_from_str = parse_url))]
+	pub fallback_uri: Vec<String>,
 
-		/// The url to connect to.
-		#[structopt(default_value = "ws://localhost:9944", parse(try_from_str = parse_url))]
-		url: String,
-	},
+	/// The block hash at which to scrape. Will be latest finalized head if not provided.
+	#[structopt(short, long, multiple = false, parse(try_from_str = parse_hash))]
+	pub at: Option<String>,
+
+	/// The pallets to scrape. If empty, entire chain state will be scraped.
+	#[structopt(short, long, require_delimiter = true)]
+	pub pallets: Vec<String>,
+
+	/// An optional state snapshot file to WRITE to. Not written if not provided.
+	#[structopt(short, long)]
+	pub snapshot_path: Option<PathBuf>,
+
+	/// Also scrape the child tries of the state, e.g. those of crowdloan contributions or of
+	/// contracts, such that migrations of child storage can be tried out.
+	///
+	/// Along with `--pallets`, the child tries of all pallets are scraped.
+	#[structopt(long)]
+	pub child_tree: bool,
+}
+
+impl LiveState {
+	/// The configuration to scrape this state with, at `at` if given, rather than at the
+	/// configured block.
+	pub(crate) fn config<B: BlockT>(&self, at: Option<B::Hash>) -> sc_cli::Result<OnlineConfig<B>>
+	where
+		B::Hash: FromStr,
+		<B::Hash as FromStr>::Err: Debug,
+	{
+		let uris = std::iter::once(&self.uri).chain(&self.fallback_uri).cloned().collect();
+		Ok(OnlineConfig {
+			transport: Transport::try_from(uris)?,
+			state_snapshot: self.snapshot_path.as_ref().map(SnapshotConfig::new),
+			modules: self.pallets.clone(),
+			at: match at {
+				Some(at) => Some(at),
+				None => self.at.as_deref().map(parse_block_hash::<B>).transpose()?,
+			},
+			scrape_child_tries: self.child_tree,
+			..Default::default()
+		})
+	}
+}
+
+impl State {
+	/// A builder of externalities of this state.
+	pub(crate) fn builder<B: BlockT>(&self) -> sc_cli::Result<Builder<B>>
+	where
+		B::Hash: FromStr,
+		<B::Hash as FromStr>::Err: Debug,
+	{
+		self.builder_at(None)
+	}
+
+	/// Like [`State::builder`], but scraping a live chain at `at` if given, rather than at the
+	/// configured block.
+	pub(crate) fn builder_at<B: BlockT>(&self, at: Option<B::Hash>) -> sc_cli::Result<Builder<B>>
+	where
+		B::Hash: FromStr,
+		<B::Hash as FromStr>::Err: Debug,
+	{
+		Ok(match self {
+			State::Snap { snapshot_path } => {
+				Builder::<B>::new().mode(Mode::Offline(OfflineConfig {
+					state_snapshot: SnapshotConfig::new(snapshot_path),
+				}))
+			},
+			State::Live(live) => Builder::<B>::new().mode(Mode::Online(live.config::<B>(at)?)),
+		})
+	}
 }
 
 fn parse_hash(block_number: &str) -> Result<String, String> {
@@ -108,6 +273,69 @@ fn parse_hash(block_number: &str) -> Result<String, String> {
 	}
 }
 
+/// A block, by number or by hash.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockRef {
+	/// The block with this number on the canonical chain.
+	Number(u64),
+	/// The block with this hash, hex encoded without `0x` prefix.
+	Hash(String),
+}
+
+/// Parse a block number, which may contain `_` separators such as `9_000_000`, or else a block
+/// hash.
+fn parse_block_ref(block: &str) -> Result<BlockRef, String> {
+	if !block.starts_with("0x") && block.chars().all(|c| c.is_ascii_digit() || c == '_') {
+		block
+			.replace('_', "")
+			.parse()
+			.map(BlockRef::Number)
+			.map_err(|e| format!("Expected block number: {}", e))
+	} else {
+		parse_hash(block).map(BlockRef::Hash)
+	}
+}
+
+/// The hash of `block`, resolved by the node at `uri` if given by number.
+pub(crate) async fn resolve_block<B: BlockT>(
+	uri: &str,
+	block: &BlockRef,
+) -> sc_cli::Result<B::Hash>
+where
+	B::Hash: FromStr,
+	<B::Hash as FromStr>::Err: Debug,
+{
+	match block {
+		BlockRef::Number(number) => {
+			let config = OnlineConfig { transport: uri.to_string().into(), ..Default::default() };
+			let hash = Builder::<B>::new()
+				.mode(Mode::Online(config))
+				.block_hash((*number).unique_saturated_into())
+				.await?;
+			log::info!("block #{} is {:?}", number, hash);
+			Ok(hash)
+		},
+		BlockRef::Hash(hash) => parse_block_hash::<B>(hash),
+	}
+}
+
+/// Parse a block hash, as validated by [`parse_hash`].
+pub(crate) fn parse_block_hash<B: BlockT>(hash: &str) -> sc_cli::Result<B::Hash>
+where
+	B::Hash: FromStr,
+	<B::Hash as FromStr>::Err: Debug,
+{
+	hash.parse().map_err(|e| format!("Could not parse hash: {:?}", e).into())
+}
+
+fn parse_wasm_method(s: &str) -> Result<WasmExecutionMethod, String> {
+	if s.eq_ignore_ascii_case("interpreted") {
+		Ok(WasmExecutionMethod::Interpreted)
+	} else {
+		s.parse()
+	}
+}
+
 fn parse_url(s: &str) -> Result<String, &'static str> {
 	if s.starts_with("ws://") || s.starts_with("wss://") {
 		// could use Url crate as well, but lets keep it simple for now.
@@ -117,103 +345,249 @@ fn parse_url(s: &str) -> Result<String, &'static str> {
 	}
 }
 
+/// The code of the locally built runtime, i.e. the one of the genesis of the chain spec, along
+/// with its key.
+pub(crate) fn local_code(config: &Configuration) -> sc_cli::Result<(StorageKey, StorageData)> {
+	let genesis_storage = config.chain_spec.build_storage()?;
+	let code = StorageData(
+		genesis_storage
+			.top
+			.get(well_known_keys::CODE)
+			.expect("code key must exist in genesis storage; qed")
+			.to_vec(),
+	);
+	Ok((StorageKey(well_known_keys::CODE.to_vec()), code))
+}
+
+/// Fetch the block at `at` from the node at `uri`, or its latest finalized block if `at` is not
+/// given.
+pub(crate) async fn fetch_block<B: BlockT>(uri: String, at: Option<B::Hash>) -> sc_cli::Result<B>
+where
+	B::Header: serde::de::DeserializeOwned,
+{
+	let signed = Builder::<B>::new()
+		.mode(Mode::Online(OnlineConfig { transport: uri.into(), at, ..Default::default() }))
+		.fetch_block()
+		.await?;
+	Ok(signed.block)
+}
+
+/// The executor of the locally built runtime.
+pub(crate) fn build_executor<ExecDispatch: NativeExecutionDispatch + 'static>(
+	shared: &SharedParams,
+	config: &Configuration,
+) -> NativeExecutor<ExecDispatch> {
+	// don't really care about this one -- use the default value.
+	let max_runtime_instances = config.max_runtime_instances;
+	let heap_pages = shared.heap_pages.or(config.default_heap_pages);
+	log::info!(
+		"executing wasm {} with {} heap pages",
+		shared.wasm_method,
+		match shared.heap_pages {
+			Some(heap_pages) => heap_pages.to_string(),
+			None => "the default".into(),
+		},
+	);
+	NativeExecutor::<ExecDispatch>::new(
+		shared.wasm_method.into(),
+		heap_pages,
+		max_runtime_instances,
+	)
+}
+
+/// Check the spec name and version of the code in `ext`, i.e. the injected local runtime, against
+/// the last runtime upgrade noted in its state.
+///
+/// The spec names must match. The local spec version must be greater than the noted one if
+/// `upgrade`, as the migrations would not run on chain otherwise, and not lower in any case.
+/// Either check only warns if disabled by `shared`, and both are skipped if the state does not
+/// note any runtime upgrade.
+pub(crate) fn check_spec<ExecDispatch: NativeExecutionDispatch + 'static>(
+	ext: &TestExternalities,
+	executor: &NativeExecutor<ExecDispatch>,
+	shared: &SharedParams,
+	upgrade: bool,
+) -> sc_cli::Result<()> {
+	let key = storage_value_key("System", "LastRuntimeUpgrade");
+	let (Compact(noted_version), noted_name) = match ext.backend.storage(&key.0)? {
+		Some(noted) => <(Compact<u32>, String) as Decode>::decode(&mut &*noted)
+			.map_err(|e| format!("failed to decode the last runtime upgrade due to {:?}", e))?,
+		None => {
+			log::warn!("no runtime upgrade noted in the state, skipping the spec checks");
+			return Ok(());
+		},
+	};
+
+	let code = ext.backend.storage(well_known_keys::CODE)?.ok_or("no code in the state")?;
+	let encoded = executor
+		.read_runtime_version(&code, &mut BasicExternalities::default())
+		.map_err(|e| format!("failed to read the version of the local runtime: {}", e))?;
+	let local = <RuntimeVersion as Decode>::decode(&mut &*encoded)
+		.map_err(|e| format!("failed to decode the version of the local runtime due to {:?}", e))?;
+
+	let relax = |disabled: bool, message: String| -> sc_cli::Result<()> {
+		if disabled {
+			log::warn!("{}", message);
+			Ok(())
+		} else {
+			Err(message.into())
+		}
+	};
+	if local.spec_name.to_string() != noted_name {
+		relax(
+			shared.disable_spec_name_check,
+			format!(
+				"spec name {} of the local runtime differs from {} of the state",
+				local.spec_name,
+				noted_name,
+			),
+		)?;
+	}
+	let bumped = if upgrade {
+		local.spec_version > noted_version
+	} else {
+		local.spec_version >= noted_version
+	};
+	if !bumped {
+		relax(
+			shared.disable_spec_version_check,
+			format!(
+				"spec version {} of the local runtime is {} the version {} of the state",
+				local.spec_version,
+				if upgrade { "not greater than" } else { "lower than" },
+				noted_version,
+			),
+		)?;
+	}
+	Ok(())
+}
+
+/// Call the runtime api `method` with the encoded `data` on top of `ext`, taking over its
+/// extensions.
+///
+/// The heap pages of `shared`, if any, take precedence over those stored in `ext`.
+///
+/// Returns the changes made to the state, and the encoded result.
+pub(crate) fn state_machine_call<B: BlockT, ExecDispatch: NativeExecutionDispatch + 'static>(
+	ext: &mut TestExternalities,
+	executor: &NativeExecutor<ExecDispatch>,
+	shared: &SharedParams,
+	method: &'static str,
+	data: &[u8],
+) -> sc_cli::Result<(OverlayedChanges, Vec<u8>)> {
+	let runtime_code = BackendRuntimeCode::new(&ext.backend).runtime_code()?;
+	state_machine_call_on::<B, _, ExecDispatch>(
+		&ext.backend,
+		&runtime_code,
+		std::mem::take(&mut ext.extensions),
+		executor,
+		shared,
+		shared.execution,
+		method,
+		data,
+	)
+}
+
+/// Commit `changes` to the backend of `ext`, and return the resulting state root.
+pub(crate) fn commit_changes<B: BlockT>(
+	ext: &mut TestExternalities,
+	mut changes: OverlayedChanges,
+) -> Result<H256, String> {
+	let storage_changes = changes.drain_storage_changes::<_, _, NumberFor<B>>(
+		&ext.backend,
+		None,
+		Default::default(),
+		&mut Default::default(),
+	)?;
+	let root = storage_changes.transaction_storage_root;
+	ext.backend.apply_transaction(root, storage_changes.transaction);
+	Ok(root)
+}
+
+/// Like [`state_machine_call`], but on top of `backend`, e.g. one that wraps the backend of some
+/// externalities to record the accesses to it, and with the given execution `strategy`.
+pub(crate) fn state_machine_call_on<B, S, ExecDispatch>(
+	backend: &S,
+	runtime_code: &RuntimeCode,
+	extensions: Extensions,
+	executor: &NativeExecutor<ExecDispatch>,
+	shared: &SharedParams,
+	strategy: ExecutionStrategy,
+	method: &'static str,
+	data: &[u8],
+) -> sc_cli::Result<(OverlayedChanges, Vec<u8>)>
+where
+	B: BlockT,
+	S: Backend<Blake2Hasher>,
+	ExecDispatch: NativeExecutionDispatch + 'static,
+{
+	let runtime_code = RuntimeCode {
+		heap_pages: shared.heap_pages.or(runtime_code.heap_pages),
+		..runtime_code.clone()
+	};
+	let mut changes = Default::default();
+	let encoded_result = StateMachine::<_, _, NumberFor<B>, _>::new(
+		backend,
+		None,
+		&mut changes,
+		executor,
+		method,
+		data,
+		extensions,
+		&runtime_code,
+		sp_core::testing::TaskExecutor::new(),
+	)
+	.execute(strategy.into())
+	.map_err(|e| format!("failed to execute '{}' due to {:?}", method, e))?;
+
+	Ok((changes, encoded_result))
+}
+
 impl TryRuntimeCmd {
-	pub async fn run<B, ExecDispatch>(&self, config: Configuration) -> sc_cli::Result<()>
+	/// Run the command, authoring blocks with the help of `block_building` if need be.
+	pub async fn run<B, ExecDispatch, BBIP>(
+		&self,
+		config: Configuration,
+		block_building: BBIP,
+	) -> sc_cli::Result<()>
 	where
 		B: BlockT,
+		B::Header: serde::de::DeserializeOwned,
 		B::Hash: FromStr,
 		<B::Hash as FromStr>::Err: Debug,
 		NumberFor<B>: FromStr,
 		<NumberFor<B> as FromStr>::Err: Debug,
 		ExecDispatch: NativeExecutionDispatch + 'static,
+		BBIP: BlockBuildingInfoProvider<B>,
 	{
-		let spec = config.chain_spec;
-		let genesis_storage = spec.build_storage()?;
-
-		let code = StorageData(
-			genesis_storage
-				.top
-				.get(well_known_keys::CODE)
-				.expect("code key must exist in genesis storage; qed")
-				.to_vec(),
-		);
-		let code_key = StorageKey(well_known_keys::CODE.to_vec());
-
-		let wasm_method = self.wasm_method;
-		let execution = self.execution;
-
-		let mut changes = Default::default();
-		// don't really care about these -- use the default values.
-		let max_runtime_instances = config.max_runtime_instances;
-		let heap_pages = config.default_heap_pages;
-		let executor = NativeExecutor::<ExecDispatch>::new(
-			wasm_method.into(),
-			heap_pages,
-			max_runtime_instances,
-		);
-
-		let ext = {
-			use remote_externalities::{Builder, Mode, SnapshotConfig, OfflineConfig, OnlineConfig};
-			let builder = match &self.state {
-				State::Snap { snapshot_path } => {
-					Builder::<B>::new().mode(Mode::Offline(OfflineConfig {
-						state_snapshot: SnapshotConfig::new(snapshot_path),
-					}))
-				},
-				State::Live {
-					url,
-					snapshot_path,
-					block_at,
-					modules
-				} => Builder::<B>::new().mode(Mode::Online(OnlineConfig {
-					transport: url.to_owned().into(),
-					state_snapshot: snapshot_path.as_ref().map(SnapshotConfig::new),
-					modules: modules.to_owned().unwrap_or_default(),
-					at: block_at.as_ref()
-						.map(|b| b.parse().map_err(|e| format!("Could not parse hash: {:?}", e))).transpose()?,
-					..Default::default()
-				})),
-			};
-
-			// inject the code into this ext.
-			builder.inject(&[(code_key, code)]).build().await?
-		};
-
-		let encoded_result = StateMachine::<_, _, NumberFor<B>, _>::new(
-			&ext.backend,
-			None,
-			&mut changes,
-			&executor,
-			"TryRuntime_on_runtime_upgrade",
-			&[],
-			ext.extensions,
-			&sp_state_machine::backend::BackendRuntimeCode::new(&ext.backend)
-				.runtime_code()?,
-			sp_core::testing::TaskExecutor::new(),
-		)
-		.execute(execution.into())
-		.map_err(|e| format!("failed to execute 'TryRuntime_on_runtime_upgrade' due to {:?}", e))?;
-
-		let (weight, total_weight) = <(u64, u64) as Decode>::decode(&mut &*encoded_result)
-			.map_err(|e| format!("failed to decode output due to {:?}", e))?;
-		log::info!(
-			"try-runtime executed without errors. Consumed weight = {}, total weight = {} ({})",
-			weight,
-			total_weight,
-			weight as f64 / total_weight as f64
-		);
-
-		Ok(())
+		match &self.command {
+			Command::OnRuntimeUpgrade(cmd) =>
+				commands::on_runtime_upgrade::<B, ExecDispatch>(&self.shared, cmd, config).await,
+			Command::ExecuteBlock(cmd) =>
+				commands::execute_block::<B, ExecDispatch>(&self.shared, cmd, config).await,
+			Command::FollowChain(cmd) =>
+				commands::follow_chain::<B, ExecDispatch>(&self.shared, cmd, config).await,
+			Command::OffchainWorker(cmd) =>
+				commands::offchain_worker::<B, ExecDispatch>(&self.shared, cmd, config).await,
+			Command::CreateSnapshot(cmd) => commands::create_snapshot::<B>(cmd).await,
+			Command::FastForward(cmd) =>
+				commands::fast_forward::<B, ExecDispatch, _>(
+					&self.shared,
+					cmd,
+					config,
+					block_building,
+				).await,
+		}
 	}
 }
 
 impl CliConfiguration for TryRuntimeCmd {
 	fn shared_params(&self) -> &sc_cli::SharedParams {
-		&self.shared_params
+		&self.shared.shared_params
 	}
 
 	fn chain_id(&self, _is_dev: bool) -> sc_cli::Result<String> {
-		Ok(match self.shared_params.chain {
+		Ok(match self.shared.shared_params.chain {
 			Some(ref chain) => chain.clone(),
 			None => "dev".into(),
 		})
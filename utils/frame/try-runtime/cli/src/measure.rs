@@ -0,0 +1,417 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Measuring the storage accesses of a runtime call, and the size of the proof it takes to
+//! replay it, as a parachain validator would.
+
+use std::{collections::{BTreeMap, BTreeSet}, fmt::Write, sync::Mutex};
+use parity_scale_codec::{Decode, Encode};
+use sc_executor::NativeExecutor;
+use sc_service::NativeExecutionDispatch;
+use sp_core::{
+	Blake2Hasher,
+	hashing::twox_128,
+	hexdisplay::HexDisplay,
+	storage::{ChildInfo, well_known_keys::CHILD_STORAGE_KEY_PREFIX},
+};
+use sp_runtime::traits::Block as BlockT;
+use sp_state_machine::{
+	Backend, OverlayedChanges, ProvingBackend, StateMachineStats, UsageInfo,
+	backend::BackendRuntimeCode,
+};
+use remote_externalities::{TestExternalities, export::Labels};
+use crate::{SharedParams, output::PalletMeasurements, state_machine_call_on};
+
+/// Length of a pallet prefix, i.e. of `twox_128(pallet)`.
+const PALLET_PREFIX_LEN: usize = 16;
+
+/// The postfix of the key of the version of a pallet, which follows its prefix.
+const PALLET_VERSION_POSTFIX: &[u8] = b":__PALLET_VERSION__:";
+
+/// Whether `key` is the key of the version of some pallet.
+fn is_pallet_version(key: &[u8]) -> bool {
+	key.len() == 2 * PALLET_PREFIX_LEN &&
+		key[PALLET_PREFIX_LEN..] == twox_128(PALLET_VERSION_POSTFIX)
+}
+
+/// The encoded pallet version `value` as `major.minor.patch`.
+fn display_version(value: Option<&Vec<u8>>) -> String {
+	match value.map(|value| <(u16, u8, u8) as Decode>::decode(&mut &value[..])) {
+		Some(Ok((major, minor, patch))) => format!("{}.{}.{}", major, minor, patch),
+		Some(Err(_)) => "invalid".into(),
+		None => "none".into(),
+	}
+}
+
+/// A backend recording the keys whose values are read from it, along with the size of those.
+///
+/// Reads served by the overlay never reach the backend, just like they never reach the database
+/// of a node, and thus are not recorded.
+struct RecordingBackend<'a, S> {
+	inner: &'a S,
+	reads: Mutex<BTreeMap<Vec<u8>, usize>>,
+}
+
+impl<'a, S> RecordingBackend<'a, S> {
+	fn new(inner: &'a S) -> Self {
+		Self { inner, reads: Default::default() }
+	}
+
+	fn record(&self, key: Vec<u8>, value: &Option<Vec<u8>>) {
+		let len = value.as_ref().map_or(0, |value| value.len());
+		self.reads.lock().expect("never poisoned; qed.").insert(key, len);
+	}
+}
+
+impl<S> std::fmt::Debug for RecordingBackend<'_, S> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "RecordingBackend")
+	}
+}
+
+impl<'a, S: Backend<Blake2Hasher>> Backend<Blake2Hasher> for RecordingBackend<'a, S> {
+	type Error = S::Error;
+	type Transaction = S::Transaction;
+	type TrieBackendStorage = S::TrieBackendStorage;
+
+	fn storage(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
+		let value = self.inner.storage(key)?;
+		self.record(key.to_vec(), &value);
+		Ok(value)
+	}
+
+	fn child_storage(
+		&self,
+		child_info: &ChildInfo,
+		key: &[u8],
+	) -> Result<Option<Vec<u8>>, Self::Error> {
+		let value = self.inner.child_storage(child_info, key)?;
+		let prefixed = child_info.prefixed_storage_key().into_inner();
+		self.record([&prefixed[..], key].concat(), &value);
+		Ok(value)
+	}
+
+	fn next_storage_key(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
+		self.inner.next_storage_key(key)
+	}
+
+	fn next_child_storage_key(
+		&self,
+		child_info: &ChildInfo,
+		key: &[u8],
+	) -> Result<Option<Vec<u8>>, Self::Error> {
+		self.inner.next_child_storage_key(child_info, key)
+	}
+
+	fn apply_to_child_keys_while<F: FnMut(&[u8]) -> bool>(&self, child_info: &ChildInfo, f: F) {
+		self.inner.apply_to_child_keys_while(child_info, f)
+	}
+
+	fn for_keys_with_prefix<F: FnMut(&[u8])>(&self, prefix: &[u8], f: F) {
+		self.inner.for_keys_with_prefix(prefix, f)
+	}
+
+	fn for_key_values_with_prefix<F: FnMut(&[u8], &[u8])>(&self, prefix: &[u8], f: F) {
+		self.inner.for_key_values_with_prefix(prefix, f)
+	}
+
+	fn for_child_keys_with_prefix<F: FnMut(&[u8])>(
+		&self,
+		child_info: &ChildInfo,
+		prefix: &[u8],
+		f: F,
+	) {
+		self.inner.for_child_keys_with_prefix(child_info, prefix, f)
+	}
+
+	fn storage_root<'b>(
+		&self,
+		delta: impl Iterator<Item = (&'b [u8], Option<&'b [u8]>)>,
+	) -> (sp_core::H256, Self::Transaction) {
+		self.inner.storage_root(delta)
+	}
+
+	fn child_storage_root<'b>(
+		&self,
+		child_info: &ChildInfo,
+		delta: impl Iterator<Item = (&'b [u8], Option<&'b [u8]>)>,
+	) -> (sp_core::H256, bool, Self::Transaction) {
+		self.inner.child_storage_root(child_info, delta)
+	}
+
+	fn pairs(&self) -> Vec<(Vec<u8>, Vec<u8>)> {
+		self.inner.pairs()
+	}
+
+	fn keys(&self, prefix: &[u8]) -> Vec<Vec<u8>> {
+		self.inner.keys(prefix)
+	}
+
+	fn child_keys(&self, child_info: &ChildInfo, prefix: &[u8]) -> Vec<Vec<u8>> {
+		self.inner.child_keys(child_info, prefix)
+	}
+
+	fn register_overlay_stats(&self, stats: &StateMachineStats) {
+		self.inner.register_overlay_stats(stats)
+	}
+
+	fn usage_info(&self) -> UsageInfo {
+		self.inner.usage_info()
+	}
+}
+
+/// The storage accesses of a runtime call.
+#[derive(Debug, Default)]
+pub(crate) struct Measurements {
+	/// The keys read from the state, along with the size of their values.
+	pub reads: BTreeMap<Vec<u8>, usize>,
+	/// The keys written to, along with the size of their new values, or `None` if removed.
+	pub writes: BTreeMap<Vec<u8>, Option<usize>>,
+	/// The pallet versions written, by key, along with their values before and after.
+	pub versions: BTreeMap<Vec<u8>, (Option<Vec<u8>>, Option<Vec<u8>>)>,
+	/// The size of the proof of all the state read, in bytes.
+	pub proof_size: usize,
+}
+
+/// Call the runtime api `method` with the encoded `data` on top of `ext` like
+/// [`crate::state_machine_call`], and measure the storage accesses of the call.
+///
+/// Returns the changes made to the state, the encoded result and the measurements.
+pub(crate) fn measured_state_machine_call<B, ExecDispatch>(
+	ext: &mut TestExternalities,
+	executor: &NativeExecutor<ExecDispatch>,
+	shared: &SharedParams,
+	method: &'static str,
+	data: &[u8],
+) -> sc_cli::Result<(OverlayedChanges, Vec<u8>, Measurements)>
+where
+	B: BlockT,
+	ExecDispatch: NativeExecutionDispatch + 'static,
+{
+	let proving = ProvingBackend::new(&ext.backend);
+	let recording = RecordingBackend::new(&proving);
+	let runtime_code = BackendRuntimeCode::new(&ext.backend).runtime_code()?;
+	let (changes, encoded_result) = state_machine_call_on::<B, _, ExecDispatch>(
+		&recording,
+		&runtime_code,
+		std::mem::take(&mut ext.extensions),
+		executor,
+		shared,
+		shared.execution,
+		method,
+		data,
+	)?;
+
+	let mut writes = BTreeMap::new();
+	for (key, value) in changes.changes() {
+		writes.insert(key.clone(), value.value().map(|value| value.len()));
+	}
+	for (child_changes, child_info) in changes.children() {
+		let prefixed = child_info.prefixed_storage_key().into_inner();
+		for (key, value) in child_changes {
+			writes.insert([&prefixed[..], key].concat(), value.value().map(|value| value.len()));
+		}
+	}
+
+	let mut versions = BTreeMap::new();
+	for (key, value) in changes.changes().filter(|(key, _)| is_pallet_version(key)) {
+		versions.insert(key.clone(), (ext.backend.storage(key)?, value.value().cloned()));
+	}
+
+	let measurements = Measurements {
+		reads: recording.reads.into_inner().expect("never poisoned; qed."),
+		writes,
+		versions,
+		proof_size: proving.extract_proof().encoded_size(),
+	};
+	Ok((changes, encoded_result, measurements))
+}
+
+/// The pallet that `key` belongs to, by name if found in `labels`, and by its hex encoded prefix
+/// otherwise.
+///
+/// Well known keys, such as `:code`, are their own pallet, and all child tries are one.
+fn pallet_of(key: &[u8], labels: Option<&Labels>) -> String {
+	if key.starts_with(CHILD_STORAGE_KEY_PREFIX) {
+		return String::from_utf8_lossy(CHILD_STORAGE_KEY_PREFIX).into_owned();
+	}
+	if let Some(label) = labels.and_then(|labels| labels.label(key)) {
+		return label.split("::").next().unwrap_or_default().to_string();
+	}
+	format!("0x{}", HexDisplay::from(&&key[..key.len().min(PALLET_PREFIX_LEN)]))
+}
+
+/// Storage accesses of a single pallet.
+#[derive(Debug, Default, Clone)]
+struct Accesses {
+	reads: usize,
+	read_bytes: usize,
+	writes: usize,
+	deletes: usize,
+	items: BTreeSet<String>,
+	version_bump: Option<String>,
+}
+
+/// The storage item that `key` belongs to, if found in `labels`.
+fn item_of(key: &[u8], labels: Option<&Labels>) -> Option<String> {
+	labels
+		.and_then(|labels| labels.label(key))
+		.and_then(|label| label.split("::").nth(1).map(String::from))
+}
+
+/// The weight reported by each of `weights`, given as `(pallet, weight)` and relative to
+/// `max_block_weight`, along with the storage accesses of `measurements` and the bumps of pallet
+/// versions per pallet.
+///
+/// Storage accesses are attributed to pallets by the prefix of their keys, hence those made by
+/// a migration to the storage of another pallet count towards the latter. Pallets are named after
+/// `labels` if given, and listed in the order their migrations ran, followed by those that were
+/// only accessed.
+pub(crate) fn breakdown(
+	weights: &[(String, u64)],
+	max_block_weight: u64,
+	measurements: &Measurements,
+	labels: Option<&Labels>,
+) -> Vec<PalletMeasurements> {
+	let mut accesses = BTreeMap::<String, Accesses>::new();
+	for (key, len) in &measurements.reads {
+		let pallet = accesses.entry(pallet_of(key, labels)).or_default();
+		pallet.reads += 1;
+		pallet.read_bytes += len;
+		pallet.items.extend(item_of(key, labels));
+	}
+	for (key, len) in &measurements.writes {
+		let pallet = accesses.entry(pallet_of(key, labels)).or_default();
+		pallet.writes += 1;
+		pallet.deletes += len.is_none() as usize;
+		pallet.items.extend(item_of(key, labels));
+	}
+	for (key, (before, after)) in &measurements.versions {
+		if before != after {
+			accesses.entry(pallet_of(key, labels)).or_default().version_bump = Some(format!(
+				"{} -> {}",
+				display_version(before.as_ref()),
+				display_version(after.as_ref()),
+			));
+		}
+	}
+
+	let share = |weight: u64| weight as f64 * 100.0 / max_block_weight.max(1) as f64;
+	let entry = |pallet: String, weight: Option<u64>, accesses: Accesses| PalletMeasurements {
+		pallet,
+		weight,
+		reads: accesses.reads,
+		read_bytes: accesses.read_bytes,
+		writes: accesses.writes,
+		deletes: accesses.deletes,
+		items: accesses.items.into_iter().collect(),
+		block_share: weight.map(share),
+		version_bump: accesses.version_bump,
+	};
+	let mut pallets = weights
+		.iter()
+		.map(|(pallet, weight)| {
+			entry(pallet.clone(), Some(*weight), accesses.remove(pallet).unwrap_or_default())
+		})
+		.collect::<Vec<_>>();
+	pallets.extend(accesses.into_iter().map(|(pallet, accesses)| entry(pallet, None, accesses)));
+	pallets
+}
+
+/// A summary of `pallets`, as given by [`breakdown`], followed by the totals of `measurements`:
+/// a table of their weight and storage accesses, the storage items they touched, and the
+/// migrations that wrote to storage without bumping the version of their pallet.
+pub(crate) fn report(
+	pallets: &[PalletMeasurements],
+	max_block_weight: u64,
+	measurements: &Measurements,
+) -> String {
+	let mut table = String::new();
+	let _ = writeln!(
+		table,
+		"{:<32} {:>16} {:>8} {:>8} {:>12} {:>8} {:>8} {:>16}",
+		"pallet",
+		"weight",
+		"of block",
+		"reads",
+		"read bytes",
+		"writes",
+		"deletes",
+		"version",
+	);
+	let mut row = |pallet: &PalletMeasurements| {
+		let weight = match (pallet.weight, pallet.block_share) {
+			(Some(weight), Some(share)) => format!("{:>16} {:>7.2}%", weight, share),
+			_ => format!("{:>16} {:>8}", "-", "-"),
+		};
+		let _ = writeln!(
+			table,
+			"{:<32} {} {:>8} {:>12} {:>8} {:>8} {:>16}",
+			pallet.pallet,
+			weight,
+			pallet.reads,
+			pallet.read_bytes,
+			pallet.writes,
+			pallet.deletes,
+			pallet.version_bump.as_deref().unwrap_or("-"),
+		);
+	};
+
+	for pallet in pallets {
+		row(pallet);
+	}
+	let total_weight = pallets.iter().filter_map(|pallet| pallet.weight).sum::<u64>();
+	row(&PalletMeasurements {
+		pallet: "total".into(),
+		weight: Some(total_weight),
+		reads: measurements.reads.len(),
+		read_bytes: measurements.reads.values().sum(),
+		writes: measurements.writes.len(),
+		deletes: measurements.writes.values().filter(|len| len.is_none()).count(),
+		items: Vec::new(),
+		block_share: Some(total_weight as f64 * 100.0 / max_block_weight.max(1) as f64),
+		version_bump: None,
+	});
+	let _ = writeln!(table, "proof size: {} bytes", measurements.proof_size);
+
+	let _ = writeln!(table, "storage items touched:");
+	for pallet in pallets.iter().filter(|pallet| !pallet.items.is_empty()) {
+		let _ = writeln!(table, "  {}: {}", pallet.pallet, pallet.items.join(", "));
+	}
+
+	// the pallet macros write the version on every upgrade, even if it is left unchanged.
+	let unbumped = pallets
+		.iter()
+		.filter(|pallet| pallet.weight.is_some() && pallet.version_bump.is_none())
+		.filter(|pallet| {
+			let version_written = measurements
+				.versions
+				.keys()
+				.any(|key| key.starts_with(&twox_128(pallet.pallet.as_bytes())));
+			pallet.writes > version_written as usize
+		})
+		.map(|pallet| pallet.pallet.as_str())
+		.collect::<Vec<_>>();
+	if !unbumped.is_empty() {
+		let _ = writeln!(
+			table,
+			"migrations writing to storage without bumping their pallet version: {}",
+			unbumped.join(", "),
+		);
+	}
+	table
+}
@@ -0,0 +1,156 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Machine readable results of the commands.
+//!
+//! With `--output json`, every command prints its results to stdout as JSON, one object per
+//! line, while logs keep going to stderr. A failing command still prints its results, with the
+//! `error` field set, before exiting with an error.
+
+use std::str::FromStr;
+use serde::Serialize;
+
+/// The format to report the results of the commands in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+	/// Logs only.
+	Text,
+	/// JSON on stdout, in addition to the logs.
+	Json,
+}
+
+impl FromStr for OutputFormat {
+	type Err = &'static str;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.to_lowercase().as_str() {
+			"text" => Ok(Self::Text),
+			"json" => Ok(Self::Json),
+			_ => Err("expected `text` or `json`"),
+		}
+	}
+}
+
+impl OutputFormat {
+	/// Print `result` as a line of JSON, if JSON is the format.
+	pub(crate) fn emit<T: Serialize>(&self, result: &T) {
+		if *self == Self::Json {
+			match serde_json::to_string(result) {
+				Ok(json) => println!("{}", json),
+				Err(e) => log::error!("failed to serialize the results: {:?}", e),
+			}
+		}
+	}
+}
+
+/// The weight, storage accesses and version bump of a single pallet.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct PalletMeasurements {
+	/// The name of the pallet, or its hex encoded storage prefix.
+	pub pallet: String,
+	/// The weight reported by the migration of the pallet, if it has one.
+	pub weight: Option<u64>,
+	/// The number of keys of the pallet read.
+	pub reads: usize,
+	/// The total size of the values read.
+	pub read_bytes: usize,
+	/// The number of keys of the pallet written, including those removed.
+	pub writes: usize,
+	/// The number of keys of the pallet removed.
+	pub deletes: usize,
+	/// The storage items of the pallet read or written, if known.
+	pub items: Vec<String>,
+	/// The weight relative to the maximum weight of a block, in percent.
+	pub block_share: Option<f64>,
+	/// The change of the pallet version, e.g. `1.0.0 -> 2.0.0`, if it was bumped.
+	pub version_bump: Option<String>,
+}
+
+/// The results of `on-runtime-upgrade`.
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct OnRuntimeUpgradeOutput {
+	/// The state root before the migrations.
+	pub state_root_before: String,
+	/// The state root after the migrations, if they succeeded.
+	pub state_root_after: Option<String>,
+	/// The migrations run, in order, followed by the pallets only accessed by them.
+	pub migrations: Vec<PalletMeasurements>,
+	/// The total weight consumed by the migrations.
+	pub total_weight: u64,
+	/// The maximum weight of a block.
+	pub max_block_weight: u64,
+	/// The size of the proof of all state read by the migrations, in bytes.
+	pub proof_size: usize,
+	/// Why the migrations or their checks failed, if they did.
+	pub error: Option<String>,
+}
+
+/// The results of executing a block, by `execute-block` and `follow-chain`.
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct ExecuteBlockOutput {
+	/// The hash of the block.
+	pub block_hash: String,
+	/// The number of the block.
+	pub block_number: u64,
+	/// The weight the block consumed, if it was executed successfully.
+	pub weight: Option<u64>,
+	/// The state root before the block.
+	pub state_root_before: String,
+	/// The state root computed by the local runtime, if the block was executed successfully.
+	pub state_root_after: Option<String>,
+	/// The state root of the header of the block.
+	pub expected_state_root: String,
+	/// Why the block or its checks failed, if they did.
+	pub error: Option<String>,
+}
+
+/// The results of authoring a block with `fast-forward`.
+#[derive(Debug, Serialize)]
+pub(crate) struct AuthoredBlockOutput {
+	/// The hash of the block.
+	pub block_hash: String,
+	/// The number of the block.
+	pub block_number: u64,
+	/// The timestamp of the block, in milliseconds.
+	pub timestamp: u64,
+	/// The state root after the block.
+	pub state_root: String,
+}
+
+/// An HTTP request captured by `offchain-worker`.
+#[derive(Debug, Serialize)]
+pub(crate) struct HttpRequestOutput {
+	/// The method of the request.
+	pub method: String,
+	/// The uri of the request.
+	pub uri: String,
+	/// The size of the body of the request.
+	pub body_len: usize,
+}
+
+/// The results of `offchain-worker`.
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct OffchainWorkerOutput {
+	/// The hash of the block the offchain worker ran at.
+	pub block_hash: String,
+	/// The HTTP requests made.
+	pub http_requests: Vec<HttpRequestOutput>,
+	/// The hex encoded transactions submitted to the pool.
+	pub transactions: Vec<String>,
+	/// Why the offchain worker failed, if it did.
+	pub error: Option<String>,
+}
@@ -28,7 +28,7 @@ pub use prometheus::{
 	}
 };
 use prometheus::{Encoder, TextEncoder, core::Collector};
-use std::net::SocketAddr;
+use std::{net::SocketAddr, sync::Arc};
 
 #[cfg(not(target_os = "unknown"))]
 mod networking;
@@ -37,9 +37,17 @@ mod sourced;
 pub use sourced::{SourcedCounter, SourcedGauge, MetricSource, SourcedMetric};
 
 #[cfg(target_os = "unknown")]
-pub use unknown_os::init_prometheus;
+pub use unknown_os::{init_prometheus, init_prometheus_with_health};
 #[cfg(not(target_os = "unknown"))]
-pub use known_os::init_prometheus;
+pub use known_os::{init_prometheus, init_prometheus_with_health};
+
+/// Answers the liveness and readiness probes served next to the metrics.
+pub trait HealthProbe: Send + Sync + 'static {
+	/// Whether the process is alive. A failing liveness probe means it should be restarted.
+	fn is_live(&self) -> bool;
+	/// Whether the node is ready to serve requests.
+	fn is_ready(&self) -> bool;
+}
 
 pub fn register<T: Clone + Collector + 'static>(metric: T, registry: &Registry) -> Result<T, PrometheusError> {
 	registry.register(Box::new(metric.clone()))?;
@@ -56,6 +64,14 @@ mod unknown_os {
 	pub async fn init_prometheus(_: SocketAddr, _registry: Registry) -> Result<(), Error> {
 		Ok(())
 	}
+
+	pub async fn init_prometheus_with_health(
+		_: SocketAddr,
+		_registry: Registry,
+		_probe: Option<Arc<dyn HealthProbe>>,
+	) -> Result<(), Error> {
+		Ok(())
+	}
 }
 
 #[cfg(not(target_os = "unknown"))]
@@ -87,7 +103,29 @@ mod known_os {
 		}
 	}
 
-	async fn request_metrics(req: Request<Body>, registry: Registry) -> Result<Response<Body>, Error> {
+	fn probe_response(ok: bool) -> Result<Response<Body>, Error> {
+		let (status, body) = if ok {
+			(StatusCode::OK, "OK")
+		} else {
+			(StatusCode::SERVICE_UNAVAILABLE, "Unavailable")
+		};
+
+		Response::builder().status(status)
+			.body(Body::from(body))
+			.map_err(Error::Http)
+	}
+
+	async fn request_metrics(
+		req: Request<Body>,
+		registry: Registry,
+		probe: Option<Arc<dyn HealthProbe>>,
+	) -> Result<Response<Body>, Error> {
+		match (req.uri().path(), probe) {
+			("/health/liveness", Some(probe)) => return probe_response(probe.is_live()),
+			("/health/readiness", Some(probe)) => return probe_response(probe.is_ready()),
+			_ => {},
+		}
+
 		if req.uri().path() == "/metrics" {
 			let metric_families = registry.gather();
 			let mut buffer = vec![];
@@ -122,6 +160,16 @@ mod known_os {
 	/// Initializes the metrics context, and starts an HTTP server
 	/// to serve metrics.
 	pub async fn init_prometheus(prometheus_addr: SocketAddr, registry: Registry) -> Result<(), Error>{
+		init_prometheus_with_health(prometheus_addr, registry, None).await
+	}
+
+	/// Like [`init_prometheus`], but additionally serves `/health/liveness` and
+	/// `/health/readiness`, answered by the given probe.
+	pub async fn init_prometheus_with_health(
+		prometheus_addr: SocketAddr,
+		registry: Registry,
+		probe: Option<Arc<dyn HealthProbe>>,
+	) -> Result<(), Error> {
 		use networking::Incoming;
 		let listener = async_std::net::TcpListener::bind(&prometheus_addr)
 			.await
@@ -131,10 +179,11 @@ mod known_os {
 
 		let service = make_service_fn(move |_| {
 			let registry = registry.clone();
+			let probe = probe.clone();
 
 			async move {
 				Ok::<_, hyper::Error>(service_fn(move |req: Request<Body>| {
-					request_metrics(req, registry.clone())
+					request_metrics(req, registry.clone(), probe.clone())
 				}))
 			}
 		});